@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use knukki::*;
+
+#[cfg(not(feature = "golem_rendering"))]
+fn bench_mouse_move_storms(c: &mut Criterion) {
+    for num_children in &[10, 100, 1000] {
+        let mut storm = MouseMoveStorm::new(*num_children, 1000);
+        c.bench_function(&format!("mouse move storm ({} children)", num_children), |b| {
+            b.iter(|| storm.run())
+        });
+    }
+}
+
+#[cfg(feature = "golem_rendering")]
+fn bench_mouse_move_storms(_c: &mut Criterion) {
+    // The mouse move storms need a dummy renderer, which is not available with golem_rendering
+}
+
+fn bench_glyph_packing(c: &mut Criterion) {
+    for num_glyphs in &[100, 1000, 5000] {
+        let packing = GlyphPacking::new(*num_glyphs, 8, 40, 2048, 2048);
+        c.bench_function(&format!("glyph packing ({} glyphs)", num_glyphs), |b| {
+            b.iter(|| packing.run())
+        });
+    }
+}
+
+criterion_group!(benches, bench_mouse_move_storms, bench_glyph_packing);
+criterion_main!(benches);
@@ -0,0 +1,237 @@
+use crate::*;
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+enum AnimationStep<T: Tweenable> {
+    Tween { to: T, duration: Duration, easing: Easing },
+    Wait(Duration),
+    Call(Box<dyn FnOnce()>),
+}
+
+enum ActiveStep<T: Tweenable> {
+    Tween(Tween<T>),
+    Wait { remaining: Duration },
+}
+
+/// Animates a value through a sequence of tweens, waits, and callbacks. Components should
+/// typically store an `Animator` for each animated property, subscribe to tick events, and call
+/// `advance` from their `on_tick` method.
+///
+/// Each tween starts at the value where the previous step ended, so sequences can be built by
+/// simply chaining `then` calls. Time that is left over when a step finishes is passed on to the
+/// next step, so the timing doesn't depend on the frame rate.
+///
+/// ## Example
+/// ```
+/// use knukki::*;
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use std::time::Duration;
+///
+/// let finished = Rc::new(Cell::new(false));
+/// let finished_flag = Rc::clone(&finished);
+///
+/// let mut size = Animator::new(0.5);
+/// size.animate_to(1.0, Duration::from_millis(100), Easing::QuadraticOut)
+///     .wait(Duration::from_millis(50))
+///     .then(0.5, Duration::from_millis(100), Easing::QuadraticIn)
+///     .then_call(move || finished_flag.set(true));
+///
+/// // This would typically be done in on_tick, followed by buddy.request_render() when it
+/// // returns true
+/// assert!(size.advance(Duration::from_millis(100)));
+/// assert_eq!(1.0, size.get_value());
+///
+/// size.advance(Duration::from_millis(200));
+/// assert_eq!(0.5, size.get_value());
+/// assert!(finished.get());
+/// assert!(!size.is_animating());
+/// ```
+pub struct Animator<T: Tweenable> {
+    value: T,
+    active_step: Option<ActiveStep<T>>,
+    queued_steps: VecDeque<AnimationStep<T>>,
+}
+
+impl<T: Tweenable> Animator<T> {
+    /// Constructs an `Animator` with the given initial *value* that is not animating
+    pub fn new(value: T) -> Self {
+        Self { value, active_step: None, queued_steps: VecDeque::new() }
+    }
+
+    /// Gets the current value of the animated property
+    pub fn get_value(&self) -> T {
+        self.value
+    }
+
+    /// Checks whether this animator has any unfinished steps
+    pub fn is_animating(&self) -> bool {
+        self.active_step.is_some() || !self.queued_steps.is_empty()
+    }
+
+    /// Immediately changes the value, and cancels all (queued) steps. The callbacks of the
+    /// cancelled steps will *not* be called.
+    pub fn jump_to(&mut self, value: T) {
+        self.stop();
+        self.value = value;
+    }
+
+    /// Cancels all (queued) steps, and keeps the current value. The callbacks of the cancelled
+    /// steps will *not* be called.
+    pub fn stop(&mut self) {
+        self.active_step = None;
+        self.queued_steps.clear();
+    }
+
+    /// Cancels all (queued) steps, and starts a tween from the current value to *to*. More steps
+    /// can be chained after it.
+    pub fn animate_to(&mut self, to: T, duration: Duration, easing: Easing) -> &mut Self {
+        self.stop();
+        self.then(to, duration, easing)
+    }
+
+    /// Queues a tween to *to*, which will start at the value where the previous step ended
+    pub fn then(&mut self, to: T, duration: Duration, easing: Easing) -> &mut Self {
+        self.queued_steps.push_back(AnimationStep::Tween { to, duration, easing });
+        self
+    }
+
+    /// Queues a step that keeps the value unchanged for the given *duration*
+    pub fn wait(&mut self, duration: Duration) -> &mut Self {
+        self.queued_steps.push_back(AnimationStep::Wait(duration));
+        self
+    }
+
+    /// Queues a callback that will be called (during `advance`) when all previous steps have
+    /// finished. This can be used for completion callbacks.
+    pub fn then_call(&mut self, callback: impl FnOnce() + 'static) -> &mut Self {
+        self.queued_steps.push_back(AnimationStep::Call(Box::new(callback)));
+        self
+    }
+
+    /// Advances the animation by *delta_time* (typically the delta time of a `TickEvent`), and
+    /// returns true if a tween was active during this time (in which case the value has probably
+    /// changed, and the component should request a render).
+    pub fn advance(&mut self, delta_time: Duration) -> bool {
+        let mut changed = false;
+        let mut remaining_time = delta_time;
+
+        loop {
+            if self.active_step.is_none() {
+                match self.queued_steps.pop_front() {
+                    Some(AnimationStep::Tween { to, duration, easing }) => {
+                        let tween = Tween::new(self.value, to, duration, easing);
+                        self.active_step = Some(ActiveStep::Tween(tween));
+                    }
+                    Some(AnimationStep::Wait(duration)) => {
+                        self.active_step = Some(ActiveStep::Wait { remaining: duration });
+                    }
+                    Some(AnimationStep::Call(callback)) => {
+                        callback();
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            let finished = match self.active_step.as_mut().unwrap() {
+                ActiveStep::Tween(tween) => {
+                    remaining_time = tween.advance(remaining_time);
+                    self.value = tween.get_value();
+                    changed = true;
+                    tween.is_finished()
+                }
+                ActiveStep::Wait { remaining } => {
+                    if remaining_time >= *remaining {
+                        remaining_time -= *remaining;
+                        true
+                    } else {
+                        *remaining -= remaining_time;
+                        remaining_time = Duration::from_secs(0);
+                        false
+                    }
+                }
+            };
+
+            if finished {
+                self.active_step = None;
+            } else {
+                break;
+            }
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sequence() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let first_log = Rc::clone(&log);
+        let second_log = Rc::clone(&log);
+
+        let millis = Duration::from_millis;
+        let mut animator = Animator::new(Color::rgb(0, 0, 0));
+        animator
+            .animate_to(Color::rgb(100, 0, 0), millis(100), Easing::Linear)
+            .then_call(move || first_log.borrow_mut().push("red"))
+            .wait(millis(50))
+            .then(Color::rgb(100, 200, 0), millis(100), Easing::Linear)
+            .then_call(move || second_log.borrow_mut().push("green"));
+        assert!(animator.is_animating());
+
+        assert!(animator.advance(millis(50)));
+        assert_eq!(Color::rgb(50, 0, 0), animator.get_value());
+        assert!(log.borrow().is_empty());
+
+        // The left-over time should be passed to the wait step
+        assert!(animator.advance(millis(70)));
+        assert_eq!(Color::rgb(100, 0, 0), animator.get_value());
+        assert_eq!(vec!["red"], *log.borrow());
+
+        // Only waiting shouldn't change the value
+        assert!(!animator.advance(millis(20)));
+
+        assert!(animator.advance(millis(35)));
+        assert_eq!(Color::rgb(100, 50, 0), animator.get_value());
+
+        assert!(animator.advance(millis(500)));
+        assert_eq!(Color::rgb(100, 200, 0), animator.get_value());
+        assert_eq!(vec!["red", "green"], *log.borrow());
+        assert!(!animator.is_animating());
+        assert!(!animator.advance(millis(10)));
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let called = Rc::new(RefCell::new(false));
+        let called_flag = Rc::clone(&called);
+
+        let mut animator = Animator::new(0.0);
+        animator
+            .animate_to(1.0, Duration::from_secs(1), Easing::Linear)
+            .then_call(move || *called_flag.borrow_mut() = true);
+        animator.advance(Duration::from_millis(500));
+
+        // The new tween should start at the current value, and the callback should be cancelled
+        animator.animate_to(0.0, Duration::from_secs(1), Easing::Linear);
+        animator.advance(Duration::from_millis(500));
+        assert!((animator.get_value() - 0.25).abs() < 0.001);
+
+        animator.jump_to(2.0);
+        assert!(!animator.is_animating());
+        assert_eq!(2.0, animator.get_value());
+        animator.advance(Duration::from_secs(5));
+        assert!(!*called.borrow());
+    }
+}
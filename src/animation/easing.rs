@@ -0,0 +1,89 @@
+use std::f32::consts::PI;
+
+/// An easing curve, which maps the linear progress of an animation (between 0.0 and 1.0) to the
+/// eased progress. All curves map 0.0 to 0.0 and 1.0 to 1.0.
+///
+/// The *In* variants start slowly and end quickly, the *Out* variants start quickly and end
+/// slowly, and the *InOut* variants start and end slowly.
+#[derive(Copy, Clone, Debug)]
+pub enum Easing {
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    /// A custom easing curve. The function should map 0.0 to 0.0 and 1.0 to 1.0.
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    /// Computes the eased progress for the given linear *progress*, which will be clamped between
+    /// 0.0 and 1.0
+    pub fn apply(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::QuadraticIn => t * t,
+            Self::QuadraticOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Self::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - 4.0 * (1.0 - t).powi(3)
+                }
+            }
+            Self::SineIn => 1.0 - (0.5 * PI * t).cos(),
+            Self::SineOut => (0.5 * PI * t).sin(),
+            Self::SineInOut => 0.5 - 0.5 * (PI * t).cos(),
+            Self::Custom(function) => function(t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_easing_bounds_and_symmetry() {
+        let curves = [
+            Easing::Linear,
+            Easing::QuadraticIn,
+            Easing::QuadraticOut,
+            Easing::QuadraticInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::SineIn,
+            Easing::SineOut,
+            Easing::SineInOut,
+            Easing::Custom(|t| t.sqrt()),
+        ];
+        for curve in &curves {
+            assert!(curve.apply(0.0).abs() < 0.001);
+            assert!((curve.apply(1.0) - 1.0).abs() < 0.001);
+            assert!((curve.apply(2.0) - 1.0).abs() < 0.001);
+        }
+
+        assert!(Easing::QuadraticIn.apply(0.5) < 0.5);
+        assert!(Easing::CubicOut.apply(0.5) > 0.5);
+        assert!((Easing::SineInOut.apply(0.5) - 0.5).abs() < 0.001);
+        let cubic = Easing::CubicInOut;
+        assert!((cubic.apply(0.25) + cubic.apply(0.75) - 1.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,7 @@
+mod animator;
+mod easing;
+mod tween;
+
+pub use animator::*;
+pub use easing::*;
+pub use tween::*;
@@ -0,0 +1,148 @@
+use crate::*;
+
+use std::time::Duration;
+
+/// A value that can be animated with a `Tween` or `Animator`
+pub trait Tweenable: Copy {
+    /// Computes the value between `self` (when *progress* is 0.0) and *target* (when *progress*
+    /// is 1.0). Note that the *progress* can be slightly outside this range for custom easing
+    /// curves.
+    fn interpolate(self, target: Self, progress: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        self + (target - self) * progress
+    }
+}
+
+impl Tweenable for Point {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        Point::new(
+            self.get_x().interpolate(target.get_x(), progress),
+            self.get_y().interpolate(target.get_y(), progress),
+        )
+    }
+}
+
+impl Tweenable for Color {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        let component = |from: u8, to: u8| {
+            (from as f32).interpolate(to as f32, progress).round().clamp(0.0, 255.0) as u8
+        };
+        Color::rgba(
+            component(self.get_red_int(), target.get_red_int()),
+            component(self.get_green_int(), target.get_green_int()),
+            component(self.get_blue_int(), target.get_blue_int()),
+            component(self.get_alpha_int(), target.get_alpha_int()),
+        )
+    }
+}
+
+impl Tweenable for ComponentDomain {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        ComponentDomain::between(
+            self.get_min_x().interpolate(target.get_min_x(), progress),
+            self.get_min_y().interpolate(target.get_min_y(), progress),
+            self.get_max_x().interpolate(target.get_max_x(), progress),
+            self.get_max_y().interpolate(target.get_max_y(), progress),
+        )
+    }
+}
+
+/// A single transition of a value from *from* to *to* during *duration*, using an `Easing` curve.
+/// The tween needs to be advanced explicitly (typically from `on_tick`). Use `Animator` to chain
+/// multiple tweens.
+#[derive(Copy, Clone, Debug)]
+pub struct Tween<T: Tweenable> {
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+    elapsed: Duration,
+}
+
+impl<T: Tweenable> Tween<T> {
+    pub fn new(from: T, to: T, duration: Duration, easing: Easing) -> Self {
+        Self { from, to, duration, easing, elapsed: Duration::from_secs(0) }
+    }
+
+    pub fn get_from(&self) -> T {
+        self.from
+    }
+
+    pub fn get_to(&self) -> T {
+        self.to
+    }
+
+    /// Advances this tween by *delta_time*, and returns the part of *delta_time* that was left
+    /// after this tween finished (which is 0 if it hasn't finished yet)
+    pub fn advance(&mut self, delta_time: Duration) -> Duration {
+        let remaining_time = self.duration - self.elapsed;
+        if delta_time >= remaining_time {
+            self.elapsed = self.duration;
+            delta_time - remaining_time
+        } else {
+            self.elapsed += delta_time;
+            Duration::from_secs(0)
+        }
+    }
+
+    /// Gets the linear progress of this tween, which is between 0.0 and 1.0
+    pub fn get_progress(&self) -> f32 {
+        if self.duration == Duration::from_secs(0) {
+            1.0
+        } else {
+            self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Gets the current value of this tween, after applying its easing curve to the progress
+    pub fn get_value(&self) -> T {
+        if self.is_finished() {
+            self.to
+        } else {
+            self.from.interpolate(self.to, self.easing.apply(self.get_progress()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_tween() {
+        let mut tween = Tween::new(
+            Point::new(0.0, 1.0), Point::new(1.0, 0.0), Duration::from_millis(100), Easing::Linear
+        );
+        assert!(Point::new(0.0, 1.0).nearly_equal(tween.get_value()));
+
+        assert_eq!(Duration::from_secs(0), tween.advance(Duration::from_millis(25)));
+        assert!(Point::new(0.25, 0.75).nearly_equal(tween.get_value()));
+        assert!(!tween.is_finished());
+
+        assert_eq!(Duration::from_millis(5), tween.advance(Duration::from_millis(80)));
+        assert!(tween.is_finished());
+        assert_eq!(Point::new(1.0, 0.0), tween.get_value());
+    }
+
+    #[test]
+    fn test_interpolate_color() {
+        let from = Color::rgba(0, 100, 200, 255);
+        let to = Color::rgba(200, 100, 0, 55);
+        assert_eq!(Color::rgba(50, 100, 150, 205), from.interpolate(to, 0.25));
+        assert_eq!(from, from.interpolate(to, 0.0));
+        assert_eq!(to, from.interpolate(to, 1.0));
+
+        // Custom easing curves can overshoot, but the color components should stay valid
+        assert_eq!(Color::rgba(255, 100, 0, 0), from.interpolate(to, 2.0));
+    }
+}
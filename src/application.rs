@@ -3,6 +3,7 @@ use crate::*;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::Duration;
 
 /// The `Application` is the 'highest' object that is cross-platform. It
 /// encapsulates all the components and their buddies.
@@ -33,15 +34,105 @@ pub struct Application {
     root_buddy: RootComponentBuddy,
 
     mouse_store: Rc<RefCell<MouseStore>>,
-    fonts_to_register: HashMap<String, Box<dyn Font>>
+    gamepad_store: Rc<RefCell<GamepadStore>>,
+    fonts_to_register: HashMap<String, Box<dyn Font>>,
+    input_settings: InputSettings,
+    ui_scale: f32,
+    render_scale: f32,
+    // This is set when the UI scale or the background changed, since everything needs to be
+    // redrawn
+    needs_full_render: bool,
+    // When the GPU context of the renderer was replaced, everything needs to be redrawn as well
+    last_context_generation: u64,
+    fixed_ticks: Option<FixedTickAccumulator>,
+    // The window frame area where the primary mouse button was pressed most recently, if it
+    // wasn't Content and the wrapper didn't take it yet
+    window_drag_request: Option<WindowFrameArea>,
+
+    background_color: Color,
+    background: Option<(Box<dyn Component>, RootComponentBuddy)>,
+    theme: Rc<Theme>,
+    frame_timing: FrameTiming,
+    // The highest memory pressure of the low memory events since the previous render, which
+    // determines how much memory the renderer should release
+    pending_memory_pressure: Option<MemoryPressure>,
+
+    // The sum of the delta times of all tick events, which is used to detect double clicks,
+    // long presses, and hovers
+    current_time: Duration,
+    previous_clicks: Vec<TimedMouseAction>,
+    long_press_candidates: Vec<TimedMouseAction>,
+    hover_candidates: Vec<RestingMouse>,
+    // The mouses that are inside the drawn region of the root component (or its hysteresis
+    // margin), which is only tracked when the hover hysteresis is enabled
+    hovering_mouses: Vec<Mouse>,
+    gesture_recognizer: GestureRecognizer,
+
+    invalidation_debug: Option<InvalidationDebug>,
+    invalidation_flashes: Vec<InvalidationFlash>,
+
+    queued_events: Vec<InputEvent>,
+    menu_change_policy: MenuChangePolicy,
+}
+
+/// The maximum number of consecutive menu changes that will be processed after a single event.
+/// This prevents an endless loop when root components keep changing the menu in their
+/// `on_attach` method.
+const MAX_MENU_CHANGES_PER_EVENT: u32 = 16;
+
+/// The settings of the invalidation debug mode of the `Application` (see
+/// `Application.set_invalidation_debug`).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InvalidationDebug {
+    /// The color of the overlay that is drawn over each repainted region
+    pub color: Color,
+    /// The opacity of the overlay, which should be between 0.0 and 1.0
+    pub opacity: f32,
+    /// The number of frames (calls to `Application.render`) during which the overlay remains
+    /// visible
+    pub num_frames: u32,
+}
+
+impl Default for InvalidationDebug {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(255, 0, 200),
+            opacity: 0.4,
+            num_frames: 20,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct InvalidationFlash {
+    region: RenderRegion,
+    remaining_frames: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TimedMouseAction {
+    mouse: Mouse,
+    button: MouseButton,
+    point: Point,
+    time: Duration,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct RestingMouse {
+    mouse: Mouse,
+    point: Point,
+    // The time at which the mouse stopped moving
+    time: Duration,
 }
 
 impl Application {
     pub fn new(mut initial_root_component: Box<dyn Component>) -> Self {
         let mouse_store = Rc::new(RefCell::new(MouseStore::new()));
+        let gamepad_store = Rc::new(RefCell::new(GamepadStore::new()));
 
         let mut root_buddy = RootComponentBuddy::new();
         root_buddy.set_mouse_store(Rc::clone(&mouse_store));
+        root_buddy.set_gamepad_store(Rc::clone(&gamepad_store));
 
         initial_root_component.on_attach(&mut root_buddy);
         // No need to call request_render, because the did_request_render field
@@ -51,18 +142,375 @@ impl Application {
             root_buddy,
 
             mouse_store,
-            fonts_to_register: HashMap::new()
+            gamepad_store,
+            fonts_to_register: HashMap::new(),
+            input_settings: InputSettings::default(),
+            ui_scale: 1.0,
+            render_scale: 1.0,
+            needs_full_render: false,
+            last_context_generation: 0,
+            fixed_ticks: None,
+            window_drag_request: None,
+
+            background_color: Color::rgb(0, 0, 0),
+            background: None,
+            theme: Rc::new(Theme::default()),
+            frame_timing: FrameTiming::default(),
+            pending_memory_pressure: None,
+
+            current_time: Duration::from_secs(0),
+            previous_clicks: Vec::new(),
+            long_press_candidates: Vec::new(),
+            hover_candidates: Vec::new(),
+            hovering_mouses: Vec::new(),
+            gesture_recognizer: GestureRecognizer::new(),
+
+            invalidation_debug: None,
+            invalidation_flashes: Vec::new(),
+
+            queued_events: Vec::new(),
+            menu_change_policy: MenuChangePolicy::LastWins,
         };
         result.work_after_events();
         result
     }
 
+    /// Gets the `InputSettings` that the *wrapper* and this `Application` use to synthesize
+    /// events like `MouseClickEvent`s
+    pub fn get_input_settings(&self) -> InputSettings {
+        self.input_settings
+    }
+
+    pub fn set_input_settings(&mut self, new_settings: InputSettings) {
+        self.input_settings = new_settings;
+    }
+
+    pub fn get_background_color(&self) -> Color {
+        self.background_color
+    }
+
+    /// Sets the color that the `Application` uses to clear the screen before it redraws
+    /// everything (the default is black). The application will be redrawn entirely during the
+    /// next `render`.
+    pub fn set_background_color(&mut self, new_color: Color) {
+        if new_color != self.background_color {
+            self.background_color = new_color;
+            self.needs_full_render = true;
+        }
+    }
+
+    /// Sets the *background* component, which will be rendered behind the root component
+    /// (after clearing the screen with the background color) whenever the application is
+    /// redrawn entirely. Unlike the root component, the background component is not affected by
+    /// the UI scale and always covers the entire viewport.
+    ///
+    /// The background component only receives tick, theme change, low memory, and window state
+    /// events (when it subscribes to them): it can't receive any mouse or keyboard events or close
+    /// requests, and its menu change requests are ignored. When it requests a render, the entire
+    /// application will be redrawn.
+    ///
+    /// The previous background component (if any) will be detached.
+    pub fn set_background_component(&mut self, new_background: Option<Box<dyn Component>>) {
+        if let Some((mut old_background, _)) = self.background.take() {
+            old_background.on_detach();
+        }
+        if let Some(mut new_background) = new_background {
+            let mut buddy = RootComponentBuddy::new();
+            buddy.set_mouse_store(Rc::clone(&self.mouse_store));
+            buddy.set_gamepad_store(Rc::clone(&self.gamepad_store));
+            buddy.set_theme(Rc::clone(&self.theme));
+            buddy.set_frame_timing(self.frame_timing);
+            new_background.on_attach(&mut buddy);
+            self.background = Some((new_background, buddy));
+        }
+        self.needs_full_render = true;
+    }
+
+    /// Gets the current `Theme`, which is also available to all components via
+    /// `ComponentBuddy.get_theme`
+    pub fn get_theme(&self) -> Rc<Theme> {
+        Rc::clone(&self.theme)
+    }
+
+    /// Replaces the `Theme` of this application. Since the theme can affect every component, the
+    /// application will be redrawn entirely during the next `render`, which gives the components
+    /// that follow the theme the chance to update their style.
+    ///
+    /// Furthermore, a `ThemeChangeEvent` will be fired to all components that subscribed to it,
+    /// which is useful for components that derive state from the theme outside `render`.
+    pub fn set_theme(&mut self, new_theme: Theme) {
+        let old_theme = std::mem::replace(&mut self.theme, Rc::new(new_theme));
+        self.root_buddy.set_theme(Rc::clone(&self.theme));
+        if let Some((_, background_buddy)) = &mut self.background {
+            background_buddy.set_theme(Rc::clone(&self.theme));
+        }
+        self.needs_full_render = true;
+
+        let event = ThemeChangeEvent::new(old_theme, Rc::clone(&self.theme));
+        if let Some((background, background_buddy)) = &mut self.background {
+            if background_buddy.get_subscriptions().theme_change {
+                background.on_theme_change(&event, background_buddy);
+            }
+        }
+        if self.root_buddy.get_subscriptions().theme_change {
+            self.root_component.on_theme_change(&event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Describes the current component tree, starting at the root component, which is useful
+    /// for debugging deep menu hierarchies. The result can be printed as indented text (using
+    /// its `Display` implementation) or converted to JSON (using `to_json`).
+    ///
+    /// The menus describe their children via `Component.describe_children`, so the tree will
+    /// stop at custom menu components that don't implement it.
+    pub fn dump_tree(&self) -> ComponentDescription {
+        ComponentDescription::of(self.root_component.as_ref())
+            .with_subscriptions(self.root_buddy.get_subscriptions())
+            .with_render_result(self.root_buddy.get_last_render_result())
+    }
+
+    /// Computes the `LayoutMap` of the current component tree, for an image of *width* x *height*
+    /// pixels that covers the entire window (like the frames captured by the `Renderer`)
+    pub fn create_layout_map(&self, width: u32, height: u32) -> LayoutMap {
+        LayoutMap::new(&self.dump_tree(), self.get_root_domain(), width, height)
+    }
+
+    /// Renders this application entirely, and exports an annotated screenshot (a PNG file at
+    /// *png_path*) and the JSON representation of its `LayoutMap` (at *json_path*). Each component
+    /// will be outlined in the screenshot, labeled with the number of its entry in the layout map.
+    /// This is useful for documentation and for tools that drive the UI externally.
+    ///
+    /// When the *renderer* can't capture its frame (see `Renderer.capture_frame`), the outlines
+    /// will be drawn on the background color instead.
+    pub fn export_layout_map(
+        &mut self,
+        renderer: &Renderer,
+        png_path: &str,
+        json_path: &str,
+    ) -> std::io::Result<LayoutMap> {
+        self.render(renderer, true);
+
+        let viewport = renderer.get_viewport();
+        let layout_map = self.create_layout_map(viewport.get_width(), viewport.get_height());
+        let mut screenshot = renderer.capture_frame().unwrap_or_else(|| {
+            Texture::new(viewport.get_width(), viewport.get_height(), self.background_color)
+        });
+        layout_map.annotate(&mut screenshot);
+
+        write_png(&screenshot, png_path)?;
+        std::fs::write(json_path, layout_map.to_json())?;
+        Ok(layout_map)
+    }
+
+    pub fn get_menu_change_policy(&self) -> MenuChangePolicy {
+        self.menu_change_policy
+    }
+
+    /// Sets the `MenuChangePolicy` that decides which request wins when multiple components
+    /// request to change the menu during the same event. The default policy is `LastWins`.
+    pub fn set_menu_change_policy(&mut self, policy: MenuChangePolicy) {
+        self.menu_change_policy = policy;
+        self.root_buddy.set_menu_change_policy(policy);
+    }
+
+    pub fn get_ui_scale(&self) -> f32 {
+        self.ui_scale
+    }
+
+    /// Sets the UI scale of this application, which is 1.0 by default. This is meant for settings
+    /// like "UI zoom" or "large text": all components will be scaled uniformly, regardless of the
+    /// DPI of the screen.
+    ///
+    /// When the scale is larger than 1.0, the root component will be rendered in a viewport that
+    /// is larger than the window, so part of it won't be visible. When the scale is smaller than
+    /// 1.0, the root component won't cover the entire window. In both cases, the scaling is done
+    /// around the top-left corner of the window, like the content of a web page that is zoomed
+    /// in or out. The points of the mouse events (and the caret rectangle) will be converted
+    /// between the coordinate system of the window and that of the root component, so the
+    /// *wrapper* doesn't need to take the scale into account.
+    ///
+    /// ## Panics
+    /// This method will panic if *new_scale* is not positive.
+    pub fn set_ui_scale(&mut self, new_scale: f32) {
+        assert!(new_scale > 0.0, "The UI scale must be positive, but is {}", new_scale);
+        if new_scale != self.ui_scale {
+            self.ui_scale = new_scale;
+            self.needs_full_render = true;
+        }
+    }
+
+    /// Gets the render scale of this application (see `set_render_scale`)
+    pub fn get_render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Sets the render scale of this application, which is 1.0 by default. When the render scale
+    /// is smaller than 1.0, the *wrapper* will render the application at a fraction of the
+    /// resolution of the window (into an offscreen target), and upscale the result to the window.
+    /// This trades sharpness for performance, which is useful on weak GPUs. Unlike the UI scale,
+    /// this doesn't affect the layout or the coordinates of the events at all.
+    ///
+    /// This can be changed at any time: the application will be rendered again entirely.
+    ///
+    /// ## Panics
+    /// This method will panic if *new_scale* is not in the range (0.0, 1.0].
+    pub fn set_render_scale(&mut self, new_scale: f32) {
+        assert!(
+            new_scale > 0.0 && new_scale <= 1.0,
+            "The render scale must be in the range (0.0, 1.0], but is {}", new_scale
+        );
+        if new_scale != self.render_scale {
+            self.render_scale = new_scale;
+            self.needs_full_render = true;
+        }
+    }
+
+    /// Computes the size (in pixels) of the offscreen target on which the *wrapper* should render
+    /// this application, when the window is *window_width* pixels wide and *window_height* pixels
+    /// high. This is the window size multiplied by the render scale, but at least 1 pixel.
+    pub fn get_render_size(&self, window_width: u32, window_height: u32) -> (u32, u32) {
+        let scale = |size: u32| ((size as f32 * self.render_scale).round() as u32).max(1);
+        (scale(window_width), scale(window_height))
+    }
+
+    /// Gets the duration of the fixed ticks of this application, or `None` if it doesn't use
+    /// fixed ticks (which is the default). See `set_fixed_tick_duration`.
+    pub fn get_fixed_tick_duration(&self) -> Option<Duration> {
+        self.fixed_ticks
+            .as_ref()
+            .map(|accumulator| accumulator.get_tick_duration())
+    }
+
+    /// Enables fixed ticks with the given duration (or disables them when *tick_duration* is
+    /// `None`). By default, `update` fires exactly 1 `TickEvent` per frame, whose delta time is
+    /// the time since the previous frame. When fixed ticks are enabled, `update` will instead
+    /// fire as many ticks of *tick_duration* as fit in the time since the previous frame, and
+    /// accumulate the remaining time for the next frame (see `FixedTickAccumulator`).
+    ///
+    /// This is meant for components driven by a simulation (like the HUD of a game). To render
+    /// smoothly at refresh rates that are higher than the tick rate, they should interpolate
+    /// between their previous and current state using `Renderer.get_interpolation_alpha`, and
+    /// request a render every frame while they are moving (not only after each tick).
+    ///
+    /// ## Panics
+    /// This method will panic if *tick_duration* is zero.
+    pub fn set_fixed_tick_duration(&mut self, tick_duration: Option<Duration>) {
+        self.fixed_ticks = tick_duration.map(FixedTickAccumulator::new);
+    }
+
+    /// Gets the interpolation alpha that will be given to the `Renderer` during the next `render`
+    /// call. This is always 1.0 when fixed ticks are disabled. See `set_fixed_tick_duration`.
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        match &self.fixed_ticks {
+            Some(accumulator) => accumulator.get_alpha(),
+            None => 1.0,
+        }
+    }
+
+    /// Gets the current `FrameTiming`, which is also available to all components via
+    /// `ComponentBuddy.get_frame_timing`
+    pub fn get_frame_timing(&self) -> FrameTiming {
+        self.frame_timing
+    }
+
+    /// Sets the refresh rate (in Hz) of the display that the application is currently shown on,
+    /// or `None` if it is unknown (which is the default). The *wrapper* should call this when the
+    /// application starts, and whenever the window moves to another display.
+    ///
+    /// ## Panics
+    /// This method will panic if *refresh_rate* is not positive.
+    pub fn set_refresh_rate(&mut self, refresh_rate: Option<f32>) {
+        if let Some(refresh_rate) = refresh_rate {
+            assert!(
+                refresh_rate > 0.0,
+                "The refresh rate must be positive, but is {}",
+                refresh_rate
+            );
+        }
+        self.set_frame_timing(self.frame_timing.with_refresh_rate(refresh_rate));
+    }
+
+    /// Informs the application about the timestamp of the frame that it is about to produce. The
+    /// *wrapper* should call this right before each `frame` (or `update`), preferably with the
+    /// timestamp that the platform gives for the frame (like the timestamp that browsers pass to
+    /// `requestAnimationFrame` callbacks). The timestamps can be measured from any starting point,
+    /// as long as it stays the same.
+    pub fn set_frame_time(&mut self, frame_time: Duration) {
+        self.set_frame_timing(self.frame_timing.next_frame(frame_time));
+    }
+
+    fn set_frame_timing(&mut self, frame_timing: FrameTiming) {
+        self.frame_timing = frame_timing;
+        self.root_buddy.set_frame_timing(frame_timing);
+        if let Some((_, background_buddy)) = &mut self.background {
+            background_buddy.set_frame_timing(frame_timing);
+        }
+    }
+
+    /// Gets the domain of the root component in the coordinate system of the window, which
+    /// depends on the UI scale (see `set_ui_scale`)
+    fn get_root_domain(&self) -> ComponentDomain {
+        let scale = self.ui_scale;
+        ComponentDomain::between(0.0, 1.0 - scale, scale, 1.0)
+    }
+
+    /// Converts a point in the coordinate system of the window to the coordinate system of the
+    /// root component
+    fn to_root_point(&self, point: Point) -> Point {
+        self.get_root_domain().transform(point)
+    }
+
+    pub fn get_invalidation_debug(&self) -> Option<InvalidationDebug> {
+        self.invalidation_debug
+    }
+
+    /// Enables (`Some`) or disables (`None`) the invalidation debug mode. While this mode is
+    /// enabled, every region that is repainted will be tinted with a transient translucent
+    /// overlay (like the paint flashing of web browsers), which shows exactly which components
+    /// redraw during each frame. This is useful to catch components that request renders (or
+    /// redraw their entire domain) more often than needed.
+    ///
+    /// The overlay of a region will be removed after the given number of frames, by force
+    /// rendering the root component inside that region. These cleanup renders won't be tinted.
+    pub fn set_invalidation_debug(&mut self, invalidation_debug: Option<InvalidationDebug>) {
+        if invalidation_debug.is_none() && !self.invalidation_flashes.is_empty() {
+            self.invalidation_flashes.clear();
+            self.needs_full_render = true;
+        }
+        self.invalidation_debug = invalidation_debug;
+    }
+
+    /// Registers the given font to the `TextRenderer` of the `Renderer` (during the next render),
+    /// so that components can use it by its *font_id*. When the `Renderer` shares its
+    /// `GlyphCache` with the renderers of other `Application`s, and one of them already
+    /// registered a font with the same id, that font will be used instead (to avoid storing the
+    /// glyphs of the same font multiple times).
     pub fn register_font(&mut self, font_id: &str, font: Box<dyn Font>) {
         self.fonts_to_register.insert(font_id.to_string(), font);
     }
 
+    /// Processes the menu changes that were requested during the last event. The requests that
+    /// lost (according to the `MenuChangePolicy`) are rejected before the winning request is
+    /// carried out. When the new root component requests another menu change during its
+    /// `on_attach`, that change will be processed right away as well (at most
+    /// `MAX_MENU_CHANGES_PER_EVENT` times in a row).
     fn work_after_events(&mut self) {
-        if self.root_buddy.has_next_menu() {
+        let mut num_menu_changes = 0;
+        loop {
+            for rejected_request in self.root_buddy.take_rejected_menu_changes() {
+                rejected_request.reject();
+            }
+            if !self.root_buddy.has_next_menu() {
+                break;
+            }
+            if num_menu_changes == MAX_MENU_CHANGES_PER_EVENT {
+                // Shouldn't happen, but not critical enough for a release panic
+                debug_assert!(false, "The root components keep changing the menu");
+                break;
+            }
+            num_menu_changes += 1;
+
             self.root_component.on_detach();
 
             // Work around because self.root_component must have some value at all times
@@ -72,15 +520,96 @@ impl Application {
 
             // A fresh main component requires a fresh buddy
             self.root_buddy = RootComponentBuddy::new();
+            self.hovering_mouses.clear();
             self.root_buddy
                 .set_mouse_store(Rc::clone(&self.mouse_store));
+            self.root_buddy.set_gamepad_store(Rc::clone(&self.gamepad_store));
+            self.root_buddy.set_menu_change_policy(self.menu_change_policy);
+            self.root_buddy.set_theme(Rc::clone(&self.theme));
+            self.root_buddy.set_frame_timing(self.frame_timing);
 
             self.root_component.on_attach(&mut self.root_buddy);
-            self.work_after_events();
+        }
+        if num_menu_changes > 0 {
             self.root_buddy.request_render();
         }
     }
 
+    /// Queues the given input *event*, which will be fired during the next call to `update` (or
+    /// `frame`). See `frame` for the ordering guarantees.
+    pub fn queue_event(&mut self, event: InputEvent) {
+        self.queued_events.push(event);
+    }
+
+    /// Fires the given input *event* immediately, which is equivalent to calling the corresponding
+    /// `fire_..._event` method.
+    pub fn fire_event(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::MouseClick(event) => self.fire_mouse_click_event(event),
+            InputEvent::MousePress(event) => self.fire_mouse_press_event(event),
+            InputEvent::MouseRelease(event) => self.fire_mouse_release_event(event),
+            InputEvent::MouseMove(event) => self.fire_mouse_move_event(event),
+            InputEvent::MouseEnter(event) => self.fire_mouse_enter_event(event),
+            InputEvent::MouseLeave(event) => self.fire_mouse_leave_event(event),
+            InputEvent::MouseScroll(event) => self.fire_mouse_scroll_event(event),
+            InputEvent::RelativeMouseMove(event) => self.fire_relative_mouse_move_event(event),
+            InputEvent::CharType(event) => self.fire_char_type_event(event),
+            InputEvent::CompositionStart(event) => self.fire_composition_start_event(event),
+            InputEvent::CompositionUpdate(event) => self.fire_composition_update_event(event),
+            InputEvent::CompositionEnd(event) => self.fire_composition_end_event(event),
+            InputEvent::GamepadButton(event) => self.fire_gamepad_button_event(event),
+            InputEvent::GamepadAxis(event) => self.fire_gamepad_axis_event(event),
+            InputEvent::LowMemory(event) => self.fire_low_memory_event(event),
+            InputEvent::WindowFocus(event) => self.fire_window_focus_event(event),
+            InputEvent::WindowMinimize(event) => self.fire_window_minimize_event(event),
+        }
+    }
+
+    /// Fires all queued input events (in the order in which they were queued), and then fires a
+    /// `TickEvent` with the given *delta_time* (or the fixed ticks that fit in *delta_time*, see
+    /// `set_fixed_tick_duration`). This is the first half of `frame`: *wrapper*s that need to do
+    /// some work between updating and rendering can call `update` and `render` separately.
+    pub fn update(&mut self, delta_time: Duration) {
+        for event in std::mem::take(&mut self.queued_events) {
+            self.fire_event(event);
+        }
+        if let Some(accumulator) = &mut self.fixed_ticks {
+            let num_ticks = accumulator.advance(delta_time);
+            let tick_duration = accumulator.get_tick_duration();
+            for _counter in 0..num_ticks {
+                self.fire_tick_event(TickEvent::new(tick_duration));
+            }
+        } else {
+            self.fire_tick_event(TickEvent::new(delta_time));
+        }
+    }
+
+    /// Runs a single frame of this application: the *wrapper* should call this once per frame,
+    /// after queueing the input events that it received since the previous frame (using
+    /// `queue_event`).
+    ///
+    /// ### Ordering
+    /// Each frame consists of the following steps, in this order:
+    /// 1. All queued input events are fired, in the order in which they were queued.
+    /// 2. A single `TickEvent` with the given *delta_time* is fired (or any number of fixed ticks,
+    ///    see `set_fixed_tick_duration`). The synthesized events that became due (long presses
+    ///    and hovers) are fired right after each tick.
+    /// 3. The application gets the opportunity to render (see `render`), which is mandatory if
+    ///    *force* is true. This returns true if and only if the application rendered.
+    ///
+    /// Components can thus rely on receiving all input of a frame before the tick of that frame,
+    /// and on their tick before they render.
+    ///
+    /// ### Menu changes
+    /// When a component calls `change_menu` during an event, the new root component will be
+    /// attached right after that event (before the next event is fired). If the new root
+    /// component changes the menu again during its `on_attach`, that change will be processed
+    /// immediately as well, up to `MAX_MENU_CHANGES_PER_EVENT` times in a row.
+    pub fn frame(&mut self, renderer: &Renderer, delta_time: Duration, force: bool) -> bool {
+        self.update(delta_time);
+        self.render(renderer, force)
+    }
+
     /// Gives the `Application` the opportunity to render its components, or
     /// even `force`s it to do so.
     ///
@@ -111,28 +640,69 @@ impl Application {
     /// This method returns true if the application chose to render (or it was
     /// forced to do so) and false if the application chose not to render.
     pub fn render(&mut self, renderer: &Renderer, force: bool) -> bool {
-        if force || self.root_buddy.did_request_render() {
-            self.root_buddy.clear_render_request();
+        if let Some(pressure) = self.pending_memory_pressure.take() {
+            renderer.release_memory(pressure);
+        }
+        let background_requested_render = match &self.background {
+            Some((_, background_buddy)) => background_buddy.did_request_render(),
+            None => false,
+        };
+        renderer.set_interpolation_alpha(self.get_interpolation_alpha());
+        self.gesture_recognizer
+            .set_aspect_ratio(renderer.get_viewport().get_aspect_ratio());
+        let context_generation = renderer.get_context_generation();
+        let lost_context = context_generation != self.last_context_generation;
+        self.last_context_generation = context_generation;
+        let force = force
+            || std::mem::take(&mut self.needs_full_render)
+            || background_requested_render
+            || lost_context;
+        let expired_flashes = self.take_expired_invalidation_flashes();
+        if force || self.root_buddy.did_request_render() || !expired_flashes.is_empty() {
 
             // If new fonts were registered to the Application, propagate them to the Renderer
+            let text_renderer = renderer.get_text_renderer();
             for (font_id, font) in self.fonts_to_register.drain() {
-                renderer.get_text_renderer().register_font(&font_id, font);
+                if text_renderer.get_font(&font_id).is_none() {
+                    text_renderer.register_font(&font_id, font);
+                }
             }
 
             // Make sure we draw onto the right area
             renderer.start();
 
-            // If we are forced to redraw, we should clean the previous render actions up
-            if force {
-                renderer.clear(Color::rgb(0, 0, 0));
+            // Remove the overlays of the invalidation debug mode that expired (unnecessary when
+            // everything will be redrawn anyway)
+            if !force {
+                for region in expired_flashes {
+                    renderer.push_scissor_region(Some(region), || {
+                        self.render_background(renderer);
+                        self.render_root(renderer, true);
+                    });
+                }
             }
 
-            // Let the root component render itself
-            let result = self
-                .root_component
-                .render(renderer, &mut self.root_buddy, force)
-                .expect("Render shouldn't fail");
-            self.root_buddy.set_last_render_result(result);
+            if force || self.root_buddy.did_request_render() {
+                self.root_buddy.clear_render_request();
+                if self.invalidation_debug.is_some() {
+                    renderer.start_repaint_log();
+                }
+
+                // If we are forced to redraw, we should clean the previous render actions up
+                if force {
+                    self.render_background(renderer);
+                }
+
+                // Let the root component render itself
+                self.render_root(renderer, force);
+
+                if let Some(invalidation_debug) = self.invalidation_debug {
+                    let repainted_regions = renderer.finish_repaint_log();
+                    self.flash_invalidated_regions(
+                        renderer, invalidation_debug, repainted_regions
+                    );
+                }
+            }
 
             // Check if the root component requested anything while rendering
             self.work_after_events();
@@ -142,44 +712,354 @@ impl Application {
         }
     }
 
+    fn render_background(&mut self, renderer: &Renderer) {
+        renderer.clear(self.background_color);
+        if let Some((background, background_buddy)) = &mut self.background {
+            background_buddy.clear_render_request();
+            background
+                .render(renderer, background_buddy, true)
+                .expect("Render shouldn't fail");
+        }
+    }
+
+    fn render_root(&mut self, renderer: &Renderer, force: bool) {
+        let root_component = &mut self.root_component;
+        let root_buddy = &mut self.root_buddy;
+        let mut render_root = || root_component.render(renderer, root_buddy, force);
+        let maybe_result = match self.ui_scale == 1.0 {
+            true => Some(render_root()),
+            false => {
+                let scale = self.ui_scale;
+                renderer.push_viewport(0.0, 1.0 - scale, scale, 1.0, render_root)
+            }
+        };
+
+        // The result will be None if the scaled viewport would be empty
+        if let Some(result) = maybe_result {
+            let result = result.expect("Render shouldn't fail");
+            self.root_buddy.set_last_render_result(result);
+        }
+    }
+
+    /// Counts down the remaining frames of the invalidation flashes, and removes and returns the
+    /// regions of the flashes that expired
+    fn take_expired_invalidation_flashes(&mut self) -> Vec<RenderRegion> {
+        let mut expired_regions = Vec::new();
+        self.invalidation_flashes.retain(|flash| {
+            if flash.remaining_frames <= 1 {
+                expired_regions.push(flash.region);
+                false
+            } else {
+                true
+            }
+        });
+        for flash in &mut self.invalidation_flashes {
+            flash.remaining_frames -= 1;
+        }
+        expired_regions
+    }
+
+    fn flash_invalidated_regions(
+        &mut self, renderer: &Renderer,
+        invalidation_debug: InvalidationDebug, regions: Vec<RenderRegion>
+    ) {
+        for region in regions {
+            // Nested components often repaint the same region, which shouldn't be tinted twice
+            if self.invalidation_flashes.iter().any(|flash| {
+                flash.region == region && flash.remaining_frames == invalidation_debug.num_frames
+            }) {
+                continue;
+            }
+
+            // The repaint erased the old overlay of this region, so the old flash should not
+            // trigger a cleanup that would erase the new overlay early
+            self.invalidation_flashes.retain(|flash| flash.region != region);
+
+            renderer.push_scissor_region(Some(region), || {
+                renderer.push_opacity(invalidation_debug.opacity, || {
+                    renderer.clear(invalidation_debug.color);
+                });
+            });
+            self.invalidation_flashes.push(InvalidationFlash {
+                region,
+                remaining_frames: invalidation_debug.num_frames,
+            });
+        }
+    }
+
+    /// Gets the `AccessibleText` of the deepest component at the given *point* that has one. This
+    /// is meant for screen readers and test drivers.
+    pub fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        let point = self.to_root_point(point);
+        self.root_component.get_accessible_text_at(point).or_else(|| {
+            let root_text = self.root_buddy.get_accessible_text();
+            match root_text.is_empty() {
+                true => None,
+                false => Some(root_text.clone()),
+            }
+        })
+    }
+
+    /// Gets the `CursorIcon` that should be shown when the mouse cursor is at the given *point*:
+    /// the cursor of the deepest component at *point* that set one, or `CursorIcon::Arrow` when
+    /// none of them did.
+    pub fn get_cursor_at(&self, point: Point) -> CursorIcon {
+        self.get_root_cursor_at(self.to_root_point(point))
+    }
+
+    /// Gets the `CursorIcon` that the *wrapper* should show for the given *mouse*, based on the
+    /// position of its last event (see `get_cursor_at`). When the mouse is not inside the
+    /// window, this will return `CursorIcon::Arrow`. Wrappers should call this after firing
+    /// events, since components can change their cursor while handling events.
+    pub fn get_cursor(&self, mouse: Mouse) -> CursorIcon {
+        let mouse_store = self.mouse_store.borrow();
+        match mouse_store.get_mouse_state(mouse) {
+            Some(state) => self.get_root_cursor_at(state.position),
+            None => CursorIcon::Arrow,
+        }
+    }
+
+    fn get_root_cursor_at(&self, root_point: Point) -> CursorIcon {
+        self.root_component
+            .get_cursor_at(root_point)
+            .or_else(|| self.root_buddy.get_cursor())
+            .unwrap_or(CursorIcon::Arrow)
+    }
+
+    /// Gets the `WindowFrameArea` at the given *point*: the area of the deepest component at
+    /// *point* that declared one, or `WindowFrameArea::Content` when none of them did.
+    pub fn get_window_frame_area_at(&self, point: Point) -> WindowFrameArea {
+        self.get_root_window_frame_area_at(self.to_root_point(point))
+    }
+
+    fn get_root_window_frame_area_at(&self, root_point: Point) -> WindowFrameArea {
+        self.root_component
+            .get_window_frame_area_at(root_point)
+            .or_else(|| self.root_buddy.get_window_frame_area())
+            .unwrap_or(WindowFrameArea::Content)
+    }
+
+    /// When the primary mouse button was pressed in a `WindowFrameArea` other than `Content`
+    /// since the last call to this method, this method returns that area. The *wrapper* should
+    /// call this after firing the events, and start moving or resizing the window (until the
+    /// button is released) when this returns something.
+    pub fn take_window_drag_request(&mut self) -> Option<WindowFrameArea> {
+        self.window_drag_request.take()
+    }
+
+    /// Starts recording a hit test trace: for each mouse click, press, release, and scroll event
+    /// that is fired after this call, the `Application` and the menu components will record
+    /// which components considered the event, and why they did or did not pass it on to their
+    /// event handler. This is meant for diagnosing why mouse events don't arrive at some
+    /// component.
+    ///
+    /// Use `finish_hit_test_trace` to get the recorded trace and stop the tracing.
+    pub fn start_hit_test_trace(&mut self) {
+        start_hit_test_trace();
+    }
+
+    /// Stops recording the hit test trace (see `start_hit_test_trace`) and returns all records
+    /// since it was started, in chronological order.
+    pub fn finish_hit_test_trace(&mut self) -> Vec<HitTestRecord> {
+        finish_hit_test_trace()
+    }
+
+    /// Checks whether the root component should receive a mouse event at the given *point* and
+    /// records the outcome in the hit test trace (if it is enabled)
+    fn check_root_hit(&self, event_name: &'static str, subscribed: bool, point: Point) -> bool {
+        let outcome = if !subscribed {
+            HitTestOutcome::NotSubscribed
+        } else {
+            match self.root_buddy.get_last_render_result() {
+                Some(render_result) => {
+                    if !render_result.filter_mouse_actions
+                        || render_result.drawn_region.is_inside(point)
+                    {
+                        HitTestOutcome::Delivered
+                    } else {
+                        HitTestOutcome::OutsideDrawnRegion
+                    }
+                }
+                None => HitTestOutcome::NotRenderedYet,
+            }
+        };
+
+        trace_hit_test(event_name, None, outcome);
+        outcome == HitTestOutcome::Delivered
+    }
+
     pub fn fire_mouse_click_event(&mut self, event: MouseClickEvent) {
+        let point = self.to_root_point(event.get_point());
+        let event = MouseClickEvent::new(event.get_mouse(), point, event.get_button());
+
         let sub_mouse_click = self.root_buddy.get_subscriptions().mouse_click;
         let sub_mouse_click_out = self.root_buddy.get_subscriptions().mouse_click_out;
 
-        if sub_mouse_click || sub_mouse_click_out {
-            let point = event.get_point();
+        let subscribed = sub_mouse_click || sub_mouse_click_out;
+        let fire = self.check_root_hit("MouseClick", subscribed, event.get_point());
 
-            let mut fire = false;
-            let mut fire_out = false;
-            let maybe_render_result = self.root_buddy.get_last_render_result();
+        // Don't pass on any click events until the component has been
+        // rendered for the first time.
+        let fire_out =
+            subscribed && !fire && self.root_buddy.get_last_render_result().is_some();
 
-            // Don't pass on any click events until the component has been
-            // rendered for the first time.
-            if let Some(render_result) = maybe_render_result {
-                // If we should filter mouse actions, we need to do an additional check
-                if render_result.filter_mouse_actions {
-                    fire = render_result.drawn_region.is_inside(point);
-                } else {
-                    fire = true;
-                }
-                fire_out = !fire;
-            }
+        if fire {
+            self.root_component
+                .on_mouse_click(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+        if fire_out {
+            let out_event = MouseClickOutEvent::new(event.get_mouse(), event.get_button());
+            self.root_component
+                .on_mouse_click_out(out_event, &mut self.root_buddy);
+            self.work_after_events();
+        }
 
-            if fire {
-                self.root_component
-                    .on_mouse_click(event, &mut self.root_buddy);
-                self.work_after_events();
+        if event.get_button().is_secondary() {
+            self.fire_mouse_context_click_event(MouseContextClickEvent::new(
+                event.get_mouse(),
+                event.get_point(),
+                ContextClickSource::SecondaryButton,
+            ));
+        }
+        if event.get_button().is_middle() {
+            let middle_event = MouseMiddleClickEvent::new(event.get_mouse(), event.get_point());
+            self.fire_mouse_middle_click_event(middle_event);
+        }
+
+        if self.register_click(event) {
+            self.fire_mouse_double_click_event(MouseDoubleClickEvent::new(
+                event.get_mouse(),
+                event.get_point(),
+                event.get_button(),
+            ));
+        }
+    }
+
+    /// Remembers the given click event and returns true if it completes a double click
+    fn register_click(&mut self, event: MouseClickEvent) -> bool {
+        let settings = self.input_settings;
+        let current_time = self.current_time;
+        let previous_index = self.previous_clicks.iter().position(|previous| {
+            previous.mouse == event.get_mouse() && previous.button == event.get_button()
+        });
+
+        if let Some(previous_index) = previous_index {
+            let previous = self.previous_clicks.swap_remove(previous_index);
+            if current_time - previous.time <= settings.double_click_time
+                && previous.point.distance_to(event.get_point()) < settings.drag_start_distance
+            {
+                // Don't remember this click, to prevent triple clicks from becoming 2 double
+                // clicks
+                return true;
             }
-            if fire_out {
-                let out_event = MouseClickOutEvent::new(event.get_mouse(), event.get_button());
-                self.root_component
-                    .on_mouse_click_out(out_event, &mut self.root_buddy);
-                self.work_after_events();
+        }
+
+        self.previous_clicks.push(TimedMouseAction {
+            mouse: event.get_mouse(),
+            button: event.get_button(),
+            point: event.get_point(),
+            time: current_time,
+        });
+        false
+    }
+
+    fn fire_mouse_double_click_event(&mut self, event: MouseDoubleClickEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().mouse_double_click;
+        if self.check_root_hit("MouseDoubleClick", subscribed, event.get_point()) {
+            self.root_component
+                .on_mouse_double_click(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    fn fire_mouse_long_press_event(&mut self, event: MouseLongPressEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().mouse_long_press;
+        if self.check_root_hit("MouseLongPress", subscribed, event.get_point()) {
+            self.root_component
+                .on_mouse_long_press(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    fn fire_mouse_context_click_event(&mut self, event: MouseContextClickEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().mouse_context_click;
+        if self.check_root_hit("MouseContextClick", subscribed, event.get_point()) {
+            self.root_component
+                .on_mouse_context_click(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    fn fire_mouse_middle_click_event(&mut self, event: MouseMiddleClickEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().mouse_middle_click;
+        if self.check_root_hit("MouseMiddleClick", subscribed, event.get_point()) {
+            self.root_component
+                .on_mouse_middle_click(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Fires a `MouseLongPressEvent` for each mouse button that has been held down long enough
+    fn check_long_presses(&mut self) {
+        let long_press_duration = self.input_settings.long_press_duration;
+        let current_time = self.current_time;
+        let candidates = std::mem::take(&mut self.long_press_candidates);
+        let (expired, remaining): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .partition(|candidate| current_time - candidate.time >= long_press_duration);
+        self.long_press_candidates = remaining;
+
+        for candidate in expired {
+            self.fire_mouse_long_press_event(MouseLongPressEvent::new(
+                candidate.mouse,
+                candidate.point,
+                candidate.button,
+            ));
+
+            if self.input_settings.long_press_context_click && candidate.button.is_primary() {
+                self.fire_mouse_context_click_event(MouseContextClickEvent::new(
+                    candidate.mouse,
+                    candidate.point,
+                    ContextClickSource::LongPress,
+                ));
             }
         }
     }
 
+    fn fire_mouse_hover_event(&mut self, event: MouseHoverEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().mouse_hover;
+        if self.check_root_hit("MouseHover", subscribed, event.get_point()) {
+            self.root_component
+                .on_mouse_hover(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Fires a `MouseHoverEvent` for each mouse that has been resting long enough
+    fn check_hovers(&mut self) {
+        let hover_duration = self.input_settings.hover_duration;
+        let current_time = self.current_time;
+        let candidates = std::mem::take(&mut self.hover_candidates);
+        let (expired, remaining): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .partition(|candidate| current_time - candidate.time >= hover_duration);
+        self.hover_candidates = remaining;
+
+        for candidate in expired {
+            self.fire_mouse_hover_event(MouseHoverEvent::new(candidate.mouse, candidate.point));
+        }
+    }
+
     pub fn fire_mouse_press_event(&mut self, event: MousePressEvent) {
+        let point = self.to_root_point(event.get_point());
+        let event = MousePressEvent::with_details(
+            event.get_mouse(),
+            point,
+            event.get_button(),
+            event.get_details(),
+        );
+
         let mut mouse_store = self.mouse_store.borrow_mut();
         match mouse_store.update_mouse_state(event.get_mouse()) {
             Some(state) => state.buttons.press(event.get_button()),
@@ -187,20 +1067,48 @@ impl Application {
         };
         drop(mouse_store);
 
-        if self.root_buddy.get_subscriptions().mouse_press {
-            if let Some(render_result) = self.root_buddy.get_last_render_result() {
-                if !render_result.filter_mouse_actions
-                    || render_result.drawn_region.is_inside(event.get_point())
-                {
-                    self.root_component
-                        .on_mouse_press(event, &mut self.root_buddy);
-                    self.work_after_events();
-                }
+        self.long_press_candidates.retain(|candidate| {
+            candidate.mouse != event.get_mouse() || candidate.button != event.get_button()
+        });
+        self.long_press_candidates.push(TimedMouseAction {
+            mouse: event.get_mouse(),
+            button: event.get_button(),
+            point: event.get_point(),
+            time: self.current_time,
+        });
+
+        // Pressing a button means that the user is no longer just hovering
+        self.hover_candidates
+            .retain(|candidate| candidate.mouse != event.get_mouse());
+
+        if event.get_button() == MouseButton::primary() {
+            let area = self.get_root_window_frame_area_at(event.get_point());
+            if area != WindowFrameArea::Content {
+                self.window_drag_request = Some(area);
             }
+
+            // On touch screens, each finger is a mouse with its primary button pressed
+            self.gesture_recognizer
+                .press(event.get_mouse(), event.get_point(), self.current_time);
+        }
+
+        let subscribed = self.root_buddy.get_subscriptions().mouse_press;
+        if self.check_root_hit("MousePress", subscribed, event.get_point()) {
+            self.root_component
+                .on_mouse_press(event, &mut self.root_buddy);
+            self.work_after_events();
         }
     }
 
     pub fn fire_mouse_release_event(&mut self, event: MouseReleaseEvent) {
+        let point = self.to_root_point(event.get_point());
+        let event = MouseReleaseEvent::with_details(
+            event.get_mouse(),
+            point,
+            event.get_button(),
+            event.get_details(),
+        );
+
         let mut mouse_store = self.mouse_store.borrow_mut();
         match mouse_store.update_mouse_state(event.get_mouse()) {
             Some(state) => state.buttons.release(event.get_button()),
@@ -208,22 +1116,298 @@ impl Application {
         };
         drop(mouse_store);
 
-        if self.root_buddy.get_subscriptions().mouse_release {
-            if let Some(render_result) = self.root_buddy.get_last_render_result() {
-                if !render_result.filter_mouse_actions
-                    || render_result.drawn_region.is_inside(event.get_point())
-                {
-                    self.root_component
-                        .on_mouse_release(event, &mut self.root_buddy);
-                    self.work_after_events();
-                }
+        self.long_press_candidates.retain(|candidate| {
+            candidate.mouse != event.get_mouse() || candidate.button != event.get_button()
+        });
+
+        let subscribed = self.root_buddy.get_subscriptions().mouse_release;
+        let fire = if self.root_buddy.is_capturing_mouse(event.get_mouse()) {
+            // The root component should receive the event, regardless of its position
+            let outcome = match subscribed {
+                true => HitTestOutcome::Captured,
+                false => HitTestOutcome::NotSubscribed,
+            };
+            trace_hit_test("MouseRelease", None, outcome);
+            subscribed
+        } else {
+            self.check_root_hit("MouseRelease", subscribed, event.get_point())
+        };
+
+        if fire {
+            self.root_component
+                .on_mouse_release(event, &mut self.root_buddy);
+        }
+
+        // The release event ends the capture (if any)
+        self.root_buddy.release_mouse_capture(event.get_mouse());
+        if fire {
+            self.work_after_events();
+        }
+
+        if event.get_button() == MouseButton::primary() {
+            let maybe_swipe = self.gesture_recognizer.release(
+                event.get_mouse(),
+                event.get_point(),
+                self.current_time,
+                &self.input_settings,
+            );
+            if let Some(swipe) = maybe_swipe {
+                self.fire_swipe_event(swipe);
             }
         }
     }
 
-    fn sub_mouse_enter(&self) -> bool {
-        self.root_buddy.get_subscriptions().mouse_enter
-    }
+    fn fire_pinch_event(&mut self, event: PinchEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().gestures;
+        if self.check_root_hit("Pinch", subscribed, event.get_center()) {
+            self.root_component.on_pinch(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    fn fire_rotate_gesture_event(&mut self, event: RotateGestureEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().gestures;
+        if self.check_root_hit("RotateGesture", subscribed, event.get_center()) {
+            self.root_component
+                .on_rotate_gesture(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    fn fire_swipe_event(&mut self, event: SwipeEvent) {
+        let subscribed = self.root_buddy.get_subscriptions().gestures;
+        if self.check_root_hit("Swipe", subscribed, event.get_start_point()) {
+            self.root_component.on_swipe(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    pub fn fire_mouse_scroll_event(&mut self, event: MouseScrollEvent) {
+        let point = self.to_root_point(event.get_point());
+        let event = MouseScrollEvent::new(
+            event.get_mouse(),
+            point,
+            event.get_delta_x(),
+            event.get_delta_y(),
+        );
+
+        let subscribed = self.root_buddy.get_subscriptions().mouse_scroll;
+        if self.check_root_hit("MouseScroll", subscribed, event.get_point()) {
+            self.root_component
+                .on_mouse_scroll(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Fires a `RelativeMouseMoveEvent` at the component that holds the pointer lock of its mouse
+    /// (if any). The *wrapper* should fire these events (instead of `MouseMoveEvent`s) while
+    /// `is_pointer_locked` returns true for the mouse.
+    pub fn fire_relative_mouse_move_event(&mut self, event: RelativeMouseMoveEvent) {
+        if self.root_buddy.is_locking_pointer(event.get_mouse()) {
+            let event = RelativeMouseMoveEvent::new(
+                event.get_mouse(),
+                event.get_delta_x() / self.ui_scale,
+                event.get_delta_y() / self.ui_scale,
+            );
+            self.root_component
+                .on_relative_mouse_move(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Checks whether a component holds the pointer lock of the given *mouse* (see
+    /// `ComponentBuddy.request_pointer_lock`). While this returns true, the *wrapper* should hide
+    /// the cursor, keep it in place, and fire `RelativeMouseMoveEvent`s for *mouse*.
+    pub fn is_pointer_locked(&self, mouse: Mouse) -> bool {
+        self.root_buddy.is_locking_pointer(mouse)
+    }
+
+    pub fn fire_char_type_event(&mut self, event: CharTypeEvent) {
+        if self.root_buddy.get_subscriptions().char_type {
+            self.root_component.on_char_type(&event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    pub fn fire_composition_start_event(&mut self, event: CompositionStartEvent) {
+        if self.root_buddy.get_subscriptions().composition {
+            self.root_component
+                .on_composition_start(&event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    pub fn fire_composition_update_event(&mut self, event: CompositionUpdateEvent) {
+        if self.root_buddy.get_subscriptions().composition {
+            self.root_component
+                .on_composition_update(&event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    pub fn fire_composition_end_event(&mut self, event: CompositionEndEvent) {
+        if self.root_buddy.get_subscriptions().composition {
+            self.root_component
+                .on_composition_end(&event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Registers a newly connected *gamepad* with the given *name*, after which it will be
+    /// included in the `get_gamepads` of all `ComponentBuddy`s. The *wrapper* should call this
+    /// before firing any events of the gamepad.
+    pub fn connect_gamepad(&mut self, gamepad: Gamepad, name: String) {
+        self.gamepad_store.borrow_mut().add_gamepad(gamepad, name);
+    }
+
+    /// Removes the given *gamepad* (which was disconnected) from the `get_gamepads` of all
+    /// `ComponentBuddy`s. Events of the gamepad that are fired after this will be ignored.
+    pub fn disconnect_gamepad(&mut self, gamepad: Gamepad) {
+        self.gamepad_store.borrow_mut().remove_gamepad(gamepad);
+    }
+
+    /// Gets all gamepads that are currently connected (see `connect_gamepad`)
+    pub fn get_gamepads(&self) -> Vec<Gamepad> {
+        self.gamepad_store.borrow().get_gamepads()
+    }
+
+    pub fn fire_gamepad_button_event(&mut self, event: GamepadButtonEvent) {
+        let mut gamepad_store = self.gamepad_store.borrow_mut();
+        match gamepad_store.update_gamepad_state(event.get_gamepad()) {
+            Some(state) => state.set_pressed(event.get_button(), event.is_pressed()),
+            // This can happen when the gamepad was disconnected while the event was queued
+            None => return,
+        };
+        drop(gamepad_store);
+
+        if self.root_buddy.get_subscriptions().gamepad {
+            self.root_component.on_gamepad_button(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    pub fn fire_gamepad_axis_event(&mut self, event: GamepadAxisEvent) {
+        let mut gamepad_store = self.gamepad_store.borrow_mut();
+        match gamepad_store.update_gamepad_state(event.get_gamepad()) {
+            Some(state) => state.set_axis_value(event.get_axis(), event.get_value()),
+            // This can happen when the gamepad was disconnected while the event was queued
+            None => return,
+        };
+        drop(gamepad_store);
+
+        if self.root_buddy.get_subscriptions().gamepad {
+            self.root_component.on_gamepad_axis(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Fires the given `LowMemoryEvent` to all components that are subscribed for it (including
+    /// the background component), and lets the `Renderer` release its cached resources during the
+    /// next `render` call (see `Renderer.release_memory`).
+    pub fn fire_low_memory_event(&mut self, event: LowMemoryEvent) {
+        self.pending_memory_pressure = self.pending_memory_pressure.max(Some(event.get_pressure()));
+        if let Some((background, background_buddy)) = &mut self.background {
+            if background_buddy.get_subscriptions().low_memory {
+                background.on_low_memory(event, background_buddy);
+            }
+        }
+        if self.root_buddy.get_subscriptions().low_memory {
+            self.root_component.on_low_memory(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Fires the given `WindowFocusEvent` to all components that are subscribed for window state
+    /// events (including the background component)
+    pub fn fire_window_focus_event(&mut self, event: WindowFocusEvent) {
+        if let Some((background, background_buddy)) = &mut self.background {
+            if background_buddy.get_subscriptions().window_state {
+                background.on_window_focus(event, background_buddy);
+            }
+        }
+        if self.root_buddy.get_subscriptions().window_state {
+            self.root_component.on_window_focus(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// Fires the given `WindowMinimizeEvent` to all components that are subscribed for window
+    /// state events (including the background component)
+    pub fn fire_window_minimize_event(&mut self, event: WindowMinimizeEvent) {
+        if let Some((background, background_buddy)) = &mut self.background {
+            if background_buddy.get_subscriptions().window_state {
+                background.on_window_minimize(event, background_buddy);
+            }
+        }
+        if self.root_buddy.get_subscriptions().window_state {
+            self.root_component.on_window_minimize(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    /// The *wrapper* should call this method when the user tries to close the window (or browser
+    /// tab). All components that are subscribed for close requests will be asked whether the
+    /// window may be closed, and any of them can veto it (for instance to prompt the user to save
+    /// their work).
+    ///
+    /// Returns true if the window may be closed, and false if a component vetoed the request (in
+    /// which case the *wrapper* should keep the window open). Unlike the other events, close
+    /// requests can't be queued, since the *wrapper* needs the answer immediately.
+    pub fn fire_close_request(&mut self) -> bool {
+        if self.root_buddy.get_subscriptions().close_request {
+            let may_close = self.root_component.on_close_request(&mut self.root_buddy);
+            self.work_after_events();
+            may_close
+        } else {
+            true
+        }
+    }
+
+    /// Gets the rectangle around the text caret that was reported most recently by a component,
+    /// in the coordinate system of the window (which is the coordinate system of the root
+    /// component, unless the UI scale was changed). The *wrapper* should use this to position the
+    /// candidate window of the input method editor.
+    pub fn get_caret_rectangle(&self) -> Option<CaretRectangle> {
+        let root_domain = self.get_root_domain();
+        self.root_buddy
+            .get_caret_rectangle()
+            .map(|caret| caret.transform_back(|point| root_domain.transform_back(point)))
+    }
+
+    pub fn fire_tick_event(&mut self, event: TickEvent) {
+        self.current_time += event.get_delta_time();
+        if let Some((background, background_buddy)) = &mut self.background {
+            if background_buddy.get_subscriptions().tick {
+                background.on_tick(event, background_buddy);
+            }
+        }
+        if self.root_buddy.get_subscriptions().tick {
+            self.root_component.on_tick(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+        self.check_long_presses();
+        self.check_hovers();
+    }
+
+    /// Simulates the passing of *duration* by firing `TickEvent`s, as if the application were
+    /// running at 60 frames per second: all ticks (except possibly the last one) will have a delta
+    /// time of 1/60 seconds. This doesn't depend on the wall clock at all, so it is useful for
+    /// unit testing (animated) components reproducibly.
+    ///
+    /// The *wrapper*s should advance the time with `frame` (or `update`) instead.
+    pub fn advance_time(&mut self, duration: Duration) {
+        let frame_duration = Duration::from_secs(1) / 60;
+        let mut remaining_duration = duration;
+        while remaining_duration > Duration::from_secs(0) {
+            let delta_time = remaining_duration.min(frame_duration);
+            self.fire_tick_event(TickEvent::new(delta_time));
+            remaining_duration -= delta_time;
+        }
+    }
+
+    fn sub_mouse_enter(&self) -> bool {
+        self.root_buddy.get_subscriptions().mouse_enter
+    }
 
     fn sub_mouse_move(&self) -> bool {
         self.root_buddy.get_subscriptions().mouse_move
@@ -234,6 +1418,10 @@ impl Application {
     }
 
     pub fn fire_mouse_move_event(&mut self, event: MouseMoveEvent) {
+        let from = self.to_root_point(event.get_from());
+        let to = self.to_root_point(event.get_to());
+        let event = MouseMoveEvent::with_details(event.get_mouse(), from, to, event.get_details());
+
         // Keep the MouseStore up-to-date
         let mut mouse_store = self.mouse_store.borrow_mut();
         match mouse_store.update_mouse_state(event.get_mouse()) {
@@ -254,19 +1442,62 @@ impl Application {
         };
         drop(mouse_store);
 
+        // Moving the mouse too far cancels long presses
+        let max_distance = self.input_settings.drag_start_distance;
+        self.long_press_candidates.retain(|candidate| {
+            candidate.mouse != event.get_mouse()
+                || candidate.point.distance_to(event.get_to()) < max_distance
+        });
+
+        // Any movement restarts the hover timer
+        self.hover_candidates
+            .retain(|candidate| candidate.mouse != event.get_mouse());
+        self.hover_candidates.push(RestingMouse {
+            mouse: event.get_mouse(),
+            point: event.get_to(),
+            time: self.current_time,
+        });
+
+        // The gesture events will be fired after the mouse move events
+        let (maybe_pinch, maybe_rotation) =
+            self.gesture_recognizer
+                .move_finger(event.get_mouse(), event.get_to(), self.current_time);
+
         // Fire the necessary events
         if let Some(render_result) = self.root_buddy.get_last_render_result() {
             // Don't bother doing computations if the root component isn't interested in either event
             if self.sub_mouse_enter() || self.sub_mouse_move() || self.sub_mouse_leave() {
                 let filter_mouse = render_result.filter_mouse_actions;
+
+                // If the root component captured the mouse, it should receive the entire movement
+                let captured = self.root_buddy.is_capturing_mouse(event.get_mouse());
                 if filter_mouse {
                     // Complex case: we need to take the render region into account
-                    match render_result
-                        .drawn_region
-                        .find_line_intersection(event.get_from(), event.get_to())
-                    {
+                    let region = &render_result.drawn_region;
+                    let mut intersection =
+                        region.find_line_intersection(event.get_from(), event.get_to());
+
+                    let margin = self.input_settings.hover_hysteresis / self.ui_scale;
+                    if margin > 0.0 {
+                        let mouse = event.get_mouse();
+                        let was_inside = self.hovering_mouses.contains(&mouse);
+                        let end_is_near = region.is_near(event.get_to(), margin, margin);
+                        intersection =
+                            intersection.with_hysteresis(was_inside, end_is_near, event.get_from());
+
+                        self.hovering_mouses.retain(|hovering| *hovering != mouse);
+                        if intersection.ends_inside() {
+                            self.hovering_mouses.push(mouse);
+                        }
+                    }
+
+                    match intersection {
                         LineIntersection::FullyOutside => {
-                            // Do nothing
+                            // Do nothing, unless the root component captured the mouse
+                            if captured && self.sub_mouse_move() {
+                                self.root_component
+                                    .on_mouse_move(event, &mut self.root_buddy);
+                            }
                         }
                         LineIntersection::FullyInside => {
                             // Simple case: just propagate the event
@@ -283,9 +1514,16 @@ impl Application {
                                 self.root_component
                                     .on_mouse_enter(enter_event, &mut self.root_buddy);
                             }
-                            if self.sub_mouse_move() && event.get_to() != point {
-                                let move_event =
-                                    MouseMoveEvent::new(event.get_mouse(), point, event.get_to());
+                            if captured && self.sub_mouse_move() {
+                                self.root_component
+                                    .on_mouse_move(event, &mut self.root_buddy);
+                            } else if self.sub_mouse_move() && event.get_to() != point {
+                                let move_event = MouseMoveEvent::with_details(
+                                    event.get_mouse(),
+                                    point,
+                                    event.get_to(),
+                                    event.get_details(),
+                                );
                                 self.root_component
                                     .on_mouse_move(move_event, &mut self.root_buddy);
                             }
@@ -293,9 +1531,16 @@ impl Application {
                         LineIntersection::Exits { point } => {
                             // Fire a MouseMoveEvent from `from` to `point`
                             // and a MouseLeaveEvent at `point`
-                            if self.sub_mouse_move() && event.get_from() != point {
-                                let move_event =
-                                    MouseMoveEvent::new(event.get_mouse(), event.get_from(), point);
+                            if captured && self.sub_mouse_move() {
+                                self.root_component
+                                    .on_mouse_move(event, &mut self.root_buddy);
+                            } else if self.sub_mouse_move() && event.get_from() != point {
+                                let move_event = MouseMoveEvent::with_details(
+                                    event.get_mouse(),
+                                    event.get_from(),
+                                    point,
+                                    event.get_details(),
+                                );
                                 self.root_component
                                     .on_mouse_move(move_event, &mut self.root_buddy);
                             }
@@ -310,7 +1555,15 @@ impl Application {
                             // and a MouseMoveEvent from `entrance` to `exit`
                             // and a MouseLeaveEvent at `exit`
                             let enter_event = MouseEnterEvent::new(event.get_mouse(), entrance);
-                            let move_event = MouseMoveEvent::new(event.get_mouse(), entrance, exit);
+                            let move_event = match captured {
+                                true => event,
+                                false => MouseMoveEvent::with_details(
+                                    event.get_mouse(),
+                                    entrance,
+                                    exit,
+                                    event.get_details(),
+                                ),
+                            };
                             let leave_event = MouseLeaveEvent::new(event.get_mouse(), exit);
                             if self.sub_mouse_enter() {
                                 self.root_component
@@ -336,9 +1589,19 @@ impl Application {
                 self.work_after_events();
             }
         }
+
+        if let Some(pinch) = maybe_pinch {
+            self.fire_pinch_event(pinch);
+        }
+        if let Some(rotation) = maybe_rotation {
+            self.fire_rotate_gesture_event(rotation);
+        }
     }
 
     pub fn fire_mouse_enter_event(&mut self, event: MouseEnterEvent) {
+        let entrance_point = self.to_root_point(event.get_entrance_point());
+        let event = MouseEnterEvent::new(event.get_mouse(), entrance_point);
+
         // Keep the MouseStore up-to-date
         let mut mouse_store = self.mouse_store.borrow_mut();
         mouse_store.add_mouse(
@@ -350,35 +1613,59 @@ impl Application {
         );
         drop(mouse_store);
 
+        self.hover_candidates.push(RestingMouse {
+            mouse: event.get_mouse(),
+            point: event.get_entrance_point(),
+            time: self.current_time,
+        });
+
         // Propagate the MouseEnterEvent
         if let Some(render_result) = self.root_buddy.get_last_render_result() {
-            if self.root_buddy.get_subscriptions().mouse_enter {
-                let should_propagate = match render_result.filter_mouse_actions {
-                    true => render_result
-                        .drawn_region
-                        .is_inside(event.get_entrance_point()),
-                    false => true,
-                };
-                if should_propagate {
-                    self.root_component
-                        .on_mouse_enter(event, &mut self.root_buddy);
-                    self.work_after_events();
-                }
+            let should_propagate = match render_result.filter_mouse_actions {
+                true => render_result
+                    .drawn_region
+                    .is_inside(event.get_entrance_point()),
+                false => true,
+            };
+            if should_propagate && self.input_settings.hover_hysteresis > 0.0 {
+                self.hovering_mouses.push(event.get_mouse());
+            }
+            if should_propagate && self.root_buddy.get_subscriptions().mouse_enter {
+                self.root_component
+                    .on_mouse_enter(event, &mut self.root_buddy);
+                self.work_after_events();
             }
         }
     }
 
     pub fn fire_mouse_leave_event(&mut self, event: MouseLeaveEvent) {
+        let exit_point = self.to_root_point(event.get_exit_point());
+        let event = MouseLeaveEvent::new(event.get_mouse(), exit_point);
+
         // Keep the MouseStore up-to-date
         let mut mouse_store = self.mouse_store.borrow_mut();
         mouse_store.remove_mouse(event.get_mouse());
         drop(mouse_store);
 
+        self.long_press_candidates
+            .retain(|candidate| candidate.mouse != event.get_mouse());
+        self.hover_candidates
+            .retain(|candidate| candidate.mouse != event.get_mouse());
+        self.gesture_recognizer
+            .cancel(event.get_mouse(), self.current_time);
+
+        // When the hover hysteresis is enabled, the exit point could be within the margin
+        let was_hovering = self.hovering_mouses.contains(&event.get_mouse());
+        self.hovering_mouses
+            .retain(|hovering| *hovering != event.get_mouse());
+
         // Propagate the MouseLeaveEvent
         if let Some(render_result) = self.root_buddy.get_last_render_result() {
             if self.root_buddy.get_subscriptions().mouse_leave {
                 let should_propagate = match render_result.filter_mouse_actions {
-                    true => render_result.drawn_region.is_inside(event.get_exit_point()),
+                    true => {
+                        was_hovering || render_result.drawn_region.is_inside(event.get_exit_point())
+                    }
                     false => true,
                 };
                 if should_propagate {
@@ -394,6 +1681,9 @@ impl Application {
 impl Drop for Application {
     fn drop(&mut self) {
         self.root_component.on_detach();
+        if let Some((background, _)) = &mut self.background {
+            background.on_detach();
+        }
     }
 }
 
@@ -404,6 +1694,7 @@ mod tests {
 
     use std::cell::{Cell, RefCell};
     use std::rc::Rc;
+    use std::time::Duration;
 
     struct CountingComponent {
         counter: Rc<Cell<u32>>,
@@ -493,6 +1784,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_render_after_context_loss() {
+        let counter = Rc::new(Cell::new(0));
+        let component = CountingComponent {
+            counter: Rc::clone(&counter),
+        };
+        let mut application = Application::new(Box::new(component));
+        let mut renderer = test_renderer(RenderRegion::with_size(0, 0, 150, 100));
+
+        application.render(&renderer, false);
+        assert_eq!(4, counter.get());
+        application.render(&renderer, false);
+        assert_eq!(4, counter.get());
+
+        // Everything was lost, so everything needs to be rendered again (but only once)
+        renderer.simulate_context_loss();
+        assert!(application.render(&renderer, false));
+        assert_eq!(7, counter.get());
+        assert!(!application.render(&renderer, false));
+        assert_eq!(7, counter.get());
+    }
+
     #[test]
     fn test_click_and_render() {
         let counter = Rc::new(Cell::new(0));
@@ -757,6 +2070,56 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_hover_hysteresis() {
+        let mouse_enter_log = Rc::new(RefCell::new(Vec::new()));
+        let mouse_leave_log = Rc::new(RefCell::new(Vec::new()));
+        let component = ConditionalMouseFilterComponent {
+            should_filter_mouse_actions: Rc::new(Cell::new(true)),
+            mouse_move_log: Rc::new(RefCell::new(Vec::new())),
+            mouse_enter_log: Rc::clone(&mouse_enter_log),
+            mouse_leave_log: Rc::clone(&mouse_leave_log),
+        };
+
+        let mut application = Application::new(Box::new(component));
+        application.set_input_settings(InputSettings {
+            hover_hysteresis: 0.05,
+            ..InputSettings::default()
+        });
+        application.render(&test_renderer(RenderRegion::between(0, 0, 100, 100)), false);
+
+        let mouse = Mouse::new(0);
+        let mut position = Point::new(0.5, 0.2);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, position));
+        let mut move_to = |x: f32, y: f32| {
+            let destination = Point::new(x, y);
+            application.fire_mouse_move_event(MouseMoveEvent::new(mouse, position, destination));
+            position = destination;
+        };
+        let check_counts = |num_enters: usize, num_leaves: usize| {
+            assert_eq!(num_enters, mouse_enter_log.borrow().len());
+            assert_eq!(num_leaves, mouse_leave_log.borrow().len());
+        };
+        check_counts(1, 0);
+
+        // Moving within the margin shouldn't fire enter or leave events
+        move_to(0.82, 0.2);
+        move_to(0.7, 0.2);
+        check_counts(1, 0);
+
+        move_to(0.9, 0.2);
+        check_counts(1, 1);
+        assert_eq!(Point::new(0.8, 0.2), mouse_leave_log.borrow()[0].get_exit_point());
+        move_to(0.7, 0.2);
+        check_counts(2, 1);
+
+        // When the mouse leaves the window within the margin, the component should be notified
+        move_to(0.7, 0.52);
+        check_counts(2, 1);
+        application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, position));
+        check_counts(2, 2);
+    }
+
     #[test]
     fn test_mouse_move_subscriptions() {
         let should_filter_mouse_actions = Rc::new(Cell::new(false));
@@ -1548,4 +2911,1023 @@ mod tests {
         // And component 1 shouldn't have received any more events
         assert_eq!(4, counter1.get());
     }
+
+    #[test]
+    fn test_menu_change_policy() {
+        struct NamedComponent {
+            name: &'static str,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for NamedComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                self.log.borrow_mut().push(format!("attach {}", self.name));
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                for (name, priority) in &[("low", 1), ("high", 3), ("also high", 3)] {
+                    let log = Rc::clone(&self.log);
+                    let reject_log = Rc::clone(&self.log);
+                    let name = *name;
+                    buddy.request_menu_change(
+                        MenuChangeRequest::new(Box::new(move |_old_menu: Box<dyn Component>| {
+                            Box::new(NamedComponent { name, log })
+                        }))
+                        .with_priority(*priority)
+                        .with_rejection_callback(move || {
+                            reject_log.borrow_mut().push(format!("reject {}", name))
+                        }),
+                    );
+                }
+            }
+        }
+
+        let click_event =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.5, 0.5), MouseButton::primary());
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+
+        let check_policy = |policy: MenuChangePolicy, expected: Vec<&str>| {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut application = Application::new(Box::new(NamedComponent {
+                name: "initial",
+                log: Rc::clone(&log),
+            }));
+            application.set_menu_change_policy(policy);
+            assert_eq!(policy, application.get_menu_change_policy());
+            application.render(&renderer, false);
+            log.borrow_mut().clear();
+
+            application.fire_mouse_click_event(click_event);
+            assert_eq!(expected, *log.borrow());
+        };
+
+        check_policy(
+            MenuChangePolicy::FirstWins,
+            vec!["reject high", "reject also high", "attach low"],
+        );
+        check_policy(
+            MenuChangePolicy::LastWins,
+            vec!["reject low", "reject high", "attach also high"],
+        );
+        check_policy(
+            MenuChangePolicy::HighestPriority,
+            vec!["reject low", "reject also high", "attach high"],
+        );
+    }
+
+    #[test]
+    fn test_advance_time() {
+        struct TickComponent {
+            delta_times: Rc<RefCell<Vec<Duration>>>,
+        }
+
+        impl Component for TickComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_tick();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_tick(&mut self, event: TickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.delta_times.borrow_mut().push(event.get_delta_time());
+            }
+        }
+
+        let delta_times = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(TickComponent {
+            delta_times: Rc::clone(&delta_times),
+        }));
+
+        // Advancing 0 time shouldn't fire any tick events
+        application.advance_time(Duration::from_secs(0));
+        assert!(delta_times.borrow().is_empty());
+
+        let frame_duration = Duration::from_secs(1) / 60;
+        application.advance_time(frame_duration * 2 + Duration::from_millis(5));
+        assert_eq!(
+            vec![frame_duration, frame_duration, Duration::from_millis(5)],
+            *delta_times.borrow()
+        );
+
+        delta_times.borrow_mut().clear();
+        application.advance_time(frame_duration * 100);
+        assert_eq!(vec![frame_duration; 100], *delta_times.borrow());
+    }
+
+    #[test]
+    fn test_fixed_ticks() {
+        struct SimulationComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for SimulationComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_tick();
+            }
+
+            fn render(
+                &mut self,
+                renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let alpha = renderer.get_interpolation_alpha();
+                self.log.borrow_mut().push(format!("render {:.2}", alpha));
+                entire_render_result()
+            }
+
+            fn on_tick(&mut self, event: TickEvent, _buddy: &mut dyn ComponentBuddy) {
+                let millis = event.get_delta_time().as_millis();
+                self.log.borrow_mut().push(format!("tick {}", millis));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(SimulationComponent {
+            log: Rc::clone(&log),
+        }));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+
+        // Without fixed ticks, each frame has exactly 1 tick and the alpha is always 1
+        assert_eq!(None, application.get_fixed_tick_duration());
+        application.frame(&renderer, Duration::from_millis(7), true);
+        assert_eq!(vec!["tick 7", "render 1.00"], *log.borrow());
+        log.borrow_mut().clear();
+
+        application.set_fixed_tick_duration(Some(Duration::from_millis(20)));
+        assert_eq!(Some(Duration::from_millis(20)), application.get_fixed_tick_duration());
+
+        application.frame(&renderer, Duration::from_millis(15), true);
+        assert_eq!(vec!["render 0.75"], *log.borrow());
+        log.borrow_mut().clear();
+
+        application.frame(&renderer, Duration::from_millis(50), true);
+        assert_eq!(vec!["tick 20", "tick 20", "tick 20", "render 0.25"], *log.borrow());
+        log.borrow_mut().clear();
+
+        application.set_fixed_tick_duration(None);
+        assert_eq!(1.0, application.get_interpolation_alpha());
+        application.frame(&renderer, Duration::from_millis(3), true);
+        assert_eq!(vec!["tick 3", "render 1.00"], *log.borrow());
+    }
+
+    #[test]
+    fn test_frame_ordering() {
+        struct FrameComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for FrameComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                buddy.subscribe_char_type().unwrap();
+                buddy.subscribe_tick();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.log.borrow_mut().push("render".to_string());
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("click {}", event.get_point().get_x()));
+                buddy.request_render();
+            }
+
+            fn on_char_type(&mut self, event: &CharTypeEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("type {}", event.get_text()));
+            }
+
+            fn on_tick(&mut self, _event: TickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("tick".to_string());
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(FrameComponent {
+            log: Rc::clone(&log),
+        }));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+        let frame_time = Duration::from_millis(16);
+
+        let click = |x: f32| {
+            InputEvent::MouseClick(MouseClickEvent::new(
+                Mouse::new(0), Point::new(x, 0.5), MouseButton::primary()
+            ))
+        };
+
+        // Mouse clicks are only fired after the first render
+        application.render(&renderer, false);
+        log.borrow_mut().clear();
+
+        // Queued events should only be fired during the next frame
+        application.queue_event(click(0.25));
+        application.queue_event(InputEvent::CharType(CharTypeEvent::new("a".to_string())));
+        application.queue_event(click(0.75));
+        assert!(log.borrow().is_empty());
+
+        assert!(application.frame(&renderer, frame_time, false));
+        assert_eq!(
+            vec!["click 0.25", "type a", "click 0.75", "tick", "render"],
+            *log.borrow()
+        );
+
+        // Without input, the frame should only tick
+        log.borrow_mut().clear();
+        assert!(!application.frame(&renderer, frame_time, false));
+        assert_eq!(vec!["tick"], *log.borrow());
+
+        // Events can still be fired immediately
+        log.borrow_mut().clear();
+        application.fire_event(click(0.5));
+        assert_eq!(vec!["click 0.5"], *log.borrow());
+    }
+
+    #[test]
+    fn test_chained_menu_changes() {
+        struct ChainComponent {
+            remaining_changes: u32,
+            log: Rc<RefCell<Vec<u32>>>,
+        }
+
+        impl Component for ChainComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(self.remaining_changes);
+                if self.remaining_changes > 0 {
+                    let remaining_changes = self.remaining_changes - 1;
+                    let log = Rc::clone(&self.log);
+                    buddy.change_menu(Box::new(move |_old_menu: Box<dyn Component>| {
+                        Box::new(ChainComponent { remaining_changes, log })
+                    }));
+                }
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+        }
+
+        // All menu changes should be processed before the constructor returns
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let _application = Application::new(Box::new(ChainComponent {
+            remaining_changes: 3,
+            log: Rc::clone(&log),
+        }));
+        assert_eq!(vec![3, 2, 1, 0], *log.borrow());
+    }
+
+    struct TimingComponent {
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for TimingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_double_click();
+            buddy.subscribe_mouse_long_press();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_double_click(
+            &mut self,
+            event: MouseDoubleClickEvent,
+            _buddy: &mut dyn ComponentBuddy,
+        ) {
+            self.log
+                .borrow_mut()
+                .push(format!("double click {}", event.get_point().get_x()));
+        }
+
+        fn on_mouse_long_press(
+            &mut self,
+            event: MouseLongPressEvent,
+            _buddy: &mut dyn ComponentBuddy,
+        ) {
+            self.log
+                .borrow_mut()
+                .push(format!("long press {}", event.get_point().get_x()));
+        }
+    }
+
+    #[test]
+    fn test_double_click() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(TimingComponent {
+            log: Rc::clone(&log),
+        }));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let click = |x: f32| MouseClickEvent::new(mouse, Point::new(x, 0.5), button);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.5, 0.5)));
+
+        // 2 quick clicks at (almost) the same position
+        application.fire_mouse_click_event(click(0.5));
+        application.advance_time(Duration::from_millis(100));
+        application.fire_mouse_click_event(click(0.51));
+        assert_eq!(vec!["double click 0.51"], *log.borrow());
+
+        // The third click shouldn't form another double click with the second click
+        application.fire_mouse_click_event(click(0.51));
+        assert_eq!(1, log.borrow().len());
+
+        // 2 slow clicks
+        application.advance_time(Duration::from_secs(1));
+        application.fire_mouse_click_event(click(0.5));
+        assert_eq!(1, log.borrow().len());
+
+        // 2 quick clicks at different positions
+        application.fire_mouse_click_event(click(0.9));
+        assert_eq!(1, log.borrow().len());
+
+        // The double click time should be configurable
+        application.set_input_settings(InputSettings {
+            double_click_time: Duration::from_secs(2),
+            ..InputSettings::default()
+        });
+        application.advance_time(Duration::from_secs(1));
+        application.fire_mouse_click_event(click(0.9));
+        assert_eq!(vec!["double click 0.51", "double click 0.9"], *log.borrow());
+    }
+
+    #[test]
+    fn test_long_press() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(TimingComponent {
+            log: Rc::clone(&log),
+        }));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let press = |x: f32| MousePressEvent::new(mouse, Point::new(x, 0.5), button);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.5, 0.5)));
+
+        // Releasing the button too early should prevent the long press
+        application.fire_mouse_press_event(press(0.5));
+        application.advance_time(Duration::from_millis(400));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            Point::new(0.5, 0.5),
+            button,
+        ));
+        application.advance_time(Duration::from_millis(400));
+        assert!(log.borrow().is_empty());
+
+        // Moving the mouse too far should prevent the long press
+        application.fire_mouse_press_event(press(0.5));
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse,
+            Point::new(0.5, 0.5),
+            Point::new(0.8, 0.5),
+        ));
+        application.advance_time(Duration::from_millis(600));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            Point::new(0.8, 0.5),
+            button,
+        ));
+        assert!(log.borrow().is_empty());
+
+        // But small movements are fine
+        application.fire_mouse_press_event(press(0.8));
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse,
+            Point::new(0.8, 0.5),
+            Point::new(0.82, 0.5),
+        ));
+        application.advance_time(Duration::from_millis(450));
+        assert!(log.borrow().is_empty());
+        application.advance_time(Duration::from_millis(100));
+        assert_eq!(vec!["long press 0.8"], *log.borrow());
+
+        // The long press should only be fired once
+        application.advance_time(Duration::from_secs(1));
+        assert_eq!(1, log.borrow().len());
+    }
+
+    #[test]
+    fn test_context_and_middle_clicks() {
+        struct ContextComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for ContextComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_context_click();
+                buddy.subscribe_mouse_middle_click();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_context_click(
+                &mut self,
+                event: MouseContextClickEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                let point = event.get_point();
+                self.log.borrow_mut().push(format!(
+                    "context {:?} {} {}",
+                    event.get_source(),
+                    point.get_x(),
+                    point.get_y()
+                ));
+            }
+
+            fn on_mouse_middle_click(
+                &mut self,
+                event: MouseMiddleClickEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log
+                    .borrow_mut()
+                    .push(format!("middle {}", event.get_point().get_x()));
+            }
+        }
+
+        // Put the component in a menu to check that the menu propagates the events
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(ContextComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let point = Point::new(0.75, 0.5);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+        let click = |button: MouseButton| MouseClickEvent::new(mouse, point, button);
+        application.fire_mouse_click_event(click(MouseButton::primary()));
+        application.fire_mouse_click_event(click(MouseButton::secondary()));
+        application.fire_mouse_click_event(click(MouseButton::middle()));
+        application.fire_mouse_click_event(click(MouseButton::new(3)));
+        assert_eq!(
+            vec!["context SecondaryButton 0.5 0.5", "middle 0.5"],
+            *log.borrow()
+        );
+
+        // Long presses should only cause context clicks when the input settings say so
+        let press = MousePressEvent::new(mouse, point, MouseButton::primary());
+        application.fire_mouse_press_event(press);
+        application.advance_time(Duration::from_secs(1));
+        assert_eq!(2, log.borrow().len());
+
+        application.set_input_settings(InputSettings {
+            long_press_context_click: true,
+            ..InputSettings::default()
+        });
+        application.fire_mouse_press_event(press);
+        application.advance_time(Duration::from_secs(1));
+        assert_eq!("context LongPress 0.5 0.5", log.borrow()[2]);
+        assert_eq!(3, log.borrow().len());
+    }
+
+    #[test]
+    fn test_hover() {
+        struct HoverComponent {
+            log: Rc<RefCell<Vec<Point>>>,
+        }
+
+        impl Component for HoverComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_hover();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_hover(&mut self, event: MouseHoverEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(event.get_point());
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(HoverComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let move_to = |application: &mut Application, from: Point, to: Point| {
+            application.fire_mouse_move_event(MouseMoveEvent::new(mouse, from, to));
+        };
+
+        // Passing over the component shouldn't trigger a hover
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.75, 0.5)));
+        application.advance_time(Duration::from_millis(300));
+        move_to(&mut application, Point::new(0.75, 0.5), Point::new(0.25, 0.5));
+        application.advance_time(Duration::from_secs(1));
+        assert!(log.borrow().is_empty());
+
+        // But resting on it should, exactly once
+        move_to(&mut application, Point::new(0.25, 0.5), Point::new(0.75, 0.25));
+        application.advance_time(Duration::from_millis(400));
+        assert!(log.borrow().is_empty());
+        application.advance_time(Duration::from_millis(200));
+        assert_eq!(vec![Point::new(0.5, 0.25)], *log.borrow());
+        application.advance_time(Duration::from_secs(1));
+        assert_eq!(1, log.borrow().len());
+
+        // Pressing a button should cancel the hover
+        move_to(&mut application, Point::new(0.75, 0.25), Point::new(0.75, 0.5));
+        let press = MousePressEvent::new(mouse, Point::new(0.75, 0.5), MouseButton::primary());
+        application.fire_mouse_press_event(press);
+        application.advance_time(Duration::from_secs(1));
+        assert_eq!(1, log.borrow().len());
+    }
+
+    #[test]
+    fn test_render_scale() {
+        let mut application = Application::new(Box::new(CountingComponent {
+            counter: Rc::new(Cell::new(0)),
+        }));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+        assert_eq!(1.0, application.get_render_scale());
+        assert_eq!((800, 600), application.get_render_size(800, 600));
+        assert!(application.render(&renderer, false));
+        assert!(!application.render(&renderer, false));
+
+        // Changing the render scale should cause a full render
+        application.set_render_scale(0.5);
+        assert_eq!(0.5, application.get_render_scale());
+        assert_eq!((400, 300), application.get_render_size(800, 600));
+        assert_eq!((1, 1), application.get_render_size(1, 1));
+        assert!(application.render(&renderer, false));
+        assert!(!application.render(&renderer, false));
+
+        // Setting the same render scale again shouldn't
+        application.set_render_scale(0.5);
+        assert!(!application.render(&renderer, false));
+    }
+
+    #[test]
+    fn test_ui_scale() {
+        struct ScaleComponent {
+            viewports: Rc<RefCell<Vec<RenderRegion>>>,
+            clicks: Rc<RefCell<Vec<Point>>>,
+        }
+
+        impl Component for ScaleComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn render(
+                &mut self,
+                renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.viewports.borrow_mut().push(renderer.get_viewport());
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.clicks.borrow_mut().push(event.get_point());
+            }
+        }
+
+        let viewports = Rc::new(RefCell::new(Vec::new()));
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(ScaleComponent {
+            viewports: Rc::clone(&viewports),
+            clicks: Rc::clone(&clicks),
+        }));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+        let click = |x: f32, y: f32| {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        };
+
+        assert_eq!(1.0, application.get_ui_scale());
+        assert!(application.render(&renderer, false));
+        application.fire_mouse_click_event(click(0.5, 0.5));
+
+        // Changing the UI scale should cause a full render
+        application.set_ui_scale(2.0);
+        assert_eq!(2.0, application.get_ui_scale());
+        assert!(application.render(&renderer, false));
+        assert!(!application.render(&renderer, false));
+        application.fire_mouse_click_event(click(0.5, 0.5));
+
+        application.set_ui_scale(0.5);
+        assert!(application.render(&renderer, false));
+        application.fire_mouse_click_event(click(0.25, 0.75));
+
+        // The root component should be anchored at the top-left corner of the window
+        assert_eq!(
+            vec![
+                RenderRegion::with_size(0, 0, 10, 10),
+                RenderRegion::with_size(0, 0, 20, 20),
+                RenderRegion::with_size(0, 5, 5, 5),
+            ],
+            *viewports.borrow()
+        );
+        assert_eq!(
+            vec![
+                Point::new(0.5, 0.5),
+                Point::new(0.25, 0.75),
+                Point::new(0.5, 0.5),
+            ],
+            *clicks.borrow()
+        );
+    }
+
+    #[test]
+    fn test_dump_tree() {
+        let black = || Box::new(SimpleFlatColorComponent::new(Color::rgb(0, 0, 0)));
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(black(), ComponentDomain::between(0.0, 0.0, 0.5, 0.5));
+        let mut flex_menu = FlexMenu::new(FlexDirection::Row, None);
+        flex_menu.add_component(Box::new(inner_menu), FlexSize::Weight(1.0));
+        flex_menu.add_component(black(), FlexSize::Weight(1.0));
+
+        let mut application = Application::new(Box::new(flex_menu));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 10, 10)), false);
+
+        let tree = application.dump_tree();
+        assert!(tree.get_type_name().ends_with("FlexMenu"));
+        assert_eq!(2, tree.get_children().len());
+
+        let inner_tree = &tree.get_children()[0];
+        assert!(inner_tree.get_type_name().ends_with("SimpleFlatMenu"));
+        assert_eq!(0.5, inner_tree.get_domain().unwrap().get_max_x());
+        // The inner menu doesn't have a background, so it only drew its child
+        assert_eq!(Some([0.0, 0.0, 0.5, 0.5]), inner_tree.get_drawn_bounds());
+        assert_eq!(1, inner_tree.get_children().len());
+        assert!(inner_tree.get_children()[0].get_type_name().ends_with("SimpleFlatColorComponent"));
+        assert_eq!(Some(&[][..]), inner_tree.get_children()[0].get_subscriptions());
+
+        let text = tree.to_string();
+        assert_eq!(4, text.lines().count());
+        assert!(text.lines().nth(2).unwrap().starts_with("    knukki::components::color::"));
+        assert!(tree.to_json().starts_with("{\"type\":\"knukki::components::menu::flex::"));
+    }
+
+    #[test]
+    fn test_background() {
+        struct LogComponent {
+            name: &'static str,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for LogComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_tick();
+            }
+
+            fn render(
+                &mut self,
+                renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                force: bool,
+            ) -> RenderResult {
+                let width = renderer.get_viewport().get_width();
+                self.log.borrow_mut().push(format!("{} {} {}", self.name, width, force));
+                entire_render_result()
+            }
+
+            fn on_tick(&mut self, _event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+                if self.name == "background" {
+                    buddy.request_render();
+                }
+            }
+
+            fn on_detach(&mut self) {
+                self.log.borrow_mut().push(format!("detach {}", self.name));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let new_component = |name| Box::new(LogComponent { name, log: Rc::clone(&log) });
+        let mut application = Application::new(new_component("root"));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+
+        assert_eq!(Color::rgb(0, 0, 0), application.get_background_color());
+        assert!(application.render(&renderer, false));
+        assert!(!application.render(&renderer, false));
+
+        // Changing the background color should cause a full render
+        application.set_background_color(Color::rgb(0, 100, 200));
+        assert_eq!(Color::rgb(0, 100, 200), application.get_background_color());
+        assert!(application.render(&renderer, false));
+        assert_eq!(vec!["root 10 false", "root 10 true"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // The background component should be rendered behind the root, and not be affected by the
+        // UI scale
+        application.set_ui_scale(0.5);
+        application.set_background_component(Some(new_component("background")));
+        assert!(application.render(&renderer, false));
+        assert!(!application.render(&renderer, false));
+        assert_eq!(vec!["background 10 true", "root 5 true"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // When the background requests a render, everything should be redrawn
+        application.fire_tick_event(TickEvent::new(Duration::from_millis(10)));
+        assert!(application.render(&renderer, false));
+        assert_eq!(vec!["background 10 true", "root 5 true"], *log.borrow());
+        log.borrow_mut().clear();
+
+        application.set_background_component(None);
+        assert_eq!(vec!["detach background"], *log.borrow());
+        assert!(application.render(&renderer, false));
+        assert_eq!(vec!["detach background", "root 5 true"], *log.borrow());
+    }
+
+    #[test]
+    fn test_invalidation_debug() {
+        struct QuadrantComponent {
+            renders: Rc<RefCell<Vec<(bool, RenderRegion)>>>,
+            shader: FragmentOnlyShader,
+        }
+
+        impl Component for QuadrantComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn render(
+                &mut self,
+                renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                force: bool,
+            ) -> RenderResult {
+                self.renders.borrow_mut().push((force, renderer.get_scissor()));
+                if force {
+                    renderer.clear(Color::rgb(0, 0, 0));
+                }
+                renderer.apply_fragment_shader(
+                    0.5, 0.0, 1.0, 0.5, &self.shader, FragmentOnlyDrawParameters::default()
+                );
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                buddy.request_render();
+            }
+        }
+
+        let renders = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(QuadrantComponent {
+            renders: Rc::clone(&renders),
+            shader: Renderer::create_clear_shader(),
+        }));
+        application.set_invalidation_debug(Some(InvalidationDebug {
+            num_frames: 2,
+            ..InvalidationDebug::default()
+        }));
+
+        let window = RenderRegion::with_size(0, 0, 10, 10);
+        let quadrant = window.conservative_child_region(0.5, 0.0, 1.0, 0.5).unwrap();
+        let renderer = test_renderer(window);
+        let flash_regions = |application: &Application| -> Vec<RenderRegion> {
+            application.invalidation_flashes.iter().map(|flash| flash.region).collect()
+        };
+
+        assert!(application.render(&renderer, false));
+        assert_eq!(vec![quadrant], flash_regions(&application));
+
+        // The overlay should stay for 2 frames, after which the quadrant should be repainted
+        assert!(!application.render(&renderer, false));
+        assert_eq!(1, renders.borrow().len());
+        assert!(application.render(&renderer, false));
+        assert_eq!(vec![(false, window), (true, quadrant)], *renders.borrow());
+        assert!(flash_regions(&application).is_empty());
+        assert!(!application.render(&renderer, false));
+
+        // Disabling the invalidation debug mode should remove all overlays
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0), Point::new(0.5, 0.5), MouseButton::primary()
+        ));
+        assert!(application.render(&renderer, false));
+        assert_eq!(vec![quadrant], flash_regions(&application));
+        application.set_invalidation_debug(None);
+        assert!(application.render(&renderer, false));
+        assert!(flash_regions(&application).is_empty());
+        assert_eq!((true, window), renders.borrow()[3]);
+    }
+
+    #[test]
+    fn test_set_theme() {
+        struct ThemeComponent {
+            primary_colors: Rc<RefCell<Vec<Color>>>,
+        }
+
+        impl Component for ThemeComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let primary_color = buddy.get_theme().palette.get_color(ColorRole::Primary);
+                self.primary_colors.borrow_mut().push(primary_color);
+                entire_render_result()
+            }
+        }
+
+        let primary_colors = Rc::new(RefCell::new(Vec::new()));
+        let label = ui().following_theme().label("Hello");
+        let label_controller = label.get_controller();
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(ThemeComponent { primary_colors: Rc::clone(&primary_colors) }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(Box::new(label), ComponentDomain::between(0.5, 0.0, 1.0, 1.0));
+
+        let mut application = Application::new(Box::new(menu));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 50));
+        assert!(application.render(&renderer, false));
+
+        let light = Palette::light();
+        assert_eq!(vec![light.get_color(ColorRole::Primary)], *primary_colors.borrow());
+        let light_text_color = TextLabelStyle::from_palette(&light).text_color;
+        assert_eq!(light_text_color, label_controller.get_style().text_color);
+
+        // Changing the theme should cause a full render, during which the components should see
+        // the new theme
+        application.set_theme(Theme::dark());
+        assert_eq!(Theme::dark(), *application.get_theme());
+        assert!(application.render(&renderer, false));
+
+        let dark = Palette::dark();
+        assert_eq!(Some(&dark.get_color(ColorRole::Primary)), primary_colors.borrow().last());
+        let dark_text_color = TextLabelStyle::from_palette(&dark).text_color;
+        assert_ne!(light_text_color, dark_text_color);
+        assert_eq!(dark_text_color, label_controller.get_style().text_color);
+    }
+
+    #[test]
+    fn test_theme_change_event() {
+        struct ThemeChangeComponent {
+            changes: Rc<RefCell<Vec<(Color, Color)>>>,
+        }
+
+        impl Component for ThemeChangeComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_theme_change();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_theme_change(
+                &mut self,
+                event: &ThemeChangeEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                let primary = |theme: &Theme| theme.palette.get_color(ColorRole::Primary);
+                assert_eq!(primary(event.get_new_theme()), primary(&buddy.get_theme()));
+                self.changes.borrow_mut().push(
+                    (primary(event.get_old_theme()), primary(event.get_new_theme()))
+                );
+                buddy.unsubscribe_theme_change();
+            }
+        }
+
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(ThemeChangeComponent { changes: Rc::clone(&changes) }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        let mut menu = SimpleFlatMenu::new(None);
+        let label = ui().label("Hello");
+        menu.add_component(Box::new(label), ComponentDomain::between(0.0, 0.0, 0.5, 1.0));
+        menu.add_component(Box::new(inner_menu), ComponentDomain::between(0.5, 0.0, 1.0, 1.0));
+
+        let mut application = Application::new(Box::new(menu));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 50));
+        application.render(&renderer, false);
+
+        // The event should reach the nested component, even before the next render
+        let light = Palette::light().get_color(ColorRole::Primary);
+        let dark = Palette::dark().get_color(ColorRole::Primary);
+        application.set_theme(Theme::dark());
+        assert_eq!(vec![(light, dark)], *changes.borrow());
+
+        // The component unsubscribed, so it shouldn't receive the next change
+        application.set_theme(Theme::light());
+        assert_eq!(1, changes.borrow().len());
+        assert!(!application.root_buddy.get_subscriptions().theme_change);
+    }
+
+    #[test]
+    fn test_frame_timing() {
+        struct TimingComponent {
+            timings: Rc<RefCell<Vec<FrameTiming>>>,
+        }
+
+        impl Component for TimingComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_tick();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_tick(&mut self, _event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+                self.timings.borrow_mut().push(buddy.get_frame_timing());
+            }
+        }
+
+        let timings = Rc::new(RefCell::new(Vec::new()));
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(TimingComponent { timings: Rc::clone(&timings) }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(Box::new(inner_menu), ComponentDomain::between(0.5, 0.0, 1.0, 1.0));
+
+        let mut application = Application::new(Box::new(menu));
+        application.update(Duration::from_millis(16));
+        assert_eq!(FrameTiming::default(), timings.borrow()[0]);
+
+        application.set_refresh_rate(Some(60.0));
+        application.set_frame_time(Duration::from_millis(1000));
+        application.update(Duration::from_millis(16));
+        application.set_frame_time(Duration::from_millis(1050));
+        application.update(Duration::from_millis(50));
+
+        let timing = timings.borrow()[2];
+        assert_eq!(application.get_frame_timing(), timing);
+        assert_eq!(Some(60.0), timing.get_refresh_rate());
+        assert_eq!(Some(Duration::from_millis(1050)), timing.get_frame_time());
+        assert_eq!(Some(Duration::from_millis(50)), timing.get_frame_interval());
+        assert_eq!(Some(2), timing.get_dropped_frames());
+    }
 }
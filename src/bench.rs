@@ -0,0 +1,173 @@
+//! Scenarios for measuring the performance of the hot paths of this crate, like the hit-testing
+//! of menus and the packing of texture atlases. Each scenario is constructed once (outside the
+//! measurement) and can then be `run` any number of times, which makes them easy to use with
+//! benchmark harnesses like criterion:
+//!
+//! ```ignore
+//! let mut storm = MouseMoveStorm::new(100, 1000);
+//! c.bench_function("mouse move storm", |b| b.iter(|| storm.run()));
+//! ```
+//!
+//! The scenarios are deterministic: they don't use the wall clock or real randomness, so the
+//! results of different runs can be compared fairly.
+use crate::*;
+use crate::random::DeterministicRandom;
+
+#[cfg(not(feature = "golem_rendering"))]
+struct StormComponent {}
+
+#[cfg(not(feature = "golem_rendering"))]
+impl Component for StormComponent {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_mouse_enter();
+        buddy.subscribe_mouse_leave();
+    }
+
+    fn render(
+        &mut self,
+        _renderer: &Renderer,
+        _buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(OvalDrawnRegion::new(Point::new(0.5, 0.5), 0.5, 0.5)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_move(&mut self, _event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {}
+
+    fn on_mouse_enter(&mut self, _event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {}
+
+    fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {}
+}
+
+/// A `SimpleFlatMenu` with a grid of child components (that are interested in mouse movement)
+/// that receives a storm of `MouseMoveEvent`s along a random path.
+///
+/// This scenario is only available without the `golem_rendering` feature, because the menu must
+/// be rendered (with a dummy `Renderer`) before it can receive mouse events.
+#[cfg(not(feature = "golem_rendering"))]
+pub struct MouseMoveStorm {
+    application: Application,
+    path: Vec<Point>,
+}
+
+#[cfg(not(feature = "golem_rendering"))]
+impl MouseMoveStorm {
+    /// Constructs a menu with (approximately) *num_children* child components and a random path
+    /// of *num_moves* mouse movements
+    pub fn new(num_children: usize, num_moves: usize) -> Self {
+        let grid_size = (num_children as f32).sqrt().ceil().max(1.0) as usize;
+        let cell_size = 1.0 / grid_size as f32;
+
+        let mut menu = SimpleFlatMenu::new(None);
+        for index in 0..num_children {
+            let min_x = (index % grid_size) as f32 * cell_size;
+            let min_y = (index / grid_size) as f32 * cell_size;
+            menu.add_component(
+                Box::new(StormComponent {}),
+                ComponentDomain::with_size(min_x, min_y, cell_size, cell_size),
+            );
+        }
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 1000, 1000)), true);
+
+        let mut random = DeterministicRandom::new(num_children as u64);
+        let mut path = Vec::with_capacity(num_moves + 1);
+        for _ in 0..=num_moves {
+            path.push(random.next_point());
+        }
+
+        Self { application, path }
+    }
+
+    /// Lets the mouse enter the menu, fires all mouse movements of the path, and lets the mouse
+    /// leave the menu again
+    pub fn run(&mut self) {
+        let mouse = Mouse::new(0);
+        self.application
+            .fire_mouse_enter_event(MouseEnterEvent::new(mouse, self.path[0]));
+        for index in 1..self.path.len() {
+            self.application.fire_mouse_move_event(MouseMoveEvent::new(
+                mouse,
+                self.path[index - 1],
+                self.path[index],
+            ));
+        }
+        self.application
+            .fire_mouse_leave_event(MouseLeaveEvent::new(mouse, self.path[self.path.len() - 1]));
+    }
+}
+
+/// Packs a large number of small, glyph-like textures onto an empty `TextureAtlas`
+pub struct GlyphPacking {
+    atlas_width: u32,
+    atlas_height: u32,
+    glyphs: Vec<Texture>,
+}
+
+impl GlyphPacking {
+    /// Prepares *num_glyphs* textures with random sizes between *min_glyph_size* (inclusive) and
+    /// *max_glyph_size* (exclusive) that will be packed onto atlases of the given size
+    pub fn new(
+        num_glyphs: usize,
+        min_glyph_size: u32,
+        max_glyph_size: u32,
+        atlas_width: u32,
+        atlas_height: u32,
+    ) -> Self {
+        let mut random = DeterministicRandom::new(num_glyphs as u64);
+        let glyphs = (0..num_glyphs)
+            .map(|_| {
+                Texture::new(
+                    random.next_range(min_glyph_size, max_glyph_size),
+                    random.next_range(min_glyph_size, max_glyph_size),
+                    Color::rgb(0, 0, 0),
+                )
+            })
+            .collect();
+
+        Self {
+            atlas_width,
+            atlas_height,
+            glyphs,
+        }
+    }
+
+    /// Packs all glyphs onto a new atlas and returns the number of glyphs that could be placed
+    pub fn run(&self) -> usize {
+        let mut atlas = TextureAtlas::new(self.atlas_width, self.atlas_height);
+        let glyph_references: Vec<&Texture> = self.glyphs.iter().collect();
+        let result = atlas.add_textures(&glyph_references, false);
+        result
+            .placements
+            .iter()
+            .filter(|placement| placement.get_position().is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    #[cfg(not(feature = "golem_rendering"))]
+    fn test_mouse_move_storm() {
+        let mut storm = MouseMoveStorm::new(10, 50);
+        storm.run();
+        // Running it again should be possible as well
+        storm.run();
+    }
+
+    #[test]
+    fn test_glyph_packing() {
+        let packing = GlyphPacking::new(200, 8, 16, 512, 512);
+        assert_eq!(200, packing.run());
+        assert_eq!(200, packing.run());
+    }
+}
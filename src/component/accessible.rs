@@ -0,0 +1,38 @@
+/// A textual alternative for a (typically graphical) component, which can be set using the
+/// `set_accessible_label` and `set_accessible_description` methods of `ComponentBuddy`. Screen
+/// readers and test drivers can query these via `Application.get_accessible_text_at`.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct AccessibleText {
+    label: Option<String>,
+    description: Option<String>,
+}
+
+impl AccessibleText {
+    pub fn new(label: Option<String>, description: Option<String>) -> Self {
+        Self { label, description }
+    }
+
+    /// Gets the short name of the component, for instance "Settings" for a gear icon
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Gets the (possibly longer) description of the component, for instance a summary of the
+    /// data of a chart
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+    }
+
+    /// Checks whether this has neither a label nor a description
+    pub fn is_empty(&self) -> bool {
+        self.label.is_none() && self.description.is_none()
+    }
+}
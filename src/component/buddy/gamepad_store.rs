@@ -0,0 +1,128 @@
+use crate::*;
+
+/// A helper struct to keep track of the connected gamepads and their state (the pressed buttons
+/// and the values of their axes). Like `MouseStore`, this struct is made to make the
+/// implementation of `ComponentBuddy`s easier.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GamepadStore {
+    // I won't use a (Hash)Map because the number of gamepads is expected to be very small
+    entries: Vec<(Gamepad, GamepadState)>,
+}
+
+impl GamepadStore {
+    /// Constructs a new empty `GamepadStore`
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds the given `Gamepad` to this store, with the given *name*, no pressed buttons, and all
+    /// axes at 0.0. If this store already had the given gamepad, its state will be reset.
+    ///
+    /// This should be called when the gamepad is connected.
+    pub fn add_gamepad(&mut self, gamepad: Gamepad, name: String) {
+        self.remove_gamepad(gamepad);
+        self.entries.push((gamepad, GamepadState::new(name)));
+    }
+
+    /// Removes the given `Gamepad` (and its state) from this store.
+    ///
+    /// This should be called when the gamepad is disconnected.
+    pub fn remove_gamepad(&mut self, gamepad: Gamepad) {
+        self.entries.retain(|(candidate, _)| *candidate != gamepad);
+    }
+
+    /// Gets the state of the given `Gamepad`, or `None` if it is not in this store
+    pub fn get_gamepad_state(&self, gamepad: Gamepad) -> Option<&GamepadState> {
+        self.entries
+            .iter()
+            .find(|(candidate, _)| *candidate == gamepad)
+            .map(|(_, state)| state)
+    }
+
+    /// Gives the opportunity to update the state of the given `Gamepad`, or returns `None` if it
+    /// is not in this store
+    pub fn update_gamepad_state(&mut self, gamepad: Gamepad) -> Option<&mut GamepadState> {
+        self.entries
+            .iter_mut()
+            .find(|(candidate, _)| *candidate == gamepad)
+            .map(|(_, state)| state)
+    }
+
+    /// Gets all `Gamepad`s in this store, in the order in which they were added
+    pub fn get_gamepads(&self) -> Vec<Gamepad> {
+        self.entries.iter().map(|(gamepad, _)| *gamepad).collect()
+    }
+
+    /// Replaces the content of this store with the gamepads (and their state) that are known to
+    /// the given *buddy*. Menu components can use this to share the gamepads of their own buddy
+    /// with the buddies of their child components.
+    pub fn copy_from(&mut self, buddy: &dyn ComponentBuddy) {
+        self.entries.clear();
+        for gamepad in buddy.get_gamepads() {
+            if let Some(state) = buddy.get_gamepad_state(gamepad) {
+                self.entries.push((gamepad, state));
+            }
+        }
+    }
+}
+
+/// Represents the state (name, pressed buttons, and axis values) of a single `Gamepad`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GamepadState {
+    name: String,
+    pressed_buttons: Vec<GamepadButton>,
+    axis_values: Vec<(GamepadAxis, f32)>,
+}
+
+impl GamepadState {
+    /// Constructs a new `GamepadState` with the given *name*, no pressed buttons, and all axes
+    /// at 0.0
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            pressed_buttons: Vec::new(),
+            axis_values: Vec::new(),
+        }
+    }
+
+    /// Gets the name of the gamepad, as reported by the operating system or browser
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets all buttons of the gamepad that are currently pressed
+    pub fn get_pressed_buttons(&self) -> &[GamepadButton] {
+        &self.pressed_buttons
+    }
+
+    /// Checks whether the given *button* of the gamepad is currently pressed
+    pub fn is_pressed(&self, button: GamepadButton) -> bool {
+        self.pressed_buttons.contains(&button)
+    }
+
+    /// Gets the current value of the given *axis* (see `GamepadAxisEvent.get_value`). This is 0.0
+    /// for axes that haven't reported any value yet.
+    pub fn get_axis_value(&self, axis: GamepadAxis) -> f32 {
+        self.axis_values
+            .iter()
+            .find(|(candidate, _)| *candidate == axis)
+            .map(|(_, value)| *value)
+            .unwrap_or(0.0)
+    }
+
+    /// Marks the given *button* as pressed (when *pressed* is true) or released
+    pub fn set_pressed(&mut self, button: GamepadButton, pressed: bool) {
+        self.pressed_buttons.retain(|candidate| *candidate != button);
+        if pressed {
+            self.pressed_buttons.push(button);
+        }
+    }
+
+    /// Changes the value of the given *axis*
+    pub fn set_axis_value(&mut self, axis: GamepadAxis, value: f32) {
+        self.axis_values.retain(|(candidate, _)| *candidate != axis);
+        self.axis_values.push((axis, value));
+    }
+}
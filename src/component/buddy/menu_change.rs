@@ -0,0 +1,97 @@
+use crate::*;
+
+/// A function that creates the next menu, given the current menu. See `ComponentBuddy.change_menu`.
+pub type CreateMenu = Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>;
+
+/// A request to change the menu, which can be passed to `ComponentBuddy.request_menu_change`.
+///
+/// Besides the function that creates the new menu, a request can have a *priority* (which is
+/// only used by `MenuChangePolicy::HighestPriority`) and a *rejection callback*, which will be
+/// called when the request loses from another menu change request.
+pub struct MenuChangeRequest {
+    create_new_menu: CreateMenu,
+    priority: i32,
+    on_reject: Option<Box<dyn FnOnce()>>,
+}
+
+impl MenuChangeRequest {
+    /// Constructs a request with priority 0 and without rejection callback
+    pub fn new(create_new_menu: CreateMenu) -> Self {
+        Self { create_new_menu, priority: 0, on_reject: None }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the function that will be called when this request is rejected. The `Application`
+    /// will call it right after the event during which the request was made (before the winning
+    /// menu change is carried out).
+    pub fn with_rejection_callback(mut self, on_reject: impl FnOnce() + 'static) -> Self {
+        self.on_reject = Some(Box::new(on_reject));
+        self
+    }
+
+    pub fn get_priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Creates the new menu, given the *current_menu*
+    pub fn create_menu(self, current_menu: Box<dyn Component>) -> Box<dyn Component> {
+        (self.create_new_menu)(current_menu)
+    }
+
+    /// Calls the rejection callback of this request (if it has one)
+    pub fn reject(self) {
+        if let Some(on_reject) = self.on_reject {
+            on_reject();
+        }
+    }
+}
+
+/// Decides which request wins when multiple components request to change the menu during the
+/// same event. The losing requests will be rejected (see
+/// `MenuChangeRequest.with_rejection_callback`). Use `Application.set_menu_change_policy` to
+/// choose the policy.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MenuChangePolicy {
+    /// The first request wins
+    FirstWins,
+    /// The last request wins. This is the default policy.
+    LastWins,
+    /// The request with the highest priority wins. When multiple requests have the highest
+    /// priority, the first one of them wins.
+    HighestPriority,
+}
+
+impl MenuChangePolicy {
+    /// Decides whether the *new* request should replace the *current* request
+    pub fn should_replace(&self, current: &MenuChangeRequest, new: &MenuChangeRequest) -> bool {
+        match self {
+            Self::FirstWins => false,
+            Self::LastWins => true,
+            Self::HighestPriority => new.get_priority() > current.get_priority(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_should_replace() {
+        let create = || -> CreateMenu { Box::new(|menu| menu) };
+        let low = MenuChangeRequest::new(create()).with_priority(-1);
+        let high = MenuChangeRequest::new(create()).with_priority(5);
+        let other_high = MenuChangeRequest::new(create()).with_priority(5);
+
+        assert!(!MenuChangePolicy::FirstWins.should_replace(&low, &high));
+        assert!(MenuChangePolicy::LastWins.should_replace(&high, &low));
+        assert!(MenuChangePolicy::HighestPriority.should_replace(&low, &high));
+        assert!(!MenuChangePolicy::HighestPriority.should_replace(&high, &low));
+        assert!(!MenuChangePolicy::HighestPriority.should_replace(&high, &other_high));
+    }
+}
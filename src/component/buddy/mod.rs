@@ -1,13 +1,19 @@
+mod gamepad_store;
+mod menu_change;
 mod mouse_store;
 mod root;
 mod subscriptions;
 
+pub use gamepad_store::*;
+pub use menu_change::*;
 pub use mouse_store::*;
 pub use root::*;
 pub use subscriptions::*;
 
 use crate::*;
 
+use std::rc::Rc;
+
 /// Every `Component` will be assigned a *buddy*. This buddy will be passed as
 /// parameter to every method of the `Component` trait. The buddy is the primary
 /// way the component can interact with its parent menu, or the root of knukki
@@ -41,13 +47,34 @@ pub trait ComponentBuddy {
     ///
     /// ### Request
     /// Like the docs above suggest, it is a *request*: it might not happen in
-    /// some rare cases (when multiple components request to change the menu at
-    /// the same time, only one of them can be chosen). The buddy might reject
-    /// the request for other reasons as well, but this should be uncommon.
-    fn change_menu(
-        &mut self,
-        create_new_menu: Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>,
-    );
+    /// some rare cases (when multiple components request to change the menu
+    /// during the same event, only one of them can be chosen; see
+    /// `MenuChangePolicy`). Use `request_menu_change` instead if the component
+    /// needs a priority or wants to know when its request is rejected.
+    fn change_menu(&mut self, create_new_menu: CreateMenu) {
+        self.request_menu_change(MenuChangeRequest::new(create_new_menu));
+    }
+
+    /// Requests to change the parent menu component, like `change_menu`. The
+    /// given *request* can also have a priority and a rejection callback.
+    fn request_menu_change(&mut self, request: MenuChangeRequest);
+
+    /// Requests to show the given *component* as a temporary floating *popup* (like a context
+    /// menu or the list of a dropdown) in the given *domain*. Like the points of events, the
+    /// *domain* is relative to this component, but it can extend beyond the bounds of this
+    /// component (for instance to show a dropdown list below a button).
+    ///
+    /// The request travels up through the parent menus until it reaches a `PopupMenu`, which
+    /// will show the popup on top of everything else it contains, and close it when the user
+    /// clicks outside of it. The request is ignored when none of the ancestors is a `PopupMenu`.
+    fn open_popup(&mut self, domain: ComponentDomain, component: Box<dyn Component>);
+
+    /// Requests to show the given *component* as a popup, like `open_popup`. Instead of a fixed
+    /// domain, the popup will be positioned according to the given *placement*: the `PopupMenu`
+    /// that hosts the popup will keep it inside its own domain, and move it to the other side of
+    /// its anchor when there is not enough room. This is convenient for tooltips, context menus,
+    /// and dropdown lists that are opened near the edge of the window.
+    fn open_placed_popup(&mut self, placement: PopupPlacement, component: Box<dyn Component>);
 
     /// Prompts the user to type some text for the component.
     ///
@@ -70,6 +97,84 @@ pub trait ComponentBuddy {
     /// not called, for instance when the window is resized.
     fn request_render(&mut self);
 
+    /// Sets the label of the textual alternative of this component. This is useful for purely
+    /// graphical components like icons, so that screen readers (and test drivers) know what the
+    /// component represents. Use `None` to remove the label.
+    fn set_accessible_label(&mut self, label: Option<String>);
+
+    /// Sets the (longer) description of the textual alternative of this component, for instance a
+    /// summary of the data in a chart. Use `None` to remove the description.
+    fn set_accessible_description(&mut self, description: Option<String>);
+
+    /// Reports the rectangle around the text caret of this component, so that the *wrapper* can
+    /// position the candidate window of the input method editor near it. Text components should
+    /// call this whenever their caret moves, and use `None` when they no longer have a caret.
+    ///
+    /// The rectangle that was reported most recently (by any component) wins, regardless of which
+    /// component has the focus.
+    fn set_caret_rectangle(&mut self, rectangle: Option<CaretRectangle>);
+
+    /// Sets the shape of the mouse cursor while it hovers over this component, for instance
+    /// `CursorIcon::Hand` for buttons and `CursorIcon::Text` for text fields. Components that
+    /// never call this method will get the cursor of their parent. When components overlap, the
+    /// cursor of the deepest component at the position of the mouse wins.
+    fn set_cursor(&mut self, cursor: CursorIcon);
+
+    /// Declares what happens when the user starts dragging the window at this component. Use
+    /// `WindowFrameArea::TitleBar` to make this component a window drag region (for instance
+    /// for a custom title bar of a borderless window), or `WindowFrameArea::Resize` to let the
+    /// user resize the window from this component.
+    ///
+    /// Like cursors, components that never call this method will get the area of their parent,
+    /// so buttons inside a custom title bar should use `WindowFrameArea::Content`. When
+    /// components overlap, the area of the deepest component at the position of the mouse wins.
+    /// Only the *wrapper*s of desktop platforms support moving and resizing the window.
+    fn set_window_frame_area(&mut self, area: WindowFrameArea);
+
+    /// Captures the given *mouse*: until the capture ends, all `MouseMoveEvent`s of *mouse* (and
+    /// its next `MouseReleaseEvent`) will be delivered to this component, even when the cursor is
+    /// outside the domain of this component. The positions of these events can thus be outside
+    /// the usual range [0, 1]. The component will still receive `MouseEnterEvent`s and
+    /// `MouseLeaveEvent`s when the cursor crosses its border.
+    ///
+    /// Components should normally call this method during `on_mouse_press`, for instance to keep
+    /// dragging the handle of a slider when the cursor leaves the slider. The capture ends
+    /// automatically when the component receives the next `MouseReleaseEvent` of *mouse* (or
+    /// would receive it if it were subscribed), or when `release_mouse_capture` is called.
+    ///
+    /// Only 1 component can capture a mouse at the same time: if another component had already
+    /// captured *mouse*, its capture will end.
+    fn capture_mouse(&mut self, mouse: Mouse);
+
+    /// Ends the capture of the given *mouse* by this component, if it had captured it
+    fn release_mouse_capture(&mut self, mouse: Mouse);
+
+    /// Requests to lock the pointer of the given *mouse*: while the pointer is locked, the
+    /// *wrapper* hides the cursor and keeps it in place, and this component will receive a
+    /// `RelativeMouseMoveEvent` for each movement of *mouse*, regardless of where it is. This is
+    /// meant for components that need unlimited mouse movement, like 3D viewports.
+    ///
+    /// Browsers only allow pointer locks shortly after a user interaction, so components should
+    /// normally call this method while handling a mouse press or click. The user (or platform)
+    /// can end the pointer lock at any time (for instance by pressing Escape in a browser), after
+    /// which the component will simply stop receiving `RelativeMouseMoveEvent`s.
+    ///
+    /// Only 1 component can lock the pointer of a mouse at the same time: if another component
+    /// had already locked it, its pointer lock will end.
+    fn request_pointer_lock(&mut self, mouse: Mouse);
+
+    /// Ends the pointer lock of the given *mouse* by this component, if it had locked it
+    fn release_pointer_lock(&mut self, mouse: Mouse);
+
+    /// Gives the focus to this component. The `CharTypeEvent`, the composition events, and the
+    /// gamepad events are only delivered to the focused component (if it is subscribed for them).
+    ///
+    /// Menus give the focus to the last component that was clicked, so components only need to
+    /// call this method when they want the focus without being clicked (for instance a game
+    /// that wants gamepad events right after it is attached). When a component inside a menu
+    /// requests the focus, the menu will request the focus from its own parent as well.
+    fn request_focus(&mut self);
+
     // Subscribe methods
 
     /// Subscribes the component for the `MouseClickEvent`
@@ -114,14 +219,115 @@ pub trait ComponentBuddy {
     /// Cancels the components subscription for the `MouseLeaveEvent`
     fn unsubscribe_mouse_leave(&mut self);
 
+    /// Subscribes the component for the `MouseScrollEvent`
+    fn subscribe_mouse_scroll(&mut self);
+
+    /// Cancels the components subscription for the `MouseScrollEvent`
+    fn unsubscribe_mouse_scroll(&mut self);
+
+    /// Subscribes the component for the `MouseDoubleClickEvent`
+    fn subscribe_mouse_double_click(&mut self);
+
+    /// Cancels the components subscription for the `MouseDoubleClickEvent`
+    fn unsubscribe_mouse_double_click(&mut self);
+
+    /// Subscribes the component for the `MouseLongPressEvent`
+    fn subscribe_mouse_long_press(&mut self);
+
+    /// Cancels the components subscription for the `MouseLongPressEvent`
+    fn unsubscribe_mouse_long_press(&mut self);
+
+    /// Subscribes the component for the `MouseContextClickEvent`
+    fn subscribe_mouse_context_click(&mut self);
+
+    /// Cancels the components subscription for the `MouseContextClickEvent`
+    fn unsubscribe_mouse_context_click(&mut self);
+
+    /// Subscribes the component for the `MouseMiddleClickEvent`
+    fn subscribe_mouse_middle_click(&mut self);
+
+    /// Cancels the components subscription for the `MouseMiddleClickEvent`
+    fn unsubscribe_mouse_middle_click(&mut self);
+
+    /// Subscribes the component for the `MouseHoverEvent`
+    fn subscribe_mouse_hover(&mut self);
+
+    /// Cancels the components subscription for the `MouseHoverEvent`
+    fn unsubscribe_mouse_hover(&mut self);
+
+    /// Subscribes the component for the `PinchEvent`, `RotateGestureEvent`, and `SwipeEvent`,
+    /// which are synthesized from the fingers on a touch screen
+    fn subscribe_gestures(&mut self);
+
+    /// Cancels the subscription of the component for the gesture events
+    fn unsubscribe_gestures(&mut self);
+
     /// Subscribes the component for the `CharTypeEvent`. This method will return
     /// `Ok` if a keyboard is available, and `Err` if not. If this method returns
     /// `Err`, but the component really needs text input, it should call
     /// `request_text_input`.
-    fn subscribe_char_type(&self) -> Result<(), ()>;
+    ///
+    /// Only the focused component will receive this event, see `request_focus`.
+    fn subscribe_char_type(&mut self) -> Result<(), ()>;
 
     /// Cancels the subscription of the component for the `CharTypeEvent`.
-    fn unsubscribe_char_type(&self);
+    fn unsubscribe_char_type(&mut self);
+
+    /// Subscribes the component for the `CompositionStartEvent`, `CompositionUpdateEvent`, and
+    /// `CompositionEndEvent`. Components that accept text input should subscribe for these
+    /// events as well as the `CharTypeEvent`, because text composed with an input method editor
+    /// (which is needed for for instance Chinese and Japanese) will only be reported with these
+    /// events. Like the `CharTypeEvent`, only the focused component will receive them.
+    fn subscribe_composition(&mut self);
+
+    /// Cancels the subscription of the component for the composition events.
+    fn unsubscribe_composition(&mut self);
+
+    /// Subscribes the component for the `TickEvent`, which will be fired once per frame. Note
+    /// that components should only subscribe while they actually need it (for instance while an
+    /// animation is playing) because each tick costs a bit of performance.
+    fn subscribe_tick(&mut self);
+
+    /// Cancels the subscription of the component for the `TickEvent`.
+    fn unsubscribe_tick(&mut self);
+
+    /// Subscribes the component for the `ThemeChangeEvent`, which will be fired when the `Theme`
+    /// of the application is replaced.
+    fn subscribe_theme_change(&mut self);
+
+    /// Cancels the subscription of the component for the `ThemeChangeEvent`.
+    fn unsubscribe_theme_change(&mut self);
+
+    /// Subscribes the component for the `GamepadButtonEvent` and `GamepadAxisEvent`. Like the
+    /// `CharTypeEvent`, only the focused component will receive them.
+    fn subscribe_gamepad(&mut self);
+
+    /// Cancels the subscription of the component for the gamepad events.
+    fn unsubscribe_gamepad(&mut self);
+
+    /// Subscribes the component for the `LowMemoryEvent`, which will be fired when the device is
+    /// running low on memory. Components that keep large caches (or other data that they can
+    /// restore later) should subscribe for this event, and release that data when it is fired.
+    fn subscribe_low_memory(&mut self);
+
+    /// Cancels the subscription of the component for the `LowMemoryEvent`.
+    fn unsubscribe_low_memory(&mut self);
+
+    /// Subscribes the component for the `WindowFocusEvent` and `WindowMinimizeEvent`, which will
+    /// be fired when the window gains or loses the focus, and when it is minimized or restored.
+    /// Components can use these events to pause their animations while the user is not looking.
+    fn subscribe_window_state(&mut self);
+
+    /// Cancels the subscription of the component for the window focus and minimize events.
+    fn unsubscribe_window_state(&mut self);
+
+    /// Subscribes the component for close requests: its `on_close_request` method will be called
+    /// when the user tries to close the window, and it can veto the request (for instance to
+    /// prompt the user to save their work).
+    fn subscribe_close_request(&mut self);
+
+    /// Cancels the subscription of the component for close requests.
+    fn unsubscribe_close_request(&mut self);
 
     // Read methods
 
@@ -195,4 +401,39 @@ pub trait ComponentBuddy {
     /// If this method is called during the `fire_mouse_leave_event` of the `Application` for some
     /// mouse *M*, the result of this method *won't* contain *M*.
     fn get_all_mouses(&self) -> Vec<Mouse>;
+
+    /// Checks whether this component has currently captured the given *mouse* (see
+    /// `capture_mouse`)
+    fn is_capturing_mouse(&self, mouse: Mouse) -> bool;
+
+    /// Checks whether this component holds the pointer lock of the given *mouse* (see
+    /// `request_pointer_lock`)
+    fn is_locking_pointer(&self, mouse: Mouse) -> bool;
+
+    /// Gets the current `Theme` of the application (see `Application.set_theme`). Components that
+    /// follow the theme should check it whenever they render, since it can change at any time.
+    /// The theme is only replaced (rather than modified), so `Rc::ptr_eq` can be used to check
+    /// whether it changed.
+    fn get_theme(&self) -> Rc<Theme>;
+
+    /// Gets the `FrameTiming` of the frame that is currently being produced, which contains the
+    /// refresh rate of the display and the timestamps of the current and previous frame (as far
+    /// as the *wrapper* knows them). This is useful for animations that want to adapt to the
+    /// refresh rate, or to measure how many frames were dropped.
+    fn get_frame_timing(&self) -> FrameTiming;
+
+    /// Gets all `Gamepad`s that are currently connected, in the order in which they were
+    /// connected. Unlike mouses, gamepads are not bound to a position, so this is the same for
+    /// every component.
+    fn get_gamepads(&self) -> Vec<Gamepad>;
+
+    /// Gets the current `GamepadState` of the given *gamepad*, or `None` if it is not connected
+    fn get_gamepad_state(&self, gamepad: Gamepad) -> Option<GamepadState>;
+
+    /// Checks if the given *button* of the given *gamepad* is currently pressed. This will return
+    /// `None` if the gamepad is not connected.
+    fn is_gamepad_button_down(&self, gamepad: Gamepad, button: GamepadButton) -> Option<bool> {
+        self.get_gamepad_state(gamepad)
+            .map(|state| state.is_pressed(button))
+    }
 }
@@ -11,7 +11,19 @@ pub struct RootComponentBuddy {
 
     last_render_result: Option<RenderResultStruct>,
 
-    create_next_menu: Option<Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>>,
+    next_menu: Option<MenuChangeRequest>,
+    rejected_menu_changes: Vec<MenuChangeRequest>,
+    menu_change_policy: MenuChangePolicy,
+
+    accessible_text: AccessibleText,
+    caret_rectangle: Option<CaretRectangle>,
+    cursor: Option<CursorIcon>,
+    window_frame_area: Option<WindowFrameArea>,
+    captured_mouses: Vec<Mouse>,
+    locked_mouses: Vec<Mouse>,
+    theme: Rc<Theme>,
+    frame_timing: FrameTiming,
+    gamepad_store: Rc<RefCell<GamepadStore>>,
 
     requested_render: bool,
 }
@@ -22,7 +34,18 @@ impl RootComponentBuddy {
             subscriptions: ComponentSubscriptions::new(),
             mouse_store: None,
             last_render_result: None,
-            create_next_menu: None,
+            next_menu: None,
+            rejected_menu_changes: Vec::new(),
+            menu_change_policy: MenuChangePolicy::LastWins,
+            accessible_text: AccessibleText::default(),
+            caret_rectangle: None,
+            cursor: None,
+            window_frame_area: None,
+            captured_mouses: Vec::new(),
+            locked_mouses: Vec::new(),
+            theme: Rc::new(Theme::default()),
+            frame_timing: FrameTiming::default(),
+            gamepad_store: Rc::new(RefCell::new(GamepadStore::new())),
 
             // Components should normally render as soon as possible after they
             // are attached
@@ -49,6 +72,22 @@ impl RootComponentBuddy {
         self.requested_render
     }
 
+    pub fn get_accessible_text(&self) -> &AccessibleText {
+        &self.accessible_text
+    }
+
+    pub fn get_caret_rectangle(&self) -> Option<CaretRectangle> {
+        self.caret_rectangle
+    }
+
+    pub fn get_cursor(&self) -> Option<CursorIcon> {
+        self.cursor
+    }
+
+    pub fn get_window_frame_area(&self) -> Option<WindowFrameArea> {
+        self.window_frame_area
+    }
+
     pub fn get_last_render_result(&self) -> &Option<RenderResultStruct> {
         &self.last_render_result
     }
@@ -61,25 +100,65 @@ impl RootComponentBuddy {
         self.requested_render = false;
     }
 
+    /// Changes the theme that `get_theme` returns. Note that this doesn't request a render: the
+    /// `Application` renders everything again when its theme is changed.
+    pub fn set_theme(&mut self, theme: Rc<Theme>) {
+        self.theme = theme;
+    }
+
+    pub fn set_frame_timing(&mut self, frame_timing: FrameTiming) {
+        self.frame_timing = frame_timing;
+    }
+
+    pub fn set_gamepad_store(&mut self, gamepad_store: Rc<RefCell<GamepadStore>>) {
+        self.gamepad_store = gamepad_store;
+    }
+
+    pub fn set_menu_change_policy(&mut self, policy: MenuChangePolicy) {
+        self.menu_change_policy = policy;
+    }
+
     pub fn has_next_menu(&self) -> bool {
-        self.create_next_menu.is_some()
+        self.next_menu.is_some()
     }
 
     pub fn create_next_menu(&mut self, current_menu: Box<dyn Component>) -> Box<dyn Component> {
-        let create_next_menu = self
-            .create_next_menu
+        let next_menu = self
+            .next_menu
             .take()
             .expect("Only call this method after has_next_menu returned true");
-        create_next_menu(current_menu)
+        next_menu.create_menu(current_menu)
+    }
+
+    /// Takes the menu change requests that lost from another request since the last call to this
+    /// method. The caller should `reject` them.
+    pub fn take_rejected_menu_changes(&mut self) -> Vec<MenuChangeRequest> {
+        std::mem::take(&mut self.rejected_menu_changes)
     }
 }
 
 impl ComponentBuddy for RootComponentBuddy {
-    fn change_menu(
-        &mut self,
-        create_new_menu: Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>,
-    ) {
-        self.create_next_menu = Some(create_new_menu);
+    fn request_menu_change(&mut self, request: MenuChangeRequest) {
+        match self.next_menu.take() {
+            Some(current) => {
+                if self.menu_change_policy.should_replace(&current, &request) {
+                    self.rejected_menu_changes.push(current);
+                    self.next_menu = Some(request);
+                } else {
+                    self.rejected_menu_changes.push(request);
+                    self.next_menu = Some(current);
+                }
+            }
+            None => self.next_menu = Some(request),
+        }
+    }
+
+    fn open_popup(&mut self, _domain: ComponentDomain, _component: Box<dyn Component>) {
+        // There is no PopupMenu that could host the popup, so the request is ignored
+    }
+
+    fn open_placed_popup(&mut self, _placement: PopupPlacement, _component: Box<dyn Component>) {
+        // There is no PopupMenu that could host the popup, so the request is ignored
     }
 
     fn request_text_input(&self, _start_text: String) -> Option<String> {
@@ -90,6 +169,50 @@ impl ComponentBuddy for RootComponentBuddy {
         self.requested_render = true;
     }
 
+    fn set_accessible_label(&mut self, label: Option<String>) {
+        self.accessible_text.set_label(label);
+    }
+
+    fn set_accessible_description(&mut self, description: Option<String>) {
+        self.accessible_text.set_description(description);
+    }
+
+    fn set_caret_rectangle(&mut self, rectangle: Option<CaretRectangle>) {
+        self.caret_rectangle = rectangle;
+    }
+
+    fn set_cursor(&mut self, cursor: CursorIcon) {
+        self.cursor = Some(cursor);
+    }
+
+    fn set_window_frame_area(&mut self, area: WindowFrameArea) {
+        self.window_frame_area = Some(area);
+    }
+
+    fn capture_mouse(&mut self, mouse: Mouse) {
+        if !self.captured_mouses.contains(&mouse) {
+            self.captured_mouses.push(mouse);
+        }
+    }
+
+    fn release_mouse_capture(&mut self, mouse: Mouse) {
+        self.captured_mouses.retain(|captured| *captured != mouse);
+    }
+
+    fn request_pointer_lock(&mut self, mouse: Mouse) {
+        if !self.locked_mouses.contains(&mouse) {
+            self.locked_mouses.push(mouse);
+        }
+    }
+
+    fn release_pointer_lock(&mut self, mouse: Mouse) {
+        self.locked_mouses.retain(|locked| *locked != mouse);
+    }
+
+    fn request_focus(&mut self) {
+        // The root component always has the focus
+    }
+
     fn subscribe_mouse_click(&mut self) {
         self.subscriptions.mouse_click = true;
     }
@@ -146,12 +269,126 @@ impl ComponentBuddy for RootComponentBuddy {
         self.subscriptions.mouse_leave = false;
     }
 
-    fn subscribe_char_type(&self) -> Result<(), ()> {
-        todo!()
+    fn subscribe_mouse_scroll(&mut self) {
+        self.subscriptions.mouse_scroll = true;
     }
 
-    fn unsubscribe_char_type(&self) {
-        todo!()
+    fn unsubscribe_mouse_scroll(&mut self) {
+        self.subscriptions.mouse_scroll = false;
+    }
+
+    fn subscribe_mouse_double_click(&mut self) {
+        self.subscriptions.mouse_double_click = true;
+    }
+
+    fn unsubscribe_mouse_double_click(&mut self) {
+        self.subscriptions.mouse_double_click = false;
+    }
+
+    fn subscribe_mouse_long_press(&mut self) {
+        self.subscriptions.mouse_long_press = true;
+    }
+
+    fn unsubscribe_mouse_long_press(&mut self) {
+        self.subscriptions.mouse_long_press = false;
+    }
+
+    fn subscribe_mouse_context_click(&mut self) {
+        self.subscriptions.mouse_context_click = true;
+    }
+
+    fn unsubscribe_mouse_context_click(&mut self) {
+        self.subscriptions.mouse_context_click = false;
+    }
+
+    fn subscribe_mouse_middle_click(&mut self) {
+        self.subscriptions.mouse_middle_click = true;
+    }
+
+    fn unsubscribe_mouse_middle_click(&mut self) {
+        self.subscriptions.mouse_middle_click = false;
+    }
+
+    fn subscribe_mouse_hover(&mut self) {
+        self.subscriptions.mouse_hover = true;
+    }
+
+    fn unsubscribe_mouse_hover(&mut self) {
+        self.subscriptions.mouse_hover = false;
+    }
+
+    fn subscribe_gestures(&mut self) {
+        self.subscriptions.gestures = true;
+    }
+
+    fn unsubscribe_gestures(&mut self) {
+        self.subscriptions.gestures = false;
+    }
+
+    fn subscribe_char_type(&mut self) -> Result<(), ()> {
+        // All current wrappers have a keyboard
+        self.subscriptions.char_type = true;
+        Ok(())
+    }
+
+    fn unsubscribe_char_type(&mut self) {
+        self.subscriptions.char_type = false;
+    }
+
+    fn subscribe_composition(&mut self) {
+        self.subscriptions.composition = true;
+    }
+
+    fn unsubscribe_composition(&mut self) {
+        self.subscriptions.composition = false;
+    }
+
+    fn subscribe_tick(&mut self) {
+        self.subscriptions.tick = true;
+    }
+
+    fn unsubscribe_tick(&mut self) {
+        self.subscriptions.tick = false;
+    }
+
+    fn subscribe_theme_change(&mut self) {
+        self.subscriptions.theme_change = true;
+    }
+
+    fn unsubscribe_theme_change(&mut self) {
+        self.subscriptions.theme_change = false;
+    }
+
+    fn subscribe_gamepad(&mut self) {
+        self.subscriptions.gamepad = true;
+    }
+
+    fn unsubscribe_gamepad(&mut self) {
+        self.subscriptions.gamepad = false;
+    }
+
+    fn subscribe_low_memory(&mut self) {
+        self.subscriptions.low_memory = true;
+    }
+
+    fn unsubscribe_low_memory(&mut self) {
+        self.subscriptions.low_memory = false;
+    }
+
+    fn subscribe_window_state(&mut self) {
+        self.subscriptions.window_state = true;
+    }
+
+    fn unsubscribe_window_state(&mut self) {
+        self.subscriptions.window_state = false;
+    }
+
+    fn subscribe_close_request(&mut self) {
+        self.subscriptions.close_request = true;
+    }
+
+    fn unsubscribe_close_request(&mut self) {
+        self.subscriptions.close_request = false;
     }
 
     fn get_mouse_position(&self, mouse: Mouse) -> Option<Point> {
@@ -197,4 +434,28 @@ impl ComponentBuddy for RootComponentBuddy {
         // All mouses are local for the root component
         self.get_local_mouses()
     }
+
+    fn is_capturing_mouse(&self, mouse: Mouse) -> bool {
+        self.captured_mouses.contains(&mouse)
+    }
+
+    fn is_locking_pointer(&self, mouse: Mouse) -> bool {
+        self.locked_mouses.contains(&mouse)
+    }
+
+    fn get_theme(&self) -> Rc<Theme> {
+        Rc::clone(&self.theme)
+    }
+
+    fn get_frame_timing(&self) -> FrameTiming {
+        self.frame_timing
+    }
+
+    fn get_gamepads(&self) -> Vec<Gamepad> {
+        self.gamepad_store.borrow().get_gamepads()
+    }
+
+    fn get_gamepad_state(&self, gamepad: Gamepad) -> Option<GamepadState> {
+        self.gamepad_store.borrow().get_gamepad_state(gamepad).cloned()
+    }
 }
@@ -7,9 +7,23 @@ pub struct ComponentSubscriptions {
     pub mouse_move: bool,
     pub mouse_leave: bool,
     pub mouse_enter: bool,
+    pub mouse_scroll: bool,
+    pub mouse_double_click: bool,
+    pub mouse_long_press: bool,
+    pub mouse_context_click: bool,
+    pub mouse_middle_click: bool,
+    pub mouse_hover: bool,
+    pub gestures: bool,
 
     // Other subscriptions
     pub char_type: bool,
+    pub composition: bool,
+    pub tick: bool,
+    pub theme_change: bool,
+    pub gamepad: bool,
+    pub low_memory: bool,
+    pub window_state: bool,
+    pub close_request: bool,
 }
 
 impl ComponentSubscriptions {
@@ -22,8 +36,56 @@ impl ComponentSubscriptions {
             mouse_move: false,
             mouse_leave: false,
             mouse_enter: false,
+            mouse_scroll: false,
+            mouse_double_click: false,
+            mouse_long_press: false,
+            mouse_context_click: false,
+            mouse_middle_click: false,
+            mouse_hover: false,
+            gestures: false,
 
             char_type: false,
+            composition: false,
+            tick: false,
+            theme_change: false,
+            gamepad: false,
+            low_memory: false,
+            window_state: false,
+            close_request: false,
         }
     }
+
+    /// Gets the names of the events of all subscriptions that are enabled, for instance
+    /// "MouseClick". This is used by `ComponentDescription`.
+    pub fn get_names(&self) -> Vec<&'static str> {
+        let subscriptions = [
+            (self.mouse_click, "MouseClick"),
+            (self.mouse_click_out, "MouseClickOut"),
+            (self.mouse_press, "MousePress"),
+            (self.mouse_release, "MouseRelease"),
+            (self.mouse_move, "MouseMove"),
+            (self.mouse_leave, "MouseLeave"),
+            (self.mouse_enter, "MouseEnter"),
+            (self.mouse_scroll, "MouseScroll"),
+            (self.mouse_double_click, "MouseDoubleClick"),
+            (self.mouse_long_press, "MouseLongPress"),
+            (self.mouse_context_click, "MouseContextClick"),
+            (self.mouse_middle_click, "MouseMiddleClick"),
+            (self.mouse_hover, "MouseHover"),
+            (self.gestures, "Gestures"),
+            (self.char_type, "CharType"),
+            (self.composition, "Composition"),
+            (self.tick, "Tick"),
+            (self.theme_change, "ThemeChange"),
+            (self.gamepad, "Gamepad"),
+            (self.low_memory, "LowMemory"),
+            (self.window_state, "WindowState"),
+            (self.close_request, "CloseRequest"),
+        ];
+        subscriptions
+            .iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, name)| *name)
+            .collect()
+    }
 }
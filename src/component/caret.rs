@@ -0,0 +1,48 @@
+use crate::*;
+
+/// The rectangle (in the coordinate system of a component) that surrounds the text caret of the
+/// component. Text components should report this rectangle via `set_caret_rectangle` of their
+/// buddy, so that the *wrapper* can position the candidate window of the input method editor
+/// (IME) near the caret.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CaretRectangle {
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+}
+
+impl CaretRectangle {
+    pub fn between(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Self {
+        Self {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
+
+    pub fn get_min_x(&self) -> f32 {
+        self.min_x
+    }
+
+    pub fn get_min_y(&self) -> f32 {
+        self.min_y
+    }
+
+    pub fn get_max_x(&self) -> f32 {
+        self.max_x
+    }
+
+    pub fn get_max_y(&self) -> f32 {
+        self.max_y
+    }
+
+    /// Converts this rectangle from the coordinate system of a child component to the coordinate
+    /// system of its parent, using the given *transform_back* function.
+    pub fn transform_back(&self, transform_back: impl Fn(Point) -> Point) -> Self {
+        let min = transform_back(Point::new(self.min_x, self.min_y));
+        let max = transform_back(Point::new(self.max_x, self.max_y));
+        Self::between(min.get_x(), min.get_y(), max.get_x(), max.get_y())
+    }
+}
@@ -0,0 +1,22 @@
+/// The shapes that the mouse cursor can have. Components can choose the shape of the cursor while
+/// it hovers over them using `ComponentBuddy.set_cursor`, and the *wrapper* will apply the shape
+/// that `Application.get_cursor` returns.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CursorIcon {
+    /// The default cursor
+    Arrow,
+    /// The cursor that indicates that something can be clicked, like a link or a button
+    Hand,
+    /// The cursor that indicates that text can be selected or typed
+    Text,
+    /// The cursor that indicates that something can be resized to the left and right
+    ResizeHorizontal,
+    /// The cursor that indicates that something can be resized upwards and downwards
+    ResizeVertical,
+    /// The cursor that indicates that something can be resized towards the top-left or
+    /// bottom-right corner
+    ResizeTopLeftBottomRight,
+    /// The cursor that indicates that something can be resized towards the top-right or
+    /// bottom-left corner
+    ResizeTopRightBottomLeft,
+}
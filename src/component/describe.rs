@@ -0,0 +1,191 @@
+use crate::*;
+
+use std::fmt::{Display, Formatter, Write};
+
+/// A description of a component and its children, which is meant for debugging (deep) menu
+/// hierarchies. Use `Application.dump_tree` to describe the entire component tree.
+///
+/// The description of a component consists of its type name, its domain within its parent menu,
+/// the events it is subscribed to, the bounds of the region it drew during its last render, and
+/// the descriptions of its children. Only the menu that owns a component knows its domain,
+/// subscriptions, and drawn region, so these are `None` when the component isn't described by its
+/// menu. The `Display` implementation prints the tree as indented text, and `to_json` converts it
+/// to JSON.
+#[derive(Clone, Debug)]
+pub struct ComponentDescription {
+    type_name: String,
+    domain: Option<ComponentDomain>,
+    subscriptions: Option<Vec<&'static str>>,
+    drawn_bounds: Option<[f32; 4]>,
+    children: Vec<ComponentDescription>,
+}
+
+impl ComponentDescription {
+    /// Describes the given *component* (using `Component.get_type_name`) and its children (using
+    /// `Component.describe_children`). The domain, subscriptions, and drawn region can be added
+    /// with the `with_...` methods.
+    pub fn of(component: &dyn Component) -> Self {
+        Self {
+            type_name: component.get_type_name().to_string(),
+            domain: None,
+            subscriptions: None,
+            drawn_bounds: None,
+            children: component.describe_children(),
+        }
+    }
+
+    pub fn with_domain(mut self, domain: ComponentDomain) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    pub fn with_subscriptions(mut self, subscriptions: &ComponentSubscriptions) -> Self {
+        self.subscriptions = Some(subscriptions.get_names());
+        self
+    }
+
+    /// Stores the bounds of the drawn region of the given *render_result* (if it is `Some`)
+    pub fn with_render_result(mut self, render_result: &Option<RenderResultStruct>) -> Self {
+        self.drawn_bounds = render_result.as_ref().map(|result| {
+            let region = &result.drawn_region;
+            [region.get_left(), region.get_bottom(), region.get_right(), region.get_top()]
+        });
+        self
+    }
+
+    pub fn get_type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn get_domain(&self) -> Option<ComponentDomain> {
+        self.domain
+    }
+
+    /// Gets the names of the events that the component is subscribed to (for instance
+    /// "MouseClick"), if known
+    pub fn get_subscriptions(&self) -> Option<&[&'static str]> {
+        self.subscriptions.as_deref()
+    }
+
+    /// Gets the bounds (left, bottom, right, top) of the region that the component drew during its
+    /// last render, if known. This is `None` if the component hasn't been rendered yet.
+    pub fn get_drawn_bounds(&self) -> Option<[f32; 4]> {
+        self.drawn_bounds
+    }
+
+    pub fn get_children(&self) -> &[ComponentDescription] {
+        &self.children
+    }
+
+    /// Converts this description (and the descriptions of its children) to a JSON object
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"type\":");
+        write_json_string(json, &self.type_name);
+        if let Some(domain) = self.domain {
+            let _ = write!(
+                json, ",\"domain\":[{},{},{},{}]",
+                domain.get_min_x(), domain.get_min_y(), domain.get_max_x(), domain.get_max_y()
+            );
+        }
+        if let Some(subscriptions) = &self.subscriptions {
+            json.push_str(",\"subscriptions\":[");
+            for (index, name) in subscriptions.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                write_json_string(json, name);
+            }
+            json.push(']');
+        }
+        if let Some([left, bottom, right, top]) = self.drawn_bounds {
+            let _ = write!(json, ",\"drawn\":[{},{},{},{}]", left, bottom, right, top);
+        }
+        json.push_str(",\"children\":[");
+        for (index, child) in self.children.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            child.write_json(json);
+        }
+        json.push_str("]}");
+    }
+
+    fn fmt_indented(&self, f: &mut Formatter<'_>, depth: usize) -> std::fmt::Result {
+        write!(f, "{:indent$}{}", "", self.type_name, indent = 2 * depth)?;
+        if let Some(domain) = self.domain {
+            write!(
+                f, " domain=({}, {}, {}, {})",
+                domain.get_min_x(), domain.get_min_y(), domain.get_max_x(), domain.get_max_y()
+            )?;
+        }
+        if let Some([left, bottom, right, top]) = self.drawn_bounds {
+            write!(f, " drawn=({}, {}, {}, {})", left, bottom, right, top)?;
+        }
+        if let Some(subscriptions) = &self.subscriptions {
+            write!(f, " subscriptions=[{}]", subscriptions.join(", "))?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ComponentDescription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+pub(crate) fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            character if character.is_control() => {
+                let _ = write!(json, "\\u{:04x}", character as u32);
+            }
+            character => json.push(character),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_text_and_json() {
+        let mut subscriptions = ComponentSubscriptions::new();
+        subscriptions.mouse_click = true;
+        subscriptions.tick = true;
+
+        let description = ComponentDescription::of(&DummyComponent {})
+            .with_domain(ComponentDomain::between(0.0, 0.5, 1.0, 1.0))
+            .with_subscriptions(&subscriptions)
+            .with_render_result(&Some(RenderResultStruct::entire()));
+
+        assert_eq!(Some(&["MouseClick", "Tick"][..]), description.get_subscriptions());
+        assert_eq!(
+            "knukki::component::dummy::DummyComponent domain=(0, 0.5, 1, 1) drawn=(0, 0, 1, 1) \
+            subscriptions=[MouseClick, Tick]\n",
+            description.to_string()
+        );
+        assert_eq!(
+            "{\"type\":\"knukki::component::dummy::DummyComponent\",\"domain\":[0,0.5,1,1],\
+            \"subscriptions\":[\"MouseClick\",\"Tick\"],\"drawn\":[0,0,1,1],\"children\":[]}",
+            description.to_json()
+        );
+    }
+}
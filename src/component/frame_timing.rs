@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+/// Describes the timing of the frames that the *wrapper* presents, which components can get via
+/// `ComponentBuddy.get_frame_timing`. Animation code can use the refresh rate to adapt to displays
+/// of 60, 120, or 144 Hz, and the frame timestamps to measure how many frames were dropped.
+///
+/// All values are optional because not every *wrapper* (or platform) can provide them. For
+/// instance, browsers don't expose the refresh rate of the display, and the frame timestamps are
+/// only known after the *wrapper* presented its first frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FrameTiming {
+    refresh_rate: Option<f32>,
+    previous_frame_time: Option<Duration>,
+    frame_time: Option<Duration>,
+}
+
+impl FrameTiming {
+    /// Constructs a new `FrameTiming`. The *refresh_rate* is in Hz and the frame times are the
+    /// timestamps of the previous and the current frame. These timestamps are measured from an
+    /// arbitrary starting point that is chosen by the *wrapper*, so only the differences between
+    /// them are meaningful.
+    pub fn new(
+        refresh_rate: Option<f32>,
+        previous_frame_time: Option<Duration>,
+        frame_time: Option<Duration>,
+    ) -> Self {
+        Self {
+            refresh_rate,
+            previous_frame_time,
+            frame_time,
+        }
+    }
+
+    /// Gets the refresh rate of the display (in Hz) that the application is currently shown on,
+    /// if the *wrapper* knows it
+    pub fn get_refresh_rate(&self) -> Option<f32> {
+        self.refresh_rate
+    }
+
+    /// Gets the time between 2 refreshes of the display, if the refresh rate is known
+    pub fn get_refresh_interval(&self) -> Option<Duration> {
+        self.refresh_rate
+            .map(|refresh_rate| Duration::from_secs_f32(1.0 / refresh_rate))
+    }
+
+    /// Gets the timestamp of the frame that is currently being produced
+    pub fn get_frame_time(&self) -> Option<Duration> {
+        self.frame_time
+    }
+
+    /// Gets the timestamp of the frame before the current frame
+    pub fn get_previous_frame_time(&self) -> Option<Duration> {
+        self.previous_frame_time
+    }
+
+    /// Gets the time between the previous frame and the current frame
+    pub fn get_frame_interval(&self) -> Option<Duration> {
+        match (self.previous_frame_time, self.frame_time) {
+            (Some(previous), Some(current)) => current.checked_sub(previous),
+            _ => None,
+        }
+    }
+
+    /// Estimates the number of display refreshes that were missed between the previous frame and
+    /// the current frame. This is 0 when the application keeps up with the display, and `None`
+    /// when the refresh rate or either frame time is unknown.
+    pub fn get_dropped_frames(&self) -> Option<u32> {
+        let refresh_rate = self.refresh_rate?;
+        let frame_interval = self.get_frame_interval()?;
+
+        // Rounding is needed because the frame timestamps are never perfectly regular
+        let num_refreshes = (frame_interval.as_secs_f32() * refresh_rate).round();
+        Some((num_refreshes - 1.0).max(0.0) as u32)
+    }
+
+    pub(crate) fn with_refresh_rate(&self, refresh_rate: Option<f32>) -> Self {
+        Self {
+            refresh_rate,
+            previous_frame_time: self.previous_frame_time,
+            frame_time: self.frame_time,
+        }
+    }
+
+    pub(crate) fn next_frame(&self, frame_time: Duration) -> Self {
+        Self {
+            refresh_rate: self.refresh_rate,
+            previous_frame_time: self.frame_time,
+            frame_time: Some(frame_time),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_dropped_frames() {
+        let millis = Duration::from_millis;
+        let timing = FrameTiming::default().with_refresh_rate(Some(120.0));
+        assert_eq!(None, timing.get_frame_interval());
+        assert_eq!(None, timing.get_dropped_frames());
+
+        let timing = timing.next_frame(millis(1000));
+        assert_eq!(Some(millis(1000)), timing.get_frame_time());
+        assert_eq!(None, timing.get_dropped_frames());
+
+        // A frame that arrives right on time (or a little early) didn't drop anything
+        let timing = timing.next_frame(millis(1008));
+        assert_eq!(Some(millis(8)), timing.get_frame_interval());
+        assert_eq!(Some(0), timing.get_dropped_frames());
+        let timing = timing.next_frame(millis(1012));
+        assert_eq!(Some(0), timing.get_dropped_frames());
+
+        // 25 milliseconds is 3 refreshes at 120 Hz, so 2 frames were dropped
+        let timing = timing.next_frame(millis(1037));
+        assert_eq!(Some(millis(1012)), timing.get_previous_frame_time());
+        assert_eq!(Some(2), timing.get_dropped_frames());
+
+        // Without refresh rate, the frame interval is still known
+        let timing = timing.with_refresh_rate(None);
+        assert_eq!(Some(millis(25)), timing.get_frame_interval());
+        assert_eq!(None, timing.get_dropped_frames());
+    }
+}
@@ -0,0 +1,392 @@
+use crate::*;
+
+use std::fmt::Write;
+
+/// The (red, green, blue) outline colors of the components in an annotated layout image, indexed
+/// by the depth of the component (modulo the number of colors)
+const OUTLINE_COLORS: [(u8, u8, u8); 6] = [
+    (230, 25, 75),
+    (60, 180, 75),
+    (0, 130, 200),
+    (245, 130, 48),
+    (145, 30, 180),
+    (240, 50, 230),
+];
+
+/// The bitmaps of the digits 0 to 9 that are used to label the outlines. Each digit consists of
+/// 5 rows (from top to bottom) of 3 bits (from left to right).
+const DIGIT_BITMAPS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+const DIGIT_SCALE: u32 = 2;
+
+/// The pixel rectangle of a single component in a `LayoutMap`
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutMapEntry {
+    id: String,
+    label: usize,
+    type_name: String,
+    depth: usize,
+    min_x: u32,
+    min_y: u32,
+    max_x: u32,
+    max_y: u32,
+}
+
+impl LayoutMapEntry {
+    /// Gets the id of the component, which is the path of child indices from the root component
+    /// to the component. For instance, "root/2/0" is the first child of the third child of the
+    /// root component.
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    /// Gets the number that labels the outline of the component in the annotated image
+    pub fn get_label(&self) -> usize {
+        self.label
+    }
+
+    pub fn get_type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Gets the number of ancestors of the component (0 for the root component)
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Gets the pixel x-coordinate of the left edge of the component (inclusive)
+    pub fn get_min_x(&self) -> u32 {
+        self.min_x
+    }
+
+    /// Gets the pixel y-coordinate of the top edge of the component (inclusive), measured from
+    /// the top of the image
+    pub fn get_min_y(&self) -> u32 {
+        self.min_y
+    }
+
+    /// Gets the pixel x-coordinate of the right edge of the component (exclusive)
+    pub fn get_max_x(&self) -> u32 {
+        self.max_x
+    }
+
+    /// Gets the pixel y-coordinate of the bottom edge of the component (exclusive), measured from
+    /// the top of the image
+    pub fn get_max_y(&self) -> u32 {
+        self.max_y
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.max_x - self.min_x
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.max_y - self.min_y
+    }
+}
+
+/// A map from the components in a component tree to the pixel rectangles they occupy in an image
+/// (typically a screenshot) of the application. This is useful for documentation screenshots and
+/// for external tools that drive the UI. Use `Application.create_layout_map` to create one, or
+/// `Application.export_layout_map` to write an annotated screenshot and the JSON map to files.
+///
+/// Unlike the `Point`s of the `Application`, the pixel rectangles use the convention of images:
+/// the origin is the top-left corner of the image, and the y-axis points down.
+///
+/// Note that the layout map is based on the `ComponentDescription`s of the menus, so it uses the
+/// domains that the menus assigned to their children. This doesn't take scrolling or animations
+/// into account, and menus that don't implement `Component.describe_children` won't show their
+/// children.
+#[derive(Clone, Debug)]
+pub struct LayoutMap {
+    width: u32,
+    height: u32,
+    entries: Vec<LayoutMapEntry>,
+}
+
+impl LayoutMap {
+    /// Computes the layout map of the component tree that is described by *description*, for an
+    /// image of *width* x *height* pixels. The *root_domain* is the domain of the root component
+    /// within the image (which is not the entire image when the UI scale is not 1.0).
+    pub fn new(
+        description: &ComponentDescription,
+        root_domain: ComponentDomain,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let mut layout_map = Self { width, height, entries: Vec::new() };
+        layout_map.add_entries(description, "root".to_string(), 0, root_domain);
+        layout_map
+    }
+
+    fn add_entries(
+        &mut self,
+        description: &ComponentDescription,
+        id: String,
+        depth: usize,
+        domain: ComponentDomain,
+    ) {
+        let to_pixel = |value: f32, size: u32| {
+            (value * size as f32).round().clamp(0.0, size as f32) as u32
+        };
+        let min_x = to_pixel(domain.get_min_x(), self.width);
+        let max_x = to_pixel(domain.get_max_x(), self.width);
+        let min_y = to_pixel(1.0 - domain.get_max_y(), self.height);
+        let max_y = to_pixel(1.0 - domain.get_min_y(), self.height);
+
+        self.entries.push(LayoutMapEntry {
+            label: self.entries.len(),
+            type_name: description.get_type_name().to_string(),
+            depth,
+            min_x,
+            min_y,
+            max_x: max_x.max(min_x),
+            max_y: max_y.max(min_y),
+            id: id.clone(),
+        });
+
+        for (index, child) in description.get_children().iter().enumerate() {
+            // Children without domain (like the content of a tab) cover their entire parent
+            let child_domain = match child.get_domain() {
+                Some(relative) => {
+                    let min = domain.transform_back(Point::new(
+                        relative.get_min_x(), relative.get_min_y()
+                    ));
+                    let max = domain.transform_back(Point::new(
+                        relative.get_max_x(), relative.get_max_y()
+                    ));
+                    ComponentDomain::between(min.get_x(), min.get_y(), max.get_x(), max.get_y())
+                }
+                None => domain,
+            };
+            self.add_entries(child, format!("{}/{}", id, index), depth + 1, child_domain);
+        }
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the entries of all components, in depth-first order (so the root component comes
+    /// first, and each menu comes before its children)
+    pub fn get_entries(&self) -> &[LayoutMapEntry] {
+        &self.entries
+    }
+
+    /// Finds the entry of the component with the given *id* (see `LayoutMapEntry.get_id`)
+    pub fn find(&self, id: &str) -> Option<&LayoutMapEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Draws the outline of each component onto the given *texture*, labeled with the number of
+    /// its entry (see `LayoutMapEntry.get_label`). The outline color depends on the depth of the
+    /// component.
+    ///
+    /// Like the textures captured by `Renderer.capture_frame`, the first row of the *texture*
+    /// should be the bottom row of the image.
+    ///
+    /// ## Panics
+    /// This method panics if the size of the *texture* is not the size of this layout map.
+    pub fn annotate(&self, texture: &mut Texture) {
+        assert_eq!(self.width, texture.get_width());
+        assert_eq!(self.height, texture.get_height());
+
+        let height = self.height;
+        let mut set_pixel = |x: u32, y: u32, color: Color| {
+            if x < texture.get_width() && y < height {
+                texture.set_color(x, height - 1 - y, color);
+            }
+        };
+
+        for entry in &self.entries {
+            if entry.get_width() == 0 || entry.get_height() == 0 {
+                continue;
+            }
+
+            let (red, green, blue) = OUTLINE_COLORS[entry.depth % OUTLINE_COLORS.len()];
+            let color = Color::rgb(red, green, blue);
+            for x in entry.min_x..entry.max_x {
+                set_pixel(x, entry.min_y, color);
+                set_pixel(x, entry.max_y - 1, color);
+            }
+            for y in entry.min_y..entry.max_y {
+                set_pixel(entry.min_x, y, color);
+                set_pixel(entry.max_x - 1, y, color);
+            }
+
+            // Draw the label in the top-left corner, on a background with the outline color
+            let label = entry.label.to_string();
+            let label_width = (4 * label.len() as u32 + 1) * DIGIT_SCALE;
+            let label_height = 7 * DIGIT_SCALE;
+            for x in 0..label_width {
+                for y in 0..label_height {
+                    set_pixel(entry.min_x + x, entry.min_y + y, color);
+                }
+            }
+            for (index, digit) in label.bytes().enumerate() {
+                let bitmap = DIGIT_BITMAPS[(digit - b'0') as usize];
+                let digit_x = entry.min_x + (1 + 4 * index as u32) * DIGIT_SCALE;
+                let digit_y = entry.min_y + DIGIT_SCALE;
+                for (row_index, row) in bitmap.iter().enumerate() {
+                    for column in 0..3 {
+                        if row & (0b100 >> column) != 0 {
+                            for offset_x in 0..DIGIT_SCALE {
+                                for offset_y in 0..DIGIT_SCALE {
+                                    set_pixel(
+                                        digit_x + column * DIGIT_SCALE + offset_x,
+                                        digit_y + row_index as u32 * DIGIT_SCALE + offset_y,
+                                        Color::rgb(255, 255, 255),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts this layout map to a JSON object that contains the size of the image and the
+    /// id, label, type, depth, and pixel rectangle (x, y, width, height) of each component
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = write!(
+            json, "{{\"width\":{},\"height\":{},\"components\":[", self.width, self.height
+        );
+        for (index, entry) in self.entries.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"id\":");
+            write_json_string(&mut json, &entry.id);
+            let _ = write!(json, ",\"label\":{},\"type\":", entry.label);
+            write_json_string(&mut json, &entry.type_name);
+            let _ = write!(
+                json, ",\"depth\":{},\"rect\":[{},{},{},{}]}}",
+                entry.depth, entry.min_x, entry.min_y, entry.get_width(), entry.get_height()
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Writes the given *texture* to a PNG file at the given *file_path*. Since the first row of the
+/// texture is the bottom row of the image, the rows will be written in reverse order.
+pub(crate) fn write_png(texture: &Texture, file_path: &str) -> std::io::Result<()> {
+    let file = std::fs::File::create(std::path::Path::new(file_path))?;
+    let mut encoder = png::Encoder::new(
+        std::io::BufWriter::new(file), texture.get_width(), texture.get_height()
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let pixels = texture.create_pixel_buffer();
+    let row_size = 4 * texture.get_width() as usize;
+    let mut flipped_pixels = Vec::with_capacity(pixels.len());
+    for row in pixels.chunks(row_size.max(1)).rev() {
+        flipped_pixels.extend_from_slice(row);
+    }
+
+    let mut writer = encoder.write_header().map_err(std::io::Error::other)?;
+    writer.write_image_data(&flipped_pixels).map_err(std::io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    fn create_layout_map() -> LayoutMap {
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(ui().label("Inner")),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+
+        let mut menu = SimpleFlatMenu::new(None);
+        let left_label = ui().label("Left");
+        menu.add_component(Box::new(left_label), ComponentDomain::between(0.0, 0.0, 0.5, 1.0));
+        menu.add_component(Box::new(inner_menu), ComponentDomain::between(0.5, 0.5, 1.0, 1.0));
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 100, 40)), false);
+        application.create_layout_map(100, 40)
+    }
+
+    #[test]
+    fn test_rectangles() {
+        let layout_map = create_layout_map();
+        let ids: Vec<_> = layout_map.get_entries().iter().map(|entry| entry.get_id()).collect();
+        assert_eq!(vec!["root", "root/0", "root/1", "root/1/0"], ids);
+
+        let rectangle = |id: &str| {
+            let entry = layout_map.find(id).unwrap();
+            (entry.get_min_x(), entry.get_min_y(), entry.get_width(), entry.get_height())
+        };
+        assert_eq!((0, 0, 100, 40), rectangle("root"));
+        assert_eq!((0, 0, 50, 40), rectangle("root/0"));
+        // The inner menu covers the top-right quarter, which is at the top of the image
+        assert_eq!((50, 0, 50, 20), rectangle("root/1"));
+        assert_eq!((50, 0, 25, 20), rectangle("root/1/0"));
+        assert_eq!(2, layout_map.find("root/1/0").unwrap().get_depth());
+        assert!(layout_map.find("root/2").is_none());
+    }
+
+    #[test]
+    fn test_annotate_and_json() {
+        let layout_map = create_layout_map();
+        let background = Color::rgb(0, 0, 0);
+        let mut texture = Texture::new(100, 40, background);
+        layout_map.annotate(&mut texture);
+
+        // The bottom edge of the left label is at the bottom row of the texture
+        assert_ne!(background, texture.get_color(20, 0));
+        // The bottom edge of the inner label is halfway
+        assert_ne!(background, texture.get_color(60, 20));
+        // But the bottom-right quarter doesn't contain any components besides the root
+        assert_eq!(background, texture.get_color(80, 10));
+
+        let json = layout_map.to_json();
+        assert!(json.starts_with("{\"width\":100,\"height\":40,\"components\":[{\"id\":\"root\","));
+        assert!(json.contains(
+            "{\"id\":\"root/1/0\",\"label\":3,\"type\":\"knukki::components::text::label::\
+            TextLabel\",\"depth\":2,\"rect\":[50,0,25,20]}"
+        ));
+    }
+
+    #[test]
+    fn test_export() {
+        let mut application = Application::new(Box::new(ui().label("Hello")));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 30, 20));
+
+        let directory = std::env::temp_dir();
+        let png_path = directory.join("knukki_test_layout_map.png");
+        let json_path = directory.join("knukki_test_layout_map.json");
+        let layout_map = application.export_layout_map(
+            &renderer, png_path.to_str().unwrap(), json_path.to_str().unwrap()
+        ).unwrap();
+
+        assert_eq!(layout_map.to_json(), std::fs::read_to_string(&json_path).unwrap());
+        let png_bytes = std::fs::read(&png_path).unwrap();
+        assert_eq!(b"\x89PNG", &png_bytes[0..4]);
+
+        std::fs::remove_file(png_path).unwrap();
+        std::fs::remove_file(json_path).unwrap();
+    }
+}
@@ -1,12 +1,28 @@
 use crate::*;
 
+mod accessible;
 mod buddy;
+mod caret;
+mod cursor;
+mod describe;
 mod dummy;
+mod frame_timing;
+mod layout_map;
 mod render;
+mod trace;
+mod window_frame;
 
+pub use accessible::*;
 pub use buddy::*;
+pub use caret::*;
+pub use cursor::*;
+pub use describe::*;
 pub use dummy::*;
+pub use frame_timing::*;
+pub use layout_map::*;
 pub use render::*;
+pub use trace::*;
+pub use window_frame::*;
 
 /// The core trait of this crate. `Component`s are basically event handlers for
 /// gui events like mouse events and keyboard events, but most importantly render
@@ -117,10 +133,168 @@ pub trait Component {
         forgot("MouseLeave")
     }
 
-    fn on_char_type(&mut self, _event: &CharTypeEvent) {
+    fn on_mouse_scroll(&mut self, _event: MouseScrollEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("MouseScroll")
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        _event: MouseDoubleClickEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        forgot("MouseDoubleClick")
+    }
+
+    fn on_mouse_long_press(
+        &mut self,
+        _event: MouseLongPressEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        forgot("MouseLongPress")
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        _event: MouseContextClickEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        forgot("MouseContextClick")
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        _event: MouseMiddleClickEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        forgot("MouseMiddleClick")
+    }
+
+    fn on_mouse_hover(&mut self, _event: MouseHoverEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("MouseHover")
+    }
+
+    fn on_pinch(&mut self, _event: PinchEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("Pinch")
+    }
+
+    fn on_rotate_gesture(&mut self, _event: RotateGestureEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("RotateGesture")
+    }
+
+    fn on_swipe(&mut self, _event: SwipeEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("Swipe")
+    }
+
+    /// Handles a `RelativeMouseMoveEvent`. Components don't need to subscribe for this event,
+    /// but they will only receive it while they hold the pointer lock of the mouse.
+    fn on_relative_mouse_move(
+        &mut self,
+        _event: RelativeMouseMoveEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        forgot("RelativeMouseMove")
+    }
+
+    fn on_char_type(&mut self, _event: &CharTypeEvent, _buddy: &mut dyn ComponentBuddy) {
         forgot("CharType")
     }
 
+    fn on_composition_start(
+        &mut self,
+        _event: &CompositionStartEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        forgot("CompositionStart")
+    }
+
+    fn on_composition_update(
+        &mut self,
+        _event: &CompositionUpdateEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        forgot("CompositionUpdate")
+    }
+
+    fn on_composition_end(&mut self, _event: &CompositionEndEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("CompositionEnd")
+    }
+
+    fn on_tick(&mut self, _event: TickEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("Tick")
+    }
+
+    fn on_theme_change(&mut self, _event: &ThemeChangeEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("ThemeChange")
+    }
+
+    fn on_gamepad_button(&mut self, _event: GamepadButtonEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("GamepadButton")
+    }
+
+    fn on_gamepad_axis(&mut self, _event: GamepadAxisEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("GamepadAxis")
+    }
+
+    fn on_low_memory(&mut self, _event: LowMemoryEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("LowMemory")
+    }
+
+    fn on_window_focus(&mut self, _event: WindowFocusEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("WindowFocus")
+    }
+
+    fn on_window_minimize(&mut self, _event: WindowMinimizeEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("WindowMinimize")
+    }
+
+    /// This method will be called when the user tries to close the window (or browser tab), if
+    /// this component is subscribed for close requests (see `subscribe_close_request` of
+    /// `ComponentBuddy`). Return false to veto the close request (for instance to ask the user to
+    /// save their work first), or true to allow the window to close.
+    fn on_close_request(&mut self, _buddy: &mut dyn ComponentBuddy) -> bool {
+        forgot("CloseRequest")
+    }
+
+    /// Gets the `AccessibleText` of the (child) component at the given *point*, if any. Regular
+    /// components don't need to implement this method since their `AccessibleText` is set via
+    /// their buddy. Menu components should override this to expose the `AccessibleText`s of their
+    /// child components: they should return the `AccessibleText` of the deepest component at
+    /// *point* that has one.
+    fn get_accessible_text_at(&self, _point: Point) -> Option<AccessibleText> {
+        None
+    }
+
+    /// Gets the `CursorIcon` of the (child) component at the given *point*, if any. Like
+    /// `get_accessible_text_at`, regular components don't need to implement this method since
+    /// their cursor is set via their buddy. Menu components should override this to return the
+    /// cursor of the deepest component at *point* that has one.
+    fn get_cursor_at(&self, _point: Point) -> Option<CursorIcon> {
+        None
+    }
+
+    /// Gets the `WindowFrameArea` of the (child) component at the given *point*, if any. This
+    /// works like `get_cursor_at`: regular components don't need to implement this method, but
+    /// menu components should override it to return the area of the deepest component at *point*
+    /// that declared one.
+    fn get_window_frame_area_at(&self, _point: Point) -> Option<WindowFrameArea> {
+        None
+    }
+
+    /// Gets the name of the type of this component, which is used by `ComponentDescription`.
+    /// There is normally no reason to override this method.
+    fn get_type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Describes the child components of this component, which is used by
+    /// `Application.dump_tree`. Regular components don't need to implement this method. Menu
+    /// components should override it to describe each of their children using
+    /// `ComponentDescription::of`, and add the domain, subscriptions, and render result of each
+    /// child if they know it.
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        Vec::new()
+    }
+
     fn on_detach(&mut self) {
         // Components don't register for this event explicitly and many events
         // won't need to implement this, so no need for a panic
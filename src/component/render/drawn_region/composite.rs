@@ -1,4 +1,4 @@
-use crate::{DrawnRegion, LineIntersection, Point};
+use crate::{DrawnRegion, LineIntersection, OvalDrawnRegion, Point, RectangularDrawnRegion};
 
 /// A `DrawnRegion` that is composed of other `DrawnRegion`s (typically more than
 /// 1). Points will be considered *inside* a `CompositeDrawnRegion` if it is
@@ -36,6 +36,48 @@ impl CompositeDrawnRegion {
             top_bound,
         }
     }
+
+    /// Constructs a `CompositeDrawnRegion` in the shape of a rectangle with rounded corners,
+    /// where *radius_x* and *radius_y* are the radii of the corners on the x-axis and y-axis.
+    /// The radii should be at most half of the width and height of the rectangle.
+    pub fn rounded_rectangle(
+        left: f32,
+        bottom: f32,
+        right: f32,
+        top: f32,
+        radius_x: f32,
+        radius_y: f32,
+    ) -> Self {
+        let mut components: Vec<Box<dyn DrawnRegion>> = vec![
+            Box::new(RectangularDrawnRegion::new(
+                left + radius_x,
+                bottom,
+                right - radius_x,
+                top,
+            )),
+            Box::new(RectangularDrawnRegion::new(
+                left,
+                bottom + radius_y,
+                right,
+                top - radius_y,
+            )),
+        ];
+
+        // Ovals with a radius of 0 would cause divisions by zero
+        if radius_x > 0.0 && radius_y > 0.0 {
+            for center_x in &[left + radius_x, right - radius_x] {
+                for center_y in &[bottom + radius_y, top - radius_y] {
+                    components.push(Box::new(OvalDrawnRegion::new(
+                        Point::new(*center_x, *center_y),
+                        radius_x,
+                        radius_y,
+                    )));
+                }
+            }
+        }
+
+        Self::new(components)
+    }
 }
 
 impl DrawnRegion for CompositeDrawnRegion {
@@ -338,4 +380,31 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_rounded_rectangle() {
+        let region = CompositeDrawnRegion::rounded_rectangle(1.0, 2.0, 5.0, 4.0, 1.0, 0.5);
+        assert_eq!(1.0, region.get_left());
+        assert_eq!(2.0, region.get_bottom());
+        assert_eq!(5.0, region.get_right());
+        assert_eq!(4.0, region.get_top());
+
+        // The center and the middle of the edges should be inside
+        assert!(region.is_inside(Point::new(3.0, 3.0)));
+        assert!(region.is_inside(Point::new(1.0, 3.0)));
+        assert!(region.is_inside(Point::new(3.0, 2.0)));
+        assert!(region.is_inside(Point::new(5.0, 3.0)));
+        assert!(region.is_inside(Point::new(3.0, 4.0)));
+
+        // But the sharp corners should not
+        assert!(!region.is_inside(Point::new(1.05, 2.05)));
+        assert!(!region.is_inside(Point::new(4.95, 2.05)));
+        assert!(!region.is_inside(Point::new(1.05, 3.95)));
+        assert!(!region.is_inside(Point::new(4.95, 3.95)));
+
+        // Without radius, the corners should be inside
+        let sharp = CompositeDrawnRegion::rounded_rectangle(1.0, 2.0, 5.0, 4.0, 0.0, 0.0);
+        assert!(sharp.is_inside(Point::new(1.05, 2.05)));
+        assert!(sharp.is_inside(Point::new(4.95, 3.95)));
+    }
 }
@@ -29,6 +29,65 @@ pub enum LineIntersection {
 }
 
 impl LineIntersection {
+    /// Adjusts this intersection for occlusion by other (overlapping) components. The
+    /// *was_inside* parameter indicates whether the mouse was hovering the region before the
+    /// movement (it received an enter event, but no leave event yet), and *occluded* indicates
+    /// whether another component covers the ending point of the line (which only matters when
+    /// the line ends inside the region). The *from* and *to* parameters are the starting point
+    /// and ending point of the line.
+    ///
+    /// The result is always consistent with *was_inside*: it starts inside if and only if
+    /// *was_inside* is true. When the mouse becomes occluded, the result will exit the region at
+    /// *to*. When the mouse is revealed (it starts inside the region without hovering it), the
+    /// result will enter the region at *from*.
+    pub fn with_occlusion(self, was_inside: bool, occluded: bool, from: Point, to: Point) -> Self {
+        if occluded && self.ends_inside() {
+            return match was_inside {
+                true => Self::Exits { point: to },
+                false => Self::FullyOutside,
+            };
+        }
+        match (was_inside, self) {
+            (true, Self::FullyInside | Self::Enters { .. }) => Self::FullyInside,
+            (true, Self::Crosses { exit, .. }) => Self::Exits { point: exit },
+            (true, Self::FullyOutside) => Self::Exits { point: from },
+            (false, Self::FullyInside) => Self::Enters { point: from },
+            (false, Self::Exits { point }) => Self::Crosses {
+                entrance: from,
+                exit: point,
+            },
+            (_, unchanged) => unchanged,
+        }
+    }
+
+    /// Adjusts this intersection for mouse-leave hysteresis. When the mouse was already *inside*
+    /// the region before the movement (it received an enter event, but no leave event yet), it
+    /// should only leave the region once it moved past the hysteresis margin around it. The
+    /// *end_is_near* parameter indicates whether the ending point of the line is inside the
+    /// region or its margin, and *from* is the starting point of the line.
+    ///
+    /// When the mouse was inside, the result will never be `Enters` or `Crosses`, since the
+    /// mouse shouldn't enter the region twice. When the mouse wasn't inside, this intersection is
+    /// returned unchanged.
+    pub fn with_hysteresis(self, was_inside: bool, end_is_near: bool, from: Point) -> Self {
+        if !was_inside {
+            return self;
+        }
+        match self {
+            Self::FullyInside | Self::Enters { .. } => Self::FullyInside,
+            _ if end_is_near => Self::FullyInside,
+            Self::Exits { point } => Self::Exits { point },
+            Self::Crosses { exit, .. } => Self::Exits { point: exit },
+            // The mouse was still within the margin at the start of the line
+            Self::FullyOutside => Self::Exits { point: from },
+        }
+    }
+
+    /// Checks whether the ending point of the line is inside the drawn region
+    pub fn ends_inside(&self) -> bool {
+        matches!(self, Self::FullyInside | Self::Enters { .. })
+    }
+
     #[cfg(test)]
     pub(crate) fn nearly_equal(&self, other: LineIntersection) -> bool {
         return match self {
@@ -62,3 +121,36 @@ impl LineIntersection {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_with_hysteresis() {
+        let from = Point::new(1.0, 0.5);
+        let point = Point::new(0.9, 0.5);
+        let exits = LineIntersection::Exits { point };
+        let crosses = LineIntersection::Crosses { entrance: from, exit: point };
+        let outside = LineIntersection::FullyOutside;
+
+        // Nothing should change when the mouse wasn't inside
+        assert_eq!(crosses, crosses.with_hysteresis(false, true, from));
+        assert_eq!(outside, outside.with_hysteresis(false, false, from));
+
+        // The mouse shouldn't leave while it's still within the margin
+        assert_eq!(LineIntersection::FullyInside, exits.with_hysteresis(true, true, from));
+        assert_eq!(LineIntersection::FullyInside, outside.with_hysteresis(true, true, from));
+
+        // The mouse shouldn't enter twice
+        let enters = LineIntersection::Enters { point };
+        assert_eq!(LineIntersection::FullyInside, enters.with_hysteresis(true, true, from));
+        assert_eq!(exits, crosses.with_hysteresis(true, false, from));
+
+        // When the mouse moves past the margin, it should leave where it was last seen
+        let leave_at_from = LineIntersection::Exits { point: from };
+        assert_eq!(leave_at_from, outside.with_hysteresis(true, false, from));
+        assert_eq!(exits, exits.with_hysteresis(true, false, from));
+    }
+}
@@ -80,6 +80,21 @@ pub trait DrawnRegion {
             && point.get_y() <= self.get_top()
     }
 
+    /// Checks if `point` is inside this region, or within the *bounds* of this region after they
+    /// have been expanded by `margin_x` on the left and right side and by `margin_y` on the bottom
+    /// and top side. This is used for the mouse-leave hysteresis of the `Application` and the
+    /// `SimpleFlatMenu`.
+    fn is_near(&self, point: Point, margin_x: f32, margin_y: f32) -> bool {
+        if self.is_inside(point) {
+            return true;
+        }
+        (margin_x > 0.0 || margin_y > 0.0)
+            && point.get_x() >= self.get_left() - margin_x
+            && point.get_x() <= self.get_right() + margin_x
+            && point.get_y() >= self.get_bottom() - margin_y
+            && point.get_y() <= self.get_top() + margin_y
+    }
+
     /// Gets the width of this region. This is simply the result of subtracting
     /// the left bound from the right bound.
     fn get_width(&self) -> f32 {
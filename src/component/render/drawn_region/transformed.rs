@@ -26,18 +26,38 @@ impl<T: Clone + Fn(Point) -> Point + 'static, B: Clone + Fn(Point) -> Point + 's
         let test_point = Point::new(81.37, -35.71);
         assert!(test_point.nearly_equal(transform_back_function(transform_function(test_point))));
 
-        // Use the transform back function to compute the transformed bounds
-        let bottom_left =
-            transform_back_function(Point::new(region.get_left(), region.get_bottom()));
-        let top_right = transform_back_function(Point::new(region.get_right(), region.get_top()));
+        // Empty regions (like an empty CompositeDrawnRegion) have infinite bounds in the wrong
+        // order, which must not be transformed into infinite bounds in the right order
+        if region.get_left() > region.get_right() || region.get_bottom() > region.get_top() {
+            return Self {
+                region,
+                transform_function,
+                transform_back_function,
+                left_bound: f32::INFINITY,
+                bottom_bound: f32::INFINITY,
+                right_bound: f32::NEG_INFINITY,
+                top_bound: f32::NEG_INFINITY,
+            };
+        }
+
+        // Use the transform back function to compute the transformed bounds. All 4 corners are
+        // needed because rotations can map any of them to the outermost position.
+        let corners = [
+            transform_back_function(Point::new(region.get_left(), region.get_bottom())),
+            transform_back_function(Point::new(region.get_right(), region.get_bottom())),
+            transform_back_function(Point::new(region.get_left(), region.get_top())),
+            transform_back_function(Point::new(region.get_right(), region.get_top())),
+        ];
+        let xs = corners.iter().map(|corner| corner.get_x());
+        let ys = corners.iter().map(|corner| corner.get_y());
         Self {
             region,
             transform_function,
             transform_back_function,
-            left_bound: f32::min(bottom_left.get_x(), top_right.get_x()),
-            bottom_bound: f32::min(bottom_left.get_y(), top_right.get_y()),
-            right_bound: f32::max(bottom_left.get_x(), top_right.get_x()),
-            top_bound: f32::max(bottom_left.get_y(), top_right.get_y()),
+            left_bound: xs.clone().fold(f32::INFINITY, f32::min),
+            bottom_bound: ys.clone().fold(f32::INFINITY, f32::min),
+            right_bound: xs.fold(f32::NEG_INFINITY, f32::max),
+            top_bound: ys.fold(f32::NEG_INFINITY, f32::max),
         }
     }
 }
@@ -138,6 +158,20 @@ mod tests {
         assert_eq!(0.0, transformed_region.get_top());
     }
 
+    #[test]
+    fn test_empty_region() {
+        let empty_region = Box::new(CompositeDrawnRegion::new(Vec::new()));
+        let transform_function = |point: Point| Point::new(-point.get_x(), -point.get_y());
+        let transformed_region = TransformedDrawnRegion::new(
+            empty_region,
+            transform_function,
+            transform_function,
+        );
+        assert!(transformed_region.get_left() > transformed_region.get_right());
+        assert!(transformed_region.get_bottom() > transformed_region.get_top());
+        assert!(!transformed_region.is_inside(Point::new(0.0, 0.0)));
+    }
+
     #[test]
     fn test_find_line_intersection() {
         let original_region = Box::new(RectangularDrawnRegion::new(0.0, 1.0, 3.0, 2.0));
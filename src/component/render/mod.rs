@@ -1,7 +1,9 @@
 mod drawn_region;
 mod region;
 mod result;
+mod transform;
 
 pub use drawn_region::*;
 pub use region::*;
 pub use result::*;
+pub use transform::*;
@@ -1,3 +1,5 @@
+use crate::DrawnRegion;
+
 /// Describe the region of the viewport where a `Component` is allowed to render
 /// itself. This is normally the *domain* of the component.
 ///
@@ -138,6 +140,13 @@ impl RenderRegion {
     /// );
     /// assert!(region.child_region(0.0, 0.0, 0.001, 0.001).is_none());
     /// ```
+    ///
+    /// ### Seams
+    /// Each relative coordinate is converted to a pixel coordinate on its own, so adjacent
+    /// child regions that share a boundary will also share the pixel boundary: there won't be any
+    /// gaps or overlaps between them. This also holds when the boundary of one child is computed
+    /// slightly differently than the boundary of the next child (for instance `0.1 + 0.2` versus
+    /// `0.3`), since tiny floating point errors are ignored.
     pub fn child_region(
         &self,
         relative_min_x: f32,
@@ -145,11 +154,81 @@ impl RenderRegion {
         relative_max_x: f32,
         relative_max_y: f32,
     ) -> Option<Self> {
-        let min_x = self.get_min_x() + (self.get_width() as f32 * relative_min_x).round() as u32;
-        let min_y = self.get_min_y() + (self.get_height() as f32 * relative_min_y).round() as u32;
+        let min_x = self.get_min_x() + relative_to_pixels(self.get_width(), relative_min_x);
+        let min_y = self.get_min_y() + relative_to_pixels(self.get_height(), relative_min_y);
+
+        let bound_x = self.get_min_x() + relative_to_pixels(self.get_width(), relative_max_x);
+        let bound_y = self.get_min_y() + relative_to_pixels(self.get_height(), relative_max_y);
+
+        if bound_x > min_x && bound_y > min_y {
+            Some(Self::between(min_x, min_y, bound_x, bound_y))
+        } else {
+            None
+        }
+    }
+
+    /// Like `child_region`, but the relative coordinates won't be clamped to this region, so the
+    /// child can extend below or left of this region (and even below or left of pixel 0). That's
+    /// why this returns the signed pixel bounds `(min_x, min_y, bound_x, bound_y)` rather than a
+    /// `RenderRegion`. This is used by `Renderer.push_viewport`.
+    pub(crate) fn unclamped_child_bounds(
+        &self,
+        relative_min_x: f32,
+        relative_min_y: f32,
+        relative_max_x: f32,
+        relative_max_y: f32,
+    ) -> Option<(i64, i64, i64, i64)> {
+        let (width, height) = (self.get_width(), self.get_height());
+        let (own_x, own_y) = (self.get_min_x() as i64, self.get_min_y() as i64);
+        let min_x = own_x + relative_to_signed_pixels(width, relative_min_x);
+        let min_y = own_y + relative_to_signed_pixels(height, relative_min_y);
+
+        let bound_x = own_x + relative_to_signed_pixels(width, relative_max_x);
+        let bound_y = own_y + relative_to_signed_pixels(height, relative_max_y);
+
+        if bound_x > min_x && bound_y > min_y {
+            Some((min_x, min_y, bound_x, bound_y))
+        } else {
+            None
+        }
+    }
+
+    /// Like `child_region`, but rounds *conservatively*: the returned region contains every pixel
+    /// that is (partially) covered by the given relative coordinates, so the minimum coordinates
+    /// are rounded down and the maximum coordinates are rounded up. The result is clamped to this
+    /// region.
+    ///
+    /// This is useful for scissoring during partial redraws: using `child_region` for that could
+    /// cut off the pixels at the edges that are only partially covered.
+    ///
+    /// ### Examples
+    /// ```
+    /// use knukki::RenderRegion;
+    ///
+    /// let region = RenderRegion::between(20, 20, 30, 30);
+    /// assert_eq!(
+    ///     Some(RenderRegion::between(21, 22, 28, 30)),
+    ///     region.conservative_child_region(0.12, 0.23, 0.71, 1.5)
+    /// );
+    /// assert_eq!(
+    ///     Some(RenderRegion::between(20, 20, 21, 21)),
+    ///     region.conservative_child_region(0.0, 0.0, 0.001, 0.001)
+    /// );
+    /// ```
+    pub fn conservative_child_region(
+        &self,
+        relative_min_x: f32,
+        relative_min_y: f32,
+        relative_max_x: f32,
+        relative_max_y: f32,
+    ) -> Option<Self> {
+        let width = self.get_width();
+        let height = self.get_height();
+        let min_x = self.get_min_x() + relative_to_pixels_floor(width, relative_min_x);
+        let min_y = self.get_min_y() + relative_to_pixels_floor(height, relative_min_y);
 
-        let bound_x = self.get_min_x() + (self.get_width() as f32 * relative_max_x).round() as u32;
-        let bound_y = self.get_min_y() + (self.get_height() as f32 * relative_max_y).round() as u32;
+        let bound_x = self.get_min_x() + relative_to_pixels_ceil(width, relative_max_x);
+        let bound_y = self.get_min_y() + relative_to_pixels_ceil(height, relative_max_y);
 
         if bound_x > min_x && bound_y > min_y {
             Some(Self::between(min_x, min_y, bound_x, bound_y))
@@ -158,6 +237,41 @@ impl RenderRegion {
         }
     }
 
+    /// Rounds the given *relative_x* coordinate (relative to this region) to the nearest edge
+    /// between 2 pixels of this region. This is used for pixel snapping (see
+    /// `Renderer.set_pixel_snapping`).
+    ///
+    /// ```
+    /// use knukki::*;
+    ///
+    /// let region = RenderRegion::with_size(5, 0, 10, 4);
+    /// assert_eq!(0.3, region.snap_x(0.33));
+    /// assert_eq!(0.5, region.snap_y(0.4));
+    /// ```
+    pub fn snap_x(&self, relative_x: f32) -> f32 {
+        let width = self.get_width() as f32;
+        (relative_x * width).round() / width
+    }
+
+    /// Rounds the given *relative_y* coordinate (relative to this region) to the nearest edge
+    /// between 2 pixels of this region. See `snap_x`.
+    pub fn snap_y(&self, relative_y: f32) -> f32 {
+        let height = self.get_height() as f32;
+        (relative_y * height).round() / height
+    }
+
+    /// Computes the smallest region that contains all pixels of this region that could be covered
+    /// by the given `DrawnRegion` (based on its bounds), assuming that this region is the viewport
+    /// of the component that drew it. See `conservative_child_region`.
+    pub fn drawn_region_bounds(&self, drawn_region: &dyn DrawnRegion) -> Option<Self> {
+        self.conservative_child_region(
+            drawn_region.get_left(),
+            drawn_region.get_bottom(),
+            drawn_region.get_right(),
+            drawn_region.get_top(),
+        )
+    }
+
     /// Computes the intersection of this region with the other region. That is, a new `RenderRegion`
     /// that covers the region where this region intersects/overlaps the other region. If this
     /// region doesn't have any overlap with the other region, this method returns `None`.
@@ -213,6 +327,41 @@ impl RenderRegion {
     }
 }
 
+const STEPS_PER_PIXEL: i64 = 256;
+
+/// Converts the relative coordinate *relative* within a region of *size* pixels to the number of
+/// 1/256 pixel steps between the start of the region and the coordinate. Snapping to these steps
+/// ensures that values within 1/512 pixel of each other are considered equal, so that floating
+/// point errors won't cause seams between regions that should be adjacent.
+fn relative_to_steps(size: u32, relative: f32) -> i64 {
+    // Use f64 to avoid introducing more errors
+    (size as f64 * relative as f64 * STEPS_PER_PIXEL as f64).round() as i64
+}
+
+/// Rounds the relative coordinate to the nearest pixel boundary
+fn relative_to_pixels(size: u32, relative: f32) -> u32 {
+    relative_to_signed_pixels(size, relative).max(0) as u32
+}
+
+/// Rounds the relative coordinate to the nearest pixel boundary, without clamping it to 0
+fn relative_to_signed_pixels(size: u32, relative: f32) -> i64 {
+    let steps = relative_to_steps(size, relative);
+    (steps + STEPS_PER_PIXEL / 2).div_euclid(STEPS_PER_PIXEL)
+}
+
+/// Rounds the relative coordinate down to a pixel boundary, and clamps it between 0 and *size*
+fn relative_to_pixels_floor(size: u32, relative: f32) -> u32 {
+    let pixels = relative_to_steps(size, relative).div_euclid(STEPS_PER_PIXEL);
+    pixels.max(0).min(size as i64) as u32
+}
+
+/// Rounds the relative coordinate up to a pixel boundary, and clamps it between 0 and *size*
+fn relative_to_pixels_ceil(size: u32, relative: f32) -> u32 {
+    let steps = relative_to_steps(size, relative);
+    let pixels = (steps + STEPS_PER_PIXEL - 1).div_euclid(STEPS_PER_PIXEL);
+    pixels.max(0).min(size as i64) as u32
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -273,6 +422,47 @@ mod tests {
         assert!(mini_region.child_region(0.1, 0.1, 0.4, 0.4).is_none());
     }
 
+    #[test]
+    fn test_unclamped_child_bounds() {
+        let parent = RenderRegion::between(0, 10, 10, 20);
+        assert_eq!(
+            Some((-5, -10, 15, 20)),
+            parent.unclamped_child_bounds(-0.5, -2.0, 1.5, 1.0)
+        );
+        assert_eq!(Some((2, 12, 5, 20)), parent.unclamped_child_bounds(0.2, 0.2, 0.5, 1.0));
+        assert!(parent.unclamped_child_bounds(-0.3, 0.0, -0.3, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_conservative_child_region() {
+        let parent = RenderRegion::between(100, 200, 110, 210);
+        assert_eq!(
+            Some(RenderRegion::between(102, 203, 108, 207)),
+            parent.conservative_child_region(0.25, 0.3, 0.75, 0.65)
+        );
+
+        // Edges that are (nearly) on a pixel boundary shouldn't be expanded
+        assert_eq!(
+            Some(RenderRegion::between(101, 203, 105, 210)),
+            parent.conservative_child_region(0.1, 0.1 + 0.2, 0.5, 1.0)
+        );
+        assert_eq!(Some(parent), parent.conservative_child_region(-0.5, -0.1, 1.2, 3.0));
+
+        // Tiny regions should still cover a pixel
+        assert_eq!(
+            Some(RenderRegion::with_size(109, 200, 1, 1)),
+            parent.conservative_child_region(0.95, 0.0, 0.96, 0.01)
+        );
+        assert!(parent.conservative_child_region(0.5, 0.5, 0.5, 0.7).is_none());
+        assert!(parent.conservative_child_region(1.0, 0.0, 1.5, 1.0).is_none());
+
+        let drawn_region = OvalDrawnRegion::new(Point::new(0.5, 0.5), 0.22, 0.31);
+        assert_eq!(
+            Some(RenderRegion::between(102, 201, 108, 209)),
+            parent.drawn_region_bounds(&drawn_region)
+        );
+    }
+
     #[test]
     fn test_intersection() {
         let region1 = RenderRegion::between(0, 0, 20, 20);
@@ -298,4 +488,36 @@ mod tests {
             region1.intersection(region_mini)
         );
     }
+
+    #[test]
+    fn test_child_region_seams() {
+        // Splits the part of the parent after *start* into *num_parts* parts (computed like
+        // `ComponentDomain::with_size` would) and checks that there are no gaps or overlaps
+        let check = |parent: RenderRegion, start: f32, num_parts: u32| {
+            let part_size = (1.0 - start) / num_parts as f32;
+            let mut next_min = parent
+                .child_region(0.0, 0.0, 1.0, start)
+                .map(|region| region.get_bound_y())
+                .unwrap_or(parent.get_min_y());
+
+            for index in 0..num_parts {
+                let min = start + index as f32 * part_size;
+                let max = min + part_size;
+                if let Some(child) = parent.child_region(0.0, min, 1.0, max) {
+                    assert_eq!(next_min, child.get_min_y(), "{:?} {} {}", parent, start, index);
+                    next_min = child.get_bound_y();
+                }
+            }
+            assert_eq!(parent.get_bound_y(), next_min, "{:?} {}", parent, start);
+        };
+
+        for height in 1..300 {
+            let parent = RenderRegion::with_size(3, 17, 5, height);
+            for num_parts in 1..20 {
+                for start in &[0.0, 0.1, 0.25, 0.3, 1.0 / 3.0, 0.7] {
+                    check(parent, *start, num_parts);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,235 @@
+use crate::*;
+
+/// Represents an affine 2d transformation from the *local* coordinate space of a (possibly
+/// rotated) child component to the *outer* coordinate space of its parent.
+///
+/// Menus normally give each child an axis-aligned `ComponentDomain`, which can't describe rotated
+/// children. This struct can be used by menus (or other components) that want to draw rotated
+/// content: its `transform` methods map the points of mouse events from the outer space to the
+/// local space of the child, and its `transform_back` methods map points and drawn regions of the
+/// child back to the outer space.
+///
+/// ### Aspect ratio
+/// The rotation angles are applied in the outer coordinate space, in which both the width and the
+/// height of the parent are 1.0. When the parent is not square, rotated children will thus look
+/// sheared.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform2D {
+    // outer_x = a * local_x + b * local_y + offset_x
+    // outer_y = c * local_x + d * local_y + offset_y
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    offset_x: f32,
+    offset_y: f32,
+}
+
+impl Transform2D {
+    /// Constructs the transformation that doesn't change any points
+    pub fn identity() -> Self {
+        Self { a: 1.0, b: 0.0, c: 0.0, d: 1.0, offset_x: 0.0, offset_y: 0.0 }
+    }
+
+    /// Constructs the transformation that maps the local space of a child onto the given *domain*
+    /// of its parent, just like the `transform_back` method of `ComponentDomain`.
+    pub fn domain(domain: ComponentDomain) -> Self {
+        Self {
+            a: domain.get_width(),
+            b: 0.0,
+            c: 0.0,
+            d: domain.get_height(),
+            offset_x: domain.get_min_x(),
+            offset_y: domain.get_min_y(),
+        }
+    }
+
+    /// Constructs the transformation that rotates points counter-clockwise around the given
+    /// *center* by the given *angle* (in radians).
+    pub fn rotation(center: Point, angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            a: cos,
+            b: -sin,
+            c: sin,
+            d: cos,
+            offset_x: center.get_x() - cos * center.get_x() + sin * center.get_y(),
+            offset_y: center.get_y() - sin * center.get_x() - cos * center.get_y(),
+        }
+    }
+
+    /// Constructs the transformation that maps the local space of a child onto the given *domain*
+    /// of its parent, and then rotates it counter-clockwise around the center of that domain by
+    /// the given *angle* (in radians).
+    pub fn rotated_domain(domain: ComponentDomain, angle: f32) -> Self {
+        let center = Point::new(
+            domain.get_min_x() + 0.5 * domain.get_width(),
+            domain.get_min_y() + 0.5 * domain.get_height(),
+        );
+        Self::domain(domain).then(Self::rotation(center, angle))
+    }
+
+    /// Constructs the transformation that first applies this transformation, and then applies
+    /// the *outer* transformation. This is useful for nested components: use the transformation
+    /// of the innermost child as `self`.
+    pub fn then(&self, outer: Transform2D) -> Self {
+        Self {
+            a: outer.a * self.a + outer.b * self.c,
+            b: outer.a * self.b + outer.b * self.d,
+            c: outer.c * self.a + outer.d * self.c,
+            d: outer.c * self.b + outer.d * self.d,
+            offset_x: outer.a * self.offset_x + outer.b * self.offset_y + outer.offset_x,
+            offset_y: outer.c * self.offset_x + outer.d * self.offset_y + outer.offset_y,
+        }
+    }
+
+    /// Maps the given *outer* point to the local coordinate space of the child
+    pub fn transform(&self, outer: Point) -> Point {
+        let determinant = self.a * self.d - self.b * self.c;
+        let relative_x = outer.get_x() - self.offset_x;
+        let relative_y = outer.get_y() - self.offset_y;
+        Point::new(
+            (self.d * relative_x - self.b * relative_y) / determinant,
+            (self.a * relative_y - self.c * relative_x) / determinant,
+        )
+    }
+
+    /// Maps the given *local* point of the child to the outer coordinate space
+    pub fn transform_back(&self, local: Point) -> Point {
+        Point::new(
+            self.a * local.get_x() + self.b * local.get_y() + self.offset_x,
+            self.c * local.get_x() + self.d * local.get_y() + self.offset_y,
+        )
+    }
+
+    /// Checks whether the given *outer* point lies inside the (possibly rotated) domain of the
+    /// child. This should be used instead of `ComponentDomain.is_inside` to decide whether a mouse
+    /// event should be passed to a rotated child.
+    pub fn is_inside(&self, outer: Point) -> bool {
+        let local = self.transform(outer);
+        local.get_x() >= 0.0 && local.get_x() <= 1.0 && local.get_y() >= 0.0 && local.get_y() <= 1.0
+    }
+
+    /// Maps the given `DrawnRegion` of the child to the outer coordinate space. The bounds of the
+    /// result will be the axis-aligned bounding box of the rotated region.
+    pub fn transform_back_drawn_region(&self, local: Box<dyn DrawnRegion>) -> Box<dyn DrawnRegion> {
+        let transform = *self;
+        Box::new(TransformedDrawnRegion::new(
+            local,
+            move |point| transform.transform(point),
+            move |point| transform.transform_back(point),
+        ))
+    }
+
+    pub fn transform_click_event(&self, event: MouseClickEvent) -> MouseClickEvent {
+        MouseClickEvent::new(
+            event.get_mouse(),
+            self.transform(event.get_point()),
+            event.get_button(),
+        )
+    }
+
+    pub fn transform_press_event(&self, event: MousePressEvent) -> MousePressEvent {
+        MousePressEvent::with_details(
+            event.get_mouse(),
+            self.transform(event.get_point()),
+            event.get_button(),
+            event.get_details(),
+        )
+    }
+
+    pub fn transform_release_event(&self, event: MouseReleaseEvent) -> MouseReleaseEvent {
+        MouseReleaseEvent::with_details(
+            event.get_mouse(),
+            self.transform(event.get_point()),
+            event.get_button(),
+            event.get_details(),
+        )
+    }
+
+    pub fn transform_move_event(&self, event: MouseMoveEvent) -> MouseMoveEvent {
+        MouseMoveEvent::with_details(
+            event.get_mouse(),
+            self.transform(event.get_from()),
+            self.transform(event.get_to()),
+            event.get_details(),
+        )
+    }
+
+    pub fn transform_enter_event(&self, event: MouseEnterEvent) -> MouseEnterEvent {
+        MouseEnterEvent::new(event.get_mouse(), self.transform(event.get_entrance_point()))
+    }
+
+    pub fn transform_leave_event(&self, event: MouseLeaveEvent) -> MouseLeaveEvent {
+        MouseLeaveEvent::new(event.get_mouse(), self.transform(event.get_exit_point()))
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_rotated_domain() {
+        // A child that occupies the left half of its parent and is rotated by 90 degrees
+        let transform =
+            Transform2D::rotated_domain(ComponentDomain::between(0.0, 0.0, 0.5, 1.0), FRAC_PI_2);
+
+        // The bottom-left corner of the child should end up at the bottom-right of the rotated
+        // domain, which covers x from -0.25 to 0.75 and y from 0.25 to 0.75
+        let bottom_left = transform.transform_back(Point::new(0.0, 0.0));
+        assert!(Point::new(0.75, 0.25).nearly_equal(bottom_left));
+        assert!(Point::new(0.0, 0.0).nearly_equal(transform.transform(bottom_left)));
+
+        for point in &[Point::new(0.3, 0.7), Point::new(-1.0, 2.0), Point::new(0.5, 0.5)] {
+            assert!(point.nearly_equal(transform.transform(transform.transform_back(*point))));
+        }
+
+        assert!(transform.is_inside(Point::new(0.7, 0.3)));
+        assert!(!transform.is_inside(Point::new(0.2, 0.1)));
+        assert!(!transform.is_inside(Point::new(0.7, 0.8)));
+
+        let mouse = Mouse::new(0);
+        let event = transform.transform_move_event(
+            MouseMoveEvent::new(mouse, Point::new(0.75, 0.25), Point::new(0.75, 0.75))
+        );
+        assert!(Point::new(0.0, 0.0).nearly_equal(event.get_from()));
+        assert!(Point::new(1.0, 0.0).nearly_equal(event.get_to()));
+    }
+
+    #[test]
+    fn test_transform_back_drawn_region() {
+        let transform = Transform2D::rotation(Point::new(0.5, 0.5), 0.25 * std::f32::consts::PI);
+        let region = transform.transform_back_drawn_region(
+            Box::new(RectangularDrawnRegion::new(0.25, 0.25, 0.75, 0.75))
+        );
+
+        // The bounds should contain the corners of the rotated square
+        let half_diagonal = 0.25 * 2f32.sqrt();
+        assert!((region.get_left() - (0.5 - half_diagonal)).abs() < 0.001);
+        assert!((region.get_top() - (0.5 + half_diagonal)).abs() < 0.001);
+
+        assert!(region.is_inside(Point::new(0.5, 0.8)));
+        assert!(!region.is_inside(Point::new(0.27, 0.27)));
+        assert!(region.is_inside(Point::new(0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_then() {
+        let inner = Transform2D::domain(ComponentDomain::between(0.5, 0.0, 1.0, 0.5));
+        let outer = Transform2D::domain(ComponentDomain::between(0.0, 0.5, 0.5, 1.0));
+        let combined = inner.then(outer);
+        assert!(Point::new(0.25, 0.5).nearly_equal(combined.transform_back(Point::new(0.0, 0.0))));
+        assert!(Point::new(0.5, 0.75).nearly_equal(combined.transform_back(Point::new(1.0, 1.0))));
+        assert_eq!(Transform2D::identity(), Transform2D::default());
+    }
+}
@@ -0,0 +1,136 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+
+/// The outcome of the hit test of a single component for a single mouse event. See
+/// `Application.start_hit_test_trace` for more information.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum HitTestOutcome {
+    /// The event was passed to the component
+    Delivered,
+    /// The component is not subscribed for this type of event
+    NotSubscribed,
+    /// The component hasn't been rendered yet, so it can't receive mouse events yet
+    NotRenderedYet,
+    /// The event happened outside the domain of the component
+    OutsideDomain,
+    /// The event happened inside the domain of the component, but another component with an
+    /// overlapping domain received it instead
+    Occluded,
+    /// The event happened inside the domain of the component, but outside the region it has drawn
+    /// (and the component uses `filter_mouse_actions`)
+    OutsideDrawnRegion,
+    /// The `SeatPolicy` of the component doesn't accept the mouse of the event
+    RejectedBySeatPolicy,
+    /// The component captured the mouse of the event, so it received the event regardless of its
+    /// position
+    Captured,
+    /// The component is hidden (see `SimpleFlatMenu.set_component_visible`)
+    Hidden,
+}
+
+/// A single entry of a hit test trace: it tells what happened to 1 mouse event for 1 component.
+///
+/// The *path* identifies the component: the root component has an empty path, its child
+/// components have paths of length 1 (containing their index in the root menu), their children
+/// have paths of length 2, and so on.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HitTestRecord {
+    event_name: &'static str,
+    path: Vec<usize>,
+    outcome: HitTestOutcome,
+}
+
+impl HitTestRecord {
+    /// Gets the name of the type of the event, for instance "MouseClick"
+    pub fn get_event_name(&self) -> &'static str {
+        self.event_name
+    }
+
+    pub fn get_path(&self) -> &[usize] {
+        &self.path
+    }
+
+    pub fn get_outcome(&self) -> HitTestOutcome {
+        self.outcome
+    }
+}
+
+impl Display for HitTestRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {:?}: {:?}", self.event_name, self.path, self.outcome)
+    }
+}
+
+struct HitTestTrace {
+    current_path: Vec<usize>,
+    records: Vec<HitTestRecord>,
+}
+
+thread_local! {
+    static HIT_TEST_TRACE: RefCell<Option<HitTestTrace>> = RefCell::new(None);
+}
+
+pub(crate) fn start_hit_test_trace() {
+    HIT_TEST_TRACE.with(|trace| {
+        *trace.borrow_mut() = Some(HitTestTrace {
+            current_path: Vec::new(),
+            records: Vec::new(),
+        });
+    });
+}
+
+pub(crate) fn finish_hit_test_trace() -> Vec<HitTestRecord> {
+    HIT_TEST_TRACE.with(|trace| match trace.borrow_mut().take() {
+        Some(trace) => trace.records,
+        None => Vec::new(),
+    })
+}
+
+pub(crate) fn is_hit_test_trace_enabled() -> bool {
+    HIT_TEST_TRACE.with(|trace| trace.borrow().is_some())
+}
+
+/// Records the outcome of the current component (or the child with the given index if
+/// *child_index* is `Some`) for an event, if tracing is enabled.
+pub(crate) fn trace_hit_test(
+    event_name: &'static str,
+    child_index: Option<usize>,
+    outcome: HitTestOutcome,
+) {
+    HIT_TEST_TRACE.with(|trace| {
+        if let Some(trace) = trace.borrow_mut().as_mut() {
+            let mut path = trace.current_path.clone();
+            if let Some(child_index) = child_index {
+                path.push(child_index);
+            }
+            trace.records.push(HitTestRecord {
+                event_name,
+                path,
+                outcome,
+            });
+        }
+    });
+}
+
+/// Runs *action* while the child with the given index is the current component of the trace. Menu
+/// components should use this when they propagate an event to one of their children.
+pub(crate) fn with_hit_test_child<R>(child_index: usize, action: impl FnOnce() -> R) -> R {
+    let enabled = HIT_TEST_TRACE.with(|trace| match trace.borrow_mut().as_mut() {
+        Some(trace) => {
+            trace.current_path.push(child_index);
+            true
+        }
+        None => false,
+    });
+
+    let result = action();
+
+    if enabled {
+        HIT_TEST_TRACE.with(|trace| {
+            if let Some(trace) = trace.borrow_mut().as_mut() {
+                trace.current_path.pop();
+            }
+        });
+    }
+    result
+}
@@ -0,0 +1,28 @@
+/// An edge or corner of the window, which is used by `WindowFrameArea::Resize`. Like the
+/// coordinates of points, the bottom is below the top.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum WindowEdge {
+    Left,
+    Right,
+    Bottom,
+    Top,
+    BottomLeft,
+    BottomRight,
+    TopLeft,
+    TopRight,
+}
+
+/// Determines what happens when the user starts dragging the window at some point. By default,
+/// everything is `Content`, but components can declare themselves as a window drag region using
+/// `ComponentBuddy.set_window_frame_area`. This allows applications with a borderless window to
+/// draw their own title bar: the *wrapper* will move (or resize) the window when the user drags
+/// in a `TitleBar` (or `Resize`) area.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum WindowFrameArea {
+    /// Dragging in this area doesn't affect the window, which is the default
+    Content,
+    /// Dragging in this area moves the window, like dragging the title bar of a regular window
+    TitleBar,
+    /// Dragging in this area resizes the window at the given edge or corner
+    Resize(WindowEdge),
+}
@@ -0,0 +1,308 @@
+use crate::*;
+
+/// This trait is implemented by all `Component`s and by the builders of `UiBuilder`, so that
+/// builders can be nested without calling `build` and `Box::new` for every child.
+pub trait IntoComponent {
+    fn into_component(self) -> Box<dyn Component>;
+}
+
+impl<C: Component + 'static> IntoComponent for C {
+    fn into_component(self) -> Box<dyn Component> {
+        Box::new(self)
+    }
+}
+
+impl IntoComponent for Box<dyn Component> {
+    fn into_component(self) -> Box<dyn Component> {
+        self
+    }
+}
+
+/// Creates a `UiBuilder` that uses the light `Palette`. See `UiBuilder`.
+pub fn ui() -> UiBuilder {
+    UiBuilder::new(Palette::light())
+}
+
+/// A fluent API to construct menus and the common built-in components with sensible defaults,
+/// which avoids a lot of `Box::new(...)` and `ComponentDomain::between(...)` boilerplate. The
+/// styles of the created components will be derived from the `Palette` of this builder.
+///
+/// ## Example
+/// ```
+/// use knukki::*;
+///
+/// let ui = ui();
+/// let menu = ui.column()
+///     .padding(0.05)
+///     .child(ui.label("Welcome"), FlexSize::Fraction(0.2))
+///     .weighted(ui.row()
+///         .spacing(0.1)
+///         .weighted(ui.button("Cancel", |_buddy| {}))
+///         .weighted(ui.button("OK", |_buddy| println!("OK")))
+///     )
+///     .build();
+/// # let _ = menu;
+/// ```
+#[derive(Clone, Debug)]
+pub struct UiBuilder {
+    palette: Palette,
+    follow_theme: bool,
+}
+
+impl UiBuilder {
+    pub fn new(palette: Palette) -> Self {
+        Self { palette, follow_theme: false }
+    }
+
+    pub fn get_palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Lets the components created by this builder follow the `Theme` of the application (see
+    /// for instance `TextButton.follow_theme`) rather than using the palette of this builder.
+    /// Their style will be based on the palette of this builder until their first render.
+    pub fn following_theme(mut self) -> Self {
+        self.follow_theme = true;
+        self
+    }
+
+    /// Starts building a `SimpleFlatMenu`, whose children will be placed at explicit domains
+    pub fn flat(&self) -> FlatMenuBuilder {
+        FlatMenuBuilder { background_color: None, children: Vec::new() }
+    }
+
+    /// Starts building a `FlexMenu` that places its children from left to right
+    pub fn row(&self) -> FlexMenuBuilder {
+        FlexMenuBuilder::new(FlexDirection::Row)
+    }
+
+    /// Starts building a `FlexMenu` that places its children from top to bottom
+    pub fn column(&self) -> FlexMenuBuilder {
+        FlexMenuBuilder::new(FlexDirection::Column)
+    }
+
+    /// Starts building a `TabbedPane` that uses the style of the palette
+    pub fn tabs(&self) -> TabbedPaneBuilder {
+        let mut pane = TabbedPane::new(TabbedPaneStyle::from_palette(&self.palette));
+        if self.follow_theme {
+            pane.follow_theme(StyleOverrides::new());
+        }
+        TabbedPaneBuilder { pane }
+    }
+
+    /// Creates a `TextLabel` that centers its text, and uses the style of the palette
+    pub fn label(&self, text: impl Into<String>) -> TextLabel {
+        let mut label = TextLabel::new(
+            text,
+            HorizontalTextAlignment::Center,
+            VerticalTextAlignment::Center,
+            TextLabelStyle::from_palette(&self.palette),
+        );
+        if self.follow_theme {
+            label.follow_theme(StyleOverrides::new());
+        }
+        label
+    }
+
+    /// Creates a `TextButton` that uses the style of the palette
+    pub fn button(
+        &self, text: &str, on_click: impl FnMut(&mut dyn ComponentBuddy) + 'static
+    ) -> TextButton {
+        let style = TextButtonStyle::from_palette(&self.palette);
+        let mut button = TextButton::new(text, style, Box::new(on_click));
+        if self.follow_theme {
+            button.follow_theme(StyleOverrides::new());
+        }
+        button
+    }
+
+    /// Creates a `ScrollPane` that uses the style of the palette. See `ScrollPane::new`.
+    pub fn scroll(
+        &self, content: impl IntoComponent, content_width: f32, content_height: f32
+    ) -> ScrollPane {
+        let mut pane = ScrollPane::new(
+            content.into_component(),
+            content_width,
+            content_height,
+            ScrollPaneStyle::from_palette(&self.palette),
+        );
+        if self.follow_theme {
+            pane.follow_theme(StyleOverrides::new());
+        }
+        pane
+    }
+}
+
+/// Builds a `SimpleFlatMenu`. Use `UiBuilder.flat` to create one.
+pub struct FlatMenuBuilder {
+    background_color: Option<Color>,
+    children: Vec<(Box<dyn Component>, ComponentDomain)>,
+}
+
+impl FlatMenuBuilder {
+    pub fn background(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Adds a child that will occupy the domain between (*min_x*, *min_y*) and (*max_x*, *max_y*)
+    pub fn child(
+        self, child: impl IntoComponent, min_x: f32, min_y: f32, max_x: f32, max_y: f32
+    ) -> Self {
+        self.child_at(child, ComponentDomain::between(min_x, min_y, max_x, max_y))
+    }
+
+    pub fn child_at(mut self, child: impl IntoComponent, domain: ComponentDomain) -> Self {
+        self.children.push((child.into_component(), domain));
+        self
+    }
+
+    pub fn build(self) -> SimpleFlatMenu {
+        let mut menu = SimpleFlatMenu::new(self.background_color);
+        for (child, domain) in self.children {
+            menu.add_component(child, domain);
+        }
+        menu
+    }
+}
+
+impl IntoComponent for FlatMenuBuilder {
+    fn into_component(self) -> Box<dyn Component> {
+        Box::new(self.build())
+    }
+}
+
+/// Builds a `FlexMenu`. Use `UiBuilder.row` or `UiBuilder.column` to create one.
+pub struct FlexMenuBuilder {
+    direction: FlexDirection,
+    background_color: Option<Color>,
+    padding: f32,
+    spacing: f32,
+    children: Vec<(Box<dyn Component>, FlexSize, FlexAlignment)>,
+}
+
+impl FlexMenuBuilder {
+    fn new(direction: FlexDirection) -> Self {
+        Self {
+            direction,
+            background_color: None,
+            padding: 0.0,
+            spacing: 0.0,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// See `FlexMenu.set_padding`
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// See `FlexMenu.set_spacing`
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Adds a child that will use the entire cross axis
+    pub fn child(self, child: impl IntoComponent, size: FlexSize) -> Self {
+        self.aligned_child(child, size, FlexAlignment::Stretch)
+    }
+
+    /// Adds a child with a weight of 1 that will use the entire cross axis
+    pub fn weighted(self, child: impl IntoComponent) -> Self {
+        self.child(child, FlexSize::Weight(1.0))
+    }
+
+    pub fn aligned_child(
+        mut self, child: impl IntoComponent, size: FlexSize, alignment: FlexAlignment
+    ) -> Self {
+        self.children.push((child.into_component(), size, alignment));
+        self
+    }
+
+    pub fn build(self) -> FlexMenu {
+        let mut menu = FlexMenu::new(self.direction, self.background_color);
+        menu.set_padding(self.padding);
+        menu.set_spacing(self.spacing);
+        for (child, size, alignment) in self.children {
+            menu.add_component_with_alignment(child, size, alignment);
+        }
+        menu
+    }
+}
+
+impl IntoComponent for FlexMenuBuilder {
+    fn into_component(self) -> Box<dyn Component> {
+        Box::new(self.build())
+    }
+}
+
+/// Builds a `TabbedPane`. Use `UiBuilder.tabs` to create one.
+pub struct TabbedPaneBuilder {
+    pane: TabbedPane,
+}
+
+impl TabbedPaneBuilder {
+    pub fn tab(mut self, label: impl Into<String>, content: impl IntoComponent) -> Self {
+        self.pane.add_tab(label, content.into_component());
+        self
+    }
+
+    pub fn build(self) -> TabbedPane {
+        self.pane
+    }
+}
+
+impl IntoComponent for TabbedPaneBuilder {
+    fn into_component(self) -> Box<dyn Component> {
+        Box::new(self.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_nested_builders() {
+        let clicked = Rc::new(RefCell::new(Vec::new()));
+        let left_clicked = Rc::clone(&clicked);
+        let right_clicked = Rc::clone(&clicked);
+
+        let ui = ui();
+        let mut menu = ui.flat()
+            .background(Color::rgb(0, 0, 0))
+            .child(ui.label("Title"), 0.0, 0.8, 1.0, 1.0)
+            .child(ui.row()
+                .padding(0.1)
+                .weighted(ui.button("Left", move |_| left_clicked.borrow_mut().push("left")))
+                .weighted(ui.button("Right", move |_| right_clicked.borrow_mut().push("right"))),
+                0.0, 0.0, 1.0, 0.8
+            )
+            .build();
+
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        menu.on_attach(&mut buddy);
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 400, 200));
+        menu.render(&renderer, &mut buddy, false).unwrap();
+
+        let click = |x: f32, y: f32| {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        };
+        menu.on_mouse_click(click(0.3, 0.4), &mut buddy);
+        menu.on_mouse_click(click(0.7, 0.4), &mut buddy);
+        menu.on_mouse_click(click(0.5, 0.9), &mut buddy);
+        assert_eq!(vec!["left", "right"], *clicked.borrow());
+    }
+}
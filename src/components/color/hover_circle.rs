@@ -18,9 +18,14 @@ impl HoverColorCircleComponent {
         Self {
             base_color,
             hover_color,
-            shader: create_fragment_only_shader()
+            shader: Self::create_shader()
         }
     }
+
+    /// Creates the `FragmentOnlyShader` that hover color circles use. See `Renderer.prewarm`.
+    pub(crate) fn create_shader() -> FragmentOnlyShader {
+        create_fragment_only_shader()
+    }
 }
 
 fn create_fragment_only_shader() -> FragmentOnlyShader {
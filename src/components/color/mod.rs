@@ -1,8 +1,14 @@
 mod hover_circle;
+mod palette;
 mod simple_flat;
+mod style;
+mod theme;
 
 pub use hover_circle::*;
+pub use palette::*;
 pub use simple_flat::*;
+pub use style::*;
+pub use theme::*;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct Color {
@@ -66,4 +72,89 @@ impl Color {
     pub fn to_float_array(&self) -> [f32; 4] {
         [self.get_red_float(), self.get_green_float(), self.get_blue_float(), self.get_alpha_float()]
     }
+
+    /// Computes the relative luminance of this color, as defined by WCAG 2: 0.0 for black and
+    /// 1.0 for white. This takes the gamma of the sRGB color space into account, and ignores the
+    /// alpha component.
+    pub fn get_relative_luminance(&self) -> f32 {
+        fn linearize(component: f32) -> f32 {
+            if component <= 0.03928 {
+                component / 12.92
+            } else {
+                ((component + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        0.2126 * linearize(self.get_red_float())
+            + 0.7152 * linearize(self.get_green_float())
+            + 0.0722 * linearize(self.get_blue_float())
+    }
+
+    /// Computes the WCAG 2 contrast ratio between this color and the *other* color, which is
+    /// between 1.0 (no contrast) and 21.0 (black and white). The order of the colors doesn't
+    /// matter.
+    pub fn get_contrast_ratio(&self, other: Color) -> f32 {
+        let luminance1 = self.get_relative_luminance();
+        let luminance2 = other.get_relative_luminance();
+        (luminance1.max(luminance2) + 0.05) / (luminance1.min(luminance2) + 0.05)
+    }
+
+    /// Picks black or white, depending on which of them has the highest contrast with this
+    /// (background) color
+    pub fn get_readable_text_color(&self) -> Color {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        if self.get_contrast_ratio(black) >= self.get_contrast_ratio(white) {
+            black
+        } else {
+            white
+        }
+    }
+
+    /// Returns this (text) color if its contrast ratio with the *background* is at least
+    /// `MIN_TEXT_CONTRAST`, and the readable text color of the *background* otherwise (see
+    /// `get_readable_text_color`).
+    pub fn readable_on(&self, background: Color) -> Color {
+        if self.get_contrast_ratio(background) >= MIN_TEXT_CONTRAST {
+            *self
+        } else {
+            background.get_readable_text_color()
+        }
+    }
+}
+
+/// The minimum contrast ratio between text and its background that `Color.readable_on` accepts.
+/// This is the ratio that WCAG 2 level AA requires for normal text.
+pub const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        assert!((black.get_contrast_ratio(white) - 21.0).abs() < 0.01);
+        assert!((white.get_contrast_ratio(black) - 21.0).abs() < 0.01);
+        assert!((white.get_contrast_ratio(white) - 1.0).abs() < 0.01);
+
+        // The luminance isn't linear in the color components, so mid-gray is darker than 0.5
+        let gray = Color::rgb(128, 128, 128);
+        assert!((gray.get_relative_luminance() - 0.216).abs() < 0.001);
+        assert!((gray.get_contrast_ratio(white) - 3.95).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_readable_text_color() {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        assert_eq!(white, Color::rgb(20, 20, 100).get_readable_text_color());
+        assert_eq!(black, Color::rgb(255, 230, 0).get_readable_text_color());
+
+        // Yellow text is fine on a dark background, but not on a white background
+        let yellow = Color::rgb(255, 230, 0);
+        assert_eq!(yellow, yellow.readable_on(Color::rgb(30, 30, 30)));
+        assert_eq!(black, yellow.readable_on(white));
+    }
 }
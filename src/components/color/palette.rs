@@ -0,0 +1,168 @@
+use crate::Color;
+
+/// The semantic roles of the colors in a `Palette`. The `On...` roles are meant for content (like
+/// text and icons) that is drawn on top of the corresponding role, so `OnPrimary` should be
+/// readable on `Primary`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ColorRole {
+    /// The color of the most important interactive components, like buttons
+    Primary,
+    OnPrimary,
+    /// The color used to highlight or distinguish components, like hovered buttons
+    Secondary,
+    OnSecondary,
+    /// The color behind all components
+    Background,
+    OnBackground,
+    /// The color of areas like cards and menus, which are drawn on top of the background
+    Surface,
+    OnSurface,
+    /// The color of borders and the 'empty' parts of components, like empty stars of a rating
+    Outline,
+    Error,
+    OnError,
+}
+
+impl ColorRole {
+    pub const ALL: [ColorRole; 11] = [
+        ColorRole::Primary,
+        ColorRole::OnPrimary,
+        ColorRole::Secondary,
+        ColorRole::OnSecondary,
+        ColorRole::Background,
+        ColorRole::OnBackground,
+        ColorRole::Surface,
+        ColorRole::OnSurface,
+        ColorRole::Outline,
+        ColorRole::Error,
+        ColorRole::OnError,
+    ];
+
+    fn get_index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Maps each `ColorRole` to a `Color`. The styles of the built-in components can be created from
+/// a palette (for instance `TextButtonStyle::from_palette`), so that applications can change the
+/// look of all of them by changing only the palette, rather than specifying the colors of each
+/// component separately. Individual components can still be restyled with `StyleOverrides`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Palette {
+    colors: [Color; ColorRole::ALL.len()],
+}
+
+impl Palette {
+    /// A palette with dark content on a light background
+    pub fn light() -> Self {
+        let mut palette = Self {
+            colors: [Color::rgb(255, 255, 255); ColorRole::ALL.len()],
+        };
+        palette.set_color(ColorRole::Primary, Color::rgb(98, 0, 238));
+        palette.set_color(ColorRole::OnPrimary, Color::rgb(255, 255, 255));
+        palette.set_color(ColorRole::Secondary, Color::rgb(3, 218, 198));
+        palette.set_color(ColorRole::OnSecondary, Color::rgb(0, 0, 0));
+        palette.set_color(ColorRole::Background, Color::rgb(255, 255, 255));
+        palette.set_color(ColorRole::OnBackground, Color::rgb(0, 0, 0));
+        palette.set_color(ColorRole::Surface, Color::rgb(245, 245, 245));
+        palette.set_color(ColorRole::OnSurface, Color::rgb(0, 0, 0));
+        palette.set_color(ColorRole::Outline, Color::rgb(121, 116, 126));
+        palette.set_color(ColorRole::Error, Color::rgb(176, 0, 32));
+        palette.set_color(ColorRole::OnError, Color::rgb(255, 255, 255));
+        palette
+    }
+
+    /// A palette with light content on a dark background
+    pub fn dark() -> Self {
+        let mut palette = Self::light();
+        palette.set_color(ColorRole::Primary, Color::rgb(187, 134, 252));
+        palette.set_color(ColorRole::OnPrimary, Color::rgb(0, 0, 0));
+        palette.set_color(ColorRole::Background, Color::rgb(18, 18, 18));
+        palette.set_color(ColorRole::OnBackground, Color::rgb(255, 255, 255));
+        palette.set_color(ColorRole::Surface, Color::rgb(30, 30, 30));
+        palette.set_color(ColorRole::OnSurface, Color::rgb(255, 255, 255));
+        palette.set_color(ColorRole::Outline, Color::rgb(147, 143, 153));
+        palette.set_color(ColorRole::Error, Color::rgb(207, 102, 121));
+        palette.set_color(ColorRole::OnError, Color::rgb(0, 0, 0));
+        palette
+    }
+
+    pub fn get_color(&self, role: ColorRole) -> Color {
+        self.colors[role.get_index()]
+    }
+
+    pub fn set_color(&mut self, role: ColorRole, color: Color) {
+        self.colors[role.get_index()] = color;
+    }
+
+    /// Gets the color of the *content* role, unless it is not readable on the color of the
+    /// *background* role, in which case black or white will be returned instead (see
+    /// `Color.readable_on`). The built-in components use this for their text colors, so that
+    /// custom palettes won't cause unreadable text.
+    pub fn get_readable_color(&self, content: ColorRole, background: ColorRole) -> Color {
+        self.get_color(content).readable_on(self.get_color(background))
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_get_and_set() {
+        let mut palette = Palette::default();
+        assert_eq!(Palette::light(), palette);
+        assert_ne!(Palette::dark(), palette);
+
+        let red = Color::rgb(200, 0, 0);
+        palette.set_color(ColorRole::Error, red);
+        assert_eq!(red, palette.get_color(ColorRole::Error));
+        assert_eq!(Color::rgb(98, 0, 238), palette.get_color(ColorRole::Primary));
+    }
+
+    #[test]
+    fn test_readable_color() {
+        let mut palette = Palette::light();
+        let on_primary = palette.get_color(ColorRole::OnPrimary);
+        assert_eq!(
+            on_primary,
+            palette.get_readable_color(ColorRole::OnPrimary, ColorRole::Primary)
+        );
+
+        // The built-in palettes should already be readable
+        for palette in &[Palette::light(), Palette::dark()] {
+            for (content, background) in &[
+                (ColorRole::OnPrimary, ColorRole::Primary),
+                (ColorRole::OnSecondary, ColorRole::Secondary),
+                (ColorRole::OnBackground, ColorRole::Background),
+                (ColorRole::OnSurface, ColorRole::Surface),
+                (ColorRole::OnError, ColorRole::Error),
+            ] {
+                let color = palette.get_color(*content);
+                assert_eq!(color, palette.get_readable_color(*content, *background));
+            }
+        }
+
+        // A light primary color would make the white text unreadable
+        palette.set_color(ColorRole::Primary, Color::rgb(255, 240, 150));
+        assert_eq!(
+            Color::rgb(0, 0, 0),
+            palette.get_readable_color(ColorRole::OnPrimary, ColorRole::Primary)
+        );
+    }
+
+    #[test]
+    fn test_role_indices() {
+        // Each role should have its own color
+        for (index, role) in ColorRole::ALL.iter().enumerate() {
+            assert_eq!(index, role.get_index());
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::*;
+
+use std::rc::Rc;
+
+/// This trait is implemented by the style structs of the built-in components (like
+/// `TextButtonStyle` and `ScrollPaneStyle`). It allows generic code (like `StyleOverrides`) to
+/// create the default style of a component family from a `Palette` or `Theme`.
+pub trait WidgetStyle: Sized {
+    /// Creates the default style of this component family for the given `Palette`. This should be
+    /// equivalent to the `from_palette` method of the style struct.
+    fn default_for_palette(palette: &Palette) -> Self;
+
+    /// Creates the default style of this component family for the given `Theme`. This should be
+    /// equivalent to the `from_theme` method of the style struct.
+    fn default_for_theme(theme: &Theme) -> Self;
+}
+
+macro_rules! impl_widget_style {
+    ($($style:ty),*) => {
+        $(
+            impl WidgetStyle for $style {
+                fn default_for_palette(palette: &Palette) -> Self {
+                    <$style>::from_palette(palette)
+                }
+
+                fn default_for_theme(theme: &Theme) -> Self {
+                    <$style>::from_theme(theme)
+                }
+            }
+        )*
+    };
+}
+
+impl_widget_style!(
+    TextStyle,
+    TextButtonStyle,
+    TextLabelStyle,
+    BreadcrumbsStyle,
+    InitialsBadgeStyle,
+    RatingStyle,
+    TabbedPaneStyle,
+    ScrollPaneStyle
+);
+
+type StyleOverride<S> = Rc<dyn Fn(&mut S, &Palette)>;
+
+/// A list of changes to the default style of a component family, which can be used to restyle a
+/// single component instance while still following the `Palette`. The style can be resolved for
+/// any palette with the `resolve` method, which starts with the default style of the palette and
+/// then applies the overrides in the order in which they were added.
+///
+/// ## Example
+/// ```
+/// use knukki::*;
+///
+/// let danger_button = StyleOverrides::<TextButtonStyle>::new()
+///     .with_color(|style| &mut style.base_background_color, ColorRole::Error)
+///     .with_color(|style| &mut style.base_text_color, ColorRole::OnError)
+///     .with(|style, _palette| style.corner_radius = 0.0);
+///
+/// let style = danger_button.resolve(&Palette::dark());
+/// assert_eq!(Palette::dark().get_color(ColorRole::Error), style.base_background_color);
+/// assert_eq!(0.0, style.corner_radius);
+/// ```
+pub struct StyleOverrides<S: WidgetStyle> {
+    overrides: Vec<StyleOverride<S>>,
+}
+
+impl<S: WidgetStyle> StyleOverrides<S> {
+    /// Constructs an empty list of overrides, which will resolve to the default style
+    pub fn new() -> Self {
+        Self { overrides: Vec::new() }
+    }
+
+    /// Adds an override that can change any property of the style. The palette is passed as
+    /// second parameter, so that the override can use its colors.
+    pub fn with(mut self, change: impl Fn(&mut S, &Palette) + 'static) -> Self {
+        self.overrides.push(Rc::new(change));
+        self
+    }
+
+    /// Adds an override that gives the color property selected by *get_property* the color of
+    /// the given *role* in the palette.
+    pub fn with_color(self, get_property: fn(&mut S) -> &mut Color, role: ColorRole) -> Self
+    where
+        S: 'static,
+    {
+        self.with(move |style, palette| *get_property(style) = palette.get_color(role))
+    }
+
+    /// Adds all overrides of *other* after the overrides of this list
+    pub fn extend(mut self, other: &StyleOverrides<S>) -> Self {
+        self.overrides.extend(other.overrides.iter().cloned());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Creates the default style for the given `Palette`, and applies all overrides to it
+    pub fn resolve(&self, palette: &Palette) -> S {
+        let mut style = S::default_for_palette(palette);
+        for change in &self.overrides {
+            change(&mut style, palette);
+        }
+        style
+    }
+
+    /// Creates the default style for the given `Theme`, and applies all overrides to it (using
+    /// the palette of the theme).
+    ///
+    /// The built-in components that have a `follow_theme` method (like `TextButton.follow_theme`)
+    /// use this method to follow the `Theme` of their buddy: when such a component is given
+    /// overrides, it will replace its style with the style that the overrides resolve for the
+    /// theme of its buddy, and it will do so again whenever that theme is replaced. Use
+    /// `StyleOverrides::new()` to simply use the default style of the theme.
+    pub fn resolve_theme(&self, theme: &Theme) -> S {
+        let mut style = S::default_for_theme(theme);
+        for change in &self.overrides {
+            change(&mut style, &theme.palette);
+        }
+        style
+    }
+}
+
+impl<S: WidgetStyle> Clone for StyleOverrides<S> {
+    fn clone(&self) -> Self {
+        Self { overrides: self.overrides.clone() }
+    }
+}
+
+impl<S: WidgetStyle> Default for StyleOverrides<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps track of the `Theme` of a built-in component that follows the theme (for instance
+/// `TextButton.follow_theme`), and resolves the style of the component when the theme changes.
+pub(crate) struct ThemeFollower<S: WidgetStyle> {
+    overrides: StyleOverrides<S>,
+    current_theme: Option<Rc<Theme>>,
+}
+
+impl<S: WidgetStyle> ThemeFollower<S> {
+    pub(crate) fn new(overrides: StyleOverrides<S>) -> Self {
+        Self { overrides, current_theme: None }
+    }
+
+    /// Replaces *style* if the theme of the *buddy* changed since the previous call to this
+    /// method (or if this is the first call), and returns whether it did so
+    pub(crate) fn update(&mut self, style: &mut S, buddy: &dyn ComponentBuddy) -> bool {
+        let theme = buddy.get_theme();
+        if let Some(current_theme) = &self.current_theme {
+            if Rc::ptr_eq(current_theme, &theme) {
+                return false;
+            }
+        }
+        *style = self.overrides.resolve_theme(&theme);
+        self.current_theme = Some(theme);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::rc::Rc;
+
+    #[test]
+    fn test_resolve_overrides() {
+        let overrides = StyleOverrides::<TextButtonStyle>::new()
+            .with_color(|style| &mut style.hover_background_color, ColorRole::Surface)
+            .with(|style, _palette| style.margin = 0.25);
+        assert!(!overrides.is_empty());
+
+        for palette in &[Palette::light(), Palette::dark()] {
+            let style = overrides.resolve(palette);
+            let default_style = TextButtonStyle::from_palette(palette);
+            assert_eq!(palette.get_color(ColorRole::Surface), style.hover_background_color);
+            assert_eq!(0.25, style.margin);
+            assert_eq!(default_style.base_background_color, style.base_background_color);
+            assert_eq!(default_style.corner_radius, style.corner_radius);
+        }
+
+        // Later overrides should win
+        let extended = overrides.clone().extend(
+            &StyleOverrides::new().with(|style: &mut TextButtonStyle, _palette| style.margin = 0.1)
+        );
+        assert_eq!(0.1, extended.resolve(&Palette::light()).margin);
+        assert_eq!(0.25, overrides.resolve(&Palette::light()).margin);
+
+        assert_eq!(
+            ScrollPaneStyle::from_palette(&Palette::dark()).bar_size,
+            StyleOverrides::<ScrollPaneStyle>::default().resolve(&Palette::dark()).bar_size
+        );
+    }
+
+    #[test]
+    fn test_theme_follower() {
+        let overrides = StyleOverrides::<TextButtonStyle>::new()
+            .with(|style, _palette| style.margin = 0.3);
+        let mut follower = ThemeFollower::new(overrides);
+        let mut style = TextButtonStyle::from_palette(&Palette::light());
+
+        let mut buddy = RootComponentBuddy::new();
+        let mut theme = Theme::dark();
+        theme.corner_radius = 0.0;
+        theme.font_id = Some("fancy".to_string());
+        buddy.set_theme(Rc::new(theme));
+
+        assert!(follower.update(&mut style, &buddy));
+        assert_eq!(0.3, style.margin);
+        assert_eq!(0.0, style.corner_radius);
+        assert_eq!(Some("fancy".to_string()), style.font_id);
+        let dark_primary = Palette::dark().get_color(ColorRole::Primary);
+        assert_eq!(dark_primary, style.base_background_color);
+
+        // The style should only be resolved again when the theme is replaced
+        style.margin = 0.4;
+        assert!(!follower.update(&mut style, &buddy));
+        assert_eq!(0.4, style.margin);
+
+        buddy.set_theme(Rc::new(Theme::light()));
+        assert!(follower.update(&mut style, &buddy));
+        assert_eq!(0.3, style.margin);
+        assert_eq!(0.25, style.corner_radius);
+        assert_eq!(None, style.font_id);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::*;
+
+/// The visual identity of an application: its `Palette`, font, corner radius, and spacing.
+/// Components can get the current theme with `ComponentBuddy.get_theme`, and applications can
+/// change it with `Application.set_theme` (for instance to switch to dark mode).
+///
+/// The built-in components can follow the theme (see for instance `TextButton.follow_theme`), in
+/// which case their style will be created with the `from_theme` method of their style struct
+/// (for instance `TextButtonStyle::from_theme`) whenever the theme changes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Theme {
+    pub palette: Palette,
+    /// The id of the font of the text of the built-in components, or `None` to use the default
+    /// font
+    pub font_id: Option<String>,
+    /// The radius of rounded corners (like those of buttons), relative to the height of the
+    /// component. It should be between 0.0 (sharp corners) and 0.5 (which gives round ends).
+    pub corner_radius: f32,
+    /// The margin between the edges of small components (like buttons) and their content,
+    /// relative to the height of the component
+    pub spacing: f32,
+}
+
+impl Theme {
+    /// Creates a theme with the given *palette* and the default font, corner radius, and spacing
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            font_id: None,
+            corner_radius: 0.25,
+            spacing: 0.1,
+        }
+    }
+
+    /// A theme that uses the light `Palette`
+    pub fn light() -> Self {
+        Self::new(Palette::light())
+    }
+
+    /// A theme that uses the dark `Palette`
+    pub fn dark() -> Self {
+        Self::new(Palette::dark())
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
@@ -0,0 +1,3 @@
+mod rating;
+
+pub use rating::*;
@@ -0,0 +1,288 @@
+use crate::*;
+
+pub struct RatingStyle {
+    pub filled_color: Color,
+    pub empty_color: Color,
+    /// The color of the filled part of the stars while the user is hovering over the component
+    pub preview_color: Color,
+}
+
+impl RatingStyle {
+    /// Creates a `RatingStyle` that fills the stars with the primary color of the given `Palette`
+    /// and previews values with its secondary color
+    pub fn from_palette(palette: &Palette) -> Self {
+        Self {
+            filled_color: palette.get_color(ColorRole::Primary),
+            empty_color: palette.get_color(ColorRole::Outline),
+            preview_color: palette.get_color(ColorRole::Secondary),
+        }
+    }
+
+    /// Creates the `RatingStyle` for the palette of the given `Theme`
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self::from_palette(&theme.palette)
+    }
+}
+
+/// A component that lets the user pick a rating by clicking on (or dragging over) a row of stars.
+/// The values are multiples of 0.5 between 0 (no stars) and the number of stars (inclusive): the
+/// left half of each star selects a half star.
+///
+/// While the user hovers over the stars, the value that would be selected by clicking will be
+/// previewed using the preview color of the style.
+pub struct Rating {
+    num_stars: u8,
+    value: f32,
+    hover_value: Option<f32>,
+    /// The width of each drawn star, relative to the width of the component. This is updated
+    /// during each render, since it depends on the aspect ratio of the viewport.
+    star_width: f32,
+
+    style: RatingStyle,
+    theme_follower: Option<ThemeFollower<RatingStyle>>,
+    shader: FragmentOnlyShader,
+    on_change: Box<dyn FnMut(f32, &mut dyn ComponentBuddy)>,
+}
+
+fn shader_description() -> FragmentOnlyShaderDescription {
+    FragmentOnlyShaderDescription {
+        source_code: "
+            float starDistance(vec2 p, float outerRadius, float innerFactor) {
+                const vec2 k1 = vec2(0.809016994375, -0.587785252292);
+                const vec2 k2 = vec2(-k1.x, k1.y);
+                p.x = abs(p.x);
+                p -= 2.0 * max(dot(k1, p), 0.0) * k1;
+                p -= 2.0 * max(dot(k2, p), 0.0) * k2;
+                p.x = abs(p.x);
+                p.y -= outerRadius;
+                vec2 ba = innerFactor * vec2(-k1.y, k1.x) - vec2(0.0, 1.0);
+                float h = clamp(dot(p, ba) / dot(ba, ba), 0.0, outerRadius);
+                return length(p - ba * h) * sign(p.y * ba.x - p.x * ba.y);
+            }
+
+            void main() {
+                if (starDistance(innerPosition - vec2(0.5, 0.45), 0.5, 0.4) > 0.0) {
+                    discard;
+                }
+                if (innerPosition.x <= float1) {
+                    gl_FragColor = color1;
+                } else {
+                    gl_FragColor = color2;
+                }
+            }
+        ".to_string(),
+        num_float_matrices: 0,
+        num_colors: 2,
+        num_float_vectors: 0,
+        num_int_vectors: 0,
+        num_floats: 1,
+        num_ints: 0
+    }
+}
+
+/// Computes the value that would be selected when the user clicks at the given relative
+/// x-coordinate, when each star is drawn *star_width* wide in the middle of its slot. Clicking in
+/// the gap left of a star selects all stars before it, and clicking in the gap right of a star
+/// selects that star entirely.
+fn compute_value_at(x: f32, num_stars: u8, star_width: f32) -> f32 {
+    if num_stars == 0 || x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return num_stars as f32;
+    }
+
+    let slot_width = 1.0 / num_stars as f32;
+    let star_index = ((x / slot_width) as u8).min(num_stars - 1);
+    let star_min_x = (star_index as f32 + 0.5) * slot_width - 0.5 * star_width;
+    let star_fraction = ((x - star_min_x) / star_width).max(0.0).min(1.0);
+    star_index as f32 + (star_fraction * 2.0).ceil() / 2.0
+}
+
+/// Computes the width and height (relative to the domain of the component) of each star, when a
+/// rating with *num_stars* stars is drawn in a viewport with the given *aspect_ratio*. Each star
+/// gets an equally wide slot and is drawn as a square in the middle of that slot.
+fn compute_star_size(num_stars: u8, aspect_ratio: f32) -> (f32, f32) {
+    let slot_width = 1.0 / num_stars as f32;
+    let slot_aspect_ratio = aspect_ratio * slot_width;
+    match slot_aspect_ratio >= 1.0 {
+        true => (slot_width / slot_aspect_ratio, 1.0),
+        false => (slot_width, slot_aspect_ratio),
+    }
+}
+
+impl Rating {
+    pub fn new(
+        num_stars: u8,
+        initial_value: f32,
+        style: RatingStyle,
+        on_change: Box<dyn FnMut(f32, &mut dyn ComponentBuddy)>,
+    ) -> Self {
+        Self {
+            num_stars,
+            value: initial_value.max(0.0).min(num_stars as f32),
+            hover_value: None,
+            // This will be corrected during the first render
+            star_width: 1.0 / num_stars as f32,
+            style,
+            theme_follower: None,
+            shader: Self::create_shader(),
+            on_change,
+        }
+    }
+
+    /// Lets this rating follow the `Theme` of its buddy, see `StyleOverrides.resolve_theme`
+    pub fn follow_theme(&mut self, overrides: StyleOverrides<RatingStyle>) {
+        self.theme_follower = Some(ThemeFollower::new(overrides));
+    }
+
+    /// Creates the `FragmentOnlyShader` that ratings use. See `Renderer.prewarm`.
+    pub(crate) fn create_shader() -> FragmentOnlyShader {
+        FragmentOnlyShader::new(shader_description())
+    }
+
+    pub fn get_num_stars(&self) -> u8 {
+        self.num_stars
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value
+    }
+
+    /// Changes the value of this rating, without firing the *on_change* listener. The caller is
+    /// responsible for requesting a render.
+    pub fn set_value(&mut self, new_value: f32) {
+        self.value = new_value.max(0.0).min(self.num_stars as f32);
+    }
+
+    fn select_value_at(&mut self, x: f32, buddy: &mut dyn ComponentBuddy) {
+        let new_value = compute_value_at(x, self.num_stars, self.star_width);
+        if new_value != self.value {
+            self.value = new_value;
+            (self.on_change)(new_value, buddy);
+            buddy.request_render();
+        }
+    }
+
+    fn set_hover_value(&mut self, new_hover_value: Option<f32>, buddy: &mut dyn ComponentBuddy) {
+        if new_hover_value != self.hover_value {
+            self.hover_value = new_hover_value;
+            buddy.request_render();
+        }
+    }
+}
+
+impl Component for Rating {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_press();
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_mouse_leave();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        if let Some(follower) = &mut self.theme_follower {
+            follower.update(&mut self.style, buddy);
+        }
+
+        if self.num_stars == 0 {
+            return entire_render_result();
+        }
+
+        let slot_width = 1.0 / self.num_stars as f32;
+        let aspect_ratio = renderer.get_viewport().get_aspect_ratio();
+        let (star_width, star_height) = compute_star_size(self.num_stars, aspect_ratio);
+        self.star_width = star_width;
+
+        let (displayed_value, fill_color) = match self.hover_value {
+            Some(hover_value) => (hover_value, self.style.preview_color),
+            None => (self.value, self.style.filled_color),
+        };
+
+        for star_index in 0..self.num_stars {
+            let center_x = (star_index as f32 + 0.5) * slot_width;
+            let fill_fraction = (displayed_value - star_index as f32).max(0.0).min(1.0);
+            renderer.apply_fragment_shader(
+                center_x - 0.5 * star_width,
+                0.5 - 0.5 * star_height,
+                center_x + 0.5 * star_width,
+                0.5 + 0.5 * star_height,
+                &self.shader,
+                FragmentOnlyDrawParameters {
+                    colors: &[fill_color, self.style.empty_color],
+                    floats: &[fill_fraction],
+                    ..FragmentOnlyDrawParameters::default()
+                },
+            );
+        }
+
+        // The gaps between the stars should also respond to the mouse, so claim the entire domain
+        entire_render_result()
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_button().is_primary() {
+            self.select_value_at(event.get_point().get_x(), buddy);
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        let x = event.get_to().get_x();
+        if buddy.is_primary_mouse_button_down(event.get_mouse()) == Some(true) {
+            self.select_value_at(x, buddy);
+        }
+        let hover_value = compute_value_at(x, self.num_stars, self.star_width);
+        self.set_hover_value(Some(hover_value), buddy);
+    }
+
+    fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.set_hover_value(None, buddy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_compute_value_at() {
+        // When the stars fill their slots entirely
+        assert_eq!(0.0, compute_value_at(0.0, 5, 0.2));
+        assert_eq!(0.5, compute_value_at(0.01, 5, 0.2));
+        assert_eq!(0.5, compute_value_at(0.1, 5, 0.2));
+        assert_eq!(1.0, compute_value_at(0.11, 5, 0.2));
+        assert_eq!(1.0, compute_value_at(0.2, 5, 0.2));
+        assert_eq!(3.5, compute_value_at(0.65, 5, 0.2));
+        assert_eq!(5.0, compute_value_at(0.95, 5, 0.2));
+        assert_eq!(5.0, compute_value_at(1.0, 5, 0.2));
+        assert_eq!(5.0, compute_value_at(1.2, 5, 0.2));
+        assert_eq!(0.0, compute_value_at(-0.2, 5, 0.2));
+    }
+
+    #[test]
+    fn test_compute_value_at_narrow_stars() {
+        // Each slot is 0.2 wide, but the stars are only 0.1 wide, so the first star is drawn
+        // between x = 0.05 and x = 0.15
+        assert_eq!(0.0, compute_value_at(0.03, 5, 0.1));
+        assert_eq!(0.5, compute_value_at(0.07, 5, 0.1));
+        assert_eq!(0.5, compute_value_at(0.09, 5, 0.1));
+        assert_eq!(1.0, compute_value_at(0.12, 5, 0.1));
+        assert_eq!(1.0, compute_value_at(0.18, 5, 0.1));
+        assert_eq!(1.0, compute_value_at(0.23, 5, 0.1));
+        assert_eq!(1.5, compute_value_at(0.27, 5, 0.1));
+        assert_eq!(5.0, compute_value_at(0.97, 5, 0.1));
+    }
+
+    #[test]
+    fn test_compute_star_size() {
+        // The slots are 0.25 wide and have an aspect ratio of 2, so the stars are 0.125 wide
+        assert_eq!((0.125, 1.0), compute_star_size(4, 8.0));
+        // The slots are 0.25 wide and have an aspect ratio of 0.5, so the stars are half as high
+        assert_eq!((0.25, 0.5), compute_star_size(4, 2.0));
+    }
+}
@@ -0,0 +1,468 @@
+use crate::*;
+
+use std::time::Duration;
+
+/// Determines how many sections of an `Accordion` can be open at the same time
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AccordionMode {
+    /// Opening a section will close the section that was open
+    Single,
+    /// Any number of sections can be open at the same time
+    Multiple,
+}
+
+struct AccordionSection {
+    header: ComponentHandle,
+    body: ComponentHandle,
+    open: bool,
+    // The time until the body should be hidden, while the section is closing
+    hide_timer: Option<Duration>,
+}
+
+/// A container with a column of collapsible sections. Each section consists of a *header*, which
+/// is always visible, and a *body*, which is only visible while the section is open. Clicking on
+/// the header of a section will open or close it.
+///
+/// The headers have a fixed height, and the open sections share the remaining height equally.
+/// The bodies of closed sections get no space, and they will be hidden (see
+/// `SimpleFlatMenu.set_component_visible`), so they don't receive any events. Use
+/// `set_animation` to let the sections grow and shrink smoothly.
+///
+/// This uses a `FlexMenu` to place the headers and bodies, so it propagates events in the same
+/// way.
+pub struct Accordion {
+    menu: FlexMenu,
+    mode: AccordionMode,
+    header_height: f32,
+    sections: Vec<AccordionSection>,
+}
+
+impl Accordion {
+    /// Constructs a new `Accordion` without sections. The *header_height* is the height of each
+    /// header, as a fraction of the height of the accordion.
+    pub fn new(mode: AccordionMode, header_height: f32, background_color: Option<Color>) -> Self {
+        Self {
+            menu: FlexMenu::new(FlexDirection::Column, background_color),
+            mode,
+            header_height,
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn get_mode(&self) -> AccordionMode {
+        self.mode
+    }
+
+    /// When *animation* is `Some((duration, easing))`, sections will gradually grow when they are
+    /// opened and shrink when they are closed (see `FlexMenu.set_layout_animation`). When it is
+    /// `None` (the default), they will be resized immediately.
+    pub fn set_animation(&mut self, animation: Option<(Duration, Easing)>) {
+        self.menu.set_layout_animation(animation);
+    }
+
+    /// Adds a (closed) section with the given *header* and *body* to the bottom of this accordion,
+    /// and returns its index
+    pub fn add_section(&mut self, header: Box<dyn Component>, body: Box<dyn Component>) -> usize {
+        let header = self
+            .menu
+            .add_component(header, FlexSize::Fraction(self.header_height));
+        let body = self.menu.add_component(body, FlexSize::Weight(0.0));
+        self.menu.set_component_visible(body, false);
+        self.sections.push(AccordionSection {
+            header,
+            body,
+            open: false,
+            hide_timer: None,
+        });
+        self.sections.len() - 1
+    }
+
+    pub fn get_num_sections(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Checks whether the section with the given *index* is open
+    ///
+    /// ## Panics
+    /// This function will panic if *index* is not smaller than the number of sections.
+    pub fn is_open(&self, index: usize) -> bool {
+        self.sections[index].open
+    }
+
+    /// Opens or closes the section with the given *index*. In `AccordionMode::Single`, opening a
+    /// section will close the other sections. The caller is responsible for requesting a render.
+    ///
+    /// ## Panics
+    /// This function will panic if *index* is not smaller than the number of sections.
+    pub fn set_open(&mut self, index: usize, open: bool) {
+        if open && self.mode == AccordionMode::Single {
+            for other_index in 0..self.sections.len() {
+                if other_index != index {
+                    self.set_section_open(other_index, false);
+                }
+            }
+        }
+        self.set_section_open(index, open);
+    }
+
+    /// Opens the section with the given *index* if it is closed, and closes it otherwise. See
+    /// `set_open`.
+    pub fn toggle(&mut self, index: usize) {
+        self.set_open(index, !self.is_open(index));
+    }
+
+    fn set_section_open(&mut self, index: usize, open: bool) {
+        let section = &mut self.sections[index];
+        if section.open == open {
+            return;
+        }
+        section.open = open;
+
+        if open {
+            section.hide_timer = None;
+            self.menu.set_component_visible(section.body, true);
+        } else {
+            // The body should stay visible until it finished shrinking
+            match self.menu.get_layout_animation() {
+                Some((duration, _)) => section.hide_timer = Some(duration),
+                None => self.menu.set_component_visible(section.body, false),
+            };
+        }
+
+        let weight = if open { 1.0 } else { 0.0 };
+        let body = section.body;
+        self.menu.set_component_size(body, FlexSize::Weight(weight));
+    }
+
+    fn get_header_at(&self, point: Point) -> Option<usize> {
+        let handle = self.menu.find_component_at(point)?;
+        self.sections
+            .iter()
+            .position(|section| section.header == handle)
+    }
+}
+
+impl Component for Accordion {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy)
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        self.menu.render(renderer, buddy, force)
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        // The header should get the click before the layout changes. Note that the menu applies
+        // its pending layout changes before it propagates the click.
+        self.menu.on_mouse_click(event, buddy);
+        if event.get_button().is_primary() {
+            if let Some(index) = self.get_header_at(event.get_point()) {
+                self.toggle(index);
+                buddy.request_render();
+            }
+        }
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_press(event, buddy)
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_release(event, buddy)
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_move(event, buddy)
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_enter(event, buddy)
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_leave(event, buddy)
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_scroll(event, buddy)
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_double_click(event, buddy)
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_long_press(event, buddy)
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_context_click(event, buddy)
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_middle_click(event, buddy)
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_hover(event, buddy)
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_pinch(event, buddy)
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_rotate_gesture(event, buddy)
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_swipe(event, buddy)
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy);
+
+        // Hide the bodies of the sections that finished closing
+        let mut needs_ticks = false;
+        for section in &mut self.sections {
+            if let Some(remaining_time) = section.hide_timer {
+                let remaining_time = remaining_time.saturating_sub(event.get_delta_time());
+                if remaining_time == Duration::from_secs(0) {
+                    section.hide_timer = None;
+                    self.menu.set_component_visible(section.body, false);
+                } else {
+                    section.hide_timer = Some(remaining_time);
+                    needs_ticks = true;
+                }
+            }
+        }
+
+        // The menu unsubscribes when its children don't need ticks anymore
+        if needs_ticks {
+            buddy.subscribe_tick();
+        }
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.menu.get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.menu.get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.menu.get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct ClickComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for ClickComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            let point = event.get_point();
+            self.log.borrow_mut().push(format!(
+                "{} {:.2} {:.2}", self.name, point.get_x(), point.get_y()
+            ));
+        }
+    }
+
+    fn add_section(
+        accordion: &mut Accordion, name: &'static str, log: &Rc<RefCell<Vec<String>>>
+    ) -> usize {
+        let header = ClickComponent { name: "header", log: Rc::clone(log) };
+        let body = ClickComponent { name, log: Rc::clone(log) };
+        accordion.add_section(Box::new(header), Box::new(body))
+    }
+
+    #[test]
+    fn test_toggle_sections() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut accordion = Accordion::new(AccordionMode::Single, 0.25, None);
+        add_section(&mut accordion, "a", &log);
+        add_section(&mut accordion, "b", &log);
+        assert_eq!(2, accordion.get_num_sections());
+
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        accordion.on_attach(&mut buddy);
+        accordion.render(&renderer, &mut buddy, false).unwrap();
+
+        let click = |x: f32, y: f32| {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        };
+
+        // Initially, all sections are closed, so only the headers can be clicked
+        accordion.on_mouse_click(click(0.5, 0.25), &mut buddy);
+        assert!(log.borrow().is_empty());
+
+        // Clicking the header of b should open it below its header
+        accordion.on_mouse_click(click(0.5, 0.6), &mut buddy);
+        assert!(accordion.is_open(1));
+        assert!(buddy.did_request_render());
+        accordion.render(&renderer, &mut buddy, false).unwrap();
+        accordion.on_mouse_click(click(0.5, 0.25), &mut buddy);
+        assert_eq!(vec!["header 0.50 0.40", "b 0.50 0.50"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // Opening a should close b, since only 1 section can be open at the same time
+        accordion.on_mouse_click(click(0.5, 0.9), &mut buddy);
+        assert!(accordion.is_open(0));
+        assert!(!accordion.is_open(1));
+        accordion.render(&renderer, &mut buddy, false).unwrap();
+        accordion.on_mouse_click(click(0.5, 0.5), &mut buddy);
+
+        // The header of b moved to the bottom, and clicking it should open b again
+        accordion.on_mouse_click(click(0.5, 0.1), &mut buddy);
+        assert_eq!(
+            vec!["header 0.50 0.60", "a 0.50 0.50", "header 0.50 0.40"],
+            *log.borrow()
+        );
+        assert!(accordion.is_open(1));
+
+        // Clicking the header of b again should close it, even before the new layout is rendered
+        accordion.on_mouse_click(click(0.5, 0.6), &mut buddy);
+        assert!(!accordion.is_open(0));
+        assert!(!accordion.is_open(1));
+    }
+
+    #[test]
+    fn test_multiple_sections_animated() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut accordion = Accordion::new(AccordionMode::Multiple, 0.1, None);
+        accordion.set_animation(Some((Duration::from_millis(100), Easing::Linear)));
+        add_section(&mut accordion, "a", &log);
+        add_section(&mut accordion, "b", &log);
+
+        accordion.set_open(0, true);
+        accordion.set_open(1, true);
+        assert!(accordion.is_open(0) && accordion.is_open(1));
+
+        // The body of a should stay visible until it finished shrinking
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        accordion.on_attach(&mut buddy);
+        accordion.set_open(0, false);
+        assert_eq!(Some(Duration::from_millis(100)), accordion.sections[0].hide_timer);
+        accordion.on_tick(TickEvent::new(Duration::from_millis(60)), &mut buddy);
+        assert_eq!(Some(Duration::from_millis(40)), accordion.sections[0].hide_timer);
+        assert!(buddy.get_subscriptions().tick);
+        accordion.on_tick(TickEvent::new(Duration::from_millis(60)), &mut buddy);
+        assert_eq!(None, accordion.sections[0].hide_timer);
+        assert!(accordion.is_open(1));
+    }
+}
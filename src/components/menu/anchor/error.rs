@@ -0,0 +1,34 @@
+use crate::ComponentHandle;
+
+use std::error::Error;
+use std::fmt::{
+    Display,
+    Formatter,
+    Result,
+};
+
+/// This error is returned by `AnchorLayoutMenu.set_constraints` when the new constraints would
+/// anchor a component (directly or indirectly) to itself. Such constraints can't be resolved, so
+/// the menu keeps the old constraints of the component.
+///
+/// The *cycle* starts and ends with the component whose constraints were being set, and each
+/// component in it is anchored to the next one.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AnchorCycle {
+    pub cycle: Vec<ComponentHandle>,
+}
+
+impl Display for AnchorCycle {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result {
+        write!(formatter, "These anchors would form a cycle: ")?;
+        for (index, handle) in self.cycle.iter().enumerate() {
+            if index > 0 {
+                write!(formatter, " -> ")?;
+            }
+            write!(formatter, "{:?}", handle)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for AnchorCycle {}
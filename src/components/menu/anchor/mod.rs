@@ -0,0 +1,676 @@
+use crate::*;
+
+use std::collections::HashSet;
+
+mod error;
+
+pub use error::*;
+
+/// An edge of the parent or a sibling that an `Anchor` can be attached to. Which edge is meant
+/// depends on the axis: for horizontal anchors (*left* and *right*), `Min` means the left edge and
+/// `Max` means the right edge. For vertical anchors (*bottom* and *top*), `Min` means the bottom
+/// edge and `Max` means the top edge.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AnchorEdge {
+    Min,
+    Center,
+    Max,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AnchorTarget {
+    Parent,
+    /// The sibling with the given handle. When the sibling is removed, the anchor will behave as
+    /// if its target were the `Parent`. Anchors are not allowed to form a cycle, see
+    /// `AnchorLayoutMenu.set_constraints`.
+    Sibling(ComponentHandle),
+}
+
+/// Attaches an edge of a child of an `AnchorLayoutMenu` to an edge of its parent or a sibling. The
+/// *offset* is relative to the size of the parent, so an offset of -0.05 on the right anchor
+/// means a margin of 5% of the width of the parent.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Anchor {
+    pub target: AnchorTarget,
+    pub edge: AnchorEdge,
+    pub offset: f32,
+}
+
+impl Anchor {
+    pub fn parent(edge: AnchorEdge, offset: f32) -> Self {
+        Self {
+            target: AnchorTarget::Parent,
+            edge,
+            offset,
+        }
+    }
+
+    pub fn sibling(sibling: ComponentHandle, edge: AnchorEdge, offset: f32) -> Self {
+        Self {
+            target: AnchorTarget::Sibling(sibling),
+            edge,
+            offset,
+        }
+    }
+}
+
+/// Determines the domain of a child of an `AnchorLayoutMenu`. Each axis is resolved separately:
+/// - when both edges are anchored, the child will span the space between them
+/// - when 1 edge is anchored and the size is known, the child will extend from that edge
+/// - when only the size is known, the child will be centered
+/// - when the size is unknown, an edge without anchor will be the edge of the parent
+///
+/// The *width* and *height* are relative to the size of the parent. When both edges of an axis
+/// are anchored, the size of that axis is ignored.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct AnchorConstraints {
+    pub left: Option<Anchor>,
+    pub right: Option<Anchor>,
+    pub bottom: Option<Anchor>,
+    pub top: Option<Anchor>,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+}
+
+struct AnchorEntry {
+    handle: ComponentHandle,
+    constraints: AnchorConstraints,
+    domain: ComponentDomain,
+}
+
+/// A menu whose children are positioned by anchoring their edges to the edges of the menu or to
+/// the edges of their siblings. Since everything is relative to the size of the menu, the layout
+/// stays correct when the menu is resized. For instance, a button pinned to the bottom-right
+/// corner with a margin of 5% can be created like this:
+///
+/// ```
+/// use knukki::*;
+///
+/// let mut menu = AnchorLayoutMenu::new(None);
+/// let button = SimpleFlatColorComponent::new(Color::rgb(200, 0, 0));
+/// menu.add_component(Box::new(button), AnchorConstraints {
+///     right: Some(Anchor::parent(AnchorEdge::Max, -0.05)),
+///     bottom: Some(Anchor::parent(AnchorEdge::Min, 0.05)),
+///     width: Some(0.2),
+///     height: Some(0.1),
+///     ..AnchorConstraints::default()
+/// });
+/// ```
+///
+/// When a child is added, removed, or gets new constraints, only the domains of that child and the
+/// children that are (directly or indirectly) anchored to it will be recomputed. This uses a
+/// `SimpleFlatMenu` to manage the children, so it propagates events in the same way.
+pub struct AnchorLayoutMenu {
+    menu: SimpleFlatMenu,
+    entries: Vec<AnchorEntry>,
+    last_resolved: Vec<ComponentHandle>,
+}
+
+impl AnchorLayoutMenu {
+    pub fn new(background_color: Option<Color>) -> Self {
+        Self {
+            menu: SimpleFlatMenu::new(background_color),
+            entries: Vec::new(),
+            last_resolved: Vec::new(),
+        }
+    }
+
+    pub fn add_component(
+        &mut self,
+        component: Box<dyn Component>,
+        constraints: AnchorConstraints,
+    ) -> ComponentHandle {
+        let domain = resolve_anchor_constraints(&constraints, &self.entries);
+        let handle = self.menu.add_component(component, domain);
+        self.entries.push(AnchorEntry {
+            handle,
+            constraints,
+            domain,
+        });
+
+        // Handles are never reused, so no other component can be anchored to the new one yet
+        self.last_resolved = vec![handle];
+        handle
+    }
+
+    /// Removes the component with the given handle from this menu. The components that were
+    /// anchored to it will be anchored to the parent instead. See
+    /// `SimpleFlatMenu.remove_component`.
+    pub fn remove_component(&mut self, handle: ComponentHandle) {
+        self.menu.remove_component(handle);
+        if let Some(index) = self.find_index(handle) {
+            self.entries.remove(index);
+            let dependents = self
+                .entries
+                .iter()
+                .filter(|entry| get_anchor_targets(&entry.constraints).contains(&handle))
+                .map(|entry| entry.handle)
+                .collect();
+            self.update_domains(dependents);
+        }
+    }
+
+    /// Replaces the constraints of the component with the given handle, and moves it (and all
+    /// components that are anchored to it) accordingly.
+    ///
+    /// When the new constraints would anchor the component (directly or indirectly) to itself,
+    /// this method will keep the old constraints, and return an `AnchorCycle` describing the
+    /// cycle instead.
+    pub fn set_constraints(
+        &mut self,
+        handle: ComponentHandle,
+        constraints: AnchorConstraints,
+    ) -> Result<(), AnchorCycle> {
+        if let Some(index) = self.find_index(handle) {
+            let mut visited = HashSet::new();
+            for target in get_anchor_targets(&constraints) {
+                if let Some(path) = self.find_anchor_path(target, handle, &mut visited) {
+                    let mut cycle = vec![handle];
+                    cycle.extend(path);
+                    return Err(AnchorCycle { cycle });
+                }
+            }
+
+            self.entries[index].constraints = constraints;
+            self.update_domains(vec![handle]);
+        }
+        Ok(())
+    }
+
+    /// Gets the current domain of the component with the given handle, or `None` if it is not
+    /// part of this menu
+    pub fn get_domain(&self, handle: ComponentHandle) -> Option<ComponentDomain> {
+        self.entries
+            .iter()
+            .find(|entry| entry.handle == handle)
+            .map(|entry| entry.domain)
+    }
+
+    /// Gets the handles of the components whose domains were recomputed during the last call to
+    /// `add_component`, `remove_component`, or `set_constraints`, in the order in which they were
+    /// recomputed. This is meant for diagnosing layout performance problems.
+    pub fn get_last_resolved(&self) -> &[ComponentHandle] {
+        &self.last_resolved
+    }
+
+    fn find_index(&self, handle: ComponentHandle) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.handle == handle)
+    }
+
+    /// Finds a chain of anchors from the component *from* to the component *to*, which starts
+    /// with *from* and ends with *to*
+    fn find_anchor_path(
+        &self,
+        from: ComponentHandle,
+        to: ComponentHandle,
+        visited: &mut HashSet<ComponentHandle>,
+    ) -> Option<Vec<ComponentHandle>> {
+        if from == to {
+            return Some(vec![to]);
+        }
+        if !visited.insert(from) {
+            return None;
+        }
+        let entry = &self.entries[self.find_index(from)?];
+        for target in get_anchor_targets(&entry.constraints) {
+            if let Some(mut path) = self.find_anchor_path(target, to, visited) {
+                path.insert(0, from);
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Recomputes the domains of the components in *invalid* and the components that are
+    /// (directly or indirectly) anchored to them. The domains of all other components are left
+    /// alone.
+    fn update_domains(&mut self, invalid: Vec<ComponentHandle>) {
+        let mut affected: HashSet<ComponentHandle> = invalid.into_iter().collect();
+        let mut found_more = true;
+        while found_more {
+            found_more = false;
+            for entry in &self.entries {
+                if !affected.contains(&entry.handle)
+                    && get_anchor_targets(&entry.constraints)
+                        .iter()
+                        .any(|target| affected.contains(target))
+                {
+                    affected.insert(entry.handle);
+                    found_more = true;
+                }
+            }
+        }
+
+        // Every component must be resolved after the siblings it is anchored to
+        let mut order = Vec::with_capacity(affected.len());
+        let mut ordered = HashSet::with_capacity(affected.len());
+        for entry in &self.entries {
+            self.push_in_anchor_order(entry.handle, &affected, &mut ordered, &mut order);
+        }
+
+        for handle in &order {
+            let index = self.find_index(*handle).unwrap();
+            let constraints = &self.entries[index].constraints;
+            let new_domain = resolve_anchor_constraints(constraints, &self.entries);
+            let entry = &mut self.entries[index];
+            if !same_domain(entry.domain, new_domain) {
+                entry.domain = new_domain;
+                self.menu.move_component(entry.handle, new_domain);
+            }
+        }
+        self.last_resolved = order;
+    }
+
+    fn push_in_anchor_order(
+        &self,
+        handle: ComponentHandle,
+        affected: &HashSet<ComponentHandle>,
+        ordered: &mut HashSet<ComponentHandle>,
+        order: &mut Vec<ComponentHandle>,
+    ) {
+        if !affected.contains(&handle) || !ordered.insert(handle) {
+            return;
+        }
+        if let Some(index) = self.find_index(handle) {
+            for target in get_anchor_targets(&self.entries[index].constraints) {
+                self.push_in_anchor_order(target, affected, ordered, order);
+            }
+        }
+        order.push(handle);
+    }
+}
+
+fn get_anchor_targets(constraints: &AnchorConstraints) -> Vec<ComponentHandle> {
+    [constraints.left, constraints.right, constraints.bottom, constraints.top]
+        .iter()
+        .filter_map(|anchor| match anchor {
+            Some(Anchor {
+                target: AnchorTarget::Sibling(handle),
+                ..
+            }) => Some(*handle),
+            _ => None,
+        })
+        .collect()
+}
+
+fn same_domain(a: ComponentDomain, b: ComponentDomain) -> bool {
+    a.get_min_x() == b.get_min_x()
+        && a.get_min_y() == b.get_min_y()
+        && a.get_max_x() == b.get_max_x()
+        && a.get_max_y() == b.get_max_y()
+}
+
+fn resolve_anchor_constraints(
+    constraints: &AnchorConstraints,
+    siblings: &[AnchorEntry],
+) -> ComponentDomain {
+    let resolve_anchor = |anchor: Anchor, horizontal: bool| {
+        let sibling_domain = match anchor.target {
+            AnchorTarget::Parent => None,
+            AnchorTarget::Sibling(handle) => siblings
+                .iter()
+                .find(|sibling| sibling.handle == handle)
+                .map(|sibling| sibling.domain),
+        };
+        let (min, max) = match (sibling_domain, horizontal) {
+            (Some(domain), true) => (domain.get_min_x(), domain.get_max_x()),
+            (Some(domain), false) => (domain.get_min_y(), domain.get_max_y()),
+            (None, _) => (0.0, 1.0),
+        };
+        let edge = match anchor.edge {
+            AnchorEdge::Min => min,
+            AnchorEdge::Center => 0.5 * (min + max),
+            AnchorEdge::Max => max,
+        };
+        edge + anchor.offset
+    };
+
+    let resolve_axis = |min: Option<Anchor>, max: Option<Anchor>, size: Option<f32>, horizontal| {
+        let min = min.map(|anchor| resolve_anchor(anchor, horizontal));
+        let max = max.map(|anchor| resolve_anchor(anchor, horizontal));
+        match (min, max, size) {
+            (Some(min), Some(max), _) => (min, max),
+            (Some(min), None, Some(size)) => (min, min + size),
+            (None, Some(max), Some(size)) => (max - size, max),
+            (None, None, Some(size)) => (0.5 - 0.5 * size, 0.5 + 0.5 * size),
+            (min, max, None) => (min.unwrap_or(0.0), max.unwrap_or(1.0)),
+        }
+    };
+
+    let (min_x, max_x) = resolve_axis(
+        constraints.left, constraints.right, constraints.width, true
+    );
+    let (min_y, max_y) = resolve_axis(
+        constraints.bottom, constraints.top, constraints.height, false
+    );
+    ComponentDomain::between(min_x, min_y, max_x, max_y)
+}
+
+impl Component for AnchorLayoutMenu {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy)
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        self.menu.render(renderer, buddy, force)
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click(event, buddy)
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_press(event, buddy)
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_release(event, buddy)
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_move(event, buddy)
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_enter(event, buddy)
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_leave(event, buddy)
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_scroll(event, buddy)
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_double_click(event, buddy)
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_long_press(event, buddy)
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_context_click(event, buddy)
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_middle_click(event, buddy)
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_hover(event, buddy)
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_pinch(event, buddy)
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_rotate_gesture(event, buddy)
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_swipe(event, buddy)
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy)
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.menu.get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.menu.get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.menu.get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    fn assert_domain(expected: (f32, f32, f32, f32), actual: ComponentDomain) {
+        let actual_tuple = (
+            actual.get_min_x(),
+            actual.get_min_y(),
+            actual.get_max_x(),
+            actual.get_max_y(),
+        );
+        let difference = (expected.0 - actual_tuple.0).abs()
+            + (expected.1 - actual_tuple.1).abs()
+            + (expected.2 - actual_tuple.2).abs()
+            + (expected.3 - actual_tuple.3).abs();
+        assert!(difference < 0.0001, "Expected {:?}, but got {:?}", expected, actual_tuple);
+    }
+
+    #[test]
+    fn test_parent_anchors() {
+        let mut menu = AnchorLayoutMenu::new(None);
+
+        let fill = menu.add_component(Box::new(DummyComponent {}), AnchorConstraints::default());
+        assert_domain((0.0, 0.0, 1.0, 1.0), menu.get_domain(fill).unwrap());
+
+        let corner = menu.add_component(Box::new(DummyComponent {}), AnchorConstraints {
+            right: Some(Anchor::parent(AnchorEdge::Max, -0.05)),
+            bottom: Some(Anchor::parent(AnchorEdge::Min, 0.05)),
+            width: Some(0.2),
+            height: Some(0.1),
+            ..AnchorConstraints::default()
+        });
+        assert_domain((0.75, 0.05, 0.95, 0.15), menu.get_domain(corner).unwrap());
+
+        let centered = menu.add_component(Box::new(DummyComponent {}), AnchorConstraints {
+            left: Some(Anchor::parent(AnchorEdge::Min, 0.1)),
+            right: Some(Anchor::parent(AnchorEdge::Center, 0.0)),
+            width: Some(0.9),
+            height: Some(0.5),
+            ..AnchorConstraints::default()
+        });
+        assert_domain((0.1, 0.25, 0.5, 0.75), menu.get_domain(centered).unwrap());
+
+        let top = menu.add_component(Box::new(DummyComponent {}), AnchorConstraints {
+            bottom: Some(Anchor::parent(AnchorEdge::Max, -0.2)),
+            ..AnchorConstraints::default()
+        });
+        assert_domain((0.0, 0.8, 1.0, 1.0), menu.get_domain(top).unwrap());
+    }
+
+    #[test]
+    fn test_sibling_anchors() {
+        let mut menu = AnchorLayoutMenu::new(None);
+
+        let base_constraints = AnchorConstraints {
+            left: Some(Anchor::parent(AnchorEdge::Min, 0.1)),
+            bottom: Some(Anchor::parent(AnchorEdge::Min, 0.1)),
+            width: Some(0.2),
+            height: Some(0.2),
+            ..AnchorConstraints::default()
+        };
+        let base = menu.add_component(Box::new(DummyComponent {}), base_constraints);
+
+        // This component is placed right of base, with the same height
+        let next = menu.add_component(Box::new(DummyComponent {}), AnchorConstraints {
+            left: Some(Anchor::sibling(base, AnchorEdge::Max, 0.05)),
+            bottom: Some(Anchor::sibling(base, AnchorEdge::Min, 0.0)),
+            top: Some(Anchor::sibling(base, AnchorEdge::Max, 0.0)),
+            width: Some(0.3),
+            ..AnchorConstraints::default()
+        });
+        assert_domain((0.35, 0.1, 0.65, 0.3), menu.get_domain(next).unwrap());
+
+        // When base moves, next should move along
+        menu.set_constraints(base, AnchorConstraints {
+            bottom: Some(Anchor::parent(AnchorEdge::Min, 0.5)),
+            ..base_constraints
+        }).unwrap();
+        assert_domain((0.1, 0.5, 0.3, 0.7), menu.get_domain(base).unwrap());
+        assert_domain((0.35, 0.5, 0.65, 0.7), menu.get_domain(next).unwrap());
+
+        // When base is removed, next should be anchored to the parent instead
+        menu.remove_component(base);
+        assert!(menu.get_domain(base).is_none());
+        assert_domain((1.05, 0.0, 1.35, 1.0), menu.get_domain(next).unwrap());
+    }
+
+    #[test]
+    fn test_partial_updates_and_cycles() {
+        let mut menu = AnchorLayoutMenu::new(None);
+
+        let left_constraints = AnchorConstraints {
+            left: Some(Anchor::parent(AnchorEdge::Min, 0.0)),
+            width: Some(0.2),
+            ..AnchorConstraints::default()
+        };
+        let left = menu.add_component(Box::new(DummyComponent {}), left_constraints);
+        let unrelated = menu.add_component(Box::new(DummyComponent {}), AnchorConstraints {
+            right: Some(Anchor::parent(AnchorEdge::Max, 0.0)),
+            width: Some(0.1),
+            ..AnchorConstraints::default()
+        });
+        let middle = menu.add_component(Box::new(DummyComponent {}), AnchorConstraints {
+            left: Some(Anchor::sibling(left, AnchorEdge::Max, 0.0)),
+            width: Some(0.2),
+            ..AnchorConstraints::default()
+        });
+        assert_eq!(&[middle], menu.get_last_resolved());
+
+        // Only left and the components anchored to it should be recomputed
+        menu.set_constraints(left, AnchorConstraints {
+            width: Some(0.3),
+            ..left_constraints
+        }).unwrap();
+        assert_eq!(&[left, middle], menu.get_last_resolved());
+        assert_domain((0.3, 0.0, 0.5, 1.0), menu.get_domain(middle).unwrap());
+        assert_domain((0.9, 0.0, 1.0, 1.0), menu.get_domain(unrelated).unwrap());
+
+        // Components can be anchored to siblings that were added later
+        menu.set_constraints(unrelated, AnchorConstraints {
+            left: Some(Anchor::sibling(middle, AnchorEdge::Max, 0.0)),
+            width: Some(0.1),
+            ..AnchorConstraints::default()
+        }).unwrap();
+        assert_eq!(&[unrelated], menu.get_last_resolved());
+        assert_domain((0.5, 0.0, 0.6, 1.0), menu.get_domain(unrelated).unwrap());
+
+        menu.set_constraints(left, left_constraints).unwrap();
+        assert_eq!(&[left, middle, unrelated], menu.get_last_resolved());
+        assert_domain((0.4, 0.0, 0.5, 1.0), menu.get_domain(unrelated).unwrap());
+
+        // Cycles should be rejected, and the old constraints should be kept
+        let cycle = menu.set_constraints(left, AnchorConstraints {
+            left: Some(Anchor::sibling(unrelated, AnchorEdge::Max, 0.0)),
+            ..left_constraints
+        });
+        assert_eq!(Err(AnchorCycle { cycle: vec![left, unrelated, middle, left] }), cycle);
+        assert!(cycle.unwrap_err().to_string().contains(" -> "));
+        assert_domain((0.0, 0.0, 0.2, 1.0), menu.get_domain(left).unwrap());
+
+        let self_cycle = menu.set_constraints(left, AnchorConstraints {
+            right: Some(Anchor::sibling(left, AnchorEdge::Min, 0.0)),
+            ..left_constraints
+        });
+        assert_eq!(Err(AnchorCycle { cycle: vec![left, left] }), self_cycle);
+
+        // Removing a component should only recompute the components anchored to it
+        menu.remove_component(middle);
+        assert_eq!(&[unrelated], menu.get_last_resolved());
+        assert_domain((1.0, 0.0, 1.1, 1.0), menu.get_domain(unrelated).unwrap());
+    }
+}
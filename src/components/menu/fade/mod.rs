@@ -0,0 +1,352 @@
+use crate::*;
+
+use std::time::Duration;
+
+/// A decorator that draws its *content* component with an adjustable opacity (using
+/// `Renderer.push_opacity`), which is typically used for show/hide transitions of panels and
+/// toasts. The opacity can be changed immediately with `set_opacity`, or gradually with
+/// `fade_to`.
+///
+/// Since translucent drawings are blended with the pixels behind them, this component fills its
+/// domain with its *background_color* before drawing the content, so that the content won't
+/// blend with its own previous drawings. The *background_color* should thus be the color of
+/// whatever is behind this component.
+///
+/// Note that the content will still receive mouse events while it is (nearly) invisible. When a
+/// fade-out is finished, applications should typically remove or hide this component, for
+/// instance using `SimpleFlatMenu.set_component_visible`.
+pub struct FadeComponent {
+    menu: SimpleFlatMenu,
+    background_color: Color,
+
+    opacity: f32,
+    target_opacity: f32,
+    /// The change of the opacity per second while fading
+    fade_speed: f32,
+    rendered_opacity: Option<f32>,
+}
+
+impl FadeComponent {
+    /// Constructs a new `FadeComponent` that draws *content* (which will occupy the entire domain)
+    /// with the given initial *opacity*.
+    pub fn new(content: Box<dyn Component>, background_color: Color, opacity: f32) -> Self {
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(content, ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        let opacity = opacity.clamp(0.0, 1.0);
+        Self {
+            menu,
+            background_color,
+            opacity,
+            target_opacity: opacity,
+            fade_speed: 0.0,
+            rendered_opacity: None,
+        }
+    }
+
+    /// Gets the current opacity, which is between 0.0 (invisible) and 1.0 (fully visible)
+    pub fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Gets the opacity that this component is currently fading to. This is the same as
+    /// `get_opacity` when it is not fading.
+    pub fn get_target_opacity(&self) -> f32 {
+        self.target_opacity
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.opacity != self.target_opacity
+    }
+
+    /// Immediately changes the opacity (which will be clamped between 0.0 and 1.0), and stops
+    /// fading. This component will be redrawn during the next `TickEvent`.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self.target_opacity = self.opacity;
+    }
+
+    /// Gradually changes the opacity to *target_opacity* (which will be clamped between 0.0 and
+    /// 1.0) during the given *duration*, using the `TickEvent`s.
+    pub fn fade_to(&mut self, target_opacity: f32, duration: Duration) {
+        self.target_opacity = target_opacity.clamp(0.0, 1.0);
+        let seconds = duration.as_secs_f32();
+        if seconds > 0.0 {
+            self.fade_speed = (self.target_opacity - self.opacity).abs() / seconds;
+        } else {
+            self.opacity = self.target_opacity;
+        }
+    }
+
+    fn advance_fade(&mut self, delta_seconds: f32) {
+        let max_change = self.fade_speed * delta_seconds;
+        if self.opacity < self.target_opacity {
+            self.opacity = (self.opacity + max_change).min(self.target_opacity);
+        } else {
+            self.opacity = (self.opacity - max_change).max(self.target_opacity);
+        }
+    }
+}
+
+impl Component for FadeComponent {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy);
+        buddy.subscribe_tick();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        // When the content is opaque, there is no risk of blending with old drawings
+        let opacity = self.opacity;
+        if opacity >= 1.0 && self.rendered_opacity == Some(opacity) {
+            return self.menu.render(renderer, buddy, force);
+        }
+
+        renderer.clear(self.background_color);
+        if opacity > 0.0 {
+            let menu = &mut self.menu;
+            renderer.push_opacity(opacity, || menu.render(renderer, buddy, true))?;
+        }
+        self.rendered_opacity = Some(opacity);
+        entire_render_result()
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click(event, buddy)
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_press(event, buddy)
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_release(event, buddy)
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_move(event, buddy)
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_enter(event, buddy)
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_leave(event, buddy)
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_scroll(event, buddy)
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_double_click(event, buddy)
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_long_press(event, buddy)
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_context_click(event, buddy)
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_middle_click(event, buddy)
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_hover(event, buddy)
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_pinch(event, buddy)
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_rotate_gesture(event, buddy)
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_swipe(event, buddy)
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy);
+
+        // The menu unsubscribes when its content doesn't need ticks, but the fading does
+        buddy.subscribe_tick();
+        if self.is_fading() {
+            self.advance_fade(event.get_delta_seconds());
+        }
+        if self.rendered_opacity != Some(self.opacity) {
+            buddy.request_render();
+        }
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.menu.get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.menu.get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.menu.get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    struct OpacityLogComponent {
+        log: Rc<RefCell<Vec<f32>>>,
+    }
+
+    impl Component for OpacityLogComponent {
+        fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+        fn render(
+            &mut self,
+            renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            self.log.borrow_mut().push(renderer.get_opacity());
+            entire_render_result()
+        }
+    }
+
+    #[test]
+    fn test_fade() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let content = OpacityLogComponent {
+            log: Rc::clone(&log),
+        };
+        let mut fade = FadeComponent::new(Box::new(content), Color::rgb(0, 0, 0), 1.0);
+
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        fade.on_attach(&mut buddy);
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+        let mut tick = |fade: &mut FadeComponent, millis: u64| {
+            buddy.clear_render_request();
+            fade.on_tick(TickEvent::new(Duration::from_millis(millis)), &mut buddy);
+            if buddy.did_request_render() {
+                fade.render(&renderer, &mut buddy, false).unwrap();
+            }
+        };
+
+        tick(&mut fade, 10);
+        fade.fade_to(0.0, Duration::from_secs(1));
+        assert!(fade.is_fading());
+        tick(&mut fade, 250);
+        assert_eq!(0.75, fade.get_opacity());
+
+        // The opacity shouldn't go below the target, and the content shouldn't be drawn at 0.0
+        tick(&mut fade, 1000);
+        assert_eq!(0.0, fade.get_opacity());
+        assert!(!fade.is_fading());
+
+        // Nothing should be rendered when the opacity didn't change
+        tick(&mut fade, 10);
+
+        fade.set_opacity(0.5);
+        tick(&mut fade, 10);
+        assert_eq!(vec![1.0, 0.75, 0.5], *log.borrow());
+    }
+}
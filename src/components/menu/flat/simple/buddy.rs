@@ -1,31 +1,69 @@
 use crate::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 pub struct SimpleFlatBuddy {
     subscriptions: ComponentSubscriptions,
 
     mouse_buddy: Rc<RefCell<MouseBuddy>>,
+    theme: Rc<RefCell<Rc<Theme>>>,
+    frame_timing: Rc<Cell<FrameTiming>>,
+    gamepad_store: Rc<RefCell<GamepadStore>>,
     domain: ComponentDomain,
+    seat_policy: SeatPolicy,
 
     last_render_result: Option<RenderResultStruct>,
 
-    create_next_menu: Option<Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>>,
+    menu_change_requests: Vec<MenuChangeRequest>,
+    popup_requests: Vec<PopupRequest>,
+
+    accessible_text: AccessibleText,
+    caret_rectangle: Option<CaretRectangle>,
+    changed_caret_rectangle: bool,
+    cursor: Option<CursorIcon>,
+    window_frame_area: Option<WindowFrameArea>,
+    captured_mouses: Vec<Mouse>,
+    capture_changes: Vec<(Mouse, bool)>,
+    locked_mouses: Vec<Mouse>,
+    lock_changes: Vec<(Mouse, bool)>,
+    requested_focus: bool,
 
     requested_render: bool,
     has_changes: bool,
 }
 
 impl SimpleFlatBuddy {
-    pub(super) fn new(domain: ComponentDomain, mouse_buddy: Rc<RefCell<MouseBuddy>>) -> Self {
+    pub(super) fn new(
+        domain: ComponentDomain,
+        seat_policy: SeatPolicy,
+        mouse_buddy: Rc<RefCell<MouseBuddy>>,
+        theme: Rc<RefCell<Rc<Theme>>>,
+        frame_timing: Rc<Cell<FrameTiming>>,
+        gamepad_store: Rc<RefCell<GamepadStore>>,
+    ) -> Self {
         Self {
             subscriptions: ComponentSubscriptions::new(),
 
             mouse_buddy,
+            theme,
+            frame_timing,
+            gamepad_store,
             domain,
+            seat_policy,
 
             last_render_result: None,
-            create_next_menu: None,
+            menu_change_requests: Vec::new(),
+            popup_requests: Vec::new(),
+            accessible_text: AccessibleText::default(),
+            caret_rectangle: None,
+            changed_caret_rectangle: false,
+            cursor: None,
+            window_frame_area: None,
+            captured_mouses: Vec::new(),
+            capture_changes: Vec::new(),
+            locked_mouses: Vec::new(),
+            lock_changes: Vec::new(),
+            requested_focus: false,
 
             // Components should always render right after they are attached
             requested_render: true,
@@ -52,6 +90,79 @@ impl SimpleFlatBuddy {
 
     pub fn clear_changes(&mut self) {
         self.has_changes = false;
+        self.changed_caret_rectangle = false;
+        self.capture_changes.clear();
+        self.lock_changes.clear();
+        self.requested_focus = false;
+    }
+
+    pub fn get_accessible_text(&self) -> &AccessibleText {
+        &self.accessible_text
+    }
+
+    pub fn get_caret_rectangle(&self) -> Option<CaretRectangle> {
+        self.caret_rectangle
+    }
+
+    pub fn get_cursor(&self) -> Option<CursorIcon> {
+        self.cursor
+    }
+
+    pub fn get_window_frame_area(&self) -> Option<WindowFrameArea> {
+        self.window_frame_area
+    }
+
+    /// Checks whether the component called `set_caret_rectangle` since the last call to
+    /// `clear_changes`
+    pub fn did_change_caret_rectangle(&self) -> bool {
+        self.changed_caret_rectangle
+    }
+
+    /// Gets the mouses that were captured (`true`) or released (`false`) by the component since
+    /// the last call to `clear_changes`, in chronological order
+    pub fn get_capture_changes(&self) -> &[(Mouse, bool)] {
+        &self.capture_changes
+    }
+
+    pub fn get_captured_mouses(&self) -> &[Mouse] {
+        &self.captured_mouses
+    }
+
+    /// Gets the mouses whose pointer was locked (`true`) or released (`false`) by the component
+    /// since the last call to `clear_changes`, in chronological order
+    pub fn get_lock_changes(&self) -> &[(Mouse, bool)] {
+        &self.lock_changes
+    }
+
+    pub fn get_locked_mouses(&self) -> &[Mouse] {
+        &self.locked_mouses
+    }
+
+    /// Checks whether the component called `request_focus` since the last call to `clear_changes`
+    pub fn did_request_focus(&self) -> bool {
+        self.requested_focus
+    }
+
+    /// Changes the domain of the component. If the component has a caret rectangle, it will be
+    /// marked as changed, since its position in the parent changes as well.
+    pub fn set_domain(&mut self, new_domain: ComponentDomain) {
+        self.domain = new_domain;
+        if self.caret_rectangle.is_some() {
+            self.changed_caret_rectangle = true;
+            self.has_changes = true;
+        }
+    }
+
+    /// Ends the capture of the given *mouse* without recording it as change. The menu should use
+    /// this when another component captures the mouse.
+    pub fn lose_mouse_capture(&mut self, mouse: Mouse) {
+        self.captured_mouses.retain(|captured| *captured != mouse);
+    }
+
+    /// Ends the pointer lock of the given *mouse* without recording it as change. The menu should
+    /// use this when another component locks the pointer.
+    pub fn lose_pointer_lock(&mut self, mouse: Mouse) {
+        self.locked_mouses.retain(|locked| *locked != mouse);
     }
 
     pub fn get_last_render_result(&self) -> &Option<RenderResultStruct> {
@@ -62,25 +173,38 @@ impl SimpleFlatBuddy {
         self.last_render_result = Some(result);
     }
 
-    pub fn has_next_menu(&self) -> bool {
-        self.create_next_menu.is_some()
+    /// Takes all menu change requests that the component made since the last call to this
+    /// method, in chronological order. The menu should pass them on to its own buddy, which
+    /// decides which request wins.
+    pub fn take_menu_change_requests(&mut self) -> Vec<MenuChangeRequest> {
+        std::mem::take(&mut self.menu_change_requests)
     }
 
-    pub fn create_next_menu(
-        &mut self,
-    ) -> Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>> {
-        self.create_next_menu
-            .take()
-            .expect("Only call this method after has_next_menu returned true")
+    /// Takes all popups that the component requested to open since the last call to this method
+    pub fn take_popup_requests(&mut self) -> Vec<PopupRequest> {
+        std::mem::take(&mut self.popup_requests)
     }
 }
 
 impl ComponentBuddy for SimpleFlatBuddy {
-    fn change_menu(
-        &mut self,
-        create_new_menu: Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>,
-    ) {
-        self.create_next_menu = Some(create_new_menu);
+    fn request_menu_change(&mut self, request: MenuChangeRequest) {
+        self.menu_change_requests.push(request);
+        self.has_changes = true;
+    }
+
+    fn open_popup(&mut self, domain: ComponentDomain, component: Box<dyn Component>) {
+        self.popup_requests.push(PopupRequest {
+            position: PopupPosition::Fixed(domain),
+            component,
+        });
+        self.has_changes = true;
+    }
+
+    fn open_placed_popup(&mut self, placement: PopupPlacement, component: Box<dyn Component>) {
+        self.popup_requests.push(PopupRequest {
+            position: PopupPosition::Placed(placement),
+            component,
+        });
         self.has_changes = true;
     }
 
@@ -95,6 +219,65 @@ impl ComponentBuddy for SimpleFlatBuddy {
         }
     }
 
+    fn set_accessible_label(&mut self, label: Option<String>) {
+        self.accessible_text.set_label(label);
+    }
+
+    fn set_accessible_description(&mut self, description: Option<String>) {
+        self.accessible_text.set_description(description);
+    }
+
+    fn set_caret_rectangle(&mut self, rectangle: Option<CaretRectangle>) {
+        self.caret_rectangle = rectangle;
+        self.changed_caret_rectangle = true;
+        self.has_changes = true;
+    }
+
+    fn set_cursor(&mut self, cursor: CursorIcon) {
+        self.cursor = Some(cursor);
+    }
+
+    fn set_window_frame_area(&mut self, area: WindowFrameArea) {
+        self.window_frame_area = Some(area);
+    }
+
+    fn capture_mouse(&mut self, mouse: Mouse) {
+        if self.seat_policy.accepts(mouse) && !self.captured_mouses.contains(&mouse) {
+            self.captured_mouses.push(mouse);
+            self.capture_changes.push((mouse, true));
+            self.has_changes = true;
+        }
+    }
+
+    fn release_mouse_capture(&mut self, mouse: Mouse) {
+        if self.captured_mouses.contains(&mouse) {
+            self.lose_mouse_capture(mouse);
+            self.capture_changes.push((mouse, false));
+            self.has_changes = true;
+        }
+    }
+
+    fn request_pointer_lock(&mut self, mouse: Mouse) {
+        if self.seat_policy.accepts(mouse) && !self.locked_mouses.contains(&mouse) {
+            self.locked_mouses.push(mouse);
+            self.lock_changes.push((mouse, true));
+            self.has_changes = true;
+        }
+    }
+
+    fn release_pointer_lock(&mut self, mouse: Mouse) {
+        if self.locked_mouses.contains(&mouse) {
+            self.lose_pointer_lock(mouse);
+            self.lock_changes.push((mouse, false));
+            self.has_changes = true;
+        }
+    }
+
+    fn request_focus(&mut self) {
+        self.requested_focus = true;
+        self.has_changes = true;
+    }
+
     fn subscribe_mouse_click(&mut self) {
         if !self.subscriptions.mouse_click {
             self.subscriptions.mouse_click = true;
@@ -193,18 +376,222 @@ impl ComponentBuddy for SimpleFlatBuddy {
         }
     }
 
-    fn subscribe_char_type(&self) -> Result<(), ()> {
-        todo!()
+    fn subscribe_mouse_scroll(&mut self) {
+        if !self.subscriptions.mouse_scroll {
+            self.subscriptions.mouse_scroll = true;
+            self.has_changes = true;
+        }
     }
 
-    fn unsubscribe_char_type(&self) {
-        todo!()
+    fn unsubscribe_mouse_scroll(&mut self) {
+        if self.subscriptions.mouse_scroll {
+            self.subscriptions.mouse_scroll = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_mouse_double_click(&mut self) {
+        if !self.subscriptions.mouse_double_click {
+            self.subscriptions.mouse_double_click = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_mouse_double_click(&mut self) {
+        if self.subscriptions.mouse_double_click {
+            self.subscriptions.mouse_double_click = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_mouse_long_press(&mut self) {
+        if !self.subscriptions.mouse_long_press {
+            self.subscriptions.mouse_long_press = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_mouse_long_press(&mut self) {
+        if self.subscriptions.mouse_long_press {
+            self.subscriptions.mouse_long_press = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_mouse_context_click(&mut self) {
+        if !self.subscriptions.mouse_context_click {
+            self.subscriptions.mouse_context_click = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_mouse_context_click(&mut self) {
+        if self.subscriptions.mouse_context_click {
+            self.subscriptions.mouse_context_click = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_mouse_middle_click(&mut self) {
+        if !self.subscriptions.mouse_middle_click {
+            self.subscriptions.mouse_middle_click = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_mouse_middle_click(&mut self) {
+        if self.subscriptions.mouse_middle_click {
+            self.subscriptions.mouse_middle_click = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_mouse_hover(&mut self) {
+        if !self.subscriptions.mouse_hover {
+            self.subscriptions.mouse_hover = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_mouse_hover(&mut self) {
+        if self.subscriptions.mouse_hover {
+            self.subscriptions.mouse_hover = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_gestures(&mut self) {
+        if !self.subscriptions.gestures {
+            self.subscriptions.gestures = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_gestures(&mut self) {
+        if self.subscriptions.gestures {
+            self.subscriptions.gestures = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_char_type(&mut self) -> Result<(), ()> {
+        // All current wrappers have a keyboard
+        if !self.subscriptions.char_type {
+            self.subscriptions.char_type = true;
+            self.has_changes = true;
+        }
+        Ok(())
+    }
+
+    fn unsubscribe_char_type(&mut self) {
+        if self.subscriptions.char_type {
+            self.subscriptions.char_type = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_composition(&mut self) {
+        if !self.subscriptions.composition {
+            self.subscriptions.composition = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_composition(&mut self) {
+        if self.subscriptions.composition {
+            self.subscriptions.composition = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_tick(&mut self) {
+        if !self.subscriptions.tick {
+            self.subscriptions.tick = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_tick(&mut self) {
+        if self.subscriptions.tick {
+            self.subscriptions.tick = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_theme_change(&mut self) {
+        if !self.subscriptions.theme_change {
+            self.subscriptions.theme_change = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_theme_change(&mut self) {
+        if self.subscriptions.theme_change {
+            self.subscriptions.theme_change = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_gamepad(&mut self) {
+        if !self.subscriptions.gamepad {
+            self.subscriptions.gamepad = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_gamepad(&mut self) {
+        if self.subscriptions.gamepad {
+            self.subscriptions.gamepad = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_low_memory(&mut self) {
+        if !self.subscriptions.low_memory {
+            self.subscriptions.low_memory = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_low_memory(&mut self) {
+        if self.subscriptions.low_memory {
+            self.subscriptions.low_memory = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_window_state(&mut self) {
+        if !self.subscriptions.window_state {
+            self.subscriptions.window_state = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_window_state(&mut self) {
+        if self.subscriptions.window_state {
+            self.subscriptions.window_state = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_close_request(&mut self) {
+        if !self.subscriptions.close_request {
+            self.subscriptions.close_request = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_close_request(&mut self) {
+        if self.subscriptions.close_request {
+            self.subscriptions.close_request = false;
+            self.has_changes = true;
+        }
     }
 
     fn get_mouse_position(&self, mouse: Mouse) -> Option<Point> {
         let mouse_buddy = self.mouse_buddy.borrow();
         for entry in &mouse_buddy.local_mouses {
-            if entry.mouse == mouse {
+            if entry.mouse == mouse && self.seat_policy.accepts(mouse) {
                 return match self.domain.is_inside(entry.position) {
                     true => Some(self.domain.transform(entry.position)),
                     false => None,
@@ -217,7 +604,10 @@ impl ComponentBuddy for SimpleFlatBuddy {
     fn get_pressed_mouse_buttons(&self, mouse: Mouse) -> Option<Vec<MouseButton>> {
         let mouse_buddy = self.mouse_buddy.borrow();
         for entry in &mouse_buddy.local_mouses {
-            if self.domain.is_inside(entry.position) && entry.mouse == mouse {
+            if self.domain.is_inside(entry.position)
+                && entry.mouse == mouse
+                && self.seat_policy.accepts(mouse)
+            {
                 return Some(entry.pressed_buttons.clone());
             }
         }
@@ -228,7 +618,10 @@ impl ComponentBuddy for SimpleFlatBuddy {
     fn is_mouse_button_down(&self, mouse: Mouse, button: MouseButton) -> Option<bool> {
         let mouse_buddy = self.mouse_buddy.borrow();
         for entry in &mouse_buddy.local_mouses {
-            if self.domain.is_inside(entry.position) && entry.mouse == mouse {
+            if self.domain.is_inside(entry.position)
+                && entry.mouse == mouse
+                && self.seat_policy.accepts(mouse)
+            {
                 return Some(entry.pressed_buttons.contains(&button));
             }
         }
@@ -242,13 +635,43 @@ impl ComponentBuddy for SimpleFlatBuddy {
             .local_mouses
             .iter()
             .filter(|mouse| self.domain.is_inside(mouse.position))
+            .filter(|mouse| self.seat_policy.accepts(mouse.mouse))
             .map(|mouse_entry| mouse_entry.mouse)
             .collect();
     }
 
     fn get_all_mouses(&self) -> Vec<Mouse> {
         let mouse_buddy = self.mouse_buddy.borrow();
-        return mouse_buddy.all_mouses.clone();
+        return mouse_buddy
+            .all_mouses
+            .iter()
+            .filter(|mouse| self.seat_policy.accepts(**mouse))
+            .map(|mouse| *mouse)
+            .collect();
+    }
+
+    fn is_capturing_mouse(&self, mouse: Mouse) -> bool {
+        self.captured_mouses.contains(&mouse)
+    }
+
+    fn is_locking_pointer(&self, mouse: Mouse) -> bool {
+        self.locked_mouses.contains(&mouse)
+    }
+
+    fn get_theme(&self) -> Rc<Theme> {
+        Rc::clone(&self.theme.borrow())
+    }
+
+    fn get_frame_timing(&self) -> FrameTiming {
+        self.frame_timing.get()
+    }
+
+    fn get_gamepads(&self) -> Vec<Gamepad> {
+        self.gamepad_store.borrow().get_gamepads()
+    }
+
+    fn get_gamepad_state(&self, gamepad: Gamepad) -> Option<GamepadState> {
+        self.gamepad_store.borrow().get_gamepad_state(gamepad).cloned()
     }
 }
 
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+/// Limits the time that a `SimpleFlatMenu` spends on rendering its child components during a
+/// single frame. When rendering the children takes longer than the `max_time` of the budget, the
+/// menu will defer the remaining render requests to the next frame, which keeps the input latency
+/// low in very heavy menus. See `SimpleFlatMenu.set_render_budget` for more information.
+///
+/// The *clock* of the budget is used to measure the elapsed time. It should return the time that
+/// passed since some fixed moment in the past. Since `std::time::Instant` is not available on all
+/// platforms (for instance the web), the clock needs to be supplied by the application. This also
+/// makes it possible to use a fake clock in unit tests.
+pub struct RenderBudget {
+    max_time: Duration,
+    clock: Box<dyn Fn() -> Duration>,
+}
+
+impl RenderBudget {
+    pub fn new(max_time: Duration, clock: Box<dyn Fn() -> Duration>) -> Self {
+        Self { max_time, clock }
+    }
+
+    /// Constructs a `RenderBudget` that uses `std::time::Instant` as clock. This is not available
+    /// on the web.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_system_clock(max_time: Duration) -> Self {
+        let start_instant = std::time::Instant::now();
+        Self::new(max_time, Box::new(move || start_instant.elapsed()))
+    }
+
+    pub fn get_max_time(&self) -> Duration {
+        self.max_time
+    }
+
+    pub(super) fn get_current_time(&self) -> Duration {
+        (self.clock)()
+    }
+}
+
+/// How urgently a child component of a `SimpleFlatMenu` needs to be rendered when the menu has a
+/// `RenderBudget`. See `SimpleFlatMenu.set_render_priority`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RenderPriority {
+    /// The render requests of the component can be deferred to the next frame when the render
+    /// budget is exceeded. This is the default priority.
+    Normal,
+    /// The component will be rendered during every frame in which it requests a render, even
+    /// when the render budget is exceeded. This is meant for components that give direct feedback
+    /// to the user, like a text field that is being typed in.
+    High,
+}
@@ -58,6 +58,15 @@ impl ComponentDomain {
             && point.get_y() <= self.get_max_y()
     }
 
+    /// Checks whether this domain overlaps *other*. Domains that only touch each other (for
+    /// instance when the max x of this domain is equal to the min x of *other*) don't overlap.
+    pub fn overlaps(&self, other: ComponentDomain) -> bool {
+        self.get_min_x() < other.get_max_x()
+            && other.get_min_x() < self.get_max_x()
+            && self.get_min_y() < other.get_max_y()
+            && other.get_min_y() < self.get_max_y()
+    }
+
     pub fn transform(&self, outer: Point) -> Point {
         let inner_x = (outer.get_x() - self.get_min_x()) / self.get_width();
         let inner_y = (outer.get_y() - self.get_min_y()) / self.get_height();
@@ -116,6 +125,20 @@ mod tests {
         assert!(domain.is_inside(Point::new(2.0, 3.0)));
     }
 
+    #[test]
+    fn test_overlaps() {
+        let domain = ComponentDomain::between(0.25, 0.25, 0.75, 0.75);
+
+        assert!(domain.overlaps(domain));
+        assert!(domain.overlaps(ComponentDomain::between(0.0, 0.0, 1.0, 1.0)));
+        assert!(domain.overlaps(ComponentDomain::between(0.5, 0.0, 1.0, 0.5)));
+        assert!(ComponentDomain::between(0.5, 0.0, 1.0, 0.5).overlaps(domain));
+
+        assert!(!domain.overlaps(ComponentDomain::between(0.75, 0.25, 1.0, 0.75)));
+        assert!(!domain.overlaps(ComponentDomain::between(0.0, 0.8, 1.0, 1.0)));
+        assert!(!ComponentDomain::between(0.0, 0.8, 1.0, 1.0).overlaps(domain));
+    }
+
     #[test]
     fn test_transform() {
         // These numbers are carefully chosen to avoid rounding errors
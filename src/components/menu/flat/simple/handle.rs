@@ -0,0 +1,16 @@
+/// A handle to a component that was added to a `SimpleFlatMenu`. The `add_component` methods of
+/// the menu return a handle, which can be used to refer to the component later (for instance to
+/// remove it).
+///
+/// Handles are only meaningful for the menu that created them. The handle of a component that
+/// has been removed will never be reused (not even when another component is added).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ComponentHandle {
+    id: u64,
+}
+
+impl ComponentHandle {
+    pub(super) fn new(id: u64) -> Self {
+        Self { id }
+    }
+}
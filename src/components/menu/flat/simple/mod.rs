@@ -1,62 +1,632 @@
 use crate::*;
 
-use std::cell::RefCell;
+use std::cell::{Cell, Ref, RefCell};
 use std::rc::Rc;
+use std::time::Duration;
 
+mod budget;
 mod buddy;
 mod domain;
+mod handle;
 
+pub use budget::*;
 use buddy::*;
 pub use domain::*;
+pub use handle::*;
 
 type RR<T> = Rc<RefCell<T>>;
 //type WR<T> = Weak<RefCell<T>>;
 
+/// A popup that a component requested to open using `ComponentBuddy.open_popup`
+pub(crate) struct PopupRequest {
+    pub(crate) position: PopupPosition,
+    pub(crate) component: Box<dyn Component>,
+}
+
+/// A menu component that renders its child components in the domains that were given when they
+/// were added.
+///
+/// ## Z-order
+/// The domains of the child components are allowed to overlap. Components that were added later
+/// will be rendered on top of the components that were added earlier, and they will receive the
+/// mouse events where they overlap. Components that filter mouse actions let the mouse events
+/// outside their drawn region through to the components below them. Use `raise_component` and
+/// `lower_component` to change the order.
 pub struct SimpleFlatMenu {
     components: Vec<RR<ComponentEntry>>,
-    components_to_add: Vec<ComponentToAdd>,
+    // The components that are subscribed to mouse move, enter, or leave events
+    mouse_move_components: Vec<RR<ComponentEntry>>,
+    // The components that are subscribed to mouse click out events
+    click_out_components: Vec<RR<ComponentEntry>>,
+    subscription_lists_dirty: Cell<bool>,
+    pending_changes: Vec<PendingChange>,
+    next_handle_id: u64,
+    // The optional string ids of the components (see set_component_id)
+    component_ids: Vec<(String, ComponentHandle)>,
+    // The component that receives the char type, composition, and gamepad events
+    focused_component: Cell<Option<ComponentHandle>>,
+    // This is set when a component was removed or moved, since its old area needs to be repainted
+    needs_full_render: bool,
     background_color: Option<Color>,
     has_rendered_before: bool,
 
+    render_budget: Option<RenderBudget>,
+    clip_to_drawn_regions: bool,
+    hover_hysteresis: f32,
+    // The components whose render priority is not Normal (see set_render_priority)
+    render_priorities: Vec<(ComponentHandle, RenderPriority)>,
+
+    // When this is set, the popup requests of the components are put in here rather than being
+    // passed on to the own buddy (this is used by the PopupMenu)
+    popup_sink: Option<RR<Vec<PopupRequest>>>,
+
     mouse_buddy: RR<MouseBuddy>,
+    // The theme of the own buddy, which is shared with the buddies of the components
+    theme: RR<Rc<Theme>>,
+    // The frame timing of the own buddy, which is shared in the same way
+    frame_timing: Rc<Cell<FrameTiming>>,
+    // A copy of the gamepads of the own buddy, which is shared in the same way
+    gamepad_store: RR<GamepadStore>,
 }
 
 impl SimpleFlatMenu {
     pub fn new(background_color: Option<Color>) -> Self {
         Self {
             components: Vec::new(),
-            components_to_add: Vec::new(),
+            mouse_move_components: Vec::new(),
+            click_out_components: Vec::new(),
+            subscription_lists_dirty: Cell::new(false),
+            pending_changes: Vec::new(),
+            next_handle_id: 0,
+            component_ids: Vec::new(),
+            focused_component: Cell::new(None),
+            needs_full_render: false,
             background_color,
             has_rendered_before: false,
 
+            render_budget: None,
+            clip_to_drawn_regions: false,
+            hover_hysteresis: 0.0,
+            render_priorities: Vec::new(),
+
+            popup_sink: None,
+
             mouse_buddy: Rc::new(RefCell::new(MouseBuddy {
                 all_mouses: Vec::new(),
                 local_mouses: Vec::new(),
             })),
+            theme: Rc::new(RefCell::new(Rc::new(Theme::default()))),
+            frame_timing: Rc::new(Cell::new(FrameTiming::default())),
+            gamepad_store: Rc::new(RefCell::new(GamepadStore::new())),
+        }
+    }
+
+    pub fn add_component(
+        &mut self,
+        component: Box<dyn Component>,
+        domain: ComponentDomain,
+    ) -> ComponentHandle {
+        self.add_component_with_seat_policy(component, domain, SeatPolicy::AllSeats)
+    }
+
+    /// Adds a component to this menu that will only receive the mouse events of the `Mouse`s that
+    /// are accepted by the given `SeatPolicy`. Other mouses will be invisible to the component:
+    /// they won't fire any events on it and its buddy won't report them as local mouses.
+    pub fn add_component_with_seat_policy(
+        &mut self,
+        component: Box<dyn Component>,
+        domain: ComponentDomain,
+        seat_policy: SeatPolicy,
+    ) -> ComponentHandle {
+        let handle = ComponentHandle::new(self.next_handle_id);
+        self.next_handle_id += 1;
+
+        self.pending_changes.push(PendingChange::Add(ComponentToAdd {
+            handle,
+            component,
+            domain,
+            seat_policy,
+        }));
+        handle
+    }
+
+    /// Removes the component with the given handle from this menu. Its `on_detach` method will be
+    /// called and the menu will repaint the area that it occupied (if the menu has a background
+    /// color; otherwise the parent of the menu is responsible for that).
+    ///
+    /// Like adding components, the removal will be processed when the menu receives its next
+    /// event (or render call). Removing a component that is not (or no longer) part of this menu
+    /// has no effect.
+    pub fn remove_component(&mut self, handle: ComponentHandle) {
+        self.component_ids.retain(|(_, id_handle)| *id_handle != handle);
+        self.render_priorities.retain(|(priority_handle, _)| *priority_handle != handle);
+        self.pending_changes.push(PendingChange::Remove(handle));
+    }
+
+    /// Replaces the component with the given handle with *new_component*, which will get the same
+    /// domain, seat policy, and handle. The old component will be detached and the new component
+    /// will be attached. Like `remove_component`, this will be processed during the next event.
+    pub fn replace_component(
+        &mut self,
+        handle: ComponentHandle,
+        new_component: Box<dyn Component>,
+    ) {
+        self.pending_changes.push(PendingChange::Replace(handle, new_component));
+    }
+
+    /// Moves and/or resizes the component with the given handle to *new_domain*. The `on_resize`
+    /// method of the component will be called, and the menu will repaint both the old and the new
+    /// region of the component. Like `remove_component`, this will be processed during the next
+    /// event, and it has no effect if the component is not (or no longer) part of this menu.
+    pub fn move_component(&mut self, handle: ComponentHandle, new_domain: ComponentDomain) {
+        self.pending_changes.push(PendingChange::Move(handle, new_domain));
+    }
+
+    /// Like `move_component`, but the component will gradually move from its current domain to
+    /// *new_domain* during the given *duration*, using the given `Easing` curve. During the
+    /// animation, the domain is updated during every tick: both the region in which the component
+    /// is rendered and the transformation of the mouse positions use the interpolated domain, and
+    /// the `on_resize` method of the component will be called whenever it changed.
+    ///
+    /// Calling `move_component` or `move_component_animated` again while the component is still
+    /// moving cancels the running animation (a new animation starts at the current interpolated
+    /// domain).
+    pub fn move_component_animated(
+        &mut self,
+        handle: ComponentHandle,
+        new_domain: ComponentDomain,
+        duration: Duration,
+        easing: Easing,
+    ) {
+        self.pending_changes
+            .push(PendingChange::AnimatedMove(handle, new_domain, duration, easing));
+    }
+
+    /// Moves the component with the given handle to the top of this menu: it will be rendered
+    /// after (and thus on top of) all other components, and it will receive the mouse events
+    /// where it overlaps other components. Like `remove_component`, this will be processed during
+    /// the next event.
+    pub fn raise_component(&mut self, handle: ComponentHandle) {
+        self.pending_changes.push(PendingChange::Raise(handle));
+    }
+
+    /// Moves the component with the given handle to the bottom of this menu: it will be rendered
+    /// before (and thus below) all other components. See `raise_component`.
+    pub fn lower_component(&mut self, handle: ComponentHandle) {
+        self.pending_changes.push(PendingChange::Lower(handle));
+    }
+
+    /// Hides (when *visible* is false) or shows the component with the given handle. Hidden
+    /// components won't be rendered and won't receive any events, but they stay attached, so they
+    /// keep their state for when they are shown again. Like `remove_component`, this will be
+    /// processed during the next event, and the menu will repaint the area that the component
+    /// occupied when it is hidden.
+    ///
+    /// The mouses that are on the component when it is hidden will leave it (if it is subscribed
+    /// to `MouseLeaveEvent`s), and the mouses that are on the component when it is shown again
+    /// will enter it.
+    pub fn set_component_visible(&mut self, handle: ComponentHandle, visible: bool) {
+        self.pending_changes
+            .push(PendingChange::SetVisible(handle, visible));
+    }
+
+    /// Checks whether the component with the given handle is visible (see
+    /// `set_component_visible`), taking the changes that haven't been processed yet into account.
+    /// Returns `None` if this menu doesn't have a component with the given handle (anymore).
+    pub fn is_component_visible(&self, handle: ComponentHandle) -> Option<bool> {
+        let mut visible = self
+            .find_component_index(handle)
+            .map(|index| self.components[index].borrow().visible);
+        for change in &self.pending_changes {
+            match change {
+                PendingChange::Add(to_add) if to_add.handle == handle => visible = Some(true),
+                PendingChange::Remove(removed) if *removed == handle => visible = None,
+                PendingChange::Replace(replaced, _) if *replaced == handle && visible.is_some() => {
+                    visible = Some(true)
+                }
+                PendingChange::SetVisible(changed, new_visible) if *changed == handle => {
+                    visible = visible.map(|_| *new_visible)
+                }
+                PendingChange::Clear => visible = None,
+                _ => {}
+            }
+        }
+        visible
+    }
+
+    /// Removes all components from this menu (including the components that were added, but not
+    /// yet processed). See `remove_component`.
+    pub fn clear_components(&mut self) {
+        self.component_ids.clear();
+        self.render_priorities.clear();
+        self.pending_changes.clear();
+        self.pending_changes.push(PendingChange::Clear);
+    }
+
+    /// Gives the component with the given handle a string *id*, which can be used to find its
+    /// handle later (see `find_component_by_id`). This is convenient for application code that
+    /// doesn't want to keep track of all handles. Ids are unique within a menu: if another
+    /// component already has the given *id*, it will lose its id.
+    ///
+    /// Unlike most other methods, this takes effect immediately. The id will be forgotten when
+    /// the component is removed.
+    pub fn set_component_id(&mut self, handle: ComponentHandle, id: impl Into<String>) {
+        let id = id.into();
+        self.component_ids
+            .retain(|(other_id, other_handle)| *other_id != id && *other_handle != handle);
+        self.component_ids.push((id, handle));
+    }
+
+    /// Gets the id that was given to the component with the given handle (see
+    /// `set_component_id`), if any
+    pub fn get_component_id(&self, handle: ComponentHandle) -> Option<&str> {
+        self.component_ids
+            .iter()
+            .find(|(_, id_handle)| *id_handle == handle)
+            .map(|(id, _)| id.as_str())
+    }
+
+    /// Finds the handle of the component with the given *id* (see `set_component_id`)
+    pub fn find_component_by_id(&self, id: &str) -> Option<ComponentHandle> {
+        self.component_ids
+            .iter()
+            .find(|(other_id, _)| other_id == id)
+            .map(|(_, handle)| *handle)
+    }
+
+    /// Gets the component with the given handle, which can be used to query it (for instance its
+    /// accessible text). Components that were added (or replaced) since the last event of this
+    /// menu can't be retrieved yet, since the menu hasn't processed them yet.
+    ///
+    /// Note that this borrows the component, so it must be dropped before the menu receives its
+    /// next event.
+    pub fn get_component(&self, handle: ComponentHandle) -> Option<Ref<'_, dyn Component>> {
+        self.find_component_index(handle).map(|index| {
+            Ref::map(self.components[index].borrow(), |entry| entry.component.as_ref())
+        })
+    }
+
+    /// Finds the handle of the topmost visible component at the given *point* (relative to this
+    /// menu), using the same rules as the mouse events: components that filter mouse actions are
+    /// skipped when *point* is outside their drawn region, unless no component below them
+    /// contains *point*.
+    pub fn find_component_at(&self, point: Point) -> Option<ComponentHandle> {
+        self.get_component_index_at(point)
+            .map(|index| self.components[index].borrow().handle)
+    }
+
+    fn find_component_index(&self, handle: ComponentHandle) -> Option<usize> {
+        self.components
+            .iter()
+            .position(|entry_cell| entry_cell.borrow().handle == handle)
+    }
+
+    /// Lets the own buddy forget the mouse captures, pointer locks, and caret rectangle of the
+    /// given entry
+    fn forget_entry_requests(own_buddy: &mut dyn ComponentBuddy, entry: &mut ComponentEntry) {
+        for mouse in entry.buddy.get_captured_mouses().to_vec() {
+            own_buddy.release_mouse_capture(mouse);
+            entry.buddy.lose_mouse_capture(mouse);
+        }
+        for mouse in entry.buddy.get_locked_mouses().to_vec() {
+            own_buddy.release_pointer_lock(mouse);
+            entry.buddy.lose_pointer_lock(mouse);
+        }
+        if entry.buddy.get_caret_rectangle().is_some() {
+            own_buddy.set_caret_rectangle(None);
+        }
+    }
+
+    /// Detaches the given entry and lets the own buddy forget everything the component requested
+    fn remove_entry(&mut self, own_buddy: &mut dyn ComponentBuddy, entry_cell: RR<ComponentEntry>) {
+        Self::forget_entry_requests(own_buddy, &mut entry_cell.borrow_mut());
+
+        let is_other = |other: &RR<ComponentEntry>| !Rc::ptr_eq(other, &entry_cell);
+        self.mouse_move_components.retain(is_other);
+        self.click_out_components.retain(is_other);
+        if self.focused_component.get() == Some(entry_cell.borrow().handle) {
+            self.focused_component.set(None);
+        }
+
+        // The entry will call on_detach when it is dropped (at the end of this method)
+        self.needs_full_render = true;
+        own_buddy.request_render();
+    }
+
+    pub fn get_background_color(&self) -> Option<Color> {
+        self.background_color
+    }
+
+    /// Changes (or removes) the background color of this menu. The menu will be redrawn entirely
+    /// during the next render.
+    pub fn set_background_color(&mut self, background_color: Option<Color>) {
+        self.background_color = background_color;
+        self.needs_full_render = true;
+    }
+
+    /// Sets (or removes) the `RenderBudget` of this menu. When rendering the child components
+    /// during a frame takes longer than the budget allows, the render requests of the remaining
+    /// child components will be deferred to the next frame. The components are still rendered
+    /// from bottom to top, and a component is never deferred during 2 consecutive frames, so every
+    /// component will get its turn in the next frame.
+    ///
+    /// Components with `RenderPriority::High` (see `set_render_priority`) are never deferred. The
+    /// same holds for components that must be redrawn because a component below them was redrawn,
+    /// since they would be painted over otherwise.
+    ///
+    /// The budget is ignored when the menu is *forced* to render, since all components need to be
+    /// drawn in that case.
+    pub fn set_render_budget(&mut self, budget: Option<RenderBudget>) {
+        self.render_budget = budget;
+    }
+
+    /// Sets the `RenderPriority` of the component with the given handle, which is
+    /// `RenderPriority::Normal` by default. This only matters when this menu has a `RenderBudget`.
+    pub fn set_render_priority(&mut self, handle: ComponentHandle, priority: RenderPriority) {
+        self.render_priorities.retain(|(priority_handle, _)| *priority_handle != handle);
+        if priority != RenderPriority::Normal {
+            self.render_priorities.push((handle, priority));
+        }
+    }
+
+    /// Gets the `RenderPriority` of the component with the given handle (see
+    /// `set_render_priority`)
+    pub fn get_render_priority(&self, handle: ComponentHandle) -> RenderPriority {
+        self.render_priorities
+            .iter()
+            .find(|(priority_handle, _)| *priority_handle == handle)
+            .map_or(RenderPriority::Normal, |(_, priority)| *priority)
+    }
+
+    /// Enables or disables the *clip-to-drawn-region* mode of this menu, which is meant to catch
+    /// buggy components during development. In this mode, the menu will repaint the parts of the
+    /// domain of a component that are outside the bounds of the drawn region it reported, right
+    /// after it rendered. Anything that a component draws outside its reported drawn region will
+    /// thus disappear, just like it would be ignored by the mouse hit tests.
+    ///
+    /// The menu repaints those parts with its background color (if it has one) and by rendering
+    /// the components underneath the component again, which makes rendering more expensive. Note
+    /// that only the (rectangular) bounds of the drawn regions are enforced.
+    pub fn set_clip_to_drawn_regions(&mut self, clip: bool) {
+        self.clip_to_drawn_regions = clip;
+    }
+
+    /// Sets the hover hysteresis *margin* of this menu, which is 0.0 (disabled) by default. When
+    /// it is positive, a child component will only receive a `MouseLeaveEvent` once the mouse
+    /// moved more than *margin* outside the bounds of its drawn region (or its domain, when it
+    /// doesn't filter mouse actions), and it won't receive another `MouseEnterEvent` when the
+    /// mouse returns before that. This prevents rapid jitter across the edge of a component from
+    /// spamming enter and leave events (and the re-renders they typically cause). The margin is
+    /// ignored when the mouse moves onto another component that covers it.
+    ///
+    /// The *margin* is relative to the size of this menu (like the domains of the components).
+    /// See `InputSettings.hover_hysteresis` for the equivalent setting of the root component.
+    pub fn set_hover_hysteresis(&mut self, margin: f32) {
+        self.hover_hysteresis = margin;
+    }
+
+    /// Repaints the parts of *domain* that are outside the bounds of *drawn_region* (which is
+    /// relative to *domain*), using the background color and the components before
+    /// *component_index*. Returns the render result of the first component that failed to render
+    /// (if any). See `set_clip_to_drawn_regions`.
+    fn repaint_outside_drawn_region(
+        &self,
+        renderer: &Renderer,
+        own_buddy: &mut dyn ComponentBuddy,
+        component_index: usize,
+        domain: ComponentDomain,
+        drawn_region: &dyn DrawnRegion,
+    ) -> Option<RenderResult> {
+        // Work with whole pixels of the viewport of the component: the strips shouldn't touch the
+        // pixels that are (partially) covered by the drawn region, but they shouldn't leave any
+        // gaps next to it either
+        let viewport = renderer.get_viewport();
+        // When the component doesn't cover any pixels, there is nothing to repaint
+        let (component_x, component_y, bound_x, bound_y) = viewport.unclamped_child_bounds(
+            domain.get_min_x(),
+            domain.get_min_y(),
+            domain.get_max_x(),
+            domain.get_max_y(),
+        )?;
+        let (width, height) = ((bound_x - component_x) as u32, (bound_y - component_y) as u32);
+        let drawn_bounds = RenderRegion::with_size(0, 0, width, height)
+            .drawn_region_bounds(drawn_region);
+
+        // Convert pixels of the component to coordinates relative to the viewport of this menu
+        let to_x = |pixels: u32| {
+            (component_x + pixels as i64 - viewport.get_min_x() as i64) as f32
+                / viewport.get_width() as f32
+        };
+        let to_y = |pixels: u32| {
+            (component_y + pixels as i64 - viewport.get_min_y() as i64) as f32
+                / viewport.get_height() as f32
+        };
+
+        let strips = match drawn_bounds {
+            Some(bounds) => {
+                let (left, bottom) = (bounds.get_min_x(), bounds.get_min_y());
+                let (right, top) = (bounds.get_bound_x(), bounds.get_bound_y());
+                vec![
+                    (0, 0, left, height),
+                    (right, 0, width, height),
+                    (left, 0, right, bottom),
+                    (left, top, right, height),
+                ]
+            }
+            None => vec![(0, 0, width, height)],
+        };
+        for (min_x, min_y, max_x, max_y) in strips {
+            if min_x >= max_x || min_y >= max_y {
+                continue;
+            }
+
+            let (min_x, max_x) = (to_x(min_x), to_x(max_x));
+            let (min_y, max_y) = (to_y(min_y), to_y(max_y));
+            let strip = ComponentDomain::between(min_x, min_y, max_x, max_y);
+            let failed_result = renderer.push_scissor(min_x, min_y, max_x, max_y, || {
+                if let Some(background_color) = self.background_color {
+                    renderer.clear(background_color);
+                }
+                for lower_cell in &self.components[..component_index] {
+                    let mut lower = lower_cell.borrow_mut();
+                    if lower.visible && lower.domain.overlaps(strip) {
+                        let lower_result = lower.render(renderer, true);
+                        self.check_buddy(own_buddy, &mut lower, false);
+                        if let Some(Err(error)) = lower_result {
+                            return Some(Err(error));
+                        }
+                    }
+                }
+                None
+            });
+            if let Some(Some(failed_result)) = failed_result {
+                return Some(failed_result);
+            }
         }
+
+        None
     }
 
-    pub fn add_component(&mut self, component: Box<dyn Component>, domain: ComponentDomain) {
-        self.components_to_add
-            .push(ComponentToAdd { component, domain });
+    /// Makes this menu put the popup requests of its components in the given *sink*, rather than
+    /// passing them on to its own buddy
+    pub(crate) fn set_popup_sink(&mut self, sink: RR<Vec<PopupRequest>>) {
+        self.popup_sink = Some(sink);
     }
 
     fn update_internal(&mut self, own_buddy: &mut dyn ComponentBuddy, is_about_to_render: bool) {
-        while !self.components_to_add.is_empty() {
-            let to_add = self.components_to_add.swap_remove(0);
-            let mut entry_to_add = ComponentEntry {
-                component: to_add.component,
-                domain: to_add.domain,
-                buddy: SimpleFlatBuddy::new(to_add.domain, Rc::clone(&self.mouse_buddy)),
-            };
+        // The components that are about to be attached should already see the right theme
+        *self.theme.borrow_mut() = own_buddy.get_theme();
+        self.frame_timing.set(own_buddy.get_frame_timing());
+        self.gamepad_store.borrow_mut().copy_from(own_buddy);
+
+        // Note: the components might add more pending changes while they are being attached
+        while !self.pending_changes.is_empty() {
+            self.subscription_lists_dirty.set(true);
+            match self.pending_changes.remove(0) {
+                PendingChange::Add(to_add) => {
+                    let mut entry_to_add = ComponentEntry::new(
+                        to_add.handle,
+                        to_add.component,
+                        to_add.domain,
+                        to_add.seat_policy,
+                        Rc::clone(&self.mouse_buddy),
+                        Rc::clone(&self.theme),
+                        Rc::clone(&self.frame_timing),
+                        Rc::clone(&self.gamepad_store),
+                    );
 
-            entry_to_add.attach();
-            self.check_buddy(own_buddy, &mut entry_to_add, is_about_to_render);
+                    entry_to_add.attach();
+                    self.check_buddy(own_buddy, &mut entry_to_add, is_about_to_render);
 
-            // Don't forget this x)
-            self.components.push(Rc::new(RefCell::new(entry_to_add)));
+                    // Don't forget this x)
+                    self.components.push(Rc::new(RefCell::new(entry_to_add)));
+                }
+                PendingChange::Remove(handle) => {
+                    if let Some(index) = self.find_component_index(handle) {
+                        let entry = self.components.remove(index);
+                        self.remove_entry(own_buddy, entry);
+                    }
+                }
+                PendingChange::Replace(handle, new_component) => {
+                    if let Some(index) = self.find_component_index(handle) {
+                        let old_entry = self.components.remove(index);
+                        let domain = old_entry.borrow().domain;
+                        let seat_policy = old_entry.borrow().seat_policy;
+                        self.remove_entry(own_buddy, old_entry);
+
+                        let mut new_entry = ComponentEntry::new(
+                            handle,
+                            new_component,
+                            domain,
+                            seat_policy,
+                            Rc::clone(&self.mouse_buddy),
+                            Rc::clone(&self.theme),
+                            Rc::clone(&self.frame_timing),
+                            Rc::clone(&self.gamepad_store),
+                        );
+                        new_entry.attach();
+                        self.check_buddy(own_buddy, &mut new_entry, is_about_to_render);
+                        self.components.insert(index, Rc::new(RefCell::new(new_entry)));
+                    }
+                }
+                PendingChange::Move(handle, new_domain) => {
+                    if let Some(index) = self.find_component_index(handle) {
+                        let mut entry = self.components[index].borrow_mut();
+                        entry.domain_animation = None;
+                        entry.resize(new_domain);
+                        self.check_buddy(own_buddy, &mut entry, is_about_to_render);
+
+                        // The old region needs to be repainted, so just redraw everything
+                        self.needs_full_render = true;
+                        own_buddy.request_render();
+                    }
+                }
+                PendingChange::AnimatedMove(handle, new_domain, duration, easing) => {
+                    if let Some(index) = self.find_component_index(handle) {
+                        let mut entry = self.components[index].borrow_mut();
+                        let tween = Tween::new(entry.domain, new_domain, duration, easing);
+                        entry.domain_animation = Some(tween);
+
+                        // The domain will be updated in on_tick
+                        own_buddy.subscribe_tick();
+                    }
+                }
+                PendingChange::Raise(handle) => {
+                    if let Some(index) = self.find_component_index(handle) {
+                        let entry = self.components.remove(index);
+                        self.components.push(entry);
+                        self.needs_full_render = true;
+                        own_buddy.request_render();
+                    }
+                }
+                PendingChange::Lower(handle) => {
+                    if let Some(index) = self.find_component_index(handle) {
+                        let entry = self.components.remove(index);
+                        self.components.insert(0, entry);
+                        self.needs_full_render = true;
+                        own_buddy.request_render();
+                    }
+                }
+                PendingChange::SetVisible(handle, visible) => {
+                    if let Some(index) = self.find_component_index(handle) {
+                        let mut entry = self.components[index].borrow_mut();
+                        if entry.visible != visible {
+                            entry.visible = visible;
+                            if visible {
+                                entry.needs_forced_render = true;
+                            } else {
+                                // The mouses on the component should leave it when it is hidden
+                                let hovering_mouses = entry.hovering_mouses.clone();
+                                for mouse in hovering_mouses {
+                                    if let Some(point) = own_buddy.get_mouse_position(mouse) {
+                                        entry.mouse_leave(MouseLeaveEvent::new(mouse, point));
+                                    }
+                                }
+                                entry.hovering_mouses.clear();
+                                Self::forget_entry_requests(own_buddy, &mut entry);
+
+                                // The area of the hidden component needs to be repainted
+                                self.needs_full_render = true;
+                            }
+                            self.check_buddy(own_buddy, &mut entry, is_about_to_render);
+                            own_buddy.request_render();
+                            drop(entry);
+
+                            // The component could cover or reveal the components below it
+                            self.update_hovered_components(own_buddy, is_about_to_render);
+                        }
+                    }
+                }
+                PendingChange::Clear => {
+                    for entry in std::mem::take(&mut self.components) {
+                        self.remove_entry(own_buddy, entry);
+                    }
+                }
+            }
         }
 
+        self.update_subscription_lists();
+
         // Keep the mouse buddy up-to-date
         let mut mouse_buddy = self.mouse_buddy.borrow_mut();
         mouse_buddy.all_mouses = own_buddy.get_all_mouses();
@@ -84,6 +654,58 @@ impl SimpleFlatMenu {
         drop(mouse_buddy);
     }
 
+    /// Rebuilds the lists of components that are subscribed to mouse move (or enter or leave)
+    /// events and mouse click out events, if any subscriptions changed since the last time
+    fn update_subscription_lists(&mut self) {
+        if !self.subscription_lists_dirty.get() {
+            return;
+        }
+
+        self.mouse_move_components.clear();
+        self.click_out_components.clear();
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            entry.in_mouse_move_list = entry.visible && entry.wants_mouse_moves();
+            entry.in_click_out_list =
+                entry.visible && entry.buddy.get_subscriptions().mouse_click_out;
+
+            if entry.in_mouse_move_list {
+                self.mouse_move_components.push(Rc::clone(entry_cell));
+            }
+            if entry.in_click_out_list {
+                self.click_out_components.push(Rc::clone(entry_cell));
+            }
+        }
+        self.subscription_lists_dirty.set(false);
+    }
+
+    /// Sends enter and leave events to the components that have been covered or revealed by
+    /// another component (for instance because it became visible or hidden), for all mouses that
+    /// are currently in this menu. Only the topmost component at a mouse should be hovered by it.
+    fn update_hovered_components(
+        &self,
+        own_buddy: &mut dyn ComponentBuddy,
+        is_about_to_render: bool,
+    ) {
+        for mouse in own_buddy.get_local_mouses() {
+            if let Some(point) = own_buddy.get_mouse_position(mouse) {
+                let hit_index = self.get_component_index_at(point);
+                for (index, entry_cell) in self.components.iter().enumerate() {
+                    let mut entry = entry_cell.borrow_mut();
+                    let hovering = entry.hovering_mouses.contains(&mouse);
+                    if hovering && hit_index.is_some() && hit_index != Some(index) {
+                        entry.mouse_leave(MouseLeaveEvent::new(mouse, point));
+                    } else if !hovering && hit_index == Some(index) {
+                        entry.mouse_enter(MouseEnterEvent::new(mouse, point));
+                    } else {
+                        continue;
+                    }
+                    self.check_buddy(own_buddy, &mut entry, is_about_to_render);
+                }
+            }
+        }
+    }
+
     fn check_buddy(
         &self,
         own_buddy: &mut dyn ComponentBuddy,
@@ -91,29 +713,264 @@ impl SimpleFlatMenu {
         is_about_to_render: bool,
     ) {
         if entry.buddy.has_changes() {
+            // The subscription lists will be updated before the next event
+            if entry.in_mouse_move_list != (entry.visible && entry.wants_mouse_moves())
+                || entry.in_click_out_list
+                    != (entry.visible && entry.buddy.get_subscriptions().mouse_click_out)
+            {
+                self.subscription_lists_dirty.set(true);
+            }
+
             if !is_about_to_render && entry.buddy.did_request_render() {
                 own_buddy.request_render();
                 // Don't clear the render request until we have really rendered it
             }
 
-            if entry.buddy.has_next_menu() {
-                own_buddy.change_menu(entry.buddy.create_next_menu());
+            for request in entry.buddy.take_menu_change_requests() {
+                own_buddy.request_menu_change(request);
+            }
+
+            // The component can only have the focus if this menu has the focus
+            if entry.buddy.did_request_focus() {
+                self.focused_component.set(Some(entry.handle));
+                own_buddy.request_focus();
+            }
+
+            for mut request in entry.buddy.take_popup_requests() {
+                request.position = request.position.transform_back(entry.domain);
+                match &self.popup_sink {
+                    Some(sink) => sink.borrow_mut().push(request),
+                    None => match request.position {
+                        PopupPosition::Fixed(domain) => {
+                            own_buddy.open_popup(domain, request.component)
+                        }
+                        PopupPosition::Placed(placement) => {
+                            own_buddy.open_placed_popup(placement, request.component)
+                        }
+                    },
+                };
+            }
+
+            // The menu only needs ticks while at least 1 of its components needs them. The
+            // unsubscribing is done lazily in on_tick.
+            if entry.buddy.get_subscriptions().tick {
+                own_buddy.subscribe_tick();
+            }
+
+            // The same holds for char type events
+            if entry.buddy.get_subscriptions().char_type {
+                // The result doesn't matter because the component already got the result from
+                // its own buddy
+                let _ = own_buddy.subscribe_char_type();
+            }
+
+            // And for composition events
+            if entry.buddy.get_subscriptions().composition {
+                own_buddy.subscribe_composition();
+            }
+
+            // And for theme change events
+            if entry.buddy.get_subscriptions().theme_change {
+                own_buddy.subscribe_theme_change();
+            }
+
+            // And for gamepad events
+            if entry.buddy.get_subscriptions().gamepad {
+                own_buddy.subscribe_gamepad();
+            }
+
+            // And for low memory events
+            if entry.buddy.get_subscriptions().low_memory {
+                own_buddy.subscribe_low_memory();
+            }
+
+            // And for window state events
+            if entry.buddy.get_subscriptions().window_state {
+                own_buddy.subscribe_window_state();
+            }
+
+            // And for close requests
+            if entry.buddy.get_subscriptions().close_request {
+                own_buddy.subscribe_close_request();
+            }
+
+            for (mouse, captured) in entry.buddy.get_capture_changes().to_vec() {
+                if captured {
+                    // Only 1 component can capture a mouse at the same time. Note that the entry
+                    // itself is already borrowed, so try_borrow_mut will skip it.
+                    for other_cell in &self.components {
+                        if let Ok(mut other_entry) = other_cell.try_borrow_mut() {
+                            other_entry.buddy.lose_mouse_capture(mouse);
+                        }
+                    }
+                    own_buddy.capture_mouse(mouse);
+                } else {
+                    own_buddy.release_mouse_capture(mouse);
+                }
+            }
+
+            // Pointer locks are propagated the same way
+            for (mouse, locked) in entry.buddy.get_lock_changes().to_vec() {
+                if locked {
+                    for other_cell in &self.components {
+                        if let Ok(mut other_entry) = other_cell.try_borrow_mut() {
+                            other_entry.buddy.lose_pointer_lock(mouse);
+                        }
+                    }
+                    own_buddy.request_pointer_lock(mouse);
+                } else {
+                    own_buddy.release_pointer_lock(mouse);
+                }
+            }
+
+            if entry.buddy.did_change_caret_rectangle() {
+                let domain = entry.domain;
+                let caret_rectangle = entry.buddy.get_caret_rectangle();
+                own_buddy.set_caret_rectangle(caret_rectangle.map(|rectangle| {
+                    rectangle.transform_back(|point| domain.transform_back(point))
+                }));
             }
 
             entry.buddy.clear_changes();
         }
     }
 
+    fn fire_composition_event(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        fire: impl Fn(&mut ComponentEntry),
+    ) {
+        self.update_internal(own_buddy, false);
+
+        // Only the focused component should receive the event, but the others still count for the
+        // subscription of this menu
+        let mut needs_composition = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.get_subscriptions().composition {
+                if entry.visible && self.focused_component.get() == Some(entry.handle) {
+                    fire(&mut entry);
+                    self.check_buddy(own_buddy, &mut entry, false);
+                }
+
+                // The component might have unsubscribed during the event
+                needs_composition |= entry.buddy.get_subscriptions().composition;
+            }
+        }
+
+        if !needs_composition {
+            own_buddy.unsubscribe_composition();
+        }
+    }
+
+    fn fire_gamepad_event(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        fire: impl Fn(&mut ComponentEntry),
+    ) {
+        self.update_internal(own_buddy, false);
+
+        // Only the focused component should receive the event, but the others still count for the
+        // subscription of this menu
+        let mut needs_gamepad = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.get_subscriptions().gamepad {
+                if entry.visible && self.focused_component.get() == Some(entry.handle) {
+                    fire(&mut entry);
+                    self.check_buddy(own_buddy, &mut entry, false);
+                }
+
+                // The component might have unsubscribed during the event
+                needs_gamepad |= entry.buddy.get_subscriptions().gamepad;
+            }
+        }
+
+        if !needs_gamepad {
+            own_buddy.unsubscribe_gamepad();
+        }
+    }
+
+    fn fire_window_state_event(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        fire: impl Fn(&mut ComponentEntry),
+    ) {
+        self.update_internal(own_buddy, false);
+
+        let mut needs_window_state = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.get_subscriptions().window_state {
+                // Hidden components should also know the state of the window, since they might
+                // be shown again while the window is still minimized or unfocused
+                fire(&mut entry);
+                self.check_buddy(own_buddy, &mut entry, false);
+
+                // The component might have unsubscribed during the event
+                needs_window_state |= entry.buddy.get_subscriptions().window_state;
+            }
+        }
+
+        if !needs_window_state {
+            own_buddy.unsubscribe_window_state();
+        }
+    }
+
     fn get_component_at(&self, point: Point) -> Option<RR<ComponentEntry>> {
+        self.get_component_index_at(point)
+            .map(|index| Rc::clone(&self.components[index]))
+    }
+
+    /// Finds the topmost component whose domain contains *point*. Components that filter mouse
+    /// actions are skipped when *point* is outside their drawn region, unless no component below
+    /// them contains *point*.
+    fn get_component_index_at(&self, point: Point) -> Option<usize> {
         // TODO PERFORMANCE Use some kind of 2d range tree instead
-        for entry_cell in &self.components {
+        let mut topmost_index = None;
+        for (index, entry_cell) in self.components.iter().enumerate().rev() {
             let entry = entry_cell.borrow();
-            if entry.domain.is_inside(point) {
-                return Some(Rc::clone(&entry_cell));
+            if entry.visible && entry.domain.is_inside(point) {
+                if !entry.is_filtering(entry.domain.transform(point)) {
+                    return Some(index);
+                }
+                if topmost_index.is_none() {
+                    topmost_index = Some(index);
+                }
             }
         }
 
-        None
+        topmost_index
+    }
+
+    /// Records all components that didn't get the chance to handle a mouse event at *point* in
+    /// the hit test trace (if it is enabled)
+    fn trace_missed_components(
+        &self,
+        event_name: &'static str,
+        point: Point,
+        hit_index: Option<usize>,
+    ) {
+        if !is_hit_test_trace_enabled() {
+            return;
+        }
+
+        for (index, entry_cell) in self.components.iter().enumerate() {
+            if hit_index != Some(index) {
+                let entry = entry_cell.borrow();
+                let outcome = if !entry.visible {
+                    HitTestOutcome::Hidden
+                } else if !entry.domain.is_inside(point) {
+                    HitTestOutcome::OutsideDomain
+                } else if matches!(hit_index, Some(hit_index) if index < hit_index) {
+                    HitTestOutcome::Occluded
+                } else {
+                    // The component is above the hit component, so it must have been skipped
+                    HitTestOutcome::OutsideDrawnRegion
+                };
+                trace_hit_test(event_name, Some(index), outcome);
+            }
+        }
     }
 }
 
@@ -127,6 +984,13 @@ impl Component for SimpleFlatMenu {
         buddy.subscribe_mouse_move();
         buddy.subscribe_mouse_enter();
         buddy.subscribe_mouse_leave();
+        buddy.subscribe_mouse_scroll();
+        buddy.subscribe_mouse_double_click();
+        buddy.subscribe_mouse_long_press();
+        buddy.subscribe_mouse_context_click();
+        buddy.subscribe_mouse_middle_click();
+        buddy.subscribe_mouse_hover();
+        buddy.subscribe_gestures();
     }
 
     // Variables only used when the golem_rendering feature is enabled are
@@ -141,6 +1005,9 @@ impl Component for SimpleFlatMenu {
         // This needs to happen before each event
         self.update_internal(buddy, true);
 
+        // When a component was removed, the area it occupied needs to be repainted
+        let force = force || std::mem::take(&mut self.needs_full_render);
+
         // Now onto the 'actual' drawing
         if force || !self.has_rendered_before {
             if let Some(background_color) = self.background_color {
@@ -149,9 +1016,39 @@ impl Component for SimpleFlatMenu {
             }
         }
         let mut drawn_regions: Vec<Box<dyn DrawnRegion>> = Vec::new();
-        for entry_cell in &self.components {
-            let mut entry = entry_cell.borrow_mut();
+
+        let start_time = self
+            .render_budget
+            .as_ref()
+            .map(|budget| budget.get_current_time());
+        let mut deferred_any = false;
+
+        // The components must be rendered from bottom to top, even when some are deferred
+        for index in 0..self.components.len() {
+            let mut entry = self.components[index].borrow_mut();
             let component_domain = entry.domain;
+            if !entry.visible {
+                continue;
+            }
+
+            let can_defer = !force
+                && entry.buddy.did_request_render()
+                && !entry.needs_forced_render
+                && !entry.render_deferred
+                && self.get_render_priority(entry.handle) == RenderPriority::Normal;
+            if can_defer {
+                if let (Some(budget), Some(start_time)) = (&self.render_budget, start_time) {
+                    // Don't panic when the clock goes backwards
+                    let elapsed_time = budget.get_current_time().saturating_sub(start_time);
+                    if elapsed_time > budget.get_max_time() {
+                        // Keep the render request of the component for the next frame
+                        entry.render_deferred = true;
+                        deferred_any = true;
+                        continue;
+                    }
+                }
+            }
+            entry.render_deferred = false;
 
             if let Some(entry_result) = entry.render(renderer, force) {
                 match entry_result {
@@ -165,6 +1062,28 @@ impl Component for SimpleFlatMenu {
                             drawn_regions.push(Box::new(transformed_region));
                         }
                         self.check_buddy(buddy, &mut entry, false);
+
+                        if self.clip_to_drawn_regions {
+                            if let Some(failed_result) = self.repaint_outside_drawn_region(
+                                renderer,
+                                buddy,
+                                index,
+                                component_domain,
+                                good_entry_result.drawn_region.as_ref(),
+                            ) {
+                                return failed_result;
+                            }
+                        }
+
+                        // The components above this one might have been painted over
+                        if !force {
+                            for other_cell in &self.components[index + 1..] {
+                                let mut other = other_cell.borrow_mut();
+                                if other.domain.overlaps(component_domain) {
+                                    other.needs_forced_render = true;
+                                }
+                            }
+                        }
                     }
                     Err(bad_result) => {
                         return Err(bad_result);
@@ -173,6 +1092,10 @@ impl Component for SimpleFlatMenu {
             }
         }
 
+        if deferred_any {
+            buddy.request_render();
+        }
+
         if (force || !self.has_rendered_before) && self.background_color.is_some() {
             self.has_rendered_before = true;
             entire_render_result()
@@ -190,20 +1113,25 @@ impl Component for SimpleFlatMenu {
         self.update_internal(own_buddy, false);
 
         // Lets now handle the actual click event
-        let maybe_clicked_cell = self.get_component_at(event.get_point());
+        let maybe_clicked_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MouseClick", event.get_point(), maybe_clicked_index);
 
-        if let Some(clicked_cell) = &maybe_clicked_cell {
-            let mut clicked_entry = clicked_cell.borrow_mut();
-            clicked_entry.mouse_click(event);
+        // The clicked component gets the focus (even when it's not subscribed for clicks)
+        let maybe_clicked_handle =
+            maybe_clicked_index.map(|index| self.components[index].borrow().handle);
+        self.focused_component.set(maybe_clicked_handle);
+
+        if let Some(clicked_index) = maybe_clicked_index {
+            let mut clicked_entry = self.components[clicked_index].borrow_mut();
+            with_hit_test_child(clicked_index, || clicked_entry.mouse_click(event));
             self.check_buddy(own_buddy, &mut clicked_entry, false);
         }
 
-        // TODO PERFORMANCE Maintain a list for just the interested components
         let out_event = MouseClickOutEvent::new(event.get_mouse(), event.get_button());
-        for component_cell in &self.components {
-            if maybe_clicked_cell.is_none()
-                || !Rc::ptr_eq(component_cell, maybe_clicked_cell.as_ref().unwrap())
-            {
+        let maybe_clicked_entry = maybe_clicked_index.map(|index| &self.components[index]);
+        for component_cell in &self.click_out_components {
+            let is_clicked = |clicked: &RR<ComponentEntry>| Rc::ptr_eq(clicked, component_cell);
+            if !maybe_clicked_entry.map_or(false, is_clicked) {
                 let mut component_entry = component_cell.borrow_mut();
                 component_entry.mouse_click_out(out_event);
                 self.check_buddy(own_buddy, &mut component_entry, false);
@@ -218,8 +1146,7 @@ impl Component for SimpleFlatMenu {
     ) {
         self.update_internal(own_buddy, false);
 
-        // TODO PERFORMANCE Maintain a list for just the interested components
-        for component_cell in &self.components {
+        for component_cell in &self.click_out_components {
             let mut component_entry = component_cell.borrow_mut();
             component_entry.mouse_click_out(event);
             self.check_buddy(own_buddy, &mut component_entry, false);
@@ -231,11 +1158,12 @@ impl Component for SimpleFlatMenu {
         self.update_internal(own_buddy, false);
 
         // Lets now handle the actual press event
-        let maybe_clicked_cell = self.get_component_at(event.get_point());
+        let maybe_clicked_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MousePress", event.get_point(), maybe_clicked_index);
 
-        if let Some(clicked_cell) = &maybe_clicked_cell {
-            let mut clicked_entry = clicked_cell.borrow_mut();
-            clicked_entry.mouse_press(event);
+        if let Some(clicked_index) = maybe_clicked_index {
+            let mut clicked_entry = self.components[clicked_index].borrow_mut();
+            with_hit_test_child(clicked_index, || clicked_entry.mouse_press(event));
             self.check_buddy(own_buddy, &mut clicked_entry, false);
         }
     }
@@ -244,12 +1172,25 @@ impl Component for SimpleFlatMenu {
         // This should be done before every important action
         self.update_internal(own_buddy, false);
 
-        // Lets now handle the actual press event
-        let maybe_clicked_cell = self.get_component_at(event.get_point());
+        // If a component captured the mouse, it should get the event, regardless of its position
+        let capturing_index = self.components.iter().position(|entry_cell| {
+            let entry = entry_cell.borrow();
+            entry.visible && entry.buddy.is_capturing_mouse(event.get_mouse())
+        });
+
+        // Lets now handle the actual release event
+        let maybe_clicked_index = match capturing_index {
+            Some(index) => Some(index),
+            None => {
+                let hit_index = self.get_component_index_at(event.get_point());
+                self.trace_missed_components("MouseRelease", event.get_point(), hit_index);
+                hit_index
+            }
+        };
 
-        if let Some(clicked_cell) = &maybe_clicked_cell {
-            let mut clicked_entry = clicked_cell.borrow_mut();
-            clicked_entry.mouse_release(event);
+        if let Some(clicked_index) = maybe_clicked_index {
+            let mut clicked_entry = self.components[clicked_index].borrow_mut();
+            with_hit_test_child(clicked_index, || clicked_entry.mouse_release(event));
             self.check_buddy(own_buddy, &mut clicked_entry, false);
         }
     }
@@ -257,11 +1198,17 @@ impl Component for SimpleFlatMenu {
     fn on_mouse_move(&mut self, event: MouseMoveEvent, own_buddy: &mut dyn ComponentBuddy) {
         self.update_internal(own_buddy, false);
 
+        // Only the topmost component at the end of the line should be hovered, so the other
+        // components that contain the end of the line are occluded
+        let hit_entry = self.get_component_at(event.get_to());
+
         // TODO PERFORMANCE Consider only the components intersecting the rectangle around the line from
         // event.from to event.to (using some kind of 2d range tree)
-        for entry_cell in &self.components {
+        for entry_cell in &self.mouse_move_components {
             let mut entry = entry_cell.borrow_mut();
-            entry.mouse_move(event);
+            let occluded =
+                matches!(&hit_entry, Some(hit_entry) if !Rc::ptr_eq(hit_entry, entry_cell));
+            entry.mouse_move(event, self.hover_hysteresis, occluded);
             self.check_buddy(own_buddy, &mut entry, false);
         }
     }
@@ -279,868 +1226,3483 @@ impl Component for SimpleFlatMenu {
     fn on_mouse_leave(&mut self, event: MouseLeaveEvent, own_buddy: &mut dyn ComponentBuddy) {
         self.update_internal(own_buddy, false);
 
-        if let Some(hit_component_entry) = self.get_component_at(event.get_exit_point()) {
-            let mut borrowed_entry = hit_component_entry.borrow_mut();
-            borrowed_entry.mouse_leave(event);
-            self.check_buddy(own_buddy, &mut borrowed_entry, false);
+        // The mouse should leave the component(s) it is hovering
+        for entry_cell in &self.mouse_move_components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.hovering_mouses.contains(&event.get_mouse()) {
+                entry.mouse_leave(event);
+                self.check_buddy(own_buddy, &mut entry, false);
+            }
         }
     }
 
-    fn on_detach(&mut self) {
-        self.components.clear();
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        let maybe_scrolled_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MouseScroll", event.get_point(), maybe_scrolled_index);
+
+        if let Some(scrolled_index) = maybe_scrolled_index {
+            let mut scrolled_entry = self.components[scrolled_index].borrow_mut();
+            with_hit_test_child(scrolled_index, || scrolled_entry.mouse_scroll(event));
+            self.check_buddy(own_buddy, &mut scrolled_entry, false);
+        }
     }
-}
 
-struct ComponentToAdd {
-    component: Box<dyn Component>,
-    domain: ComponentDomain,
-}
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.update_internal(own_buddy, false);
 
-struct ComponentEntry {
-    component: Box<dyn Component>,
-    domain: ComponentDomain,
-    buddy: SimpleFlatBuddy,
-}
+        let maybe_clicked_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MouseDoubleClick", event.get_point(), maybe_clicked_index);
 
-impl ComponentEntry {
-    fn attach(&mut self) {
-        self.component.on_attach(&mut self.buddy);
+        if let Some(clicked_index) = maybe_clicked_index {
+            let mut clicked_entry = self.components[clicked_index].borrow_mut();
+            with_hit_test_child(clicked_index, || clicked_entry.mouse_double_click(event));
+            self.check_buddy(own_buddy, &mut clicked_entry, false);
+        }
     }
 
-    fn mouse_click(&mut self, outer_event: MouseClickEvent) {
-        let mut filtered = false;
-        if self.buddy.get_subscriptions().mouse_click {
-            let transformed_point = self.domain.transform(outer_event.get_point());
-            if let Some(render_result) = self.buddy.get_last_render_result() {
-                if !render_result.filter_mouse_actions
-                    || render_result.drawn_region.is_inside(transformed_point)
-                {
-                    let transformed_event = MouseClickEvent::new(
-                        outer_event.get_mouse(),
-                        transformed_point,
-                        outer_event.get_button(),
-                    );
+    fn on_mouse_long_press(
+        &mut self,
+        event: MouseLongPressEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.update_internal(own_buddy, false);
 
-                    self.component
-                        .on_mouse_click(transformed_event, &mut self.buddy);
-                } else {
-                    filtered = true;
-                }
-            }
-        }
+        let maybe_pressed_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MouseLongPress", event.get_point(), maybe_pressed_index);
 
-        if filtered && self.buddy.get_subscriptions().mouse_click_out {
-            self.component.on_mouse_click_out(
-                MouseClickOutEvent::new(outer_event.get_mouse(), outer_event.get_button()),
-                &mut self.buddy,
-            );
+        if let Some(pressed_index) = maybe_pressed_index {
+            let mut pressed_entry = self.components[pressed_index].borrow_mut();
+            with_hit_test_child(pressed_index, || pressed_entry.mouse_long_press(event));
+            self.check_buddy(own_buddy, &mut pressed_entry, false);
         }
     }
 
-    fn mouse_click_out(&mut self, event: MouseClickOutEvent) {
-        if self.buddy.get_subscriptions().mouse_click_out {
-            if self.buddy.get_last_render_result().is_some() {
-                self.component.on_mouse_click_out(event, &mut self.buddy);
-            }
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.update_internal(own_buddy, false);
+
+        let maybe_clicked_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MouseContextClick", event.get_point(), maybe_clicked_index);
+
+        if let Some(clicked_index) = maybe_clicked_index {
+            let mut clicked_entry = self.components[clicked_index].borrow_mut();
+            with_hit_test_child(clicked_index, || clicked_entry.mouse_context_click(event));
+            self.check_buddy(own_buddy, &mut clicked_entry, false);
         }
     }
 
-    fn mouse_press(&mut self, outer_event: MousePressEvent) {
-        if self.buddy.get_subscriptions().mouse_press {
-            let transformed_point = self.domain.transform(outer_event.get_point());
-            if let Some(render_result) = self.buddy.get_last_render_result() {
-                if !render_result.filter_mouse_actions
-                    || render_result.drawn_region.is_inside(transformed_point)
-                {
-                    let transformed_event = MousePressEvent::new(
-                        outer_event.get_mouse(),
-                        transformed_point,
-                        outer_event.get_button(),
-                    );
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.update_internal(own_buddy, false);
 
-                    self.component
-                        .on_mouse_press(transformed_event, &mut self.buddy);
-                }
-            }
+        let maybe_clicked_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MouseMiddleClick", event.get_point(), maybe_clicked_index);
+
+        if let Some(clicked_index) = maybe_clicked_index {
+            let mut clicked_entry = self.components[clicked_index].borrow_mut();
+            with_hit_test_child(clicked_index, || clicked_entry.mouse_middle_click(event));
+            self.check_buddy(own_buddy, &mut clicked_entry, false);
         }
     }
 
-    fn mouse_release(&mut self, outer_event: MouseReleaseEvent) {
-        if self.buddy.get_subscriptions().mouse_release {
-            let transformed_point = self.domain.transform(outer_event.get_point());
-            if let Some(render_result) = self.buddy.get_last_render_result() {
-                if !render_result.filter_mouse_actions
-                    || render_result.drawn_region.is_inside(transformed_point)
-                {
-                    let transformed_event = MouseReleaseEvent::new(
-                        outer_event.get_mouse(),
-                        transformed_point,
-                        outer_event.get_button(),
-                    );
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
 
-                    self.component
-                        .on_mouse_release(transformed_event, &mut self.buddy);
-                }
-            }
+        let maybe_hovered_index = self.get_component_index_at(event.get_point());
+        self.trace_missed_components("MouseHover", event.get_point(), maybe_hovered_index);
+
+        if let Some(hovered_index) = maybe_hovered_index {
+            let mut hovered_entry = self.components[hovered_index].borrow_mut();
+            with_hit_test_child(hovered_index, || hovered_entry.mouse_hover(event));
+            self.check_buddy(own_buddy, &mut hovered_entry, false);
         }
     }
 
-    fn mouse_enter(&mut self, event: MouseEnterEvent) {
-        if self.buddy.get_subscriptions().mouse_enter {
-            if let Some(render_result) = self.buddy.get_last_render_result() {
-                let transformed_entrance_point = self.domain.transform(event.get_entrance_point());
-                if !render_result.filter_mouse_actions
-                    || render_result
-                        .drawn_region
-                        .is_inside(transformed_entrance_point)
-                {
-                    let transformed_event =
-                        MouseEnterEvent::new(event.get_mouse(), transformed_entrance_point);
-                    self.component
-                        .on_mouse_enter(transformed_event, &mut self.buddy);
-                }
-            }
+    fn on_pinch(&mut self, event: PinchEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        let maybe_pinched_index = self.get_component_index_at(event.get_center());
+        self.trace_missed_components("Pinch", event.get_center(), maybe_pinched_index);
+
+        if let Some(pinched_index) = maybe_pinched_index {
+            let mut pinched_entry = self.components[pinched_index].borrow_mut();
+            with_hit_test_child(pinched_index, || pinched_entry.pinch(event));
+            self.check_buddy(own_buddy, &mut pinched_entry, false);
         }
     }
 
-    fn mouse_leave(&mut self, event: MouseLeaveEvent) {
-        if self.buddy.get_subscriptions().mouse_leave {
-            if let Some(render_result) = self.buddy.get_last_render_result() {
-                let transformed_exit_point = self.domain.transform(event.get_exit_point());
-                if !render_result.filter_mouse_actions
-                    || render_result.drawn_region.is_inside(transformed_exit_point)
-                {
-                    let transformed_event =
-                        MouseLeaveEvent::new(event.get_mouse(), transformed_exit_point);
-                    self.component
-                        .on_mouse_leave(transformed_event, &mut self.buddy);
-                }
-            }
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        let maybe_rotated_index = self.get_component_index_at(event.get_center());
+        self.trace_missed_components("RotateGesture", event.get_center(), maybe_rotated_index);
+
+        if let Some(rotated_index) = maybe_rotated_index {
+            let mut rotated_entry = self.components[rotated_index].borrow_mut();
+            with_hit_test_child(rotated_index, || rotated_entry.rotate_gesture(event));
+            self.check_buddy(own_buddy, &mut rotated_entry, false);
         }
     }
 
-    fn mouse_move(&mut self, event: MouseMoveEvent) {
-        let sub_enter = self.buddy.get_subscriptions().mouse_enter;
-        let sub_move = self.buddy.get_subscriptions().mouse_move;
-        let sub_leave = self.buddy.get_subscriptions().mouse_leave;
-        if sub_enter || sub_move || sub_leave {
-            if let Some(render_result) = self.buddy.get_last_render_result() {
-                let transformed_from = self.domain.transform(event.get_from());
-                let transformed_to = self.domain.transform(event.get_to());
-                let backup_region = RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0);
-                let reference_region = match render_result.filter_mouse_actions {
-                    true => render_result.drawn_region.as_ref(),
-                    false => &backup_region,
-                };
-                let intersection =
-                    reference_region.find_line_intersection(transformed_from, transformed_to);
-                match intersection {
-                    LineIntersection::FullyOutside => {
-                        // I don't need to do anything
-                    }
-                    LineIntersection::FullyInside => {
-                        // Just pass a MouseMoveEvent
-                        if sub_move {
-                            let move_event = MouseMoveEvent::new(
-                                event.get_mouse(),
-                                transformed_from,
-                                transformed_to,
-                            );
-                            self.component.on_mouse_move(move_event, &mut self.buddy);
-                        }
-                    }
-                    LineIntersection::Enters { point } => {
-                        // Pass a MouseEnterEvent and a MouseMoveEvent
-                        if sub_enter {
-                            let enter_event = MouseEnterEvent::new(event.get_mouse(), point);
-                            self.component.on_mouse_enter(enter_event, &mut self.buddy);
-                        }
+    fn on_swipe(&mut self, event: SwipeEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
 
-                        // Note: the component might have subscribed during its on_mouse_enter
-                        if self.buddy.get_subscriptions().mouse_move {
-                            let move_event =
-                                MouseMoveEvent::new(event.get_mouse(), point, transformed_to);
-                            self.component.on_mouse_move(move_event, &mut self.buddy);
-                        }
-                    }
-                    LineIntersection::Exits { point } => {
-                        // Pass a MouseMoveEvent and a MouseLeaveEvent
-                        if sub_move {
-                            let move_event =
-                                MouseMoveEvent::new(event.get_mouse(), transformed_from, point);
-                            self.component.on_mouse_move(move_event, &mut self.buddy);
-                        }
+        // The swipe belongs to the component where it started
+        let maybe_swiped_index = self.get_component_index_at(event.get_start_point());
+        self.trace_missed_components("Swipe", event.get_start_point(), maybe_swiped_index);
 
-                        // Note: the component might have subscribed during its on_mouse_move
-                        if self.buddy.get_subscriptions().mouse_leave {
-                            let leave_event = MouseLeaveEvent::new(event.get_mouse(), point);
-                            self.component.on_mouse_leave(leave_event, &mut self.buddy);
-                        }
-                    }
-                    LineIntersection::Crosses { entrance, exit } => {
-                        // Pass a MouseEnterEvent, MouseMoveEvent, and MouseLeaveEvent
-                        if sub_enter {
-                            let enter_event = MouseEnterEvent::new(event.get_mouse(), entrance);
-                            self.component.on_mouse_enter(enter_event, &mut self.buddy);
-                        }
+        if let Some(swiped_index) = maybe_swiped_index {
+            let mut swiped_entry = self.components[swiped_index].borrow_mut();
+            with_hit_test_child(swiped_index, || swiped_entry.swipe(event));
+            self.check_buddy(own_buddy, &mut swiped_entry, false);
+        }
+    }
 
-                        // Note: the component might have subscribed during its on_mouse_enter
-                        if self.buddy.get_subscriptions().mouse_move {
-                            let move_event = MouseMoveEvent::new(event.get_mouse(), entrance, exit);
-                            self.component.on_mouse_move(move_event, &mut self.buddy);
-                        }
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.update_internal(own_buddy, false);
 
-                        if self.buddy.get_subscriptions().mouse_leave {
-                            let leave_event = MouseLeaveEvent::new(event.get_mouse(), exit);
-                            self.component.on_mouse_leave(leave_event, &mut self.buddy);
-                        }
-                    }
-                };
+        // Only the component that locked the pointer should receive the event
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.is_locking_pointer(event.get_mouse()) {
+                entry.relative_mouse_move(event);
+                self.check_buddy(own_buddy, &mut entry, false);
+                break;
             }
         }
     }
 
-    fn render(&mut self, renderer: &Renderer, force: bool) -> Option<RenderResult> {
-        if force || self.buddy.did_request_render() {
-            self.buddy.clear_render_request();
-
-            let maybe_render_result = renderer.push_viewport(
-                self.domain.get_min_x(),
-                self.domain.get_min_y(),
-                self.domain.get_max_x(),
-                self.domain.get_max_y(),
-                || self.component.render(renderer, &mut self.buddy, force),
-            );
+    fn on_char_type(&mut self, event: &CharTypeEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
 
-            if let Some(render_result) = maybe_render_result {
-                if render_result.is_err() {
-                    return Some(render_result);
+        // Only the focused component should receive the event, but the others still count for the
+        // subscription of this menu
+        let mut needs_char_type = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.get_subscriptions().char_type {
+                if entry.visible && self.focused_component.get() == Some(entry.handle) {
+                    entry.char_type(event);
+                    self.check_buddy(own_buddy, &mut entry, false);
                 }
 
-                let good_result = render_result.unwrap();
-                self.buddy.set_last_render_result(good_result.clone());
-                Some(Ok(good_result))
-            } else {
-                None
+                // The component might have unsubscribed during its on_char_type
+                needs_char_type |= entry.buddy.get_subscriptions().char_type;
             }
-        } else {
-            None
+        }
+
+        if !needs_char_type {
+            own_buddy.unsubscribe_char_type();
         }
     }
-}
 
-impl Drop for ComponentEntry {
-    fn drop(&mut self) {
-        self.component.on_detach();
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.fire_composition_event(own_buddy, |entry| entry.composition_start(event));
     }
-}
 
-#[cfg(test)]
-mod tests {
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.fire_composition_event(own_buddy, |entry| entry.composition_update(event));
+    }
 
-    use crate::*;
+    fn on_composition_end(
+        &mut self,
+        event: &CompositionEndEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.fire_composition_event(own_buddy, |entry| entry.composition_end(event));
+    }
 
-    use std::cell::*;
-    use std::rc::Rc;
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, own_buddy: &mut dyn ComponentBuddy) {
+        // Note that update_internal will also share the new theme with the buddies
+        self.update_internal(own_buddy, false);
 
-    fn root_buddy() -> RootComponentBuddy {
-        let mut buddy = RootComponentBuddy::new();
-        init(&mut buddy);
-        buddy
+        let mut needs_theme_changes = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.get_subscriptions().theme_change {
+                // Hidden components should also receive this event, since they will need the new
+                // theme when they are shown again
+                entry.theme_change(event);
+                self.check_buddy(own_buddy, &mut entry, false);
+
+                // The component might have unsubscribed during its on_theme_change
+                needs_theme_changes |= entry.buddy.get_subscriptions().theme_change;
+            }
+        }
+
+        if !needs_theme_changes {
+            own_buddy.unsubscribe_theme_change();
+        }
     }
 
-    fn init(buddy: &mut RootComponentBuddy) {
-        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.fire_gamepad_event(own_buddy, |entry| entry.gamepad_button(event));
     }
 
-    #[test]
-    fn test_attach_and_detach() {
-        struct CountingComponent {
-            counter: Rc<Cell<u8>>,
-        }
-
-        impl Component for CountingComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {
-                self.counter.set(self.counter.get() + 1);
-            }
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.fire_gamepad_event(own_buddy, |entry| entry.gamepad_axis(event));
+    }
 
-            fn render(
-                &mut self,
-                _renderer: &Renderer,
-                _buddy: &mut dyn ComponentBuddy,
-                _force: bool,
-            ) -> RenderResult {
-                entire_render_result()
-            }
+    fn on_low_memory(&mut self, event: LowMemoryEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
 
-            fn on_detach(&mut self) {
-                self.counter.set(self.counter.get() + 1);
+        let mut needs_low_memory = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.get_subscriptions().low_memory {
+                // Hidden components should also receive this event, since they can often release
+                // more memory than the visible components
+                entry.low_memory(event);
+                self.check_buddy(own_buddy, &mut entry, false);
+
+                // The component might have unsubscribed during its on_low_memory
+                needs_low_memory |= entry.buddy.get_subscriptions().low_memory;
             }
         }
 
-        let counter1 = Rc::new(Cell::new(0));
-        let counter2 = Rc::new(Cell::new(0));
-
-        let mut menu = SimpleFlatMenu::new(None);
-        menu.add_component(
-            Box::new(CountingComponent {
-                counter: Rc::clone(&counter1),
-            }),
-            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
-        );
-
-        let mut buddy = root_buddy();
-        menu.on_attach(&mut buddy);
-
-        // The first component should have been attached
-        assert_eq!(1, counter1.get());
-        assert_eq!(0, counter2.get());
+        if !needs_low_memory {
+            own_buddy.unsubscribe_low_memory();
+        }
+    }
 
-        menu.add_component(
-            Box::new(CountingComponent {
-                counter: Rc::clone(&counter2),
-            }),
-            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
-        );
+    fn on_window_focus(&mut self, event: WindowFocusEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.fire_window_state_event(own_buddy, |entry| entry.window_focus(event));
+    }
 
-        // It should attach the second component as soon as possible
-        menu.render(
-            &test_renderer(RenderRegion::between(0, 0, 10, 10)),
-            &mut buddy,
-            false,
-        )
-        .unwrap();
-        assert_eq!(1, counter1.get());
-        assert_eq!(1, counter2.get());
+    fn on_window_minimize(
+        &mut self,
+        event: WindowMinimizeEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.fire_window_state_event(own_buddy, |entry| entry.window_minimize(event));
+    }
 
-        // But they should be attached only once
-        menu.render(
-            &test_renderer(RenderRegion::between(0, 0, 10, 10)),
-            &mut buddy,
-            false,
-        )
-        .unwrap();
-        assert_eq!(1, counter1.get());
-        assert_eq!(1, counter2.get());
+    fn on_close_request(&mut self, own_buddy: &mut dyn ComponentBuddy) -> bool {
+        self.update_internal(own_buddy, false);
 
-        // When the menu is detached, so should their components be
-        menu.on_detach();
-        assert_eq!(2, counter1.get());
-        assert_eq!(2, counter2.get());
+        let mut may_close = true;
+        let mut needs_close_requests = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.get_subscriptions().close_request {
+                // Hidden components can have unsaved work as well. All subscribed components are
+                // asked, even when an earlier component already vetoed the request.
+                may_close &= entry.close_request();
+                self.check_buddy(own_buddy, &mut entry, false);
+
+                // The component might have unsubscribed during its on_close_request
+                needs_close_requests |= entry.buddy.get_subscriptions().close_request;
+            }
+        }
 
-        // And no 'second' detach when the menu is dropped
-        drop(menu);
+        if !needs_close_requests {
+            own_buddy.unsubscribe_close_request();
+        }
+        may_close
     }
 
-    #[test]
-    fn test_basic_mouse_clicking() {
-        struct FullComponent {
-            click_counter: Rc<Cell<u8>>,
-        }
+    fn on_tick(&mut self, event: TickEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
 
-        impl Component for FullComponent {
-            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-                buddy.subscribe_mouse_click();
-            }
+        let mut needs_ticks = false;
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if let Some(mut animation) = entry.domain_animation.take() {
+                animation.advance(event.get_delta_time());
+                entry.resize(animation.get_value());
+                self.check_buddy(own_buddy, &mut entry, false);
 
-            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
-                self.click_counter.set(self.click_counter.get() + 1);
+                // The old region needs to be repainted, so just redraw everything
+                self.needs_full_render = true;
+                own_buddy.request_render();
+
+                if !animation.is_finished() {
+                    entry.domain_animation = Some(animation);
+                    needs_ticks = true;
+                }
             }
+            if entry.buddy.get_subscriptions().tick {
+                // Hidden components don't get ticks, but they will need them once they are shown
+                if entry.visible {
+                    entry.tick(event);
+                    self.check_buddy(own_buddy, &mut entry, false);
+                }
 
-            fn render(
-                &mut self,
-                _renderer: &Renderer,
-                _buddy: &mut dyn ComponentBuddy,
-                _force: bool,
-            ) -> RenderResult {
-                entire_render_result()
+                // The component might have unsubscribed during its on_tick
+                needs_ticks |= entry.buddy.get_subscriptions().tick;
             }
         }
 
-        struct HalfComponent {
-            click_counter: Rc<Cell<u8>>,
+        if !needs_ticks {
+            own_buddy.unsubscribe_tick();
         }
+    }
 
-        impl Component for HalfComponent {
-            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-                buddy.subscribe_mouse_click();
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        let entry_cell = self.get_component_at(point)?;
+        let entry = entry_cell.borrow();
+        let transformed_point = entry.domain.transform(point);
+        entry.component.get_accessible_text_at(transformed_point).or_else(|| {
+            let own_text = entry.buddy.get_accessible_text();
+            match own_text.is_empty() {
+                true => None,
+                false => Some(own_text.clone()),
             }
+        })
+    }
 
-            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
-                self.click_counter.set(self.click_counter.get() + 1);
-            }
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        let entry_cell = self.get_component_at(point)?;
+        let entry = entry_cell.borrow();
+        let transformed_point = entry.domain.transform(point);
+        entry
+            .component
+            .get_cursor_at(transformed_point)
+            .or_else(|| entry.buddy.get_cursor())
+    }
 
-            fn render(
-                &mut self,
-                _renderer: &Renderer,
-                _buddy: &mut dyn ComponentBuddy,
-                _force: bool,
-            ) -> RenderResult {
-                Ok(RenderResultStruct {
-                    filter_mouse_actions: true,
-                    drawn_region: Box::new(RectangularDrawnRegion::new(0.25, 0.0, 0.75, 1.0)),
-                })
-            }
-        }
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        let entry_cell = self.get_component_at(point)?;
+        let entry = entry_cell.borrow();
+        let transformed_point = entry.domain.transform(point);
+        entry
+            .component
+            .get_window_frame_area_at(transformed_point)
+            .or_else(|| entry.buddy.get_window_frame_area())
+    }
 
-        let full_counter = Rc::new(Cell::new(0));
-        let half_counter = Rc::new(Cell::new(0));
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.components
+            .iter()
+            .map(|entry_cell| {
+                let entry = entry_cell.borrow();
+                ComponentDescription::of(entry.component.as_ref())
+                    .with_domain(entry.domain)
+                    .with_subscriptions(entry.buddy.get_subscriptions())
+                    .with_render_result(entry.buddy.get_last_render_result())
+            })
+            .collect()
+    }
 
-        let mut menu = SimpleFlatMenu::new(None);
-        menu.add_component(
-            Box::new(FullComponent {
-                click_counter: Rc::clone(&full_counter),
-            }),
-            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
-        );
-        menu.add_component(
-            Box::new(HalfComponent {
-                click_counter: Rc::clone(&half_counter),
-            }),
-            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
-        );
+    fn on_detach(&mut self) {
+        self.mouse_move_components.clear();
+        self.click_out_components.clear();
+        self.components.clear();
+    }
+}
 
-        let mut application = Application::new(Box::new(menu));
+enum PendingChange {
+    Add(ComponentToAdd),
+    Remove(ComponentHandle),
+    Replace(ComponentHandle, Box<dyn Component>),
+    Move(ComponentHandle, ComponentDomain),
+    AnimatedMove(ComponentHandle, ComponentDomain, Duration, Easing),
+    Raise(ComponentHandle),
+    Lower(ComponentHandle),
+    SetVisible(ComponentHandle, bool),
+    Clear,
+}
 
-        fn click_event(x: f32, y: f32) -> MouseClickEvent {
-            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
-        }
+struct ComponentToAdd {
+    handle: ComponentHandle,
+    component: Box<dyn Component>,
+    domain: ComponentDomain,
+    seat_policy: SeatPolicy,
+}
 
-        // Before the initial render, clicking shouldn't fire any events
-        application.fire_mouse_click_event(click_event(0.2, 0.2));
-        application.fire_mouse_click_event(click_event(0.7, 0.7));
-        assert_eq!(0, full_counter.get());
-        assert_eq!(0, half_counter.get());
+struct ComponentEntry {
+    handle: ComponentHandle,
+    component: Box<dyn Component>,
+    domain: ComponentDomain,
+    seat_policy: SeatPolicy,
+    buddy: SimpleFlatBuddy,
+    // This is set when a component below this one has been redrawn, and overlaps this one
+    needs_forced_render: bool,
+    // The mouses that entered this component, but didn't leave it yet
+    hovering_mouses: Vec<Mouse>,
+    in_mouse_move_list: bool,
+    in_click_out_list: bool,
+    visible: bool,
+    // The running animation of the domain, see move_component_animated
+    domain_animation: Option<Tween<ComponentDomain>>,
+    // This is set when the render request of the component was deferred because the render
+    // budget was exceeded. Such components won't be deferred again during the next frame.
+    render_deferred: bool,
+}
 
-        // After at least 1 render call, clicking should have effect
-        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
-        application.fire_mouse_click_event(click_event(0.2, 0.2));
-        application.fire_mouse_click_event(click_event(0.7, 0.7));
-        assert_eq!(1, full_counter.get());
-        assert_eq!(1, half_counter.get());
+impl ComponentEntry {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        handle: ComponentHandle,
+        component: Box<dyn Component>,
+        domain: ComponentDomain,
+        seat_policy: SeatPolicy,
+        mouse_buddy: Rc<RefCell<MouseBuddy>>,
+        theme: RR<Rc<Theme>>,
+        frame_timing: Rc<Cell<FrameTiming>>,
+        gamepad_store: RR<GamepadStore>,
+    ) -> Self {
+        Self {
+            handle,
+            component,
+            domain,
+            seat_policy,
+            buddy: SimpleFlatBuddy::new(
+                domain,
+                seat_policy,
+                mouse_buddy,
+                theme,
+                frame_timing,
+                gamepad_store,
+            ),
+            needs_forced_render: false,
+            hovering_mouses: Vec::new(),
+            in_mouse_move_list: false,
+            in_click_out_list: false,
+            visible: true,
+            domain_animation: None,
+            render_deferred: false,
+        }
+    }
 
-        // When clicking near the edge, only the full component should
-        // receive the event
-        application.fire_mouse_click_event(click_event(0.05, 0.05));
-        application.fire_mouse_click_event(click_event(0.55, 0.55));
-        assert_eq!(2, full_counter.get());
-        assert_eq!(1, half_counter.get());
+    fn wants_mouse_moves(&self) -> bool {
+        let subscriptions = self.buddy.get_subscriptions();
+        subscriptions.mouse_move || subscriptions.mouse_enter || subscriptions.mouse_leave
+    }
 
-        // When miss-clicking entirely, neither should receive the event
-        application.fire_mouse_click_event(click_event(0.1, 0.8));
-        assert_eq!(2, full_counter.get());
-        assert_eq!(1, half_counter.get());
+    fn attach(&mut self) {
+        self.component.on_attach(&mut self.buddy);
     }
 
-    #[test]
-    fn test_rendering_components() {
-        struct BusyRenderComponent {
-            counter: Rc<Cell<u8>>,
+    /// Checks whether this component would reject mouse events at the given (transformed) point
+    /// because the point is outside its drawn region
+    fn is_filtering(&self, transformed_point: Point) -> bool {
+        match self.buddy.get_last_render_result() {
+            Some(render_result) => {
+                render_result.filter_mouse_actions
+                    && !render_result.drawn_region.is_inside(transformed_point)
+            }
+            None => false,
         }
+    }
 
-        impl Component for BusyRenderComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+    fn resize(&mut self, new_domain: ComponentDomain) {
+        self.domain = new_domain;
+        self.buddy.set_domain(new_domain);
+        self.buddy.request_render();
+        self.component.on_resize(&mut self.buddy);
+    }
 
-            fn render(
-                &mut self,
-                _renderer: &Renderer,
-                buddy: &mut dyn ComponentBuddy,
-                _force: bool,
-            ) -> RenderResult {
-                buddy.request_render();
-                self.counter.set(self.counter.get() + 1);
-                entire_render_result()
+    /// Determines whether the component should receive a mouse event at the given (transformed)
+    /// point, and records the outcome in the hit test trace (if it is enabled)
+    fn check_hit(
+        &self,
+        event_name: &'static str,
+        mouse: Mouse,
+        subscribed: bool,
+        transformed_point: Point,
+    ) -> HitTestOutcome {
+        let outcome = if !self.seat_policy.accepts(mouse) {
+            HitTestOutcome::RejectedBySeatPolicy
+        } else if !subscribed {
+            HitTestOutcome::NotSubscribed
+        } else {
+            match self.buddy.get_last_render_result() {
+                Some(render_result) => {
+                    if !render_result.filter_mouse_actions
+                        || render_result.drawn_region.is_inside(transformed_point)
+                    {
+                        HitTestOutcome::Delivered
+                    } else {
+                        HitTestOutcome::OutsideDrawnRegion
+                    }
+                }
+                None => HitTestOutcome::NotRenderedYet,
             }
-        }
+        };
 
-        struct ClickRenderComponent {
-            counter: Rc<Cell<u8>>,
-        }
+        trace_hit_test(event_name, None, outcome);
+        outcome
+    }
 
-        impl Component for ClickRenderComponent {
-            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-                buddy.subscribe_mouse_click();
-            }
+    fn mouse_click(&mut self, outer_event: MouseClickEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_click;
+        let outcome = self.check_hit(
+            "MouseClick",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
+        );
 
-            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
-                buddy.request_render();
-            }
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event = MouseClickEvent::new(
+                outer_event.get_mouse(),
+                transformed_point,
+                outer_event.get_button(),
+            );
 
-            fn render(
-                &mut self,
-                _renderer: &Renderer,
-                _buddy: &mut dyn ComponentBuddy,
-                _force: bool,
-            ) -> RenderResult {
-                self.counter.set(self.counter.get() + 1);
-                entire_render_result()
-            }
+            self.component
+                .on_mouse_click(transformed_event, &mut self.buddy);
         }
 
-        let mut menu = SimpleFlatMenu::new(None);
-        let mut buddy = root_buddy();
-        let render_region = RenderRegion::between(10, 20, 30, 40);
+        let filtered = outcome == HitTestOutcome::OutsideDrawnRegion;
+        if filtered && self.buddy.get_subscriptions().mouse_click_out {
+            self.component.on_mouse_click_out(
+                MouseClickOutEvent::new(outer_event.get_mouse(), outer_event.get_button()),
+                &mut self.buddy,
+            );
+        }
+    }
 
-        let click_counter = Rc::new(Cell::new(0));
-        let busy_counter = Rc::new(Cell::new(0));
+    fn mouse_click_out(&mut self, event: MouseClickOutEvent) {
+        if !self.seat_policy.accepts(event.get_mouse()) {
+            return;
+        }
 
-        menu.add_component(
-            Box::new(ClickRenderComponent {
-                counter: Rc::clone(&click_counter),
-            }),
-            ComponentDomain::between(0.1, 0.1, 0.3, 0.3),
+        if self.buddy.get_subscriptions().mouse_click_out {
+            if self.buddy.get_last_render_result().is_some() {
+                self.component.on_mouse_click_out(event, &mut self.buddy);
+            }
+        }
+    }
+
+    fn mouse_press(&mut self, outer_event: MousePressEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_press;
+        let outcome = self.check_hit(
+            "MousePress",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
         );
 
-        // All components should be rendered during their first render call
-        assert!(buddy.did_request_render());
-        buddy.clear_render_request();
-        menu.render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        assert_eq!(1, click_counter.get());
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event = MousePressEvent::with_details(
+                outer_event.get_mouse(),
+                transformed_point,
+                outer_event.get_button(),
+                outer_event.get_details(),
+            );
 
-        // But the menu shouldn't request another one until we click the component
-        assert!(!buddy.did_request_render());
+            self.component
+                .on_mouse_press(transformed_event, &mut self.buddy);
+        }
+    }
 
-        // So let's click it and render again
-        let hit_click =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary());
-        menu.on_mouse_click(hit_click, &mut buddy);
-        assert!(buddy.did_request_render());
-        buddy.clear_render_request();
-        menu.render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        assert!(!buddy.did_request_render());
-        assert_eq!(2, click_counter.get());
+    fn mouse_release(&mut self, outer_event: MouseReleaseEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_release;
+        let captured = self.buddy.is_capturing_mouse(outer_event.get_mouse());
+        let outcome = if captured {
+            // The component should receive the event, regardless of its position
+            let outcome = match subscribed {
+                true => HitTestOutcome::Captured,
+                false => HitTestOutcome::NotSubscribed,
+            };
+            trace_hit_test("MouseRelease", None, outcome);
+            outcome
+        } else {
+            self.check_hit(
+                "MouseRelease",
+                outer_event.get_mouse(),
+                subscribed,
+                transformed_point,
+            )
+        };
 
-        // Miss clicking shouldn't change anything
-        let miss_click = MouseClickEvent::new(
-            Mouse::new(0),
-            Point::new(0.35, 0.35),
-            MouseButton::primary(),
-        );
-        menu.on_mouse_click(miss_click, &mut buddy);
-        assert!(!buddy.did_request_render());
+        if outcome == HitTestOutcome::Delivered || outcome == HitTestOutcome::Captured {
+            let transformed_event = MouseReleaseEvent::with_details(
+                outer_event.get_mouse(),
+                transformed_point,
+                outer_event.get_button(),
+                outer_event.get_details(),
+            );
 
-        // Force rendering should cause the render method to be called
-        // And thus increment the click counter
-        buddy.clear_render_request();
-        menu.render(&test_renderer(render_region), &mut buddy, true)
-            .unwrap();
-        assert!(!buddy.did_request_render());
-        assert_eq!(3, click_counter.get());
+            self.component
+                .on_mouse_release(transformed_event, &mut self.buddy);
+        }
 
-        // Add the busy rendering component
-        menu.add_component(
-            Box::new(BusyRenderComponent {
-                counter: Rc::clone(&busy_counter),
-            }),
-            ComponentDomain::between(0.5, 0.7, 0.9, 0.8),
+        // The release event ends the capture (if any)
+        self.buddy.release_mouse_capture(outer_event.get_mouse());
+    }
+
+    fn mouse_scroll(&mut self, outer_event: MouseScrollEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_scroll;
+        let outcome = self.check_hit(
+            "MouseScroll",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
         );
 
-        // Only the busy component should render
-        buddy.clear_render_request();
-        menu.render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        // And it should have requested a redraw right away
-        assert!(buddy.did_request_render());
-        assert_eq!(3, click_counter.get());
-        assert_eq!(1, busy_counter.get());
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event = MouseScrollEvent::new(
+                outer_event.get_mouse(),
+                transformed_point,
+                outer_event.get_delta_x(),
+                outer_event.get_delta_y(),
+            );
 
-        // Again, only the busy component should render
-        buddy.clear_render_request();
-        menu.render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        // And it should have requested a redraw again
-        assert!(buddy.did_request_render());
-        assert_eq!(3, click_counter.get());
-        assert_eq!(2, busy_counter.get());
+            self.component
+                .on_mouse_scroll(transformed_event, &mut self.buddy);
+        }
+    }
 
-        // When we force a render, both components should be redrawn
-        buddy.clear_render_request();
-        menu.render(&test_renderer(render_region), &mut buddy, true)
-            .unwrap();
-        // Like usual, it should request a next render
-        assert!(buddy.did_request_render());
-        assert_eq!(4, click_counter.get());
-        assert_eq!(3, busy_counter.get());
+    fn mouse_double_click(&mut self, outer_event: MouseDoubleClickEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_double_click;
+        let outcome = self.check_hit(
+            "MouseDoubleClick",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
+        );
 
-        // But when we render without force again, only the busy one should be drawn
-        buddy.clear_render_request();
-        menu.render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        assert!(buddy.did_request_render());
-        assert_eq!(4, click_counter.get());
-        assert_eq!(4, busy_counter.get());
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event = MouseDoubleClickEvent::new(
+                outer_event.get_mouse(),
+                transformed_point,
+                outer_event.get_button(),
+            );
 
-        // Unless we click it...
-        menu.on_mouse_click(hit_click, &mut buddy);
-        menu.render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        assert!(buddy.did_request_render());
-        assert_eq!(5, click_counter.get());
-        assert_eq!(5, busy_counter.get());
+            self.component
+                .on_mouse_double_click(transformed_event, &mut self.buddy);
+        }
     }
 
-    struct ClickComponent {
-        render_result: RenderResult,
-    }
+    fn mouse_long_press(&mut self, outer_event: MouseLongPressEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_long_press;
+        let outcome = self.check_hit(
+            "MouseLongPress",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
+        );
 
-    impl Component for ClickComponent {
-        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-            buddy.subscribe_mouse_click();
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event = MouseLongPressEvent::new(
+                outer_event.get_mouse(),
+                transformed_point,
+                outer_event.get_button(),
+            );
+
+            self.component
+                .on_mouse_long_press(transformed_event, &mut self.buddy);
         }
+    }
 
-        fn render(
-            &mut self,
-            _renderer: &Renderer,
-            _buddy: &mut dyn ComponentBuddy,
-            _force: bool,
-        ) -> RenderResult {
-            self.render_result.clone()
+    fn mouse_context_click(&mut self, outer_event: MouseContextClickEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_context_click;
+        let outcome = self.check_hit(
+            "MouseContextClick",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
+        );
+
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event = MouseContextClickEvent::new(
+                outer_event.get_mouse(),
+                transformed_point,
+                outer_event.get_source(),
+            );
+
+            self.component
+                .on_mouse_context_click(transformed_event, &mut self.buddy);
         }
+    }
 
-        fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
-            buddy.request_render();
+    fn mouse_middle_click(&mut self, outer_event: MouseMiddleClickEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_middle_click;
+        let outcome = self.check_hit(
+            "MouseMiddleClick",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
+        );
+
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event =
+                MouseMiddleClickEvent::new(outer_event.get_mouse(), transformed_point);
+
+            self.component
+                .on_mouse_middle_click(transformed_event, &mut self.buddy);
         }
     }
 
-    #[test]
-    fn test_render_results_with_background() {
-        test_render_results(Some(Color::rgb(0, 200, 100)), true);
+    fn relative_mouse_move(&mut self, outer_event: RelativeMouseMoveEvent) {
+        // The deltas are relative to the size of the component
+        let transformed_event = RelativeMouseMoveEvent::new(
+            outer_event.get_mouse(),
+            outer_event.get_delta_x() / self.domain.get_width(),
+            outer_event.get_delta_y() / self.domain.get_height(),
+        );
+        self.component
+            .on_relative_mouse_move(transformed_event, &mut self.buddy);
     }
 
-    #[test]
-    fn test_render_results_without_background() {
-        test_render_results(None, false);
+    fn mouse_hover(&mut self, outer_event: MouseHoverEvent) {
+        let transformed_point = self.domain.transform(outer_event.get_point());
+        let subscribed = self.buddy.get_subscriptions().mouse_hover;
+        let outcome = self.check_hit(
+            "MouseHover",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_point,
+        );
+
+        if outcome == HitTestOutcome::Delivered {
+            let transformed_event =
+                MouseHoverEvent::new(outer_event.get_mouse(), transformed_point);
+
+            self.component
+                .on_mouse_hover(transformed_event, &mut self.buddy);
+        }
     }
 
-    fn test_render_results(background: Option<Color>, draw_background: bool) {
-        let mut menu = SimpleFlatMenu::new(background);
-        menu.add_component(
-            Box::new(ClickComponent {
-                render_result: entire_render_result(),
-            }),
-            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+    fn pinch(&mut self, outer_event: PinchEvent) {
+        let transformed_center = self.domain.transform(outer_event.get_center());
+        let subscribed = self.buddy.get_subscriptions().gestures;
+        let outcome = self.check_hit(
+            "Pinch",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_center,
         );
-        menu.add_component(
-            Box::new(ClickComponent {
-                render_result: Ok(RenderResultStruct {
-                    drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 0.6, 0.6)),
-                    filter_mouse_actions: false,
-                }),
-            }),
-            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+
+        if outcome == HitTestOutcome::Delivered {
+            // The scale is a ratio, so it doesn't depend on the domain
+            let transformed_event = PinchEvent::new(
+                outer_event.get_mouse(),
+                transformed_center,
+                outer_event.get_scale(),
+                outer_event.get_scale_velocity(),
+            );
+
+            self.component.on_pinch(transformed_event, &mut self.buddy);
+        }
+    }
+
+    fn rotate_gesture(&mut self, outer_event: RotateGestureEvent) {
+        let transformed_center = self.domain.transform(outer_event.get_center());
+        let subscribed = self.buddy.get_subscriptions().gestures;
+        let outcome = self.check_hit(
+            "RotateGesture",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_center,
         );
 
-        let click1 =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary());
-        let click2 =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.6, 0.6), MouseButton::primary());
-        let render_region = RenderRegion::between(0, 0, 100, 40);
-        let mut buddy = root_buddy();
+        if outcome == HitTestOutcome::Delivered {
+            // The angle is measured on the screen, so it doesn't depend on the domain
+            let transformed_event = RotateGestureEvent::new(
+                outer_event.get_mouse(),
+                transformed_center,
+                outer_event.get_angle(),
+                outer_event.get_angular_velocity(),
+            );
 
-        let result = menu
-            .render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        // This menu should never request filtering mouse actions
-        assert!(!result.filter_mouse_actions);
-        // It should have rendered both components, and maybe the area outside
-        {
-            let region = &result.drawn_region;
-            assert_eq!(draw_background, region.is_inside(Point::new(1.0, 0.0)));
-            assert_eq!(draw_background, region.is_inside(Point::new(0.0, 1.0)));
-            assert!(region.is_inside(Point::new(0.0, 0.0)));
-            assert!(region.is_inside(Point::new(0.5, 0.5)));
-            assert!(region.is_inside(Point::new(0.7, 0.7)));
-            // Only draw outside the drawn region of component 2 if there is background
-            assert_eq!(draw_background, region.is_inside(Point::new(0.9, 0.9)));
+            self.component
+                .on_rotate_gesture(transformed_event, &mut self.buddy);
         }
+    }
 
-        menu.on_mouse_click(click1, &mut buddy);
-        let result = menu
-            .render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        // This time, it should only have drawn the first component
-        {
-            let region = &result.drawn_region;
-            assert!(region.is_inside(Point::new(0.0, 0.0)));
-            assert!(region.is_inside(Point::new(0.5, 0.5)));
-            assert!(!region.is_inside(Point::new(0.6, 0.6)));
+    fn swipe(&mut self, outer_event: SwipeEvent) {
+        let transformed_start = self.domain.transform(outer_event.get_start_point());
+        let subscribed = self.buddy.get_subscriptions().gestures;
+        let outcome = self.check_hit(
+            "Swipe",
+            outer_event.get_mouse(),
+            subscribed,
+            transformed_start,
+        );
+
+        if outcome == HitTestOutcome::Delivered {
+            // The velocity is relative to the size of the component
+            let transformed_event = SwipeEvent::new(
+                outer_event.get_mouse(),
+                transformed_start,
+                self.domain.transform(outer_event.get_end_point()),
+                outer_event.get_velocity_x() / self.domain.get_width(),
+                outer_event.get_velocity_y() / self.domain.get_height(),
+            );
+
+            self.component.on_swipe(transformed_event, &mut self.buddy);
         }
+    }
 
-        menu.on_mouse_click(click2, &mut buddy);
-        let result = menu
-            .render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        // This time, it should only have drawn the second component
-        {
-            let region = &result.drawn_region;
-            assert!(!region.is_inside(Point::new(0.2, 0.2)));
-            assert!(region.is_inside(Point::new(0.5, 0.5)));
-            assert!(region.is_inside(Point::new(0.7, 0.7)));
-            // But only inside the region returned by the second component
-            assert!(!region.is_inside(Point::new(0.9, 0.9)));
+    fn char_type(&mut self, event: &CharTypeEvent) {
+        if self.buddy.get_subscriptions().char_type {
+            self.component.on_char_type(event, &mut self.buddy);
         }
+    }
 
-        // When we force, it should draw both components again, and maybe even background
-        let result = menu
-            .render(&test_renderer(render_region), &mut buddy, true)
-            .unwrap();
-        {
-            let region = &result.drawn_region;
-            assert_eq!(draw_background, region.is_inside(Point::new(1.0, 0.0)));
-            assert_eq!(draw_background, region.is_inside(Point::new(0.0, 1.0)));
-            assert!(region.is_inside(Point::new(0.0, 0.0)));
-            assert!(region.is_inside(Point::new(0.5, 0.5)));
-            assert!(region.is_inside(Point::new(0.7, 0.7)));
-            // Only draw outside the drawn region of component 2 if there is background
-            assert_eq!(draw_background, region.is_inside(Point::new(0.9, 0.9)));
+    fn composition_start(&mut self, event: &CompositionStartEvent) {
+        if self.buddy.get_subscriptions().composition {
+            self.component.on_composition_start(event, &mut self.buddy);
         }
+    }
 
-        // And when we do a normal render thereafter, it should only draw component 1
-        menu.on_mouse_click(click1, &mut buddy);
-        let result = menu
-            .render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
-        // This time, it should only have drawn the first component
-        {
-            let region = &result.drawn_region;
-            assert!(region.is_inside(Point::new(0.0, 0.0)));
-            assert!(region.is_inside(Point::new(0.5, 0.5)));
-            assert!(!region.is_inside(Point::new(0.6, 0.6)));
+    fn composition_update(&mut self, event: &CompositionUpdateEvent) {
+        if self.buddy.get_subscriptions().composition {
+            self.component.on_composition_update(event, &mut self.buddy);
         }
     }
 
-    #[test]
-    fn test_click_out() {
-        struct ClickCountComponent {
-            in_counter: Rc<Cell<u8>>,
-            out_counter: Rc<Cell<u8>>,
+    fn composition_end(&mut self, event: &CompositionEndEvent) {
+        if self.buddy.get_subscriptions().composition {
+            self.component.on_composition_end(event, &mut self.buddy);
         }
+    }
 
-        impl Component for ClickCountComponent {
-            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-                buddy.subscribe_mouse_click();
-                buddy.subscribe_mouse_click_out();
-            }
+    fn tick(&mut self, event: TickEvent) {
+        if self.buddy.get_subscriptions().tick {
+            self.component.on_tick(event, &mut self.buddy);
+        }
+    }
 
-            fn render(
-                &mut self,
-                _renderer: &Renderer,
+    fn theme_change(&mut self, event: &ThemeChangeEvent) {
+        if self.buddy.get_subscriptions().theme_change {
+            self.component.on_theme_change(event, &mut self.buddy);
+        }
+    }
+
+    fn gamepad_button(&mut self, event: GamepadButtonEvent) {
+        if self.buddy.get_subscriptions().gamepad {
+            self.component.on_gamepad_button(event, &mut self.buddy);
+        }
+    }
+
+    fn gamepad_axis(&mut self, event: GamepadAxisEvent) {
+        if self.buddy.get_subscriptions().gamepad {
+            self.component.on_gamepad_axis(event, &mut self.buddy);
+        }
+    }
+
+    fn low_memory(&mut self, event: LowMemoryEvent) {
+        if self.buddy.get_subscriptions().low_memory {
+            self.component.on_low_memory(event, &mut self.buddy);
+        }
+    }
+
+    fn window_focus(&mut self, event: WindowFocusEvent) {
+        if self.buddy.get_subscriptions().window_state {
+            self.component.on_window_focus(event, &mut self.buddy);
+        }
+    }
+
+    fn window_minimize(&mut self, event: WindowMinimizeEvent) {
+        if self.buddy.get_subscriptions().window_state {
+            self.component.on_window_minimize(event, &mut self.buddy);
+        }
+    }
+
+    /// Returns false if the component vetoed the close request
+    fn close_request(&mut self) -> bool {
+        if self.buddy.get_subscriptions().close_request {
+            self.component.on_close_request(&mut self.buddy)
+        } else {
+            true
+        }
+    }
+
+    fn mouse_enter(&mut self, event: MouseEnterEvent) {
+        if !self.seat_policy.accepts(event.get_mouse()) {
+            return;
+        }
+
+        if self.buddy.get_subscriptions().mouse_enter {
+            if let Some(render_result) = self.buddy.get_last_render_result() {
+                let transformed_entrance_point = self.domain.transform(event.get_entrance_point());
+                if !render_result.filter_mouse_actions
+                    || render_result
+                        .drawn_region
+                        .is_inside(transformed_entrance_point)
+                {
+                    self.hovering_mouses.push(event.get_mouse());
+                    let transformed_event =
+                        MouseEnterEvent::new(event.get_mouse(), transformed_entrance_point);
+                    self.component
+                        .on_mouse_enter(transformed_event, &mut self.buddy);
+                }
+            }
+        }
+    }
+
+    fn mouse_leave(&mut self, event: MouseLeaveEvent) {
+        if !self.seat_policy.accepts(event.get_mouse()) {
+            return;
+        }
+
+        let was_hovering = self.hovering_mouses.contains(&event.get_mouse());
+        self.hovering_mouses
+            .retain(|hovering| *hovering != event.get_mouse());
+
+        if was_hovering && self.buddy.get_subscriptions().mouse_leave {
+            let transformed_exit_point = self.domain.transform(event.get_exit_point());
+            let transformed_event = MouseLeaveEvent::new(event.get_mouse(), transformed_exit_point);
+            self.component
+                .on_mouse_leave(transformed_event, &mut self.buddy);
+        }
+    }
+
+    fn mouse_move(&mut self, event: MouseMoveEvent, hover_hysteresis: f32, occluded: bool) {
+        if !self.seat_policy.accepts(event.get_mouse()) {
+            return;
+        }
+
+        let sub_enter = self.buddy.get_subscriptions().mouse_enter;
+        let sub_move = self.buddy.get_subscriptions().mouse_move;
+        let sub_leave = self.buddy.get_subscriptions().mouse_leave;
+        if sub_enter || sub_move || sub_leave {
+            if let Some(render_result) = self.buddy.get_last_render_result() {
+                let transformed_from = self.domain.transform(event.get_from());
+                let transformed_to = self.domain.transform(event.get_to());
+
+                // If the component captured the mouse, it should receive the entire movement
+                let captured = self.buddy.is_capturing_mouse(event.get_mouse());
+                let details = event.get_details();
+                let full_move_event = MouseMoveEvent::with_details(
+                    event.get_mouse(),
+                    transformed_from,
+                    transformed_to,
+                    details,
+                );
+
+                let backup_region = RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0);
+                let reference_region = match render_result.filter_mouse_actions {
+                    true => render_result.drawn_region.as_ref(),
+                    false => &backup_region,
+                };
+                let mouse = event.get_mouse();
+                let was_inside = self.hovering_mouses.contains(&mouse);
+                let mut intersection =
+                    reference_region.find_line_intersection(transformed_from, transformed_to);
+
+                if hover_hysteresis > 0.0 {
+                    let end_is_near = reference_region.is_near(
+                        transformed_to,
+                        hover_hysteresis / self.domain.get_width(),
+                        hover_hysteresis / self.domain.get_height(),
+                    );
+                    intersection =
+                        intersection.with_hysteresis(was_inside, end_is_near, transformed_from);
+                }
+                intersection = intersection.with_occlusion(
+                    was_inside,
+                    occluded,
+                    transformed_from,
+                    transformed_to,
+                );
+
+                self.hovering_mouses.retain(|hovering| *hovering != mouse);
+                if intersection.ends_inside() {
+                    self.hovering_mouses.push(mouse);
+                }
+
+                match intersection {
+                    LineIntersection::FullyOutside => {
+                        // I don't need to do anything, unless the component captured the mouse
+                        if captured && sub_move {
+                            self.component.on_mouse_move(full_move_event, &mut self.buddy);
+                        }
+                    }
+                    LineIntersection::FullyInside => {
+                        // Just pass a MouseMoveEvent
+                        if sub_move {
+                            self.component.on_mouse_move(full_move_event, &mut self.buddy);
+                        }
+                    }
+                    LineIntersection::Enters { point } => {
+                        // Pass a MouseEnterEvent and a MouseMoveEvent
+                        if sub_enter {
+                            let enter_event = MouseEnterEvent::new(event.get_mouse(), point);
+                            self.component.on_mouse_enter(enter_event, &mut self.buddy);
+                        }
+
+                        // Note: the component might have subscribed during its on_mouse_enter
+                        if self.buddy.get_subscriptions().mouse_move {
+                            let move_event = match captured {
+                                true => full_move_event,
+                                false => MouseMoveEvent::with_details(
+                                    event.get_mouse(),
+                                    point,
+                                    transformed_to,
+                                    details,
+                                ),
+                            };
+                            self.component.on_mouse_move(move_event, &mut self.buddy);
+                        }
+                    }
+                    LineIntersection::Exits { point } => {
+                        // Pass a MouseMoveEvent and a MouseLeaveEvent
+                        if sub_move {
+                            let move_event = match captured {
+                                true => full_move_event,
+                                false => MouseMoveEvent::with_details(
+                                    event.get_mouse(),
+                                    transformed_from,
+                                    point,
+                                    details,
+                                ),
+                            };
+                            self.component.on_mouse_move(move_event, &mut self.buddy);
+                        }
+
+                        // Note: the component might have subscribed during its on_mouse_move
+                        if self.buddy.get_subscriptions().mouse_leave {
+                            let leave_event = MouseLeaveEvent::new(event.get_mouse(), point);
+                            self.component.on_mouse_leave(leave_event, &mut self.buddy);
+                        }
+                    }
+                    LineIntersection::Crosses { entrance, exit } => {
+                        // Pass a MouseEnterEvent, MouseMoveEvent, and MouseLeaveEvent
+                        if sub_enter {
+                            let enter_event = MouseEnterEvent::new(event.get_mouse(), entrance);
+                            self.component.on_mouse_enter(enter_event, &mut self.buddy);
+                        }
+
+                        // Note: the component might have subscribed during its on_mouse_enter
+                        if self.buddy.get_subscriptions().mouse_move {
+                            let move_event = match captured {
+                                true => full_move_event,
+                                false => MouseMoveEvent::with_details(
+                                    event.get_mouse(),
+                                    entrance,
+                                    exit,
+                                    details,
+                                ),
+                            };
+                            self.component.on_mouse_move(move_event, &mut self.buddy);
+                        }
+
+                        if self.buddy.get_subscriptions().mouse_leave {
+                            let leave_event = MouseLeaveEvent::new(event.get_mouse(), exit);
+                            self.component.on_mouse_leave(leave_event, &mut self.buddy);
+                        }
+                    }
+                };
+            }
+        }
+    }
+
+    fn render(&mut self, renderer: &Renderer, force: bool) -> Option<RenderResult> {
+        let force = force || self.needs_forced_render;
+        if force || self.buddy.did_request_render() {
+            self.buddy.clear_render_request();
+            self.needs_forced_render = false;
+
+            let maybe_render_result = renderer.push_viewport(
+                self.domain.get_min_x(),
+                self.domain.get_min_y(),
+                self.domain.get_max_x(),
+                self.domain.get_max_y(),
+                || self.component.render(renderer, &mut self.buddy, force),
+            );
+
+            if let Some(render_result) = maybe_render_result {
+                if render_result.is_err() {
+                    return Some(render_result);
+                }
+
+                let good_result = render_result.unwrap();
+                self.buddy.set_last_render_result(good_result.clone());
+                Some(Ok(good_result))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for ComponentEntry {
+    fn drop(&mut self) {
+        self.component.on_detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::*;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    fn root_buddy() -> RootComponentBuddy {
+        let mut buddy = RootComponentBuddy::new();
+        init(&mut buddy);
+        buddy
+    }
+
+    fn init(buddy: &mut RootComponentBuddy) {
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+    }
+
+    #[test]
+    fn test_attach_and_detach() {
+        struct CountingComponent {
+            counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for CountingComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {
+                self.counter.set(self.counter.get() + 1);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_detach(&mut self) {
+                self.counter.set(self.counter.get() + 1);
+            }
+        }
+
+        let counter1 = Rc::new(Cell::new(0));
+        let counter2 = Rc::new(Cell::new(0));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(CountingComponent {
+                counter: Rc::clone(&counter1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+
+        let mut buddy = root_buddy();
+        menu.on_attach(&mut buddy);
+
+        // The first component should have been attached
+        assert_eq!(1, counter1.get());
+        assert_eq!(0, counter2.get());
+
+        menu.add_component(
+            Box::new(CountingComponent {
+                counter: Rc::clone(&counter2),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        // It should attach the second component as soon as possible
+        menu.render(
+            &test_renderer(RenderRegion::between(0, 0, 10, 10)),
+            &mut buddy,
+            false,
+        )
+        .unwrap();
+        assert_eq!(1, counter1.get());
+        assert_eq!(1, counter2.get());
+
+        // But they should be attached only once
+        menu.render(
+            &test_renderer(RenderRegion::between(0, 0, 10, 10)),
+            &mut buddy,
+            false,
+        )
+        .unwrap();
+        assert_eq!(1, counter1.get());
+        assert_eq!(1, counter2.get());
+
+        // When the menu is detached, so should their components be
+        menu.on_detach();
+        assert_eq!(2, counter1.get());
+        assert_eq!(2, counter2.get());
+
+        // And no 'second' detach when the menu is dropped
+        drop(menu);
+    }
+
+    #[test]
+    fn test_basic_mouse_clicking() {
+        struct FullComponent {
+            click_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for FullComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.click_counter.set(self.click_counter.get() + 1);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+        }
+
+        struct HalfComponent {
+            click_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for HalfComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.click_counter.set(self.click_counter.get() + 1);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    filter_mouse_actions: true,
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.25, 0.0, 0.75, 1.0)),
+                })
+            }
+        }
+
+        let full_counter = Rc::new(Cell::new(0));
+        let half_counter = Rc::new(Cell::new(0));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(FullComponent {
+                click_counter: Rc::clone(&full_counter),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            Box::new(HalfComponent {
+                click_counter: Rc::clone(&half_counter),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+
+        fn click_event(x: f32, y: f32) -> MouseClickEvent {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        }
+
+        // Before the initial render, clicking shouldn't fire any events
+        application.fire_mouse_click_event(click_event(0.2, 0.2));
+        application.fire_mouse_click_event(click_event(0.7, 0.7));
+        assert_eq!(0, full_counter.get());
+        assert_eq!(0, half_counter.get());
+
+        // After at least 1 render call, clicking should have effect
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+        application.fire_mouse_click_event(click_event(0.2, 0.2));
+        application.fire_mouse_click_event(click_event(0.7, 0.7));
+        assert_eq!(1, full_counter.get());
+        assert_eq!(1, half_counter.get());
+
+        // When clicking near the edge, only the full component should
+        // receive the event
+        application.fire_mouse_click_event(click_event(0.05, 0.05));
+        application.fire_mouse_click_event(click_event(0.55, 0.55));
+        assert_eq!(2, full_counter.get());
+        assert_eq!(1, half_counter.get());
+
+        // When miss-clicking entirely, neither should receive the event
+        application.fire_mouse_click_event(click_event(0.1, 0.8));
+        assert_eq!(2, full_counter.get());
+        assert_eq!(1, half_counter.get());
+    }
+
+    #[test]
+    fn test_rendering_components() {
+        struct BusyRenderComponent {
+            counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for BusyRenderComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                buddy.request_render();
+                self.counter.set(self.counter.get() + 1);
+                entire_render_result()
+            }
+        }
+
+        struct ClickRenderComponent {
+            counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for ClickRenderComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                buddy.request_render();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.counter.set(self.counter.get() + 1);
+                entire_render_result()
+            }
+        }
+
+        let mut menu = SimpleFlatMenu::new(None);
+        let mut buddy = root_buddy();
+        let render_region = RenderRegion::between(10, 20, 30, 40);
+
+        let click_counter = Rc::new(Cell::new(0));
+        let busy_counter = Rc::new(Cell::new(0));
+
+        menu.add_component(
+            Box::new(ClickRenderComponent {
+                counter: Rc::clone(&click_counter),
+            }),
+            ComponentDomain::between(0.1, 0.1, 0.3, 0.3),
+        );
+
+        // All components should be rendered during their first render call
+        assert!(buddy.did_request_render());
+        buddy.clear_render_request();
+        menu.render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        assert_eq!(1, click_counter.get());
+
+        // But the menu shouldn't request another one until we click the component
+        assert!(!buddy.did_request_render());
+
+        // So let's click it and render again
+        let hit_click =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary());
+        menu.on_mouse_click(hit_click, &mut buddy);
+        assert!(buddy.did_request_render());
+        buddy.clear_render_request();
+        menu.render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        assert!(!buddy.did_request_render());
+        assert_eq!(2, click_counter.get());
+
+        // Miss clicking shouldn't change anything
+        let miss_click = MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.35, 0.35),
+            MouseButton::primary(),
+        );
+        menu.on_mouse_click(miss_click, &mut buddy);
+        assert!(!buddy.did_request_render());
+
+        // Force rendering should cause the render method to be called
+        // And thus increment the click counter
+        buddy.clear_render_request();
+        menu.render(&test_renderer(render_region), &mut buddy, true)
+            .unwrap();
+        assert!(!buddy.did_request_render());
+        assert_eq!(3, click_counter.get());
+
+        // Add the busy rendering component
+        menu.add_component(
+            Box::new(BusyRenderComponent {
+                counter: Rc::clone(&busy_counter),
+            }),
+            ComponentDomain::between(0.5, 0.7, 0.9, 0.8),
+        );
+
+        // Only the busy component should render
+        buddy.clear_render_request();
+        menu.render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        // And it should have requested a redraw right away
+        assert!(buddy.did_request_render());
+        assert_eq!(3, click_counter.get());
+        assert_eq!(1, busy_counter.get());
+
+        // Again, only the busy component should render
+        buddy.clear_render_request();
+        menu.render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        // And it should have requested a redraw again
+        assert!(buddy.did_request_render());
+        assert_eq!(3, click_counter.get());
+        assert_eq!(2, busy_counter.get());
+
+        // When we force a render, both components should be redrawn
+        buddy.clear_render_request();
+        menu.render(&test_renderer(render_region), &mut buddy, true)
+            .unwrap();
+        // Like usual, it should request a next render
+        assert!(buddy.did_request_render());
+        assert_eq!(4, click_counter.get());
+        assert_eq!(3, busy_counter.get());
+
+        // But when we render without force again, only the busy one should be drawn
+        buddy.clear_render_request();
+        menu.render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        assert!(buddy.did_request_render());
+        assert_eq!(4, click_counter.get());
+        assert_eq!(4, busy_counter.get());
+
+        // Unless we click it...
+        menu.on_mouse_click(hit_click, &mut buddy);
+        menu.render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        assert!(buddy.did_request_render());
+        assert_eq!(5, click_counter.get());
+        assert_eq!(5, busy_counter.get());
+    }
+
+    struct ClickComponent {
+        render_result: RenderResult,
+    }
+
+    impl Component for ClickComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            self.render_result.clone()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+            buddy.request_render();
+        }
+    }
+
+    #[test]
+    fn test_render_results_with_background() {
+        test_render_results(Some(Color::rgb(0, 200, 100)), true);
+    }
+
+    #[test]
+    fn test_render_results_without_background() {
+        test_render_results(None, false);
+    }
+
+    fn test_render_results(background: Option<Color>, draw_background: bool) {
+        let mut menu = SimpleFlatMenu::new(background);
+        menu.add_component(
+            Box::new(ClickComponent {
+                render_result: entire_render_result(),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            Box::new(ClickComponent {
+                render_result: Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 0.6, 0.6)),
+                    filter_mouse_actions: false,
+                }),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        let click1 =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary());
+        let click2 =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.6, 0.6), MouseButton::primary());
+        let render_region = RenderRegion::between(0, 0, 100, 40);
+        let mut buddy = root_buddy();
+
+        let result = menu
+            .render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        // This menu should never request filtering mouse actions
+        assert!(!result.filter_mouse_actions);
+        // It should have rendered both components, and maybe the area outside
+        {
+            let region = &result.drawn_region;
+            assert_eq!(draw_background, region.is_inside(Point::new(1.0, 0.0)));
+            assert_eq!(draw_background, region.is_inside(Point::new(0.0, 1.0)));
+            assert!(region.is_inside(Point::new(0.0, 0.0)));
+            assert!(region.is_inside(Point::new(0.5, 0.5)));
+            assert!(region.is_inside(Point::new(0.7, 0.7)));
+            // Only draw outside the drawn region of component 2 if there is background
+            assert_eq!(draw_background, region.is_inside(Point::new(0.9, 0.9)));
+        }
+
+        menu.on_mouse_click(click1, &mut buddy);
+        let result = menu
+            .render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        // This time, it should only have drawn the first component
+        {
+            let region = &result.drawn_region;
+            assert!(region.is_inside(Point::new(0.0, 0.0)));
+            assert!(region.is_inside(Point::new(0.5, 0.5)));
+            assert!(!region.is_inside(Point::new(0.6, 0.6)));
+        }
+
+        menu.on_mouse_click(click2, &mut buddy);
+        let result = menu
+            .render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        // This time, it should only have drawn the second component
+        {
+            let region = &result.drawn_region;
+            assert!(!region.is_inside(Point::new(0.2, 0.2)));
+            assert!(region.is_inside(Point::new(0.5, 0.5)));
+            assert!(region.is_inside(Point::new(0.7, 0.7)));
+            // But only inside the region returned by the second component
+            assert!(!region.is_inside(Point::new(0.9, 0.9)));
+        }
+
+        // When we force, it should draw both components again, and maybe even background
+        let result = menu
+            .render(&test_renderer(render_region), &mut buddy, true)
+            .unwrap();
+        {
+            let region = &result.drawn_region;
+            assert_eq!(draw_background, region.is_inside(Point::new(1.0, 0.0)));
+            assert_eq!(draw_background, region.is_inside(Point::new(0.0, 1.0)));
+            assert!(region.is_inside(Point::new(0.0, 0.0)));
+            assert!(region.is_inside(Point::new(0.5, 0.5)));
+            assert!(region.is_inside(Point::new(0.7, 0.7)));
+            // Only draw outside the drawn region of component 2 if there is background
+            assert_eq!(draw_background, region.is_inside(Point::new(0.9, 0.9)));
+        }
+
+        // And when we do a normal render thereafter, it should only draw component 1
+        menu.on_mouse_click(click1, &mut buddy);
+        let result = menu
+            .render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+        // This time, it should only have drawn the first component
+        {
+            let region = &result.drawn_region;
+            assert!(region.is_inside(Point::new(0.0, 0.0)));
+            assert!(region.is_inside(Point::new(0.5, 0.5)));
+            assert!(!region.is_inside(Point::new(0.6, 0.6)));
+        }
+    }
+
+    struct ScissorLogComponent {
+        log: Rc<RefCell<Vec<RenderRegion>>>,
+        drawn_region: RectangularDrawnRegion,
+    }
+
+    impl Component for ScissorLogComponent {
+        fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+        fn render(
+            &mut self,
+            renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            self.log.borrow_mut().push(renderer.get_scissor());
+            Ok(RenderResultStruct {
+                drawn_region: Box::new(self.drawn_region),
+                filter_mouse_actions: false,
+            })
+        }
+    }
+
+    #[test]
+    fn test_clip_to_drawn_regions() {
+        for clip in &[false, true] {
+            let lower_log = Rc::new(RefCell::new(Vec::new()));
+            let upper_log = Rc::new(RefCell::new(Vec::new()));
+
+            let mut menu = SimpleFlatMenu::new(Some(Color::rgb(0, 0, 0)));
+            menu.set_clip_to_drawn_regions(*clip);
+            menu.add_component(
+                Box::new(ScissorLogComponent {
+                    log: Rc::clone(&lower_log),
+                    drawn_region: RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0),
+                }),
+                ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+            );
+            // The upper component only claims to draw on the left half of its domain
+            menu.add_component(
+                Box::new(ScissorLogComponent {
+                    log: Rc::clone(&upper_log),
+                    drawn_region: RectangularDrawnRegion::new(0.0, 0.0, 0.5, 1.0),
+                }),
+                ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+            );
+
+            let renderer = test_renderer(RenderRegion::between(0, 0, 100, 100));
+            let mut buddy = root_buddy();
+            menu.render(&renderer, &mut buddy, false).unwrap();
+
+            let full = RenderRegion::between(0, 0, 100, 100);
+            let upper = RenderRegion::between(50, 0, 100, 100);
+            assert_eq!(vec![upper], *upper_log.borrow());
+
+            // In clip mode, the lower component should repaint the right half of the upper one
+            let mut expected_lower_log = vec![full];
+            if *clip {
+                expected_lower_log.push(RenderRegion::between(75, 0, 100, 100));
+            }
+            assert_eq!(expected_lower_log, *lower_log.borrow());
+        }
+    }
+
+    #[test]
+    fn test_clip_to_unaligned_drawn_region() {
+        let lower_log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(Some(Color::rgb(0, 0, 0)));
+        menu.set_clip_to_drawn_regions(true);
+        menu.add_component(
+            Box::new(ScissorLogComponent {
+                log: Rc::clone(&lower_log),
+                drawn_region: RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        // The drawn region of the upper component ends in the middle of pixel 75
+        menu.add_component(
+            Box::new(ScissorLogComponent {
+                log: Rc::new(RefCell::new(Vec::new())),
+                drawn_region: RectangularDrawnRegion::new(0.0, 0.2, 0.51, 0.8),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let renderer = test_renderer(RenderRegion::between(0, 0, 100, 100));
+        let mut buddy = root_buddy();
+        menu.render(&renderer, &mut buddy, false).unwrap();
+
+        // The strips must not touch pixel 75, but they must cover everything else
+        assert_eq!(
+            vec![
+                RenderRegion::between(0, 0, 100, 100),
+                RenderRegion::between(76, 0, 100, 100),
+                RenderRegion::between(50, 0, 76, 20),
+                RenderRegion::between(50, 80, 76, 100),
+            ],
+            *lower_log.borrow()
+        );
+    }
+
+    #[test]
+    fn test_click_out() {
+        struct ClickCountComponent {
+            in_counter: Rc<Cell<u8>>,
+            out_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for ClickCountComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                buddy.subscribe_mouse_click_out();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 0.5, 0.5)),
+                    filter_mouse_actions: true,
+                })
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.in_counter.set(self.in_counter.get() + 1);
+            }
+
+            fn on_mouse_click_out(
+                &mut self,
+                _event: MouseClickOutEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.out_counter.set(self.out_counter.get() + 1);
+            }
+        }
+
+        let mut buddy = root_buddy();
+        let mut menu = SimpleFlatMenu::new(None);
+        let in1 = Rc::new(Cell::new(0));
+        let in2 = Rc::new(Cell::new(0));
+        let out1 = Rc::new(Cell::new(0));
+        let out2 = Rc::new(Cell::new(0));
+
+        menu.add_component(
+            Box::new(ClickCountComponent {
+                in_counter: Rc::clone(&in1),
+                out_counter: Rc::clone(&out1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            Box::new(ClickCountComponent {
+                in_counter: Rc::clone(&in2),
+                out_counter: Rc::clone(&out2),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        let click_miss =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.8, 0.2), MouseButton::primary());
+        let click_out = MouseClickOutEvent::new(Mouse::new(0), MouseButton::primary());
+        let click1 =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary());
+        let click2 =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.6, 0.6), MouseButton::primary());
+        let click1out =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.3, 0.3), MouseButton::primary());
+        let click2out =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.9, 0.9), MouseButton::primary());
+
+        let check_counters = |value_in1: u8, value_out1: u8, value_in2: u8, value_out2: u8| {
+            assert_eq!(in1.get(), value_in1);
+            assert_eq!(out1.get(), value_out1);
+            assert_eq!(in2.get(), value_in2);
+            assert_eq!(out2.get(), value_out2);
+        };
+
+        // Clicking before rendering shouldn't have any effect
+        menu.on_mouse_click(click1, &mut buddy);
+        check_counters(0, 0, 0, 0);
+
+        // So let's render
+        menu.render(
+            &test_renderer(RenderRegion::between(0, 0, 120, 10)),
+            &mut buddy,
+            false,
+        )
+        .unwrap();
+
+        // Clicking outside the menu should increment both out counters
+        menu.on_mouse_click_out(click_out, &mut buddy);
+        check_counters(0, 1, 0, 1);
+
+        // Clicking outside the components should also increment both out counters
+        menu.on_mouse_click(click_miss, &mut buddy);
+        check_counters(0, 2, 0, 2);
+
+        // Clicking inside the render region of component 1...
+        menu.on_mouse_click(click1, &mut buddy);
+        check_counters(1, 2, 0, 3);
+
+        // Clicking inside the render region of component 2...
+        menu.on_mouse_click(click2, &mut buddy);
+        check_counters(1, 3, 1, 3);
+
+        // Clicking outside the render region of component 1
+        // should increment both out counters
+        menu.on_mouse_click(click1out, &mut buddy);
+        check_counters(1, 4, 1, 4);
+
+        // Same when clicking outside the render region of component 2
+        menu.on_mouse_click(click2out, &mut buddy);
+        check_counters(1, 5, 1, 5);
+    }
+
+    #[test]
+    fn test_subscriptions() {
+        struct SubscribingComponent {
+            should_subscribe: Rc<Cell<bool>>,
+            should_unsubscribe: Rc<Cell<bool>>,
+            click_counter: Rc<Cell<u8>>,
+            click_out_counter: Rc<Cell<u8>>,
+            press_counter: Rc<Cell<u8>>,
+            release_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for SubscribingComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                if self.should_subscribe.get() {
+                    buddy.subscribe_mouse_click();
+                    buddy.subscribe_mouse_click_out();
+                    buddy.subscribe_mouse_press();
+                    buddy.subscribe_mouse_release();
+                }
+                if self.should_unsubscribe.get() {
+                    buddy.unsubscribe_mouse_click();
+                    buddy.unsubscribe_mouse_click_out();
+                    buddy.unsubscribe_mouse_press();
+                    buddy.unsubscribe_mouse_release();
+                }
+                self.should_subscribe.set(false);
+                self.should_unsubscribe.set(false);
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.click_counter.set(self.click_counter.get() + 1);
+            }
+
+            fn on_mouse_click_out(
+                &mut self,
+                _event: MouseClickOutEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.click_out_counter.set(self.click_out_counter.get() + 1);
+            }
+
+            fn on_mouse_press(&mut self, _event: MousePressEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.press_counter.set(self.press_counter.get() + 1);
+            }
+
+            fn on_mouse_release(
+                &mut self,
+                _event: MouseReleaseEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.release_counter.set(self.release_counter.get() + 1);
+            }
+        }
+
+        let subscribe1 = Rc::new(Cell::new(false));
+        let subscribe2 = Rc::new(Cell::new(false));
+        let unsubscribe1 = Rc::new(Cell::new(false));
+        let unsubscribe2 = Rc::new(Cell::new(false));
+
+        let click_count1 = Rc::new(Cell::new(0));
+        let click_count2 = Rc::new(Cell::new(0));
+        let click_out_count1 = Rc::new(Cell::new(0));
+        let click_out_count2 = Rc::new(Cell::new(0));
+        let press_count1 = Rc::new(Cell::new(0));
+        let press_count2 = Rc::new(Cell::new(0));
+        let release_count1 = Rc::new(Cell::new(0));
+        let release_count2 = Rc::new(Cell::new(0));
+
+        let buddy = root_buddy();
+        let region = RenderRegion::between(0, 10, 20, 30);
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(SubscribingComponent {
+                should_subscribe: Rc::clone(&subscribe1),
+                should_unsubscribe: Rc::clone(&unsubscribe1),
+                click_counter: Rc::clone(&click_count1),
+                click_out_counter: Rc::clone(&click_out_count1),
+                press_counter: Rc::clone(&press_count1),
+                release_counter: Rc::clone(&release_count1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            Box::new(SubscribingComponent {
+                should_subscribe: Rc::clone(&subscribe2),
+                should_unsubscribe: Rc::clone(&unsubscribe2),
+                click_counter: Rc::clone(&click_count2),
+                click_out_counter: Rc::clone(&click_out_count2),
+                press_counter: Rc::clone(&press_count2),
+                release_counter: Rc::clone(&release_count2),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        let menu_cell = Rc::new(RefCell::new(menu));
+        let buddy_cell = Rc::new(RefCell::new(buddy));
+        let do_render = || {
+            let mut menu = menu_cell.borrow_mut();
+            let mut buddy = buddy_cell.borrow_mut();
+            menu.render(&test_renderer(region), &mut *buddy, true)
+                .unwrap();
+        };
+
+        let fire_click = || {
+            let mut menu = menu_cell.borrow_mut();
+            let mut buddy = buddy_cell.borrow_mut();
+            menu.on_mouse_click(
+                MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary()),
+                &mut *buddy,
+            );
+            menu.on_mouse_click(
+                MouseClickEvent::new(Mouse::new(0), Point::new(0.8, 0.8), MouseButton::primary()),
+                &mut *buddy,
+            );
+        };
+        let fire_click_out = || {
+            let mut menu = menu_cell.borrow_mut();
+            let mut buddy = buddy_cell.borrow_mut();
+            menu.on_mouse_click_out(
+                MouseClickOutEvent::new(Mouse::new(0), MouseButton::primary()),
+                &mut *buddy,
+            );
+        };
+        let fire_press = || {
+            let mut menu = menu_cell.borrow_mut();
+            let mut buddy = buddy_cell.borrow_mut();
+            menu.on_mouse_press(
+                MousePressEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary()),
+                &mut *buddy,
+            );
+            menu.on_mouse_press(
+                MousePressEvent::new(Mouse::new(0), Point::new(0.8, 0.8), MouseButton::primary()),
+                &mut *buddy,
+            );
+        };
+        let fire_release = || {
+            let mut menu = menu_cell.borrow_mut();
+            let mut buddy = buddy_cell.borrow_mut();
+            menu.on_mouse_release(
+                MouseReleaseEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary()),
+                &mut *buddy,
+            );
+            menu.on_mouse_release(
+                MouseReleaseEvent::new(Mouse::new(0), Point::new(0.8, 0.8), MouseButton::primary()),
+                &mut *buddy,
+            );
+        };
+
+        let check_values = |click1: u8,
+                            click_out1: u8,
+                            press1: u8,
+                            release1: u8,
+                            click2: u8,
+                            click_out2: u8,
+                            press2: u8,
+                            release2: u8| {
+            assert_eq!(click1, click_count1.get());
+            assert_eq!(click_out1, click_out_count1.get());
+            assert_eq!(press1, press_count1.get());
+            assert_eq!(release1, release_count1.get());
+            assert_eq!(click2, click_count2.get());
+            assert_eq!(click_out2, click_out_count2.get());
+            assert_eq!(press2, press_count2.get());
+            assert_eq!(release2, release_count2.get());
+        };
+
+        // No subscriptions yet, so these events should be ignored
+        do_render();
+        fire_click();
+        fire_click_out();
+        check_values(0, 0, 0, 0, 0, 0, 0, 0);
+
+        // Lets subscribe the first component
+        subscribe1.set(true);
+        do_render();
+        fire_click();
+        check_values(1, 1, 0, 0, 0, 0, 0, 0);
+
+        // Now the second one as well
+        subscribe2.set(true);
+        do_render();
+        fire_click_out();
+        fire_press();
+        check_values(1, 2, 1, 0, 0, 1, 1, 0);
+
+        // Nah, let's cancel the subscription for the second one
+        unsubscribe2.set(true);
+        do_render();
+        fire_click();
+        fire_release();
+        check_values(2, 3, 1, 1, 0, 1, 1, 0);
+
+        // This is not fair... lets cancel the first one as well
+        unsubscribe1.set(true);
+        do_render();
+        fire_click_out();
+        fire_release();
+        fire_press();
+        check_values(2, 3, 1, 1, 0, 1, 1, 0);
+
+        // Lets give the second one a comeback
+        subscribe2.set(true);
+        do_render();
+        fire_click();
+        fire_click_out();
+        fire_press();
+        fire_release();
+        check_values(2, 3, 1, 1, 1, 3, 2, 1);
+
+        // Let's stop
+        unsubscribe2.set(true);
+        do_render();
+        fire_click();
+        fire_click_out();
+        check_values(2, 3, 1, 1, 1, 3, 2, 1);
+    }
+
+    struct MouseMotionComponent {
+        should_filter_mouse_actions: Rc<Cell<bool>>,
+        mouse_move_log: Rc<RefCell<Vec<MouseMoveEvent>>>,
+        mouse_enter_log: Rc<RefCell<Vec<MouseEnterEvent>>>,
+        mouse_leave_log: Rc<RefCell<Vec<MouseLeaveEvent>>>,
+    }
+
+    impl Component for MouseMotionComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_move();
+            buddy.subscribe_mouse_enter();
+            buddy.subscribe_mouse_leave();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            Ok(RenderResultStruct {
+                filter_mouse_actions: self.should_filter_mouse_actions.get(),
+                drawn_region: Box::new(RectangularDrawnRegion::new(0.2, 0.2, 0.8, 0.8)),
+            })
+        }
+
+        fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+            let mut move_log = self.mouse_move_log.borrow_mut();
+            move_log.push(event);
+        }
+
+        fn on_mouse_enter(&mut self, event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+            let mut enter_log = self.mouse_enter_log.borrow_mut();
+            enter_log.push(event);
+        }
+
+        fn on_mouse_leave(&mut self, event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+            let mut leave_log = self.mouse_leave_log.borrow_mut();
+            leave_log.push(event);
+        }
+    }
+
+    #[test]
+    fn test_mouse_enter_and_leave() {
+        let enter_log1 = Rc::new(RefCell::new(Vec::new()));
+        let leave_log1 = Rc::new(RefCell::new(Vec::new()));
+        let enter_log2 = Rc::new(RefCell::new(Vec::new()));
+        let leave_log2 = Rc::new(RefCell::new(Vec::new()));
+
+        let component1 = MouseMotionComponent {
+            should_filter_mouse_actions: Rc::new(Cell::new(true)),
+            mouse_move_log: Rc::new(RefCell::new(Vec::new())),
+            mouse_enter_log: Rc::clone(&enter_log1),
+            mouse_leave_log: Rc::clone(&leave_log1),
+        };
+        let component2 = MouseMotionComponent {
+            should_filter_mouse_actions: Rc::new(Cell::new(true)),
+            mouse_move_log: Rc::new(RefCell::new(Vec::new())),
+            mouse_enter_log: Rc::clone(&enter_log2),
+            mouse_leave_log: Rc::clone(&leave_log2),
+        };
+
+        let mut buddy = root_buddy();
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.on_attach(&mut buddy);
+        menu.add_component(
+            Box::new(component1),
+            ComponentDomain::between(0.1, 0.1, 0.4, 0.9),
+        );
+        menu.add_component(
+            Box::new(component2),
+            ComponentDomain::between(0.6, 0.1, 0.9, 0.9),
+        );
+
+        let miss_enter_event = MouseEnterEvent::new(Mouse::new(0), Point::new(0.5, 0.5));
+        let miss_leave_event = MouseLeaveEvent::new(Mouse::new(0), Point::new(0.5, 0.5));
+        let edge_enter_event = MouseEnterEvent::new(Mouse::new(0), Point::new(0.65, 0.5));
+        let edge_leave_event = MouseLeaveEvent::new(Mouse::new(0), Point::new(0.65, 0.5));
+        let hit_enter_event = MouseEnterEvent::new(Mouse::new(0), Point::new(0.75, 0.5));
+        let hit_leave_event = MouseLeaveEvent::new(Mouse::new(0), Point::new(0.75, 0.5));
+        let render_region = RenderRegion::between(1, 2, 3, 4);
+
+        // Nothing should happen before the first render
+        menu.on_mouse_enter(hit_enter_event, &mut buddy);
+        menu.on_mouse_leave(hit_leave_event, &mut buddy);
+
+        // So let's render
+        menu.render(&test_renderer(render_region), &mut buddy, false)
+            .unwrap();
+
+        // Due to the mouse filtering, the edge event shouldn't trigger any reaction either
+        menu.on_mouse_enter(edge_enter_event, &mut buddy);
+        menu.on_mouse_leave(edge_leave_event, &mut buddy);
+
+        // But, the hit events should have effect
+        menu.on_mouse_enter(hit_enter_event, &mut buddy);
+        menu.on_mouse_leave(hit_leave_event, &mut buddy);
+
+        // The miss events shouldn't have any effect anyway
+        menu.on_mouse_enter(miss_enter_event, &mut buddy);
+        menu.on_mouse_leave(miss_leave_event, &mut buddy);
+
+        let enter_log1 = enter_log1.borrow();
+        assert!(enter_log1.is_empty());
+        let leave_log1 = leave_log1.borrow();
+        assert!(leave_log1.is_empty());
+
+        let enter_log2 = enter_log2.borrow();
+        assert_eq!(1, enter_log2.len());
+        assert!(enter_log2[0]
+            .get_entrance_point()
+            .nearly_equal(Point::new(0.5, 0.5)));
+
+        let leave_log2 = leave_log2.borrow();
+        assert_eq!(1, leave_log2.len());
+        assert!(leave_log2[0]
+            .get_exit_point()
+            .nearly_equal(Point::new(0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_hover_hysteresis() {
+        let enter_log = Rc::new(RefCell::new(Vec::new()));
+        let leave_log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut buddy = root_buddy();
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.set_hover_hysteresis(0.1);
+        menu.on_attach(&mut buddy);
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(true)),
+                mouse_move_log: Rc::new(RefCell::new(Vec::new())),
+                mouse_enter_log: Rc::clone(&enter_log),
+                mouse_leave_log: Rc::clone(&leave_log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        menu.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), &mut buddy, false)
+            .unwrap();
+
+        let mut move_mouse = |from_x: f32, to_x: f32| {
+            let event =
+                MouseMoveEvent::new(Mouse::new(0), Point::new(from_x, 0.5), Point::new(to_x, 0.5));
+            menu.on_mouse_move(event, &mut buddy);
+        };
+        let check_counts = |num_enters: usize, num_leaves: usize| {
+            assert_eq!(num_enters, enter_log.borrow().len());
+            assert_eq!(num_leaves, leave_log.borrow().len());
+        };
+
+        move_mouse(0.1, 0.3);
+        check_counts(1, 0);
+
+        // Jittering within the margin shouldn't fire any enter or leave events
+        move_mouse(0.3, 0.15);
+        move_mouse(0.15, 0.25);
+        move_mouse(0.25, 0.15);
+        check_counts(1, 0);
+
+        // Moving past the margin should
+        move_mouse(0.15, 0.05);
+        check_counts(1, 1);
+        assert!(leave_log.borrow()[0].get_exit_point().nearly_equal(Point::new(0.15, 0.5)));
+
+        // Getting near the component again shouldn't make the mouse enter it
+        move_mouse(0.05, 0.15);
+        check_counts(1, 1);
+        move_mouse(0.15, 0.3);
+        check_counts(2, 1);
+
+        // The component should also receive a leave event when the mouse leaves the menu while
+        // it's within the margin
+        move_mouse(0.3, 0.85);
+        check_counts(2, 1);
+        let leave_event = MouseLeaveEvent::new(Mouse::new(0), Point::new(0.85, 0.5));
+        menu.on_mouse_leave(leave_event, &mut buddy);
+        check_counts(2, 2);
+    }
+
+    #[test]
+    fn test_mouse_move() {
+        let move_logs = vec![
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+        ];
+        let enter_logs = vec![
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+        ];
+        let leave_logs = vec![
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+            Rc::new(RefCell::new(Vec::new())),
+        ];
+
+        let mut menu = SimpleFlatMenu::new(None);
+        let mut buddy = root_buddy();
+        menu.on_attach(&mut buddy);
+
+        // The outer bottom-left component
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(true)),
+                mouse_move_log: Rc::clone(&move_logs[0]),
+                mouse_enter_log: Rc::clone(&enter_logs[0]),
+                mouse_leave_log: Rc::clone(&leave_logs[0]),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.25, 0.25),
+        );
+
+        // The inner bottom-left component
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(false)),
+                mouse_move_log: Rc::clone(&move_logs[1]),
+                mouse_enter_log: Rc::clone(&enter_logs[1]),
+                mouse_leave_log: Rc::clone(&leave_logs[1]),
+            }),
+            ComponentDomain::between(0.25, 0.25, 0.5, 0.5),
+        );
+
+        // The inner top-right component
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(true)),
+                mouse_move_log: Rc::clone(&move_logs[2]),
+                mouse_enter_log: Rc::clone(&enter_logs[2]),
+                mouse_leave_log: Rc::clone(&leave_logs[2]),
+            }),
+            ComponentDomain::between(0.5, 0.5, 0.75, 0.75),
+        );
+
+        // The outer top-right component
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(true)),
+                mouse_move_log: Rc::clone(&move_logs[4]),
+                mouse_enter_log: Rc::clone(&enter_logs[4]),
+                mouse_leave_log: Rc::clone(&leave_logs[4]),
+            }),
+            ComponentDomain::between(0.75, 0.75, 1.0, 1.0),
+        );
+
+        // This component should be missed entirely
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(false)),
+                mouse_move_log: Rc::clone(&move_logs[3]),
+                mouse_enter_log: Rc::clone(&enter_logs[3]),
+                mouse_leave_log: Rc::clone(&leave_logs[3]),
+            }),
+            ComponentDomain::between(0.5, 0.0, 0.75, 0.25),
+        );
+
+        menu.render(
+            &test_renderer(RenderRegion::between(0, 0, 20, 30)),
+            &mut buddy,
+            false,
+        )
+        .unwrap();
+
+        let mouse = Mouse::new(3);
+        let entrance_x = 0.25 * 0.25;
+        let entrance_y = 0.25 * 0.25;
+        let entrance = Point::new(entrance_x, entrance_y);
+        let exit_x = 1.0 - entrance_x;
+        let exit_y = 1.0 - entrance_y;
+        let exit = Point::new(exit_x, exit_y);
+
+        let enter_event = MouseEnterEvent::new(mouse, entrance);
+        let move_event = MouseMoveEvent::new(mouse, entrance, exit);
+        let leave_event = MouseLeaveEvent::new(mouse, exit);
+        menu.on_mouse_enter(enter_event, &mut buddy);
+        menu.on_mouse_move(move_event, &mut buddy);
+        menu.on_mouse_leave(leave_event, &mut buddy);
+
+        // Time to check the results...
+
+        // But first some helper functions
+        let eq_mouse_move =
+            |enter_x: f32, enter_y: f32, exit_x: f32, exit_y: f32, event: &MouseMoveEvent| {
+                assert_eq!(mouse, event.get_mouse());
+                assert!(Point::new(enter_x, enter_y).nearly_equal(event.get_from()));
+                assert!(Point::new(exit_x, exit_y).nearly_equal(event.get_to()));
+            };
+        let eq_mouse_enter = |enter_x: f32, enter_y: f32, event: &MouseEnterEvent| {
+            assert_eq!(mouse, event.get_mouse());
+            assert!(Point::new(enter_x, enter_y).nearly_equal(event.get_entrance_point()));
+        };
+        let eq_mouse_leave = |exit_x: f32, exit_y: f32, event: &MouseLeaveEvent| {
+            assert_eq!(mouse, event.get_mouse());
+            assert!(Point::new(exit_x, exit_y).nearly_equal(event.get_exit_point()));
+        };
+        let check_log = |index: usize, enter_x: f32, enter_y: f32, exit_x: f32, exit_y: f32| {
+            let move_log = move_logs[index].borrow();
+            assert_eq!(1, move_log.len());
+            eq_mouse_move(enter_x, enter_y, exit_x, exit_y, &move_log[0]);
+            let enter_log = enter_logs[index].borrow();
+            assert_eq!(1, enter_log.len());
+            eq_mouse_enter(enter_x, enter_y, &enter_log[0]);
+            let leave_log = leave_logs[index].borrow();
+            assert_eq!(1, leave_log.len());
+            eq_mouse_leave(exit_x, exit_y, &leave_log[0]);
+        };
+
+        // Finally check the actual results
+        check_log(0, 0.25, 0.25, 0.8, 0.8);
+        check_log(1, 0.0, 0.0, 1.0, 1.0);
+        check_log(2, 0.2, 0.2, 0.8, 0.8);
+        check_log(4, 0.2, 0.2, 0.75, 0.75);
+
+        // And check that the out-of-line component didn't receive any events
+        let move_log = move_logs[3].borrow();
+        assert!(move_log.is_empty());
+        let enter_log = enter_logs[3].borrow();
+        assert!(enter_log.is_empty());
+        let leave_log = leave_logs[3].borrow();
+        assert!(leave_log.is_empty());
+    }
+
+    #[test]
+    fn test_mouse_move_subscriptions() {
+        struct MouseMoveSubscribeComponent {
+            subscribe_mouse_move: Rc<Cell<bool>>,
+            subscribe_mouse_enter: Rc<Cell<bool>>,
+            subscribe_mouse_leave: Rc<Cell<bool>>,
+
+            mouse_move_log: Rc<RefCell<Vec<MouseMoveEvent>>>,
+            mouse_enter_log: Rc<RefCell<Vec<MouseEnterEvent>>>,
+            mouse_leave_log: Rc<RefCell<Vec<MouseLeaveEvent>>>,
+        }
+
+        impl Component for MouseMoveSubscribeComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                if self.subscribe_mouse_move.get() {
+                    buddy.subscribe_mouse_move();
+                } else {
+                    buddy.unsubscribe_mouse_move();
+                }
+                if self.subscribe_mouse_enter.get() {
+                    buddy.subscribe_mouse_enter();
+                } else {
+                    buddy.unsubscribe_mouse_enter();
+                }
+                if self.subscribe_mouse_leave.get() {
+                    buddy.subscribe_mouse_leave();
+                } else {
+                    buddy.unsubscribe_mouse_leave();
+                }
+                entire_render_result()
+            }
+
+            fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+                let mut move_log = self.mouse_move_log.borrow_mut();
+                move_log.push(event);
+            }
+
+            fn on_mouse_enter(&mut self, event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+                let mut enter_log = self.mouse_enter_log.borrow_mut();
+                enter_log.push(event);
+            }
+
+            fn on_mouse_leave(&mut self, event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+                let mut leave_log = self.mouse_leave_log.borrow_mut();
+                leave_log.push(event);
+            }
+        }
+
+        let mouse_move_log = Rc::new(RefCell::new(Vec::new()));
+        let mouse_enter_log = Rc::new(RefCell::new(Vec::new()));
+        let mouse_leave_log = Rc::new(RefCell::new(Vec::new()));
+
+        let sub_mouse_move = Rc::new(Cell::new(false));
+        let sub_mouse_enter = Rc::new(Cell::new(false));
+        let sub_mouse_leave = Rc::new(Cell::new(false));
+
+        let component = MouseMoveSubscribeComponent {
+            mouse_move_log: Rc::clone(&mouse_move_log),
+            mouse_enter_log: Rc::clone(&mouse_enter_log),
+            mouse_leave_log: Rc::clone(&mouse_leave_log),
+
+            subscribe_mouse_move: Rc::clone(&sub_mouse_move),
+            subscribe_mouse_enter: Rc::clone(&sub_mouse_enter),
+            subscribe_mouse_leave: Rc::clone(&sub_mouse_leave),
+        };
+
+        let mut menu = SimpleFlatMenu::new(None);
+        let mut buddy = root_buddy();
+
+        menu.on_attach(&mut buddy);
+        menu.add_component(
+            Box::new(component),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+
+        let mut try_combination = |mouse_move: bool, mouse_enter: bool, mouse_leave: bool| {
+            sub_mouse_move.set(mouse_move);
+            sub_mouse_enter.set(mouse_enter);
+            sub_mouse_leave.set(mouse_leave);
+            menu.render(
+                &test_renderer(RenderRegion::between(0, 1, 4, 7)),
+                &mut buddy,
+                true,
+            )
+            .unwrap();
+            let mouse = Mouse::new(2);
+            let original_enter_event1 = MouseEnterEvent::new(mouse, Point::new(0.1, 0.6));
+            let original_enter_event2 =
+                MouseMoveEvent::new(mouse, Point::new(0.1, 0.6), Point::new(0.1, 0.25));
+            let original_move_event =
+                MouseMoveEvent::new(mouse, Point::new(0.1, 0.25), Point::new(0.4, 0.25));
+            let original_leave_event1 =
+                MouseMoveEvent::new(mouse, Point::new(0.4, 0.25), Point::new(0.4, 0.6));
+            let original_leave_event2 = MouseLeaveEvent::new(mouse, Point::new(0.4, 0.6));
+            let transformed_enter_event1 = MouseEnterEvent::new(mouse, Point::new(0.2, 1.0));
+            let transformed_enter_event2 =
+                MouseMoveEvent::new(mouse, Point::new(0.2, 1.0), Point::new(0.2, 0.5));
+            let transformed_move_event =
+                MouseMoveEvent::new(mouse, Point::new(0.2, 0.5), Point::new(0.8, 0.5));
+            let transformed_leave_event1 =
+                MouseMoveEvent::new(mouse, Point::new(0.8, 0.5), Point::new(0.8, 1.0));
+            let transformed_leave_event2 = MouseLeaveEvent::new(mouse, Point::new(0.8, 1.0));
+
+            menu.on_mouse_enter(original_enter_event1, &mut buddy);
+            menu.on_mouse_move(original_enter_event2, &mut buddy);
+            menu.on_mouse_move(original_move_event, &mut buddy);
+            menu.on_mouse_move(original_leave_event1, &mut buddy);
+            menu.on_mouse_leave(original_leave_event2, &mut buddy);
+
+            let mut move_log = mouse_move_log.borrow_mut();
+            let mut enter_log = mouse_enter_log.borrow_mut();
+            let mut leave_log = mouse_leave_log.borrow_mut();
+
+            if mouse_move {
+                let move_event_eq = |expected: &MouseMoveEvent, actual: &MouseMoveEvent| {
+                    assert_eq!(expected.get_mouse(), actual.get_mouse());
+                    assert!(expected.get_from().nearly_equal(actual.get_from()));
+                    assert!(expected.get_to().nearly_equal(actual.get_to()));
+                };
+
+                assert_eq!(3, move_log.len());
+                move_event_eq(&transformed_enter_event2, &move_log[0]);
+                move_event_eq(&transformed_move_event, &move_log[1]);
+                move_event_eq(&transformed_leave_event1, &move_log[2]);
+            } else {
+                assert!(move_log.is_empty());
+            }
+
+            if mouse_enter {
+                let enter_event_eq = |expected: &MouseEnterEvent, actual: &MouseEnterEvent| {
+                    assert_eq!(expected.get_mouse(), actual.get_mouse());
+                    assert!(expected
+                        .get_entrance_point()
+                        .nearly_equal(actual.get_entrance_point()));
+                };
+
+                assert_eq!(1, enter_log.len());
+                enter_event_eq(&transformed_enter_event1, &enter_log[0]);
+            } else {
+                assert!(enter_log.is_empty());
+            }
+
+            if mouse_leave {
+                let leave_event_eq = |expected: &MouseLeaveEvent, actual: &MouseLeaveEvent| {
+                    assert_eq!(expected.get_mouse(), actual.get_mouse());
+                    assert!(expected
+                        .get_exit_point()
+                        .nearly_equal(actual.get_exit_point()));
+                };
+
+                assert_eq!(1, leave_log.len());
+                leave_event_eq(&transformed_leave_event2, &leave_log[0]);
+            } else {
+                assert!(leave_log.is_empty());
+            }
+
+            move_log.clear();
+            enter_log.clear();
+            leave_log.clear();
+        };
+
+        for _counter in 0..2 {
+            try_combination(false, false, false);
+            try_combination(false, false, true);
+            try_combination(false, true, false);
+            try_combination(false, true, true);
+            try_combination(true, false, false);
+            try_combination(true, false, true);
+            try_combination(true, true, false);
+            try_combination(true, true, true);
+        }
+    }
+
+    #[test]
+    fn test_own_subscriptions() {
+        struct CuriousComponent {}
+
+        impl Component for CuriousComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                buddy.subscribe_mouse_click_out();
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_release();
+                buddy.subscribe_mouse_move();
+                buddy.subscribe_mouse_enter();
+                buddy.subscribe_mouse_leave();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+        }
+
+        let mut menu = SimpleFlatMenu::new(None);
+        let mut buddy = root_buddy();
+        menu.on_attach(&mut buddy);
+        menu.add_component(
+            Box::new(CuriousComponent {}),
+            ComponentDomain::between(0.3, 0.6, 1.0, 0.9),
+        );
+
+        // The menu should have subscribed to all events
+        let subs = buddy.get_subscriptions();
+        assert!(subs.mouse_click);
+        assert!(subs.mouse_click_out);
+        assert!(subs.mouse_press);
+        assert!(subs.mouse_release);
+        assert!(subs.mouse_move);
+        assert!(subs.mouse_enter);
+        assert!(subs.mouse_leave);
+    }
+
+    #[test]
+    fn test_buddy_get_all_mouses() {
+        struct GetMouseComponent {
+            expected: Rc<RefCell<Vec<Mouse>>>,
+            call_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for GetMouseComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let expected = self.expected.borrow();
+                assert_eq!(expected.as_ref() as &Vec<Mouse>, &buddy.get_all_mouses());
+                self.call_counter.set(self.call_counter.get() + 1);
+                entire_render_result()
+            }
+        }
+
+        let expected_mouses = Rc::new(RefCell::new(Vec::new()));
+        let call_counter = Rc::new(Cell::new(0));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(GetMouseComponent {
+                expected: Rc::clone(&expected_mouses),
+                call_counter: Rc::clone(&call_counter),
+            }),
+            ComponentDomain::between(0.1, 0.2, 0.3, 0.4),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+
+        let region = RenderRegion::with_size(1, 2, 3, 4);
+
+        // The mouses should be empty initially
+        application.render(&test_renderer(region), true);
+
+        let enter_event =
+            |mouse_id: u16| MouseEnterEvent::new(Mouse::new(mouse_id), Point::new(0.2, 0.3));
+        let leave_event =
+            |mouse_id: u16| MouseLeaveEvent::new(Mouse::new(mouse_id), Point::new(0.2, 0.3));
+        let mouse_vec = |ids: &[u16]| ids.iter().map(|id| Mouse::new(*id)).collect();
+
+        // Add the first mouse
+        application.fire_mouse_enter_event(enter_event(123));
+        expected_mouses.replace(mouse_vec(&[123]));
+        application.render(&test_renderer(region), true);
+
+        // Add the second mouse
+        application.fire_mouse_enter_event(enter_event(1));
+        expected_mouses.replace(mouse_vec(&[123, 1]));
+        application.render(&test_renderer(region), true);
+
+        // Remove the first mouse
+        application.fire_mouse_leave_event(leave_event(123));
+        expected_mouses.replace(mouse_vec(&[1]));
+        application.render(&test_renderer(region), true);
+
+        // Add the first mouse back, and add yet another mouse
+        application.fire_mouse_enter_event(enter_event(123));
+        application.fire_mouse_enter_event(enter_event(8));
+        expected_mouses.replace(mouse_vec(&[1, 123, 8]));
+        application.render(&test_renderer(region), true);
+
+        // Remove all mouses
+        application.fire_mouse_leave_event(leave_event(123));
+        application.fire_mouse_leave_event(leave_event(8));
+        application.fire_mouse_leave_event(leave_event(1));
+        expected_mouses.replace(mouse_vec(&[]));
+        application.render(&test_renderer(region), true);
+
+        assert_eq!(6, call_counter.get());
+    }
+
+    #[test]
+    fn test_buddy_get_local_mouses_and_positions() {
+        struct LocalMouse {
+            mouse: Mouse,
+            position: Point,
+        }
+
+        struct LocalMouseCheckComponent {
+            expected_mouses: Rc<RefCell<Vec<LocalMouse>>>,
+        }
+
+        impl Component for LocalMouseCheckComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let local_mouses = buddy.get_local_mouses();
+                let expected_mouses = self.expected_mouses.borrow();
+                assert_eq!(expected_mouses.len(), local_mouses.len());
+
+                // Test that all local mouses are present and have the right position
+                'local_outer: for mouse in &local_mouses {
+                    for entry in &*expected_mouses {
+                        if entry.mouse == *mouse {
+                            assert!(entry
+                                .position
+                                .nearly_equal(buddy.get_mouse_position(*mouse).unwrap()));
+                            continue 'local_outer;
+                        }
+                    }
+                    panic!("Expected mouse {:?}, but didn't find its entry", mouse);
+                }
+
+                // Test that the other mouses do not have a position
+                'all_outer: for mouse in buddy.get_all_mouses() {
+                    for local_mouse in &local_mouses {
+                        if *local_mouse == mouse {
+                            continue 'all_outer;
+                        }
+                    }
+                    assert!(buddy.get_mouse_position(mouse).is_none());
+                }
+
+                // Neither should a non-existing mouse
+                assert!(buddy.get_mouse_position(Mouse::new(8347)).is_none());
+
+                entire_render_result()
+            }
+        }
+
+        let expected_mouses1 = Rc::new(RefCell::new(Vec::new()));
+        let expected_mouses2 = Rc::new(RefCell::new(Vec::new()));
+
+        let set = |target: &Rc<RefCell<Vec<LocalMouse>>>, mut expected: Vec<LocalMouse>| {
+            let mut mouses = target.borrow_mut();
+            mouses.clear();
+            mouses.append(&mut expected);
+        };
+        let set1 = |expected: Vec<LocalMouse>| set(&expected_mouses1, expected);
+        let set2 = |expected: Vec<LocalMouse>| set(&expected_mouses2, expected);
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(LocalMouseCheckComponent {
+                expected_mouses: Rc::clone(&expected_mouses1),
+            }),
+            ComponentDomain::between(0.2, 0.0, 0.5, 0.7),
+        );
+        menu.add_component(
+            Box::new(LocalMouseCheckComponent {
+                expected_mouses: Rc::clone(&expected_mouses2),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        let region = RenderRegion::between(10, 20, 30, 40);
+        application.render(&test_renderer(region), true);
+
+        // Start with 1 mouse, and spawn it in the middle of the first component
+        let mouse1 = Mouse::new(6);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse1, Point::new(0.35, 0.35)));
+        set1(vec![LocalMouse {
+            mouse: mouse1,
+            position: Point::new(0.5, 0.5),
+        }]);
+        set2(vec![]);
+        application.render(&test_renderer(region), true);
+
+        // Move the mouse to the other component
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse1,
+            Point::new(0.35, 0.35),
+            Point::new(0.6, 0.9),
+        ));
+        set1(vec![]);
+        set2(vec![LocalMouse {
+            mouse: mouse1,
+            position: Point::new(0.2, 0.8),
+        }]);
+        application.render(&test_renderer(region), true);
+
+        // Move the mouse away from both components
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse1,
+            Point::new(0.6, 0.9),
+            Point::new(0.1, 0.1),
+        ));
+        set1(vec![]);
+        set2(vec![]);
+        application.render(&test_renderer(region), true);
+
+        // Introduce the second mouse
+        let mouse2 = Mouse::new(120);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse2, Point::new(0.1, 0.1)));
+        // Neither of the mouses is inside any of the components
+        application.render(&test_renderer(region), true);
+
+        // Move the second mouse to the second component
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse2,
+            Point::new(0.1, 0.1),
+            Point::new(0.7, 0.8),
+        ));
+        set1(vec![]);
+        set2(vec![LocalMouse {
+            mouse: mouse2,
+            position: Point::new(0.4, 0.6),
+        }]);
+        application.render(&test_renderer(region), true);
+
+        // Move the first mouse to the first component
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse1,
+            Point::new(0.1, 0.1),
+            Point::new(0.35, 0.35),
+        ));
+        set1(vec![LocalMouse {
+            mouse: mouse1,
+            position: Point::new(0.5, 0.5),
+        }]);
+        set2(vec![LocalMouse {
+            mouse: mouse2,
+            position: Point::new(0.4, 0.6),
+        }]);
+        application.render(&test_renderer(region), true);
+
+        // Move the first mouse to the second component
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse1,
+            Point::new(0.35, 0.35),
+            Point::new(0.8, 0.7),
+        ));
+        set1(vec![]);
+        set2(vec![
+            LocalMouse {
+                mouse: mouse1,
+                position: Point::new(0.6, 0.4),
+            },
+            LocalMouse {
+                mouse: mouse2,
+                position: Point::new(0.4, 0.6),
+            },
+        ]);
+        application.render(&test_renderer(region), true);
+
+        // Remove the second mouse
+        application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse2, Point::new(0.7, 0.8)));
+        set1(vec![]);
+        set2(vec![LocalMouse {
+            mouse: mouse1,
+            position: Point::new(0.6, 0.4),
+        }]);
+        application.render(&test_renderer(region), true);
+    }
+
+    #[test]
+    fn test_render_viewports_and_scissors() {
+        struct ViewportTestComponent {
+            expected_viewport: Rc<Cell<RenderRegion>>,
+            render_counter: Rc<Cell<u8>>,
+        }
+        impl Component for ViewportTestComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.render_counter.set(self.render_counter.get() + 1);
+                assert_eq!(self.expected_viewport.get(), renderer.get_viewport());
+                assert_eq!(self.expected_viewport.get(), renderer.get_scissor());
+                entire_render_result()
+            }
+        }
+
+        let mut renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+        let mut buddy = root_buddy();
+        let mut menu = SimpleFlatMenu::new(None);
+
+        let render_counter = Rc::new(Cell::new(0));
+
+        // I will assign proper values later
+        let viewport1 = Rc::new(Cell::new(RenderRegion::with_size(1, 2, 3, 4)));
+        let viewport2 = Rc::new(Cell::new(RenderRegion::with_size(1, 2, 3, 4)));
+
+        menu.add_component(
+            Box::new(ViewportTestComponent {
+                render_counter: Rc::clone(&render_counter),
+                expected_viewport: Rc::clone(&viewport1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            Box::new(ViewportTestComponent {
+                render_counter: Rc::clone(&render_counter),
+                expected_viewport: Rc::clone(&viewport2),
+            }),
+            ComponentDomain::between(0.2, 0.5, 0.9, 1.0),
+        );
+
+        viewport1.set(RenderRegion::between(0, 0, 50, 50));
+        viewport2.set(RenderRegion::between(20, 50, 90, 100));
+        menu.render(&renderer, &mut buddy, true).unwrap();
+
+        renderer.reset_viewport(RenderRegion::between(100, 200, 300, 400));
+        viewport1.set(RenderRegion::between(100, 200, 200, 300));
+        viewport2.set(RenderRegion::between(140, 300, 280, 400));
+        menu.render(&renderer, &mut buddy, true).unwrap();
+
+        // Check that the render methods were actually called twice per component
+        assert_eq!(4, render_counter.get());
+    }
+
+    #[test]
+    fn test_render_with_custom_scissor() {
+        struct ScissorTestComponent {
+            expected_viewport: Rc<Cell<RenderRegion>>,
+            expected_scissor: Rc<Cell<RenderRegion>>,
+            render_counter: Rc<Cell<u8>>,
+        }
+        impl Component for ScissorTestComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.render_counter.set(self.render_counter.get() + 1);
+                assert_eq!(self.expected_viewport.get(), renderer.get_viewport());
+                assert_eq!(self.expected_scissor.get(), renderer.get_scissor());
+                entire_render_result()
+            }
+        }
+
+        let counter_left = Rc::new(Cell::new(0));
+        let counter_middle = Rc::new(Cell::new(0));
+        let counter_right = Rc::new(Cell::new(0));
+
+        let viewport_left = Rc::new(Cell::new(RenderRegion::between(10, 10, 20, 20)));
+        let scissor_left = Rc::new(Cell::new(RenderRegion::between(10, 10, 20, 20)));
+        let viewport_middle = Rc::new(Cell::new(RenderRegion::between(40, 40, 60, 60)));
+        let scissor_middle = Rc::new(Cell::new(RenderRegion::between(40, 40, 60, 60)));
+        let viewport_right = Rc::new(Cell::new(RenderRegion::between(80, 80, 90, 90)));
+        let scissor_right = Rc::new(Cell::new(RenderRegion::between(80, 80, 90, 90)));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(ScissorTestComponent {
+                expected_viewport: Rc::clone(&viewport_left),
+                expected_scissor: Rc::clone(&scissor_left),
+                render_counter: Rc::clone(&counter_left),
+            }),
+            ComponentDomain::between(0.1, 0.1, 0.2, 0.2),
+        );
+        menu.add_component(
+            Box::new(ScissorTestComponent {
+                expected_viewport: Rc::clone(&viewport_middle),
+                expected_scissor: Rc::clone(&scissor_middle),
+                render_counter: Rc::clone(&counter_middle),
+            }),
+            ComponentDomain::between(0.4, 0.4, 0.6, 0.6),
+        );
+        menu.add_component(
+            Box::new(ScissorTestComponent {
+                expected_viewport: Rc::clone(&viewport_right),
+                expected_scissor: Rc::clone(&scissor_right),
+                render_counter: Rc::clone(&counter_right),
+            }),
+            ComponentDomain::between(0.8, 0.8, 0.9, 0.9),
+        );
+
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+        let mut buddy = root_buddy();
+
+        // First try without scissor
+        menu.render(&renderer, &mut buddy, true).unwrap();
+        assert_eq!(1, counter_left.get());
+        assert_eq!(1, counter_middle.get());
+        assert_eq!(1, counter_right.get());
+
+        // Now with a scissor in the left half
+        scissor_middle.set(RenderRegion::between(40, 40, 50, 60));
+        renderer.push_scissor(0.0, 0.0, 0.5, 1.0, || {
+            menu.render(&renderer, &mut buddy, true).unwrap();
+        });
+        assert_eq!(2, counter_left.get());
+        assert_eq!(2, counter_middle.get());
+        assert_eq!(1, counter_right.get());
+    }
+
+    #[test]
+    fn test_buddy_change_menu() {
+        struct NewMenuComponent {
+            counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for NewMenuComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                self.counter.set(5);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.counter.set(self.counter.get() * 3);
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.counter.set(self.counter.get() + 1);
+            }
+        }
+
+        struct ChangeMenuComponent {
+            counter: Rc<Cell<u8>>,
+            new_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for ChangeMenuComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                self.counter.set(2);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.counter.set(self.counter.get() * 3);
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                self.counter.set(self.counter.get() + 1);
+                let new_counter = Rc::clone(&self.new_counter);
+                buddy.change_menu(Box::new(move |_old_menu| {
+                    Box::new(NewMenuComponent {
+                        counter: new_counter,
+                    })
+                }));
+            }
+        }
+
+        let counter = Rc::new(Cell::new(0));
+        let new_counter = Rc::new(Cell::new(0));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(ChangeMenuComponent {
+                counter: Rc::clone(&counter),
+                new_counter: Rc::clone(&new_counter),
+            }),
+            ComponentDomain::between(0.3, 0.3, 0.7, 0.7),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+
+        let region = RenderRegion::with_size(1, 2, 3, 4);
+        let renderer = test_renderer(region);
+
+        assert_eq!(2, counter.get());
+        application.render(&renderer, false);
+        assert_eq!(6, counter.get());
+        assert_eq!(0, new_counter.get());
+
+        let click_event =
+            MouseClickEvent::new(Mouse::new(3), Point::new(0.4, 0.4), MouseButton::primary());
+
+        application.fire_mouse_click_event(click_event);
+        assert_eq!(7, counter.get());
+        assert_eq!(5, new_counter.get());
+
+        application.render(&renderer, false);
+        assert_eq!(7, counter.get());
+        assert_eq!(15, new_counter.get());
+
+        application.fire_mouse_click_event(click_event);
+        assert_eq!(7, counter.get());
+        assert_eq!(16, new_counter.get());
+    }
+
+    #[test]
+    fn test_mouse_press_and_release() {
+        struct PressReleaseComponent {
+            press_counter: Rc<Cell<u8>>,
+            release_counter: Rc<Cell<u8>>,
+            expected_press_event: Rc<Cell<MousePressEvent>>,
+            expected_release_event: Rc<Cell<MouseReleaseEvent>>,
+            filter_mouse_actions: bool,
+        }
+
+        impl Component for PressReleaseComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_release();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
                 _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
                 Ok(RenderResultStruct {
-                    drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 0.5, 0.5)),
-                    filter_mouse_actions: true,
+                    filter_mouse_actions: self.filter_mouse_actions,
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.2, 0.2, 0.8, 0.8)),
                 })
             }
 
-            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
-                self.in_counter.set(self.in_counter.get() + 1);
-            }
+            fn on_mouse_press(&mut self, event: MousePressEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.press_counter.set(self.press_counter.get() + 1);
+                let expected = self.expected_press_event.get();
+                assert_eq!(expected.get_mouse(), event.get_mouse());
+                assert!(expected.get_point().nearly_equal(event.get_point()));
+                assert_eq!(expected.get_button(), event.get_button());
+            }
+
+            fn on_mouse_release(
+                &mut self,
+                event: MouseReleaseEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.release_counter.set(self.release_counter.get() + 1);
+                let expected = self.expected_release_event.get();
+                assert_eq!(expected.get_mouse(), event.get_mouse());
+                assert!(expected.get_point().nearly_equal(event.get_point()));
+                assert_eq!(expected.get_button(), event.get_button());
+            }
+        }
+
+        // The initial events don't matter, but need to be specified
+        let dummy_press_event =
+            MousePressEvent::new(Mouse::new(111), Point::new(1.1, 1.1), MouseButton::new(11));
+        let dummy_release_event =
+            MouseReleaseEvent::new(Mouse::new(222), Point::new(2.2, 2.2), MouseButton::new(22));
+
+        let press_counter1 = Rc::new(Cell::new(0));
+        let release_counter1 = Rc::new(Cell::new(0));
+        let expected_press_event1 = Rc::new(Cell::new(dummy_press_event));
+        let expected_release_event1 = Rc::new(Cell::new(dummy_release_event));
+
+        let press_counter2 = Rc::new(Cell::new(0));
+        let release_counter2 = Rc::new(Cell::new(0));
+        let expected_press_event2 = Rc::new(Cell::new(dummy_press_event));
+        let expected_release_event2 = Rc::new(Cell::new(dummy_release_event));
+
+        let mut buddy = root_buddy();
+        let mut menu = SimpleFlatMenu::new(None);
+
+        menu.add_component(
+            Box::new(PressReleaseComponent {
+                press_counter: Rc::clone(&press_counter1),
+                release_counter: Rc::clone(&release_counter1),
+                expected_press_event: Rc::clone(&expected_press_event1),
+                expected_release_event: Rc::clone(&expected_release_event1),
+                filter_mouse_actions: true,
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+
+        menu.add_component(
+            Box::new(PressReleaseComponent {
+                press_counter: Rc::clone(&press_counter2),
+                release_counter: Rc::clone(&release_counter2),
+                expected_press_event: Rc::clone(&expected_press_event2),
+                expected_release_event: Rc::clone(&expected_release_event2),
+                filter_mouse_actions: false,
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        // Attaching and rendering should be done before sending events
+        menu.on_attach(&mut buddy);
+        menu.render(
+            &test_renderer(RenderRegion::with_size(0, 0, 1000, 1000)),
+            &mut buddy,
+            false,
+        )
+        .unwrap();
+
+        let check_counters = |press1: u8, release1: u8, press2: u8, release2: u8| {
+            assert_eq!(press1, press_counter1.get());
+            assert_eq!(release1, release_counter1.get());
+            assert_eq!(press2, press_counter2.get());
+            assert_eq!(release2, release_counter2.get());
+        };
+
+        // Miss these events on purpose:
+        menu.on_mouse_press(
+            MousePressEvent::new(Mouse::new(1), Point::new(0.2, 0.7), MouseButton::primary()),
+            &mut buddy,
+        );
+        menu.on_mouse_release(
+            MouseReleaseEvent::new(Mouse::new(1), Point::new(0.2, 0.7), MouseButton::primary()),
+            &mut buddy,
+        );
+        check_counters(0, 0, 0, 0);
+
+        // Press and release in the middle of both components
+        expected_press_event1.set(MousePressEvent::new(
+            Mouse::new(2),
+            Point::new(0.5, 0.5),
+            MouseButton::new(1),
+        ));
+        menu.on_mouse_press(
+            MousePressEvent::new(Mouse::new(2), Point::new(0.25, 0.25), MouseButton::new(1)),
+            &mut buddy,
+        );
+        expected_press_event2.set(MousePressEvent::new(
+            Mouse::new(3),
+            Point::new(0.5, 0.5),
+            MouseButton::new(2),
+        ));
+        menu.on_mouse_press(
+            MousePressEvent::new(Mouse::new(3), Point::new(0.75, 0.75), MouseButton::new(2)),
+            &mut buddy,
+        );
+        expected_release_event1.set(MouseReleaseEvent::new(
+            Mouse::new(2),
+            Point::new(0.5, 0.5),
+            MouseButton::new(1),
+        ));
+        menu.on_mouse_release(
+            MouseReleaseEvent::new(Mouse::new(2), Point::new(0.25, 0.25), MouseButton::new(1)),
+            &mut buddy,
+        );
+        expected_release_event2.set(MouseReleaseEvent::new(
+            Mouse::new(3),
+            Point::new(0.5, 0.5),
+            MouseButton::new(2),
+        ));
+        menu.on_mouse_release(
+            MouseReleaseEvent::new(Mouse::new(3), Point::new(0.75, 0.75), MouseButton::new(2)),
+            &mut buddy,
+        );
+        check_counters(1, 1, 1, 1);
+
+        // This time, press near bottom left corner and release near top right corner
+        // Since the first component filters mouse actions, only the second component should notice this
+        menu.on_mouse_press(
+            MousePressEvent::new(Mouse::new(4), Point::new(0.05, 0.05), MouseButton::new(3)),
+            &mut buddy,
+        );
+        menu.on_mouse_release(
+            MouseReleaseEvent::new(Mouse::new(4), Point::new(0.45, 0.45), MouseButton::new(3)),
+            &mut buddy,
+        );
+        expected_press_event2.set(MousePressEvent::new(
+            Mouse::new(5),
+            Point::new(0.1, 0.1),
+            MouseButton::new(4),
+        ));
+        menu.on_mouse_press(
+            MousePressEvent::new(Mouse::new(5), Point::new(0.55, 0.55), MouseButton::new(4)),
+            &mut buddy,
+        );
+        expected_release_event2.set(MouseReleaseEvent::new(
+            Mouse::new(5),
+            Point::new(0.9, 0.9),
+            MouseButton::new(4),
+        ));
+        menu.on_mouse_release(
+            MouseReleaseEvent::new(Mouse::new(5), Point::new(0.95, 0.95), MouseButton::new(4)),
+            &mut buddy,
+        );
+        check_counters(1, 1, 2, 2);
+    }
+
+    #[test]
+    fn test_buddy_pressed_mouse_buttons() {
+        struct MouseCheck {
+            mouse: Mouse,
+            button: MouseButton,
+            result: Option<bool>,
+        }
+
+        impl MouseCheck {
+            fn new(mouse: Mouse, button: MouseButton, result: Option<bool>) -> Self {
+                Self {
+                    mouse,
+                    button,
+                    result,
+                }
+            }
+        }
+
+        struct VecCheck {
+            mouse: Mouse,
+            buttons: Option<Vec<MouseButton>>,
+        }
+
+        impl VecCheck {
+            fn new(mouse: Mouse, buttons: Option<Vec<MouseButton>>) -> Self {
+                Self { mouse, buttons }
+            }
+        }
+
+        struct MouseCheckComponent {
+            checks: Rc<Cell<Vec<MouseCheck>>>,
+            vec_checks: Rc<Cell<Vec<VecCheck>>>,
+            render_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for MouseCheckComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let checks = self.checks.take();
+                for check in checks {
+                    assert_eq!(
+                        check.result,
+                        buddy.is_mouse_button_down(check.mouse, check.button)
+                    );
+                }
+
+                let vec_checks = self.vec_checks.take();
+                for check in vec_checks {
+                    assert_eq!(check.buttons, buddy.get_pressed_mouse_buttons(check.mouse));
+                }
 
-            fn on_mouse_click_out(
-                &mut self,
-                _event: MouseClickOutEvent,
-                _buddy: &mut dyn ComponentBuddy,
-            ) {
-                self.out_counter.set(self.out_counter.get() + 1);
+                self.render_counter.set(self.render_counter.get() + 1);
+                entire_render_result()
             }
         }
 
-        let mut buddy = root_buddy();
-        let mut menu = SimpleFlatMenu::new(None);
-        let in1 = Rc::new(Cell::new(0));
-        let in2 = Rc::new(Cell::new(0));
-        let out1 = Rc::new(Cell::new(0));
-        let out2 = Rc::new(Cell::new(0));
+        let counter1 = Rc::new(Cell::new(0));
+        let counter2 = Rc::new(Cell::new(0));
 
+        let checks1 = Rc::new(Cell::new(Vec::new()));
+        let checks2 = Rc::new(Cell::new(Vec::new()));
+
+        let vec_checks1 = Rc::new(Cell::new(Vec::new()));
+        let vec_checks2 = Rc::new(Cell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(ClickCountComponent {
-                in_counter: Rc::clone(&in1),
-                out_counter: Rc::clone(&out1),
+            Box::new(MouseCheckComponent {
+                checks: Rc::clone(&checks1),
+                vec_checks: Rc::clone(&vec_checks1),
+                render_counter: Rc::clone(&counter1),
             }),
-            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+            ComponentDomain::between(0.2, 0.5, 0.7, 0.7),
         );
         menu.add_component(
-            Box::new(ClickCountComponent {
-                in_counter: Rc::clone(&in2),
-                out_counter: Rc::clone(&out2),
+            Box::new(MouseCheckComponent {
+                checks: Rc::clone(&checks2),
+                vec_checks: Rc::clone(&vec_checks2),
+                render_counter: Rc::clone(&counter2),
             }),
-            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+            ComponentDomain::between(0.8, 0.8, 1.0, 1.0),
         );
 
-        let click_miss =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.8, 0.2), MouseButton::primary());
-        let click_out = MouseClickOutEvent::new(Mouse::new(0), MouseButton::primary());
-        let click1 =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary());
-        let click2 =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.6, 0.6), MouseButton::primary());
-        let click1out =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.3, 0.3), MouseButton::primary());
-        let click2out =
-            MouseClickEvent::new(Mouse::new(0), Point::new(0.9, 0.9), MouseButton::primary());
-
-        let check_counters = |value_in1: u8, value_out1: u8, value_in2: u8, value_out2: u8| {
-            assert_eq!(in1.get(), value_in1);
-            assert_eq!(out1.get(), value_out1);
-            assert_eq!(in2.get(), value_in2);
-            assert_eq!(out2.get(), value_out2);
+        let check_counters = |expected: u8| {
+            assert_eq!(expected, counter1.get());
+            assert_eq!(expected, counter2.get());
         };
 
-        // Clicking before rendering shouldn't have any effect
-        menu.on_mouse_click(click1, &mut buddy);
-        check_counters(0, 0, 0, 0);
-
-        // So let's render
-        menu.render(
-            &test_renderer(RenderRegion::between(0, 0, 120, 10)),
-            &mut buddy,
-            false,
-        )
-        .unwrap();
+        let mut application = Application::new(Box::new(menu));
 
-        // Clicking outside the menu should increment both out counters
-        menu.on_mouse_click_out(click_out, &mut buddy);
-        check_counters(0, 1, 0, 1);
+        let renderer = test_renderer(RenderRegion::between(10, 20, 30, 40));
 
-        // Clicking outside the components should also increment both out counters
-        menu.on_mouse_click(click_miss, &mut buddy);
-        check_counters(0, 2, 0, 2);
+        // No mouse should be present initially
+        checks1.set(vec![MouseCheck::new(
+            Mouse::new(0),
+            MouseButton::primary(),
+            None,
+        )]);
+        vec_checks1.set(vec![VecCheck::new(Mouse::new(0), None)]);
+        application.render(&renderer, true);
+        check_counters(1);
 
-        // Clicking inside the render region of component 1...
-        menu.on_mouse_click(click1, &mut buddy);
-        check_counters(1, 2, 0, 3);
+        // Spawn a mouse on component 1, but don't press any buttons yet
+        let mouse1 = Mouse::new(3);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse1, Point::new(0.6, 0.6)));
+        checks1.set(vec![MouseCheck::new(
+            mouse1,
+            MouseButton::primary(),
+            Some(false),
+        )]);
+        checks2.set(vec![MouseCheck::new(mouse1, MouseButton::primary(), None)]);
+        vec_checks1.set(vec![VecCheck::new(mouse1, Some(Vec::new()))]);
+        vec_checks2.set(vec![VecCheck::new(mouse1, None)]);
+        application.render(&renderer, true);
+        check_counters(2);
 
-        // Clicking inside the render region of component 2...
-        menu.on_mouse_click(click2, &mut buddy);
-        check_counters(1, 3, 1, 3);
+        // Press a button
+        let button1 = MouseButton::new(1);
+        let button2 = MouseButton::new(2);
+        application.fire_mouse_press_event(MousePressEvent::new(
+            mouse1,
+            Point::new(0.6, 0.6),
+            button1,
+        ));
+        checks1.set(vec![
+            MouseCheck::new(mouse1, button1, Some(true)),
+            MouseCheck::new(mouse1, button2, Some(false)),
+        ]);
+        checks2.set(vec![
+            MouseCheck::new(mouse1, button1, None),
+            MouseCheck::new(mouse1, button2, None),
+        ]);
+        vec_checks1.set(vec![
+            VecCheck::new(mouse1, Some(vec![button1])),
+            VecCheck::new(Mouse::new(10), None),
+        ]);
+        application.render(&renderer, true);
+        check_counters(3);
 
-        // Clicking outside the render region of component 1
-        // should increment both out counters
-        menu.on_mouse_click(click1out, &mut buddy);
-        check_counters(1, 4, 1, 4);
+        // Move the mouse away
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse1,
+            Point::new(0.6, 0.6),
+            Point::new(0.0, 0.0),
+        ));
+        checks1.set(vec![
+            MouseCheck::new(mouse1, button1, None),
+            MouseCheck::new(mouse1, button2, None),
+        ]);
+        checks2.set(vec![
+            MouseCheck::new(mouse1, button1, None),
+            MouseCheck::new(mouse1, button2, None),
+        ]);
+        vec_checks1.set(vec![VecCheck::new(mouse1, None)]);
+        application.render(&renderer, true);
+        check_counters(4);
 
-        // Same when clicking outside the render region of component 2
-        menu.on_mouse_click(click2out, &mut buddy);
-        check_counters(1, 5, 1, 5);
+        // Move the mouse to component 2
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse1,
+            Point::new(0.0, 0.0),
+            Point::new(0.9, 0.9),
+        ));
+        checks1.set(vec![
+            MouseCheck::new(mouse1, button1, None),
+            MouseCheck::new(mouse1, button2, None),
+        ]);
+        checks2.set(vec![
+            MouseCheck::new(mouse1, button1, Some(true)),
+            MouseCheck::new(mouse1, button2, Some(false)),
+        ]);
+        vec_checks2.set(vec![
+            VecCheck::new(mouse1, Some(vec![button1])),
+            VecCheck::new(Mouse::new(10), None),
+        ]);
+        application.render(&renderer, true);
+        check_counters(5);
     }
 
     #[test]
-    fn test_subscriptions() {
-        struct SubscribingComponent {
-            should_subscribe: Rc<Cell<bool>>,
-            should_unsubscribe: Rc<Cell<bool>>,
+    fn test_seat_policy() {
+        struct SeatComponent {
             click_counter: Rc<Cell<u8>>,
-            click_out_counter: Rc<Cell<u8>>,
-            press_counter: Rc<Cell<u8>>,
-            release_counter: Rc<Cell<u8>>,
+            local_mouses: Rc<RefCell<Vec<Mouse>>>,
         }
 
-        impl Component for SubscribingComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+        impl Component for SeatComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
 
             fn render(
                 &mut self,
@@ -1148,673 +4710,795 @@ mod tests {
                 buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                if self.should_subscribe.get() {
-                    buddy.subscribe_mouse_click();
-                    buddy.subscribe_mouse_click_out();
-                    buddy.subscribe_mouse_press();
-                    buddy.subscribe_mouse_release();
-                }
-                if self.should_unsubscribe.get() {
-                    buddy.unsubscribe_mouse_click();
-                    buddy.unsubscribe_mouse_click_out();
-                    buddy.unsubscribe_mouse_press();
-                    buddy.unsubscribe_mouse_release();
-                }
-                self.should_subscribe.set(false);
-                self.should_unsubscribe.set(false);
+                self.local_mouses.replace(buddy.get_local_mouses());
                 entire_render_result()
             }
 
             fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
                 self.click_counter.set(self.click_counter.get() + 1);
             }
+        }
 
-            fn on_mouse_click_out(
-                &mut self,
-                _event: MouseClickOutEvent,
-                _buddy: &mut dyn ComponentBuddy,
-            ) {
-                self.click_out_counter.set(self.click_out_counter.get() + 1);
+        let left_counter = Rc::new(Cell::new(0));
+        let right_counter = Rc::new(Cell::new(0));
+        let left_mouses = Rc::new(RefCell::new(Vec::new()));
+        let right_mouses = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component_with_seat_policy(
+            Box::new(SeatComponent {
+                click_counter: Rc::clone(&left_counter),
+                local_mouses: Rc::clone(&left_mouses),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+            SeatPolicy::OnlySeat(1),
+        );
+        menu.add_component(
+            Box::new(SeatComponent {
+                click_counter: Rc::clone(&right_counter),
+                local_mouses: Rc::clone(&right_mouses),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 100, 100));
+        application.render(&renderer, true);
+
+        let player1 = Mouse::with_seat(1, 1);
+        let player2 = Mouse::with_seat(2, 2);
+        let shared = Mouse::new(3);
+        let left = Point::new(0.2, 0.5);
+        let right = Point::new(0.8, 0.5);
+        let click = |mouse: Mouse, point: Point| {
+            MouseClickEvent::new(mouse, point, MouseButton::primary())
+        };
+
+        // Only player 1 and the shared mouse should be able to click the left component
+        application.fire_mouse_click_event(click(player1, left));
+        application.fire_mouse_click_event(click(player2, left));
+        application.fire_mouse_click_event(click(shared, left));
+        assert_eq!(2, left_counter.get());
+
+        // But everyone can click the right component
+        application.fire_mouse_click_event(click(player1, right));
+        application.fire_mouse_click_event(click(player2, right));
+        application.fire_mouse_click_event(click(shared, right));
+        assert_eq!(3, right_counter.get());
+
+        // The left component shouldn't see the mouse of player 2
+        application.fire_mouse_enter_event(MouseEnterEvent::new(player1, left));
+        application.fire_mouse_enter_event(MouseEnterEvent::new(player2, left));
+        application.render(&renderer, true);
+        assert_eq!(vec![player1], *left_mouses.borrow());
+        assert!(right_mouses.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_mouse_scroll() {
+        struct ScrollComponent {
+            scroll_log: Rc<RefCell<Vec<MouseScrollEvent>>>,
+        }
+
+        impl Component for ScrollComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_scroll();
             }
 
-            fn on_mouse_press(&mut self, _event: MousePressEvent, _buddy: &mut dyn ComponentBuddy) {
-                self.press_counter.set(self.press_counter.get() + 1);
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 0.5, 1.0)),
+                    filter_mouse_actions: true,
+                })
             }
 
-            fn on_mouse_release(
-                &mut self,
-                _event: MouseReleaseEvent,
-                _buddy: &mut dyn ComponentBuddy,
-            ) {
-                self.release_counter.set(self.release_counter.get() + 1);
+            fn on_mouse_scroll(&mut self, event: MouseScrollEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.scroll_log.borrow_mut().push(event);
             }
         }
 
-        let subscribe1 = Rc::new(Cell::new(false));
-        let subscribe2 = Rc::new(Cell::new(false));
-        let unsubscribe1 = Rc::new(Cell::new(false));
-        let unsubscribe2 = Rc::new(Cell::new(false));
-
-        let click_count1 = Rc::new(Cell::new(0));
-        let click_count2 = Rc::new(Cell::new(0));
-        let click_out_count1 = Rc::new(Cell::new(0));
-        let click_out_count2 = Rc::new(Cell::new(0));
-        let press_count1 = Rc::new(Cell::new(0));
-        let press_count2 = Rc::new(Cell::new(0));
-        let release_count1 = Rc::new(Cell::new(0));
-        let release_count2 = Rc::new(Cell::new(0));
-
-        let buddy = root_buddy();
-        let region = RenderRegion::between(0, 10, 20, 30);
+        let scroll_log = Rc::new(RefCell::new(Vec::new()));
         let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(SubscribingComponent {
-                should_subscribe: Rc::clone(&subscribe1),
-                should_unsubscribe: Rc::clone(&unsubscribe1),
-                click_counter: Rc::clone(&click_count1),
-                click_out_counter: Rc::clone(&click_out_count1),
-                press_counter: Rc::clone(&press_count1),
-                release_counter: Rc::clone(&release_count1),
-            }),
-            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
-        );
-        menu.add_component(
-            Box::new(SubscribingComponent {
-                should_subscribe: Rc::clone(&subscribe2),
-                should_unsubscribe: Rc::clone(&unsubscribe2),
-                click_counter: Rc::clone(&click_count2),
-                click_out_counter: Rc::clone(&click_out_count2),
-                press_counter: Rc::clone(&press_count2),
-                release_counter: Rc::clone(&release_count2),
+            Box::new(ScrollComponent {
+                scroll_log: Rc::clone(&scroll_log),
             }),
-            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+            ComponentDomain::between(0.2, 0.2, 0.6, 0.6),
         );
+        let mut application = Application::new(Box::new(menu));
 
-        let menu_cell = Rc::new(RefCell::new(menu));
-        let buddy_cell = Rc::new(RefCell::new(buddy));
-        let do_render = || {
-            let mut menu = menu_cell.borrow_mut();
-            let mut buddy = buddy_cell.borrow_mut();
-            menu.render(&test_renderer(region), &mut *buddy, true)
-                .unwrap();
-        };
+        let mouse = Mouse::new(0);
+        let scroll = |x: f32, y: f32| MouseScrollEvent::new(mouse, Point::new(x, y), 0.5, -2.0);
 
-        let fire_click = || {
-            let mut menu = menu_cell.borrow_mut();
-            let mut buddy = buddy_cell.borrow_mut();
-            menu.on_mouse_click(
-                MouseClickEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary()),
-                &mut *buddy,
-            );
-            menu.on_mouse_click(
-                MouseClickEvent::new(Mouse::new(0), Point::new(0.8, 0.8), MouseButton::primary()),
-                &mut *buddy,
-            );
-        };
-        let fire_click_out = || {
-            let mut menu = menu_cell.borrow_mut();
-            let mut buddy = buddy_cell.borrow_mut();
-            menu.on_mouse_click_out(
-                MouseClickOutEvent::new(Mouse::new(0), MouseButton::primary()),
-                &mut *buddy,
-            );
-        };
-        let fire_press = || {
-            let mut menu = menu_cell.borrow_mut();
-            let mut buddy = buddy_cell.borrow_mut();
-            menu.on_mouse_press(
-                MousePressEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary()),
-                &mut *buddy,
-            );
-            menu.on_mouse_press(
-                MousePressEvent::new(Mouse::new(0), Point::new(0.8, 0.8), MouseButton::primary()),
-                &mut *buddy,
-            );
-        };
-        let fire_release = || {
-            let mut menu = menu_cell.borrow_mut();
-            let mut buddy = buddy_cell.borrow_mut();
-            menu.on_mouse_release(
-                MouseReleaseEvent::new(Mouse::new(0), Point::new(0.2, 0.2), MouseButton::primary()),
-                &mut *buddy,
-            );
-            menu.on_mouse_release(
-                MouseReleaseEvent::new(Mouse::new(0), Point::new(0.8, 0.8), MouseButton::primary()),
-                &mut *buddy,
-            );
-        };
+        // Scrolling shouldn't have effect before the first render
+        application.fire_mouse_scroll_event(scroll(0.3, 0.3));
+        assert!(scroll_log.borrow().is_empty());
 
-        let check_values = |click1: u8,
-                            click_out1: u8,
-                            press1: u8,
-                            release1: u8,
-                            click2: u8,
-                            click_out2: u8,
-                            press2: u8,
-                            release2: u8| {
-            assert_eq!(click1, click_count1.get());
-            assert_eq!(click_out1, click_out_count1.get());
-            assert_eq!(press1, press_count1.get());
-            assert_eq!(release1, release_count1.get());
-            assert_eq!(click2, click_count2.get());
-            assert_eq!(click_out2, click_out_count2.get());
-            assert_eq!(press2, press_count2.get());
-            assert_eq!(release2, release_count2.get());
-        };
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
 
-        // No subscriptions yet, so these events should be ignored
-        do_render();
-        fire_click();
-        fire_click_out();
-        check_values(0, 0, 0, 0, 0, 0, 0, 0);
+        // Scrolling outside the domain or the drawn region should be ignored
+        application.fire_mouse_scroll_event(scroll(0.1, 0.3));
+        application.fire_mouse_scroll_event(scroll(0.5, 0.3));
+        assert!(scroll_log.borrow().is_empty());
+
+        // But scrolling inside the drawn region should be propagated
+        application.fire_mouse_scroll_event(scroll(0.3, 0.4));
+        let scroll_log = scroll_log.borrow();
+        assert_eq!(1, scroll_log.len());
+        let event = scroll_log[0];
+        assert_eq!(mouse, event.get_mouse());
+        assert!(Point::new(0.25, 0.5).nearly_equal(event.get_point()));
+        assert_eq!(0.5, event.get_delta_x());
+        assert_eq!(-2.0, event.get_delta_y());
+    }
 
-        // Lets subscribe the first component
-        subscribe1.set(true);
-        do_render();
-        fire_click();
-        check_values(1, 1, 0, 0, 0, 0, 0, 0);
+    #[test]
+    fn test_accessible_text() {
+        struct IconComponent {
+            label: &'static str,
+        }
 
-        // Now the second one as well
-        subscribe2.set(true);
-        do_render();
-        fire_click_out();
-        fire_press();
-        check_values(1, 2, 1, 0, 0, 1, 1, 0);
+        impl Component for IconComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.set_accessible_label(Some(self.label.to_string()));
+                buddy.set_accessible_description(Some(format!("The {} icon", self.label)));
+            }
 
-        // Nah, let's cancel the subscription for the second one
-        unsubscribe2.set(true);
-        do_render();
-        fire_click();
-        fire_release();
-        check_values(2, 3, 1, 1, 0, 1, 1, 0);
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+        }
 
-        // This is not fair... lets cancel the first one as well
-        unsubscribe1.set(true);
-        do_render();
-        fire_click_out();
-        fire_release();
-        fire_press();
-        check_values(2, 3, 1, 1, 0, 1, 1, 0);
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(IconComponent { label: "Settings" }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
 
-        // Lets give the second one a comeback
-        subscribe2.set(true);
-        do_render();
-        fire_click();
-        fire_click_out();
-        fire_press();
-        fire_release();
-        check_values(2, 3, 1, 1, 1, 3, 2, 1);
+        let mut outer_menu = SimpleFlatMenu::new(None);
+        outer_menu.add_component(Box::new(inner_menu), ComponentDomain::between(0.0, 0.0, 0.5, 1.0));
+        outer_menu.add_component(
+            Box::new(IconComponent { label: "Close" }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
 
-        // Let's stop
-        unsubscribe2.set(true);
-        do_render();
-        fire_click();
-        fire_click_out();
-        check_values(2, 3, 1, 1, 1, 3, 2, 1);
-    }
+        let mut application = Application::new(Box::new(outer_menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
 
-    struct MouseMotionComponent {
-        should_filter_mouse_actions: Rc<Cell<bool>>,
-        mouse_move_log: Rc<RefCell<Vec<MouseMoveEvent>>>,
-        mouse_enter_log: Rc<RefCell<Vec<MouseEnterEvent>>>,
-        mouse_leave_log: Rc<RefCell<Vec<MouseLeaveEvent>>>,
+        let settings = Some(AccessibleText::new(
+            Some("Settings".to_string()),
+            Some("The Settings icon".to_string()),
+        ));
+        assert_eq!(settings, application.get_accessible_text_at(Point::new(0.1, 0.5)));
+        assert_eq!(None, application.get_accessible_text_at(Point::new(0.4, 0.5)));
+        assert_eq!(None, application.get_accessible_text_at(Point::new(0.7, 0.2)));
+        assert_eq!(
+            Some("Close"),
+            application.get_accessible_text_at(Point::new(0.7, 0.7)).unwrap().get_label()
+        );
     }
 
-    impl Component for MouseMotionComponent {
-        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-            buddy.subscribe_mouse_move();
-            buddy.subscribe_mouse_enter();
-            buddy.subscribe_mouse_leave();
+    #[test]
+    fn test_cursor() {
+        struct CursorComponent {
+            cursor: Option<CursorIcon>,
         }
 
-        fn render(
-            &mut self,
-            _renderer: &Renderer,
-            _buddy: &mut dyn ComponentBuddy,
-            _force: bool,
-        ) -> RenderResult {
-            Ok(RenderResultStruct {
-                filter_mouse_actions: self.should_filter_mouse_actions.get(),
-                drawn_region: Box::new(RectangularDrawnRegion::new(0.2, 0.2, 0.8, 0.8)),
-            })
-        }
+        impl Component for CursorComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                if let Some(cursor) = self.cursor {
+                    buddy.set_cursor(cursor);
+                }
+                buddy.subscribe_mouse_click();
+            }
 
-        fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
-            let mut move_log = self.mouse_move_log.borrow_mut();
-            move_log.push(event);
-        }
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
 
-        fn on_mouse_enter(&mut self, event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
-            let mut enter_log = self.mouse_enter_log.borrow_mut();
-            enter_log.push(event);
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                buddy.set_cursor(CursorIcon::ResizeHorizontal);
+            }
         }
 
-        fn on_mouse_leave(&mut self, event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
-            let mut leave_log = self.mouse_leave_log.borrow_mut();
-            leave_log.push(event);
-        }
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(CursorComponent { cursor: Some(CursorIcon::Text) }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        inner_menu.add_component(
+            Box::new(CursorComponent { cursor: None }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut outer_menu = SimpleFlatMenu::new(None);
+        outer_menu.add_component(
+            Box::new(CursorComponent { cursor: Some(CursorIcon::Hand) }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+        let inner_domain = ComponentDomain::between(0.0, 0.0, 0.5, 1.0);
+        outer_menu.add_component(Box::new(inner_menu), inner_domain);
+
+        let mut application = Application::new(Box::new(outer_menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+
+        assert_eq!(CursorIcon::Text, application.get_cursor_at(Point::new(0.1, 0.5)));
+        assert_eq!(CursorIcon::Arrow, application.get_cursor_at(Point::new(0.4, 0.5)));
+        assert_eq!(CursorIcon::Arrow, application.get_cursor_at(Point::new(0.7, 0.2)));
+        assert_eq!(CursorIcon::Hand, application.get_cursor_at(Point::new(0.7, 0.7)));
+
+        // The cursor of a mouse should follow its position
+        let mouse = Mouse::new(0);
+        assert_eq!(CursorIcon::Arrow, application.get_cursor(mouse));
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.1, 0.5)));
+        assert_eq!(CursorIcon::Text, application.get_cursor(mouse));
+        let move_event = MouseMoveEvent::new(mouse, Point::new(0.1, 0.5), Point::new(0.7, 0.7));
+        application.fire_mouse_move_event(move_event);
+        assert_eq!(CursorIcon::Hand, application.get_cursor(mouse));
+
+        // Components can change their cursor while handling events
+        let click_event = MouseClickEvent::new(mouse, Point::new(0.7, 0.7), MouseButton::primary());
+        application.fire_mouse_click_event(click_event);
+        assert_eq!(CursorIcon::ResizeHorizontal, application.get_cursor(mouse));
+
+        application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, Point::new(0.7, 0.7)));
+        assert_eq!(CursorIcon::Arrow, application.get_cursor(mouse));
     }
 
     #[test]
-    fn test_mouse_enter_and_leave() {
-        let enter_log1 = Rc::new(RefCell::new(Vec::new()));
-        let leave_log1 = Rc::new(RefCell::new(Vec::new()));
-        let enter_log2 = Rc::new(RefCell::new(Vec::new()));
-        let leave_log2 = Rc::new(RefCell::new(Vec::new()));
+    fn test_window_frame_area() {
+        struct FrameComponent {
+            area: Option<WindowFrameArea>,
+        }
+
+        impl Component for FrameComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                if let Some(area) = self.area {
+                    buddy.set_window_frame_area(area);
+                }
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+        }
 
-        let component1 = MouseMotionComponent {
-            should_filter_mouse_actions: Rc::new(Cell::new(true)),
-            mouse_move_log: Rc::new(RefCell::new(Vec::new())),
-            mouse_enter_log: Rc::clone(&enter_log1),
-            mouse_leave_log: Rc::clone(&leave_log1),
-        };
-        let component2 = MouseMotionComponent {
-            should_filter_mouse_actions: Rc::new(Cell::new(true)),
-            mouse_move_log: Rc::new(RefCell::new(Vec::new())),
-            mouse_enter_log: Rc::clone(&enter_log2),
-            mouse_leave_log: Rc::clone(&leave_log2),
-        };
+        // A custom title bar with a close button
+        let mut title_bar = SimpleFlatMenu::new(None);
+        title_bar.add_component(
+            Box::new(FrameComponent { area: Some(WindowFrameArea::TitleBar) }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        title_bar.add_component(
+            Box::new(FrameComponent { area: Some(WindowFrameArea::Content) }),
+            ComponentDomain::between(0.9, 0.0, 1.0, 1.0),
+        );
 
-        let mut buddy = root_buddy();
         let mut menu = SimpleFlatMenu::new(None);
-        menu.on_attach(&mut buddy);
         menu.add_component(
-            Box::new(component1),
-            ComponentDomain::between(0.1, 0.1, 0.4, 0.9),
+            Box::new(FrameComponent { area: None }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 0.9),
         );
+        menu.add_component(Box::new(title_bar), ComponentDomain::between(0.0, 0.9, 1.0, 1.0));
+        let corner = WindowFrameArea::Resize(WindowEdge::BottomRight);
         menu.add_component(
-            Box::new(component2),
-            ComponentDomain::between(0.6, 0.1, 0.9, 0.9),
+            Box::new(FrameComponent { area: Some(corner) }),
+            ComponentDomain::between(0.95, 0.0, 1.0, 0.05),
         );
 
-        let miss_enter_event = MouseEnterEvent::new(Mouse::new(0), Point::new(0.5, 0.5));
-        let miss_leave_event = MouseLeaveEvent::new(Mouse::new(0), Point::new(0.5, 0.5));
-        let edge_enter_event = MouseEnterEvent::new(Mouse::new(0), Point::new(0.65, 0.5));
-        let edge_leave_event = MouseLeaveEvent::new(Mouse::new(0), Point::new(0.65, 0.5));
-        let hit_enter_event = MouseEnterEvent::new(Mouse::new(0), Point::new(0.75, 0.5));
-        let hit_leave_event = MouseLeaveEvent::new(Mouse::new(0), Point::new(0.75, 0.5));
-        let render_region = RenderRegion::between(1, 2, 3, 4);
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
 
-        // Nothing should happen before the first render
-        menu.on_mouse_enter(hit_enter_event, &mut buddy);
-        menu.on_mouse_leave(hit_leave_event, &mut buddy);
+        let area_at = |x: f32, y: f32| application.get_window_frame_area_at(Point::new(x, y));
+        assert_eq!(WindowFrameArea::TitleBar, area_at(0.5, 0.95));
+        assert_eq!(WindowFrameArea::Content, area_at(0.95, 0.95));
+        assert_eq!(WindowFrameArea::Content, area_at(0.5, 0.5));
+        assert_eq!(corner, area_at(0.98, 0.02));
+
+        // Only presses of the primary button in the title bar should request a window drag
+        let mouse = Mouse::new(0);
+        let mut press = |x: f32, y: f32, button: MouseButton| {
+            let point = Point::new(x, y);
+            application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+            application.fire_mouse_press_event(MousePressEvent::new(mouse, point, button));
+            application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, point, button));
+            application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, point));
+            application.take_window_drag_request()
+        };
+        assert_eq!(None, press(0.5, 0.5, MouseButton::primary()));
+        assert_eq!(None, press(0.95, 0.95, MouseButton::primary()));
+        assert_eq!(None, press(0.5, 0.95, MouseButton::secondary()));
+        assert_eq!(Some(WindowFrameArea::TitleBar), press(0.5, 0.95, MouseButton::primary()));
+        assert_eq!(Some(corner), press(0.98, 0.02, MouseButton::primary()));
+    }
 
-        // So let's render
-        menu.render(&test_renderer(render_region), &mut buddy, false)
-            .unwrap();
+    #[test]
+    fn test_pointer_lock() {
+        struct ViewportComponent {
+            name: &'static str,
+            log: Rc<RefCell<Vec<String>>>,
+        }
 
-        // Due to the mouse filtering, the edge event shouldn't trigger any reaction either
-        menu.on_mouse_enter(edge_enter_event, &mut buddy);
-        menu.on_mouse_leave(edge_leave_event, &mut buddy);
+        impl Component for ViewportComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_click();
+            }
 
-        // But, the hit events should have effect
-        menu.on_mouse_enter(hit_enter_event, &mut buddy);
-        menu.on_mouse_leave(hit_leave_event, &mut buddy);
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
 
-        // The miss events shouldn't have any effect anyway
-        menu.on_mouse_enter(miss_enter_event, &mut buddy);
-        menu.on_mouse_leave(miss_leave_event, &mut buddy);
+            fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+                buddy.request_pointer_lock(event.get_mouse());
+            }
 
-        let enter_log1 = enter_log1.borrow();
-        assert!(enter_log1.is_empty());
-        let leave_log1 = leave_log1.borrow();
-        assert!(leave_log1.is_empty());
+            fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                buddy.release_pointer_lock(event.get_mouse());
+            }
 
-        let enter_log2 = enter_log2.borrow();
-        assert_eq!(1, enter_log2.len());
-        assert!(enter_log2[0]
-            .get_entrance_point()
-            .nearly_equal(Point::new(0.5, 0.5)));
+            fn on_relative_mouse_move(
+                &mut self,
+                event: RelativeMouseMoveEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                let delta = (event.get_delta_x(), event.get_delta_y());
+                self.log.borrow_mut().push(format!("{} {:?}", self.name, delta));
+            }
+        }
 
-        let leave_log2 = leave_log2.borrow();
-        assert_eq!(1, leave_log2.len());
-        assert!(leave_log2[0]
-            .get_exit_point()
-            .nearly_equal(Point::new(0.5, 0.5)));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(ViewportComponent { name: "inner", log: Rc::clone(&log) }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(Box::new(inner_menu), ComponentDomain::between(0.0, 0.0, 0.5, 1.0));
+        menu.add_component(
+            Box::new(ViewportComponent { name: "outer", log: Rc::clone(&log) }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let relative_move = |application: &mut Application| {
+            application.fire_relative_mouse_move_event(RelativeMouseMoveEvent::new(
+                mouse, 0.125, -0.25,
+            ));
+        };
+        let press = |application: &mut Application, x: f32, y: f32| {
+            let event = MousePressEvent::new(mouse, Point::new(x, y), MouseButton::primary());
+            application.fire_mouse_press_event(event);
+        };
+
+        // Without pointer lock, nobody should receive relative mouse moves
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.1, 0.1)));
+        relative_move(&mut application);
+        assert!(!application.is_pointer_locked(mouse));
+
+        // The deltas should be relative to the size of the component that locked the pointer
+        press(&mut application, 0.1, 0.1);
+        assert!(application.is_pointer_locked(mouse));
+        relative_move(&mut application);
+
+        // Only 1 component can lock the pointer
+        press(&mut application, 0.7, 0.1);
+        relative_move(&mut application);
+
+        let click_event = MouseClickEvent::new(mouse, Point::new(0.7, 0.1), MouseButton::primary());
+        application.fire_mouse_click_event(click_event);
+        assert!(!application.is_pointer_locked(mouse));
+        relative_move(&mut application);
+
+        assert_eq!(vec!["inner (0.5, -0.5)", "outer (0.25, -0.25)"], *log.borrow());
     }
 
     #[test]
-    fn test_mouse_move() {
-        let move_logs = vec![
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-        ];
-        let enter_logs = vec![
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-        ];
-        let leave_logs = vec![
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-            Rc::new(RefCell::new(Vec::new())),
-        ];
+    fn test_gestures() {
+        struct GestureComponent {
+            log: Rc<RefCell<Vec<String>>>,
+            subscribe: bool,
+        }
 
-        let mut menu = SimpleFlatMenu::new(None);
-        let mut buddy = root_buddy();
-        menu.on_attach(&mut buddy);
+        impl Component for GestureComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                if self.subscribe {
+                    buddy.subscribe_gestures();
+                }
+            }
 
-        // The outer bottom-left component
-        menu.add_component(
-            Box::new(MouseMotionComponent {
-                should_filter_mouse_actions: Rc::new(Cell::new(true)),
-                mouse_move_log: Rc::clone(&move_logs[0]),
-                mouse_enter_log: Rc::clone(&enter_logs[0]),
-                mouse_leave_log: Rc::clone(&leave_logs[0]),
-            }),
-            ComponentDomain::between(0.0, 0.0, 0.25, 0.25),
-        );
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
 
-        // The inner bottom-left component
-        menu.add_component(
-            Box::new(MouseMotionComponent {
-                should_filter_mouse_actions: Rc::new(Cell::new(false)),
-                mouse_move_log: Rc::clone(&move_logs[1]),
-                mouse_enter_log: Rc::clone(&enter_logs[1]),
-                mouse_leave_log: Rc::clone(&leave_logs[1]),
-            }),
-            ComponentDomain::between(0.25, 0.25, 0.5, 0.5),
-        );
+            fn on_pinch(&mut self, event: PinchEvent, _buddy: &mut dyn ComponentBuddy) {
+                let center = event.get_center();
+                self.log.borrow_mut().push(format!(
+                    "pinch ({}, {}) {:.3}", center.get_x(), center.get_y(), event.get_scale()
+                ));
+            }
 
-        // The inner top-right component
-        menu.add_component(
-            Box::new(MouseMotionComponent {
-                should_filter_mouse_actions: Rc::new(Cell::new(true)),
-                mouse_move_log: Rc::clone(&move_logs[2]),
-                mouse_enter_log: Rc::clone(&enter_logs[2]),
-                mouse_leave_log: Rc::clone(&leave_logs[2]),
-            }),
-            ComponentDomain::between(0.5, 0.5, 0.75, 0.75),
-        );
+            fn on_rotate_gesture(
+                &mut self,
+                event: RotateGestureEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                let center = event.get_center();
+                self.log.borrow_mut().push(format!(
+                    "rotate ({}, {}) {:.3}", center.get_x(), center.get_y(), event.get_angle()
+                ));
+            }
 
-        // The outer top-right component
+            fn on_swipe(&mut self, event: SwipeEvent, _buddy: &mut dyn ComponentBuddy) {
+                let start = event.get_start_point();
+                let end = event.get_end_point();
+                self.log.borrow_mut().push(format!(
+                    "swipe {:?} ({}, {}) -> ({}, {}) {:.3}",
+                    event.get_direction(),
+                    start.get_x(),
+                    start.get_y(),
+                    end.get_x(),
+                    end.get_y(),
+                    event.get_velocity_x()
+                ));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(MouseMotionComponent {
-                should_filter_mouse_actions: Rc::new(Cell::new(true)),
-                mouse_move_log: Rc::clone(&move_logs[4]),
-                mouse_enter_log: Rc::clone(&enter_logs[4]),
-                mouse_leave_log: Rc::clone(&leave_logs[4]),
-            }),
-            ComponentDomain::between(0.75, 0.75, 1.0, 1.0),
+            Box::new(GestureComponent { log: Rc::clone(&log), subscribe: false }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
         );
-
-        // This component should be missed entirely
         menu.add_component(
-            Box::new(MouseMotionComponent {
-                should_filter_mouse_actions: Rc::new(Cell::new(false)),
-                mouse_move_log: Rc::clone(&move_logs[3]),
-                mouse_enter_log: Rc::clone(&enter_logs[3]),
-                mouse_leave_log: Rc::clone(&leave_logs[3]),
-            }),
-            ComponentDomain::between(0.5, 0.0, 0.75, 0.25),
+            Box::new(GestureComponent { log: Rc::clone(&log), subscribe: true }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
         );
 
-        menu.render(
-            &test_renderer(RenderRegion::between(0, 0, 20, 30)),
-            &mut buddy,
-            false,
-        )
-        .unwrap();
-
-        let mouse = Mouse::new(3);
-        let entrance_x = 0.25 * 0.25;
-        let entrance_y = 0.25 * 0.25;
-        let entrance = Point::new(entrance_x, entrance_y);
-        let exit_x = 1.0 - entrance_x;
-        let exit_y = 1.0 - entrance_y;
-        let exit = Point::new(exit_x, exit_y);
-
-        let enter_event = MouseEnterEvent::new(mouse, entrance);
-        let move_event = MouseMoveEvent::new(mouse, entrance, exit);
-        let leave_event = MouseLeaveEvent::new(mouse, exit);
-        menu.on_mouse_enter(enter_event, &mut buddy);
-        menu.on_mouse_move(move_event, &mut buddy);
-        menu.on_mouse_leave(leave_event, &mut buddy);
-
-        // Time to check the results...
+        // The window is twice as wide as high
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 20, 10)), false);
 
-        // But first some helper functions
-        let eq_mouse_move =
-            |enter_x: f32, enter_y: f32, exit_x: f32, exit_y: f32, event: &MouseMoveEvent| {
-                assert_eq!(mouse, event.get_mouse());
-                assert!(Point::new(enter_x, enter_y).nearly_equal(event.get_from()));
-                assert!(Point::new(exit_x, exit_y).nearly_equal(event.get_to()));
-            };
-        let eq_mouse_enter = |enter_x: f32, enter_y: f32, event: &MouseEnterEvent| {
-            assert_eq!(mouse, event.get_mouse());
-            assert!(Point::new(enter_x, enter_y).nearly_equal(event.get_entrance_point()));
+        let touch = |application: &mut Application, mouse: Mouse, point: Point| {
+            application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+            let button = MouseButton::primary();
+            application.fire_mouse_press_event(MousePressEvent::new(mouse, point, button));
         };
-        let eq_mouse_leave = |exit_x: f32, exit_y: f32, event: &MouseLeaveEvent| {
-            assert_eq!(mouse, event.get_mouse());
-            assert!(Point::new(exit_x, exit_y).nearly_equal(event.get_exit_point()));
+        let drag = |application: &mut Application, mouse: Mouse, from: Point, to: Point| {
+            application.fire_mouse_move_event(MouseMoveEvent::new(mouse, from, to));
         };
-        let check_log = |index: usize, enter_x: f32, enter_y: f32, exit_x: f32, exit_y: f32| {
-            let move_log = move_logs[index].borrow();
-            assert_eq!(1, move_log.len());
-            eq_mouse_move(enter_x, enter_y, exit_x, exit_y, &move_log[0]);
-            let enter_log = enter_logs[index].borrow();
-            assert_eq!(1, enter_log.len());
-            eq_mouse_enter(enter_x, enter_y, &enter_log[0]);
-            let leave_log = leave_logs[index].borrow();
-            assert_eq!(1, leave_log.len());
-            eq_mouse_leave(exit_x, exit_y, &leave_log[0]);
+        let lift = |application: &mut Application, mouse: Mouse, point: Point| {
+            let button = MouseButton::primary();
+            application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, point, button));
+            application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, point));
         };
 
-        // Finally check the actual results
-        check_log(0, 0.25, 0.25, 0.8, 0.8);
-        check_log(1, 0.0, 0.0, 1.0, 1.0);
-        check_log(2, 0.2, 0.2, 0.8, 0.8);
-        check_log(4, 0.2, 0.2, 0.75, 0.75);
-
-        // And check that the out-of-line component didn't receive any events
-        let move_log = move_logs[3].borrow();
-        assert!(move_log.is_empty());
-        let enter_log = enter_logs[3].borrow();
-        assert!(enter_log.is_empty());
-        let leave_log = leave_logs[3].borrow();
-        assert!(leave_log.is_empty());
+        // Pinch and rotate with 2 fingers on the right component
+        let first = Mouse::new(1);
+        let second = Mouse::new(2);
+        touch(&mut application, first, Point::new(0.625, 0.5));
+        touch(&mut application, second, Point::new(0.875, 0.5));
+        drag(&mut application, second, Point::new(0.875, 0.5), Point::new(0.75, 0.5));
+        application.advance_time(Duration::from_millis(100));
+        drag(&mut application, second, Point::new(0.75, 0.5), Point::new(0.625, 0.75));
+        lift(&mut application, second, Point::new(0.625, 0.75));
+        lift(&mut application, first, Point::new(0.625, 0.5));
+
+        // Swipe from the right component to the left
+        let third = Mouse::new(3);
+        touch(&mut application, third, Point::new(0.75, 0.25));
+        application.advance_time(Duration::from_millis(50));
+        drag(&mut application, third, Point::new(0.75, 0.25), Point::new(0.25, 0.25));
+        lift(&mut application, third, Point::new(0.25, 0.25));
+
+        // The left component didn't subscribe, so it shouldn't receive swipes
+        touch(&mut application, third, Point::new(0.25, 0.5));
+        application.advance_time(Duration::from_millis(50));
+        drag(&mut application, third, Point::new(0.25, 0.5), Point::new(0.75, 0.5));
+        lift(&mut application, third, Point::new(0.75, 0.5));
+
+        assert_eq!(
+            vec![
+                "pinch (0.375, 0.5) 0.500",
+                "rotate (0.25, 0.625) 1.571",
+                "swipe Left (0.5, 0.25) -> (-0.5, 0.25) -20.000",
+            ],
+            *log.borrow()
+        );
     }
 
     #[test]
-    fn test_mouse_move_subscriptions() {
-        struct MouseMoveSubscribeComponent {
-            subscribe_mouse_move: Rc<Cell<bool>>,
-            subscribe_mouse_enter: Rc<Cell<bool>>,
-            subscribe_mouse_leave: Rc<Cell<bool>>,
+    fn test_pointer_details() {
+        struct DrawingComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
 
-            mouse_move_log: Rc<RefCell<Vec<MouseMoveEvent>>>,
-            mouse_enter_log: Rc<RefCell<Vec<MouseEnterEvent>>>,
-            mouse_leave_log: Rc<RefCell<Vec<MouseLeaveEvent>>>,
+        impl DrawingComponent {
+            fn log(&self, kind: &str, point: Point, details: PointerDetails) {
+                self.log.borrow_mut().push(format!(
+                    "{} ({}, {}) {:?} {:?}",
+                    kind,
+                    point.get_x(),
+                    point.get_y(),
+                    details.get_tool_type(),
+                    details.get_pressure()
+                ));
+            }
         }
 
-        impl Component for MouseMoveSubscribeComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+        impl Component for DrawingComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_move();
+                buddy.subscribe_mouse_release();
+            }
 
             fn render(
                 &mut self,
                 _renderer: &Renderer,
-                buddy: &mut dyn ComponentBuddy,
+                _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                if self.subscribe_mouse_move.get() {
-                    buddy.subscribe_mouse_move();
-                } else {
-                    buddy.unsubscribe_mouse_move();
-                }
-                if self.subscribe_mouse_enter.get() {
-                    buddy.subscribe_mouse_enter();
-                } else {
-                    buddy.unsubscribe_mouse_enter();
-                }
-                if self.subscribe_mouse_leave.get() {
-                    buddy.subscribe_mouse_leave();
-                } else {
-                    buddy.unsubscribe_mouse_leave();
-                }
                 entire_render_result()
             }
 
-            fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
-                let mut move_log = self.mouse_move_log.borrow_mut();
-                move_log.push(event);
+            fn on_mouse_press(&mut self, event: MousePressEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log("press", event.get_point(), event.get_details());
             }
 
-            fn on_mouse_enter(&mut self, event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
-                let mut enter_log = self.mouse_enter_log.borrow_mut();
-                enter_log.push(event);
+            fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log("move", event.get_to(), event.get_details());
             }
 
-            fn on_mouse_leave(&mut self, event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
-                let mut leave_log = self.mouse_leave_log.borrow_mut();
-                leave_log.push(event);
+            fn on_mouse_release(
+                &mut self,
+                event: MouseReleaseEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log("release", event.get_point(), event.get_details());
             }
         }
 
-        let mouse_move_log = Rc::new(RefCell::new(Vec::new()));
-        let mouse_enter_log = Rc::new(RefCell::new(Vec::new()));
-        let mouse_leave_log = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(DrawingComponent { log: Rc::clone(&log) }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 20, 10)), false);
+
+        let pen = |pressure| PointerDetails::new(Some(PointerToolType::Pen), Some(pressure), None);
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let point = Point::new(0.75, 0.5);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+        application.fire_mouse_press_event(MousePressEvent::with_details(
+            mouse, point, button, pen(0.25)
+        ));
 
-        let sub_mouse_move = Rc::new(Cell::new(false));
-        let sub_mouse_enter = Rc::new(Cell::new(false));
-        let sub_mouse_leave = Rc::new(Cell::new(false));
+        // The details should also be preserved when the menu splits the movement
+        let next_point = Point::new(0.25, 0.5);
+        application.fire_mouse_move_event(MouseMoveEvent::with_details(
+            mouse, point, next_point, pen(0.5)
+        ));
+        application.fire_mouse_move_event(MouseMoveEvent::with_details(
+            mouse, next_point, point, pen(0.75)
+        ));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, point, button));
+
+        assert_eq!(
+            vec![
+                "press (0.5, 0.5) Some(Pen) Some(0.25)",
+                "move (0, 0.5) Some(Pen) Some(0.5)",
+                "move (0.5, 0.5) Some(Pen) Some(0.75)",
+                "release (0.5, 0.5) None None",
+            ],
+            *log.borrow()
+        );
+    }
 
-        let component = MouseMoveSubscribeComponent {
-            mouse_move_log: Rc::clone(&mouse_move_log),
-            mouse_enter_log: Rc::clone(&mouse_enter_log),
-            mouse_leave_log: Rc::clone(&mouse_leave_log),
+    #[test]
+    fn test_tick() {
+        struct TickComponent {
+            remaining_ticks: u8,
+            total_time: Rc<Cell<Duration>>,
+        }
 
-            subscribe_mouse_move: Rc::clone(&sub_mouse_move),
-            subscribe_mouse_enter: Rc::clone(&sub_mouse_enter),
-            subscribe_mouse_leave: Rc::clone(&sub_mouse_leave),
-        };
+        impl Component for TickComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_tick();
+            }
 
-        let mut menu = SimpleFlatMenu::new(None);
-        let mut buddy = root_buddy();
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
 
-        menu.on_attach(&mut buddy);
+            fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+                self.total_time.set(self.total_time.get() + event.get_delta_time());
+                self.remaining_ticks -= 1;
+                if self.remaining_ticks == 0 {
+                    buddy.unsubscribe_tick();
+                }
+            }
+        }
+
+        let total_time1 = Rc::new(Cell::new(Duration::from_secs(0)));
+        let total_time2 = Rc::new(Cell::new(Duration::from_secs(0)));
+
+        let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(component),
+            Box::new(TickComponent {
+                remaining_ticks: 1,
+                total_time: Rc::clone(&total_time1),
+            }),
             ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
         );
+        menu.add_component(
+            Box::new(TickComponent {
+                remaining_ticks: 2,
+                total_time: Rc::clone(&total_time2),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
 
-        let mut try_combination = |mouse_move: bool, mouse_enter: bool, mouse_leave: bool| {
-            sub_mouse_move.set(mouse_move);
-            sub_mouse_enter.set(mouse_enter);
-            sub_mouse_leave.set(mouse_leave);
-            menu.render(
-                &test_renderer(RenderRegion::between(0, 1, 4, 7)),
-                &mut buddy,
-                true,
-            )
-            .unwrap();
-            let mouse = Mouse::new(2);
-            let original_enter_event1 = MouseEnterEvent::new(mouse, Point::new(0.1, 0.6));
-            let original_enter_event2 =
-                MouseMoveEvent::new(mouse, Point::new(0.1, 0.6), Point::new(0.1, 0.25));
-            let original_move_event =
-                MouseMoveEvent::new(mouse, Point::new(0.1, 0.25), Point::new(0.4, 0.25));
-            let original_leave_event1 =
-                MouseMoveEvent::new(mouse, Point::new(0.4, 0.25), Point::new(0.4, 0.6));
-            let original_leave_event2 = MouseLeaveEvent::new(mouse, Point::new(0.4, 0.6));
-            let transformed_enter_event1 = MouseEnterEvent::new(mouse, Point::new(0.2, 1.0));
-            let transformed_enter_event2 =
-                MouseMoveEvent::new(mouse, Point::new(0.2, 1.0), Point::new(0.2, 0.5));
-            let transformed_move_event =
-                MouseMoveEvent::new(mouse, Point::new(0.2, 0.5), Point::new(0.8, 0.5));
-            let transformed_leave_event1 =
-                MouseMoveEvent::new(mouse, Point::new(0.8, 0.5), Point::new(0.8, 1.0));
-            let transformed_leave_event2 = MouseLeaveEvent::new(mouse, Point::new(0.8, 1.0));
+        let mut buddy = root_buddy();
+        menu.on_attach(&mut buddy);
+        assert!(buddy.get_subscriptions().tick);
 
-            menu.on_mouse_enter(original_enter_event1, &mut buddy);
-            menu.on_mouse_move(original_enter_event2, &mut buddy);
-            menu.on_mouse_move(original_move_event, &mut buddy);
-            menu.on_mouse_move(original_leave_event1, &mut buddy);
-            menu.on_mouse_leave(original_leave_event2, &mut buddy);
+        let tick = TickEvent::new(Duration::from_millis(10));
+        menu.on_tick(tick, &mut buddy);
+        assert_eq!(Duration::from_millis(10), total_time1.get());
+        assert_eq!(Duration::from_millis(10), total_time2.get());
+        assert!(buddy.get_subscriptions().tick);
 
-            let mut move_log = mouse_move_log.borrow_mut();
-            let mut enter_log = mouse_enter_log.borrow_mut();
-            let mut leave_log = mouse_leave_log.borrow_mut();
+        // The first component unsubscribed during the previous tick
+        menu.on_tick(tick, &mut buddy);
+        assert_eq!(Duration::from_millis(10), total_time1.get());
+        assert_eq!(Duration::from_millis(20), total_time2.get());
 
-            if mouse_move {
-                let move_event_eq = |expected: &MouseMoveEvent, actual: &MouseMoveEvent| {
-                    assert_eq!(expected.get_mouse(), actual.get_mouse());
-                    assert!(expected.get_from().nearly_equal(actual.get_from()));
-                    assert!(expected.get_to().nearly_equal(actual.get_to()));
-                };
+        // Now that both components unsubscribed, the menu should unsubscribe as well
+        assert!(!buddy.get_subscriptions().tick);
+    }
 
-                assert_eq!(3, move_log.len());
-                move_event_eq(&transformed_enter_event2, &move_log[0]);
-                move_event_eq(&transformed_move_event, &move_log[1]);
-                move_event_eq(&transformed_leave_event1, &move_log[2]);
-            } else {
-                assert!(move_log.is_empty());
-            }
+    #[test]
+    fn test_char_type() {
+        struct TypeComponent {
+            typed_text: Rc<RefCell<String>>,
+            request_focus: bool,
+        }
 
-            if mouse_enter {
-                let enter_event_eq = |expected: &MouseEnterEvent, actual: &MouseEnterEvent| {
-                    assert_eq!(expected.get_mouse(), actual.get_mouse());
-                    assert!(expected
-                        .get_entrance_point()
-                        .nearly_equal(actual.get_entrance_point()));
-                };
+        impl Component for TypeComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_char_type().unwrap();
+                buddy.subscribe_mouse_click();
+                if self.request_focus {
+                    buddy.request_focus();
+                }
+            }
 
-                assert_eq!(1, enter_log.len());
-                enter_event_eq(&transformed_enter_event1, &enter_log[0]);
-            } else {
-                assert!(enter_log.is_empty());
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
             }
 
-            if mouse_leave {
-                let leave_event_eq = |expected: &MouseLeaveEvent, actual: &MouseLeaveEvent| {
-                    assert_eq!(expected.get_mouse(), actual.get_mouse());
-                    assert!(expected
-                        .get_exit_point()
-                        .nearly_equal(actual.get_exit_point()));
-                };
+            fn on_mouse_click(
+                &mut self,
+                _event: MouseClickEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+            }
 
-                assert_eq!(1, leave_log.len());
-                leave_event_eq(&transformed_leave_event2, &leave_log[0]);
-            } else {
-                assert!(leave_log.is_empty());
+            fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+                let mut typed_text = self.typed_text.borrow_mut();
+                typed_text.push_str(event.get_text());
+                if typed_text.len() >= 2 {
+                    buddy.unsubscribe_char_type();
+                }
             }
+        }
 
-            move_log.clear();
-            enter_log.clear();
-            leave_log.clear();
+        let typed_text1 = Rc::new(RefCell::new(String::new()));
+        let typed_text2 = Rc::new(RefCell::new(String::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(TypeComponent {
+                typed_text: Rc::clone(&typed_text1),
+                request_focus: true,
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            Box::new(TypeComponent {
+                typed_text: Rc::clone(&typed_text2),
+                request_focus: false,
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+        let click = |application: &mut Application, x: f32, y: f32| {
+            let point = Point::new(x, y);
+            let event = MouseClickEvent::new(Mouse::new(0), point, MouseButton::primary());
+            application.fire_mouse_click_event(event);
+        };
+        let type_char = |application: &mut Application, text: &str| {
+            application.fire_char_type_event(CharTypeEvent::new(text.to_string()));
         };
 
-        for _counter in 0..2 {
-            try_combination(false, false, false);
-            try_combination(false, false, true);
-            try_combination(false, true, false);
-            try_combination(false, true, true);
-            try_combination(true, false, false);
-            try_combination(true, false, true);
-            try_combination(true, true, false);
-            try_combination(true, true, true);
-        }
+        // Only the component that requested the focus should receive the event
+        type_char(&mut application, "a");
+        assert_eq!("a", *typed_text1.borrow());
+        assert_eq!("", *typed_text2.borrow());
+
+        // Clicking a component should give it the focus
+        click(&mut application, 0.75, 0.75);
+        type_char(&mut application, "é");
+        click(&mut application, 0.25, 0.25);
+        type_char(&mut application, "b");
+        type_char(&mut application, "c");
+        assert_eq!("ab", *typed_text1.borrow());
+        assert_eq!("é", *typed_text2.borrow());
+
+        // Clicking outside all components should take the focus away
+        click(&mut application, 0.75, 0.25);
+        type_char(&mut application, "d");
+        assert_eq!("é", *typed_text2.borrow());
     }
 
     #[test]
-    fn test_own_subscriptions() {
-        struct CuriousComponent {}
+    fn test_gamepad() {
+        struct GamepadComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
 
-        impl Component for CuriousComponent {
+        impl Component for GamepadComponent {
             fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-                buddy.subscribe_mouse_click();
-                buddy.subscribe_mouse_click_out();
-                buddy.subscribe_mouse_press();
-                buddy.subscribe_mouse_release();
-                buddy.subscribe_mouse_move();
-                buddy.subscribe_mouse_enter();
-                buddy.subscribe_mouse_leave();
+                buddy.subscribe_gamepad();
+                buddy.request_focus();
             }
 
             fn render(
@@ -1825,461 +5509,847 @@ mod tests {
             ) -> RenderResult {
                 entire_render_result()
             }
+
+            fn on_gamepad_button(
+                &mut self,
+                event: GamepadButtonEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                let gamepad = event.get_gamepad();
+                assert_eq!(vec![gamepad], buddy.get_gamepads());
+                assert_eq!(
+                    Some(event.is_pressed()),
+                    buddy.is_gamepad_button_down(gamepad, event.get_button())
+                );
+                let description = format!("{:?} {}", event.get_button(), event.is_pressed());
+                self.log.borrow_mut().push(description);
+            }
+
+            fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+                let state = buddy.get_gamepad_state(event.get_gamepad()).unwrap();
+                assert_eq!("Test pad", state.get_name());
+                assert_eq!(event.get_value(), state.get_axis_value(event.get_axis()));
+                let description = format!("{:?} {}", event.get_axis(), event.get_value());
+                self.log.borrow_mut().push(description);
+                buddy.unsubscribe_gamepad();
+            }
         }
 
+        let log = Rc::new(RefCell::new(Vec::new()));
         let mut menu = SimpleFlatMenu::new(None);
-        let mut buddy = root_buddy();
-        menu.on_attach(&mut buddy);
         menu.add_component(
-            Box::new(CuriousComponent {}),
-            ComponentDomain::between(0.3, 0.6, 1.0, 0.9),
+            Box::new(GamepadComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
         );
+        let mut application = Application::new(Box::new(menu));
 
-        // The menu should have subscribed to all events
-        let subs = buddy.get_subscriptions();
-        assert!(subs.mouse_click);
-        assert!(subs.mouse_click_out);
-        assert!(subs.mouse_press);
-        assert!(subs.mouse_release);
-        assert!(subs.mouse_move);
-        assert!(subs.mouse_enter);
-        assert!(subs.mouse_leave);
+        let gamepad = Gamepad::new(3);
+        let other_gamepad = Gamepad::new(4);
+        application.connect_gamepad(gamepad, "Test pad".to_string());
+
+        application.fire_gamepad_button_event(GamepadButtonEvent::new(
+            gamepad, GamepadButton::South, true
+        ));
+        // Events of gamepads that are not connected should be ignored
+        application.fire_gamepad_button_event(GamepadButtonEvent::new(
+            other_gamepad, GamepadButton::South, true
+        ));
+        application.fire_gamepad_button_event(GamepadButtonEvent::new(
+            gamepad, GamepadButton::South, false
+        ));
+        application.fire_gamepad_axis_event(GamepadAxisEvent::new(
+            gamepad, GamepadAxis::LeftStickX, -0.5
+        ));
+        // The component unsubscribed during the previous event
+        application.fire_gamepad_axis_event(GamepadAxisEvent::new(
+            gamepad, GamepadAxis::LeftStickY, 0.5
+        ));
+        assert_eq!(
+            vec!["South true", "South false", "LeftStickX -0.5"],
+            *log.borrow()
+        );
+
+        application.disconnect_gamepad(gamepad);
+        assert!(application.get_gamepads().is_empty());
     }
 
     #[test]
-    fn test_buddy_get_all_mouses() {
-        struct GetMouseComponent {
-            expected: Rc<RefCell<Vec<Mouse>>>,
-            call_counter: Rc<Cell<u8>>,
+    fn test_low_memory() {
+        struct CacheComponent {
+            pressures: Rc<RefCell<Vec<MemoryPressure>>>,
         }
 
-        impl Component for GetMouseComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+        impl Component for CacheComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_low_memory();
+            }
 
             fn render(
                 &mut self,
                 _renderer: &Renderer,
-                buddy: &mut dyn ComponentBuddy,
+                _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                let expected = self.expected.borrow();
-                assert_eq!(expected.as_ref() as &Vec<Mouse>, &buddy.get_all_mouses());
-                self.call_counter.set(self.call_counter.get() + 1);
                 entire_render_result()
             }
+
+            fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+                self.pressures.borrow_mut().push(event.get_pressure());
+                if event.get_pressure() == MemoryPressure::Critical {
+                    buddy.unsubscribe_low_memory();
+                }
+            }
         }
 
-        let expected_mouses = Rc::new(RefCell::new(Vec::new()));
-        let call_counter = Rc::new(Cell::new(0));
+        let pressures1 = Rc::new(RefCell::new(Vec::new()));
+        let pressures2 = Rc::new(RefCell::new(Vec::new()));
 
         let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(GetMouseComponent {
-                expected: Rc::clone(&expected_mouses),
-                call_counter: Rc::clone(&call_counter),
+            Box::new(CacheComponent {
+                pressures: Rc::clone(&pressures1),
             }),
-            ComponentDomain::between(0.1, 0.2, 0.3, 0.4),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
         );
+        let hidden_handle = menu.add_component(
+            Box::new(CacheComponent {
+                pressures: Rc::clone(&pressures2),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+        menu.set_component_visible(hidden_handle, false);
 
-        let mut application = Application::new(Box::new(menu));
-
-        let region = RenderRegion::with_size(1, 2, 3, 4);
-
-        // The mouses should be empty initially
-        application.render(&test_renderer(region), true);
-
-        let enter_event =
-            |mouse_id: u16| MouseEnterEvent::new(Mouse::new(mouse_id), Point::new(0.2, 0.3));
-        let leave_event =
-            |mouse_id: u16| MouseLeaveEvent::new(Mouse::new(mouse_id), Point::new(0.2, 0.3));
-        let mouse_vec = |ids: &[u16]| ids.iter().map(|id| Mouse::new(*id)).collect();
-
-        // Add the first mouse
-        application.fire_mouse_enter_event(enter_event(123));
-        expected_mouses.replace(mouse_vec(&[123]));
-        application.render(&test_renderer(region), true);
-
-        // Add the second mouse
-        application.fire_mouse_enter_event(enter_event(1));
-        expected_mouses.replace(mouse_vec(&[123, 1]));
-        application.render(&test_renderer(region), true);
-
-        // Remove the first mouse
-        application.fire_mouse_leave_event(leave_event(123));
-        expected_mouses.replace(mouse_vec(&[1]));
-        application.render(&test_renderer(region), true);
-
-        // Add the first mouse back, and add yet another mouse
-        application.fire_mouse_enter_event(enter_event(123));
-        application.fire_mouse_enter_event(enter_event(8));
-        expected_mouses.replace(mouse_vec(&[1, 123, 8]));
-        application.render(&test_renderer(region), true);
+        let mut buddy = root_buddy();
+        menu.on_attach(&mut buddy);
+        assert!(buddy.get_subscriptions().low_memory);
 
-        // Remove all mouses
-        application.fire_mouse_leave_event(leave_event(123));
-        application.fire_mouse_leave_event(leave_event(8));
-        application.fire_mouse_leave_event(leave_event(1));
-        expected_mouses.replace(mouse_vec(&[]));
-        application.render(&test_renderer(region), true);
+        // Hidden components should receive the event as well
+        menu.on_low_memory(LowMemoryEvent::new(MemoryPressure::Moderate), &mut buddy);
+        menu.on_low_memory(LowMemoryEvent::new(MemoryPressure::Critical), &mut buddy);
+        let expected = vec![MemoryPressure::Moderate, MemoryPressure::Critical];
+        assert_eq!(expected, *pressures1.borrow());
+        assert_eq!(expected, *pressures2.borrow());
 
-        assert_eq!(6, call_counter.get());
+        // Both components unsubscribed during the previous event
+        assert!(!buddy.get_subscriptions().low_memory);
     }
 
     #[test]
-    fn test_buddy_get_local_mouses_and_positions() {
-        struct LocalMouse {
-            mouse: Mouse,
-            position: Point,
-        }
-
-        struct LocalMouseCheckComponent {
-            expected_mouses: Rc<RefCell<Vec<LocalMouse>>>,
+    fn test_window_state_and_close_request() {
+        struct LifecycleComponent {
+            log: Rc<RefCell<Vec<String>>>,
+            may_close: bool,
         }
 
-        impl Component for LocalMouseCheckComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+        impl Component for LifecycleComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_window_state();
+                buddy.subscribe_close_request();
+            }
 
             fn render(
                 &mut self,
                 _renderer: &Renderer,
-                buddy: &mut dyn ComponentBuddy,
+                _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                let local_mouses = buddy.get_local_mouses();
-                let expected_mouses = self.expected_mouses.borrow();
-                assert_eq!(expected_mouses.len(), local_mouses.len());
-
-                // Test that all local mouses are present and have the right position
-                'local_outer: for mouse in &local_mouses {
-                    for entry in &*expected_mouses {
-                        if entry.mouse == *mouse {
-                            assert!(entry
-                                .position
-                                .nearly_equal(buddy.get_mouse_position(*mouse).unwrap()));
-                            continue 'local_outer;
-                        }
-                    }
-                    panic!("Expected mouse {:?}, but didn't find its entry", mouse);
-                }
+                entire_render_result()
+            }
 
-                // Test that the other mouses do not have a position
-                'all_outer: for mouse in buddy.get_all_mouses() {
-                    for local_mouse in &local_mouses {
-                        if *local_mouse == mouse {
-                            continue 'all_outer;
-                        }
-                    }
-                    assert!(buddy.get_mouse_position(mouse).is_none());
-                }
+            fn on_window_focus(
+                &mut self,
+                event: WindowFocusEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push(format!("focus {}", event.is_focused()));
+            }
 
-                // Neither should a non-existing mouse
-                assert!(buddy.get_mouse_position(Mouse::new(8347)).is_none());
+            fn on_window_minimize(
+                &mut self,
+                event: WindowMinimizeEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push(format!("minimize {}", event.is_minimized()));
+            }
 
-                entire_render_result()
+            fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+                self.log.borrow_mut().push("close".to_string());
+                if !self.may_close {
+                    // Pretend that the user saved their work after the first request
+                    self.may_close = true;
+                    buddy.unsubscribe_close_request();
+                    return false;
+                }
+                true
             }
         }
 
-        let expected_mouses1 = Rc::new(RefCell::new(Vec::new()));
-        let expected_mouses2 = Rc::new(RefCell::new(Vec::new()));
-
-        let set = |target: &Rc<RefCell<Vec<LocalMouse>>>, mut expected: Vec<LocalMouse>| {
-            let mut mouses = target.borrow_mut();
-            mouses.clear();
-            mouses.append(&mut expected);
-        };
-        let set1 = |expected: Vec<LocalMouse>| set(&expected_mouses1, expected);
-        let set2 = |expected: Vec<LocalMouse>| set(&expected_mouses2, expected);
+        let log1 = Rc::new(RefCell::new(Vec::new()));
+        let log2 = Rc::new(RefCell::new(Vec::new()));
 
         let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(LocalMouseCheckComponent {
-                expected_mouses: Rc::clone(&expected_mouses1),
+            Box::new(LifecycleComponent {
+                log: Rc::clone(&log1),
+                may_close: false,
             }),
-            ComponentDomain::between(0.2, 0.0, 0.5, 0.7),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
         );
-        menu.add_component(
-            Box::new(LocalMouseCheckComponent {
-                expected_mouses: Rc::clone(&expected_mouses2),
+        let hidden_handle = menu.add_component(
+            Box::new(LifecycleComponent {
+                log: Rc::clone(&log2),
+                may_close: true,
             }),
             ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
         );
+        menu.set_component_visible(hidden_handle, false);
 
         let mut application = Application::new(Box::new(menu));
-        let region = RenderRegion::between(10, 20, 30, 40);
-        application.render(&test_renderer(region), true);
+        application.fire_window_focus_event(WindowFocusEvent::new(false));
+        application.queue_event(InputEvent::WindowMinimize(WindowMinimizeEvent::new(true)));
+        application.update(Duration::from_millis(10));
+
+        // Hidden components should receive the events as well
+        let expected = vec!["focus false", "minimize true"];
+        assert_eq!(expected, *log1.borrow());
+        assert_eq!(expected, *log2.borrow());
+        log1.borrow_mut().clear();
+        log2.borrow_mut().clear();
+
+        // The first component vetoes the first request, but the second one should still be asked
+        assert!(!application.fire_close_request());
+        assert_eq!(vec!["close"], *log1.borrow());
+        assert_eq!(vec!["close"], *log2.borrow());
+        log1.borrow_mut().clear();
+        log2.borrow_mut().clear();
+
+        // The first component unsubscribed during the previous request
+        assert!(application.fire_close_request());
+        assert!(log1.borrow().is_empty());
+        assert_eq!(vec!["close"], *log2.borrow());
+    }
 
-        // Start with 1 mouse, and spawn it in the middle of the first component
-        let mouse1 = Mouse::new(6);
-        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse1, Point::new(0.35, 0.35)));
-        set1(vec![LocalMouse {
-            mouse: mouse1,
-            position: Point::new(0.5, 0.5),
-        }]);
-        set2(vec![]);
-        application.render(&test_renderer(region), true);
+    #[test]
+    fn test_hit_test_trace() {
+        struct PressComponent {
+            subscribe: bool,
+            filter: bool,
+        }
 
-        // Move the mouse to the other component
-        application.fire_mouse_move_event(MouseMoveEvent::new(
-            mouse1,
-            Point::new(0.35, 0.35),
-            Point::new(0.6, 0.9),
-        ));
-        set1(vec![]);
-        set2(vec![LocalMouse {
-            mouse: mouse1,
-            position: Point::new(0.2, 0.8),
-        }]);
-        application.render(&test_renderer(region), true);
+        impl Component for PressComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                if self.subscribe {
+                    buddy.subscribe_mouse_press();
+                }
+            }
 
-        // Move the mouse away from both components
-        application.fire_mouse_move_event(MouseMoveEvent::new(
-            mouse1,
-            Point::new(0.6, 0.9),
-            Point::new(0.1, 0.1),
-        ));
-        set1(vec![]);
-        set2(vec![]);
-        application.render(&test_renderer(region), true);
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 0.5, 1.0)),
+                    filter_mouse_actions: self.filter,
+                })
+            }
 
-        // Introduce the second mouse
-        let mouse2 = Mouse::new(120);
-        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse2, Point::new(0.1, 0.1)));
-        // Neither of the mouses is inside any of the components
-        application.render(&test_renderer(region), true);
+            fn on_mouse_press(
+                &mut self,
+                _event: MousePressEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+            }
+        }
 
-        // Move the second mouse to the second component
-        application.fire_mouse_move_event(MouseMoveEvent::new(
-            mouse2,
-            Point::new(0.1, 0.1),
-            Point::new(0.7, 0.8),
-        ));
-        set1(vec![]);
-        set2(vec![LocalMouse {
-            mouse: mouse2,
-            position: Point::new(0.4, 0.6),
-        }]);
-        application.render(&test_renderer(region), true);
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(PressComponent { subscribe: false, filter: false }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        menu.add_component_with_seat_policy(
+            Box::new(PressComponent { subscribe: true, filter: false }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+            SeatPolicy::OnlySeat(1),
+        );
+        menu.add_component(
+            Box::new(PressComponent { subscribe: true, filter: true }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
 
-        // Move the first mouse to the first component
-        application.fire_mouse_move_event(MouseMoveEvent::new(
-            mouse1,
-            Point::new(0.1, 0.1),
-            Point::new(0.35, 0.35),
-        ));
-        set1(vec![LocalMouse {
-            mouse: mouse1,
-            position: Point::new(0.5, 0.5),
-        }]);
-        set2(vec![LocalMouse {
-            mouse: mouse2,
-            position: Point::new(0.4, 0.6),
-        }]);
-        application.render(&test_renderer(region), true);
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
 
-        // Move the first mouse to the second component
-        application.fire_mouse_move_event(MouseMoveEvent::new(
-            mouse1,
-            Point::new(0.35, 0.35),
-            Point::new(0.8, 0.7),
-        ));
-        set1(vec![]);
-        set2(vec![
-            LocalMouse {
-                mouse: mouse1,
-                position: Point::new(0.6, 0.4),
-            },
-            LocalMouse {
-                mouse: mouse2,
-                position: Point::new(0.4, 0.6),
-            },
-        ]);
-        application.render(&test_renderer(region), true);
+        let press = |x: f32, y: f32| {
+            MousePressEvent::new(Mouse::with_seat(0, 2), Point::new(x, y), MouseButton::primary())
+        };
+        application.fire_mouse_enter_event(MouseEnterEvent::new(
+            Mouse::with_seat(0, 2),
+            Point::new(0.0, 0.0),
+        ));
 
-        // Remove the second mouse
-        application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse2, Point::new(0.7, 0.8)));
-        set1(vec![]);
-        set2(vec![LocalMouse {
-            mouse: mouse1,
-            position: Point::new(0.6, 0.4),
-        }]);
-        application.render(&test_renderer(region), true);
+        // Events shouldn't be recorded before the trace is started
+        application.fire_mouse_press_event(press(0.1, 0.1));
+
+        application.start_hit_test_trace();
+        application.fire_mouse_press_event(press(0.1, 0.1));
+        application.fire_mouse_press_event(press(0.4, 0.1));
+        application.fire_mouse_press_event(press(0.7, 0.7));
+        let trace = application.finish_hit_test_trace();
+
+        // Events shouldn't be recorded after the trace is finished
+        application.fire_mouse_press_event(press(0.1, 0.1));
+        assert!(application.finish_hit_test_trace().is_empty());
+
+        let record = |path: &[usize], outcome: HitTestOutcome| (path.to_vec(), outcome);
+        use HitTestOutcome::*;
+        assert_eq!(
+            vec![
+                // The first press should be delivered to the topmost component
+                record(&[], Delivered),
+                record(&[0], Occluded),
+                record(&[1], OutsideDomain),
+                record(&[2], Delivered),
+                // The second press misses the drawn region of the topmost component, so it should
+                // fall through to the bottom component
+                record(&[], Delivered),
+                record(&[1], OutsideDomain),
+                record(&[2], OutsideDrawnRegion),
+                record(&[0], NotSubscribed),
+                // The third press is rejected by the seat policy of the second component
+                record(&[], Delivered),
+                record(&[0], Occluded),
+                record(&[2], OutsideDomain),
+                record(&[1], RejectedBySeatPolicy),
+            ],
+            trace
+                .iter()
+                .map(|record| (record.get_path().to_vec(), record.get_outcome()))
+                .collect::<Vec<_>>()
+        );
+        assert!(trace.iter().all(|record| record.get_event_name() == "MousePress"));
+        assert_eq!("MousePress at [2]: OutsideDrawnRegion", trace[6].to_string());
     }
 
     #[test]
-    fn test_render_viewports_and_scissors() {
-        struct ViewportTestComponent {
-            expected_viewport: Rc<Cell<RenderRegion>>,
-            render_counter: Rc<Cell<u8>>,
+    fn test_composition() {
+        struct CompositionComponent {
+            log: Rc<RefCell<Vec<String>>>,
         }
-        impl Component for ViewportTestComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+        impl Component for CompositionComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_composition();
+                buddy.request_focus();
+            }
 
             fn render(
                 &mut self,
-                renderer: &Renderer,
+                _renderer: &Renderer,
                 _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                self.render_counter.set(self.render_counter.get() + 1);
-                assert_eq!(self.expected_viewport.get(), renderer.get_viewport());
-                assert_eq!(self.expected_viewport.get(), renderer.get_scissor());
                 entire_render_result()
             }
+
+            fn on_composition_start(
+                &mut self,
+                _event: &CompositionStartEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push("start".to_string());
+                buddy.set_caret_rectangle(Some(CaretRectangle::between(0.5, 0.0, 0.6, 1.0)));
+            }
+
+            fn on_composition_update(
+                &mut self,
+                event: &CompositionUpdateEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push(event.get_text().to_string());
+            }
+
+            fn on_composition_end(
+                &mut self,
+                event: &CompositionEndEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push(format!("end {}", event.get_text()));
+                buddy.set_caret_rectangle(None);
+                buddy.unsubscribe_composition();
+            }
         }
 
-        let mut renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
-        let mut buddy = root_buddy();
+        let log = Rc::new(RefCell::new(Vec::new()));
         let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(CompositionComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.5, 0.5, 1.0),
+        );
+        let mut application = Application::new(Box::new(menu));
+        assert_eq!(None, application.get_caret_rectangle());
 
-        let render_counter = Rc::new(Cell::new(0));
+        application.fire_composition_start_event(CompositionStartEvent::new());
+        assert_eq!(
+            Some(CaretRectangle::between(0.25, 0.5, 0.3, 1.0)),
+            application.get_caret_rectangle()
+        );
 
-        // I will assign proper values later
-        let viewport1 = Rc::new(Cell::new(RenderRegion::with_size(1, 2, 3, 4)));
-        let viewport2 = Rc::new(Cell::new(RenderRegion::with_size(1, 2, 3, 4)));
+        application.fire_composition_update_event(CompositionUpdateEvent::new("ni".to_string()));
+        application.fire_composition_end_event(CompositionEndEvent::new("你".to_string()));
+        assert_eq!(None, application.get_caret_rectangle());
+
+        // The component unsubscribed, so it shouldn't receive this event
+        application.fire_composition_start_event(CompositionStartEvent::new());
+        assert_eq!(vec!["start", "ni", "end 你"], *log.borrow());
+    }
+
+    #[test]
+    fn test_mouse_capture() {
+        struct DragComponent {
+            capture: bool,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for DragComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_release();
+                buddy.subscribe_mouse_move();
+                buddy.subscribe_mouse_leave();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+                if self.capture {
+                    buddy.capture_mouse(event.get_mouse());
+                }
+            }
+
+            fn on_mouse_release(
+                &mut self,
+                event: MouseReleaseEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                let captured = buddy.is_capturing_mouse(event.get_mouse());
+                let point = event.get_point();
+                self.log
+                    .borrow_mut()
+                    .push(format!("release {} {} {}", point.get_x(), point.get_y(), captured));
+            }
+
+            fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log
+                    .borrow_mut()
+                    .push(format!("move {} {}", event.get_from().get_x(), event.get_to().get_x()));
+            }
+
+            fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("leave".to_string());
+            }
+        }
 
+        let log1 = Rc::new(RefCell::new(Vec::new()));
+        let log2 = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(ViewportTestComponent {
-                render_counter: Rc::clone(&render_counter),
-                expected_viewport: Rc::clone(&viewport1),
+            Box::new(DragComponent {
+                capture: true,
+                log: Rc::clone(&log1),
             }),
-            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
         );
         menu.add_component(
-            Box::new(ViewportTestComponent {
-                render_counter: Rc::clone(&render_counter),
-                expected_viewport: Rc::clone(&viewport2),
+            Box::new(DragComponent {
+                capture: false,
+                log: Rc::clone(&log2),
             }),
-            ComponentDomain::between(0.2, 0.5, 0.9, 1.0),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
         );
 
-        viewport1.set(RenderRegion::between(0, 0, 50, 50));
-        viewport2.set(RenderRegion::between(20, 50, 90, 100));
-        menu.render(&renderer, &mut buddy, true).unwrap();
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
 
-        renderer.reset_viewport(RenderRegion::between(100, 200, 300, 400));
-        viewport1.set(RenderRegion::between(100, 200, 200, 300));
-        viewport2.set(RenderRegion::between(140, 300, 280, 400));
-        menu.render(&renderer, &mut buddy, true).unwrap();
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let move_event = |from_x: f32, to_x: f32| {
+            MouseMoveEvent::new(mouse, Point::new(from_x, 0.5), Point::new(to_x, 0.5))
+        };
+        let press_event = |x: f32| MousePressEvent::new(mouse, Point::new(x, 0.5), button);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.25, 0.5)));
+        application.fire_mouse_press_event(press_event(0.25));
+
+        // The first component captured the mouse, so it should receive the entire movement
+        application.fire_mouse_move_event(move_event(0.25, 0.75));
+        application.fire_mouse_move_event(move_event(0.75, 1.0));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            Point::new(1.0, 0.5),
+            button,
+        ));
+        assert_eq!(
+            vec!["move 0.5 1.5", "leave", "move 1.5 2", "release 2 0.5 true"],
+            *log1.borrow()
+        );
 
-        // Check that the render methods were actually called twice per component
-        assert_eq!(4, render_counter.get());
+        // The second component should only have received the (trimmed) movement
+        assert_eq!(vec!["move 0 0.5", "move 0.5 1"], *log2.borrow());
+
+        // The release ended the capture, so the first component shouldn't get this event
+        application.fire_mouse_move_event(move_event(1.0, 0.75));
+        assert_eq!(4, log1.borrow().len());
+
+        // The second component doesn't capture the mouse
+        log2.borrow_mut().clear();
+        application.fire_mouse_press_event(press_event(0.75));
+        application.fire_mouse_move_event(move_event(0.75, 0.25));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            Point::new(0.25, 0.5),
+            button,
+        ));
+        assert_eq!(vec!["move 0.5 0", "leave"], *log2.borrow());
+        assert_eq!("release 0.5 0.5 false", log1.borrow()[5]);
     }
 
     #[test]
-    fn test_render_with_custom_scissor() {
-        struct ScissorTestComponent {
-            expected_viewport: Rc<Cell<RenderRegion>>,
-            expected_scissor: Rc<Cell<RenderRegion>>,
+    fn test_render_budget() {
+        struct SlowComponent {
+            clock: Rc<Cell<Duration>>,
             render_counter: Rc<Cell<u8>>,
         }
-        impl Component for ScissorTestComponent {
+
+        impl Component for SlowComponent {
             fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
 
             fn render(
                 &mut self,
-                renderer: &Renderer,
+                _renderer: &Renderer,
                 _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
+                self.clock.set(self.clock.get() + Duration::from_millis(10));
                 self.render_counter.set(self.render_counter.get() + 1);
-                assert_eq!(self.expected_viewport.get(), renderer.get_viewport());
-                assert_eq!(self.expected_scissor.get(), renderer.get_scissor());
                 entire_render_result()
             }
         }
 
-        let counter_left = Rc::new(Cell::new(0));
-        let counter_middle = Rc::new(Cell::new(0));
-        let counter_right = Rc::new(Cell::new(0));
+        let clock = Rc::new(Cell::new(Duration::from_secs(0)));
+        let counters = vec![Rc::new(Cell::new(0)), Rc::new(Cell::new(0)), Rc::new(Cell::new(0))];
 
-        let viewport_left = Rc::new(Cell::new(RenderRegion::between(10, 10, 20, 20)));
-        let scissor_left = Rc::new(Cell::new(RenderRegion::between(10, 10, 20, 20)));
-        let viewport_middle = Rc::new(Cell::new(RenderRegion::between(40, 40, 60, 60)));
-        let scissor_middle = Rc::new(Cell::new(RenderRegion::between(40, 40, 60, 60)));
-        let viewport_right = Rc::new(Cell::new(RenderRegion::between(80, 80, 90, 90)));
-        let scissor_right = Rc::new(Cell::new(RenderRegion::between(80, 80, 90, 90)));
+        let mut menu = SimpleFlatMenu::new(None);
+        for (index, counter) in counters.iter().enumerate() {
+            menu.add_component(
+                Box::new(SlowComponent {
+                    clock: Rc::clone(&clock),
+                    render_counter: Rc::clone(counter),
+                }),
+                ComponentDomain::with_size(0.3 * index as f32, 0.0, 0.3, 1.0),
+            );
+        }
+        let budget_clock = Rc::clone(&clock);
+        menu.set_render_budget(Some(RenderBudget::new(
+            Duration::from_millis(15),
+            Box::new(move || budget_clock.get()),
+        )));
+
+        let counter_values = || counters.iter().map(|counter| counter.get()).collect::<Vec<_>>();
+        let mut application = Application::new(Box::new(menu));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+
+        // The budget is exceeded after rendering the second component
+        application.render(&renderer, false);
+        assert_eq!(2, counter_values().iter().sum::<u8>());
+
+        // So the last component should be rendered during the next frame
+        application.render(&renderer, false);
+        assert_eq!(vec![1, 1, 1], counter_values());
+
+        // Nothing should happen when no component requests a render
+        application.render(&renderer, false);
+        assert_eq!(vec![1, 1, 1], counter_values());
+
+        // Forced renders should ignore the budget
+        application.render(&renderer, true);
+        assert_eq!(vec![2, 2, 2], counter_values());
+    }
+
+    #[test]
+    fn test_render_budget_order_and_priority() {
+        struct BusyComponent {
+            index: usize,
+            clock: Rc<Cell<Duration>>,
+            render_log: Rc<RefCell<Vec<usize>>>,
+        }
+
+        impl Component for BusyComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.clock.set(self.clock.get() + Duration::from_millis(10));
+                self.render_log.borrow_mut().push(self.index);
+                buddy.request_render();
+                entire_render_result()
+            }
+        }
+
+        let clock = Rc::new(Cell::new(Duration::from_secs(10)));
+        let render_log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        let mut handles = Vec::new();
+        for index in 0..4 {
+            handles.push(menu.add_component(
+                Box::new(BusyComponent {
+                    index,
+                    clock: Rc::clone(&clock),
+                    render_log: Rc::clone(&render_log),
+                }),
+                ComponentDomain::with_size(0.25 * index as f32, 0.0, 0.25, 1.0),
+            ));
+        }
+        menu.set_render_priority(handles[3], RenderPriority::High);
+        assert_eq!(RenderPriority::High, menu.get_render_priority(handles[3]));
+        assert_eq!(RenderPriority::Normal, menu.get_render_priority(handles[2]));
+
+        let budget_clock = Rc::clone(&clock);
+        menu.set_render_budget(Some(RenderBudget::new(
+            Duration::from_millis(15),
+            Box::new(move || budget_clock.get()),
+        )));
+
+        let mut application = Application::new(Box::new(menu));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+
+        // Component 2 is deferred, but component 3 has a high priority
+        application.render(&renderer, false);
+        assert_eq!(vec![0, 1, 3], *render_log.borrow());
+
+        // Component 2 was deferred during the previous frame, so it must be rendered now, and
+        // the components must still be rendered from bottom to top
+        render_log.borrow_mut().clear();
+        application.render(&renderer, false);
+        assert_eq!(vec![0, 1, 2, 3], *render_log.borrow());
 
+        // A clock that goes backwards shouldn't cause a panic
+        render_log.borrow_mut().clear();
         let mut menu = SimpleFlatMenu::new(None);
         menu.add_component(
-            Box::new(ScissorTestComponent {
-                expected_viewport: Rc::clone(&viewport_left),
-                expected_scissor: Rc::clone(&scissor_left),
-                render_counter: Rc::clone(&counter_left),
+            Box::new(BusyComponent {
+                index: 0,
+                clock: Rc::clone(&clock),
+                render_log: Rc::clone(&render_log),
             }),
-            ComponentDomain::between(0.1, 0.1, 0.2, 0.2),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
         );
-        menu.add_component(
-            Box::new(ScissorTestComponent {
-                expected_viewport: Rc::clone(&viewport_middle),
-                expected_scissor: Rc::clone(&scissor_middle),
-                render_counter: Rc::clone(&counter_middle),
+        let clock_calls = Rc::new(Cell::new(0));
+        let budget_calls = Rc::clone(&clock_calls);
+        menu.set_render_budget(Some(RenderBudget::new(
+            Duration::from_millis(15),
+            Box::new(move || {
+                budget_calls.set(budget_calls.get() + 1);
+                Duration::from_secs(10) / budget_calls.get()
             }),
-            ComponentDomain::between(0.4, 0.4, 0.6, 0.6),
+        )));
+        let mut application = Application::new(Box::new(menu));
+        application.render(&renderer, false);
+        assert_eq!(2, clock_calls.get());
+        assert_eq!(vec![0], *render_log.borrow());
+    }
+
+    #[test]
+    fn test_remove_and_replace() {
+        struct LogComponent {
+            name: &'static str,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for LogComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("attach {}", self.name));
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.log.borrow_mut().push(format!("render {}", self.name));
+                entire_render_result()
+            }
+
+            fn on_detach(&mut self) {
+                self.log.borrow_mut().push(format!("detach {}", self.name));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let create = |name| Box::new(LogComponent { name, log: Rc::clone(&log) });
+
+        let mut menu = SimpleFlatMenu::new(Some(Color::rgb(100, 0, 200)));
+        let handle_a = menu.add_component(
+            create("a"),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
         );
-        menu.add_component(
-            Box::new(ScissorTestComponent {
-                expected_viewport: Rc::clone(&viewport_right),
-                expected_scissor: Rc::clone(&scissor_right),
-                render_counter: Rc::clone(&counter_right),
-            }),
-            ComponentDomain::between(0.8, 0.8, 0.9, 0.9),
+        let handle_b = menu.add_component(
+            create("b"),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
         );
+        assert_ne!(handle_a, handle_b);
 
-        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
         let mut buddy = root_buddy();
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        buddy.clear_render_request();
+        assert_eq!(vec!["attach a", "attach b", "render a", "render b"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // The removal should be processed during the next event, and the entire menu needs to be
+        // redrawn to get rid of the old component
+        menu.remove_component(handle_a);
+        assert!(log.borrow().is_empty());
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert!(buddy.did_request_render());
+        buddy.clear_render_request();
+        assert_eq!(vec!["detach a", "render b"], *log.borrow());
+        log.borrow_mut().clear();
 
-        // First try without scissor
-        menu.render(&renderer, &mut buddy, true).unwrap();
-        assert_eq!(1, counter_left.get());
-        assert_eq!(1, counter_middle.get());
-        assert_eq!(1, counter_right.get());
+        // Removing it a second time shouldn't have any effect
+        menu.remove_component(handle_a);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert!(!buddy.did_request_render());
+        assert!(log.borrow().is_empty());
 
-        // Now with a scissor in the left half
-        scissor_middle.set(RenderRegion::between(40, 40, 50, 60));
-        renderer.push_scissor(0.0, 0.0, 0.5, 1.0, || {
-            menu.render(&renderer, &mut buddy, true).unwrap();
-        });
-        assert_eq!(2, counter_left.get());
-        assert_eq!(2, counter_middle.get());
-        assert_eq!(1, counter_right.get());
+        // The replacement should take over the handle of b
+        menu.replace_component(handle_b, create("c"));
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        buddy.clear_render_request();
+        assert_eq!(vec!["detach b", "attach c", "render c"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // Components that were added after the last event shouldn't even be attached
+        menu.add_component(create("d"), ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        menu.clear_components();
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert!(buddy.did_request_render());
+        assert_eq!(vec!["detach c"], *log.borrow());
+        log.borrow_mut().clear();
+
+        menu.replace_component(handle_b, create("e"));
+        menu.on_detach();
+        assert!(log.borrow().is_empty());
     }
 
     #[test]
-    fn test_buddy_change_menu() {
-        struct NewMenuComponent {
-            counter: Rc<Cell<u8>>,
+    fn test_move_component() {
+        struct MovingComponent {
+            log: Rc<RefCell<Vec<String>>>,
         }
 
-        impl Component for NewMenuComponent {
+        impl Component for MovingComponent {
             fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
                 buddy.subscribe_mouse_click();
-                self.counter.set(5);
+            }
+
+            fn on_resize(&mut self, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("resize".to_string());
             }
 
             fn render(
                 &mut self,
                 _renderer: &Renderer,
                 _buddy: &mut dyn ComponentBuddy,
-                _force: bool,
+                force: bool,
             ) -> RenderResult {
-                self.counter.set(self.counter.get() * 3);
+                self.log.borrow_mut().push(format!("render {}", force));
                 entire_render_result()
             }
 
-            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
-                self.counter.set(self.counter.get() + 1);
+            fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                let point = event.get_point();
+                self.log.borrow_mut().push(format!("click {} {}", point.get_x(), point.get_y()));
             }
         }
 
-        struct ChangeMenuComponent {
-            counter: Rc<Cell<u8>>,
-            new_counter: Rc<Cell<u8>>,
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        let handle = menu.add_component(
+            Box::new(MovingComponent { log: Rc::clone(&log) }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+
+        let mut buddy = root_buddy();
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        buddy.clear_render_request();
+
+        let click = |x: f32, y: f32| {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        };
+        menu.on_mouse_click(click(0.25, 0.25), &mut buddy);
+        assert_eq!(vec!["render false", "click 0.5 0.5"], *log.borrow());
+        log.borrow_mut().clear();
+
+        menu.move_component(handle, ComponentDomain::between(0.5, 0.0, 1.0, 1.0));
+
+        // The move should be processed right before the next event
+        menu.on_mouse_click(click(0.75, 0.25), &mut buddy);
+        assert_eq!(vec!["resize", "click 0.5 0.25"], *log.borrow());
+        assert!(buddy.did_request_render());
+        log.borrow_mut().clear();
+
+        // Both the old and the new region need to be redrawn
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert_eq!(vec!["render true"], *log.borrow());
+        log.borrow_mut().clear();
+
+        menu.on_mouse_click(click(0.25, 0.25), &mut buddy);
+        menu.on_mouse_click(click(0.75, 0.25), &mut buddy);
+        assert_eq!(vec!["click 0.5 0.25"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // Moving a removed component shouldn't do anything
+        menu.remove_component(handle);
+        menu.move_component(handle, ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_move_component_animated() {
+        struct MovingComponent {
+            log: Rc<RefCell<Vec<String>>>,
         }
 
-        impl Component for ChangeMenuComponent {
+        impl Component for MovingComponent {
             fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
                 buddy.subscribe_mouse_click();
-                self.counter.set(2);
+            }
+
+            fn on_resize(&mut self, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("resize".to_string());
             }
 
             fn render(
@@ -2288,428 +6358,552 @@ mod tests {
                 _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                self.counter.set(self.counter.get() * 3);
                 entire_render_result()
             }
 
-            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
-                self.counter.set(self.counter.get() + 1);
-                let new_counter = Rc::clone(&self.new_counter);
-                buddy.change_menu(Box::new(move |_old_menu| {
-                    Box::new(NewMenuComponent {
-                        counter: new_counter,
-                    })
-                }));
+            fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                let point = event.get_point();
+                self.log.borrow_mut().push(format!("click {} {}", point.get_x(), point.get_y()));
             }
         }
 
-        let counter = Rc::new(Cell::new(0));
-        let new_counter = Rc::new(Cell::new(0));
-
+        let log = Rc::new(RefCell::new(Vec::new()));
         let mut menu = SimpleFlatMenu::new(None);
-        menu.add_component(
-            Box::new(ChangeMenuComponent {
-                counter: Rc::clone(&counter),
-                new_counter: Rc::clone(&new_counter),
-            }),
-            ComponentDomain::between(0.3, 0.3, 0.7, 0.7),
+        let handle = menu.add_component(
+            Box::new(MovingComponent { log: Rc::clone(&log) }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
         );
 
-        let mut application = Application::new(Box::new(menu));
-
-        let region = RenderRegion::with_size(1, 2, 3, 4);
-        let renderer = test_renderer(region);
-
-        assert_eq!(2, counter.get());
-        application.render(&renderer, false);
-        assert_eq!(6, counter.get());
-        assert_eq!(0, new_counter.get());
+        let mut buddy = root_buddy();
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert!(!buddy.get_subscriptions().tick);
 
-        let click_event =
-            MouseClickEvent::new(Mouse::new(3), Point::new(0.4, 0.4), MouseButton::primary());
+        let click = |x: f32, y: f32| {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        };
+        let tick = |millis: u64| TickEvent::new(Duration::from_millis(millis));
 
-        application.fire_mouse_click_event(click_event);
-        assert_eq!(7, counter.get());
-        assert_eq!(5, new_counter.get());
+        menu.move_component_animated(
+            handle,
+            ComponentDomain::between(0.5, 0.0, 1.0, 0.5),
+            Duration::from_millis(100),
+            Easing::Linear,
+        );
 
-        application.render(&renderer, false);
-        assert_eq!(7, counter.get());
-        assert_eq!(15, new_counter.get());
+        // The animation shouldn't move the component before the next tick
+        menu.on_mouse_click(click(0.25, 0.25), &mut buddy);
+        assert_eq!(vec!["click 0.5 0.5"], *log.borrow());
+        assert!(buddy.get_subscriptions().tick);
+        log.borrow_mut().clear();
 
-        application.fire_mouse_click_event(click_event);
-        assert_eq!(7, counter.get());
-        assert_eq!(16, new_counter.get());
+        // Halfway the animation, both rendering and mouse events should use the interpolated domain
+        buddy.clear_render_request();
+        menu.on_tick(tick(50), &mut buddy);
+        assert_eq!(vec!["resize"], *log.borrow());
+        assert!(buddy.did_request_render());
+        assert!(buddy.get_subscriptions().tick);
+        log.borrow_mut().clear();
+
+        menu.on_mouse_click(click(0.5, 0.25), &mut buddy);
+        assert_eq!(vec!["click 0.5 0.5"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // When the animation is finished, the menu no longer needs ticks
+        menu.on_tick(tick(60), &mut buddy);
+        assert_eq!(vec!["resize"], *log.borrow());
+        assert!(!buddy.get_subscriptions().tick);
+        log.borrow_mut().clear();
+
+        menu.on_mouse_click(click(0.25, 0.25), &mut buddy);
+        menu.on_mouse_click(click(0.75, 0.25), &mut buddy);
+        assert_eq!(vec!["click 0.5 0.5"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // A regular move should cancel a running animation
+        menu.move_component_animated(
+            handle,
+            ComponentDomain::between(0.0, 0.5, 0.5, 1.0),
+            Duration::from_millis(100),
+            Easing::Linear,
+        );
+        menu.move_component(handle, ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        menu.on_tick(tick(50), &mut buddy);
+        assert_eq!(vec!["resize"], *log.borrow());
+        assert!(!buddy.get_subscriptions().tick);
     }
 
     #[test]
-    fn test_mouse_press_and_release() {
-        struct PressReleaseComponent {
-            press_counter: Rc<Cell<u8>>,
-            release_counter: Rc<Cell<u8>>,
-            expected_press_event: Rc<Cell<MousePressEvent>>,
-            expected_release_event: Rc<Cell<MouseReleaseEvent>>,
-            filter_mouse_actions: bool,
+    fn test_z_order() {
+        struct LayerComponent {
+            name: &'static str,
+            log: Rc<RefCell<Vec<String>>>,
         }
 
-        impl Component for PressReleaseComponent {
+        impl Component for LayerComponent {
             fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-                buddy.subscribe_mouse_press();
-                buddy.subscribe_mouse_release();
+                buddy.subscribe_mouse_click();
             }
 
             fn render(
                 &mut self,
                 _renderer: &Renderer,
                 _buddy: &mut dyn ComponentBuddy,
-                _force: bool,
+                force: bool,
             ) -> RenderResult {
-                Ok(RenderResultStruct {
-                    filter_mouse_actions: self.filter_mouse_actions,
-                    drawn_region: Box::new(RectangularDrawnRegion::new(0.2, 0.2, 0.8, 0.8)),
-                })
-            }
-
-            fn on_mouse_press(&mut self, event: MousePressEvent, _buddy: &mut dyn ComponentBuddy) {
-                self.press_counter.set(self.press_counter.get() + 1);
-                let expected = self.expected_press_event.get();
-                assert_eq!(expected.get_mouse(), event.get_mouse());
-                assert!(expected.get_point().nearly_equal(event.get_point()));
-                assert_eq!(expected.get_button(), event.get_button());
+                self.log.borrow_mut().push(format!("render {} {}", self.name, force));
+                entire_render_result()
             }
 
-            fn on_mouse_release(
-                &mut self,
-                event: MouseReleaseEvent,
-                _buddy: &mut dyn ComponentBuddy,
-            ) {
-                self.release_counter.set(self.release_counter.get() + 1);
-                let expected = self.expected_release_event.get();
-                assert_eq!(expected.get_mouse(), event.get_mouse());
-                assert!(expected.get_point().nearly_equal(event.get_point()));
-                assert_eq!(expected.get_button(), event.get_button());
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("click {}", self.name));
+                buddy.request_render();
             }
         }
 
-        // The initial events don't matter, but need to be specified
-        let dummy_press_event =
-            MousePressEvent::new(Mouse::new(111), Point::new(1.1, 1.1), MouseButton::new(11));
-        let dummy_release_event =
-            MouseReleaseEvent::new(Mouse::new(222), Point::new(2.2, 2.2), MouseButton::new(22));
-
-        let press_counter1 = Rc::new(Cell::new(0));
-        let release_counter1 = Rc::new(Cell::new(0));
-        let expected_press_event1 = Rc::new(Cell::new(dummy_press_event));
-        let expected_release_event1 = Rc::new(Cell::new(dummy_release_event));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let create = |name| Box::new(LayerComponent { name, log: Rc::clone(&log) });
 
-        let press_counter2 = Rc::new(Cell::new(0));
-        let release_counter2 = Rc::new(Cell::new(0));
-        let expected_press_event2 = Rc::new(Cell::new(dummy_press_event));
-        let expected_release_event2 = Rc::new(Cell::new(dummy_release_event));
+        let mut menu = SimpleFlatMenu::new(None);
+        let full_domain = ComponentDomain::between(0.0, 0.0, 1.0, 1.0);
+        let handle_a = menu.add_component(create("a"), full_domain);
+        menu.add_component(create("b"), ComponentDomain::between(0.25, 0.25, 0.75, 0.75));
+        menu.add_component(create("c"), ComponentDomain::between(0.8, 0.8, 1.0, 1.0));
 
         let mut buddy = root_buddy();
-        let mut menu = SimpleFlatMenu::new(None);
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
 
-        menu.add_component(
-            Box::new(PressReleaseComponent {
-                press_counter: Rc::clone(&press_counter1),
-                release_counter: Rc::clone(&release_counter1),
-                expected_press_event: Rc::clone(&expected_press_event1),
-                expected_release_event: Rc::clone(&expected_release_event1),
-                filter_mouse_actions: true,
-            }),
-            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        let take_log = || std::mem::take(&mut *log.borrow_mut());
+        let mut render = |menu: &mut SimpleFlatMenu| {
+            menu.render(&renderer, &mut buddy, false).unwrap();
+        };
+        let click = |x: f32, y: f32| {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        };
+
+        // Components should be rendered from bottom to top, and since a is drawn first, b and c
+        // need to redraw themselves completely
+        render(&mut menu);
+        assert_eq!(vec!["render a false", "render b true", "render c true"], take_log());
+
+        // The topmost component should receive the events
+        let mut dummy_buddy = root_buddy();
+        menu.on_mouse_click(click(0.5, 0.5), &mut dummy_buddy);
+        assert_eq!(vec!["click b"], take_log());
+
+        // When b is redrawn, nothing else needs to be redrawn
+        render(&mut menu);
+        assert_eq!(vec!["render b false"], take_log());
+
+        // But when a is redrawn, b and c need to be redrawn as well since a was drawn over them
+        menu.on_mouse_click(click(0.1, 0.1), &mut dummy_buddy);
+        render(&mut menu);
+        assert_eq!(
+            vec!["click a", "render a false", "render b true", "render c true"],
+            take_log()
         );
 
-        menu.add_component(
-            Box::new(PressReleaseComponent {
-                press_counter: Rc::clone(&press_counter2),
-                release_counter: Rc::clone(&release_counter2),
-                expected_press_event: Rc::clone(&expected_press_event2),
-                expected_release_event: Rc::clone(&expected_release_event2),
-                filter_mouse_actions: false,
-            }),
-            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        menu.raise_component(handle_a);
+        menu.on_mouse_click(click(0.5, 0.5), &mut dummy_buddy);
+        render(&mut menu);
+        assert_eq!(
+            vec!["click a", "render b true", "render c true", "render a true"],
+            take_log()
         );
 
-        // Attaching and rendering should be done before sending events
-        menu.on_attach(&mut buddy);
-        menu.render(
-            &test_renderer(RenderRegion::with_size(0, 0, 1000, 1000)),
-            &mut buddy,
-            false,
-        )
-        .unwrap();
+        menu.lower_component(handle_a);
+        menu.on_mouse_click(click(0.5, 0.5), &mut dummy_buddy);
+        assert_eq!(vec!["click b"], take_log());
+    }
 
-        let check_counters = |press1: u8, release1: u8, press2: u8, release2: u8| {
-            assert_eq!(press1, press_counter1.get());
-            assert_eq!(release1, release_counter1.get());
-            assert_eq!(press2, press_counter2.get());
-            assert_eq!(release2, release_counter2.get());
-        };
+    #[test]
+    fn test_mouse_hover_occlusion() {
+        let move_log1 = Rc::new(RefCell::new(Vec::new()));
+        let enter_log1 = Rc::new(RefCell::new(Vec::new()));
+        let leave_log1 = Rc::new(RefCell::new(Vec::new()));
+        let move_log2 = Rc::new(RefCell::new(Vec::new()));
+        let enter_log2 = Rc::new(RefCell::new(Vec::new()));
+        let leave_log2 = Rc::new(RefCell::new(Vec::new()));
 
-        // Miss these events on purpose:
-        menu.on_mouse_press(
-            MousePressEvent::new(Mouse::new(1), Point::new(0.2, 0.7), MouseButton::primary()),
-            &mut buddy,
+        let mut buddy = root_buddy();
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.on_attach(&mut buddy);
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(false)),
+                mouse_move_log: Rc::clone(&move_log1),
+                mouse_enter_log: Rc::clone(&enter_log1),
+                mouse_leave_log: Rc::clone(&leave_log1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
         );
-        menu.on_mouse_release(
-            MouseReleaseEvent::new(Mouse::new(1), Point::new(0.2, 0.7), MouseButton::primary()),
-            &mut buddy,
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(false)),
+                mouse_move_log: Rc::clone(&move_log2),
+                mouse_enter_log: Rc::clone(&enter_log2),
+                mouse_leave_log: Rc::clone(&leave_log2),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
         );
-        check_counters(0, 0, 0, 0);
+        menu.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), &mut buddy, false)
+            .unwrap();
 
-        // Press and release in the middle of both components
-        expected_press_event1.set(MousePressEvent::new(
-            Mouse::new(2),
-            Point::new(0.5, 0.5),
-            MouseButton::new(1),
-        ));
-        menu.on_mouse_press(
-            MousePressEvent::new(Mouse::new(2), Point::new(0.25, 0.25), MouseButton::new(1)),
-            &mut buddy,
-        );
-        expected_press_event2.set(MousePressEvent::new(
-            Mouse::new(3),
-            Point::new(0.5, 0.5),
-            MouseButton::new(2),
-        ));
-        menu.on_mouse_press(
-            MousePressEvent::new(Mouse::new(3), Point::new(0.75, 0.75), MouseButton::new(2)),
-            &mut buddy,
-        );
-        expected_release_event1.set(MouseReleaseEvent::new(
-            Mouse::new(2),
-            Point::new(0.5, 0.5),
-            MouseButton::new(1),
-        ));
-        menu.on_mouse_release(
-            MouseReleaseEvent::new(Mouse::new(2), Point::new(0.25, 0.25), MouseButton::new(1)),
-            &mut buddy,
-        );
-        expected_release_event2.set(MouseReleaseEvent::new(
-            Mouse::new(3),
-            Point::new(0.5, 0.5),
-            MouseButton::new(2),
-        ));
-        menu.on_mouse_release(
-            MouseReleaseEvent::new(Mouse::new(3), Point::new(0.75, 0.75), MouseButton::new(2)),
-            &mut buddy,
-        );
-        check_counters(1, 1, 1, 1);
+        let mouse = Mouse::new(0);
+        let near = Point::new(0.25, 0.25);
+        let far = Point::new(0.75, 0.75);
+
+        // Only the topmost component should be hovered
+        menu.on_mouse_enter(MouseEnterEvent::new(mouse, near), &mut buddy);
+        assert_eq!(1, enter_log1.borrow().len());
+        assert_eq!(0, enter_log2.borrow().len());
+
+        // When the mouse moves onto the top component, the bottom component is occluded
+        menu.on_mouse_move(MouseMoveEvent::new(mouse, near, far), &mut buddy);
+        assert_eq!(1, move_log1.borrow().len());
+        assert_eq!(1, leave_log1.borrow().len());
+        assert!(leave_log1.borrow()[0].get_exit_point().nearly_equal(far));
+        assert_eq!(1, enter_log2.borrow().len());
+        assert!(enter_log2.borrow()[0]
+            .get_entrance_point()
+            .nearly_equal(Point::new(0.0, 0.0)));
+        assert_eq!(1, move_log2.borrow().len());
+
+        // Moving within the top component shouldn't affect the bottom component
+        menu.on_mouse_move(MouseMoveEvent::new(mouse, far, far), &mut buddy);
+        assert_eq!(1, move_log1.borrow().len());
+        assert_eq!(2, move_log2.borrow().len());
+
+        // When the mouse leaves the top component, the bottom component is revealed again
+        menu.on_mouse_move(MouseMoveEvent::new(mouse, far, near), &mut buddy);
+        assert_eq!(1, leave_log2.borrow().len());
+        assert_eq!(3, move_log2.borrow().len());
+        assert_eq!(2, enter_log1.borrow().len());
+        assert!(enter_log1.borrow()[1].get_entrance_point().nearly_equal(far));
+        assert_eq!(2, move_log1.borrow().len());
+
+        // Only the hovered component should receive the leave event
+        menu.on_mouse_leave(MouseLeaveEvent::new(mouse, near), &mut buddy);
+        assert_eq!(2, leave_log1.borrow().len());
+        assert_eq!(1, leave_log2.borrow().len());
+        assert_eq!(1, enter_log2.borrow().len());
+    }
 
-        // This time, press near bottom left corner and release near top right corner
-        // Since the first component filters mouse actions, only the second component should notice this
-        menu.on_mouse_press(
-            MousePressEvent::new(Mouse::new(4), Point::new(0.05, 0.05), MouseButton::new(3)),
-            &mut buddy,
-        );
-        menu.on_mouse_release(
-            MouseReleaseEvent::new(Mouse::new(4), Point::new(0.45, 0.45), MouseButton::new(3)),
-            &mut buddy,
-        );
-        expected_press_event2.set(MousePressEvent::new(
-            Mouse::new(5),
-            Point::new(0.1, 0.1),
-            MouseButton::new(4),
-        ));
-        menu.on_mouse_press(
-            MousePressEvent::new(Mouse::new(5), Point::new(0.55, 0.55), MouseButton::new(4)),
-            &mut buddy,
-        );
-        expected_release_event2.set(MouseReleaseEvent::new(
-            Mouse::new(5),
-            Point::new(0.9, 0.9),
-            MouseButton::new(4),
-        ));
-        menu.on_mouse_release(
-            MouseReleaseEvent::new(Mouse::new(5), Point::new(0.95, 0.95), MouseButton::new(4)),
+    #[test]
+    fn test_component_lookup() {
+        let mut menu = SimpleFlatMenu::new(None);
+        let left = menu.add_component(
+            Box::new(ui().label("Left")),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        let right = menu.add_component(
+            Box::new(SimpleFlatMenu::new(Some(Color::rgb(0, 0, 100)))),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+        menu.set_component_id(left, "left");
+        menu.set_component_id(right, "right");
+
+        // The components haven't been processed yet, but their ids can be used already
+        assert!(menu.get_component(left).is_none());
+        assert_eq!(Some(right), menu.find_component_by_id("right"));
+        assert_eq!(Some("left"), menu.get_component_id(left));
+
+        let mut buddy = root_buddy();
+        menu.on_attach(&mut buddy);
+        menu.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), &mut buddy, false)
+            .unwrap();
+
+        let type_name = menu.get_component(left).unwrap().get_type_name();
+        assert!(type_name.ends_with("TextLabel"));
+        assert_eq!(Some(right), menu.find_component_at(Point::new(0.75, 0.5)));
+        assert_eq!(None, menu.find_component_at(Point::new(1.5, 0.5)));
+
+        // Ids are unique, so the right component should lose its id
+        menu.set_component_id(left, "right");
+        assert_eq!(Some(left), menu.find_component_by_id("right"));
+        assert_eq!(None, menu.find_component_by_id("left"));
+        assert_eq!(None, menu.get_component_id(right));
+
+        menu.set_component_visible(right, false);
+        menu.remove_component(left);
+        assert_eq!(None, menu.find_component_by_id("right"));
+        menu.on_mouse_click(
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.75, 0.5), MouseButton::primary()),
             &mut buddy,
         );
-        check_counters(1, 1, 2, 2);
+        assert!(menu.get_component(left).is_none());
+        assert_eq!(None, menu.find_component_at(Point::new(0.75, 0.5)));
     }
 
     #[test]
-    fn test_buddy_pressed_mouse_buttons() {
-        struct MouseCheck {
-            mouse: Mouse,
-            button: MouseButton,
-            result: Option<bool>,
+    fn test_set_component_visible() {
+        struct HidingComponent {
+            log: Rc<RefCell<Vec<String>>>,
+            num_clicks: u32,
         }
 
-        impl MouseCheck {
-            fn new(mouse: Mouse, button: MouseButton, result: Option<bool>) -> Self {
-                Self {
-                    mouse,
-                    button,
-                    result,
-                }
+        impl Component for HidingComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                buddy.subscribe_mouse_enter();
+                buddy.subscribe_mouse_leave();
+                buddy.subscribe_tick();
             }
-        }
 
-        struct VecCheck {
-            mouse: Mouse,
-            buttons: Option<Vec<MouseButton>>,
-        }
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                force: bool,
+            ) -> RenderResult {
+                self.log.borrow_mut().push(format!("render {}", force));
+                entire_render_result()
+            }
 
-        impl VecCheck {
-            fn new(mouse: Mouse, buttons: Option<Vec<MouseButton>>) -> Self {
-                Self { mouse, buttons }
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.num_clicks += 1;
+                self.log.borrow_mut().push(format!("click {}", self.num_clicks));
+            }
+
+            fn on_mouse_enter(&mut self, _event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("enter".to_string());
+            }
+
+            fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("leave".to_string());
+            }
+
+            fn on_tick(&mut self, _event: TickEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("tick".to_string());
+            }
+
+            fn on_detach(&mut self) {
+                self.log.borrow_mut().push("detach".to_string());
             }
         }
 
-        struct MouseCheckComponent {
-            checks: Rc<Cell<Vec<MouseCheck>>>,
-            vec_checks: Rc<Cell<Vec<VecCheck>>>,
-            render_counter: Rc<Cell<u8>>,
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(Some(Color::rgb(0, 100, 0)));
+        let handle = menu.add_component(
+            Box::new(HidingComponent {
+                log: Rc::clone(&log),
+                num_clicks: 0,
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+
+        let mut buddy = RootComponentBuddy::new();
+        let mouse_store = Rc::new(RefCell::new(MouseStore::new()));
+        buddy.set_mouse_store(Rc::clone(&mouse_store));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        buddy.clear_render_request();
+
+        let take_log = || std::mem::take(&mut *log.borrow_mut());
+        let point = Point::new(0.25, 0.5);
+        let click = MouseClickEvent::new(Mouse::new(0), point, MouseButton::primary());
+        let tick = TickEvent::new(std::time::Duration::from_millis(10));
+        menu.on_mouse_click(click, &mut buddy);
+        assert_eq!(vec!["render false", "click 1"], take_log());
+        assert_eq!(Some(true), menu.is_component_visible(handle));
+
+        // Let the mouse rest on the component
+        mouse_store.borrow_mut().add_mouse(Mouse::new(0), MouseState {
+            position: point,
+            buttons: PressedMouseButtons::new(),
+        });
+        menu.on_mouse_enter(MouseEnterEvent::new(Mouse::new(0), point), &mut buddy);
+        assert_eq!(vec!["enter"], take_log());
+
+        // Hidden components shouldn't get events, and the menu should repaint their area. The
+        // mouse should leave the component when it is hidden.
+        menu.set_component_visible(handle, false);
+        assert_eq!(Some(false), menu.is_component_visible(handle));
+        menu.on_mouse_click(click, &mut buddy);
+        menu.on_tick(tick, &mut buddy);
+        assert!(buddy.did_request_render());
+        buddy.clear_render_request();
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert_eq!(vec!["leave"], take_log());
+
+        // But they should stay attached, and keep both their state and their subscriptions
+        assert!(buddy.get_subscriptions().tick);
+        menu.set_component_visible(handle, true);
+        menu.on_tick(tick, &mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        menu.on_mouse_click(click, &mut buddy);
+        assert_eq!(vec!["enter", "tick", "render true", "click 2"], take_log());
+
+        menu.remove_component(handle);
+        assert_eq!(None, menu.is_component_visible(handle));
+
+        menu.on_detach();
+        assert_eq!(vec!["detach"], take_log());
+    }
+
+    #[test]
+    fn test_set_component_visible_covers() {
+        let enter_log1 = Rc::new(RefCell::new(Vec::new()));
+        let leave_log1 = Rc::new(RefCell::new(Vec::new()));
+        let enter_log2 = Rc::new(RefCell::new(Vec::new()));
+        let leave_log2 = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(false)),
+                mouse_move_log: Rc::new(RefCell::new(Vec::new())),
+                mouse_enter_log: Rc::clone(&enter_log1),
+                mouse_leave_log: Rc::clone(&leave_log1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        let top_handle = menu.add_component(
+            Box::new(MouseMotionComponent {
+                should_filter_mouse_actions: Rc::new(Cell::new(false)),
+                mouse_move_log: Rc::new(RefCell::new(Vec::new())),
+                mouse_enter_log: Rc::clone(&enter_log2),
+                mouse_leave_log: Rc::clone(&leave_log2),
+            }),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        let mut buddy = RootComponentBuddy::new();
+        let mouse_store = Rc::new(RefCell::new(MouseStore::new()));
+        buddy.set_mouse_store(Rc::clone(&mouse_store));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+
+        let mouse = Mouse::new(0);
+        let point = Point::new(0.75, 0.75);
+        mouse_store.borrow_mut().add_mouse(mouse, MouseState {
+            position: point,
+            buttons: PressedMouseButtons::new(),
+        });
+        menu.on_mouse_enter(MouseEnterEvent::new(mouse, point), &mut buddy);
+        let counts = || {
+            (
+                enter_log1.borrow().len(),
+                leave_log1.borrow().len(),
+                enter_log2.borrow().len(),
+                leave_log2.borrow().len(),
+            )
+        };
+        assert_eq!((0, 0, 1, 0), counts());
+
+        // Hiding the top component should reveal the bottom component
+        menu.set_component_visible(top_handle, false);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert_eq!((1, 0, 1, 1), counts());
+
+        // Showing it again should cover the bottom component
+        menu.set_component_visible(top_handle, true);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert_eq!((1, 1, 2, 1), counts());
+    }
+
+    #[test]
+    fn test_subscription_lists() {
+        struct ListComponent {
+            subscribe_move: bool,
+            subscribe_click_out: bool,
         }
 
-        impl Component for MouseCheckComponent {
-            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+        impl Component for ListComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                if self.subscribe_move {
+                    buddy.subscribe_mouse_enter();
+                }
+                if self.subscribe_click_out {
+                    buddy.subscribe_mouse_click_out();
+                }
+            }
 
             fn render(
                 &mut self,
                 _renderer: &Renderer,
-                buddy: &mut dyn ComponentBuddy,
+                _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                let checks = self.checks.take();
-                for check in checks {
-                    assert_eq!(
-                        check.result,
-                        buddy.is_mouse_button_down(check.mouse, check.button)
-                    );
-                }
+                entire_render_result()
+            }
 
-                let vec_checks = self.vec_checks.take();
-                for check in vec_checks {
-                    assert_eq!(check.buttons, buddy.get_pressed_mouse_buttons(check.mouse));
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                // Toggle the mouse move subscription
+                if self.subscribe_move {
+                    buddy.unsubscribe_mouse_enter();
+                } else {
+                    buddy.subscribe_mouse_move();
                 }
-
-                self.render_counter.set(self.render_counter.get() + 1);
-                entire_render_result()
+                self.subscribe_move = !self.subscribe_move;
             }
-        }
 
-        let counter1 = Rc::new(Cell::new(0));
-        let counter2 = Rc::new(Cell::new(0));
+            fn on_mouse_click_out(
+                &mut self,
+                _event: MouseClickOutEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+            }
 
-        let checks1 = Rc::new(Cell::new(Vec::new()));
-        let checks2 = Rc::new(Cell::new(Vec::new()));
+            fn on_mouse_enter(&mut self, _event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+            }
 
-        let vec_checks1 = Rc::new(Cell::new(Vec::new()));
-        let vec_checks2 = Rc::new(Cell::new(Vec::new()));
+            fn on_mouse_move(&mut self, _event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {}
+        }
 
         let mut menu = SimpleFlatMenu::new(None);
-        menu.add_component(
-            Box::new(MouseCheckComponent {
-                checks: Rc::clone(&checks1),
-                vec_checks: Rc::clone(&vec_checks1),
-                render_counter: Rc::clone(&counter1),
-            }),
-            ComponentDomain::between(0.2, 0.5, 0.7, 0.7),
-        );
-        menu.add_component(
-            Box::new(MouseCheckComponent {
-                checks: Rc::clone(&checks2),
-                vec_checks: Rc::clone(&vec_checks2),
-                render_counter: Rc::clone(&counter2),
-            }),
-            ComponentDomain::between(0.8, 0.8, 1.0, 1.0),
-        );
-
-        let check_counters = |expected: u8| {
-            assert_eq!(expected, counter1.get());
-            assert_eq!(expected, counter2.get());
+        let add = |menu: &mut SimpleFlatMenu, x: f32, subscribe_move, subscribe_click_out| {
+            menu.add_component(
+                Box::new(ListComponent { subscribe_move, subscribe_click_out }),
+                ComponentDomain::with_size(x, 0.0, 0.25, 1.0),
+            )
         };
+        add(&mut menu, 0.0, true, false);
+        add(&mut menu, 0.25, false, true);
+        let handle = add(&mut menu, 0.5, true, true);
+        add(&mut menu, 0.75, false, false);
 
-        let mut application = Application::new(Box::new(menu));
+        let mut buddy = root_buddy();
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
 
-        let renderer = test_renderer(RenderRegion::between(10, 20, 30, 40));
+        let list_sizes = |menu: &SimpleFlatMenu| {
+            (menu.mouse_move_components.len(), menu.click_out_components.len())
+        };
+        assert_eq!((2, 2), list_sizes(&menu));
 
-        // No mouse should be present initially
-        checks1.set(vec![MouseCheck::new(
-            Mouse::new(0),
-            MouseButton::primary(),
-            None,
-        )]);
-        vec_checks1.set(vec![VecCheck::new(Mouse::new(0), None)]);
-        application.render(&renderer, true);
-        check_counters(1);
+        let click = |menu: &mut SimpleFlatMenu, buddy: &mut RootComponentBuddy, x: f32| {
+            let point = Point::new(x, 0.5);
+            let event = MouseClickEvent::new(Mouse::new(0), point, MouseButton::primary());
+            menu.on_mouse_click(event, buddy);
+        };
 
-        // Spawn a mouse on component 1, but don't press any buttons yet
-        let mouse1 = Mouse::new(3);
-        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse1, Point::new(0.6, 0.6)));
-        checks1.set(vec![MouseCheck::new(
-            mouse1,
-            MouseButton::primary(),
-            Some(false),
-        )]);
-        checks2.set(vec![MouseCheck::new(mouse1, MouseButton::primary(), None)]);
-        vec_checks1.set(vec![VecCheck::new(mouse1, Some(Vec::new()))]);
-        vec_checks2.set(vec![VecCheck::new(mouse1, None)]);
-        application.render(&renderer, true);
-        check_counters(2);
+        // The lists should be updated lazily, right before the next event
+        click(&mut menu, &mut buddy, 0.1);
+        assert_eq!((2, 2), list_sizes(&menu));
+        click(&mut menu, &mut buddy, 0.9);
+        assert_eq!((1, 2), list_sizes(&menu));
+        click(&mut menu, &mut buddy, 0.4);
+        assert_eq!((2, 2), list_sizes(&menu));
 
-        // Press a button
-        let button1 = MouseButton::new(1);
-        let button2 = MouseButton::new(2);
-        application.fire_mouse_press_event(MousePressEvent::new(
-            mouse1,
-            Point::new(0.6, 0.6),
-            button1,
-        ));
-        checks1.set(vec![
-            MouseCheck::new(mouse1, button1, Some(true)),
-            MouseCheck::new(mouse1, button2, Some(false)),
-        ]);
-        checks2.set(vec![
-            MouseCheck::new(mouse1, button1, None),
-            MouseCheck::new(mouse1, button2, None),
-        ]);
-        vec_checks1.set(vec![
-            VecCheck::new(mouse1, Some(vec![button1])),
-            VecCheck::new(Mouse::new(10), None),
-        ]);
-        application.render(&renderer, true);
-        check_counters(3);
+        // The first component unsubscribed, and the last one subscribed
+        assert!(menu.mouse_move_components.iter().all(|entry_cell| {
+            let entry = entry_cell.borrow();
+            entry.domain.get_min_x() > 0.4 && entry.wants_mouse_moves()
+        }));
 
-        // Move the mouse away
-        application.fire_mouse_move_event(MouseMoveEvent::new(
-            mouse1,
-            Point::new(0.6, 0.6),
-            Point::new(0.0, 0.0),
-        ));
-        checks1.set(vec![
-            MouseCheck::new(mouse1, button1, None),
-            MouseCheck::new(mouse1, button2, None),
-        ]);
-        checks2.set(vec![
-            MouseCheck::new(mouse1, button1, None),
-            MouseCheck::new(mouse1, button2, None),
-        ]);
-        vec_checks1.set(vec![VecCheck::new(mouse1, None)]);
-        application.render(&renderer, true);
-        check_counters(4);
+        // The second component subscribed during the last click, and the third one is removed
+        menu.remove_component(handle);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        assert_eq!((2, 1), list_sizes(&menu));
 
-        // Move the mouse to component 2
-        application.fire_mouse_move_event(MouseMoveEvent::new(
-            mouse1,
-            Point::new(0.0, 0.0),
-            Point::new(0.9, 0.9),
-        ));
-        checks1.set(vec![
-            MouseCheck::new(mouse1, button1, None),
-            MouseCheck::new(mouse1, button2, None),
-        ]);
-        checks2.set(vec![
-            MouseCheck::new(mouse1, button1, Some(true)),
-            MouseCheck::new(mouse1, button2, Some(false)),
-        ]);
-        vec_checks2.set(vec![
-            VecCheck::new(mouse1, Some(vec![button1])),
-            VecCheck::new(Mouse::new(10), None),
-        ]);
-        application.render(&renderer, true);
-        check_counters(5);
+        menu.on_detach();
+        assert_eq!((0, 0), list_sizes(&menu));
     }
 }
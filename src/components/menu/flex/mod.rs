@@ -0,0 +1,576 @@
+use crate::*;
+
+use std::time::Duration;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FlexDirection {
+    /// The children will be placed from left to right
+    Row,
+    /// The children will be placed from top to bottom
+    Column,
+}
+
+/// Determines how much space a child of a `FlexMenu` gets along the direction of the menu (the
+/// *main axis*).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FlexSize {
+    /// The child will get this fraction of the main axis of the menu (so 0.25 means a quarter of
+    /// the width of a row menu)
+    Fraction(f32),
+    /// The space that is left after the padding, spacing, and `Fraction` children will be divided
+    /// over the `Weight` children, proportional to their weight.
+    Weight(f32),
+}
+
+/// Determines where a child of a `FlexMenu` is placed perpendicular to the direction of the menu
+/// (the *cross axis*). The sizes of `Start`, `Center`, and `End` are fractions of the cross axis
+/// of the menu.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FlexAlignment {
+    /// The child will use the entire cross axis (except the padding)
+    Stretch,
+    /// The child will be placed at the left (in column menus) or top (in row menus)
+    Start(f32),
+    Center(f32),
+    /// The child will be placed at the right (in column menus) or bottom (in row menus)
+    End(f32),
+}
+
+struct FlexEntry {
+    handle: ComponentHandle,
+    size: FlexSize,
+    alignment: FlexAlignment,
+}
+
+/// A menu that places its children next to each other in a row or column, so that they don't
+/// need to compute their `ComponentDomain`s manually. The domains of all children will be
+/// recomputed whenever a child is added or removed, or when the padding or spacing is changed.
+/// Use `set_layout_animation` to let the children move smoothly to their new domains.
+///
+/// This uses a `SimpleFlatMenu` to manage the children, so it propagates events in the same way.
+pub struct FlexMenu {
+    menu: SimpleFlatMenu,
+    direction: FlexDirection,
+    entries: Vec<FlexEntry>,
+    padding: f32,
+    spacing: f32,
+    layout_animation: Option<(Duration, Easing)>,
+}
+
+impl FlexMenu {
+    pub fn new(direction: FlexDirection, background_color: Option<Color>) -> Self {
+        Self {
+            menu: SimpleFlatMenu::new(background_color),
+            direction,
+            entries: Vec::new(),
+            padding: 0.0,
+            spacing: 0.0,
+            layout_animation: None,
+        }
+    }
+
+    pub fn get_direction(&self) -> FlexDirection {
+        self.direction
+    }
+
+    pub fn get_padding(&self) -> f32 {
+        self.padding
+    }
+
+    /// Sets the space between the edges of this menu and its children, as a fraction of the size
+    /// of this menu
+    pub fn set_padding(&mut self, padding: f32) {
+        self.padding = padding;
+        self.update_domains();
+    }
+
+    pub fn get_spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    /// Sets the space between 2 adjacent children, as a fraction of the main axis of this menu
+    pub fn set_spacing(&mut self, spacing: f32) {
+        self.spacing = spacing;
+        self.update_domains();
+    }
+
+    pub fn get_layout_animation(&self) -> Option<(Duration, Easing)> {
+        self.layout_animation
+    }
+
+    /// When *animation* is `Some((duration, easing))`, the existing children will gradually move
+    /// to their new domains whenever the layout changes (see
+    /// `SimpleFlatMenu.move_component_animated`). When it is `None` (the default), they will be
+    /// moved immediately. Newly added children are always placed immediately.
+    pub fn set_layout_animation(&mut self, animation: Option<(Duration, Easing)>) {
+        self.layout_animation = animation;
+    }
+
+    /// Adds a component to the end of this menu that will use the entire cross axis
+    pub fn add_component(
+        &mut self,
+        component: Box<dyn Component>,
+        size: FlexSize,
+    ) -> ComponentHandle {
+        self.add_component_with_alignment(component, size, FlexAlignment::Stretch)
+    }
+
+    pub fn add_component_with_alignment(
+        &mut self,
+        component: Box<dyn Component>,
+        size: FlexSize,
+        alignment: FlexAlignment,
+    ) -> ComponentHandle {
+        let mut domains = self.compute_domains_with(size, alignment);
+        let new_domain = domains.pop().unwrap();
+        self.move_existing_components(&domains);
+
+        let handle = self.menu.add_component(component, new_domain);
+        self.entries.push(FlexEntry {
+            handle,
+            size,
+            alignment,
+        });
+        handle
+    }
+
+    /// Removes the component with the given handle from this menu, and gives its space to the
+    /// other children. See `SimpleFlatMenu.remove_component`.
+    pub fn remove_component(&mut self, handle: ComponentHandle) {
+        self.menu.remove_component(handle);
+        let old_length = self.entries.len();
+        self.entries.retain(|entry| entry.handle != handle);
+        if self.entries.len() != old_length {
+            self.update_domains();
+        }
+    }
+
+    /// Changes the `FlexSize` of the component with the given handle, and recomputes the domains
+    /// of all children. This has no effect if the component is not part of this menu.
+    pub fn set_component_size(&mut self, handle: ComponentHandle, size: FlexSize) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.handle == handle) {
+            entry.size = size;
+            self.update_domains();
+        }
+    }
+
+    /// Hides or shows the component with the given handle, see
+    /// `SimpleFlatMenu.set_component_visible`. Hidden components keep their space in the layout.
+    pub fn set_component_visible(&mut self, handle: ComponentHandle, visible: bool) {
+        self.menu.set_component_visible(handle, visible);
+    }
+
+    /// Finds the handle of the topmost visible component at the given *point*, see
+    /// `SimpleFlatMenu.find_component_at`
+    pub fn find_component_at(&self, point: Point) -> Option<ComponentHandle> {
+        self.menu.find_component_at(point)
+    }
+
+    fn compute_domains_with(
+        &self,
+        extra_size: FlexSize,
+        extra_alignment: FlexAlignment,
+    ) -> Vec<ComponentDomain> {
+        let mut items: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.size, entry.alignment))
+            .collect();
+        items.push((extra_size, extra_alignment));
+        compute_flex_domains(self.direction, self.padding, self.spacing, &items)
+    }
+
+    fn move_existing_components(&mut self, domains: &[ComponentDomain]) {
+        for (entry, domain) in self.entries.iter().zip(domains) {
+            match self.layout_animation {
+                Some((duration, easing)) => {
+                    self.menu.move_component_animated(entry.handle, *domain, duration, easing)
+                }
+                None => self.menu.move_component(entry.handle, *domain),
+            }
+        }
+    }
+
+    fn update_domains(&mut self) {
+        let items: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.size, entry.alignment))
+            .collect();
+        let domains = compute_flex_domains(self.direction, self.padding, self.spacing, &items);
+        self.move_existing_components(&domains);
+    }
+}
+
+/// Computes the domains of the children of a `FlexMenu` with the given properties. If the
+/// padding, spacing, and `Fraction` children need more space than available, the `Weight`
+/// children won't get any space.
+fn compute_flex_domains(
+    direction: FlexDirection,
+    padding: f32,
+    spacing: f32,
+    items: &[(FlexSize, FlexAlignment)],
+) -> Vec<ComponentDomain> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+
+    let num_spaces = (items.len() - 1) as f32;
+    let mut remaining_space = 1.0 - 2.0 * padding - num_spaces * spacing;
+    let mut total_weight = 0.0;
+    for (size, _) in items {
+        match size {
+            FlexSize::Fraction(fraction) => remaining_space -= fraction,
+            FlexSize::Weight(weight) => total_weight += weight,
+        }
+    }
+    let remaining_space = remaining_space.max(0.0);
+
+    let mut main_position = padding;
+    let mut domains = Vec::with_capacity(items.len());
+    for (size, alignment) in items {
+        let main_size = match *size {
+            FlexSize::Fraction(fraction) => fraction,
+            FlexSize::Weight(weight) => match total_weight > 0.0 {
+                true => remaining_space * weight / total_weight,
+                false => 0.0,
+            },
+        };
+        let main_start = main_position;
+        let main_end = main_position + main_size;
+        main_position = main_end + spacing;
+
+        let cross_space = 1.0 - 2.0 * padding;
+        let (cross_start, cross_end) = match *alignment {
+            FlexAlignment::Stretch => (padding, 1.0 - padding),
+            FlexAlignment::Start(size) => (padding, padding + size.min(cross_space)),
+            FlexAlignment::Center(size) => {
+                let size = size.min(cross_space);
+                (0.5 - 0.5 * size, 0.5 + 0.5 * size)
+            }
+            FlexAlignment::End(size) => (1.0 - padding - size.min(cross_space), 1.0 - padding),
+        };
+
+        // The main axis of columns goes from top to bottom, and the cross axis of rows goes from
+        // top to bottom, but the y-axis of domains goes from bottom to top
+        domains.push(match direction {
+            FlexDirection::Row => {
+                ComponentDomain::between(main_start, 1.0 - cross_end, main_end, 1.0 - cross_start)
+            }
+            FlexDirection::Column => {
+                ComponentDomain::between(cross_start, 1.0 - main_end, cross_end, 1.0 - main_start)
+            }
+        });
+    }
+    domains
+}
+
+impl Component for FlexMenu {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy)
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        self.menu.render(renderer, buddy, force)
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click(event, buddy)
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_press(event, buddy)
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_release(event, buddy)
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_move(event, buddy)
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_enter(event, buddy)
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_leave(event, buddy)
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_scroll(event, buddy)
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_double_click(event, buddy)
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_long_press(event, buddy)
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_context_click(event, buddy)
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_middle_click(event, buddy)
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_hover(event, buddy)
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_pinch(event, buddy)
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_rotate_gesture(event, buddy)
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_swipe(event, buddy)
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy)
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.menu.get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.menu.get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.menu.get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn assert_domain(expected: (f32, f32, f32, f32), actual: ComponentDomain) {
+        let actual_tuple = (
+            actual.get_min_x(),
+            actual.get_min_y(),
+            actual.get_max_x(),
+            actual.get_max_y(),
+        );
+        let difference = (expected.0 - actual_tuple.0).abs()
+            + (expected.1 - actual_tuple.1).abs()
+            + (expected.2 - actual_tuple.2).abs()
+            + (expected.3 - actual_tuple.3).abs();
+        assert!(difference < 0.0001, "Expected {:?}, but got {:?}", expected, actual_tuple);
+    }
+
+    #[test]
+    fn test_compute_flex_domains_row() {
+        assert!(compute_flex_domains(FlexDirection::Row, 0.1, 0.1, &[]).is_empty());
+
+        let domains = compute_flex_domains(FlexDirection::Row, 0.1, 0.1, &[
+            (FlexSize::Weight(1.0), FlexAlignment::Stretch),
+            (FlexSize::Fraction(0.2), FlexAlignment::Start(0.5)),
+            (FlexSize::Weight(3.0), FlexAlignment::End(0.2)),
+        ]);
+
+        // The padding and spacing take 0.4, and the fraction takes 0.2, so the weights share 0.4
+        assert_domain((0.1, 0.1, 0.2, 0.9), domains[0]);
+        assert_domain((0.3, 0.4, 0.5, 0.9), domains[1]);
+        assert_domain((0.6, 0.1, 0.9, 0.3), domains[2]);
+    }
+
+    #[test]
+    fn test_compute_flex_domains_column() {
+        let domains = compute_flex_domains(FlexDirection::Column, 0.0, 0.0, &[
+            (FlexSize::Fraction(0.25), FlexAlignment::Center(0.5)),
+            (FlexSize::Weight(1.0), FlexAlignment::Stretch),
+            // The alignment size should be limited to the size of the menu
+            (FlexSize::Fraction(0.25), FlexAlignment::Start(2.0)),
+        ]);
+
+        assert_domain((0.25, 0.75, 0.75, 1.0), domains[0]);
+        assert_domain((0.0, 0.25, 1.0, 0.75), domains[1]);
+        assert_domain((0.0, 0.0, 1.0, 0.25), domains[2]);
+
+        // The weighted child shouldn't get negative space
+        let domains = compute_flex_domains(FlexDirection::Column, 0.0, 0.0, &[
+            (FlexSize::Fraction(1.0), FlexAlignment::Stretch),
+            (FlexSize::Weight(1.0), FlexAlignment::Stretch),
+        ]);
+        assert_domain((0.0, 0.0, 1.0, 1.0), domains[0]);
+        assert_domain((0.0, 0.0, 1.0, 0.0), domains[1]);
+    }
+
+    struct ClickComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for ClickComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            let point = event.get_point();
+            self.log.borrow_mut().push(format!(
+                "{} {} {}", self.name, point.get_x(), point.get_y()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = FlexMenu::new(FlexDirection::Row, None);
+        let handle_a = menu.add_component(
+            Box::new(ClickComponent { name: "a", log: Rc::clone(&log) }),
+            FlexSize::Weight(1.0),
+        );
+
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+        menu.on_attach(&mut buddy);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+
+        let click = |x: f32, y: f32| {
+            MouseClickEvent::new(Mouse::new(0), Point::new(x, y), MouseButton::primary())
+        };
+        menu.on_mouse_click(click(0.75, 0.5), &mut buddy);
+        assert_eq!(vec!["a 0.75 0.5"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // The new component should take the right half
+        menu.add_component(
+            Box::new(ClickComponent { name: "b", log: Rc::clone(&log) }),
+            FlexSize::Weight(1.0),
+        );
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        menu.on_mouse_click(click(0.25, 0.5), &mut buddy);
+        menu.on_mouse_click(click(0.75, 0.5), &mut buddy);
+        assert_eq!(vec!["a 0.5 0.5", "b 0.5 0.5"], *log.borrow());
+        log.borrow_mut().clear();
+
+        // When a is removed, b should get the entire menu
+        menu.remove_component(handle_a);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        menu.on_mouse_click(click(0.25, 0.5), &mut buddy);
+        assert_eq!(vec!["b 0.25 0.5"], *log.borrow());
+        log.borrow_mut().clear();
+
+        menu.set_padding(0.25);
+        menu.render(&renderer, &mut buddy, false).unwrap();
+        menu.on_mouse_click(click(0.1, 0.5), &mut buddy);
+        menu.on_mouse_click(click(0.5, 0.5), &mut buddy);
+        assert_eq!(vec!["b 0.5 0.5"], *log.borrow());
+    }
+}
@@ -1,3 +1,19 @@
+mod accordion;
+mod anchor;
+mod fade;
 mod flat;
+mod flex;
+mod overlay;
+mod popup;
+mod scroll;
+mod tabbed;
 
+pub use accordion::*;
+pub use anchor::*;
+pub use fade::*;
 pub use flat::*;
+pub use flex::*;
+pub use overlay::*;
+pub use popup::*;
+pub use scroll::*;
+pub use tabbed::*;
@@ -0,0 +1,412 @@
+use crate::*;
+
+use std::rc::Rc;
+
+struct ModalEntry {
+    blocker: ComponentHandle,
+    modal: ComponentHandle,
+}
+
+/// A menu that shows a *base* component, and allows *modal* components (like confirmation
+/// dialogs) to be pushed on top of it. While a modal is open, the components underneath it will
+/// be dimmed, and they won't receive any mouse events: only the topmost modal can be used.
+///
+/// Modals form a stack: pushing a modal while another modal is open will dim and block the
+/// other modal as well. Closing the topmost modal will unblock the modal below it (or the base
+/// component when it was the last modal).
+pub struct OverlayMenu {
+    menu: SimpleFlatMenu,
+    dim_color: Color,
+    dim_shader: Rc<FragmentOnlyShader>,
+    modals: Vec<ModalEntry>,
+}
+
+impl OverlayMenu {
+    /// Constructs a new `OverlayMenu` that shows the given *base* component (which will occupy the
+    /// entire menu) and uses *dim_color* to dim everything underneath the open modals. The dimming
+    /// covers every other pixel, so the components underneath will remain (partially) visible.
+    pub fn new(base: Box<dyn Component>, dim_color: Color) -> Self {
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(base, ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        Self {
+            menu,
+            dim_color,
+            dim_shader: Rc::new(Self::create_shader()),
+            modals: Vec::new(),
+        }
+    }
+
+    /// Creates the `FragmentOnlyShader` that is used to dim the background. See
+    /// `Renderer.prewarm`.
+    pub(crate) fn create_shader() -> FragmentOnlyShader {
+        FragmentOnlyShader::new(FragmentOnlyShaderDescription {
+            source_code: "
+                void main() {
+                    if (mod(floor(gl_FragCoord.x) + floor(gl_FragCoord.y), 2.0) < 0.5) {
+                        discard;
+                    }
+                    gl_FragColor = color1;
+                }
+            "
+            .to_string(),
+            num_float_matrices: 0,
+            num_colors: 1,
+            num_float_vectors: 0,
+            num_int_vectors: 0,
+            num_floats: 0,
+            num_ints: 0,
+        })
+    }
+
+    /// Pushes the given *component* on top of the base component and all other modals, and
+    /// returns its handle (which is needed to close it with `close_modal`). Like the changes of a
+    /// `SimpleFlatMenu`, this will be processed during the next event.
+    pub fn push_modal(
+        &mut self,
+        component: Box<dyn Component>,
+        domain: ComponentDomain,
+    ) -> ComponentHandle {
+        let blocker = ModalBlocker {
+            dim_color: self.dim_color,
+            dim_shader: Rc::clone(&self.dim_shader),
+        };
+        let blocker = self.menu.add_component(
+            Box::new(blocker),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        let modal = self.menu.add_component(component, domain);
+        self.modals.push(ModalEntry { blocker, modal });
+        modal
+    }
+
+    /// Closes the modal with the given handle (which must have been returned by `push_modal`).
+    /// The modal will be detached, and the dimming it caused will disappear.
+    pub fn close_modal(&mut self, handle: ComponentHandle) {
+        match self.modals.iter().position(|entry| entry.modal == handle) {
+            Some(index) => {
+                let entry = self.modals.remove(index);
+                self.menu.remove_component(entry.modal);
+                self.menu.remove_component(entry.blocker);
+            }
+            // Shouldn't happen, but not critical enough for a release panic
+            None => debug_assert!(false),
+        };
+    }
+
+    /// Closes the topmost modal, and returns its handle (or `None` if there were no open modals)
+    pub fn pop_modal(&mut self) -> Option<ComponentHandle> {
+        let handle = self.modals.last().map(|entry| entry.modal);
+        if let Some(handle) = handle {
+            self.close_modal(handle);
+        }
+        handle
+    }
+
+    pub fn get_num_modals(&self) -> usize {
+        self.modals.len()
+    }
+}
+
+impl Component for OverlayMenu {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy)
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        self.menu.render(renderer, buddy, force)
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click(event, buddy)
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_press(event, buddy)
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_release(event, buddy)
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_move(event, buddy)
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_enter(event, buddy)
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_leave(event, buddy)
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_scroll(event, buddy)
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_double_click(event, buddy)
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_long_press(event, buddy)
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_context_click(event, buddy)
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_middle_click(event, buddy)
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_hover(event, buddy)
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_pinch(event, buddy)
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_rotate_gesture(event, buddy)
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_swipe(event, buddy)
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy)
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.menu.get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.menu.get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.menu.get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+/// The component that is placed between a modal and the components underneath it. It dims the
+/// components underneath, and it takes all mouse events by not subscribing to any of them.
+struct ModalBlocker {
+    #[allow(dead_code)] // These fields are only used when golem rendering is enabled
+    dim_color: Color,
+    #[allow(dead_code)]
+    dim_shader: Rc<FragmentOnlyShader>,
+}
+
+impl Component for ModalBlocker {
+    fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        _buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        renderer.apply_fragment_shader(
+            0.0,
+            0.0,
+            1.0,
+            1.0,
+            &self.dim_shader,
+            FragmentOnlyDrawParameters {
+                colors: &[self.dim_color],
+                ..FragmentOnlyDrawParameters::default()
+            },
+        );
+        entire_render_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct ClickLogComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for ClickLogComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("click {}", self.name));
+        }
+
+        fn on_detach(&mut self) {
+            self.log.borrow_mut().push(format!("detach {}", self.name));
+        }
+    }
+
+    #[test]
+    fn test_modals() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let create = |name| {
+            Box::new(ClickLogComponent {
+                name,
+                log: Rc::clone(&log),
+            })
+        };
+
+        let mut menu = OverlayMenu::new(create("base"), Color::rgb(0, 0, 0));
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        menu.on_attach(&mut buddy);
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+
+        // Render before each click, since the menu needs to know where its components are
+        let mut click = |menu: &mut OverlayMenu, x: f32, y: f32| {
+            menu.render(&renderer, &mut buddy, false).unwrap();
+            let point = Point::new(x, y);
+            let event = MouseClickEvent::new(Mouse::new(0), point, MouseButton::primary());
+            menu.on_mouse_click(event, &mut buddy);
+        };
+
+        click(&mut menu, 0.1, 0.1);
+
+        let domain = ComponentDomain::between(0.25, 0.25, 0.75, 0.75);
+        let first = menu.push_modal(create("first"), domain);
+        click(&mut menu, 0.1, 0.1);
+        click(&mut menu, 0.5, 0.5);
+
+        menu.push_modal(create("second"), domain);
+        assert_eq!(2, menu.get_num_modals());
+        click(&mut menu, 0.1, 0.1);
+        click(&mut menu, 0.5, 0.5);
+
+        // Closing the bottom modal should leave the top modal open
+        menu.close_modal(first);
+        click(&mut menu, 0.1, 0.1);
+        click(&mut menu, 0.5, 0.5);
+
+        assert!(menu.pop_modal().is_some());
+        assert!(menu.pop_modal().is_none());
+        click(&mut menu, 0.5, 0.5);
+
+        assert_eq!(
+            vec![
+                "click base",
+                "click first",
+                "click second",
+                "detach first",
+                "click second",
+                "detach second",
+                "click base",
+            ],
+            *log.borrow()
+        );
+    }
+}
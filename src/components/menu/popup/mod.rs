@@ -0,0 +1,445 @@
+use crate::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+mod placement;
+
+pub use placement::*;
+
+struct OpenPopup {
+    handle: ComponentHandle,
+    domain: ComponentDomain,
+}
+
+/// A menu that shows a *base* component, and hosts the popups (like context menus) that are
+/// requested by the base component or any of its descendants using `ComponentBuddy.open_popup`.
+/// The popups will float on top of the base component, and all of them will be closed when the
+/// user clicks outside of them (that click won't reach the base component).
+///
+/// Since popup requests travel up to the nearest `PopupMenu`, applications typically use a
+/// `PopupMenu` as their root component (or close to it), so that popups can appear anywhere in
+/// the window. Components inside a popup can open popups as well (for instance submenus), which
+/// will be hosted by the same `PopupMenu`.
+pub struct PopupMenu {
+    menu: SimpleFlatMenu,
+    popup_requests: Rc<RefCell<Vec<PopupRequest>>>,
+    popups: Vec<OpenPopup>,
+}
+
+impl PopupMenu {
+    pub fn new(base: Box<dyn Component>) -> Self {
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(base, ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        let popup_requests = Rc::new(RefCell::new(Vec::new()));
+        menu.set_popup_sink(Rc::clone(&popup_requests));
+        Self {
+            menu,
+            popup_requests,
+            popups: Vec::new(),
+        }
+    }
+
+    pub fn get_num_popups(&self) -> usize {
+        self.popups.len()
+    }
+
+    /// Closes all open popups. Like the changes of a `SimpleFlatMenu`, this will be processed
+    /// during the next event.
+    pub fn close_popups(&mut self) {
+        for popup in self.popups.drain(..) {
+            self.menu.remove_component(popup.handle);
+        }
+    }
+
+    fn open_requested_popups(&mut self, buddy: &mut dyn ComponentBuddy) {
+        let requests = std::mem::take(&mut *self.popup_requests.borrow_mut());
+        let bounds = ComponentDomain::between(0.0, 0.0, 1.0, 1.0);
+        for request in requests {
+            let domain = request.position.resolve(bounds);
+            let handle = self.menu.add_component(request.component, domain);
+            self.popups.push(OpenPopup { handle, domain });
+            buddy.request_render();
+        }
+    }
+}
+
+impl Component for PopupMenu {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        self.menu.render(renderer, buddy, force)
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        let point = event.get_point();
+        let is_outside_popups = !self
+            .popups
+            .iter()
+            .any(|popup| popup.domain.is_inside(point));
+        if !self.popups.is_empty() && is_outside_popups {
+            self.close_popups();
+            buddy.request_render();
+            return;
+        }
+
+        self.menu.on_mouse_click(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_press(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_release(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_move(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_enter(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_leave(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_scroll(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_double_click(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_long_press(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_context_click(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_middle_click(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_hover(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_pinch(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_rotate_gesture(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_swipe(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy);
+        self.open_requested_popups(buddy);
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.menu.get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.menu.get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.menu.get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct ClickLogComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+        popup_domain: Option<ComponentDomain>,
+    }
+
+    impl Component for ClickLogComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("click {}", self.name));
+            if let Some(domain) = self.popup_domain {
+                buddy.open_popup(
+                    domain,
+                    Box::new(ClickLogComponent {
+                        name: "popup",
+                        log: Rc::clone(&self.log),
+                        popup_domain: None,
+                    }),
+                );
+            }
+        }
+
+        fn on_detach(&mut self) {
+            self.log.borrow_mut().push(format!("detach {}", self.name));
+        }
+    }
+
+    #[test]
+    fn test_popups() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        // The opener covers only the bottom-left quarter, so the popup domain must be transformed
+        let mut base = SimpleFlatMenu::new(None);
+        base.add_component(
+            Box::new(ClickLogComponent {
+                name: "opener",
+                log: Rc::clone(&log),
+                popup_domain: Some(ComponentDomain::between(1.0, 1.0, 1.5, 1.5)),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+
+        let mut menu = PopupMenu::new(Box::new(base));
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        menu.on_attach(&mut buddy);
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+
+        // Render before each click, since the menus need to know where their components are
+        let mut click = |menu: &mut PopupMenu, x: f32, y: f32| {
+            menu.render(&renderer, &mut buddy, false).unwrap();
+            let point = Point::new(x, y);
+            let event = MouseClickEvent::new(Mouse::new(0), point, MouseButton::primary());
+            menu.on_mouse_click(event, &mut buddy);
+        };
+
+        click(&mut menu, 0.1, 0.1);
+        assert_eq!(1, menu.get_num_popups());
+        click(&mut menu, 0.6, 0.6);
+        assert_eq!(1, menu.get_num_popups());
+
+        // Clicking outside the popup should close it, without reaching the opener
+        click(&mut menu, 0.1, 0.1);
+        assert_eq!(0, menu.get_num_popups());
+        click(&mut menu, 0.1, 0.1);
+        assert_eq!(1, menu.get_num_popups());
+
+        menu.close_popups();
+        click(&mut menu, 0.6, 0.6);
+
+        assert_eq!(
+            vec![
+                "click opener",
+                "click popup",
+                "detach popup",
+                "click opener",
+                "detach popup",
+            ],
+            *log.borrow()
+        );
+    }
+
+    struct PlacedOpenerComponent {
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for PlacedOpenerComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+            let placement = PopupPlacement::at_point(event.get_point(), 0.5, 0.5, PopupSide::Below);
+            buddy.open_placed_popup(
+                placement,
+                Box::new(ClickLogComponent {
+                    name: "popup",
+                    log: Rc::clone(&self.log),
+                    popup_domain: None,
+                }),
+            );
+        }
+    }
+
+    #[test]
+    fn test_placed_popups() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut base = SimpleFlatMenu::new(None);
+        base.add_component(
+            Box::new(PlacedOpenerComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+
+        let mut menu = PopupMenu::new(Box::new(base));
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        menu.on_attach(&mut buddy);
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+
+        let mut click = |menu: &mut PopupMenu, x: f32, y: f32| {
+            menu.render(&renderer, &mut buddy, false).unwrap();
+            let point = Point::new(x, y);
+            let event = MouseClickEvent::new(Mouse::new(0), point, MouseButton::primary());
+            menu.on_mouse_click(event, &mut buddy);
+        };
+
+        // There is no room below the click, so the popup should be flipped to above the click
+        click(&mut menu, 0.1, 0.1);
+        assert_eq!(1, menu.get_num_popups());
+        click(&mut menu, 0.3, 0.3);
+        assert_eq!(1, menu.get_num_popups());
+        assert_eq!(vec!["click popup"], *log.borrow());
+
+        // This point would have been inside the popup if it wasn't flipped
+        click(&mut menu, 0.3, 0.05);
+        assert_eq!(0, menu.get_num_popups());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::*;
+
+/// The side of its anchor where a popup prefers to appear. See `PopupPlacement`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PopupSide {
+    Below,
+    Above,
+    Left,
+    Right,
+}
+
+impl PopupSide {
+    fn flip(self) -> Self {
+        match self {
+            Self::Below => Self::Above,
+            Self::Above => Self::Below,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Describes where a popup (like a tooltip, context menu, or dropdown list) should appear
+/// relative to an *anchor*, which can be a point (like the position of the mouse cursor) or a
+/// domain (like the domain of a button). Unlike a fixed `ComponentDomain`, a placement can be
+/// adjusted to the space that is available: see the `place` method.
+///
+/// The anchor and the size of the popup are relative to the component that creates the
+/// placement, just like the domain of `ComponentBuddy.open_popup`. Components can pass
+/// placements to `ComponentBuddy.open_placed_popup`, which lets the `PopupMenu` that hosts the
+/// popup keep it inside its own domain (which is typically the entire window).
+///
+/// ## Alignment
+/// When the popup is placed below or above its anchor, its left edge will be aligned with the
+/// left edge of the anchor (when there is enough room). When the popup is placed left or right of
+/// its anchor, its top edge will be aligned with the top edge of the anchor. So a popup placed
+/// below a point will extend to the bottom-right of that point, like most context menus.
+#[derive(Copy, Clone, Debug)]
+pub struct PopupPlacement {
+    anchor: ComponentDomain,
+    width: f32,
+    height: f32,
+    side: PopupSide,
+}
+
+impl PopupPlacement {
+    /// Creates a placement for a popup with the given size that should appear at the given
+    /// *side* of the given *point*
+    pub fn at_point(point: Point, width: f32, height: f32, side: PopupSide) -> Self {
+        let anchor = ComponentDomain::with_size(point.get_x(), point.get_y(), 0.0, 0.0);
+        Self::next_to(anchor, width, height, side)
+    }
+
+    /// Creates a placement for a popup with the given size that should appear at the given
+    /// *side* of the given *anchor* domain
+    pub fn next_to(anchor: ComponentDomain, width: f32, height: f32, side: PopupSide) -> Self {
+        Self {
+            anchor,
+            width,
+            height,
+            side,
+        }
+    }
+
+    pub fn get_anchor(&self) -> ComponentDomain {
+        self.anchor
+    }
+
+    pub fn get_width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn get_side(&self) -> PopupSide {
+        self.side
+    }
+
+    /// Converts this placement from the coordinate space of a child to the coordinate space of
+    /// the menu that gave the child the given *domain*
+    pub fn transform_back(&self, domain: ComponentDomain) -> Self {
+        Self {
+            anchor: transform_domain_back(domain, self.anchor),
+            width: self.width * domain.get_width(),
+            height: self.height * domain.get_height(),
+            side: self.side,
+        }
+    }
+
+    /// Determines the domain of the popup such that it stays within the given *bounds* (whenever
+    /// it's not larger than the bounds). The popup will be moved to the opposite side of its
+    /// anchor when there is not enough room at the preferred side, and the opposite side has more
+    /// room. When neither side has enough room, the popup will overlap the anchor.
+    pub fn place(&self, bounds: ComponentDomain) -> ComponentDomain {
+        let anchor = self.anchor;
+        let room = |side: PopupSide| match side {
+            PopupSide::Below => anchor.get_min_y() - bounds.get_min_y(),
+            PopupSide::Above => bounds.get_max_y() - anchor.get_max_y(),
+            PopupSide::Left => anchor.get_min_x() - bounds.get_min_x(),
+            PopupSide::Right => bounds.get_max_x() - anchor.get_max_x(),
+        };
+        let needed_room = match self.side {
+            PopupSide::Below | PopupSide::Above => self.height,
+            PopupSide::Left | PopupSide::Right => self.width,
+        };
+
+        let mut side = self.side;
+        if room(side) < needed_room && room(side.flip()) > room(side) {
+            side = side.flip();
+        }
+
+        let (min_x, min_y) = match side {
+            PopupSide::Below => (anchor.get_min_x(), anchor.get_min_y() - self.height),
+            PopupSide::Above => (anchor.get_min_x(), anchor.get_max_y()),
+            PopupSide::Left => (
+                anchor.get_min_x() - self.width,
+                anchor.get_max_y() - self.height,
+            ),
+            PopupSide::Right => (anchor.get_max_x(), anchor.get_max_y() - self.height),
+        };
+
+        // Move the popup inside the bounds. When the popup is larger than the bounds, it will
+        // stick to the left and top edges, since that is where the content typically starts.
+        let min_x = if self.width > bounds.get_width() {
+            bounds.get_min_x()
+        } else {
+            min_x
+                .max(bounds.get_min_x())
+                .min(bounds.get_max_x() - self.width)
+        };
+        let min_y = if self.height > bounds.get_height() {
+            bounds.get_max_y() - self.height
+        } else {
+            min_y
+                .max(bounds.get_min_y())
+                .min(bounds.get_max_y() - self.height)
+        };
+
+        ComponentDomain::with_size(min_x, min_y, self.width, self.height)
+    }
+}
+
+fn transform_domain_back(domain: ComponentDomain, inner: ComponentDomain) -> ComponentDomain {
+    let min = domain.transform_back(Point::new(inner.get_min_x(), inner.get_min_y()));
+    let max = domain.transform_back(Point::new(inner.get_max_x(), inner.get_max_y()));
+    ComponentDomain::between(min.get_x(), min.get_y(), max.get_x(), max.get_y())
+}
+
+/// Where a requested popup should be shown: either in a fixed domain (see
+/// `ComponentBuddy.open_popup`) or at a placement that will be resolved by the `PopupMenu`
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum PopupPosition {
+    Fixed(ComponentDomain),
+    Placed(PopupPlacement),
+}
+
+impl PopupPosition {
+    pub(crate) fn transform_back(&self, domain: ComponentDomain) -> Self {
+        match self {
+            Self::Fixed(inner) => Self::Fixed(transform_domain_back(domain, *inner)),
+            Self::Placed(placement) => Self::Placed(placement.transform_back(domain)),
+        }
+    }
+
+    pub(crate) fn resolve(&self, bounds: ComponentDomain) -> ComponentDomain {
+        match self {
+            Self::Fixed(domain) => *domain,
+            Self::Placed(placement) => placement.place(bounds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    fn assert_domain(expected: [f32; 4], actual: ComponentDomain) {
+        let actual = [
+            actual.get_min_x(),
+            actual.get_min_y(),
+            actual.get_max_x(),
+            actual.get_max_y(),
+        ];
+        for (expected, actual) in expected.iter().zip(actual.iter()) {
+            assert!((expected - actual).abs() < 0.001, "{:?}", actual);
+        }
+    }
+
+    #[test]
+    fn test_place_at_point() {
+        let bounds = ComponentDomain::between(0.0, 0.0, 1.0, 1.0);
+        let place = |x: f32, y: f32, side: PopupSide| {
+            PopupPlacement::at_point(Point::new(x, y), 0.25, 0.5, side).place(bounds)
+        };
+
+        // When there is enough room, the popup should extend to the bottom-right of the point
+        assert_domain([0.25, 0.25, 0.5, 0.75], place(0.25, 0.75, PopupSide::Below));
+        assert_domain([0.25, 0.25, 0.5, 0.75], place(0.25, 0.25, PopupSide::Above));
+
+        // Near the bottom edge, the popup should flip to above the point
+        assert_domain([0.25, 0.25, 0.5, 0.75], place(0.25, 0.25, PopupSide::Below));
+
+        // Near the right edge, the popup should be shifted to the left
+        assert_domain(
+            [0.75, 0.25, 1.0, 0.75],
+            place(0.875, 0.75, PopupSide::Below),
+        );
+
+        // The popup should only flip when the other side has more room
+        assert_domain(
+            [0.25, 0.375, 0.5, 0.875],
+            place(0.25, 0.375, PopupSide::Below),
+        );
+        assert_domain(
+            [0.25, 0.125, 0.5, 0.625],
+            place(0.25, 0.625, PopupSide::Below),
+        );
+
+        // Near the left edge, a popup on the left side should flip to the right side
+        assert_domain(
+            [0.125, 0.25, 0.375, 0.75],
+            place(0.125, 0.75, PopupSide::Left),
+        );
+        assert_domain([0.5, 0.5, 0.75, 1.0], place(0.75, 1.0, PopupSide::Left));
+    }
+
+    #[test]
+    fn test_place_next_to() {
+        let bounds = ComponentDomain::between(0.0, 0.0, 1.0, 1.0);
+        let button = ComponentDomain::between(0.5, 0.75, 0.75, 0.875);
+        let place = |width: f32, height: f32, side: PopupSide| {
+            PopupPlacement::next_to(button, width, height, side).place(bounds)
+        };
+
+        assert_domain([0.5, 0.5, 0.75, 0.75], place(0.25, 0.25, PopupSide::Below));
+        assert_domain(
+            [0.5, 0.875, 0.75, 1.0],
+            place(0.25, 0.125, PopupSide::Above),
+        );
+
+        // When neither side has enough room, the popup should stay inside the bounds
+        assert_domain([0.5, 0.0, 0.75, 1.0], place(0.25, 1.0, PopupSide::Above));
+
+        assert_domain(
+            [0.75, 0.625, 1.0, 0.875],
+            place(0.25, 0.25, PopupSide::Right),
+        );
+        assert_domain([0.0, 0.625, 0.5, 0.875], place(0.5, 0.25, PopupSide::Right));
+
+        // Popups that are larger than the bounds should stick to the top-left corner
+        assert_domain([0.0, -0.5, 1.5, 1.0], place(1.5, 1.5, PopupSide::Below));
+    }
+
+    #[test]
+    fn test_transform_back() {
+        let placement = PopupPlacement::at_point(Point::new(0.5, 0.5), 0.5, 1.0, PopupSide::Left);
+        let transformed = placement.transform_back(ComponentDomain::between(0.5, 0.0, 1.0, 0.5));
+        assert_domain([0.75, 0.25, 0.75, 0.25], transformed.get_anchor());
+        assert_eq!(0.25, transformed.get_width());
+        assert_eq!(0.5, transformed.get_height());
+        assert_eq!(PopupSide::Left, transformed.get_side());
+    }
+}
@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct ControllerState {
+    offset: f32,
+    change_listeners: Vec<Box<dyn FnMut(f32)>>,
+}
+
+/// Links the scroll offsets of multiple `ScrollPane`s along 1 axis, which is useful for things
+/// like the frozen columns of a table, or the line number gutter next to a code editor. Cloning a
+/// `ScrollController` gives another reference to the *same* controller.
+///
+/// Use `ScrollPane.set_horizontal_controller` or `ScrollPane.set_vertical_controller` to link a
+/// pane to a controller. Whenever a linked pane is scrolled (by the user or by the application),
+/// it will update the offset of the controller. The other linked panes will follow during their
+/// next `TickEvent`. Each pane clamps the offset to its own content size, so panes with a different
+/// content size can still be linked.
+///
+/// ## Change listeners
+/// Change listeners will be called with the new offset whenever the offset actually changed,
+/// regardless of whether it was changed by a pane or by `set_offset`.
+#[derive(Clone)]
+pub struct ScrollController {
+    state: Rc<RefCell<ControllerState>>,
+}
+
+impl ScrollController {
+    /// Constructs a new `ScrollController` with an offset of 0.0
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(ControllerState {
+                offset: 0.0,
+                change_listeners: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn get_offset(&self) -> f32 {
+        self.state.borrow().offset
+    }
+
+    /// Changes the offset of this controller, and thereby the scroll offsets of all linked panes
+    pub fn set_offset(&self, offset: f32) {
+        let mut state = self.state.borrow_mut();
+        if state.offset == offset {
+            return;
+        }
+        state.offset = offset;
+
+        // Temporarily take the listeners out of the state, so that they can use this controller
+        let mut listeners = std::mem::take(&mut state.change_listeners);
+        drop(state);
+        for listener in &mut listeners {
+            listener(offset);
+        }
+
+        // Keep any listeners that were somehow added during the notification
+        let mut state = self.state.borrow_mut();
+        listeners.append(&mut state.change_listeners);
+        state.change_listeners = listeners;
+    }
+
+    pub fn add_change_listener(&self, listener: Box<dyn FnMut(f32)>) {
+        self.state.borrow_mut().change_listeners.push(listener);
+    }
+}
+
+impl Default for ScrollController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_offset_and_listeners() {
+        let controller = ScrollController::new();
+        let shared = controller.clone();
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let listener_changes = Rc::clone(&changes);
+        controller.add_change_listener(Box::new(move |offset| {
+            listener_changes.borrow_mut().push(offset)
+        }));
+
+        assert_eq!(0.0, shared.get_offset());
+        shared.set_offset(1.5);
+        assert_eq!(1.5, controller.get_offset());
+
+        // Setting the same offset again is not a change
+        controller.set_offset(1.5);
+        controller.set_offset(0.5);
+        assert_eq!(vec![1.5, 0.5], *changes.borrow());
+    }
+}
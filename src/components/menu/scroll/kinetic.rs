@@ -0,0 +1,123 @@
+/// A fling stops once its velocity drops below this value (in visible areas per second)
+const MIN_FLING_SPEED: f32 = 0.01;
+
+/// Determines what happens when a fling of a `ScrollPane` reaches the start or end of its content
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ScrollEdgeBehavior {
+    /// The fling stops at the edge
+    Clamp,
+    /// The fling bounces back from the edge. The *restitution* is the fraction of the velocity
+    /// that remains after the bounce, so it should be between 0.0 and 1.0.
+    Bounce { restitution: f32 },
+}
+
+/// The settings for kinetic (fling) scrolling of a `ScrollPane`, see
+/// `ScrollPaneStyle.kinetic_scrolling`. When the user swipes over the visible area of a pane, the
+/// pane will keep scrolling in the direction of the swipe, and the velocity will gradually
+/// decrease due to *friction*.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct KineticScrolling {
+    /// Determines how quickly a fling slows down: its velocity is multiplied by `e^(-friction)`
+    /// each second
+    pub friction: f32,
+    pub edge_behavior: ScrollEdgeBehavior,
+}
+
+impl Default for KineticScrolling {
+    fn default() -> Self {
+        Self {
+            friction: 3.0,
+            edge_behavior: ScrollEdgeBehavior::Clamp,
+        }
+    }
+}
+
+/// The state of an ongoing fling of a `ScrollPane`. The velocities are expressed in visible areas
+/// per second, like the scroll offsets of the pane.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(super) struct Fling {
+    pub(super) velocity_x: f32,
+    pub(super) velocity_y: f32,
+}
+
+impl Fling {
+    pub(super) fn is_finished(&self) -> bool {
+        self.velocity_x == 0.0 && self.velocity_y == 0.0
+    }
+
+    /// Advances this fling by *delta_time* seconds, and returns the new scroll offsets
+    pub(super) fn advance(
+        &mut self,
+        scroll: (f32, f32),
+        max_scroll: (f32, f32),
+        settings: KineticScrolling,
+        delta_time: f32,
+    ) -> (f32, f32) {
+        let (scroll_x, velocity_x) =
+            advance_axis(scroll.0, self.velocity_x, max_scroll.0, settings, delta_time);
+        let (scroll_y, velocity_y) =
+            advance_axis(scroll.1, self.velocity_y, max_scroll.1, settings, delta_time);
+        self.velocity_x = velocity_x;
+        self.velocity_y = velocity_y;
+        (scroll_x, scroll_y)
+    }
+}
+
+/// Advances the fling along a single axis, and returns the new offset and velocity
+fn advance_axis(
+    offset: f32,
+    velocity: f32,
+    max_offset: f32,
+    settings: KineticScrolling,
+    delta_time: f32,
+) -> (f32, f32) {
+    let mut offset = offset + velocity * delta_time;
+    let mut velocity = velocity;
+    if offset < 0.0 || offset > max_offset {
+        offset = offset.max(0.0).min(max_offset);
+        velocity = match settings.edge_behavior {
+            ScrollEdgeBehavior::Clamp => 0.0,
+            ScrollEdgeBehavior::Bounce { restitution } => -velocity * restitution,
+        };
+    }
+
+    velocity *= (-settings.friction * delta_time).exp();
+    if velocity.abs() < MIN_FLING_SPEED {
+        velocity = 0.0;
+    }
+    (offset, velocity)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_advance_axis() {
+        let clamp = KineticScrolling {
+            friction: 2.0,
+            edge_behavior: ScrollEdgeBehavior::Clamp,
+        };
+        let bounce = KineticScrolling {
+            friction: 2.0,
+            edge_behavior: ScrollEdgeBehavior::Bounce { restitution: 0.5 },
+        };
+        let decay = (-0.2f32).exp();
+
+        // Friction should slow the fling down
+        let (offset, velocity) = advance_axis(1.0, 2.0, 3.0, clamp, 0.1);
+        assert!((offset - 1.2).abs() < 0.0001);
+        assert!((velocity - 2.0 * decay).abs() < 0.0001);
+
+        // Flings should stop or bounce back at the edges
+        assert_eq!((3.0, 0.0), advance_axis(2.9, 2.0, 3.0, clamp, 0.1));
+        assert_eq!((0.0, 0.0), advance_axis(0.1, -2.0, 3.0, clamp, 0.1));
+        let (offset, velocity) = advance_axis(2.9, 2.0, 3.0, bounce, 0.1);
+        assert_eq!(3.0, offset);
+        assert!((velocity + decay).abs() < 0.0001);
+
+        // Slow flings should stop
+        assert_eq!(0.0, advance_axis(1.0, 0.005, 3.0, clamp, 0.1).1);
+    }
+}
@@ -0,0 +1,1237 @@
+mod controller;
+mod kinetic;
+mod paging;
+
+pub use controller::*;
+pub use kinetic::*;
+pub use paging::*;
+
+use crate::*;
+
+/// The appearance and behavior of a `ScrollPane`
+pub struct ScrollPaneStyle {
+    /// The color behind the content, which is visible when the content doesn't draw on its
+    /// entire domain
+    pub background_color: Color,
+    pub track_color: Color,
+    pub thumb_color: Color,
+    /// The thickness of the scrollbars, as a fraction of the size of the pane
+    pub bar_size: f32,
+    /// The distance that will be scrolled for each *line* of a `MouseScrollEvent`, as a fraction
+    /// of the visible part of the content
+    pub scroll_speed: f32,
+    /// When this is `Some`, the pane will keep scrolling after the user swipes over its visible
+    /// area (see `KineticScrolling`). This is `None` by default.
+    pub kinetic_scrolling: Option<KineticScrolling>,
+}
+
+impl ScrollPaneStyle {
+    /// Creates a `ScrollPaneStyle` that draws the content on the surface color of the given
+    /// `Palette`, and uses its outline color for the scrollbar thumbs.
+    pub fn from_palette(palette: &Palette) -> Self {
+        Self {
+            background_color: palette.get_color(ColorRole::Surface),
+            track_color: palette.get_color(ColorRole::Background),
+            thumb_color: palette.get_color(ColorRole::Outline),
+            bar_size: 0.03,
+            scroll_speed: 0.1,
+            kinetic_scrolling: None,
+        }
+    }
+
+    /// Creates the `ScrollPaneStyle` for the palette of the given `Theme`
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self::from_palette(&theme.palette)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ScrollAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// The state of a scrollbar thumb that is being dragged
+#[derive(Copy, Clone, Debug)]
+struct ThumbDrag {
+    mouse: Mouse,
+    axis: ScrollAxis,
+    start_point: Point,
+    start_scroll: f32,
+}
+
+type LoadMoreListener = Box<dyn FnMut(&mut dyn ComponentBuddy)>;
+
+struct Paging {
+    controller: PagingController,
+    load_more: LoadMoreListener,
+}
+
+/// A container that hosts a single child component in a *virtual* domain that is larger than the
+/// pane itself. Only part of the child will be visible at any time, and the user can choose which
+/// part by scrolling, or by dragging the thumbs of the scrollbars.
+///
+/// The size of the content is expressed relative to the visible area (the part of the pane that is
+/// not occupied by scrollbars), so a content height of 3.0 means that the child is 3 times as high
+/// as the visible area. A scrollbar will only be shown for the axes where the content is larger
+/// than the visible area.
+///
+/// The scroll offsets are also expressed relative to the visible area, and they are measured from
+/// the top-left corner of the content: a scroll y of 0.0 means that the top of the content is
+/// visible, and a scroll y of `content_height - 1.0` means that the bottom is visible.
+///
+/// Mouse events are only propagated to the child when they happen in the visible area, and the
+/// rendering of the child is clipped to the visible area using the scissor of the `Renderer`.
+/// Scroll events are used by the pane itself, but the part that the pane can't apply (because it
+/// is already scrolled to the end of its content) is propagated to the child.
+///
+/// When the style enables kinetic scrolling, swipes over the visible area will start a *fling*,
+/// which keeps scrolling during the next `TickEvent`s until friction stops it. Pressing any mouse
+/// button or scrolling with the mouse wheel will stop the fling immediately.
+pub struct ScrollPane {
+    menu: SimpleFlatMenu,
+    child: ComponentHandle,
+    style: ScrollPaneStyle,
+    theme_follower: Option<ThemeFollower<ScrollPaneStyle>>,
+
+    content_width: f32,
+    content_height: f32,
+    scroll_x: f32,
+    scroll_y: f32,
+    horizontal_controller: Option<ScrollController>,
+    vertical_controller: Option<ScrollController>,
+    paging: Option<Paging>,
+
+    thumb_drag: Option<ThumbDrag>,
+    fling: Option<Fling>,
+    needs_full_render: bool,
+}
+
+impl ScrollPane {
+    /// Constructs a new `ScrollPane` that hosts the given *child*. The *content_width* and
+    /// *content_height* will be clamped to at least 1.0. The pane will initially show the top-left
+    /// part of the child.
+    pub fn new(
+        child: Box<dyn Component>,
+        content_width: f32,
+        content_height: f32,
+        style: ScrollPaneStyle,
+    ) -> Self {
+        let mut menu = SimpleFlatMenu::new(Some(style.background_color));
+        let child = menu.add_component(child, ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        let mut pane = Self {
+            menu,
+            child,
+            style,
+            theme_follower: None,
+            content_width: content_width.max(1.0),
+            content_height: content_height.max(1.0),
+            scroll_x: 0.0,
+            scroll_y: 0.0,
+            horizontal_controller: None,
+            vertical_controller: None,
+            paging: None,
+            thumb_drag: None,
+            fling: None,
+            needs_full_render: false,
+        };
+        pane.update_child_domain();
+        pane
+    }
+
+    /// Lets this pane follow the `Theme` of its buddy, see `StyleOverrides.resolve_theme`
+    pub fn follow_theme(&mut self, overrides: StyleOverrides<ScrollPaneStyle>) {
+        self.theme_follower = Some(ThemeFollower::new(overrides));
+    }
+
+    pub fn get_content_width(&self) -> f32 {
+        self.content_width
+    }
+
+    pub fn get_content_height(&self) -> f32 {
+        self.content_height
+    }
+
+    /// Changes the size of the content (both will be clamped to at least 1.0). The scroll offsets
+    /// will be clamped to the new size, but this won't affect the linked `ScrollController`s.
+    pub fn set_content_size(&mut self, content_width: f32, content_height: f32) {
+        self.content_width = content_width.max(1.0);
+        self.content_height = content_height.max(1.0);
+        self.set_clamped_scroll(self.scroll_x, self.scroll_y);
+    }
+
+    pub fn get_scroll_x(&self) -> f32 {
+        self.scroll_x
+    }
+
+    pub fn get_scroll_y(&self) -> f32 {
+        self.scroll_y
+    }
+
+    pub fn get_max_scroll_x(&self) -> f32 {
+        self.content_width - 1.0
+    }
+
+    pub fn get_max_scroll_y(&self) -> f32 {
+        self.content_height - 1.0
+    }
+
+    /// Scrolls to the given offsets, which will be clamped between 0.0 and the maximum scroll
+    /// offsets. The linked `ScrollController`s (if any) will be updated as well.
+    pub fn scroll_to(&mut self, scroll_x: f32, scroll_y: f32) {
+        let old_scroll = (self.scroll_x, self.scroll_y);
+        self.set_clamped_scroll(scroll_x, scroll_y);
+
+        // Only propagate real changes, so that linked panes with a smaller content size don't
+        // drag the other panes along when they clamp the offset
+        let publish = |controller: &Option<ScrollController>, old: f32, new: f32| {
+            if let Some(controller) = controller {
+                if old != new {
+                    controller.set_offset(new);
+                }
+            }
+        };
+        publish(&self.horizontal_controller, old_scroll.0, self.scroll_x);
+        publish(&self.vertical_controller, old_scroll.1, self.scroll_y);
+    }
+
+    /// Scrolls *delta_x* to the right and *delta_y* down. See `scroll_to`.
+    pub fn scroll_by(&mut self, delta_x: f32, delta_y: f32) {
+        self.scroll_to(self.scroll_x + delta_x, self.scroll_y + delta_y);
+    }
+
+    fn set_clamped_scroll(&mut self, scroll_x: f32, scroll_y: f32) {
+        self.scroll_x = scroll_x.max(0.0).min(self.get_max_scroll_x());
+        self.scroll_y = scroll_y.max(0.0).min(self.get_max_scroll_y());
+        self.update_child_domain();
+    }
+
+    /// Links the horizontal scroll offset of this pane to the given *controller* (or unlinks it
+    /// when *controller* is `None`). This pane will immediately scroll to the offset of the
+    /// controller.
+    ///
+    /// The pane will follow the changes of the controller during its `TickEvent`s, so this should
+    /// be called before the pane is attached.
+    pub fn set_horizontal_controller(&mut self, controller: Option<ScrollController>) {
+        self.horizontal_controller = controller;
+        self.follow_controllers();
+    }
+
+    /// Links the vertical scroll offset of this pane to the given *controller*. See
+    /// `set_horizontal_controller`.
+    pub fn set_vertical_controller(&mut self, controller: Option<ScrollController>) {
+        self.vertical_controller = controller;
+        self.follow_controllers();
+    }
+
+    /// Links this pane to the given paging *controller*: the pane will call *load_more* when the
+    /// user scrolls near the bottom of its content. See `PagingController` for the details.
+    ///
+    /// Like `set_horizontal_controller`, this should be called before the pane is attached.
+    pub fn set_paging(&mut self, controller: PagingController, load_more: LoadMoreListener) {
+        self.paging = Some(Paging { controller, load_more });
+    }
+
+    fn has_controllers(&self) -> bool {
+        self.horizontal_controller.is_some() || self.vertical_controller.is_some()
+    }
+
+    /// Applies the content height delivered by the paging controller (if any), and calls the
+    /// *load more* listener if the pane is scrolled near the bottom of its content
+    fn update_paging(&mut self, buddy: &mut dyn ComponentBuddy) {
+        let controller = match &self.paging {
+            Some(paging) => paging.controller.clone(),
+            None => return,
+        };
+
+        if let Some(content_height) = controller.take_new_content_height() {
+            self.set_content_size(self.content_width, content_height);
+            buddy.request_render();
+        }
+
+        let remaining_scroll = self.get_max_scroll_y() - self.scroll_y;
+        if controller.start_loading_if_needed(remaining_scroll) {
+            let paging = self.paging.as_mut().expect("We just checked that paging is enabled");
+            (paging.load_more)(buddy);
+        }
+    }
+
+    /// Scrolls to the offsets of the linked controllers, without updating the controllers
+    /// themselves. Returns true if the scroll offsets changed.
+    fn follow_controllers(&mut self) -> bool {
+        let follow = |controller: &Option<ScrollController>, scroll: f32| match controller {
+            Some(controller) => controller.get_offset(),
+            None => scroll,
+        };
+        let scroll_x = follow(&self.horizontal_controller, self.scroll_x);
+        let scroll_y = follow(&self.vertical_controller, self.scroll_y);
+
+        let old_scroll = (self.scroll_x, self.scroll_y);
+        let new_scroll = (
+            scroll_x.max(0.0).min(self.get_max_scroll_x()),
+            scroll_y.max(0.0).min(self.get_max_scroll_y()),
+        );
+        if new_scroll != old_scroll {
+            self.set_clamped_scroll(scroll_x, scroll_y);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn has_horizontal_bar(&self) -> bool {
+        self.content_width > 1.0
+    }
+
+    fn has_vertical_bar(&self) -> bool {
+        self.content_height > 1.0
+    }
+
+    /// Gets the part of this pane where the child is visible (everything except the scrollbars)
+    fn get_visible_domain(&self) -> ComponentDomain {
+        let max_x = match self.has_vertical_bar() {
+            true => 1.0 - self.style.bar_size,
+            false => 1.0,
+        };
+        let min_y = match self.has_horizontal_bar() {
+            true => self.style.bar_size,
+            false => 0.0,
+        };
+        ComponentDomain::between(0.0, min_y, max_x, 1.0)
+    }
+
+    fn get_child_domain(&self) -> ComponentDomain {
+        let visible = self.get_visible_domain();
+        let width = self.content_width * visible.get_width();
+        let height = self.content_height * visible.get_height();
+        let min_x = visible.get_min_x() - self.scroll_x * visible.get_width();
+        let max_y = visible.get_max_y() + self.scroll_y * visible.get_height();
+        ComponentDomain::between(min_x, max_y - height, min_x + width, max_y)
+    }
+
+    fn update_child_domain(&mut self) {
+        self.menu
+            .move_component(self.child, self.get_child_domain());
+        self.needs_full_render = true;
+    }
+
+    fn get_track_domain(&self, axis: ScrollAxis) -> ComponentDomain {
+        let visible = self.get_visible_domain();
+        match axis {
+            ScrollAxis::Horizontal => {
+                ComponentDomain::between(0.0, 0.0, visible.get_max_x(), visible.get_min_y())
+            }
+            ScrollAxis::Vertical => {
+                ComponentDomain::between(visible.get_max_x(), visible.get_min_y(), 1.0, 1.0)
+            }
+        }
+    }
+
+    fn get_thumb_domain(&self, axis: ScrollAxis) -> ComponentDomain {
+        let track = self.get_track_domain(axis);
+        match axis {
+            ScrollAxis::Horizontal => {
+                let length = track.get_width() / self.content_width;
+                let min_x = track.get_min_x() + length * self.scroll_x;
+                ComponentDomain::between(
+                    min_x,
+                    track.get_min_y(),
+                    min_x + length,
+                    track.get_max_y(),
+                )
+            }
+            ScrollAxis::Vertical => {
+                let length = track.get_height() / self.content_height;
+                let max_y = track.get_max_y() - length * self.scroll_y;
+                ComponentDomain::between(
+                    track.get_min_x(),
+                    max_y - length,
+                    track.get_max_x(),
+                    max_y,
+                )
+            }
+        }
+    }
+
+    fn get_bar_axes(&self) -> Vec<ScrollAxis> {
+        let mut axes = Vec::with_capacity(2);
+        if self.has_horizontal_bar() {
+            axes.push(ScrollAxis::Horizontal);
+        }
+        if self.has_vertical_bar() {
+            axes.push(ScrollAxis::Vertical);
+        }
+        axes
+    }
+
+    fn get_scroll(&self, axis: ScrollAxis) -> f32 {
+        match axis {
+            ScrollAxis::Horizontal => self.scroll_x,
+            ScrollAxis::Vertical => self.scroll_y,
+        }
+    }
+
+    fn set_scroll(&mut self, axis: ScrollAxis, scroll: f32, buddy: &mut dyn ComponentBuddy) {
+        match axis {
+            ScrollAxis::Horizontal => self.scroll_to(scroll, self.scroll_y),
+            ScrollAxis::Vertical => self.scroll_to(self.scroll_x, scroll),
+        };
+        buddy.request_render();
+    }
+
+    fn is_visible(&self, point: Point) -> bool {
+        self.get_visible_domain().is_inside(point)
+    }
+
+    /// Handles a press on the scrollbar of the given *axis*: pressing the thumb starts dragging
+    /// it, and pressing the track next to the thumb scrolls 1 page towards the point.
+    fn press_bar(
+        &mut self,
+        axis: ScrollAxis,
+        event: MousePressEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        let point = event.get_point();
+        let thumb = self.get_thumb_domain(axis);
+        if thumb.is_inside(point) {
+            self.thumb_drag = Some(ThumbDrag {
+                mouse: event.get_mouse(),
+                axis,
+                start_point: point,
+                start_scroll: self.get_scroll(axis),
+            });
+            buddy.capture_mouse(event.get_mouse());
+        } else {
+            let forward = match axis {
+                ScrollAxis::Horizontal => point.get_x() > thumb.get_max_x(),
+                ScrollAxis::Vertical => point.get_y() < thumb.get_min_y(),
+            };
+            let page = if forward { 1.0 } else { -1.0 };
+            self.set_scroll(axis, self.get_scroll(axis) + page, buddy);
+        }
+    }
+
+    fn drag_thumb(&mut self, drag: ThumbDrag, to: Point, buddy: &mut dyn ComponentBuddy) {
+        let track = self.get_track_domain(drag.axis);
+
+        // Moving the thumb along the entire track would scroll along the entire content
+        let delta = match drag.axis {
+            ScrollAxis::Horizontal => {
+                (to.get_x() - drag.start_point.get_x()) / track.get_width() * self.content_width
+            }
+            ScrollAxis::Vertical => {
+                (drag.start_point.get_y() - to.get_y()) / track.get_height() * self.content_height
+            }
+        };
+        self.set_scroll(drag.axis, drag.start_scroll + delta, buddy);
+    }
+
+    /// Starts a fling with the velocity of the given *swipe*, if kinetic scrolling is enabled
+    fn start_fling(&mut self, swipe: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.style.kinetic_scrolling.is_none() {
+            return;
+        }
+
+        // The swipe velocity is relative to the size of the pane, but the scroll offsets are
+        // relative to the visible area. Swiping up or to the left moves the content that way.
+        let visible = self.get_visible_domain();
+        let fling = Fling {
+            velocity_x: -swipe.get_velocity_x() / visible.get_width(),
+            velocity_y: swipe.get_velocity_y() / visible.get_height(),
+        };
+        if !fling.is_finished() {
+            self.fling = Some(fling);
+            buddy.subscribe_tick();
+        }
+    }
+
+    fn advance_fling(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        let (mut fling, settings) = match (self.fling, self.style.kinetic_scrolling) {
+            (Some(fling), Some(settings)) => (fling, settings),
+            _ => {
+                self.fling = None;
+                return;
+            }
+        };
+
+        let scroll = (self.scroll_x, self.scroll_y);
+        let max_scroll = (self.get_max_scroll_x(), self.get_max_scroll_y());
+        let (scroll_x, scroll_y) =
+            fling.advance(scroll, max_scroll, settings, event.get_delta_seconds());
+        self.scroll_to(scroll_x, scroll_y);
+        buddy.request_render();
+
+        if fling.is_finished() {
+            self.fling = None;
+        } else {
+            self.fling = Some(fling);
+            buddy.subscribe_tick();
+        }
+    }
+
+    fn render_bars(&self, renderer: &Renderer) {
+        let fill = |domain: ComponentDomain, color: Color| {
+            renderer.push_viewport(
+                domain.get_min_x(),
+                domain.get_min_y(),
+                domain.get_max_x(),
+                domain.get_max_y(),
+                || renderer.clear(color),
+            );
+        };
+
+        for axis in self.get_bar_axes() {
+            fill(self.get_track_domain(axis), self.style.track_color);
+            fill(self.get_thumb_domain(axis), self.style.thumb_color);
+        }
+
+        // The corner between the 2 scrollbars
+        if self.has_horizontal_bar() && self.has_vertical_bar() {
+            let visible = self.get_visible_domain();
+            let corner =
+                ComponentDomain::between(visible.get_max_x(), 0.0, 1.0, visible.get_min_y());
+            fill(corner, self.style.track_color);
+        }
+    }
+}
+
+impl Component for ScrollPane {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy);
+        if self.has_controllers() || self.paging.is_some() {
+            buddy.subscribe_tick();
+        }
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        if let Some(follower) = &mut self.theme_follower {
+            if follower.update(&mut self.style, buddy) {
+                self.menu.set_background_color(Some(self.style.background_color));
+                self.update_child_domain();
+            }
+        }
+
+        let force = force || std::mem::take(&mut self.needs_full_render);
+
+        // The child domain is larger than the visible domain, so clip it
+        let visible = self.get_visible_domain();
+        let menu = &mut self.menu;
+        let maybe_result = renderer.push_scissor(
+            visible.get_min_x(),
+            visible.get_min_y(),
+            visible.get_max_x(),
+            visible.get_max_y(),
+            || menu.render(renderer, buddy, force),
+        );
+
+        if force {
+            if let Some(result) = maybe_result {
+                result?;
+            }
+            self.render_bars(renderer);
+            entire_render_result()
+        } else {
+            match maybe_result {
+                Some(result) => result,
+                None => Ok(RenderResultStruct {
+                    drawn_region: Box::new(CompositeDrawnRegion::new(Vec::new())),
+                    filter_mouse_actions: false,
+                }),
+            }
+        }
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_point()) {
+            self.menu.on_mouse_click(event, buddy);
+        } else {
+            let out_event = MouseClickOutEvent::new(event.get_mouse(), event.get_button());
+            self.menu.on_mouse_click_out(out_event, buddy);
+        }
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.fling = None;
+        if self.is_visible(event.get_point()) {
+            self.menu.on_mouse_press(event, buddy);
+        } else if let Some(axis) = self
+            .get_bar_axes()
+            .into_iter()
+            .find(|axis| self.get_track_domain(*axis).is_inside(event.get_point()))
+        {
+            self.press_bar(axis, event, buddy);
+        }
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some(drag) = self.thumb_drag {
+            if drag.mouse == event.get_mouse() {
+                self.thumb_drag = None;
+                buddy.release_mouse_capture(event.get_mouse());
+                return;
+            }
+        }
+
+        // If the child captured the mouse, it should get the event, regardless of its position
+        if self.is_visible(event.get_point()) || buddy.is_capturing_mouse(event.get_mouse()) {
+            self.menu.on_mouse_release(event, buddy);
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some(drag) = self.thumb_drag {
+            if drag.mouse == event.get_mouse() {
+                self.drag_thumb(drag, event.get_to(), buddy);
+                return;
+            }
+        }
+
+        if buddy.is_capturing_mouse(event.get_mouse()) {
+            self.menu.on_mouse_move(event, buddy);
+            return;
+        }
+
+        // The child should only 'see' the part of the movement that is in the visible domain
+        let visible = self.get_visible_domain();
+        let visible_region = RectangularDrawnRegion::new(
+            visible.get_min_x(),
+            visible.get_min_y(),
+            visible.get_max_x(),
+            visible.get_max_y(),
+        );
+        let mouse = event.get_mouse();
+        let details = event.get_details();
+        match visible_region.find_line_intersection(event.get_from(), event.get_to()) {
+            LineIntersection::FullyOutside => {}
+            LineIntersection::FullyInside => self.menu.on_mouse_move(event, buddy),
+            LineIntersection::Enters { point } => {
+                self.menu
+                    .on_mouse_enter(MouseEnterEvent::new(mouse, point), buddy);
+                let move_event =
+                    MouseMoveEvent::with_details(mouse, point, event.get_to(), details);
+                self.menu.on_mouse_move(move_event, buddy);
+            }
+            LineIntersection::Exits { point } => {
+                let move_event =
+                    MouseMoveEvent::with_details(mouse, event.get_from(), point, details);
+                self.menu.on_mouse_move(move_event, buddy);
+                self.menu
+                    .on_mouse_leave(MouseLeaveEvent::new(mouse, point), buddy);
+            }
+            LineIntersection::Crosses { entrance, exit } => {
+                self.menu
+                    .on_mouse_enter(MouseEnterEvent::new(mouse, entrance), buddy);
+                let move_event = MouseMoveEvent::with_details(mouse, entrance, exit, details);
+                self.menu.on_mouse_move(move_event, buddy);
+                self.menu
+                    .on_mouse_leave(MouseLeaveEvent::new(mouse, exit), buddy);
+            }
+        };
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_entrance_point()) {
+            self.menu.on_mouse_enter(event, buddy);
+        }
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_exit_point()) {
+            self.menu.on_mouse_leave(event, buddy);
+        }
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.fling = None;
+
+        // A positive delta y means that the user scrolled up
+        let speed = self.style.scroll_speed;
+        let old_scroll = (self.scroll_x, self.scroll_y);
+        let target_x = self.scroll_x + event.get_delta_x() * speed;
+        let target_y = self.scroll_y - event.get_delta_y() * speed;
+        self.scroll_to(target_x, target_y);
+        if (self.scroll_x, self.scroll_y) != old_scroll {
+            buddy.request_render();
+        }
+
+        // The child gets the part of the scroll that this pane couldn't apply because it reached
+        // the end of its content. The excess is exactly 0.0 when nothing was clamped.
+        let to_lines = |excess: f32| if excess == 0.0 { 0.0 } else { excess / speed };
+        let remaining_x = to_lines(target_x - self.scroll_x);
+        let remaining_y = -to_lines(target_y - self.scroll_y);
+        if (remaining_x != 0.0 || remaining_y != 0.0) && self.is_visible(event.get_point()) {
+            let mouse = event.get_mouse();
+            let remaining_event =
+                MouseScrollEvent::new(mouse, event.get_point(), remaining_x, remaining_y);
+            self.menu.on_mouse_scroll(remaining_event, buddy);
+        }
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        if self.is_visible(event.get_point()) {
+            self.menu.on_mouse_double_click(event, buddy);
+        }
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_point()) {
+            self.menu.on_mouse_long_press(event, buddy);
+        }
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        if self.is_visible(event.get_point()) {
+            self.menu.on_mouse_context_click(event, buddy);
+        }
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        if self.is_visible(event.get_point()) {
+            self.menu.on_mouse_middle_click(event, buddy);
+        }
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_point()) {
+            self.menu.on_mouse_hover(event, buddy);
+        }
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_center()) {
+            self.menu.on_pinch(event, buddy);
+        }
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_center()) {
+            self.menu.on_rotate_gesture(event, buddy);
+        }
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.is_visible(event.get_start_point()) {
+            self.menu.on_swipe(event, buddy);
+            self.start_fling(event, buddy);
+        }
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy);
+
+        if self.has_controllers() {
+            // The menu unsubscribes when its child doesn't need ticks, but the controllers do
+            buddy.subscribe_tick();
+            if self.follow_controllers() {
+                buddy.request_render();
+            }
+        }
+
+        // The same holds for flings
+        if self.fling.is_some() {
+            self.advance_fling(event, buddy);
+        }
+
+        // And for paging
+        if self.paging.is_some() {
+            buddy.subscribe_tick();
+            self.update_paging(buddy);
+        }
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        match self.is_visible(point) {
+            true => self.menu.get_accessible_text_at(point),
+            false => None,
+        }
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        match self.is_visible(point) {
+            true => self.menu.get_cursor_at(point),
+            false => None,
+        }
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        match self.is_visible(point) {
+            true => self.menu.get_window_frame_area_at(point),
+            false => None,
+        }
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    /// The bounds (min x, min y, width, height) of a viewport in the coordinate system of the
+    /// window, where the minimum coordinates can be negative
+    type WindowViewport = (i64, i64, u32, u32);
+
+    fn get_window_viewport(renderer: &Renderer) -> WindowViewport {
+        let viewport = renderer.get_viewport();
+        let (target, [offset_x, offset_y, _, _]) = renderer.get_target_viewport();
+        let min_x = target.get_min_x() as f32 + offset_x * target.get_width() as f32;
+        let min_y = target.get_min_y() as f32 + offset_y * target.get_height() as f32;
+        (
+            min_x.round() as i64,
+            min_y.round() as i64,
+            viewport.get_width(),
+            viewport.get_height(),
+        )
+    }
+
+    struct ContentComponent {
+        viewports: Rc<RefCell<Vec<WindowViewport>>>,
+        clicks: Rc<RefCell<Vec<Point>>>,
+    }
+
+    impl Component for ContentComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            self.viewports.borrow_mut().push(get_window_viewport(renderer));
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.clicks.borrow_mut().push(event.get_point());
+        }
+    }
+
+    fn test_style() -> ScrollPaneStyle {
+        ScrollPaneStyle {
+            bar_size: 0.1,
+            scroll_speed: 0.25,
+            ..ScrollPaneStyle::from_palette(&Palette::default())
+        }
+    }
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 0.0001,
+            "Expected {}, but got {}",
+            expected,
+            actual
+        );
+    }
+
+    type Log<T> = Rc<RefCell<Vec<T>>>;
+
+    fn content_pane(content_height: f32) -> (ScrollPane, Log<WindowViewport>, Log<Point>) {
+        let viewports = Rc::new(RefCell::new(Vec::new()));
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let content = ContentComponent {
+            viewports: Rc::clone(&viewports),
+            clicks: Rc::clone(&clicks),
+        };
+        let pane = ScrollPane::new(Box::new(content), 1.0, content_height, test_style());
+        (pane, viewports, clicks)
+    }
+
+    #[test]
+    fn test_scrolling() {
+        let (pane, viewports, clicks) = content_pane(3.0);
+        let mut application = Application::new(Box::new(pane));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+        let mouse = Mouse::new(0);
+        let click =
+            |x: f32, y: f32| MouseClickEvent::new(mouse, Point::new(x, y), MouseButton::primary());
+        let scroll_down = MouseScrollEvent::new(mouse, Point::new(0.5, 0.5), 0.0, -1.0);
+
+        // Initially, the top part of the content should be visible
+        application.render(&renderer, false);
+        assert_eq!(
+            vec![(0, -200, 90, 300)],
+            *viewports.borrow()
+        );
+
+        // Scrolling down should move the content up
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.5, 0.5)));
+        application.fire_mouse_scroll_event(scroll_down);
+        application.render(&renderer, false);
+        assert_eq!((0, -175, 90, 300), viewports.borrow()[1]);
+
+        // Clicks in the visible area should reach the content, but clicks on the scrollbar not
+        application.fire_mouse_click_event(click(0.45, 0.5));
+        application.fire_mouse_click_event(click(0.95, 0.5));
+        assert_eq!(1, clicks.borrow().len());
+        assert_close(0.5, clicks.borrow()[0].get_x());
+        assert_close(0.75, clicks.borrow()[0].get_y());
+
+        // Scrolling can't go beyond the content
+        for _counter in 0..20 {
+            application.fire_mouse_scroll_event(scroll_down);
+        }
+        application.render(&renderer, false);
+        assert_eq!((0, 0, 90, 300), *viewports.borrow().last().unwrap());
+    }
+
+    #[test]
+    fn test_forward_remaining_scroll() {
+        struct ScrollComponent {
+            scrolls: Rc<RefCell<Vec<(f32, f32)>>>,
+        }
+
+        impl Component for ScrollComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_scroll();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_scroll(
+                &mut self,
+                event: MouseScrollEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.scrolls.borrow_mut().push((event.get_delta_x(), event.get_delta_y()));
+            }
+        }
+
+        let scrolls = Rc::new(RefCell::new(Vec::new()));
+        let content = ScrollComponent {
+            scrolls: Rc::clone(&scrolls),
+        };
+        let pane = ScrollPane::new(Box::new(content), 1.0, 2.0, test_style());
+        let mut application = Application::new(Box::new(pane));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+        application.render(&renderer, false);
+
+        let mouse = Mouse::new(0);
+        let point = Point::new(0.5, 0.5);
+        let scroll = |delta_y: f32| MouseScrollEvent::new(mouse, point, 0.0, delta_y);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+
+        // The pane can't scroll up or to the side, so the child should get those scrolls
+        application.fire_mouse_scroll_event(scroll(1.0));
+        application.fire_mouse_scroll_event(MouseScrollEvent::new(mouse, point, -2.0, 0.0));
+        assert_eq!(vec![(0.0, 1.0), (-2.0, 0.0)], *scrolls.borrow());
+
+        // The pane can scroll down, so the child shouldn't get this scroll
+        application.fire_mouse_scroll_event(scroll(-3.0));
+        assert_eq!(2, scrolls.borrow().len());
+
+        // The pane can only scroll 1 more line down, so the child should get the other line
+        application.fire_mouse_scroll_event(scroll(-2.0));
+        assert_eq!(3, scrolls.borrow().len());
+        let (remaining_x, remaining_y) = scrolls.borrow()[2];
+        assert_eq!(0.0, remaining_x);
+        assert_close(-1.0, remaining_y);
+    }
+
+    #[test]
+    fn test_scroll_to() {
+        let mut pane = ScrollPane::new(Box::new(DummyComponent {}), 2.0, 0.5, test_style());
+        assert_eq!(2.0, pane.get_content_width());
+        assert_eq!(1.0, pane.get_content_height());
+        assert_eq!(1.0, pane.get_max_scroll_x());
+        assert_eq!(0.0, pane.get_max_scroll_y());
+
+        pane.scroll_to(0.5, 0.5);
+        assert_eq!(0.5, pane.get_scroll_x());
+        assert_eq!(0.0, pane.get_scroll_y());
+
+        pane.scroll_by(0.7, 0.0);
+        assert_eq!(1.0, pane.get_scroll_x());
+
+        pane.set_content_size(1.5, 2.0);
+        assert_eq!(0.5, pane.get_scroll_x());
+        pane.scroll_by(-1.0, 1.5);
+        assert_eq!(0.0, pane.get_scroll_x());
+        assert_eq!(1.0, pane.get_scroll_y());
+    }
+
+    #[test]
+    fn test_scroll_controller() {
+        let controller = ScrollController::new();
+        let (mut big_pane, _, _) = content_pane(4.0);
+        let (mut small_pane, _, _) = content_pane(2.0);
+        big_pane.set_vertical_controller(Some(controller.clone()));
+        small_pane.set_vertical_controller(Some(controller.clone()));
+
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        small_pane.on_attach(&mut buddy);
+        assert!(buddy.get_subscriptions().tick);
+
+        // The small pane should follow the big pane during its next tick, as far as it can
+        big_pane.scroll_to(0.0, 0.5);
+        assert_eq!(0.5, controller.get_offset());
+        assert_eq!(0.0, small_pane.get_scroll_y());
+        small_pane.on_tick(TickEvent::new(Duration::from_millis(10)), &mut buddy);
+        assert_eq!(0.5, small_pane.get_scroll_y());
+        assert!(buddy.did_request_render());
+
+        big_pane.scroll_to(0.0, 3.0);
+        small_pane.on_tick(TickEvent::new(Duration::from_millis(10)), &mut buddy);
+        assert_eq!(1.0, small_pane.get_scroll_y());
+
+        // Changing the content size of the small pane shouldn't affect the big pane
+        small_pane.set_content_size(1.0, 1.5);
+        assert_eq!(3.0, controller.get_offset());
+
+        // And the small pane can also control the big pane
+        small_pane.scroll_to(0.0, 0.0);
+        assert_eq!(0.0, controller.get_offset());
+        big_pane.on_tick(TickEvent::new(Duration::from_millis(10)), &mut buddy);
+        assert_eq!(0.0, big_pane.get_scroll_y());
+
+        big_pane.set_vertical_controller(None);
+        controller.set_offset(2.0);
+        big_pane.on_tick(TickEvent::new(Duration::from_millis(10)), &mut buddy);
+        assert_eq!(0.0, big_pane.get_scroll_y());
+    }
+
+    #[test]
+    fn test_kinetic_scrolling() {
+        let kinetic = KineticScrolling {
+            friction: 1.0,
+            edge_behavior: ScrollEdgeBehavior::Clamp,
+        };
+        let (mut pane, _, _) = content_pane(3.0);
+        pane.style.kinetic_scrolling = Some(kinetic);
+
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        pane.on_attach(&mut buddy);
+        assert!(!buddy.get_subscriptions().tick);
+
+        // There is no horizontal scrollbar, so the visible area is as high as the pane
+        let mouse = Mouse::new(0);
+        let swipe = |velocity_y: f32| {
+            SwipeEvent::new(mouse, Point::new(0.5, 0.3), Point::new(0.5, 0.6), 0.0, velocity_y)
+        };
+        let tick = TickEvent::new(Duration::from_millis(100));
+        pane.on_swipe(swipe(0.9), &mut buddy);
+        assert!(buddy.get_subscriptions().tick);
+        pane.on_tick(tick, &mut buddy);
+        assert_close(0.09, pane.get_scroll_y());
+        assert!(buddy.did_request_render());
+
+        // The fling should slow down, and stop after scrolling roughly the initial velocity
+        // divided by the friction
+        for _counter in 0..100 {
+            pane.on_tick(tick, &mut buddy);
+        }
+        assert!(!buddy.get_subscriptions().tick);
+        assert!(pane.get_scroll_y() > 0.85 && pane.get_scroll_y() < 1.0);
+
+        // Fast flings should stop at the end of the content
+        pane.on_swipe(swipe(10.0), &mut buddy);
+        for _counter in 0..10 {
+            pane.on_tick(tick, &mut buddy);
+        }
+        assert_eq!(2.0, pane.get_scroll_y());
+        assert!(!buddy.get_subscriptions().tick);
+
+        // Pressing should stop the fling
+        pane.on_swipe(swipe(-1.0), &mut buddy);
+        let press_point = Point::new(0.5, 0.5);
+        pane.on_mouse_press(
+            MousePressEvent::new(mouse, press_point, MouseButton::primary()),
+            &mut buddy,
+        );
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(2.0, pane.get_scroll_y());
+
+        // Swipes shouldn't start a fling when kinetic scrolling is disabled
+        pane.style.kinetic_scrolling = None;
+        pane.on_swipe(swipe(-1.0), &mut buddy);
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(2.0, pane.get_scroll_y());
+    }
+
+    #[test]
+    fn test_paging() {
+        let controller = PagingController::new(0.5);
+        let load_count = Rc::new(RefCell::new(0));
+        let listener_count = Rc::clone(&load_count);
+        let (mut pane, _, _) = content_pane(2.0);
+        pane.set_paging(controller.clone(), Box::new(move |_buddy| {
+            *listener_count.borrow_mut() += 1;
+        }));
+
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        pane.on_attach(&mut buddy);
+        assert!(buddy.get_subscriptions().tick);
+
+        // The pane is still 1 visible area away from the bottom
+        let tick = TickEvent::new(Duration::from_millis(10));
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(0, *load_count.borrow());
+        assert!(buddy.get_subscriptions().tick);
+
+        // But now it's close enough, and it should only request more content once
+        pane.scroll_to(0.0, 0.6);
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(1, *load_count.borrow());
+        assert!(controller.is_loading());
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(1, *load_count.borrow());
+
+        // The new content should be applied during the next tick
+        controller.finish_loading(4.0, true);
+        assert!(!controller.is_loading());
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(4.0, pane.get_content_height());
+        assert_eq!(0.6, pane.get_scroll_y());
+        assert_eq!(1, *load_count.borrow());
+
+        pane.scroll_to(0.0, 2.8);
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(2, *load_count.borrow());
+
+        // When there is no more content, the pane should stop asking for it
+        controller.finish_loading(4.5, false);
+        pane.on_tick(tick, &mut buddy);
+        pane.scroll_to(0.0, 3.5);
+        pane.on_tick(tick, &mut buddy);
+        assert_eq!(3.5, pane.get_scroll_y());
+        assert_eq!(2, *load_count.borrow());
+        assert!(!controller.has_more());
+    }
+
+    #[test]
+    fn test_scrollbar_dragging() {
+        let (pane, viewports, clicks) = content_pane(4.0);
+        let mut application = Application::new(Box::new(pane));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+        application.render(&renderer, false);
+
+        // The thumb is at the top of the scrollbar, and its height is 0.25
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let thumb_point = Point::new(0.95, 0.9);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, thumb_point));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, thumb_point, button));
+
+        // Dragging the thumb a tenth of the track should scroll a tenth of the content, even when
+        // the mouse leaves the scrollbar
+        let drag_point = Point::new(0.5, 0.8);
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, thumb_point, drag_point));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, drag_point, button));
+        application.render(&renderer, false);
+        assert_eq!((0, -260, 90, 400), *viewports.borrow().last().unwrap());
+
+        // Pressing the track below the thumb should scroll 1 page down
+        let track_point = Point::new(0.95, 0.2);
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, drag_point, track_point));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, track_point, button));
+        application.render(&renderer, false);
+        assert_eq!((0, -160, 90, 400), *viewports.borrow().last().unwrap());
+
+        // None of this should have been a click on the content
+        assert!(clicks.borrow().is_empty());
+    }
+}
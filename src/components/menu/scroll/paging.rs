@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct PagingState {
+    threshold: f32,
+    loading: bool,
+    has_more: bool,
+    new_content_height: Option<f32>,
+}
+
+/// Adds infinite scrolling to a `ScrollPane`: the pane will call its *load more* listener when the
+/// user scrolls near the bottom of its content, after which the application can load more content
+/// (typically in an async task) and call `finish_loading` once it's done. Cloning a
+/// `PagingController` gives another reference to the *same* controller, so the application can
+/// keep a clone to finish the loading later.
+///
+/// Use `ScrollPane.set_paging` to link a pane to a controller. The pane checks whether it needs
+/// more content during each `TickEvent`. It won't call the listener again until the loading is
+/// finished, and it won't call the listener at all once `finish_loading` was called with a
+/// *has_more* of false.
+///
+/// The pane doesn't draw anything while it is loading, but its content can use `is_loading` to
+/// show a loading row at the end.
+#[derive(Clone)]
+pub struct PagingController {
+    state: Rc<RefCell<PagingState>>,
+}
+
+impl PagingController {
+    /// Constructs a new `PagingController` that lets its pane load more content when the pane is
+    /// scrolled within *threshold* of the bottom of its content. Like the scroll offsets of the
+    /// pane, the threshold is relative to the height of the visible area.
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(PagingState {
+                threshold,
+                loading: false,
+                has_more: true,
+                new_content_height: None,
+            })),
+        }
+    }
+
+    pub fn get_threshold(&self) -> f32 {
+        self.state.borrow().threshold
+    }
+
+    /// Checks whether the *load more* listener was called, but `finish_loading` wasn't called yet
+    pub fn is_loading(&self) -> bool {
+        self.state.borrow().loading
+    }
+
+    /// Checks whether there could be more content to load. This is true until `finish_loading` is
+    /// called with a *has_more* of false.
+    pub fn has_more(&self) -> bool {
+        self.state.borrow().has_more
+    }
+
+    /// Should be called when the content requested by the *load more* listener has been loaded.
+    /// The pane will change its content height to *content_height* during its next `TickEvent`.
+    /// When *has_more* is false, the pane won't request more content anymore.
+    pub fn finish_loading(&self, content_height: f32, has_more: bool) {
+        let mut state = self.state.borrow_mut();
+        state.loading = false;
+        state.has_more = has_more;
+        state.new_content_height = Some(content_height);
+    }
+
+    pub(super) fn take_new_content_height(&self) -> Option<f32> {
+        self.state.borrow_mut().new_content_height.take()
+    }
+
+    /// Checks whether the pane should load more content when it is *remaining_scroll* away from
+    /// the bottom of its content, and marks this controller as loading if so
+    pub(super) fn start_loading_if_needed(&self, remaining_scroll: f32) -> bool {
+        let mut state = self.state.borrow_mut();
+        if !state.loading && state.has_more && remaining_scroll <= state.threshold {
+            state.loading = true;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,737 @@
+use crate::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub struct TabbedPaneStyle {
+    pub font_id: Option<String>,
+    /// The height of the tab strip at the top of the pane, as a fraction of the height of the pane
+    pub tab_strip_height: f32,
+    pub active_tab_color: Color,
+    pub active_text_color: Color,
+    pub inactive_tab_color: Color,
+    pub inactive_text_color: Color,
+    /// The color behind the content of the active tab
+    pub background_color: Color,
+}
+
+impl TabbedPaneStyle {
+    /// Creates a `TabbedPaneStyle` where the active tab has the same (surface) color as the
+    /// content, and the inactive tabs use the background color of the given `Palette`.
+    pub fn from_palette(palette: &Palette) -> Self {
+        Self {
+            font_id: None,
+            tab_strip_height: 0.1,
+            active_tab_color: palette.get_color(ColorRole::Surface),
+            active_text_color: palette.get_readable_color(ColorRole::OnSurface, ColorRole::Surface),
+            inactive_tab_color: palette.get_color(ColorRole::Background),
+            inactive_text_color: palette
+                .get_readable_color(ColorRole::OnBackground, ColorRole::Background),
+            background_color: palette.get_color(ColorRole::Surface),
+        }
+    }
+
+    /// Creates the `TabbedPaneStyle` for the palette and font of the given `Theme`
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            font_id: theme.font_id.clone(),
+            ..Self::from_palette(&theme.palette)
+        }
+    }
+}
+
+struct Tab {
+    label: String,
+    component: Rc<RefCell<Box<dyn Component>>>,
+}
+
+/// A menu that owns a list of (label, component) pairs called *tabs*, and shows a strip with the
+/// labels of all tabs at its top. Only the component of the *active* tab is shown below the
+/// strip, and the user can change the active tab by clicking on its label.
+///
+/// Only the component of the active tab is attached: the component of the previously active tab
+/// will be detached when another tab becomes active, and attached again when its tab becomes
+/// active again. The components of inactive tabs keep their state, but they won't receive any
+/// events (not even `TickEvent`s).
+pub struct TabbedPane {
+    menu: SimpleFlatMenu,
+    style: TabbedPaneStyle,
+    theme_follower: Option<ThemeFollower<TabbedPaneStyle>>,
+    tabs: Vec<Tab>,
+
+    active_tab: Option<usize>,
+    content_handle: Option<ComponentHandle>,
+    needs_full_render: bool,
+}
+
+impl TabbedPane {
+    pub fn new(style: TabbedPaneStyle) -> Self {
+        Self {
+            menu: SimpleFlatMenu::new(Some(style.background_color)),
+            style,
+            theme_follower: None,
+            tabs: Vec::new(),
+            active_tab: None,
+            content_handle: None,
+            needs_full_render: false,
+        }
+    }
+
+    /// Lets this pane follow the `Theme` of its buddy, see `StyleOverrides.resolve_theme`
+    pub fn follow_theme(&mut self, overrides: StyleOverrides<TabbedPaneStyle>) {
+        self.theme_follower = Some(ThemeFollower::new(overrides));
+    }
+
+    /// Adds a tab with the given *label* and *component* to the end of the tab strip, and returns
+    /// its index. If this is the first tab, it will become the active tab.
+    pub fn add_tab(&mut self, label: impl Into<String>, component: Box<dyn Component>) -> usize {
+        self.tabs.push(Tab {
+            label: label.into(),
+            component: Rc::new(RefCell::new(component)),
+        });
+        self.needs_full_render = true;
+
+        let index = self.tabs.len() - 1;
+        if self.active_tab.is_none() {
+            self.set_active_tab(index);
+        }
+        index
+    }
+
+    /// Removes the tab at the given *index* (and thereby shifts the indices of the tabs after it).
+    /// If it was the active tab, its component will be detached and the next tab (or the previous
+    /// tab if it was the last tab) will become active.
+    ///
+    /// ## Panics
+    /// This method panics if *index* is out of bounds.
+    pub fn remove_tab(&mut self, index: usize) {
+        self.tabs.remove(index);
+        self.needs_full_render = true;
+
+        if let Some(active_tab) = self.active_tab {
+            if active_tab == index {
+                self.active_tab = None;
+                if self.tabs.is_empty() {
+                    if let Some(handle) = self.content_handle.take() {
+                        self.menu.remove_component(handle);
+                    }
+                } else {
+                    self.set_active_tab(index.min(self.tabs.len() - 1));
+                }
+            } else if active_tab > index {
+                self.active_tab = Some(active_tab - 1);
+            }
+        }
+    }
+
+    pub fn get_num_tabs(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn get_tab_label(&self, index: usize) -> &str {
+        &self.tabs[index].label
+    }
+
+    /// Gets the index of the active tab, which is only `None` when this pane doesn't have tabs
+    pub fn get_active_tab(&self) -> Option<usize> {
+        self.active_tab
+    }
+
+    /// Makes the tab at the given *index* the active tab: the component of the old active tab
+    /// will be detached, and the component of the new active tab will be attached. Like the
+    /// changes of a `SimpleFlatMenu`, this will be processed during the next event.
+    ///
+    /// ## Panics
+    /// This method panics if *index* is out of bounds.
+    pub fn set_active_tab(&mut self, index: usize) {
+        assert!(
+            index < self.tabs.len(),
+            "Index is {}, but there are {} tabs",
+            index,
+            self.tabs.len()
+        );
+        if self.active_tab == Some(index) {
+            return;
+        }
+
+        let content = Box::new(TabContent {
+            component: Rc::clone(&self.tabs[index].component),
+        });
+        match self.content_handle {
+            Some(handle) => self.menu.replace_component(handle, content),
+            None => {
+                let domain =
+                    ComponentDomain::between(0.0, 0.0, 1.0, 1.0 - self.style.tab_strip_height);
+                self.content_handle = Some(self.menu.add_component(content, domain));
+            }
+        };
+        self.active_tab = Some(index);
+        self.needs_full_render = true;
+    }
+
+    /// Gets the index of the tab whose label is at the given *point*, if any
+    fn get_tab_at(&self, point: Point) -> Option<usize> {
+        if point.get_y() < 1.0 - self.style.tab_strip_height || point.get_y() > 1.0 {
+            return None;
+        }
+        if point.get_x() < 0.0 || point.get_x() >= 1.0 || self.tabs.is_empty() {
+            return None;
+        }
+        Some((point.get_x() * self.tabs.len() as f32) as usize)
+    }
+
+    fn render_tab_strip(&self, renderer: &Renderer) -> RenderResult {
+        let tab_width = 1.0 / self.tabs.len() as f32;
+        let min_y = 1.0 - self.style.tab_strip_height;
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let (tab_color, text_color) = match self.active_tab == Some(index) {
+                true => (self.style.active_tab_color, self.style.active_text_color),
+                false => (
+                    self.style.inactive_tab_color,
+                    self.style.inactive_text_color,
+                ),
+            };
+
+            let min_x = index as f32 * tab_width;
+            let max_x = min_x + tab_width;
+            renderer.push_viewport(min_x, min_y, max_x, 1.0, || renderer.clear(tab_color));
+
+            let text_style = TextStyle {
+                font_id: self.style.font_id.clone(),
+                text_color,
+                background_color: tab_color,
+                background_fill_mode: TextBackgroundFillMode::DoNot,
+            };
+            let position = TextDrawPosition {
+                min_x,
+                min_y,
+                max_x,
+                max_y: 1.0,
+                horizontal_alignment: HorizontalTextAlignment::Center,
+                vertical_alignment: VerticalTextAlignment::Center,
+            };
+            renderer.get_text_renderer().draw_text(
+                &tab.label,
+                &text_style,
+                position,
+                renderer,
+                None,
+            )?;
+        }
+        entire_render_result()
+    }
+}
+
+impl Component for TabbedPane {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_attach(buddy)
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        if let Some(follower) = &mut self.theme_follower {
+            if follower.update(&mut self.style, buddy) {
+                self.menu.set_background_color(Some(self.style.background_color));
+                if let Some(handle) = self.content_handle {
+                    let strip_height = self.style.tab_strip_height;
+                    let domain = ComponentDomain::between(0.0, 0.0, 1.0, 1.0 - strip_height);
+                    self.menu.move_component(handle, domain);
+                }
+                self.needs_full_render = true;
+            }
+        }
+
+        let force = force || std::mem::take(&mut self.needs_full_render);
+        let result = self.menu.render(renderer, buddy, force)?;
+        if force {
+            self.render_tab_strip(renderer)
+        } else {
+            Ok(result)
+        }
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_button().is_primary() {
+            if let Some(index) = self.get_tab_at(event.get_point()) {
+                self.set_active_tab(index);
+                buddy.request_render();
+            }
+        }
+        self.menu.on_mouse_click(event, buddy)
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_press(event, buddy)
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_release(event, buddy)
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_move(event, buddy)
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_enter(event, buddy)
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_leave(event, buddy)
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_scroll(event, buddy)
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_double_click(event, buddy)
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_long_press(event, buddy)
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_context_click(event, buddy)
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_mouse_middle_click(event, buddy)
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_mouse_hover(event, buddy)
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_pinch(event, buddy)
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_rotate_gesture(event, buddy)
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_swipe(event, buddy)
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.menu.on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.menu.on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.menu.on_tick(event, buddy)
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.menu.get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.menu.get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.menu.get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        self.menu.describe_children()
+    }
+
+    fn on_detach(&mut self) {
+        self.menu.on_detach()
+    }
+}
+
+/// The component that the `TabbedPane` adds to its menu for the active tab. It shares the actual
+/// component with the `Tab`, so that the menu can detach it without destroying it.
+struct TabContent {
+    component: Rc<RefCell<Box<dyn Component>>>,
+}
+
+impl Component for TabContent {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_attach(buddy)
+    }
+
+    fn on_resize(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_resize(buddy)
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        self.component.borrow_mut().render(renderer, buddy, force)
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_click(event, buddy)
+    }
+
+    fn on_mouse_click_out(&mut self, event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_click_out(event, buddy)
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_press(event, buddy)
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_release(event, buddy)
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_move(event, buddy)
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_enter(event, buddy)
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_leave(event, buddy)
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_scroll(event, buddy)
+    }
+
+    fn on_mouse_double_click(
+        &mut self,
+        event: MouseDoubleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.component
+            .borrow_mut()
+            .on_mouse_double_click(event, buddy)
+    }
+
+    fn on_mouse_long_press(&mut self, event: MouseLongPressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component
+            .borrow_mut()
+            .on_mouse_long_press(event, buddy)
+    }
+
+    fn on_mouse_context_click(
+        &mut self,
+        event: MouseContextClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.component
+            .borrow_mut()
+            .on_mouse_context_click(event, buddy)
+    }
+
+    fn on_mouse_middle_click(
+        &mut self,
+        event: MouseMiddleClickEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.component
+            .borrow_mut()
+            .on_mouse_middle_click(event, buddy)
+    }
+
+    fn on_mouse_hover(&mut self, event: MouseHoverEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_mouse_hover(event, buddy)
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_pinch(event, buddy)
+    }
+
+    fn on_rotate_gesture(&mut self, event: RotateGestureEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_rotate_gesture(event, buddy)
+    }
+
+    fn on_swipe(&mut self, event: SwipeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_swipe(event, buddy)
+    }
+
+    fn on_relative_mouse_move(
+        &mut self,
+        event: RelativeMouseMoveEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.component.borrow_mut().on_relative_mouse_move(event, buddy)
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_char_type(event, buddy)
+    }
+
+    fn on_composition_start(
+        &mut self,
+        event: &CompositionStartEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.component
+            .borrow_mut()
+            .on_composition_start(event, buddy)
+    }
+
+    fn on_composition_update(
+        &mut self,
+        event: &CompositionUpdateEvent,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.component
+            .borrow_mut()
+            .on_composition_update(event, buddy)
+    }
+
+    fn on_composition_end(&mut self, event: &CompositionEndEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_composition_end(event, buddy)
+    }
+
+    fn on_theme_change(&mut self, event: &ThemeChangeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_theme_change(event, buddy)
+    }
+
+    fn on_gamepad_button(&mut self, event: GamepadButtonEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_gamepad_button(event, buddy)
+    }
+
+    fn on_gamepad_axis(&mut self, event: GamepadAxisEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_gamepad_axis(event, buddy)
+    }
+
+    fn on_low_memory(&mut self, event: LowMemoryEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_low_memory(event, buddy)
+    }
+
+    fn on_window_focus(&mut self, event: WindowFocusEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_window_focus(event, buddy)
+    }
+
+    fn on_window_minimize(&mut self, event: WindowMinimizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_window_minimize(event, buddy)
+    }
+
+    fn on_close_request(&mut self, buddy: &mut dyn ComponentBuddy) -> bool {
+        self.component.borrow_mut().on_close_request(buddy)
+    }
+
+    fn on_tick(&mut self, event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.component.borrow_mut().on_tick(event, buddy)
+    }
+
+    fn get_accessible_text_at(&self, point: Point) -> Option<AccessibleText> {
+        self.component.borrow().get_accessible_text_at(point)
+    }
+
+    fn get_cursor_at(&self, point: Point) -> Option<CursorIcon> {
+        self.component.borrow().get_cursor_at(point)
+    }
+
+    fn get_window_frame_area_at(&self, point: Point) -> Option<WindowFrameArea> {
+        self.component.borrow().get_window_frame_area_at(point)
+    }
+
+    fn describe_children(&self) -> Vec<ComponentDescription> {
+        vec![ComponentDescription::of(self.component.borrow().as_ref())]
+    }
+
+    fn on_detach(&mut self) {
+        self.component.borrow_mut().on_detach()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct LoggingComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+        num_clicks: u32,
+    }
+
+    impl Component for LoggingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+            self.log.borrow_mut().push(format!("attach {}", self.name));
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.num_clicks += 1;
+            let message = format!("click {} {}", self.name, self.num_clicks);
+            self.log.borrow_mut().push(message);
+        }
+
+        fn on_detach(&mut self) {
+            self.log.borrow_mut().push(format!("detach {}", self.name));
+        }
+    }
+
+    #[test]
+    fn test_switching_tabs() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut pane = TabbedPane::new(TabbedPaneStyle::from_palette(&Palette::default()));
+        for name in &["first", "second"] {
+            let component = LoggingComponent {
+                name,
+                log: Rc::clone(&log),
+                num_clicks: 0,
+            };
+            pane.add_tab(*name, Box::new(component));
+        }
+        assert_eq!(2, pane.get_num_tabs());
+        assert_eq!("second", pane.get_tab_label(1));
+        assert_eq!(Some(0), pane.get_active_tab());
+
+        let mut application = Application::new(Box::new(pane));
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 100));
+        application.render(&renderer, false);
+
+        let mouse = Mouse::new(0);
+        let click = |x: f32, y: f32| {
+            let event = MouseClickEvent::new(mouse, Point::new(x, y), MouseButton::primary());
+            move |application: &mut Application| application.fire_mouse_click_event(event)
+        };
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.5, 0.5)));
+
+        // Clicking the content should reach the component of the active tab
+        click(0.5, 0.5)(&mut application);
+
+        // Clicking the label of the second tab should swap the components
+        click(0.75, 0.95)(&mut application);
+        application.render(&renderer, false);
+        click(0.5, 0.5)(&mut application);
+
+        // When the first tab is activated again, its component should have kept its state
+        click(0.25, 0.95)(&mut application);
+        application.render(&renderer, false);
+        click(0.5, 0.5)(&mut application);
+
+        assert_eq!(
+            vec![
+                "attach first",
+                "click first 1",
+                "detach first",
+                "attach second",
+                "click second 1",
+                "detach second",
+                "attach first",
+                "click first 2",
+            ],
+            *log.borrow()
+        );
+    }
+
+    #[test]
+    fn test_remove_tab() {
+        let mut pane = TabbedPane::new(TabbedPaneStyle::from_palette(&Palette::default()));
+        for name in &["a", "b", "c"] {
+            pane.add_tab(*name, Box::new(DummyComponent {}));
+        }
+
+        pane.set_active_tab(1);
+        pane.remove_tab(0);
+        assert_eq!(Some(0), pane.get_active_tab());
+        assert_eq!("b", pane.get_tab_label(0));
+
+        // When the active tab is removed, the next tab should become active
+        pane.remove_tab(0);
+        assert_eq!(Some(0), pane.get_active_tab());
+        assert_eq!("c", pane.get_tab_label(0));
+
+        pane.remove_tab(0);
+        assert_eq!(None, pane.get_active_tab());
+        assert_eq!(0, pane.get_num_tabs());
+    }
+}
@@ -1,7 +1,13 @@
+mod builder;
 mod color;
+mod input;
 mod menu;
+mod selection;
 mod text;
 
+pub use builder::*;
 pub use color::*;
+pub use input::*;
 pub use menu::*;
+pub use selection::*;
 pub use text::*;
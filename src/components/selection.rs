@@ -0,0 +1,271 @@
+use std::collections::BTreeSet;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SelectionMode {
+    /// At most 1 item can be selected at the same time
+    Single,
+    /// Any number of items can be selected at the same time
+    Multiple,
+}
+
+/// Keeps track of which items (identified by their index) of a list-like component are selected.
+/// This is meant to be shared by all components that allow the user to select items, so that they
+/// all behave the same way.
+///
+/// ## Anchor and lead
+/// Besides the selected items, this model tracks the *anchor* and the *lead*. The anchor is the
+/// item that was selected (or toggled) most recently, and `extend_to` selects all items between
+/// the anchor and the given index. The lead is the item that the user is currently 'at', which is
+/// what keyboard navigation (`move_lead`) moves.
+///
+/// ## Modifiers
+/// The intended mapping of mouse clicks is:
+/// - plain click: `select`
+/// - ctrl-click: `toggle`
+/// - shift-click: `extend_to`
+///
+/// Note that mouse events don't have modifiers yet, so components can only use plain clicks for
+/// now.
+///
+/// ## Change listeners
+/// Change listeners will be called after every operation that actually changed the set of selected
+/// items.
+pub struct SelectionModel {
+    mode: SelectionMode,
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+    lead: Option<usize>,
+
+    change_listeners: Vec<Box<dyn FnMut(&SelectionModel)>>,
+}
+
+impl SelectionModel {
+    pub fn new(mode: SelectionMode) -> Self {
+        Self {
+            mode,
+            selected: BTreeSet::new(),
+            anchor: None,
+            lead: None,
+            change_listeners: Vec::new(),
+        }
+    }
+
+    pub fn get_mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    pub fn get_anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    pub fn get_lead(&self) -> Option<usize> {
+        self.lead
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// Gets the indices of all selected items, in ascending order
+    pub fn get_selected(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    pub fn get_num_selected(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn add_change_listener(&mut self, listener: Box<dyn FnMut(&SelectionModel)>) {
+        self.change_listeners.push(listener);
+    }
+
+    fn replace_selection(&mut self, new_selection: BTreeSet<usize>) {
+        if new_selection != self.selected {
+            self.selected = new_selection;
+
+            // Temporarily take the listeners out of self, so that they can borrow self
+            let mut listeners = std::mem::take(&mut self.change_listeners);
+            for listener in &mut listeners {
+                listener(self);
+            }
+
+            // Keep any listeners that were somehow added during the notification
+            listeners.append(&mut self.change_listeners);
+            self.change_listeners = listeners;
+        }
+    }
+
+    /// Deselects all items and makes the item at *index* the only selected item
+    pub fn select(&mut self, index: usize) {
+        self.anchor = Some(index);
+        self.lead = Some(index);
+
+        let mut new_selection = BTreeSet::new();
+        new_selection.insert(index);
+        self.replace_selection(new_selection);
+    }
+
+    /// Selects the item at *index* if it is not selected, or deselects it if it is selected. In
+    /// `Multiple` mode, the selection state of the other items won't be affected.
+    pub fn toggle(&mut self, index: usize) {
+        self.anchor = Some(index);
+        self.lead = Some(index);
+
+        let mut new_selection = match self.mode {
+            SelectionMode::Single => BTreeSet::new(),
+            SelectionMode::Multiple => self.selected.clone(),
+        };
+        if self.selected.contains(&index) {
+            new_selection.remove(&index);
+        } else {
+            new_selection.insert(index);
+        }
+        self.replace_selection(new_selection);
+    }
+
+    /// Selects all items between the anchor and *index* (inclusive) and deselects all other items.
+    /// The anchor will not be changed. If there is no anchor or the mode is `Single`, this behaves
+    /// like `select`.
+    pub fn extend_to(&mut self, index: usize) {
+        let anchor = match (self.mode, self.anchor) {
+            (SelectionMode::Multiple, Some(anchor)) => anchor,
+            _ => return self.select(index),
+        };
+        self.lead = Some(index);
+
+        let new_selection = (anchor.min(index)..=anchor.max(index)).collect();
+        self.replace_selection(new_selection);
+    }
+
+    /// Moves the lead by *offset* items (for instance when the user presses an arrow key), while
+    /// staying within the *num_items* items of the list. If *extend* is true, this behaves like
+    /// `extend_to` the new lead, and like `select` otherwise.
+    pub fn move_lead(&mut self, offset: isize, num_items: usize, extend: bool) {
+        if num_items == 0 {
+            return;
+        }
+
+        let new_lead = match self.lead {
+            Some(lead) => {
+                let max_index = num_items as isize - 1;
+                (lead as isize + offset).max(0).min(max_index) as usize
+            }
+            None => match offset >= 0 {
+                true => 0,
+                false => num_items - 1,
+            },
+        };
+
+        if extend {
+            self.extend_to(new_lead);
+        } else {
+            self.select(new_lead);
+        }
+    }
+
+    /// Deselects all items and forgets the anchor and lead
+    pub fn clear(&mut self) {
+        self.anchor = None;
+        self.lead = None;
+        self.replace_selection(BTreeSet::new());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_single_mode() {
+        let mut model = SelectionModel::new(SelectionMode::Single);
+        assert!(model.get_selected().is_empty());
+
+        model.select(3);
+        assert_eq!(vec![3], model.get_selected());
+
+        model.toggle(5);
+        assert_eq!(vec![5], model.get_selected());
+
+        model.extend_to(2);
+        assert_eq!(vec![2], model.get_selected());
+
+        model.toggle(2);
+        assert!(model.get_selected().is_empty());
+    }
+
+    #[test]
+    fn test_multiple_mode() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+
+        model.select(3);
+        model.toggle(5);
+        assert_eq!(vec![3, 5], model.get_selected());
+        assert_eq!(Some(5), model.get_anchor());
+
+        model.extend_to(7);
+        assert_eq!(vec![5, 6, 7], model.get_selected());
+        assert_eq!(Some(5), model.get_anchor());
+        assert_eq!(Some(7), model.get_lead());
+
+        model.extend_to(4);
+        assert_eq!(vec![4, 5], model.get_selected());
+
+        model.toggle(5);
+        assert_eq!(vec![4], model.get_selected());
+
+        model.clear();
+        assert!(model.get_selected().is_empty());
+        assert_eq!(None, model.get_anchor());
+    }
+
+    #[test]
+    fn test_move_lead() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+
+        model.move_lead(1, 0, false);
+        assert!(model.get_selected().is_empty());
+
+        model.move_lead(1, 5, false);
+        assert_eq!(vec![0], model.get_selected());
+
+        model.move_lead(2, 5, true);
+        assert_eq!(vec![0, 1, 2], model.get_selected());
+
+        model.move_lead(10, 5, true);
+        assert_eq!(vec![0, 1, 2, 3, 4], model.get_selected());
+
+        model.move_lead(-1, 5, false);
+        assert_eq!(vec![3], model.get_selected());
+    }
+
+    #[test]
+    fn test_change_listeners() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+
+        let counter = Rc::new(Cell::new(0));
+        let listener_counter = Rc::clone(&counter);
+        model.add_change_listener(Box::new(move |model| {
+            assert!(model.get_num_selected() <= 2);
+            listener_counter.set(listener_counter.get() + 1);
+        }));
+
+        model.select(1);
+        assert_eq!(1, counter.get());
+
+        // The selection doesn't change, so the listener shouldn't be called
+        model.select(1);
+        assert_eq!(1, counter.get());
+
+        model.toggle(2);
+        assert_eq!(2, counter.get());
+
+        model.clear();
+        assert_eq!(3, counter.get());
+        model.clear();
+        assert_eq!(3, counter.get());
+    }
+}
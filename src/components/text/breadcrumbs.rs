@@ -0,0 +1,271 @@
+use crate::*;
+
+pub struct BreadcrumbsStyle {
+    pub font_id: Option<String>,
+    pub segment_color: Color,
+    pub current_segment_color: Color,
+    pub separator_color: Color,
+    pub background_color: Color,
+    pub separator: String,
+    /// When the path has more segments than this, the middle segments will be collapsed into a
+    /// single ellipsis item. The first segment and the last segments will remain visible.
+    pub max_visible_segments: usize,
+}
+
+impl BreadcrumbsStyle {
+    /// Creates a `BreadcrumbsStyle` for breadcrumbs on the background of the given `Palette`. The
+    /// clickable segments will use the primary color.
+    pub fn from_palette(palette: &Palette) -> Self {
+        let readable = |role| palette.get_readable_color(role, ColorRole::Background);
+        Self {
+            font_id: None,
+            segment_color: readable(ColorRole::Primary),
+            current_segment_color: readable(ColorRole::OnBackground),
+            separator_color: palette.get_color(ColorRole::Outline),
+            background_color: palette.get_color(ColorRole::Background),
+            separator: " > ".to_string(),
+            max_visible_segments: 5,
+        }
+    }
+
+    /// Creates the `BreadcrumbsStyle` for the palette and font of the given `Theme`
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            font_id: theme.font_id.clone(),
+            ..Self::from_palette(&theme.palette)
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum BreadcrumbItem {
+    Segment(usize),
+    Separator,
+    Ellipsis,
+}
+
+/// A component that displays a path of segments (for instance the menus that the user navigated
+/// through) from left to right. The user can click on any segment (except the last one, which
+/// represents the current location) to fire the *on_click* listener with the index of that
+/// segment. It is up to that listener to navigate back (for instance by calling
+/// `buddy.change_menu`).
+///
+/// When the path is longer than the `max_visible_segments` of its style, the middle segments will
+/// be replaced by an ellipsis. Clicking on the ellipsis expands the path again.
+pub struct Breadcrumbs {
+    segments: Vec<String>,
+    style: BreadcrumbsStyle,
+    theme_follower: Option<ThemeFollower<BreadcrumbsStyle>>,
+    on_click: Box<dyn FnMut(usize, &mut dyn ComponentBuddy)>,
+
+    expanded: bool,
+    last_item_bounds: Vec<(BreadcrumbItem, f32, f32)>,
+}
+
+impl Breadcrumbs {
+    pub fn new(
+        segments: Vec<String>,
+        style: BreadcrumbsStyle,
+        on_click: Box<dyn FnMut(usize, &mut dyn ComponentBuddy)>,
+    ) -> Self {
+        Self {
+            segments,
+            style,
+            theme_follower: None,
+            on_click,
+            expanded: false,
+            last_item_bounds: Vec::new(),
+        }
+    }
+
+    /// Lets these breadcrumbs follow the `Theme` of its buddy, see `StyleOverrides.resolve_theme`
+    pub fn follow_theme(&mut self, overrides: StyleOverrides<BreadcrumbsStyle>) {
+        self.theme_follower = Some(ThemeFollower::new(overrides));
+    }
+
+    pub fn get_segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Replaces the segments of this component. This will collapse the path again if it is too
+    /// long. Note that the caller is responsible for requesting a render.
+    pub fn set_segments(&mut self, segments: Vec<String>) {
+        self.segments = segments;
+        self.expanded = false;
+    }
+
+    fn get_visible_items(&self) -> Vec<BreadcrumbItem> {
+        compute_visible_items(self.segments.len(), self.style.max_visible_segments, self.expanded)
+    }
+
+    fn get_item_text(&self, item: BreadcrumbItem) -> &str {
+        match item {
+            BreadcrumbItem::Segment(index) => &self.segments[index],
+            BreadcrumbItem::Separator => &self.style.separator,
+            BreadcrumbItem::Ellipsis => "...",
+        }
+    }
+
+    fn get_item_color(&self, item: BreadcrumbItem) -> Color {
+        match item {
+            BreadcrumbItem::Segment(index) => match index + 1 == self.segments.len() {
+                true => self.style.current_segment_color,
+                false => self.style.segment_color,
+            },
+            BreadcrumbItem::Separator => self.style.separator_color,
+            BreadcrumbItem::Ellipsis => self.style.segment_color,
+        }
+    }
+
+    fn create_text_style(&self, text_color: Color) -> TextStyle {
+        TextStyle {
+            font_id: self.style.font_id.clone(),
+            text_color,
+            background_color: self.style.background_color,
+            background_fill_mode: TextBackgroundFillMode::DoNot,
+        }
+    }
+}
+
+fn compute_visible_items(
+    num_segments: usize,
+    max_visible_segments: usize,
+    expanded: bool,
+) -> Vec<BreadcrumbItem> {
+    let mut segment_items = Vec::with_capacity(num_segments);
+
+    // There must be room for at least the first and the last segment
+    let max_visible_segments = max_visible_segments.max(2);
+    if expanded || num_segments <= max_visible_segments {
+        for index in 0..num_segments {
+            segment_items.push(BreadcrumbItem::Segment(index));
+        }
+    } else {
+        segment_items.push(BreadcrumbItem::Segment(0));
+        segment_items.push(BreadcrumbItem::Ellipsis);
+        for index in num_segments + 1 - max_visible_segments..num_segments {
+            segment_items.push(BreadcrumbItem::Segment(index));
+        }
+    }
+
+    let mut items = Vec::with_capacity(2 * segment_items.len());
+    for (index, item) in segment_items.into_iter().enumerate() {
+        if index > 0 {
+            items.push(BreadcrumbItem::Separator);
+        }
+        items.push(item);
+    }
+    items
+}
+
+impl Component for Breadcrumbs {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_click();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        if let Some(follower) = &mut self.theme_follower {
+            follower.update(&mut self.style, buddy);
+        }
+
+        renderer.clear(self.style.background_color);
+
+        let items = self.get_visible_items();
+        let mut item_widths = Vec::with_capacity(items.len());
+        let mut total_width = 0;
+        for item in &items {
+            let text_style = self.create_text_style(self.get_item_color(*item));
+            let (width, _) = renderer.get_text_renderer().get_text_size(
+                self.get_item_text(*item), &text_style, renderer
+            )?;
+            item_widths.push(width);
+            total_width += width;
+        }
+
+        self.last_item_bounds.clear();
+        let mut current_x = 0;
+        for (item, width) in items.into_iter().zip(item_widths.into_iter()) {
+            // Distribute the horizontal space proportionally to the widths of the texts, so that
+            // all texts will be drawn with the same scale (unless the component is too high)
+            let min_x = current_x as f32 / total_width.max(1) as f32;
+            current_x += width;
+            let max_x = current_x as f32 / total_width.max(1) as f32;
+
+            let text_style = self.create_text_style(self.get_item_color(item));
+            renderer.get_text_renderer().draw_text(
+                self.get_item_text(item), &text_style, TextDrawPosition {
+                    min_x,
+                    min_y: 0.0,
+                    max_x,
+                    max_y: 1.0,
+                    horizontal_alignment: HorizontalTextAlignment::Center,
+                    vertical_alignment: VerticalTextAlignment::Center,
+                }, renderer, None
+            )?;
+            self.last_item_bounds.push((item, min_x, max_x));
+        }
+
+        entire_render_result()
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if !event.get_button().is_primary() {
+            return;
+        }
+
+        let x = event.get_point().get_x();
+        let clicked_item = self.last_item_bounds.iter().find(
+            |(_, min_x, max_x)| x >= *min_x && x <= *max_x
+        ).map(|(item, _, _)| *item);
+
+        match clicked_item {
+            Some(BreadcrumbItem::Segment(index)) => {
+                // Clicking on the current location shouldn't do anything
+                if index + 1 < self.segments.len() {
+                    (self.on_click)(index, buddy);
+                }
+            }
+            Some(BreadcrumbItem::Ellipsis) => {
+                self.expanded = true;
+                buddy.request_render();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use BreadcrumbItem::*;
+
+    #[test]
+    fn test_compute_visible_items() {
+        assert_eq!(Vec::<BreadcrumbItem>::new(), compute_visible_items(0, 3, false));
+        assert_eq!(vec![Segment(0)], compute_visible_items(1, 3, false));
+        assert_eq!(
+            vec![Segment(0), Separator, Segment(1), Separator, Segment(2)],
+            compute_visible_items(3, 3, false)
+        );
+        assert_eq!(
+            vec![Segment(0), Separator, Ellipsis, Separator, Segment(3), Separator, Segment(4)],
+            compute_visible_items(5, 3, false)
+        );
+        assert_eq!(
+            vec![Segment(0), Separator, Segment(1), Separator, Segment(2)],
+            compute_visible_items(3, 2, true)
+        );
+
+        // The first and last segment should always be visible
+        assert_eq!(
+            vec![Segment(0), Separator, Ellipsis, Separator, Segment(3)],
+            compute_visible_items(4, 0, false)
+        );
+    }
+}
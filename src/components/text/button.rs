@@ -6,27 +6,81 @@ pub struct TextButtonStyle {
     pub base_background_color: Color,
     pub hover_text_color: Color,
     pub hover_background_color: Color,
+    pub pressed_text_color: Color,
+    pub pressed_background_color: Color,
+    pub disabled_text_color: Color,
+    pub disabled_background_color: Color,
+    /// The margin between the edge of the background and the text, relative to the height of the
+    /// button.
     pub margin: f32,
-    pub border_style: TextButtonBorderStyle
+    /// The radius of the corners of the background, relative to the height of the background. It
+    /// should be between 0.0 (sharp corners) and 0.5 (which gives round ends).
+    pub corner_radius: f32,
 }
 
-pub enum TextButtonBorderStyle {
-    None,
-    Rectangular { color: Color, max_width: f32, max_height: f32 },
-    RoundRectangular { color: Color, max_width: f32, max_height: f32 },
+impl TextButtonStyle {
+    /// Creates a `TextButtonStyle` that uses the primary colors of the given `Palette`, and the
+    /// secondary colors while the button is hovered.
+    pub fn from_palette(palette: &Palette) -> Self {
+        let readable = |text, background| palette.get_readable_color(text, background);
+        Self {
+            font_id: None,
+            base_text_color: readable(ColorRole::OnPrimary, ColorRole::Primary),
+            base_background_color: palette.get_color(ColorRole::Primary),
+            hover_text_color: readable(ColorRole::OnSecondary, ColorRole::Secondary),
+            hover_background_color: palette.get_color(ColorRole::Secondary),
+            pressed_text_color: readable(ColorRole::Secondary, ColorRole::OnSecondary),
+            pressed_background_color: palette.get_color(ColorRole::OnSecondary),
+            disabled_text_color: palette.get_color(ColorRole::Outline),
+            disabled_background_color: palette.get_color(ColorRole::Surface),
+            margin: 0.1,
+            corner_radius: 0.25,
+        }
+    }
+
+    /// Creates the `TextButtonStyle` for the palette of the given `Theme`, using the font, corner
+    /// radius, and spacing (as margin) of the theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            font_id: theme.font_id.clone(),
+            margin: theme.spacing,
+            corner_radius: theme.corner_radius,
+            ..Self::from_palette(&theme.palette)
+        }
+    }
 }
 
+/// A button that draws its text on a background with rounded corners, and fires its *on_click*
+/// listener when the user clicks on it with the primary mouse button. The background will be
+/// centered in the domain of the button and will be just big enough to contain the text and its
+/// margin, and only that background will receive mouse events.
+///
+/// The colors of the button depend on whether it is disabled, pressed, or hovered (in that order
+/// of priority). Disabled buttons will not fire their *on_click* listener.
 pub struct TextButton {
     text: String,
     style: TextButtonStyle,
+    theme_follower: Option<ThemeFollower<TextButtonStyle>>,
     shader: FragmentOnlyShader,
-    // TODO on_click
+    on_click: Box<dyn FnMut(&mut dyn ComponentBuddy)>,
+
+    enabled: bool,
+    hovering_mouses: Vec<Mouse>,
+    pressing_mouses: Vec<Mouse>,
 }
 
-fn shader_description_no_border() -> FragmentOnlyShaderDescription {
+fn shader_description() -> FragmentOnlyShaderDescription {
     FragmentOnlyShaderDescription {
         source_code: "
             void main() {
+                vec2 radius = vec2(float1, float2);
+                vec2 distanceToCenter = abs(innerPosition - vec2(0.5, 0.5));
+                vec2 cornerDistance = max(
+                    distanceToCenter - vec2(0.5, 0.5) + radius, vec2(0.0, 0.0)
+                );
+                if (radius.x > 0.0 && radius.y > 0.0 && length(cornerDistance / radius) > 1.0) {
+                    discard;
+                }
                 gl_FragColor = color1;
             }
         ".to_string(),
@@ -34,134 +88,248 @@ fn shader_description_no_border() -> FragmentOnlyShaderDescription {
         num_colors: 1,
         num_float_vectors: 0,
         num_int_vectors: 0,
-        num_floats: 0,
-        num_ints: 0
+        num_floats: 2,
+        num_ints: 0,
     }
 }
 
 impl TextButton {
-    pub fn new(text: &str, style: TextButtonStyle) -> Self {
-        let shader_description = shader_description_no_border();
-        let shader = FragmentOnlyShader::new(shader_description);
+    pub fn new(
+        text: &str,
+        style: TextButtonStyle,
+        on_click: Box<dyn FnMut(&mut dyn ComponentBuddy)>,
+    ) -> Self {
         Self {
             text: text.to_string(),
             style,
-            shader
+            theme_follower: None,
+            shader: Self::create_shader(),
+            on_click,
+            enabled: true,
+            hovering_mouses: Vec::new(),
+            pressing_mouses: Vec::new(),
+        }
+    }
+
+    /// Lets this button follow the `Theme` of its buddy, see `StyleOverrides.resolve_theme`
+    pub fn follow_theme(&mut self, overrides: StyleOverrides<TextButtonStyle>) {
+        self.theme_follower = Some(ThemeFollower::new(overrides));
+    }
+
+    /// Creates the `FragmentOnlyShader` that text buttons use. See `Renderer.prewarm`.
+    pub(crate) fn create_shader() -> FragmentOnlyShader {
+        FragmentOnlyShader::new(shader_description())
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Changes the text of this button. Note that the caller is responsible for requesting a
+    /// render.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables this button. Note that the caller is responsible for requesting a
+    /// render.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.pressing_mouses.clear();
+        }
+    }
+
+    fn get_colors(&self) -> (Color, Color) {
+        let style = &self.style;
+        if !self.enabled {
+            (style.disabled_text_color, style.disabled_background_color)
+        } else if !self.pressing_mouses.is_empty() {
+            (style.pressed_text_color, style.pressed_background_color)
+        } else if !self.hovering_mouses.is_empty() {
+            (style.hover_text_color, style.hover_background_color)
+        } else {
+            (style.base_text_color, style.base_background_color)
         }
     }
 }
 
 impl Component for TextButton {
     fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.set_accessible_label(Some(self.text.clone()));
         buddy.subscribe_mouse_click();
+        buddy.subscribe_mouse_press();
+        buddy.subscribe_mouse_release();
         buddy.subscribe_mouse_enter();
         buddy.subscribe_mouse_leave();
     }
 
-    fn render(&mut self, renderer: &Renderer, buddy: &mut dyn ComponentBuddy, _force: bool) -> RenderResult {
-        let (text_color, background_color) = match buddy.get_local_mouses().is_empty() {
-            true => (self.style.hover_text_color, self.style.hover_background_color),
-            false => (self.style.base_text_color, self.style.base_background_color)
-        };
-
-        renderer.clear(Color::rgb(200, 0, 150));
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        if let Some(follower) = &mut self.theme_follower {
+            follower.update(&mut self.style, buddy);
+        }
 
+        let (text_color, background_color) = self.get_colors();
         let text_style = TextStyle {
             font_id: self.style.font_id.clone(),
             text_color,
             background_color,
-            background_fill_mode: TextBackgroundFillMode::DoNot
+            background_fill_mode: TextBackgroundFillMode::DoNot,
         };
 
-        let (text_width, text_height) = renderer.get_text_renderer().get_text_size(
-            &self.text, &text_style, renderer
-        )?;
-        let domain_ratio = renderer.get_viewport().get_aspect_ratio();
-
-        let (reserved_margin_x, reserved_margin_y) = {
-            let mut reserved_margin_x = 0.0;
-            let mut reserved_margin_y = 0.0;
-
-            // TODO This system is not sound, especially when margin is big (> 0.5)
-            for _counter in 0 .. 2 {
-                let compute_scales = |test_margin_x: f32, test_margin_y: f32| {
-                    let max_scale_x1 = (1.0 - 2.0 * test_margin_x) / text_width as f32;
-                    let max_scale_y1 = (1.0 - 2.0 * test_margin_y) / text_height as f32;
-                    let max_scale_x2 = max_scale_y1 / domain_ratio;
-                    let max_scale_y2 = max_scale_x1 * domain_ratio;
-                    if max_scale_x2 <= max_scale_x1 {
-                        (max_scale_x2, max_scale_y1)
-                    } else {
-                        (max_scale_x1, max_scale_y2)
-                    }
-                };
-
-                let (full_scale_x, full_scale_y) = compute_scales(0.0, 0.0);
-                let (_, trimmed_scale_y) = compute_scales(reserved_margin_x, reserved_margin_y);
-
-                let drawn_text_height = trimmed_scale_y * text_height as f32;
-                let margin_y = self.style.margin * drawn_text_height;
-                let margin_x = margin_y / domain_ratio;
-
-                let scaled_width = text_width as f32 * full_scale_x;
-                let scaled_height = text_height as f32 * full_scale_y;
-
-                let limit_x = 1.0 - 2.0 * margin_x;
-                let limit_y = 1.0 - 2.0 * margin_y;
-                reserved_margin_x = if scaled_width <= limit_x {
-                    0.0
-                } else {
-                    (scaled_width - limit_x) / 2.0
-                };
-                reserved_margin_y = if scaled_height <= limit_y {
-                    0.0
-                } else {
-                    (scaled_height - limit_y) / 2.0
-                };
-                println!("margins currently are ({}, {})", reserved_margin_x, reserved_margin_y);
-            }
-            println!("Finished");
-            (reserved_margin_x, reserved_margin_y)
-        };
+        // The margin is relative to the height, so it must be converted for the x-axis
+        let aspect_ratio = renderer.get_viewport().get_aspect_ratio();
+        let margin_y = self.style.margin.clamp(0.0, 0.45);
+        let margin_x = (margin_y / aspect_ratio).min(0.45);
+        let corner_radius = self.style.corner_radius.clamp(0.0, 0.5);
 
+        let shader = &self.shader;
+        let mut drawn_region = None;
         renderer.get_text_renderer().draw_text(
             &self.text, &text_style, TextDrawPosition {
-                min_x: reserved_margin_x,
-                min_y: reserved_margin_y,
-                max_x: 1.0 - reserved_margin_x,
-                max_y: 1.0 - reserved_margin_y,
+                min_x: margin_x,
+                min_y: margin_y,
+                max_x: 1.0 - margin_x,
+                max_y: 1.0 - margin_y,
                 horizontal_alignment: HorizontalTextAlignment::Center,
-                vertical_alignment: VerticalTextAlignment::Center
+                vertical_alignment: VerticalTextAlignment::Center,
             }, renderer, Some(&mut |text_position: DrawnTextPosition| {
-                let draw_parameters = FragmentOnlyDrawParameters {
-                    colors: &[background_color],
-                    ..FragmentOnlyDrawParameters::default()
-                };
+                let min_x = text_position.min_x - margin_x;
+                let min_y = text_position.min_y - margin_y;
+                let max_x = text_position.max_x + margin_x;
+                let max_y = text_position.max_y + margin_y;
+
+                // The shader needs the radii relative to the size of the background
+                let width = max_x - min_x;
+                let height = max_y - min_y;
+                let radius_x = (corner_radius * height / (width * aspect_ratio)).min(0.5);
+                let radius_y = corner_radius;
 
-                let margin_y = self.style.margin * (text_position.max_y - text_position.min_y);
-                let margin_x = margin_y / renderer.get_viewport().get_aspect_ratio();
                 renderer.apply_fragment_shader(
-                    text_position.min_x - margin_x,
-                    text_position.min_y - margin_y,
-                    text_position.max_x + margin_x,
-                    text_position.max_y + margin_y,
-                    &self.shader, draw_parameters
+                    min_x, min_y, max_x, max_y, shader, FragmentOnlyDrawParameters {
+                        colors: &[background_color],
+                        floats: &[radius_x, radius_y],
+                        ..FragmentOnlyDrawParameters::default()
+                    }
                 );
+                drawn_region = Some(CompositeDrawnRegion::rounded_rectangle(
+                    min_x, min_y, max_x, max_y, radius_x * width, radius_y * height
+                ));
             })
         )?;
 
-        entire_render_result()
+        match drawn_region {
+            Some(drawn_region) => Ok(RenderResultStruct {
+                drawn_region: Box::new(drawn_region),
+                filter_mouse_actions: true,
+            }),
+            None => {
+                // Shouldn't happen, but not critical enough for a release panic
+                debug_assert!(false, "The text renderer didn't call before_draw");
+                entire_render_result()
+            }
+        }
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() {
+            (self.on_click)(buddy);
+        }
     }
 
-    fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
-        // TODO Fire event listener
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        let mouse = event.get_mouse();
+        if self.enabled && event.get_button().is_primary() && !self.pressing_mouses.contains(&mouse)
+        {
+            self.pressing_mouses.push(mouse);
+            buddy.request_render();
+        }
     }
 
-    fn on_mouse_enter(&mut self, _event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_button().is_primary() {
+            let num_pressing_mouses = self.pressing_mouses.len();
+            self.pressing_mouses.retain(|mouse| *mouse != event.get_mouse());
+            if self.pressing_mouses.len() != num_pressing_mouses {
+                buddy.request_render();
+            }
+        }
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        if !self.hovering_mouses.contains(&event.get_mouse()) {
+            self.hovering_mouses.push(event.get_mouse());
+        }
         buddy.request_render();
     }
 
-    fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        // When the mouse leaves the button, it can no longer release the button
+        let mouse = event.get_mouse();
+        self.hovering_mouses.retain(|hovering_mouse| *hovering_mouse != mouse);
+        self.pressing_mouses.retain(|pressing_mouse| *pressing_mouse != mouse);
         buddy.request_render();
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_click_and_drawn_region() {
+        let counter = Rc::new(RefCell::new(0));
+        let button_counter = Rc::clone(&counter);
+        let mut button = TextButton::new(
+            "Click me",
+            TextButtonStyle::from_palette(&Palette::light()),
+            Box::new(move |_buddy| *button_counter.borrow_mut() += 1),
+        );
+
+        let mut buddy = RootComponentBuddy::new();
+        button.on_attach(&mut buddy);
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 200, 50));
+        let result = button.render(&renderer, &mut buddy, false).unwrap();
+        assert!(result.filter_mouse_actions);
+
+        // The background should be centered, and have rounded corners
+        let region = result.drawn_region;
+        assert!(region.is_inside(Point::new(0.5, 0.5)));
+        assert!(!region.is_inside(Point::new(region.get_left(), region.get_bottom())));
+        assert!(!region.is_inside(Point::new(region.get_right(), region.get_top())));
+        assert!((region.get_left() + region.get_right() - 1.0).abs() < 0.001);
+        assert!((region.get_bottom() + region.get_top() - 1.0).abs() < 0.001);
+
+        let mouse = Mouse::new(0);
+        let point = Point::new(0.5, 0.5);
+        button.on_mouse_click(
+            MouseClickEvent::new(mouse, point, MouseButton::primary()),
+            &mut buddy,
+        );
+        button.on_mouse_click(
+            MouseClickEvent::new(mouse, point, MouseButton::new(1)),
+            &mut buddy,
+        );
+        assert_eq!(1, *counter.borrow());
+
+        button.set_enabled(false);
+        button.on_mouse_click(
+            MouseClickEvent::new(mouse, point, MouseButton::primary()),
+            &mut buddy,
+        );
+        assert_eq!(1, *counter.borrow());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum InitialsBadgeShape {
+    Circle,
+    /// A rounded rectangle. The `corner_radius` is relative to the size of the badge, so it
+    /// should be between 0.0 (sharp corners) and 0.5 (which is equivalent to a circle).
+    RoundedRectangle { corner_radius: f32 },
+}
+
+pub struct InitialsBadgeStyle {
+    pub font_id: Option<String>,
+    pub shape: InitialsBadgeShape,
+    pub background_color: Color,
+    pub text_color: Color,
+    /// The margin between the edge of the shape and the initials, relative to the size of the badge
+    pub margin: f32,
+}
+
+impl InitialsBadgeStyle {
+    /// Creates a circular `InitialsBadgeStyle` that uses the secondary colors of the given
+    /// `Palette`
+    pub fn from_palette(palette: &Palette) -> Self {
+        Self {
+            font_id: None,
+            shape: InitialsBadgeShape::Circle,
+            background_color: palette.get_color(ColorRole::Secondary),
+            text_color: palette.get_readable_color(ColorRole::OnSecondary, ColorRole::Secondary),
+            margin: 0.2,
+        }
+    }
+
+    /// Creates the `InitialsBadgeStyle` for the palette and font of the given `Theme`
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            font_id: theme.font_id.clone(),
+            ..Self::from_palette(&theme.palette)
+        }
+    }
+}
+
+/// A component that draws the initials of the name of a user or other entity on a (square)
+/// background with the shape of the style, centered in its domain. This can be used as avatar
+/// when no profile picture is available.
+pub struct InitialsBadge {
+    name: String,
+    initials: String,
+    style: InitialsBadgeStyle,
+    theme_follower: Option<ThemeFollower<InitialsBadgeStyle>>,
+    shader: FragmentOnlyShader,
+}
+
+fn shader_description() -> FragmentOnlyShaderDescription {
+    FragmentOnlyShaderDescription {
+        source_code: "
+            void main() {
+                vec2 distanceToCenter = abs(innerPosition - vec2(0.5, 0.5));
+                vec2 cornerDistance = max(distanceToCenter - vec2(0.5 - float1), vec2(0.0, 0.0));
+                if (length(cornerDistance) > float1) {
+                    discard;
+                }
+                gl_FragColor = color1;
+            }
+        ".to_string(),
+        num_float_matrices: 0,
+        num_colors: 1,
+        num_float_vectors: 0,
+        num_int_vectors: 0,
+        num_floats: 1,
+        num_ints: 0
+    }
+}
+
+fn compute_initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(|first_char| first_char.to_uppercase())
+        .collect()
+}
+
+impl InitialsBadge {
+    pub fn new(name: &str, style: InitialsBadgeStyle) -> Self {
+        Self {
+            name: name.to_string(),
+            initials: compute_initials(name),
+            style,
+            theme_follower: None,
+            shader: Self::create_shader(),
+        }
+    }
+
+    /// Lets this badge follow the `Theme` of its buddy, see `StyleOverrides.resolve_theme`
+    pub fn follow_theme(&mut self, overrides: StyleOverrides<InitialsBadgeStyle>) {
+        self.theme_follower = Some(ThemeFollower::new(overrides));
+    }
+
+    /// Creates the `FragmentOnlyShader` that initials badges use. See `Renderer.prewarm`.
+    pub(crate) fn create_shader() -> FragmentOnlyShader {
+        FragmentOnlyShader::new(shader_description())
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_initials(&self) -> &str {
+        &self.initials
+    }
+}
+
+impl Component for InitialsBadge {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.set_accessible_label(Some(self.name.clone()));
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        if let Some(follower) = &mut self.theme_follower {
+            follower.update(&mut self.style, buddy);
+        }
+
+        // The badge should be square, so it may not use the entire width or height
+        let aspect_ratio = renderer.get_viewport().get_aspect_ratio();
+        let (size_x, size_y) = match aspect_ratio >= 1.0 {
+            true => (1.0 / aspect_ratio, 1.0),
+            false => (1.0, aspect_ratio),
+        };
+        let min_x = 0.5 - 0.5 * size_x;
+        let min_y = 0.5 - 0.5 * size_y;
+        let max_x = 0.5 + 0.5 * size_x;
+        let max_y = 0.5 + 0.5 * size_y;
+
+        let corner_radius = match self.style.shape {
+            InitialsBadgeShape::Circle => 0.5,
+            InitialsBadgeShape::RoundedRectangle { corner_radius } => {
+                corner_radius.max(0.0).min(0.5)
+            }
+        };
+        renderer.apply_fragment_shader(
+            min_x, min_y, max_x, max_y, &self.shader, FragmentOnlyDrawParameters {
+                colors: &[self.style.background_color],
+                floats: &[corner_radius],
+                ..FragmentOnlyDrawParameters::default()
+            }
+        );
+
+        if !self.initials.is_empty() {
+            let text_style = TextStyle {
+                font_id: self.style.font_id.clone(),
+                text_color: self.style.text_color,
+                background_color: self.style.background_color,
+                background_fill_mode: TextBackgroundFillMode::DoNot,
+            };
+            let margin_x = self.style.margin * size_x;
+            let margin_y = self.style.margin * size_y;
+            renderer.get_text_renderer().draw_text(
+                &self.initials, &text_style, TextDrawPosition {
+                    min_x: min_x + margin_x,
+                    min_y: min_y + margin_y,
+                    max_x: max_x - margin_x,
+                    max_y: max_y - margin_y,
+                    horizontal_alignment: HorizontalTextAlignment::Center,
+                    vertical_alignment: VerticalTextAlignment::Center,
+                }, renderer, None
+            )?;
+        }
+
+        let drawn_region: Box<dyn DrawnRegion> = match self.style.shape {
+            InitialsBadgeShape::Circle => Box::new(OvalDrawnRegion::new(
+                Point::new(0.5, 0.5), 0.5 * size_x, 0.5 * size_y
+            )),
+            InitialsBadgeShape::RoundedRectangle { .. } => Box::new(RectangularDrawnRegion::new(
+                min_x, min_y, max_x, max_y
+            )),
+        };
+        Ok(RenderResultStruct {
+            drawn_region,
+            filter_mouse_actions: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_compute_initials() {
+        assert_eq!("", compute_initials(""));
+        assert_eq!("", compute_initials("   "));
+        assert_eq!("K", compute_initials("knokko"));
+        assert_eq!("JD", compute_initials("John Doe"));
+        assert_eq!("JR", compute_initials("  john  ronald reuel "));
+        assert_eq!("ÉÅ", compute_initials("élise åberg"));
+    }
+}
@@ -0,0 +1,407 @@
+use crate::*;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+const ELLIPSIS: &str = "...";
+
+/// Determines what a `TextLabel` does with lines that are too wide for the label
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TextOverflow {
+    /// Draw the line smaller, such that it fits in the width of the label
+    Shrink,
+    /// Cut off the end of the line, and replace it with an ellipsis
+    Ellipsis,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextLabelStyle {
+    pub font_id: Option<String>,
+    pub text_color: Color,
+    pub background_color: Color,
+    pub background_fill_mode: TextBackgroundFillMode,
+    /// The height of each line, relative to the height of the label. The label can show
+    /// `floor(1.0 / line_height)` lines (but at least 1).
+    pub line_height: f32,
+    /// Whether lines that are too wide should be broken into multiple lines (at spaces). Explicit
+    /// line breaks will always be respected.
+    pub wrap: bool,
+    pub overflow: TextOverflow,
+}
+
+impl TextLabelStyle {
+    /// Creates a `TextLabelStyle` for text on the background of the given `Palette`, using the
+    /// default font. The label will show a single line that shrinks when it's too wide.
+    pub fn from_palette(palette: &Palette) -> Self {
+        Self {
+            font_id: None,
+            text_color: palette.get_readable_color(ColorRole::OnBackground, ColorRole::Background),
+            background_color: palette.get_color(ColorRole::Background),
+            background_fill_mode: TextBackgroundFillMode::DoNot,
+            line_height: 1.0,
+            wrap: false,
+            overflow: TextOverflow::Shrink,
+        }
+    }
+
+    /// Creates the `TextLabelStyle` for the palette and font of the given `Theme`
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            font_id: theme.font_id.clone(),
+            ..Self::from_palette(&theme.palette)
+        }
+    }
+}
+
+struct LabelState {
+    text: String,
+    style: TextLabelStyle,
+    version: u64,
+}
+
+/// Can be used to change the text and style of a `TextLabel` after it has been attached, for
+/// instance from the event listener of another component. Cloning a `TextLabelController` gives
+/// another reference to the *same* controller. Use `TextLabel.get_controller` to obtain one.
+///
+/// The label will re-render automatically during its next `TickEvent` after the text or style
+/// changed. To follow a theme change, resolve the new style (for instance with
+/// `StyleOverrides.resolve`) and pass it to `set_style`.
+#[derive(Clone)]
+pub struct TextLabelController {
+    state: Rc<RefCell<LabelState>>,
+}
+
+impl TextLabelController {
+    pub fn get_text(&self) -> String {
+        self.state.borrow().text.clone()
+    }
+
+    pub fn set_text(&self, text: impl Into<String>) {
+        let text = text.into();
+        let mut state = self.state.borrow_mut();
+        if state.text != text {
+            state.text = text;
+            state.version += 1;
+        }
+    }
+
+    pub fn get_style(&self) -> TextLabelStyle {
+        self.state.borrow().style.clone()
+    }
+
+    pub fn set_style(&self, style: TextLabelStyle) {
+        let mut state = self.state.borrow_mut();
+        if state.style != style {
+            state.style = style;
+            state.version += 1;
+        }
+    }
+}
+
+/// A component that draws static text, possibly spread over multiple lines. Unlike the
+/// `SimpleTextComponent`, this label can wrap its text and truncate it with an ellipsis (see
+/// `TextLabelStyle`). The lines will be placed according to the given alignments.
+///
+/// The text and style can be changed with a `TextLabelController`, after which the label will
+/// re-render itself.
+pub struct TextLabel {
+    controller: TextLabelController,
+    horizontal_alignment: HorizontalTextAlignment,
+    vertical_alignment: VerticalTextAlignment,
+    rendered_version: u64,
+    theme_follower: Option<ThemeFollower<TextLabelStyle>>,
+}
+
+impl TextLabel {
+    pub fn new(
+        text: impl Into<String>,
+        horizontal_alignment: HorizontalTextAlignment,
+        vertical_alignment: VerticalTextAlignment,
+        style: TextLabelStyle,
+    ) -> Self {
+        Self {
+            controller: TextLabelController {
+                state: Rc::new(RefCell::new(LabelState {
+                    text: text.into(),
+                    style,
+                    version: 0,
+                })),
+            },
+            horizontal_alignment,
+            vertical_alignment,
+            rendered_version: 0,
+            theme_follower: None,
+        }
+    }
+
+    /// Lets this label follow the `Theme` of its buddy, see `StyleOverrides.resolve_theme`. Note
+    /// that this will overwrite styles that are set with `TextLabelController.set_style` when the
+    /// theme changes.
+    pub fn follow_theme(&mut self, overrides: StyleOverrides<TextLabelStyle>) {
+        self.theme_follower = Some(ThemeFollower::new(overrides));
+    }
+
+    pub fn get_controller(&self) -> TextLabelController {
+        self.controller.clone()
+    }
+}
+
+/// Splits *text* into the lines that a `TextLabel` with the given *style* and *aspect_ratio*
+/// should draw. The *grapheme_widths* map each grapheme to its width, relative to the line height.
+fn layout_lines(
+    text: &str, style: &TextLabelStyle, aspect_ratio: f32, grapheme_widths: &HashMap<&str, f32>,
+) -> Vec<String> {
+    let line_height = style.line_height.clamp(0.01, 1.0);
+    let max_lines = ((1.0 / line_height + 0.001).floor() as usize).max(1);
+    let max_width = aspect_ratio / line_height;
+    let width_of = |line: &str| -> f32 {
+        line.graphemes(true).map(|grapheme| grapheme_widths[grapheme]).sum()
+    };
+    let space_width = width_of(" ");
+
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        if !style.wrap {
+            lines.push(paragraph.to_string());
+            continue;
+        }
+
+        let mut line = String::new();
+        let mut line_width = 0.0;
+        for (index, word) in paragraph.split(' ').enumerate() {
+            let word_width = width_of(word);
+            if index == 0 {
+                line.push_str(word);
+                line_width = word_width;
+            } else if line_width + space_width + word_width <= max_width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += space_width + word_width;
+            } else {
+                lines.push(line);
+                line = word.to_string();
+                line_width = word_width;
+            }
+        }
+        lines.push(line);
+    }
+
+    let truncated = lines.len() > max_lines;
+    lines.truncate(max_lines);
+
+    let num_lines = lines.len();
+    let ellipsis_width = width_of(ELLIPSIS);
+    for (index, line) in lines.iter_mut().enumerate() {
+        let is_last_visible_line = truncated && index == num_lines - 1;
+        let shrink = style.overflow == TextOverflow::Shrink;
+        if shrink && is_last_visible_line {
+            line.push_str(ELLIPSIS);
+        }
+        if shrink || (width_of(line) <= max_width && !is_last_visible_line) {
+            continue;
+        }
+
+        let mut graphemes: Vec<_> = line.graphemes(true).collect();
+        let mut width = width_of(line);
+        while width + ellipsis_width > max_width || graphemes.last() == Some(&" ") {
+            match graphemes.pop() {
+                Some(grapheme) => width -= grapheme_widths[grapheme],
+                None => break,
+            }
+        }
+        *line = graphemes.concat() + ELLIPSIS;
+    }
+
+    lines
+}
+
+impl Component for TextLabel {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.set_accessible_label(Some(self.controller.get_text()));
+        buddy.subscribe_tick();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        if let Some(follower) = &mut self.theme_follower {
+            follower.update(&mut self.controller.state.borrow_mut().style, buddy);
+        }
+
+        let state = self.controller.state.borrow();
+        self.rendered_version = state.version;
+        let style = &state.style;
+
+        let text_style = TextStyle {
+            font_id: style.font_id.clone(),
+            text_color: style.text_color,
+            background_color: style.background_color,
+            background_fill_mode: match style.background_fill_mode {
+                TextBackgroundFillMode::EntireDomain => TextBackgroundFillMode::DoNot,
+                fill_mode => fill_mode,
+            },
+        };
+        if style.background_fill_mode == TextBackgroundFillMode::EntireDomain {
+            renderer.clear(style.background_color);
+        }
+
+        // Measure each grapheme only once, to avoid creating text models for all candidate lines
+        let text_renderer = renderer.get_text_renderer();
+        let mut grapheme_widths = HashMap::new();
+        let all_graphemes = state.text.split('\n').chain(Some(ELLIPSIS)).chain(Some(" "));
+        for grapheme in all_graphemes.flat_map(|paragraph| paragraph.graphemes(true)) {
+            if !grapheme_widths.contains_key(grapheme) {
+                let (width, height) = text_renderer.get_text_size(grapheme, &text_style, renderer)?;
+                grapheme_widths.insert(grapheme, width as f32 / height as f32);
+            }
+        }
+
+        let aspect_ratio = renderer.get_viewport().get_aspect_ratio();
+        let lines = layout_lines(&state.text, style, aspect_ratio, &grapheme_widths);
+
+        let line_height = style.line_height.clamp(0.01, 1.0);
+        let block_height = lines.len() as f32 * line_height;
+        let block_max_y = match self.vertical_alignment {
+            VerticalTextAlignment::Bottom => block_height,
+            VerticalTextAlignment::Center => 0.5 + 0.5 * block_height,
+            VerticalTextAlignment::Top => 1.0,
+        };
+
+        let mut drawn_regions: Vec<Box<dyn DrawnRegion>> = Vec::with_capacity(lines.len());
+        for (index, line) in lines.iter().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let max_y = block_max_y - index as f32 * line_height;
+            let position = TextDrawPosition {
+                min_x: 0.0,
+                min_y: max_y - line_height,
+                max_x: 1.0,
+                max_y,
+                horizontal_alignment: self.horizontal_alignment,
+                vertical_alignment: self.vertical_alignment,
+            };
+            let drawn = text_renderer.draw_text(line, &text_style, position, renderer, None)?;
+            drawn_regions.push(Box::new(RectangularDrawnRegion::new(
+                drawn.min_x, drawn.min_y, drawn.max_x, drawn.max_y
+            )));
+        }
+
+        if style.background_fill_mode == TextBackgroundFillMode::EntireDomain {
+            return entire_render_result();
+        }
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(CompositeDrawnRegion::new(drawn_regions)),
+            filter_mouse_actions: false,
+        })
+    }
+
+    fn on_tick(&mut self, _event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.controller.state.borrow().version != self.rendered_version {
+            buddy.set_accessible_label(Some(self.controller.get_text()));
+            buddy.request_render();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use super::layout_lines;
+
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn grapheme_widths(text: &str) -> HashMap<&str, f32> {
+        // Every grapheme is half as wide as a line is high
+        text.split("").chain(vec![".", " "])
+            .filter(|grapheme| !grapheme.is_empty())
+            .map(|grapheme| (grapheme, 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn test_layout_lines() {
+        let text = "the quick brown fox\njumps";
+        let widths = grapheme_widths(text);
+        let mut style = TextLabelStyle::from_palette(&Palette::light());
+
+        // A single line that shrinks, and ends with an ellipsis because the second line is hidden
+        assert_eq!(vec!["the quick brown fox..."], layout_lines(text, &style, 1.0, &widths));
+
+        // Each line can be 10 graphemes wide
+        style.line_height = 0.25;
+        style.wrap = true;
+        assert_eq!(
+            vec!["the quick", "brown fox", "jumps"],
+            layout_lines(text, &style, 1.25, &widths)
+        );
+
+        // Only 2 lines fit, so the second line should end with an ellipsis
+        style.line_height = 0.5;
+        assert_eq!(
+            vec!["the quick brown", "fox..."],
+            layout_lines(text, &style, 4.0, &widths)
+        );
+
+        style.overflow = TextOverflow::Ellipsis;
+        assert_eq!(vec!["the quick brown", "fox..."], layout_lines(text, &style, 4.0, &widths));
+
+        style.wrap = false;
+        assert_eq!(vec!["the qui...", "jumps"], layout_lines(text, &style, 2.5, &widths));
+        assert_eq!(
+            vec!["..."],
+            layout_lines("longword", &style, 0.25, &grapheme_widths("longword"))
+        );
+    }
+
+    #[test]
+    fn test_render_after_change() {
+        let mut label = TextLabel::new(
+            "Hello",
+            HorizontalTextAlignment::Left,
+            VerticalTextAlignment::Top,
+            TextLabelStyle::from_palette(&Palette::light()),
+        );
+        let controller = label.get_controller();
+
+        let mut buddy = RootComponentBuddy::new();
+        label.on_attach(&mut buddy);
+        assert_eq!(Some("Hello"), buddy.get_accessible_text().get_label());
+
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 200, 50));
+        let result = label.render(&renderer, &mut buddy, false).unwrap();
+        assert!(result.drawn_region.get_left() < 0.01);
+        assert!(result.drawn_region.get_top() > 0.99);
+        buddy.clear_render_request();
+
+        // Nothing changed, so the label shouldn't request a render
+        let tick = TickEvent::new(Duration::from_millis(10));
+        label.on_tick(tick, &mut buddy);
+        assert!(!buddy.did_request_render());
+
+        controller.set_text("Hello world");
+        label.on_tick(tick, &mut buddy);
+        assert!(buddy.did_request_render());
+        assert_eq!(Some("Hello world"), buddy.get_accessible_text().get_label());
+        buddy.clear_render_request();
+
+        label.render(&renderer, &mut buddy, false).unwrap();
+        label.on_tick(tick, &mut buddy);
+        assert!(!buddy.did_request_render());
+
+        let mut style = controller.get_style();
+        style.text_color = Color::rgb(200, 0, 0);
+        controller.set_style(style);
+        label.on_tick(tick, &mut buddy);
+        assert!(buddy.did_request_render());
+    }
+}
@@ -1,5 +1,11 @@
+mod breadcrumbs;
 mod button;
+mod initials_badge;
+mod label;
 mod simple;
 
+pub use breadcrumbs::*;
 pub use button::*;
+pub use initials_badge::*;
+pub use label::*;
 pub use simple::*;
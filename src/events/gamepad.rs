@@ -0,0 +1,150 @@
+/// Represents a gamepad (or another kind of game controller) that is connected to the device. Like
+/// `Mouse`, this struct only holds an id that distinguishes the gamepad from the other connected
+/// gamepads. Components can use the `get_gamepads` method of their `ComponentBuddy` to find out
+/// which gamepads are connected.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Gamepad {
+    id: u16,
+}
+
+impl Gamepad {
+    /// Constructs a new `Gamepad` with the given `id`. Only the *wrapper* should use this
+    /// function.
+    pub fn new(id: u16) -> Self {
+        Self { id }
+    }
+
+    /// Gets the numerical id of this `Gamepad`. This method is mostly useful for the *wrapper*.
+    pub fn get_id(&self) -> u16 {
+        self.id
+    }
+}
+
+/// The buttons of a gamepad. The names of the face buttons are based on their position rather
+/// than their label, because the labels differ between brands: the `South` button is the 'A'
+/// button of an Xbox controller and the 'Cross' button of a PlayStation controller.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GamepadButton {
+    /// The bottom face button, which is typically used to confirm or activate
+    South,
+    /// The right face button, which is typically used to cancel or go back
+    East,
+    /// The top face button
+    North,
+    /// The left face button
+    West,
+    LeftShoulder,
+    RightShoulder,
+    /// The left trigger, when the gamepad reports it as a button. Analog triggers are reported as
+    /// the `LeftTrigger` `GamepadAxis` as well.
+    LeftTrigger,
+    /// The right trigger, when the gamepad reports it as a button. Analog triggers are reported as
+    /// the `RightTrigger` `GamepadAxis` as well.
+    RightTrigger,
+    /// The 'Select', 'Back', 'View', or 'Share' button
+    Select,
+    /// The 'Start', 'Menu', or 'Options' button
+    Start,
+    /// The 'Home' or 'Guide' button (the button with the logo of the brand)
+    Mode,
+    /// Pressing the left stick
+    LeftStick,
+    /// Pressing the right stick
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// The axes of a gamepad, see `GamepadAxisEvent`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// This event is for the `on_gamepad_button` method of `Component`. It will be fired whenever a
+/// button of a connected gamepad is pressed or released. Components can use this to let users
+/// navigate with a controller (for instance in couch or TV applications).
+///
+/// Note that there is no focus system yet, so *all* components that are subscribed for gamepad
+/// events will receive this event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GamepadButtonEvent {
+    gamepad: Gamepad,
+    button: GamepadButton,
+    pressed: bool,
+}
+
+impl GamepadButtonEvent {
+    /// Constructs a new `GamepadButtonEvent`. This function should normally only be used by the
+    /// *wrapper*.
+    pub fn new(gamepad: Gamepad, button: GamepadButton, pressed: bool) -> Self {
+        Self {
+            gamepad,
+            button,
+            pressed,
+        }
+    }
+
+    /// Gets the `Gamepad` whose button was pressed or released
+    pub fn get_gamepad(&self) -> Gamepad {
+        self.gamepad
+    }
+
+    /// Gets the `GamepadButton` that was pressed or released
+    pub fn get_button(&self) -> GamepadButton {
+        self.button
+    }
+
+    /// Checks whether the button was pressed (`true`) or released (`false`)
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+}
+
+/// This event is for the `on_gamepad_axis` method of `Component`. It will be fired whenever the
+/// value of an axis (a stick or an analog trigger) of a connected gamepad changes. Just like
+/// `GamepadButtonEvent`, *all* components that are subscribed for gamepad events will receive it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GamepadAxisEvent {
+    gamepad: Gamepad,
+    axis: GamepadAxis,
+    value: f32,
+}
+
+impl GamepadAxisEvent {
+    /// Constructs a new `GamepadAxisEvent`. This function should normally only be used by the
+    /// *wrapper*. See `get_value` for the range of *value*.
+    pub fn new(gamepad: Gamepad, axis: GamepadAxis, value: f32) -> Self {
+        debug_assert!((-1.0..=1.0).contains(&value));
+        Self {
+            gamepad,
+            axis,
+            value,
+        }
+    }
+
+    /// Gets the `Gamepad` whose axis changed
+    pub fn get_gamepad(&self) -> Gamepad {
+        self.gamepad
+    }
+
+    /// Gets the `GamepadAxis` that changed
+    pub fn get_axis(&self) -> GamepadAxis {
+        self.axis
+    }
+
+    /// Gets the new value of the axis. The values of the sticks are between -1.0 and 1.0, where
+    /// positive values point to the right (for X) or upward (for Y), like the coordinate system
+    /// of the components. The values of the triggers are between 0.0 (released) and 1.0 (fully
+    /// pressed).
+    pub fn get_value(&self) -> f32 {
+        self.value
+    }
+}
@@ -0,0 +1,196 @@
+mod recognizer;
+
+pub(crate) use recognizer::*;
+
+use crate::{Mouse, Point};
+
+/// This event is for the `on_pinch` method of `Component`. It indicates that the user moved 2
+/// fingers towards each other (to zoom out) or away from each other (to zoom in).
+///
+/// The `Application` synthesizes this event from the `Mouse`s that have their primary button
+/// pressed, which are the fingers on a touch screen. Whenever one of the first 2 fingers moves,
+/// the `Application` fires a `PinchEvent` (and a `RotateGestureEvent` when the angle between the
+/// fingers changed) at the component below the center of the fingers. Components need to use
+/// `ComponentBuddy.subscribe_gestures` to receive it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PinchEvent {
+    mouse: Mouse,
+    center: Point,
+    scale: f32,
+    scale_velocity: f32,
+}
+
+impl PinchEvent {
+    /// Constructs a new `PinchEvent` with the given first finger `Mouse`, center `Point`, scale,
+    /// and scale velocity
+    pub fn new(mouse: Mouse, center: Point, scale: f32, scale_velocity: f32) -> Self {
+        Self {
+            mouse,
+            center,
+            scale,
+            scale_velocity,
+        }
+    }
+
+    /// Gets the `Mouse` of the first finger of the gesture
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the point in the middle of the 2 fingers, relative to the component that listens to
+    /// this event
+    pub fn get_center(&self) -> Point {
+        self.center
+    }
+
+    /// Gets the distance between the fingers divided by their distance during the previous
+    /// `PinchEvent` (or when the gesture started). This is larger than 1 when the fingers moved
+    /// away from each other, so components can multiply their zoom level with it.
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Gets the relative change of the distance between the fingers per second. For instance, a
+    /// velocity of 0.5 means that the distance is growing by 50% per second. This is useful for
+    /// components that want to keep zooming for a while after the user lifted the fingers.
+    pub fn get_scale_velocity(&self) -> f32 {
+        self.scale_velocity
+    }
+}
+
+/// This event is for the `on_rotate_gesture` method of `Component`. It indicates that the user
+/// rotated 2 fingers around each other. See `PinchEvent` for the details about when this event
+/// is fired.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RotateGestureEvent {
+    mouse: Mouse,
+    center: Point,
+    angle: f32,
+    angular_velocity: f32,
+}
+
+impl RotateGestureEvent {
+    /// Constructs a new `RotateGestureEvent` with the given first finger `Mouse`, center `Point`,
+    /// angle, and angular velocity
+    pub fn new(mouse: Mouse, center: Point, angle: f32, angular_velocity: f32) -> Self {
+        Self {
+            mouse,
+            center,
+            angle,
+            angular_velocity,
+        }
+    }
+
+    /// Gets the `Mouse` of the first finger of the gesture
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the point in the middle of the 2 fingers, relative to the component that listens to
+    /// this event
+    pub fn get_center(&self) -> Point {
+        self.center
+    }
+
+    /// Gets the angle (in radians) that the fingers rotated since the previous
+    /// `RotateGestureEvent` (or since the gesture started). Positive angles are counterclockwise.
+    /// The angle is measured on the screen, so it is not distorted by the aspect ratio of the
+    /// window or the component.
+    pub fn get_angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Gets the speed at which the fingers are rotating, in radians per second
+    pub fn get_angular_velocity(&self) -> f32 {
+        self.angular_velocity
+    }
+}
+
+/// The main direction of a `SwipeEvent`
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// This event is for the `on_swipe` method of `Component`. It indicates that the user quickly
+/// moved a single finger over the screen and lifted it.
+///
+/// The `Application` fires this event when the primary button of a `Mouse` is released after it
+/// moved at least the `min_swipe_distance` of the `InputSettings`, at a velocity of at least the
+/// `min_swipe_velocity`. Fingers that took part in a pinch or rotate gesture can't cause swipes.
+/// The event is fired at the component where the swipe started.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SwipeEvent {
+    mouse: Mouse,
+    start_point: Point,
+    end_point: Point,
+    velocity_x: f32,
+    velocity_y: f32,
+}
+
+impl SwipeEvent {
+    /// Constructs a new `SwipeEvent` with the given `Mouse`, start and end `Point`, and velocity
+    pub fn new(
+        mouse: Mouse,
+        start_point: Point,
+        end_point: Point,
+        velocity_x: f32,
+        velocity_y: f32,
+    ) -> Self {
+        Self {
+            mouse,
+            start_point,
+            end_point,
+            velocity_x,
+            velocity_y,
+        }
+    }
+
+    /// Gets the `Mouse` (finger) that swiped
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the point where the swipe started, relative to the component that listens to this
+    /// event
+    pub fn get_start_point(&self) -> Point {
+        self.start_point
+    }
+
+    /// Gets the point where the finger was lifted, relative to the component that listens to
+    /// this event
+    pub fn get_end_point(&self) -> Point {
+        self.end_point
+    }
+
+    /// Gets the horizontal velocity of the finger right before it was lifted, in component widths
+    /// per second
+    pub fn get_velocity_x(&self) -> f32 {
+        self.velocity_x
+    }
+
+    /// Gets the vertical velocity of the finger right before it was lifted, in component heights
+    /// per second (positive when the finger moved up)
+    pub fn get_velocity_y(&self) -> f32 {
+        self.velocity_y
+    }
+
+    /// Gets the main direction of this swipe, which is determined by the largest component of
+    /// the velocity
+    pub fn get_direction(&self) -> SwipeDirection {
+        if self.velocity_x.abs() >= self.velocity_y.abs() {
+            if self.velocity_x >= 0.0 {
+                SwipeDirection::Right
+            } else {
+                SwipeDirection::Left
+            }
+        } else if self.velocity_y >= 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use crate::*;
+
+use std::f32::consts::PI;
+use std::time::Duration;
+
+/// The velocity of a swipe is measured over (at least) this period before the finger was lifted
+const SWIPE_VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+struct Finger {
+    mouse: Mouse,
+    start_point: Point,
+    // The recent positions of the finger, which are needed to compute the swipe velocity
+    samples: Vec<(Duration, Point)>,
+    // Fingers that took part in a multi-finger gesture can't swipe
+    multi_touch: bool,
+}
+
+impl Finger {
+    fn get_point(&self) -> Point {
+        self.samples.last().unwrap().1
+    }
+
+    fn add_sample(&mut self, time: Duration, point: Point) {
+        self.samples.push((time, point));
+
+        // Keep the most recent sample that is at least SWIPE_VELOCITY_WINDOW old
+        while self.samples.len() > 1 && self.samples[1].0 + SWIPE_VELOCITY_WINDOW <= time {
+            self.samples.remove(0);
+        }
+    }
+}
+
+struct TwoFingerGesture {
+    mouses: [Mouse; 2],
+    distance: f32,
+    angle: f32,
+    time: Duration,
+    // The distance, angle, and time at the end of an earlier frame, which are needed to compute
+    // the velocities
+    previous_sample: (f32, f32, Duration),
+}
+
+/// Synthesizes `PinchEvent`s, `RotateGestureEvent`s, and `SwipeEvent`s from the fingers (the
+/// `Mouse`s whose primary button is pressed). The `Application` feeds the points of the fingers
+/// in the coordinate system of the root component, along with the current time.
+pub(crate) struct GestureRecognizer {
+    // The fingers in the order in which they were pressed. The first 2 fingers make up the
+    // current two-finger gesture.
+    fingers: Vec<Finger>,
+    two_finger_gesture: Option<TwoFingerGesture>,
+    // The aspect ratio of the window, which is needed to measure distances and angles on the
+    // screen rather than in the (typically non-square) coordinate system of the root component
+    aspect_ratio: f32,
+}
+
+impl GestureRecognizer {
+    pub(crate) fn new() -> Self {
+        Self {
+            fingers: Vec::new(),
+            two_finger_gesture: None,
+            aspect_ratio: 1.0,
+        }
+    }
+
+    pub(crate) fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
+        self.aspect_ratio = aspect_ratio;
+    }
+
+    /// Should be called whenever the primary button of a mouse is pressed
+    pub(crate) fn press(&mut self, mouse: Mouse, point: Point, time: Duration) {
+        self.fingers.retain(|finger| finger.mouse != mouse);
+        self.fingers.push(Finger {
+            mouse,
+            start_point: point,
+            samples: vec![(time, point)],
+            multi_touch: false,
+        });
+
+        if self.fingers.len() >= 2 {
+            for finger in &mut self.fingers {
+                finger.multi_touch = true;
+            }
+        }
+        self.update_two_finger_gesture(time);
+    }
+
+    /// Should be called whenever a mouse moves, and returns the gesture events that should be
+    /// fired because of it
+    pub(crate) fn move_finger(
+        &mut self,
+        mouse: Mouse,
+        point: Point,
+        time: Duration,
+    ) -> (Option<PinchEvent>, Option<RotateGestureEvent>) {
+        let finger = match self.fingers.iter_mut().find(|finger| finger.mouse == mouse) {
+            Some(finger) => finger,
+            None => return (None, None),
+        };
+        finger.add_sample(time, point);
+
+        let gesture = match &mut self.two_finger_gesture {
+            Some(gesture) if gesture.mouses.contains(&mouse) => gesture,
+            _ => return (None, None),
+        };
+        let (center, distance, angle) = measure(
+            self.fingers[0].get_point(),
+            self.fingers[1].get_point(),
+            self.aspect_ratio,
+        );
+
+        // The scale and angle are meaningless while the fingers are at the same position
+        if distance <= 0.0 || gesture.distance <= 0.0 {
+            gesture.distance = distance;
+            gesture.angle = angle;
+            gesture.previous_sample = (distance, angle, time);
+            return (None, None);
+        }
+
+        if time > gesture.time {
+            gesture.previous_sample = (gesture.distance, gesture.angle, gesture.time);
+            gesture.time = time;
+        }
+        let (sample_distance, sample_angle, sample_time) = gesture.previous_sample;
+        let elapsed = (time - sample_time).as_secs_f32();
+        let (scale_velocity, angular_velocity) = if elapsed > 0.0 && sample_distance > 0.0 {
+            (
+                (distance - sample_distance) / sample_distance / elapsed,
+                normalize_angle(angle - sample_angle) / elapsed,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        let scale = distance / gesture.distance;
+        let rotation = normalize_angle(angle - gesture.angle);
+        gesture.distance = distance;
+        gesture.angle = angle;
+
+        let first_mouse = gesture.mouses[0];
+        let pinch_event = if scale != 1.0 {
+            Some(PinchEvent::new(first_mouse, center, scale, scale_velocity))
+        } else {
+            None
+        };
+        let rotate_event = if rotation != 0.0 {
+            Some(RotateGestureEvent::new(
+                first_mouse,
+                center,
+                rotation,
+                angular_velocity,
+            ))
+        } else {
+            None
+        };
+        (pinch_event, rotate_event)
+    }
+
+    /// Should be called whenever the primary button of a mouse is released, and returns the
+    /// `SwipeEvent` that should be fired because of it (if any)
+    pub(crate) fn release(
+        &mut self,
+        mouse: Mouse,
+        point: Point,
+        time: Duration,
+        settings: &InputSettings,
+    ) -> Option<SwipeEvent> {
+        let index = self
+            .fingers
+            .iter()
+            .position(|finger| finger.mouse == mouse)?;
+        let mut finger = self.fingers.remove(index);
+        finger.add_sample(time, point);
+        self.update_two_finger_gesture(time);
+
+        if finger.multi_touch {
+            return None;
+        }
+
+        let (sample_time, sample_point) = finger.samples[0];
+        if time <= sample_time {
+            // The velocity can't be determined when the entire swipe happened at the same time
+            return None;
+        }
+        let elapsed = (time - sample_time).as_secs_f32();
+        let velocity_x = (point.get_x() - sample_point.get_x()) / elapsed;
+        let velocity_y = (point.get_y() - sample_point.get_y()) / elapsed;
+
+        let distance = finger.start_point.distance_to(point);
+        let speed = (velocity_x * velocity_x + velocity_y * velocity_y).sqrt();
+        if distance >= settings.min_swipe_distance && speed >= settings.min_swipe_velocity {
+            Some(SwipeEvent::new(
+                mouse,
+                finger.start_point,
+                point,
+                velocity_x,
+                velocity_y,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Should be called when a mouse leaves the window, which ends its gestures without swipe
+    pub(crate) fn cancel(&mut self, mouse: Mouse, time: Duration) {
+        self.fingers.retain(|finger| finger.mouse != mouse);
+        self.update_two_finger_gesture(time);
+    }
+
+    /// Starts a new two-finger gesture when the first 2 fingers changed
+    fn update_two_finger_gesture(&mut self, time: Duration) {
+        if self.fingers.len() < 2 {
+            self.two_finger_gesture = None;
+            return;
+        }
+
+        let mouses = [self.fingers[0].mouse, self.fingers[1].mouse];
+        if let Some(gesture) = &self.two_finger_gesture {
+            if gesture.mouses == mouses {
+                return;
+            }
+        }
+
+        let (_, distance, angle) = measure(
+            self.fingers[0].get_point(),
+            self.fingers[1].get_point(),
+            self.aspect_ratio,
+        );
+        self.two_finger_gesture = Some(TwoFingerGesture {
+            mouses,
+            distance,
+            angle,
+            time,
+            previous_sample: (distance, angle, time),
+        });
+    }
+}
+
+/// Computes the center between the 2 given points, as well as the distance and the angle between
+/// them (as seen on the screen)
+fn measure(first: Point, second: Point, aspect_ratio: f32) -> (Point, f32, f32) {
+    let center = Point::new(
+        (first.get_x() + second.get_x()) / 2.0,
+        (first.get_y() + second.get_y()) / 2.0,
+    );
+    let dx = (second.get_x() - first.get_x()) * aspect_ratio;
+    let dy = second.get_y() - first.get_y();
+    (center, (dx * dx + dy * dy).sqrt(), dy.atan2(dx))
+}
+
+/// Converts the given angle (in radians) to the equivalent angle between -PI and PI
+fn normalize_angle(angle: f32) -> f32 {
+    let angle = angle % (2.0 * PI);
+    if angle > PI {
+        angle - 2.0 * PI
+    } else if angle <= -PI {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::GestureRecognizer;
+    use crate::*;
+    use std::f32::consts::PI;
+    use std::time::Duration;
+
+    fn millis(amount: u64) -> Duration {
+        Duration::from_millis(amount)
+    }
+
+    fn assert_near(expected: f32, actual: f32) {
+        assert!((expected - actual).abs() < 0.001, "{}", actual);
+    }
+
+    #[test]
+    fn test_pinch_and_rotate() {
+        let settings = InputSettings::default();
+        let mut recognizer = GestureRecognizer::new();
+        recognizer.set_aspect_ratio(2.0);
+        let first = Mouse::new(1);
+        let second = Mouse::new(2);
+
+        // A single finger can't pinch
+        recognizer.press(first, Point::new(0.25, 0.5), millis(0));
+        let (pinch, rotate) = recognizer.move_finger(first, Point::new(0.375, 0.5), millis(10));
+        assert!(pinch.is_none() && rotate.is_none());
+
+        // The fingers are 0.25 apart on the screen since the window is twice as wide as high
+        recognizer.press(second, Point::new(0.5, 0.5), millis(10));
+        let (pinch, rotate) = recognizer.move_finger(second, Point::new(0.625, 0.5), millis(110));
+        let pinch = pinch.unwrap();
+        assert_eq!(first, pinch.get_mouse());
+        assert_eq!(Point::new(0.5, 0.5), pinch.get_center());
+        assert_near(2.0, pinch.get_scale());
+        assert_near(10.0, pinch.get_scale_velocity());
+        assert!(rotate.is_none());
+
+        // Rotate the second finger counterclockwise around the first finger
+        let (pinch, rotate) = recognizer.move_finger(second, Point::new(0.375, 1.0), millis(210));
+        assert!(pinch.is_none());
+        let rotate = rotate.unwrap();
+        assert_near(PI / 2.0, rotate.get_angle());
+        assert_near(5.0 * PI, rotate.get_angular_velocity());
+
+        // When the second finger is lifted, the third finger should take over
+        let swipe = recognizer.release(second, Point::new(0.375, 1.0), millis(210), &settings);
+        assert!(swipe.is_none());
+        let third = Mouse::new(3);
+        recognizer.press(third, Point::new(0.375, 0.0), millis(220));
+        let (pinch, rotate) = recognizer.move_finger(first, Point::new(0.375, 0.25), millis(320));
+        assert_near(0.5, pinch.unwrap().get_scale());
+        assert!(rotate.is_none());
+    }
+
+    #[test]
+    fn test_swipe() {
+        let settings = InputSettings::default();
+        let mut recognizer = GestureRecognizer::new();
+        let mouse = Mouse::new(1);
+
+        // A slow drag shouldn't be a swipe, even when it's long
+        recognizer.press(mouse, Point::new(0.1, 0.5), millis(0));
+        recognizer.move_finger(mouse, Point::new(0.5, 0.5), millis(1000));
+        let swipe = recognizer.release(mouse, Point::new(0.51, 0.5), millis(1100), &settings);
+        assert!(swipe.is_none());
+
+        // The velocity should only depend on the last part of the swipe
+        recognizer.press(mouse, Point::new(0.1, 0.5), millis(2000));
+        recognizer.move_finger(mouse, Point::new(0.2, 0.5), millis(3000));
+        recognizer.move_finger(mouse, Point::new(0.3, 0.4), millis(3050));
+        let swipe = recognizer.release(mouse, Point::new(0.4, 0.4), millis(3100), &settings);
+        let swipe = swipe.unwrap();
+        assert_eq!(mouse, swipe.get_mouse());
+        assert_eq!(Point::new(0.1, 0.5), swipe.get_start_point());
+        assert_eq!(Point::new(0.4, 0.4), swipe.get_end_point());
+        assert_near(2.0, swipe.get_velocity_x());
+        assert_near(-1.0, swipe.get_velocity_y());
+        assert_eq!(SwipeDirection::Right, swipe.get_direction());
+
+        // Fast, but too short
+        recognizer.press(mouse, Point::new(0.5, 0.5), millis(4000));
+        let swipe = recognizer.release(mouse, Point::new(0.5, 0.45), millis(4010), &settings);
+        assert!(swipe.is_none());
+
+        // Fingers that took part in a pinch can't swipe
+        let other = Mouse::new(2);
+        recognizer.press(mouse, Point::new(0.5, 0.5), millis(5000));
+        recognizer.press(other, Point::new(0.6, 0.5), millis(5000));
+        recognizer.release(other, Point::new(0.6, 0.5), millis(5010), &settings);
+        let swipe = recognizer.release(mouse, Point::new(0.5, 0.0), millis(5050), &settings);
+        assert!(swipe.is_none());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::*;
+
+/// An input event that can be queued with `Application.queue_event`. The queued events will be
+/// processed in the order in which they were queued during the next `Application.update` (or
+/// `Application.frame`) call.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    MouseClick(MouseClickEvent),
+    MousePress(MousePressEvent),
+    MouseRelease(MouseReleaseEvent),
+    MouseMove(MouseMoveEvent),
+    MouseEnter(MouseEnterEvent),
+    MouseLeave(MouseLeaveEvent),
+    MouseScroll(MouseScrollEvent),
+    RelativeMouseMove(RelativeMouseMoveEvent),
+    CharType(CharTypeEvent),
+    CompositionStart(CompositionStartEvent),
+    CompositionUpdate(CompositionUpdateEvent),
+    CompositionEnd(CompositionEndEvent),
+    GamepadButton(GamepadButtonEvent),
+    GamepadAxis(GamepadAxisEvent),
+    LowMemory(LowMemoryEvent),
+    WindowFocus(WindowFocusEvent),
+    WindowMinimize(WindowMinimizeEvent),
+}
@@ -0,0 +1,40 @@
+/// How urgently the application should reduce its memory usage, see `LowMemoryEvent`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum MemoryPressure {
+    /// The device is running low on memory: components should release the memory that they can
+    /// easily restore (like caches).
+    Moderate,
+    /// The device is almost out of memory, and the operating system (or browser) is likely to kill
+    /// the application soon: components should release everything they can do without, even if it
+    /// is expensive to restore.
+    Critical,
+}
+
+/// This event is for the `on_low_memory` method of `Component`. The *wrapper* will fire this event
+/// when the device is running low on memory, so that the application can degrade gracefully
+/// (instead of being killed by the operating system or browser). All components that are
+/// subscribed will receive this event, including hidden components.
+///
+/// Besides firing this event, the `Application` will let the `Renderer` release its cached
+/// resources during the next `render` (see `Renderer.release_memory`).
+///
+/// Note that not all platforms tell when they are low on memory: the web *wrapper* estimates it
+/// from the JS heap usage (which is only exposed by Chromium-based browsers), and the desktop
+/// *wrapper* currently never fires this event.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct LowMemoryEvent {
+    pressure: MemoryPressure,
+}
+
+impl LowMemoryEvent {
+    /// Constructs a new `LowMemoryEvent` with the given `MemoryPressure`. This function should
+    /// normally only be used by the *wrapper*.
+    pub fn new(pressure: MemoryPressure) -> Self {
+        Self { pressure }
+    }
+
+    /// Gets the `MemoryPressure`, which indicates how much memory the components should release
+    pub fn get_pressure(&self) -> MemoryPressure {
+        self.pressure
+    }
+}
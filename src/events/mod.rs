@@ -1,5 +1,21 @@
+mod gamepad;
+mod gestures;
+mod input;
+mod memory;
 mod mouse;
+mod settings;
 mod text;
+mod theme;
+mod tick;
+mod window;
 
+pub use gamepad::*;
+pub use gestures::*;
+pub use input::*;
+pub use memory::*;
 pub use mouse::*;
+pub use settings::*;
 pub use text::*;
+pub use theme::*;
+pub use tick::*;
+pub use window::*;
@@ -25,6 +25,18 @@ impl MouseButton {
         Self { index: 0 }
     }
 
+    /// Constructs an instance of `MouseButton` that represents the *secondary* button of a
+    /// `Mouse`, which is the right button of desktop mouses (see `get_index`).
+    pub const fn secondary() -> Self {
+        Self { index: 1 }
+    }
+
+    /// Constructs an instance of `MouseButton` that represents the *middle* button of a `Mouse`,
+    /// which is the wheel button of desktop mouses (see `get_index`).
+    pub const fn middle() -> Self {
+        Self { index: 2 }
+    }
+
     /// Gets the numerical index of this mouse button.
     ///
     /// This will always be 0 for the primary button, and some other value for
@@ -62,6 +74,16 @@ impl MouseButton {
     pub fn is_primary(&self) -> bool {
         self.index == 0
     }
+
+    /// Checks whether this is the secondary (typically right) mouse button
+    pub fn is_secondary(&self) -> bool {
+        self.index == 1
+    }
+
+    /// Checks whether this is the middle (typically wheel) mouse button
+    pub fn is_middle(&self) -> bool {
+        self.index == 2
+    }
 }
 
 #[cfg(test)]
@@ -79,4 +101,13 @@ mod tests {
         assert!(!MouseButton::new(3).is_primary());
         assert_eq!(3, MouseButton::new(3).get_index());
     }
+
+    #[test]
+    fn test_secondary_and_middle() {
+        assert!(MouseButton::secondary().is_secondary());
+        assert!(!MouseButton::secondary().is_middle());
+        assert!(MouseButton::middle().is_middle());
+        assert!(!MouseButton::primary().is_secondary());
+        assert_eq!(MouseButton::new(2), MouseButton::middle());
+    }
 }
@@ -1,8 +1,14 @@
 mod button;
+mod pointer;
+mod seat;
+mod virtual_mouse;
 
 use crate::Point;
 
 pub use button::*;
+pub use pointer::*;
+pub use seat::*;
+pub use virtual_mouse::*;
 
 /// Represents a mouse, or something else that can generate events *at screen
 /// positions* (like clicking, moving, dragging...).
@@ -18,16 +24,32 @@ pub use button::*;
 /// ### Creating instances
 /// The `new` function can be used to construct `Mouse`s, but only the
 /// *wrapper* should do this.
+///
+/// ### Seats
+/// A `Mouse` can optionally be tagged with a *seat* (or player), for instance
+/// when multiple players use their own controller cursor in a split-screen
+/// application. Menus can use a `SeatPolicy` to restrict which components can
+/// be used by which seat. Mouses without seat are shared by all seats.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct Mouse {
     id: u16,
+    seat: Option<u8>,
 }
 
 impl Mouse {
-    /// Constructs a new `Mouse` with the given `id`. Only the *wrapper* should
-    /// use this function.
+    /// Constructs a new `Mouse` with the given `id` that doesn't belong to any
+    /// seat. Only the *wrapper* should use this function.
     pub fn new(id: u16) -> Self {
-        Self { id }
+        Self { id, seat: None }
+    }
+
+    /// Constructs a new `Mouse` with the given `id` that belongs to the given
+    /// `seat`. Only the *wrapper* should use this function.
+    pub fn with_seat(id: u16, seat: u8) -> Self {
+        Self {
+            id,
+            seat: Some(seat),
+        }
     }
 
     /// Gets the numerical id of this `Mouse`. This method is mostly useful for the
@@ -35,6 +57,12 @@ impl Mouse {
     pub fn get_id(&self) -> u16 {
         self.id
     }
+
+    /// Gets the seat (player) this `Mouse` belongs to, or `None` if this mouse
+    /// is shared by all seats.
+    pub fn get_seat(&self) -> Option<u8> {
+        self.seat
+    }
 }
 
 /// This event is for the `on_mouse_click` method of `Component`.
@@ -42,7 +70,7 @@ impl Mouse {
 ///
 /// Use `MouseClickOutEvent` and the corresponding `on_mouse_click_out` method
 /// to keep track of mouse clicks outside the component.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct MouseClickEvent {
     mouse: Mouse,
     point: Point,
@@ -117,15 +145,29 @@ pub struct MousePressEvent {
     mouse: Mouse,
     point: Point,
     button: MouseButton,
+    details: PointerDetails,
 }
 
 impl MousePressEvent {
     /// Constructs a new `MousePressEvent` with the given `Mouse`, `Point`, and `MouseButton`.
+    /// The event won't have any `PointerDetails`.
     pub fn new(mouse: Mouse, point: Point, button: MouseButton) -> Self {
+        Self::with_details(mouse, point, button, PointerDetails::default())
+    }
+
+    /// Constructs a new `MousePressEvent` with the given `Mouse`, `Point`, `MouseButton`, and
+    /// `PointerDetails`.
+    pub fn with_details(
+        mouse: Mouse,
+        point: Point,
+        button: MouseButton,
+        details: PointerDetails,
+    ) -> Self {
         Self {
             mouse,
             point,
             button,
+            details,
         }
     }
 
@@ -143,6 +185,11 @@ impl MousePressEvent {
     pub fn get_button(&self) -> MouseButton {
         self.button
     }
+
+    /// Gets the `PointerDetails` (like the pressure) of the tool that was pressed.
+    pub fn get_details(&self) -> PointerDetails {
+        self.details
+    }
 }
 
 /// This event is for the `on_mouse_release` method of `Component`. It indicates that the user has
@@ -155,15 +202,29 @@ pub struct MouseReleaseEvent {
     mouse: Mouse,
     point: Point,
     button: MouseButton,
+    details: PointerDetails,
 }
 
 impl MouseReleaseEvent {
     /// Constructs a new `MouseReleaseEvent` with the given `Mouse`, `Point`, and `MouseButton`.
+    /// The event won't have any `PointerDetails`.
     pub fn new(mouse: Mouse, point: Point, button: MouseButton) -> Self {
+        Self::with_details(mouse, point, button, PointerDetails::default())
+    }
+
+    /// Constructs a new `MouseReleaseEvent` with the given `Mouse`, `Point`, `MouseButton`, and
+    /// `PointerDetails`.
+    pub fn with_details(
+        mouse: Mouse,
+        point: Point,
+        button: MouseButton,
+        details: PointerDetails,
+    ) -> Self {
         Self {
             mouse,
             point,
             button,
+            details,
         }
     }
 
@@ -181,6 +242,201 @@ impl MouseReleaseEvent {
     pub fn get_button(&self) -> MouseButton {
         self.button
     }
+
+    /// Gets the `PointerDetails` (like the pressure) of the tool that was released.
+    pub fn get_details(&self) -> PointerDetails {
+        self.details
+    }
+}
+
+/// This event is for the `on_mouse_double_click` method of `Component`. It indicates that the user
+/// clicked twice on the component (with the same button), quickly after each other.
+///
+/// The `Application` generates this event (right after the second `MouseClickEvent`) when the
+/// time between both clicks is at most the `double_click_time` of its `InputSettings` and the
+/// distance between both clicks is smaller than the `drag_start_distance`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MouseDoubleClickEvent {
+    mouse: Mouse,
+    point: Point,
+    button: MouseButton,
+}
+
+impl MouseDoubleClickEvent {
+    /// Constructs a new `MouseDoubleClickEvent` with the given `Mouse`, `Point`, and `MouseButton`.
+    pub fn new(mouse: Mouse, point: Point, button: MouseButton) -> Self {
+        Self {
+            mouse,
+            point,
+            button,
+        }
+    }
+
+    /// Gets the `Mouse` that was double clicked.
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the `Point` where the mouse was double clicked.
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the `MouseButton` that was double clicked.
+    pub fn get_button(&self) -> MouseButton {
+        self.button
+    }
+}
+
+/// This event is for the `on_mouse_long_press` method of `Component`. It indicates that the user
+/// pressed a mouse button on the component and kept holding it down.
+///
+/// The `Application` generates this event when the button has been held down for at least the
+/// `long_press_duration` of its `InputSettings`, while the mouse didn't move further than the
+/// `drag_start_distance` from the point where it was pressed. Note that the `Application`
+/// measures time using the `TickEvent`s that the *wrapper* fires.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MouseLongPressEvent {
+    mouse: Mouse,
+    point: Point,
+    button: MouseButton,
+}
+
+impl MouseLongPressEvent {
+    /// Constructs a new `MouseLongPressEvent` with the given `Mouse`, `Point`, and `MouseButton`.
+    pub fn new(mouse: Mouse, point: Point, button: MouseButton) -> Self {
+        Self {
+            mouse,
+            point,
+            button,
+        }
+    }
+
+    /// Gets the `Mouse` that was pressed.
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the `Point` where the mouse was pressed.
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the `MouseButton` that was pressed.
+    pub fn get_button(&self) -> MouseButton {
+        self.button
+    }
+}
+
+/// Describes how the user requested a context menu, see `MouseContextClickEvent`
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ContextClickSource {
+    /// The user clicked with the secondary (typically right) mouse button
+    SecondaryButton,
+    /// The user pressed the primary button (typically a finger on a touch screen) for a long
+    /// time. See `InputSettings.long_press_context_click`.
+    LongPress,
+}
+
+/// This event is for the `on_mouse_context_click` method of `Component`. It indicates that the
+/// user requested a context menu on the component, typically by right-clicking it.
+///
+/// The `Application` generates this event right after each `MouseClickEvent` with the secondary
+/// button, so that components don't need to check the button of each click event themselves.
+/// On touch screens (which don't have a secondary button), it can also be generated after a
+/// `MouseLongPressEvent` (see `InputSettings.long_press_context_click`).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MouseContextClickEvent {
+    mouse: Mouse,
+    point: Point,
+    source: ContextClickSource,
+}
+
+impl MouseContextClickEvent {
+    /// Constructs a new `MouseContextClickEvent` with the given `Mouse`, `Point`, and
+    /// `ContextClickSource`.
+    pub fn new(mouse: Mouse, point: Point, source: ContextClickSource) -> Self {
+        Self {
+            mouse,
+            point,
+            source,
+        }
+    }
+
+    /// Gets the `Mouse` that requested the context menu.
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the `Point` where the context menu was requested.
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the way the user requested the context menu.
+    pub fn get_source(&self) -> ContextClickSource {
+        self.source
+    }
+}
+
+/// This event is for the `on_mouse_middle_click` method of `Component`. It indicates that the user
+/// clicked on the component with the middle button (typically the mouse wheel).
+///
+/// The `Application` generates this event right after each `MouseClickEvent` with the middle
+/// button, so that components don't need to check the button of each click event themselves.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MouseMiddleClickEvent {
+    mouse: Mouse,
+    point: Point,
+}
+
+impl MouseMiddleClickEvent {
+    /// Constructs a new `MouseMiddleClickEvent` with the given `Mouse` and `Point`.
+    pub fn new(mouse: Mouse, point: Point) -> Self {
+        Self { mouse, point }
+    }
+
+    /// Gets the `Mouse` that was clicked.
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the `Point` where the mouse was clicked.
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+}
+
+/// This event is for the `on_mouse_hover` method of `Component`. It indicates that the mouse has
+/// been resting on the component for a while, which suggests that the user is interested in it
+/// (rather than merely passing over it on its way to another component). This is the right
+/// moment to show things like tooltips and previews.
+///
+/// The `Application` generates this event when a mouse didn't move for at least the
+/// `hover_duration` of its `InputSettings`. It will be fired only once per rest: the mouse needs
+/// to move again before the next `MouseHoverEvent` can be fired. Pressing a mouse button also
+/// cancels the pending hover event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MouseHoverEvent {
+    mouse: Mouse,
+    point: Point,
+}
+
+impl MouseHoverEvent {
+    /// Constructs a new `MouseHoverEvent` with the given `Mouse` and `Point`.
+    pub fn new(mouse: Mouse, point: Point) -> Self {
+        Self { mouse, point }
+    }
+
+    /// Gets the `Mouse` that is hovering.
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the `Point` where the mouse is resting.
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
 }
 
 /// This method is for the `on_mouse_move` method of `Component`. It indicates
@@ -203,13 +459,25 @@ pub struct MouseMoveEvent {
     mouse: Mouse,
     from: Point,
     to: Point,
+    details: PointerDetails,
 }
 
 impl MouseMoveEvent {
     /// Constructs a new `MouseMoveEvent` indicating that `mouse` moved from
-    /// `from` to `to`
+    /// `from` to `to`. The event won't have any `PointerDetails`.
     pub fn new(mouse: Mouse, from: Point, to: Point) -> Self {
-        Self { mouse, from, to }
+        Self::with_details(mouse, from, to, PointerDetails::default())
+    }
+
+    /// Constructs a new `MouseMoveEvent` indicating that `mouse` moved from
+    /// `from` to `to`, with the given `PointerDetails` at the new position
+    pub fn with_details(mouse: Mouse, from: Point, to: Point, details: PointerDetails) -> Self {
+        Self {
+            mouse,
+            from,
+            to,
+            details,
+        }
     }
 
     /// Gets the `Mouse` that was moved
@@ -238,6 +506,11 @@ impl MouseMoveEvent {
     pub fn get_delta_y(&self) -> f32 {
         self.to.get_y() - self.from.get_y()
     }
+
+    /// Gets the `PointerDetails` (like the pressure) of the tool at the new position
+    pub fn get_details(&self) -> PointerDetails {
+        self.details
+    }
 }
 
 /// The event for the `on_mouse_enter` method of `Component`. It indicates that the
@@ -316,3 +589,95 @@ impl MouseLeaveEvent {
         self.exit_point
     }
 }
+
+/// This event is for the `on_mouse_scroll` method of `Component`. It indicates that the user used
+/// the scroll wheel (or a similar input like a touchpad gesture) while the mouse cursor was **on**
+/// the component.
+///
+/// ### Scroll amounts
+/// The deltas are expressed in *lines*: 1 notch of a typical scroll wheel corresponds to a delta
+/// of 1. Devices with smooth scrolling (like touchpads) can cause fractional deltas. A positive
+/// `delta_y` means that the user scrolled up (away from the user) and a positive `delta_x` means
+/// that the user scrolled to the right.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MouseScrollEvent {
+    mouse: Mouse,
+    point: Point,
+    delta_x: f32,
+    delta_y: f32,
+}
+
+impl MouseScrollEvent {
+    /// Constructs a new `MouseScrollEvent` with the given `Mouse`, `Point`, and scroll deltas.
+    pub fn new(mouse: Mouse, point: Point, delta_x: f32, delta_y: f32) -> Self {
+        Self {
+            mouse,
+            point,
+            delta_x,
+            delta_y,
+        }
+    }
+
+    /// Gets the `Mouse` that was used to scroll.
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the position of the mouse cursor while scrolling, relative to the component that
+    /// listens to this event
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the number of lines the user scrolled to the right (negative when the user scrolled
+    /// to the left)
+    pub fn get_delta_x(&self) -> f32 {
+        self.delta_x
+    }
+
+    /// Gets the number of lines the user scrolled up (negative when the user scrolled down)
+    pub fn get_delta_y(&self) -> f32 {
+        self.delta_y
+    }
+}
+
+/// This event is for the `on_relative_mouse_move` method of `Component`. It is only fired at the
+/// component that holds the pointer lock of the mouse (see `ComponentBuddy.request_pointer_lock`).
+///
+/// Unlike `MouseMoveEvent`, this event doesn't have a position: while the pointer is locked, the
+/// cursor is hidden and the mouse can be moved indefinitely in any direction (which is useful for
+/// rotating the camera of a 3D viewport, for instance). The deltas are relative to the size of
+/// the component: moving the mouse over a distance equal to the width of the component results in
+/// a `delta_x` of 1.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RelativeMouseMoveEvent {
+    mouse: Mouse,
+    delta_x: f32,
+    delta_y: f32,
+}
+
+impl RelativeMouseMoveEvent {
+    /// Constructs a new `RelativeMouseMoveEvent` with the given `Mouse` and movement deltas
+    pub fn new(mouse: Mouse, delta_x: f32, delta_y: f32) -> Self {
+        Self {
+            mouse,
+            delta_x,
+            delta_y,
+        }
+    }
+
+    /// Gets the `Mouse` that was moved
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the distance the mouse travelled to the right (negative when it moved to the left)
+    pub fn get_delta_x(&self) -> f32 {
+        self.delta_x
+    }
+
+    /// Gets the distance the mouse travelled upwards (negative when it moved downwards)
+    pub fn get_delta_y(&self) -> f32 {
+        self.delta_y
+    }
+}
@@ -0,0 +1,76 @@
+/// The kind of tool that controls a `Mouse`, see `PointerDetails`
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PointerToolType {
+    /// A regular mouse (or touchpad)
+    Mouse,
+    /// A finger on a touch screen
+    Finger,
+    /// The tip of a pen/stylus
+    Pen,
+    /// The eraser of a pen/stylus (typically the back of the pen)
+    Eraser,
+}
+
+/// Additional information about the tool that caused a `MousePressEvent`, `MouseReleaseEvent`, or
+/// `MouseMoveEvent`, which is useful for drawing components that want to support pressure-sensitive
+/// input. All properties are optional, since most platforms and devices only support some of them
+/// (and events that were constructed with the `new` function of the event don't have any).
+///
+/// Only the *wrapper* should construct instances of this struct (unit tests can do it as well).
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct PointerDetails {
+    tool_type: Option<PointerToolType>,
+    pressure: Option<f32>,
+    tilt: Option<(f32, f32)>,
+}
+
+impl PointerDetails {
+    /// Constructs new `PointerDetails` with the given tool type, pressure, and tilt. See the
+    /// corresponding getters for the meaning of the values.
+    pub fn new(
+        tool_type: Option<PointerToolType>,
+        pressure: Option<f32>,
+        tilt: Option<(f32, f32)>,
+    ) -> Self {
+        if let Some(pressure) = pressure {
+            debug_assert!((0.0..=1.0).contains(&pressure));
+        }
+        Self {
+            tool_type,
+            pressure,
+            tilt,
+        }
+    }
+
+    /// Constructs new `PointerDetails` that only know the tool type
+    pub fn with_tool_type(tool_type: PointerToolType) -> Self {
+        Self::new(Some(tool_type), None, None)
+    }
+
+    /// Gets the kind of tool that caused the event, or `None` if the *wrapper* doesn't know it
+    pub fn get_tool_type(&self) -> Option<PointerToolType> {
+        self.tool_type
+    }
+
+    /// Gets the normalized pressure of the tool, which is between 0.0 (no pressure) and 1.0 (the
+    /// maximum pressure the device can detect), or `None` if the device doesn't report pressure
+    pub fn get_pressure(&self) -> Option<f32> {
+        self.pressure
+    }
+
+    /// Gets the pressure of the tool (see `get_pressure`), or *default_pressure* if the device
+    /// doesn't report pressure. Drawing components can use this to draw lines of a fixed width
+    /// for devices without pressure sensor.
+    pub fn get_pressure_or(&self, default_pressure: f32) -> f32 {
+        self.pressure.unwrap_or(default_pressure)
+    }
+
+    /// Gets the tilt of a pen in radians, or `None` if it is unknown. The first value is the angle
+    /// between the pen and the vertical plane through the y-axis (positive when the top of the
+    /// pen is tilted to the right). The second value is the angle between the pen and the vertical
+    /// plane through the x-axis (positive when the top of the pen is tilted towards the user).
+    /// Both values are 0.0 when the pen is perpendicular to the screen.
+    pub fn get_tilt(&self) -> Option<(f32, f32)> {
+        self.tilt
+    }
+}
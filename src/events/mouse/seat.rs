@@ -0,0 +1,55 @@
+use crate::Mouse;
+
+/// Determines which `Mouse`s are allowed to interact with a component, based on the *seat* of
+/// the mouses. This is mostly useful for split-screen applications where each player has its own
+/// cursor and should only be able to use its own part of the screen.
+///
+/// Mouses that don't have a seat (see `Mouse::get_seat`) are shared by all seats, so they are
+/// accepted by every policy.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SeatPolicy {
+    /// Mouses of all seats can interact with the component. This is the default policy.
+    AllSeats,
+    /// Only mouses of the given seat (and mouses without seat) can interact with the component.
+    OnlySeat(u8),
+}
+
+impl SeatPolicy {
+    /// Checks whether the given `Mouse` is allowed to interact with a component that uses this
+    /// policy.
+    pub fn accepts(&self, mouse: Mouse) -> bool {
+        match self {
+            Self::AllSeats => true,
+            Self::OnlySeat(seat) => match mouse.get_seat() {
+                Some(mouse_seat) => mouse_seat == *seat,
+                None => true,
+            },
+        }
+    }
+}
+
+impl Default for SeatPolicy {
+    fn default() -> Self {
+        Self::AllSeats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_accepts() {
+        let shared = Mouse::new(0);
+        let player1 = Mouse::with_seat(1, 1);
+        let player2 = Mouse::with_seat(2, 2);
+
+        assert!(SeatPolicy::AllSeats.accepts(shared));
+        assert!(SeatPolicy::AllSeats.accepts(player1));
+        assert!(SeatPolicy::AllSeats.accepts(player2));
+
+        assert!(SeatPolicy::OnlySeat(1).accepts(shared));
+        assert!(SeatPolicy::OnlySeat(1).accepts(player1));
+        assert!(!SeatPolicy::OnlySeat(1).accepts(player2));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::*;
+
+use std::time::Duration;
+
+/// The arrow keys that can move a `VirtualMouse`
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ArrowKey {
+    Left,
+    Right,
+    Down,
+    Up,
+}
+
+/// A synthetic `Mouse` that is controlled by the arrow keys of a keyboard or the stick of a
+/// controller, which makes all mouse-based components usable without a real mouse. See
+/// `InputSettings.virtual_mouse`.
+///
+/// The virtual mouse doesn't fire any events itself: instead, it *queues* ordinary mouse events
+/// (like `MouseMoveEvent`s and `MouseClickEvent`s) in the `Application`, so these events flow
+/// through the normal pipeline. Components can't distinguish the virtual mouse from a real one,
+/// except by its id.
+///
+/// The *wrapper* should tell the virtual mouse which arrow keys are pressed (or where the stick
+/// is pointing), and call `update` right before each `Application.update`. It should also call
+/// `press_button` and `release_button` when the key that acts as mouse button (for instance the
+/// space bar) is pressed or released.
+///
+/// ## Example
+/// ```
+/// use knukki::*;
+/// use std::time::Duration;
+///
+/// let mut application = Application::new(Box::new(SimpleFlatMenu::new(None)));
+/// let mut virtual_mouse = VirtualMouse::new(Mouse::new(1), Point::new(0.5, 0.5));
+///
+/// virtual_mouse.set_arrow_pressed(ArrowKey::Right, true);
+/// virtual_mouse.update(&mut application, Duration::from_millis(200));
+/// virtual_mouse.set_arrow_pressed(ArrowKey::Right, false);
+///
+/// // The default speed is 0.5 viewports per second
+/// assert!(Point::new(0.6, 0.5).nearly_equal(virtual_mouse.get_position()));
+/// virtual_mouse.press_button(&mut application, MouseButton::primary());
+/// virtual_mouse.release_button(&mut application, MouseButton::primary());
+///
+/// // This fires the queued enter, move, press, release, and click events
+/// application.update(Duration::from_millis(16));
+/// ```
+pub struct VirtualMouse {
+    mouse: Mouse,
+    position: Point,
+    has_entered: bool,
+
+    pressed_arrows: Vec<ArrowKey>,
+    stick_x: f32,
+    stick_y: f32,
+
+    pressed_buttons: Vec<(MouseButton, Point)>,
+}
+
+impl VirtualMouse {
+    /// Constructs a `VirtualMouse` for the given *mouse* that starts at the given *position*.
+    /// The *mouse* should have an id that is not used by any real mouse.
+    pub fn new(mouse: Mouse, position: Point) -> Self {
+        Self {
+            mouse,
+            position,
+            has_entered: false,
+            pressed_arrows: Vec::with_capacity(2),
+            stick_x: 0.0,
+            stick_y: 0.0,
+            pressed_buttons: Vec::with_capacity(1),
+        }
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the current position of the virtual mouse, in the same coordinate system as the
+    /// `Point`s that the *wrapper* passes to the `Application`
+    pub fn get_position(&self) -> Point {
+        self.position
+    }
+
+    /// Marks the given *arrow* key as pressed or released
+    pub fn set_arrow_pressed(&mut self, arrow: ArrowKey, pressed: bool) {
+        if pressed {
+            if !self.pressed_arrows.contains(&arrow) {
+                self.pressed_arrows.push(arrow);
+            }
+        } else {
+            self.pressed_arrows.retain(|pressed_arrow| *pressed_arrow != arrow);
+        }
+    }
+
+    /// Sets the position of the stick of a controller, where both *x* and *y* should be between
+    /// -1.0 and 1.0. The *wrapper* should apply a dead zone before calling this method, since
+    /// the virtual mouse would otherwise drift slowly.
+    pub fn set_stick(&mut self, x: f32, y: f32) {
+        self.stick_x = x;
+        self.stick_y = y;
+    }
+
+    /// Gets the direction in which the virtual mouse is currently moving, which combines the
+    /// pressed arrow keys and the stick position. The length of the direction is at most 1.
+    pub fn get_direction(&self) -> (f32, f32) {
+        let mut x = self.stick_x;
+        let mut y = self.stick_y;
+        for arrow in &self.pressed_arrows {
+            match arrow {
+                ArrowKey::Left => x -= 1.0,
+                ArrowKey::Right => x += 1.0,
+                ArrowKey::Down => y -= 1.0,
+                ArrowKey::Up => y += 1.0,
+            }
+        }
+
+        let length = (x * x + y * y).sqrt();
+        if length > 1.0 {
+            (x / length, y / length)
+        } else {
+            (x, y)
+        }
+    }
+
+    fn enter_if_needed(&mut self, application: &mut Application) {
+        if !self.has_entered {
+            let event = MouseEnterEvent::new(self.mouse, self.position);
+            application.queue_event(InputEvent::MouseEnter(event));
+            self.has_entered = true;
+        }
+    }
+
+    /// Moves the virtual mouse in its current direction (see `get_direction`) for *delta_time*,
+    /// using the `virtual_mouse_speed` of the `InputSettings` of the *application*. When the
+    /// position changes, a `MouseMoveEvent` will be queued in the *application* (preceded by a
+    /// `MouseEnterEvent` if the virtual mouse hasn't entered yet).
+    ///
+    /// The virtual mouse can't leave the viewport: its coordinates are clamped between 0.0
+    /// and 1.0.
+    pub fn update(&mut self, application: &mut Application, delta_time: Duration) {
+        let (direction_x, direction_y) = self.get_direction();
+        let distance = application.get_input_settings().virtual_mouse_speed
+            * delta_time.as_secs_f32();
+
+        let new_position = Point::new(
+            (self.position.get_x() + direction_x * distance).clamp(0.0, 1.0),
+            (self.position.get_y() + direction_y * distance).clamp(0.0, 1.0),
+        );
+        if new_position != self.position {
+            self.enter_if_needed(application);
+            let event = MouseMoveEvent::new(self.mouse, self.position, new_position);
+            application.queue_event(InputEvent::MouseMove(event));
+            self.position = new_position;
+        }
+    }
+
+    /// Queues a `MousePressEvent` for the given *button* at the current position. Pressing a
+    /// button that is already pressed (for instance due to key repeat) has no effect.
+    pub fn press_button(&mut self, application: &mut Application, button: MouseButton) {
+        if self.pressed_buttons.iter().any(|(pressed_button, _)| *pressed_button == button) {
+            return;
+        }
+
+        self.enter_if_needed(application);
+        let event = MousePressEvent::new(self.mouse, self.position, button);
+        application.queue_event(InputEvent::MousePress(event));
+        self.pressed_buttons.push((button, self.position));
+    }
+
+    /// Queues a `MouseReleaseEvent` for the given *button* at the current position. If the
+    /// virtual mouse didn't move further than the `drag_start_distance` since the button was
+    /// pressed, a `MouseClickEvent` will be queued as well. Releasing a button that isn't
+    /// pressed has no effect.
+    pub fn release_button(&mut self, application: &mut Application, button: MouseButton) {
+        let press_index = self.pressed_buttons.iter().position(
+            |(pressed_button, _)| *pressed_button == button
+        );
+        if let Some(press_index) = press_index {
+            let (_, press_point) = self.pressed_buttons.remove(press_index);
+
+            let event = MouseReleaseEvent::new(self.mouse, self.position, button);
+            application.queue_event(InputEvent::MouseRelease(event));
+
+            let max_distance = application.get_input_settings().drag_start_distance;
+            if self.position.distance_to(press_point) < max_distance {
+                let event = MouseClickEvent::new(self.mouse, self.position, button);
+                application.queue_event(InputEvent::MouseClick(event));
+            }
+        }
+    }
+
+    /// Releases all pressed buttons (without clicking) and queues a `MouseLeaveEvent`. This
+    /// should be used when the user switches back to a real mouse. The virtual mouse will enter
+    /// again as soon as it moves or presses a button.
+    pub fn leave(&mut self, application: &mut Application) {
+        for (button, _) in std::mem::take(&mut self.pressed_buttons) {
+            let event = MouseReleaseEvent::new(self.mouse, self.position, button);
+            application.queue_event(InputEvent::MouseRelease(event));
+        }
+        self.pressed_arrows.clear();
+        self.stick_x = 0.0;
+        self.stick_y = 0.0;
+
+        if self.has_entered {
+            let event = MouseLeaveEvent::new(self.mouse, self.position);
+            application.queue_event(InputEvent::MouseLeave(event));
+            self.has_entered = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    struct LogComponent {
+        log: Rc<RefCell<Vec<(&'static str, Point)>>>,
+    }
+
+    impl Component for LogComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+            buddy.subscribe_mouse_enter();
+            buddy.subscribe_mouse_leave();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(("click", event.get_point()));
+        }
+
+        fn on_mouse_enter(&mut self, event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(("enter", event.get_entrance_point()));
+        }
+
+        fn on_mouse_leave(&mut self, event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(("leave", event.get_exit_point()));
+        }
+    }
+
+    #[test]
+    fn test_virtual_mouse() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(LogComponent { log: Rc::clone(&log) }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 100, 100)), false);
+
+        let mut virtual_mouse = VirtualMouse::new(Mouse::new(1), Point::new(0.25, 0.5));
+
+        // Clicking on the left half should miss the component
+        virtual_mouse.press_button(&mut application, MouseButton::primary());
+        virtual_mouse.release_button(&mut application, MouseButton::primary());
+        application.update(Duration::from_millis(10));
+        assert!(log.borrow().is_empty());
+
+        // Diagonal movement shouldn't be faster than straight movement
+        virtual_mouse.set_arrow_pressed(ArrowKey::Right, true);
+        virtual_mouse.set_arrow_pressed(ArrowKey::Up, true);
+        virtual_mouse.set_arrow_pressed(ArrowKey::Up, true);
+        let diagonal = 0.5f32.sqrt();
+        assert_eq!((diagonal, diagonal), virtual_mouse.get_direction());
+
+        virtual_mouse.set_arrow_pressed(ArrowKey::Up, false);
+        virtual_mouse.update(&mut application, Duration::from_secs(1));
+        virtual_mouse.set_arrow_pressed(ArrowKey::Right, false);
+        assert_eq!(Point::new(0.75, 0.5), virtual_mouse.get_position());
+
+        // Not moving shouldn't queue any events
+        virtual_mouse.update(&mut application, Duration::from_secs(1));
+        assert_eq!(Point::new(0.75, 0.5), virtual_mouse.get_position());
+
+        // Key repeat shouldn't cause multiple presses or clicks
+        virtual_mouse.press_button(&mut application, MouseButton::primary());
+        virtual_mouse.press_button(&mut application, MouseButton::primary());
+        virtual_mouse.release_button(&mut application, MouseButton::primary());
+        virtual_mouse.release_button(&mut application, MouseButton::primary());
+        application.update(Duration::from_millis(10));
+        assert_eq!(
+            vec![("enter", Point::new(0.0, 0.5)), ("click", Point::new(0.5, 0.5))],
+            *log.borrow()
+        );
+
+        // Dragging shouldn't click
+        virtual_mouse.press_button(&mut application, MouseButton::primary());
+        virtual_mouse.set_stick(0.0, -1.0);
+        virtual_mouse.update(&mut application, Duration::from_secs(2));
+        assert_eq!(Point::new(0.75, 0.0), virtual_mouse.get_position());
+        virtual_mouse.release_button(&mut application, MouseButton::primary());
+        application.update(Duration::from_millis(10));
+        assert_eq!(2, log.borrow().len());
+
+        virtual_mouse.leave(&mut application);
+        application.update(Duration::from_millis(10));
+        assert_eq!(Some(&("leave", Point::new(0.5, 0.0))), log.borrow().last());
+        assert_eq!((0.0, 0.0), virtual_mouse.get_direction());
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+/// The settings that determine how the *wrapper* and the `Application` synthesize higher-level
+/// events (like `MouseClickEvent`s) from the raw input events of the platform.
+///
+/// Every `Application` has its own `InputSettings`, which can be changed using
+/// `Application.set_input_settings`. Wrappers that can query the corresponding operating system
+/// settings are encouraged to do so when they start the application. Applications that prefer
+/// other values can simply overwrite them afterwards.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct InputSettings {
+    /// The maximum time between 2 clicks of a double click
+    pub double_click_time: Duration,
+    /// The minimum distance the mouse needs to travel between pressing and releasing a button to
+    /// be considered a drag rather than a click. This distance is relative to the size of the
+    /// window, like the coordinates of `Point`s passed to the `Application`.
+    pub drag_start_distance: f32,
+    /// The minimum time a mouse button needs to be held down to be considered a long press
+    pub long_press_duration: Duration,
+    /// The number of pixels that corresponds to 1 line of scrolling, which is needed to convert
+    /// pixel-based scroll deltas (typical for touchpads) to the lines used by `MouseScrollEvent`
+    pub scroll_line_height: f32,
+    /// Whether a long press with the primary button should also fire a `MouseContextClickEvent`.
+    /// Wrappers for touch screens should enable this, since touch screens don't have a secondary
+    /// button to request context menus.
+    pub long_press_context_click: bool,
+    /// The minimum time a mouse needs to rest on a component before it receives a
+    /// `MouseHoverEvent`
+    pub hover_duration: Duration,
+    /// The distance the mouse needs to move outside the drawn region of the root component
+    /// before the root component receives a `MouseLeaveEvent` (relative to the size of the
+    /// window, like `drag_start_distance`). This prevents the mouse from rapidly leaving and
+    /// entering when it jitters around the edge of the drawn region. The default value is 0.0,
+    /// which disables this hysteresis. See also `SimpleFlatMenu.set_hover_hysteresis`.
+    pub hover_hysteresis: f32,
+    /// Whether the *wrapper* should let the arrow keys control a `VirtualMouse`, which makes the
+    /// application usable without a real mouse. This is an accessibility option, so the default
+    /// value is false.
+    pub virtual_mouse: bool,
+    /// The distance a `VirtualMouse` moves per second when an arrow key is held down (or the
+    /// stick of a controller is pushed all the way), relative to the size of the window
+    pub virtual_mouse_speed: f32,
+    /// The minimum distance a finger needs to travel between touching and leaving the screen to
+    /// cause a `SwipeEvent` (relative to the size of the window, like `drag_start_distance`)
+    pub min_swipe_distance: f32,
+    /// The minimum velocity of a finger right before it leaves the screen to cause a
+    /// `SwipeEvent`, in window sizes per second
+    pub min_swipe_velocity: f32,
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        Self {
+            double_click_time: Duration::from_millis(500),
+            drag_start_distance: 0.1,
+            long_press_duration: Duration::from_millis(500),
+            scroll_line_height: 100.0,
+            long_press_context_click: false,
+            hover_duration: Duration::from_millis(500),
+            hover_hysteresis: 0.0,
+            virtual_mouse: false,
+            virtual_mouse_speed: 0.5,
+            min_swipe_distance: 0.1,
+            min_swipe_velocity: 0.5,
+        }
+    }
+}
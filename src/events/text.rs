@@ -8,6 +8,7 @@
 /// Note that this event can only be fired if the user has some kind of
 /// keyboard. If no keyboard is available, only the `request_text_input` method
 /// of the component buddy can be used to ask the user for text input.
+#[derive(Clone, Debug, PartialEq)]
 pub struct CharTypeEvent {
     text: String,
 }
@@ -25,3 +26,66 @@ impl CharTypeEvent {
         &self.text
     }
 }
+
+/// This event is for the `on_composition_start` method of `Component`.
+///
+/// This event indicates that the user started composing text with an input method editor (IME),
+/// which is typically needed to type for instance Chinese or Japanese characters. While the user
+/// is composing, the *wrapper* will fire `CompositionUpdateEvent`s rather than `CharTypeEvent`s.
+/// The composition ends with a `CompositionEndEvent`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompositionStartEvent {}
+
+impl CompositionStartEvent {
+    /// Constructs a new `CompositionStartEvent`. This function should normally only be used by
+    /// the *wrapper*.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// This event is for the `on_composition_update` method of `Component`.
+///
+/// This event indicates that the text that the user is composing with an input method editor
+/// changed. This text is not final: text components should display it (typically underlined) at
+/// the position of their caret, but they shouldn't insert it yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompositionUpdateEvent {
+    text: String,
+}
+
+impl CompositionUpdateEvent {
+    /// Constructs a new `CompositionUpdateEvent` with the given `text`. This function should
+    /// normally only be used by the *wrapper*.
+    pub fn new(text: String) -> Self {
+        Self { text }
+    }
+
+    /// Gets the (entire) text that is currently being composed
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// This event is for the `on_composition_end` method of `Component`.
+///
+/// This event indicates that the user finished composing text with an input method editor. Text
+/// components should insert the text of this event at the position of their caret. If the user
+/// cancelled the composition, the text will be empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompositionEndEvent {
+    text: String,
+}
+
+impl CompositionEndEvent {
+    /// Constructs a new `CompositionEndEvent` with the given `text`. This function should
+    /// normally only be used by the *wrapper*.
+    pub fn new(text: String) -> Self {
+        Self { text }
+    }
+
+    /// Gets the final text of the composition, which is empty if the composition was cancelled
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
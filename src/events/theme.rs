@@ -0,0 +1,29 @@
+use crate::*;
+
+use std::rc::Rc;
+
+/// This event is for the `on_theme_change` method of `Component`. It will be fired when the
+/// `Theme` of the application is replaced (see `Application.set_theme`), right before everything
+/// is rendered again. Components only need to subscribe for this event if they derive state from
+/// the theme outside their `render` method, since they will render again anyway.
+#[derive(Clone, Debug)]
+pub struct ThemeChangeEvent {
+    old_theme: Rc<Theme>,
+    new_theme: Rc<Theme>,
+}
+
+impl ThemeChangeEvent {
+    pub fn new(old_theme: Rc<Theme>, new_theme: Rc<Theme>) -> Self {
+        Self { old_theme, new_theme }
+    }
+
+    pub fn get_old_theme(&self) -> &Theme {
+        &self.old_theme
+    }
+
+    /// Gets the new theme, which is also the result of `ComponentBuddy.get_theme` during this
+    /// event
+    pub fn get_new_theme(&self) -> &Theme {
+        &self.new_theme
+    }
+}
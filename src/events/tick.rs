@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+/// This event is for the `on_tick` method of `Component`. The *wrapper* will fire this event once
+/// per frame (right before the application is rendered), so components can use it to advance
+/// their time-based state (like animations). Applications can also choose to use ticks of a
+/// fixed duration instead, see `Application.set_fixed_tick_duration`.
+///
+/// Note that the time between 2 ticks can vary a lot: it depends on the frame rate and the
+/// application might be suspended for a while (for instance when the browser tab is in the
+/// background). Components should use `get_delta_time` rather than assuming a fixed rate.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TickEvent {
+    delta_time: Duration,
+}
+
+impl TickEvent {
+    /// Constructs a new `TickEvent` with the given `delta_time`. This function should normally
+    /// only be used by the *wrapper*.
+    pub fn new(delta_time: Duration) -> Self {
+        Self { delta_time }
+    }
+
+    /// Gets the time that passed since the previous tick
+    pub fn get_delta_time(&self) -> Duration {
+        self.delta_time
+    }
+
+    /// Gets the time that passed since the previous tick, in seconds
+    pub fn get_delta_seconds(&self) -> f32 {
+        self.delta_time.as_secs_f32()
+    }
+}
+
+/// The maximum number of ticks that a `FixedTickAccumulator` will produce during a single call to
+/// `advance`. When the application was suspended for a while, simulating all missed ticks at once
+/// would take so long that the next frame would be even later (and so on).
+const MAX_TICKS_PER_ADVANCE: u32 = 10;
+
+/// Splits the (variable) time between frames into ticks of a fixed duration. This is useful for
+/// components that run a simulation (like the HUD of a game), since simulations behave much more
+/// predictably with a fixed time step. See `Application.set_fixed_tick_duration`.
+///
+/// The time that remains after the last whole tick is accumulated for the next frame. The
+/// *interpolation alpha* (see `get_alpha`) tells how far the current frame is between the last
+/// tick and the next tick, so components can render smoothly at refresh rates that are higher
+/// than their tick rate:
+/// ```
+/// use knukki::*;
+/// use std::time::Duration;
+///
+/// let mut accumulator = FixedTickAccumulator::new(Duration::from_millis(20));
+/// assert_eq!(0, accumulator.advance(Duration::from_millis(10)));
+/// assert_eq!(0.5, accumulator.get_alpha());
+/// assert_eq!(2, accumulator.advance(Duration::from_millis(35)));
+/// assert_eq!(0.25, accumulator.get_alpha());
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FixedTickAccumulator {
+    tick_duration: Duration,
+    accumulated_time: Duration,
+}
+
+impl FixedTickAccumulator {
+    /// Constructs a new `FixedTickAccumulator` that produces ticks of the given *tick_duration*.
+    ///
+    /// ## Panics
+    /// This function will panic if *tick_duration* is zero.
+    pub fn new(tick_duration: Duration) -> Self {
+        assert!(tick_duration > Duration::from_secs(0), "The tick duration must be positive");
+        Self {
+            tick_duration,
+            accumulated_time: Duration::from_secs(0),
+        }
+    }
+
+    pub fn get_tick_duration(&self) -> Duration {
+        self.tick_duration
+    }
+
+    /// Adds *delta_time* to the accumulated time, and returns the number of whole ticks that
+    /// should be simulated now. This is at most 10: the remaining time is discarded when more
+    /// ticks would be needed (which can happen when the application was suspended).
+    pub fn advance(&mut self, delta_time: Duration) -> u32 {
+        self.accumulated_time += delta_time;
+
+        let num_ticks = self.accumulated_time.as_nanos() / self.tick_duration.as_nanos();
+        let remaining_nanos = self.accumulated_time.as_nanos() % self.tick_duration.as_nanos();
+        self.accumulated_time = Duration::from_nanos(remaining_nanos as u64);
+        num_ticks.min(MAX_TICKS_PER_ADVANCE as u128) as u32
+    }
+
+    /// Gets the interpolation alpha, which is the fraction of a tick that has been accumulated.
+    /// This is always at least 0.0 and smaller than 1.0. Components should render their state as
+    /// `previous_state + alpha * (current_state - previous_state)`, where *current_state* is the
+    /// state after the last tick, and *previous_state* is the state before that tick.
+    pub fn get_alpha(&self) -> f32 {
+        (self.accumulated_time.as_secs_f64() / self.tick_duration.as_secs_f64()) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_fixed_tick_accumulator() {
+        let mut accumulator = FixedTickAccumulator::new(Duration::from_millis(10));
+        assert_eq!(0.0, accumulator.get_alpha());
+
+        assert_eq!(0, accumulator.advance(Duration::from_millis(4)));
+        assert!((accumulator.get_alpha() - 0.4).abs() < 0.001);
+        assert_eq!(1, accumulator.advance(Duration::from_millis(6)));
+        assert_eq!(0.0, accumulator.get_alpha());
+        assert_eq!(3, accumulator.advance(Duration::from_millis(37)));
+        assert!((accumulator.get_alpha() - 0.7).abs() < 0.001);
+
+        // After a long suspension, the missed ticks shouldn't all be simulated
+        assert_eq!(10, accumulator.advance(Duration::from_secs(5)));
+        assert!((accumulator.get_alpha() - 0.7).abs() < 0.001);
+    }
+}
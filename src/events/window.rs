@@ -0,0 +1,45 @@
+/// This event is for the `on_window_focus` method of `Component`. The *wrapper* will fire this
+/// event whenever the window (or browser tab) gains or loses the keyboard focus, for instance when
+/// the user switches to another application. Components can use this to pause animations or
+/// videos while the user is doing something else.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WindowFocusEvent {
+    focused: bool,
+}
+
+impl WindowFocusEvent {
+    /// Constructs a new `WindowFocusEvent`. This function should normally only be used by the
+    /// *wrapper*.
+    pub fn new(focused: bool) -> Self {
+        Self { focused }
+    }
+
+    /// Checks whether the window gained (`true`) or lost (`false`) the focus
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+/// This event is for the `on_window_minimize` method of `Component`. The *wrapper* will fire this
+/// event whenever the window is minimized or restored. On the web, the browser tab counts as
+/// minimized while it is hidden (for instance when the user switches to another tab).
+///
+/// While the window is minimized, nothing of the application is visible, so components can stop
+/// all their animations (and other work that only affects the looks).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WindowMinimizeEvent {
+    minimized: bool,
+}
+
+impl WindowMinimizeEvent {
+    /// Constructs a new `WindowMinimizeEvent`. This function should normally only be used by the
+    /// *wrapper*.
+    pub fn new(minimized: bool) -> Self {
+        Self { minimized }
+    }
+
+    /// Checks whether the window was minimized (`true`) or restored (`false`)
+    pub fn is_minimized(&self) -> bool {
+        self.minimized
+    }
+}
@@ -0,0 +1,462 @@
+//! A fuzz-testing harness for the event routing of the `Application` and `SimpleFlatMenu`. It
+//! generates random component trees (nested menus with overlapping children) and fires random,
+//! but plausible, sequences of input events at them, while checking these invariants:
+//!
+//! - Nothing panics.
+//! - Components only receive the events they are subscribed to.
+//! - Mouse enter and leave events are properly paired: a component never receives 2 enter events
+//!   (or 2 leave events) in a row for the same mouse, it only receives mouse move events while the
+//!   mouse is inside, and all mouses have left all components once they have left the window.
+//! - The points of all mouse events are within the domain of the component that receives them.
+//!   (Except for mouse move and leave events when hover hysteresis is enabled.)
+//! - The drawn regions of all components are within their domains.
+//!
+//! The probe components randomly change their subscriptions while they handle events, which
+//! exercises the lazy subscription bookkeeping of the menus. Everything is deterministic: a
+//! failure reports its seed, so it can be reproduced. The number of seeds can be increased with
+//! the `KNUKKI_FUZZ_SEEDS` environment variable for longer runs.
+use crate::*;
+use crate::random::DeterministicRandom;
+
+use std::cell::RefCell;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+use std::time::Duration;
+
+const DEFAULT_NUM_SEEDS: u64 = 40;
+const NUM_EVENTS: usize = 300;
+const MAX_MENU_DEPTH: u32 = 3;
+
+// Allow some rounding errors in the bounds checks
+const EPSILON: f32 = 0.001;
+
+/// Generates a random domain that covers at least 10% of the width and height of its parent
+fn random_domain(random: &mut DeterministicRandom) -> ComponentDomain {
+    let min_x = 0.8 * random.next_f32();
+    let min_y = 0.8 * random.next_f32();
+    let width = 0.1 + (0.9 - min_x) * random.next_f32();
+    let height = 0.1 + (0.9 - min_y) * random.next_f32();
+    ComponentDomain::with_size(min_x, min_y, width, height)
+}
+
+/// The state of a probe component that the harness can inspect from the outside
+struct ProbeState {
+    name: String,
+    mouses_inside: Vec<Mouse>,
+    violations: Vec<String>,
+
+    // When hover hysteresis is enabled, the mouse can move a little outside the domain before it
+    // leaves the component
+    has_hysteresis: bool,
+}
+
+impl ProbeState {
+    fn check(&mut self, condition: bool, description: impl FnOnce() -> String) {
+        if !condition {
+            let violation = format!("{}: {}", self.name, description());
+            self.violations.push(violation);
+        }
+    }
+
+    fn check_point(&mut self, event_name: &str, point: Point) {
+        let may_be_outside = self.has_hysteresis
+            && (event_name == "MouseMove" || event_name == "MouseLeave");
+        if may_be_outside {
+            return;
+        }
+        let in_bounds = |value: f32| value >= -EPSILON && value <= 1.0 + EPSILON;
+        self.check(in_bounds(point.get_x()) && in_bounds(point.get_y()), || {
+            format!("{} at {:?} is outside the domain", event_name, point)
+        });
+    }
+}
+
+/// A leaf component that checks the invariants of the events it receives
+struct ProbeComponent {
+    random: DeterministicRandom,
+    state: Rc<RefCell<ProbeState>>,
+    tracks_hover: bool,
+    filter_region: Option<RectangularDrawnRegion>,
+
+    // The subscriptions that are toggled randomly
+    click: bool,
+    press: bool,
+    release: bool,
+    scroll: bool,
+    tick: bool,
+}
+
+impl ProbeComponent {
+    fn new(random: &mut DeterministicRandom, state: Rc<RefCell<ProbeState>>) -> Self {
+        let filter_region = if random.next_bool(0.5) {
+            let min_x = 0.5 * random.next_f32();
+            let min_y = 0.5 * random.next_f32();
+            let max_x = min_x + 0.1 + 0.4 * random.next_f32();
+            let max_y = min_y + 0.1 + 0.4 * random.next_f32();
+            Some(RectangularDrawnRegion::new(min_x, min_y, max_x, max_y))
+        } else {
+            None
+        };
+
+        Self {
+            random: DeterministicRandom::new(random.next_u32() as u64),
+            state,
+            tracks_hover: random.next_bool(0.7),
+            filter_region,
+            click: false,
+            press: false,
+            release: false,
+            scroll: false,
+            tick: false,
+        }
+    }
+
+    fn check_subscribed(&self, subscribed: bool, event_name: &str) {
+        self.state.borrow_mut().check(subscribed, || {
+            format!("received a {} event without being subscribed", event_name)
+        });
+    }
+
+    /// Randomly changes the subscriptions and sometimes requests a render
+    fn shuffle(&mut self, buddy: &mut dyn ComponentBuddy) {
+        macro_rules! toggle {
+            ($field: ident, $subscribe: ident, $unsubscribe: ident) => {
+                if self.random.next_bool(0.2) {
+                    self.$field = !self.$field;
+                    if self.$field {
+                        buddy.$subscribe();
+                    } else {
+                        buddy.$unsubscribe();
+                    }
+                }
+            };
+        }
+
+        toggle!(click, subscribe_mouse_click, unsubscribe_mouse_click);
+        toggle!(press, subscribe_mouse_press, unsubscribe_mouse_press);
+        toggle!(release, subscribe_mouse_release, unsubscribe_mouse_release);
+        toggle!(scroll, subscribe_mouse_scroll, unsubscribe_mouse_scroll);
+        toggle!(tick, subscribe_tick, unsubscribe_tick);
+
+        if self.random.next_bool(0.1) {
+            buddy.request_render();
+        }
+    }
+}
+
+impl Component for ProbeComponent {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        if self.tracks_hover {
+            buddy.subscribe_mouse_enter();
+            buddy.subscribe_mouse_move();
+            buddy.subscribe_mouse_leave();
+        }
+        self.shuffle(buddy);
+    }
+
+    fn render(
+        &mut self,
+        _renderer: &Renderer,
+        _buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        match &self.filter_region {
+            Some(region) => Ok(RenderResultStruct {
+                drawn_region: Box::new(*region),
+                filter_mouse_actions: true,
+            }),
+            None => entire_render_result(),
+        }
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.click, "MouseClick");
+        self.state.borrow_mut().check_point("MouseClick", event.get_point());
+        self.shuffle(buddy);
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.press, "MousePress");
+        self.state.borrow_mut().check_point("MousePress", event.get_point());
+        self.shuffle(buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.release, "MouseRelease");
+        self.state.borrow_mut().check_point("MouseRelease", event.get_point());
+        self.shuffle(buddy);
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.scroll, "MouseScroll");
+        self.state.borrow_mut().check_point("MouseScroll", event.get_point());
+        self.shuffle(buddy);
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.tracks_hover, "MouseMove");
+        let mut state = self.state.borrow_mut();
+        let mouse = event.get_mouse();
+        let is_inside = state.mouses_inside.contains(&mouse);
+        state.check(is_inside, || format!("{:?} moved without entering", mouse));
+        state.check_point("MouseMove", event.get_from());
+        state.check_point("MouseMove", event.get_to());
+        drop(state);
+        self.shuffle(buddy);
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.tracks_hover, "MouseEnter");
+        let mut state = self.state.borrow_mut();
+        let mouse = event.get_mouse();
+        let was_inside = state.mouses_inside.contains(&mouse);
+        state.check(!was_inside, || format!("{:?} entered twice", mouse));
+        state.check_point("MouseEnter", event.get_entrance_point());
+        if !was_inside {
+            state.mouses_inside.push(mouse);
+        }
+        drop(state);
+        self.shuffle(buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.tracks_hover, "MouseLeave");
+        let mut state = self.state.borrow_mut();
+        let mouse = event.get_mouse();
+        let was_inside = state.mouses_inside.contains(&mouse);
+        state.check(was_inside, || format!("{:?} left without entering", mouse));
+        state.check_point("MouseLeave", event.get_exit_point());
+        state.mouses_inside.retain(|inside| *inside != mouse);
+        drop(state);
+        self.shuffle(buddy);
+    }
+
+    fn on_tick(&mut self, _event: TickEvent, buddy: &mut dyn ComponentBuddy) {
+        self.check_subscribed(self.tick, "Tick");
+        self.shuffle(buddy);
+    }
+}
+
+fn create_random_menu(
+    random: &mut DeterministicRandom,
+    depth: u32,
+    has_hysteresis: bool,
+    probes: &mut Vec<Rc<RefCell<ProbeState>>>,
+) -> SimpleFlatMenu {
+    let background_color = match random.next_bool(0.5) {
+        true => Some(Color::rgb(0, 0, 0)),
+        false => None,
+    };
+    let mut menu = SimpleFlatMenu::new(background_color);
+    let has_hysteresis = has_hysteresis || random.next_bool(0.3);
+    if has_hysteresis {
+        menu.set_hover_hysteresis(0.05);
+    }
+
+    let num_children = 1 + random.next_below(4);
+    for _ in 0..num_children {
+        let domain = random_domain(random);
+        if depth < MAX_MENU_DEPTH && random.next_bool(0.3) {
+            let child_menu = create_random_menu(random, depth + 1, has_hysteresis, probes);
+            menu.add_component(Box::new(child_menu), domain);
+        } else {
+            let state = Rc::new(RefCell::new(ProbeState {
+                name: format!("probe {}", probes.len()),
+                mouses_inside: Vec::new(),
+                violations: Vec::new(),
+                has_hysteresis,
+            }));
+            probes.push(Rc::clone(&state));
+            menu.add_component(Box::new(ProbeComponent::new(random, state)), domain);
+        }
+    }
+    menu
+}
+
+/// Checks that the drawn region of the component (and those of its children) are within its
+/// domain
+fn check_drawn_regions(description: &ComponentDescription, violations: &mut Vec<String>) {
+    if let Some([left, bottom, right, top]) = description.get_drawn_bounds() {
+        // Components that didn't draw anything (during the last render) have empty bounds
+        let is_empty = left > right || bottom > top;
+        let in_bounds = |value: f32| value >= -EPSILON && value <= 1.0 + EPSILON;
+        if !is_empty
+            && !(in_bounds(left) && in_bounds(bottom) && in_bounds(right) && in_bounds(top))
+        {
+            violations.push(format!(
+                "{} drew outside its domain: {:?}",
+                description.get_type_name(),
+                [left, bottom, right, top]
+            ));
+        }
+    }
+    for child in description.get_children() {
+        check_drawn_regions(child, violations);
+    }
+}
+
+/// The state of a mouse from the perspective of the *wrapper*
+struct FuzzMouse {
+    mouse: Mouse,
+    position: Option<Point>,
+    pressed_buttons: Vec<(MouseButton, Point)>,
+}
+
+fn fire_random_event(
+    random: &mut DeterministicRandom,
+    application: &mut Application,
+    mouses: &mut [FuzzMouse],
+) -> String {
+    let mouse_index = random.next_below(mouses.len() as u32) as usize;
+    let fuzz_mouse = &mut mouses[mouse_index];
+    let mouse = fuzz_mouse.mouse;
+    let buttons = [MouseButton::primary(), MouseButton::secondary()];
+
+    let event = match fuzz_mouse.position {
+        None => {
+            if random.next_bool(0.5) {
+                let point = random.next_point();
+                fuzz_mouse.position = Some(point);
+                InputEvent::MouseEnter(MouseEnterEvent::new(mouse, point))
+            } else {
+                application.fire_tick_event(TickEvent::new(Duration::from_millis(100)));
+                return "Tick".to_string();
+            }
+        }
+        Some(position) => match random.next_below(7) {
+            0 | 1 => {
+                // Small movements are more interesting for the hover hysteresis
+                let target = match random.next_bool(0.5) {
+                    true => random.next_point(),
+                    false => Point::new(
+                        (position.get_x() + 0.1 * random.next_f32() - 0.05).clamp(0.0, 1.0),
+                        (position.get_y() + 0.1 * random.next_f32() - 0.05).clamp(0.0, 1.0),
+                    ),
+                };
+                fuzz_mouse.position = Some(target);
+                InputEvent::MouseMove(MouseMoveEvent::new(mouse, position, target))
+            }
+            2 => {
+                let button = buttons[random.next_below(2) as usize];
+                if fuzz_mouse.pressed_buttons.iter().any(|(pressed, _)| *pressed == button) {
+                    return "Nothing".to_string();
+                }
+                fuzz_mouse.pressed_buttons.push((button, position));
+                InputEvent::MousePress(MousePressEvent::new(mouse, position, button))
+            }
+            3 => match fuzz_mouse.pressed_buttons.pop() {
+                Some((button, press_point)) => {
+                    application.fire_event(InputEvent::MouseRelease(MouseReleaseEvent::new(
+                        mouse, position, button
+                    )));
+                    if position.distance_to(press_point) < 0.1 {
+                        InputEvent::MouseClick(MouseClickEvent::new(mouse, position, button))
+                    } else {
+                        return "MouseRelease".to_string();
+                    }
+                }
+                None => return "Nothing".to_string(),
+            },
+            4 => InputEvent::MouseScroll(MouseScrollEvent::new(mouse, position, 0.0, 1.0)),
+            5 => {
+                for (button, _) in std::mem::take(&mut fuzz_mouse.pressed_buttons) {
+                    application.fire_event(InputEvent::MouseRelease(MouseReleaseEvent::new(
+                        mouse, position, button
+                    )));
+                }
+                fuzz_mouse.position = None;
+                InputEvent::MouseLeave(MouseLeaveEvent::new(mouse, position))
+            }
+            _ => {
+                application.fire_tick_event(TickEvent::new(Duration::from_millis(100)));
+                return "Tick".to_string();
+            }
+        },
+    };
+
+    let description = format!("{:?}", event);
+    application.fire_event(event);
+    description
+}
+
+fn run_seed(seed: u64) {
+    let mut random = DeterministicRandom::new(seed);
+    let mut probes = Vec::new();
+    let has_root_hysteresis = random.next_bool(0.3);
+    let menu = create_random_menu(&mut random, 0, has_root_hysteresis, &mut probes);
+    let mut application = Application::new(Box::new(menu));
+    if has_root_hysteresis {
+        application.set_input_settings(InputSettings {
+            hover_hysteresis: 0.05,
+            ..InputSettings::default()
+        });
+    }
+
+    let renderer = test_renderer(RenderRegion::with_size(0, 0, 200, 100));
+    let mut mouses = vec![
+        FuzzMouse { mouse: Mouse::new(0), position: None, pressed_buttons: Vec::new() },
+        FuzzMouse { mouse: Mouse::new(1), position: None, pressed_buttons: Vec::new() },
+    ];
+
+    let mut history = Vec::new();
+    let check = |application: &Application, history: &[String]| {
+        let mut violations = Vec::new();
+        for probe in &probes {
+            violations.append(&mut probe.borrow_mut().violations);
+        }
+        check_drawn_regions(&application.dump_tree(), &mut violations);
+        assert!(
+            violations.is_empty(),
+            "Seed {} violated invariants {:?} after the events {:?}",
+            seed,
+            violations,
+            history
+        );
+    };
+
+    application.render(&renderer, true);
+    for _ in 0..NUM_EVENTS {
+        history.push(fire_random_event(&mut random, &mut application, &mut mouses));
+        if random.next_bool(0.3) {
+            application.render(&renderer, random.next_bool(0.1));
+        }
+        check(&application, &history);
+    }
+
+    // Once all mouses left the window, they should have left all components
+    for fuzz_mouse in &mut mouses {
+        if let Some(position) = fuzz_mouse.position.take() {
+            let event = MouseLeaveEvent::new(fuzz_mouse.mouse, position);
+            history.push(format!("{:?}", event));
+            application.fire_mouse_leave_event(event);
+        }
+    }
+    check(&application, &history);
+    for probe in &probes {
+        let probe = probe.borrow();
+        assert!(
+            probe.mouses_inside.is_empty(),
+            "Seed {}: {} still thinks {:?} are inside after the events {:?}",
+            seed,
+            probe.name,
+            probe.mouses_inside,
+            history
+        );
+    }
+}
+
+#[test]
+fn fuzz_event_routing() {
+    let num_seeds = std::env::var("KNUKKI_FUZZ_SEEDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_NUM_SEEDS);
+
+    for seed in 0..num_seeds {
+        // Report the seed of unexpected panics, so that they can be reproduced
+        if let Err(panic) = catch_unwind(AssertUnwindSafe(|| run_seed(seed))) {
+            eprintln!("Fuzzing seed {} panicked", seed);
+            resume_unwind(panic);
+        }
+    }
+}
@@ -1,19 +1,30 @@
 #![feature(drain_filter)]
 
+mod animation;
 mod application;
+#[cfg(feature = "bench")]
+mod bench;
 mod component;
 mod components;
 mod events;
 mod font;
+#[cfg(test)]
+mod fuzz;
 mod point;
+#[cfg(any(test, feature = "bench"))]
+mod random;
 
 #[cfg(feature = "wrapper")]
 mod wrapper;
 mod render;
 mod renderer;
 mod texture;
+mod timing;
 
+pub use animation::*;
 pub use application::*;
+#[cfg(feature = "bench")]
+pub use bench::*;
 pub use component::*;
 pub use components::*;
 pub use events::*;
@@ -25,3 +36,4 @@ pub use wrapper::*;
 pub use render::*;
 pub use renderer::*;
 pub use texture::*;
+pub use timing::*;
@@ -0,0 +1,80 @@
+//! A tiny deterministic random number generator that is shared by the fuzz-testing harness and
+//! the benchmark scenarios. It is not exposed by this crate.
+use crate::Point;
+
+/// A simple linear congruential generator, so that the fuzz-testing harness and the benchmark
+/// scenarios don't need a dependency on a random number crate (and are reproducible).
+#[derive(Clone)]
+pub(crate) struct DeterministicRandom {
+    state: u64,
+}
+
+// The benchmark scenarios don't need all methods
+#[cfg_attr(not(test), allow(dead_code))]
+impl DeterministicRandom {
+    /// Constructs a new generator from the given *seed*. The seed is scrambled first, so that small
+    /// (and consecutive) seeds still yield very different sequences.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed.wrapping_mul(0x9E3779B97F4A7C15).wrapping_add(1) }
+    }
+
+    pub(crate) fn next_u32(&mut self) -> u32 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 33) as u32
+    }
+
+    /// Returns a number between *min* (inclusive) and *bound* (exclusive)
+    pub(crate) fn next_range(&mut self, min: u32, bound: u32) -> u32 {
+        min + self.next_u32() % (bound - min)
+    }
+
+    /// Returns a number between 0 (inclusive) and *bound* (exclusive)
+    pub(crate) fn next_below(&mut self, bound: u32) -> u32 {
+        self.next_range(0, bound)
+    }
+
+    /// Returns a number between 0.0 (inclusive) and 1.0 (exclusive)
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        // next_u32 yields 31 random bits, but an f32 can only represent 24 bits exactly (so
+        // using more bits could round the result up to 1.0)
+        (self.next_u32() >> 7) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Returns `true` with the given *probability*
+    pub(crate) fn next_bool(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Returns a point whose coordinates are between 0.0 (inclusive) and 1.0 (exclusive)
+    pub(crate) fn next_point(&mut self) -> Point {
+        Point::new(self.next_f32(), self.next_f32())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_random() {
+        let mut first = DeterministicRandom::new(12);
+        let mut second = DeterministicRandom::new(12);
+        for _ in 0..100 {
+            assert_eq!(first.next_u32(), second.next_u32());
+        }
+
+        for _ in 0..100 {
+            let value = first.next_f32();
+            assert!((0.0..1.0).contains(&value));
+            assert!((5..8).contains(&first.next_range(5, 8)));
+            assert!(first.next_below(3) < 3);
+        }
+
+        // Consecutive seeds should yield different sequences
+        let mut other = DeterministicRandom::new(13);
+        assert_ne!(DeterministicRandom::new(12).next_u32(), other.next_u32());
+    }
+}
@@ -8,6 +8,27 @@ pub struct TextStyle {
     pub background_fill_mode: TextBackgroundFillMode
 }
 
+impl TextStyle {
+    /// Creates a `TextStyle` for text on the background of the given `Palette`, using the default
+    /// font. The background will not be filled.
+    pub fn from_palette(palette: &Palette) -> Self {
+        Self {
+            font_id: None,
+            text_color: palette.get_readable_color(ColorRole::OnBackground, ColorRole::Background),
+            background_color: palette.get_color(ColorRole::Background),
+            background_fill_mode: TextBackgroundFillMode::DoNot
+        }
+    }
+
+    /// Creates the `TextStyle` for the palette and font of the given `Theme`
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            font_id: theme.font_id.clone(),
+            ..Self::from_palette(&theme.palette)
+        }
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum TextBackgroundFillMode {
     DoNot,
@@ -16,11 +16,19 @@ impl Renderer {
         // There is nothing to be done without a Golem context
     }
 
+    /// Makes sure that the drawing operations of the rendering context (probably OpenGL) use the
+    /// current value of `self.get_opacity()`.
+    #[cfg(not(feature = "golem_rendering"))]
+    pub(super) fn apply_opacity(&self) {
+        // There is nothing to be done without a Golem context
+    }
+
     /// Sets the color of all pixels within the current viewport and scissor to the given `Color`.
     #[allow(unused_variables)]
     #[cfg(not(feature = "golem_rendering"))]
     pub fn clear(&self, color: Color) {
-        // There is nothing to be done without a Golem context
+        // There is nothing to be drawn without a Golem context, but the repaint is still logged
+        self.log_repaint(0.0, 0.0, 1.0, 1.0);
     }
 
     /// Uses the given *FragmentOnlyShader* to fill the rectangular region defined by *min_x*,
@@ -33,7 +41,121 @@ impl Renderer {
         &self, min_x: f32, min_y: f32, max_x: f32, max_y: f32,
         shader: &FragmentOnlyShader, parameters: FragmentOnlyDrawParameters
     ) {
+        // There is nothing to be drawn without a Golem context, but the repaint is still logged
+        let (min_x, min_y, max_x, max_y) = self.snap_to_pixels(min_x, min_y, max_x, max_y);
+        self.log_repaint(min_x, min_y, max_x, max_y);
+    }
+
+    /// Starts logging the regions that are drawn on by `clear`, `apply_fragment_shader`, and the
+    /// text renderer. This is used by the invalidation debug mode of `Application`.
+    pub(crate) fn start_repaint_log(&self) {
+        *self.repaint_log.borrow_mut() = Some(Vec::new());
+    }
+
+    /// Stops logging repaints (see `start_repaint_log`) and returns the logged regions
+    pub(crate) fn finish_repaint_log(&self) -> Vec<RenderRegion> {
+        self.repaint_log.borrow_mut().take().unwrap_or_default()
+    }
+
+    /// Logs that the region (*min_x*, *min_y*, *max_x*, *max_y*) of the current viewport was
+    /// drawn on (limited to the current scissor), if the repaint log is enabled
+    pub(crate) fn log_repaint(&self, min_x: f32, min_y: f32, max_x: f32, max_y: f32) {
+        if let Some(repaint_log) = self.repaint_log.borrow_mut().as_mut() {
+            let viewport = self.get_viewport();
+            let maybe_region = viewport.conservative_child_region(min_x, min_y, max_x, max_y);
+            if let Some(region) = maybe_region.and_then(
+                |region| region.intersection(self.get_scissor())
+            ) {
+                // Log the region in the coordinate system of the render target
+                let (origin_x, origin_y) = self.origin.get();
+                repaint_log.push(RenderRegion::with_size(
+                    region.get_min_x().saturating_sub(origin_x),
+                    region.get_min_y().saturating_sub(origin_y),
+                    region.get_width(),
+                    region.get_height(),
+                ));
+            }
+        }
+    }
+
+    /// Creates the `FragmentOnlyShader` that `clear` uses when the opacity is smaller than 1.0,
+    /// since clearing would ignore the opacity. See `Renderer.prewarm`.
+    pub(crate) fn create_clear_shader() -> FragmentOnlyShader {
+        FragmentOnlyShader::new(FragmentOnlyShaderDescription {
+            source_code: "
+                void main() {
+                    gl_FragColor = color1;
+                }
+            ".to_string(),
+            num_float_matrices: 0,
+            num_colors: 1,
+            num_float_vectors: 0,
+            num_int_vectors: 0,
+            num_floats: 0,
+            num_ints: 0,
+        })
+    }
+
+    /// Captures the pixels of the current frame (everything within the root viewport) and returns
+    /// them as `Texture`. This can be used to implement screenshot buttons, or to check the output
+    /// of real GPUs in tests.
+    ///
+    /// This always returns `None` without the `golem_rendering` feature, since nothing is drawn
+    /// in that case.
+    #[cfg(not(feature = "golem_rendering"))]
+    pub fn capture_frame(&self) -> Option<Texture> {
+        // There is nothing to be captured without a Golem context
+        None
+    }
+
+    /// Destroys the cached GPU resources that haven't been used since the previous call to this
+    /// method (or since this `Renderer` was created), and returns the number of destroyed
+    /// resources. Long-running applications should call this once in a while (the `Application`
+    /// doesn't do this automatically) to prevent them from accumulating resources that are no
+    /// longer needed, for instance the shaders of components that have been removed.
+    #[cfg(not(feature = "golem_rendering"))]
+    pub fn collect_garbage(&self) -> usize {
         // There is nothing to be done without a Golem context
+        0
+    }
+
+    /// Gets the number of GPU resources of the given kind that this `Renderer` created and
+    /// destroyed so far. This only includes the resources that are owned by this `Renderer`
+    /// (like cached shaders), so it does *not* include the textures returned by `load_texture`.
+    ///
+    /// This is meant for leak diagnostics: if the number of alive resources keeps growing, the
+    /// application should probably call `collect_garbage` more often.
+    pub fn get_gpu_resource_counts(&self, kind: GpuResourceKind) -> GpuResourceCounts {
+        self.resource_tracker.get_counts(kind)
+    }
+
+    /// Gets the number of times the GPU context of this `Renderer` was replaced because it was
+    /// lost (see `recover_from_context_loss`, which requires the `golem_rendering` feature). All
+    /// GPU resources of the previous context are gone after such a replacement, so components
+    /// that create their own GPU resources should remember the generation in which they created
+    /// them, and create them again when the generation changes.
+    pub fn get_context_generation(&self) -> u64 {
+        self.context_generation
+    }
+
+    /// Simulates the replacement of a lost GPU context, for unit tests without golem contexts
+    #[cfg(test)]
+    pub(crate) fn simulate_context_loss(&mut self) {
+        self.context_generation += 1;
+    }
+
+    /// Gets the interpolation alpha of the current frame, which is between 0.0 and 1.0. When the
+    /// `Application` uses fixed ticks (see `Application.set_fixed_tick_duration`), components
+    /// should render their state as `previous_state + alpha * (current_state - previous_state)`
+    /// to move smoothly, even when the frame rate is higher than the tick rate. This is always
+    /// 1.0 when fixed ticks are disabled, since the last tick happened right before rendering.
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        self.interpolation_alpha.get()
+    }
+
+    /// This is called by the `Application` right before it renders
+    pub(crate) fn set_interpolation_alpha(&self, alpha: f32) {
+        self.interpolation_alpha.set(alpha);
     }
 
     /// Gets the current viewport region of this `Renderer`. The drawing operations of components
@@ -56,6 +178,98 @@ impl Renderer {
         *scissor_stack.last().expect("Scissor stack is never empty")
     }
 
+    /// Gets the current opacity of this `Renderer`, which is the product of all opacities that
+    /// are currently pushed with `push_opacity`. An opacity of 1.0 means that everything will be
+    /// drawn normally.
+    pub fn get_opacity(&self) -> f32 {
+        let opacity_stack = self.opacity_stack.borrow();
+        *opacity_stack.last().expect("Opacity stack is never empty")
+    }
+
+    /// Multiplies the opacity of this `Renderer` by *opacity* (which will be clamped between 0.0
+    /// and 1.0), calls the `render_function`, and thereafter restores the opacity. Unlike the
+    /// viewport and scissor, this will always call the `render_function`.
+    ///
+    /// ## Effects on drawing
+    /// While the opacity is smaller than 1.0, everything that is drawn (including `clear`) will be
+    /// blended with the pixels that were drawn before. Note that every drawing operation is
+    /// blended separately: when something is drawn on top of something else that was drawn with
+    /// the same opacity, the thing behind it will shine through.
+    ///
+    /// Also note that components that draw on top of what they drew during their previous render
+    /// will blend with their old drawing. To avoid this, they should first reset their background
+    /// *outside* the `render_function`.
+    pub fn push_opacity<R>(&self, opacity: f32, render_function: impl FnOnce() -> R) -> R {
+        let combined_opacity = self.get_opacity() * opacity.clamp(0.0, 1.0);
+        self.opacity_stack.borrow_mut().push(combined_opacity);
+        self.apply_opacity();
+
+        let result = render_function();
+
+        self.opacity_stack.borrow_mut().pop();
+        self.apply_opacity();
+
+        result
+    }
+
+    /// Checks whether pixel snapping is currently enabled. See `set_pixel_snapping`.
+    pub fn is_pixel_snapping_enabled(&self) -> bool {
+        self.pixel_snapping.get()
+    }
+
+    /// Enables or disables pixel snapping (it is disabled by default). While pixel snapping is
+    /// enabled, the edges of the quads drawn by `apply_fragment_shader` and the bounds of the text
+    /// drawn by the text renderer will be rounded to whole pixels of the current viewport. This
+    /// prevents blurry edges (for instance of 1-pixel borders), but causes content to move in
+    /// steps of whole pixels, so it should be disabled for smooth animations.
+    ///
+    /// Since the viewports of the `Renderer` are always axis-aligned, snapping to whole pixels of
+    /// the viewport is snapping to whole device pixels. Use `push_pixel_snapping` to change this
+    /// for only a part of the drawing operations.
+    pub fn set_pixel_snapping(&self, enabled: bool) {
+        self.pixel_snapping.set(enabled);
+    }
+
+    /// Enables or disables pixel snapping (see `set_pixel_snapping`), calls the
+    /// `render_function`, and thereafter restores the previous pixel snapping setting.
+    pub fn push_pixel_snapping<R>(&self, enabled: bool, render_function: impl FnOnce() -> R) -> R {
+        let was_enabled = self.pixel_snapping.replace(enabled);
+        let result = render_function();
+        self.pixel_snapping.set(was_enabled);
+        result
+    }
+
+    /// Rounds the given bounds (relative to the current viewport) to whole pixels if pixel
+    /// snapping is enabled, and returns them unchanged otherwise. Components that draw directly
+    /// with the golem context can use this to respect the pixel snapping setting.
+    pub fn snap_to_pixels(
+        &self, min_x: f32, min_y: f32, max_x: f32, max_y: f32
+    ) -> (f32, f32, f32, f32) {
+        if self.is_pixel_snapping_enabled() {
+            let viewport = self.get_viewport();
+            (
+                viewport.snap_x(min_x),
+                viewport.snap_y(min_y),
+                viewport.snap_x(max_x),
+                viewport.snap_y(max_y),
+            )
+        } else {
+            (min_x, min_y, max_x, max_y)
+        }
+    }
+
+    /// Releases cached resources because the device is running low on memory, and returns the
+    /// number of released resources. This will destroy the cached shaders that haven't been used
+    /// recently (see `collect_garbage`), and release the cached text models and glyph atlases of
+    /// the `TextRenderer` (see its `release_memory` method). Everything will be created again when
+    /// it is needed, so this doesn't affect what is drawn.
+    ///
+    /// The `Application` will call this automatically during the first `render` after a
+    /// `LowMemoryEvent`.
+    pub fn release_memory(&self, pressure: MemoryPressure) -> usize {
+        self.collect_garbage() + self.text_renderer.release_memory(pressure)
+    }
+
     /// Gets the `TextRenderer` of this `Renderer`. As the name suggests, the text renderer can be
     /// used to draw text.
     pub fn get_text_renderer(&self) -> &TextRenderer {
@@ -77,8 +291,17 @@ impl Renderer {
     /// the `render_function`.
     ///
     /// ## Details
-    /// The `new_viewport` will be equal to `old_viewport.child_region(min_x, min_y, max_x, max_y)`
-    /// and the `new_scissor` will be equal to `old_scissor.intersection(new_viewport)`.
+    /// The `new_viewport` will normally be equal to
+    /// `old_viewport.child_region(min_x, min_y, max_x, max_y)` and the `new_scissor` will be equal
+    /// to `old_scissor.intersection(new_viewport)`.
+    ///
+    /// The coordinates can also be smaller than 0.0 or larger than 1.0 (for instance for the
+    /// content of a `ScrollPane`), in which case the new viewport will extend outside the old
+    /// viewport (but the new scissor won't). Since a `RenderRegion` can't have negative
+    /// coordinates, this `Renderer` will translate the regions of its viewport stack and scissor
+    /// stack when the new viewport would extend below or left of the render target. This doesn't
+    /// affect `clear`, `apply_fragment_shader`, and the text renderer, but components that draw
+    /// directly with the golem context should use `get_target_viewport` in that case.
     pub fn push_viewport<R>(
         &self,
         min_x: f32,
@@ -88,48 +311,107 @@ impl Renderer {
         render_function: impl FnOnce() -> R,
     ) -> Option<R> {
         let parent_viewport = self.get_viewport();
-        let maybe_child_viewport = parent_viewport.child_region(min_x, min_y, max_x, max_y);
-
-        if let Some(child_viewport) = maybe_child_viewport {
-            let parent_scissor = self.get_scissor();
-            let maybe_child_scissor = parent_scissor.intersection(child_viewport);
-
-            // Don't bother calling the render function if there would be an empty scissor
-            if let Some(child_scissor) = maybe_child_scissor {
-                // Push the viewport
-                let mut viewport_stack = self.viewport_stack.borrow_mut();
-                viewport_stack.push(child_viewport);
-                drop(viewport_stack);
+        let (min_x, min_y, bound_x, bound_y) =
+            parent_viewport.unclamped_child_bounds(min_x, min_y, max_x, max_y)?;
+
+        // Translate the regions when the child viewport would have negative coordinates
+        let shift_x = (-min_x).max(0);
+        let shift_y = (-min_y).max(0);
+        let child_viewport = RenderRegion::between(
+            (min_x + shift_x) as u32,
+            (min_y + shift_y) as u32,
+            (bound_x + shift_x) as u32,
+            (bound_y + shift_y) as u32,
+        );
+        let parent_scissor = self.get_scissor();
+        let shifted_scissor = RenderRegion::with_size(
+            parent_scissor.get_min_x() + shift_x as u32,
+            parent_scissor.get_min_y() + shift_y as u32,
+            parent_scissor.get_width(),
+            parent_scissor.get_height(),
+        );
+
+        // Don't bother calling the render function if there would be an empty scissor
+        let child_scissor = shifted_scissor.intersection(child_viewport)?;
+
+        // Push the viewport and scissor
+        self.viewport_stack.borrow_mut().push(child_viewport);
+        self.scissor_stack.borrow_mut().push(child_scissor);
+        let parent_origin = self.origin.get();
+        self.origin.set((
+            parent_origin.0 + shift_x as u32,
+            parent_origin.1 + shift_y as u32,
+        ));
+
+        // Make sure the viewport and scissor are actually used
+        self.apply_viewport_and_scissor();
 
-                // Push the scissor
-                let mut scissor_stack = self.scissor_stack.borrow_mut();
-                scissor_stack.push(child_scissor);
-                drop(scissor_stack);
+        // Call the render function
+        let result = render_function();
 
-                // Make sure the viewport and scissor are actually used
-                self.apply_viewport_and_scissor();
+        // Pop the viewport and scissor
+        self.viewport_stack.borrow_mut().pop();
+        self.scissor_stack.borrow_mut().pop();
+        self.origin.set(parent_origin);
 
-                // Call the render function
-                let result = render_function();
+        self.apply_viewport_and_scissor();
 
-                // Pop the viewport and scissor
-                let mut viewport_stack = self.viewport_stack.borrow_mut();
-                viewport_stack.pop();
-                drop(viewport_stack);
-                let mut scissor_stack = self.scissor_stack.borrow_mut();
-                scissor_stack.pop();
-                drop(scissor_stack);
+        // Return the result
+        Some(result)
+    }
 
-                self.apply_viewport_and_scissor();
+    /// Gets the part of the current viewport that is inside the render target, in the coordinate
+    /// system of the render target. The rendering context (probably OpenGL) should use this as
+    /// its viewport. This is simply the current viewport, unless the current viewport extends
+    /// below or left of the render target (see `push_viewport`).
+    ///
+    /// Since the returned region can be smaller than the current viewport, this also returns
+    /// `[offset_x, offset_y, scale_x, scale_y]`: the coordinate (x, y) relative to the current
+    /// viewport is the coordinate `(offset_x + scale_x * x, offset_y + scale_y * y)` relative to
+    /// the returned region.
+    pub fn get_target_viewport(&self) -> (RenderRegion, [f32; 4]) {
+        let viewport = self.get_viewport();
+        let (origin_x, origin_y) = self.origin.get();
+        let min_x = viewport.get_min_x() as i64 - origin_x as i64;
+        let min_y = viewport.get_min_y() as i64 - origin_y as i64;
+        let bound_x = viewport.get_bound_x() as i64 - origin_x as i64;
+        let bound_y = viewport.get_bound_y() as i64 - origin_y as i64;
+
+        // The scissor is never empty, so the viewport always overlaps the render target
+        debug_assert!(bound_x > 0 && bound_y > 0);
+        let (target_x, target_y) = (min_x.max(0), min_y.max(0));
+        let target = RenderRegion::between(
+            target_x as u32,
+            target_y as u32,
+            bound_x.max(target_x + 1) as u32,
+            bound_y.max(target_y + 1) as u32,
+        );
+
+        let target_width = target.get_width() as f32;
+        let target_height = target.get_height() as f32;
+        let transform = [
+            (min_x - target_x) as f32 / target_width,
+            (min_y - target_y) as f32 / target_height,
+            viewport.get_width() as f32 / target_width,
+            viewport.get_height() as f32 / target_height,
+        ];
+        (target, transform)
+    }
 
-                // Return the result
-                Some(result)
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+    /// Gets the current scissor in the coordinate system of the render target (see
+    /// `get_target_viewport`)
+    pub fn get_target_scissor(&self) -> RenderRegion {
+        let scissor = self.get_scissor();
+        let (origin_x, origin_y) = self.origin.get();
+
+        // The scissor is always inside the root viewport, so it's never below the origin
+        debug_assert!(scissor.get_min_x() >= origin_x && scissor.get_min_y() >= origin_y);
+        RenderRegion::with_size(
+            scissor.get_min_x().saturating_sub(origin_x),
+            scissor.get_min_y().saturating_sub(origin_y),
+            scissor.get_width(),
+            scissor.get_height(),
+        )
     }
 
     /// Calls the `render_function`, but ensures that the region `(min_x, min_y, max_x, max_y)`
@@ -171,9 +453,36 @@ impl Renderer {
         max_y: f32,
         render_function: impl FnOnce() -> R,
     ) -> Option<R> {
-        let old_scissor = self.get_scissor();
+        let maybe_new_scissor = self.get_viewport().child_region(min_x, min_y, max_x, max_y);
+        self.push_scissor_region(maybe_new_scissor, render_function)
+    }
+
+    /// Like `push_scissor`, but the scissor will contain every pixel that is (partially) covered
+    /// by the given region, rather than rounding each edge to the nearest pixel. See
+    /// `RenderRegion.conservative_child_region`.
+    ///
+    /// This should be preferred when the scissor is used to redraw (the background of) something
+    /// that has already been drawn, like a `DrawnRegion`, since `push_scissor` could leave the
+    /// pixels at the edges untouched.
+    pub fn push_conservative_scissor<R>(
+        &self,
+        min_x: f32,
+        min_y: f32,
+        max_x: f32,
+        max_y: f32,
+        render_function: impl FnOnce() -> R,
+    ) -> Option<R> {
         let viewport = self.get_viewport();
-        let maybe_new_scissor = viewport.child_region(min_x, min_y, max_x, max_y);
+        let maybe_new_scissor = viewport.conservative_child_region(min_x, min_y, max_x, max_y);
+        self.push_scissor_region(maybe_new_scissor, render_function)
+    }
+
+    pub(crate) fn push_scissor_region<R>(
+        &self,
+        maybe_new_scissor: Option<RenderRegion>,
+        render_function: impl FnOnce() -> R,
+    ) -> Option<R> {
+        let old_scissor = self.get_scissor();
         if let Some(new_scissor) = maybe_new_scissor {
             if let Some(combined_scissor) = old_scissor.intersection(new_scissor) {
                 let mut scissor_stack = self.scissor_stack.borrow_mut();
@@ -211,6 +520,7 @@ impl Renderer {
         scissor_stack.clear();
         viewport_stack.push(new_viewport);
         scissor_stack.push(new_viewport);
+        self.origin.set((0, 0));
 
         drop(viewport_stack);
         drop(scissor_stack);
@@ -288,6 +598,43 @@ mod tests {
         assert_eq!(outer_region, renderer.get_scissor());
     }
 
+    #[test]
+    fn test_push_viewport_outside_target() {
+        let root = RenderRegion::with_size(0, 0, 100, 100);
+        let renderer = test_renderer(root);
+
+        // This viewport extends 50 pixels below and left of the render target
+        renderer.push_viewport(-0.5, -0.5, 1.0, 1.0, || {
+            assert_eq!(RenderRegion::with_size(0, 0, 150, 150), renderer.get_viewport());
+            assert_eq!(RenderRegion::with_size(50, 50, 100, 100), renderer.get_scissor());
+            assert_eq!(root, renderer.get_target_scissor());
+            assert_eq!(
+                (root, [-0.5, -0.5, 1.5, 1.5]),
+                renderer.get_target_viewport()
+            );
+
+            // Nested viewports should use the same translation
+            renderer.push_viewport(0.6, 0.0, 1.0, 0.4, || {
+                assert_eq!(RenderRegion::with_size(90, 0, 60, 60), renderer.get_viewport());
+                assert_eq!(RenderRegion::with_size(90, 50, 60, 10), renderer.get_scissor());
+                assert_eq!(
+                    RenderRegion::with_size(40, 0, 60, 10),
+                    renderer.get_target_scissor()
+                );
+                assert_eq!(
+                    (RenderRegion::with_size(40, 0, 60, 10), [0.0, -5.0, 1.0, 6.0]),
+                    renderer.get_target_viewport()
+                );
+            });
+
+            // Viewports that are entirely outside the render target should be skipped
+            assert!(renderer.push_viewport(0.0, 0.0, 0.2, 0.2, || unreachable!()).is_none());
+        }).unwrap();
+
+        assert_eq!(root, renderer.get_viewport());
+        assert_eq!((root, [0.0, 0.0, 1.0, 1.0]), renderer.get_target_viewport());
+    }
+
     #[test]
     fn test_push_scissor() {
         let viewport = RenderRegion::with_size(50, 100, 400, 300);
@@ -336,6 +683,70 @@ mod tests {
         assert_eq!(viewport, renderer.get_scissor());
     }
 
+    #[test]
+    fn test_push_opacity() {
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+        assert_eq!(1.0, renderer.get_opacity());
+
+        let result = renderer.push_opacity(0.5, || {
+            assert_eq!(0.5, renderer.get_opacity());
+
+            // Nested opacities should be multiplied, and clamped between 0.0 and 1.0
+            renderer.push_opacity(0.5, || assert_eq!(0.25, renderer.get_opacity()));
+            renderer.push_opacity(2.0, || assert_eq!(0.5, renderer.get_opacity()));
+            renderer.push_opacity(-1.0, || assert_eq!(0.0, renderer.get_opacity()));
+            assert_eq!(0.5, renderer.get_opacity());
+            12
+        });
+        assert_eq!(12, result);
+        assert_eq!(1.0, renderer.get_opacity());
+    }
+
+    #[test]
+    fn test_gpu_resource_counts() {
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+
+        // The test renderer doesn't have any GPU resources
+        assert_eq!(0, renderer.collect_garbage());
+        for kind in &[GpuResourceKind::Shader, GpuResourceKind::Buffer] {
+            assert_eq!(GpuResourceCounts::default(), renderer.get_gpu_resource_counts(*kind));
+        }
+    }
+
+    #[test]
+    fn test_push_conservative_scissor() {
+        let viewport = RenderRegion::with_size(0, 0, 10, 10);
+        let renderer = test_renderer(viewport);
+
+        // The normal scissor would round to the nearest pixel
+        renderer
+            .push_scissor(0.14, 0.16, 0.55, 0.64, || {
+                assert_eq!(RenderRegion::between(1, 2, 6, 6), renderer.get_scissor());
+            })
+            .unwrap();
+
+        // But the conservative scissor should contain all pixels that are partially covered
+        renderer
+            .push_conservative_scissor(0.14, 0.16, 0.55, 0.64, || {
+                assert_eq!(RenderRegion::between(1, 1, 6, 7), renderer.get_scissor());
+
+                // It should still be intersected with the current scissor
+                renderer
+                    .push_conservative_scissor(0.5, 0.5, 1.0, 1.0, || {
+                        assert_eq!(RenderRegion::between(5, 5, 6, 7), renderer.get_scissor());
+                    })
+                    .unwrap();
+            })
+            .unwrap();
+
+        assert!(renderer
+            .push_conservative_scissor(0.3, 0.3, 0.3, 0.4, || {
+                unreachable!();
+            })
+            .is_none());
+        assert_eq!(viewport, renderer.get_scissor());
+    }
+
     #[test]
     fn test_combine_viewport_and_scissor() {
         let outer_viewport = RenderRegion::with_size(0, 0, 1000, 1000);
@@ -378,5 +789,23 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_pixel_snapping() {
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 10));
+        assert!(!renderer.is_pixel_snapping_enabled());
+        assert_eq!((0.123, 0.22, 0.5, 0.77), renderer.snap_to_pixels(0.123, 0.22, 0.5, 0.77));
+
+        renderer.set_pixel_snapping(true);
+        assert_eq!((0.12, 0.2, 0.5, 0.8), renderer.snap_to_pixels(0.123, 0.22, 0.5, 0.77));
+
+        // The snapping should use the pixels of the current viewport
+        renderer.push_viewport(0.0, 0.0, 0.5, 1.0, || {
+            assert_eq!((0.12, 0.2, 0.5, 0.8), renderer.snap_to_pixels(0.123, 0.22, 0.5, 0.77));
+        });
+
+        assert!(!renderer.push_pixel_snapping(false, || renderer.is_pixel_snapping_enabled()));
+        assert!(renderer.is_pixel_snapping_enabled());
+    }
+
     // TODO Write an example that uses the push_scissor method. Note: probably requires WrapperComponent first
 }
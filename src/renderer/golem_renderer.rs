@@ -1,23 +1,128 @@
 use crate::*;
 use golem::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::rc::Rc;
 
 impl Renderer {
     /// Constructs a new `Renderer` that will draw onto the given golem `Context` within the given
     /// *initial_viewport*. Normally, only the *wrapper* should use this function.
     pub fn new(context: Context, initial_viewport: RenderRegion) -> Self {
+        Self::with_glyph_cache(context, initial_viewport, GlyphCache::new())
+    }
+
+    /// Constructs a new `Renderer` like `new`, but lets its `TextRenderer` use the given (possibly
+    /// shared) `GlyphCache`. Wrappers that run multiple `Application`s (for instance 1 per
+    /// window) can give all their `Renderer`s the same glyph cache, so that the fonts and glyph
+    /// atlases are only stored once.
+    ///
+    /// Note that the other GPU resources (like the shader cache) can't be shared, since every
+    /// `Renderer` has its own golem `Context`.
+    pub fn with_glyph_cache(
+        context: Context, initial_viewport: RenderRegion, glyph_cache: GlyphCache
+    ) -> Self {
+        let resource_tracker = Rc::new(GpuResourceTracker::default());
         Self {
-            storage: GolemRenderStorage::new(&context).expect("Should be able to init storage"),
+            storage: GolemRenderStorage::new(&context, &resource_tracker)
+                .expect("Should be able to init storage"),
             context,
-            text_renderer: TextRenderer::new(),
+            text_renderer: TextRenderer::with_glyph_cache(glyph_cache),
             viewport_stack: RefCell::new(vec![initial_viewport]),
             scissor_stack: RefCell::new(vec![initial_viewport]),
+            origin: Cell::new((0, 0)),
+            opacity_stack: RefCell::new(vec![1.0]),
+            pixel_snapping: Cell::new(false),
+            repaint_log: RefCell::new(None),
+            resource_tracker,
+            context_generation: 0,
+            interpolation_alpha: Cell::new(1.0),
+        }
+    }
+
+    /// Replaces the golem `Context` of this `Renderer` after the previous context was lost (for
+    /// instance when the browser lost the WebGL context, or when the desktop wrapper had to
+    /// recreate its OpenGL context). The GPU resources of the old context are forgotten (they are
+    /// gone anyway): the cached shaders will be created again when they are needed, and the glyph
+    /// atlases of the text renderer will be uploaded again from their CPU copies.
+    ///
+    /// Normally, only the *wrapper* should use this method. The `Application` will automatically
+    /// render everything again during its next `render` call. Components that created their own
+    /// GPU resources (for instance using `load_texture`) should create them again when
+    /// `get_context_generation` changes.
+    pub fn recover_from_context_loss(&mut self, context: Context) -> Result<(), GolemError> {
+        let new_storage = GolemRenderStorage::new(&context, &self.resource_tracker)?;
+        std::mem::replace(&mut self.storage, new_storage).forget_lost();
+        self.text_renderer.forget_lost_gpu_resources();
+        self.context = context;
+        self.context_generation += 1;
+        Ok(())
+    }
+
+    /// Destroys the cached GPU resources that haven't been used since the previous call to this
+    /// method (or since this `Renderer` was created), and returns the number of destroyed
+    /// resources. Long-running applications should call this once in a while (the `Application`
+    /// doesn't do this automatically) to prevent them from accumulating resources that are no
+    /// longer needed, for instance the shaders of components that have been removed.
+    pub fn collect_garbage(&self) -> usize {
+        self.storage.shader_cache.borrow_mut().collect_garbage()
+    }
+
+    /// Tells this `Renderer` on which `Surface` the frames are drawn, which is needed for
+    /// `capture_frame`. Normally, only the *wrapper* should use this method.
+    pub fn set_frame_surface(&self, surface: Option<Rc<Surface>>) {
+        *self.storage.frame_surface.borrow_mut() = surface;
+    }
+
+    /// Captures the pixels of the current frame (everything within the root viewport) and returns
+    /// them as `Texture`. This can be used to implement screenshot buttons, or to check the output
+    /// of real GPUs in tests.
+    ///
+    /// If this is called during rendering, the captured frame will contain everything that has
+    /// been drawn so far. This returns `None` if the *wrapper* didn't provide a frame surface via
+    /// `set_frame_surface` (the web wrapper draws directly onto the canvas, so it doesn't support
+    /// this yet).
+    pub fn capture_frame(&self) -> Option<crate::Texture> {
+        let frame_surface = self.storage.frame_surface.borrow();
+        let surface = frame_surface.as_ref()?;
+
+        let viewport = self.viewport_stack.borrow()[0];
+        let width = viewport.get_width();
+        let height = viewport.get_height();
+        let mut pixels = vec![0; (width * height * 4) as usize];
+
+        let was_bound = surface.is_bound();
+        surface.bind();
+        unsafe {
+            surface.get_pixel_data(
+                viewport.get_min_x(),
+                viewport.get_min_y(),
+                width,
+                height,
+                ColorFormat::RGBA,
+                &mut pixels,
+            );
         }
+        if !was_bound {
+            Surface::unbind(&self.context);
+        }
+
+        Some(crate::Texture::from_pixel_buffer(width, height, &pixels))
     }
 
     /// Sets the color of all pixels within the current viewport and scissor to the given `Color`.
     pub fn clear(&self, color: Color) {
+        // Clearing ignores the blend mode, so a translucent clear needs to draw a quad instead
+        if self.get_opacity() < 1.0 {
+            let parameters = FragmentOnlyDrawParameters {
+                colors: &[color],
+                ..FragmentOnlyDrawParameters::default()
+            };
+            let shader = &self.storage.clear_shader;
+            self.apply_fragment_shader(0.0, 0.0, 1.0, 1.0, shader, parameters);
+            return;
+        }
+
+        self.log_repaint(0.0, 0.0, 1.0, 1.0);
         self.context.set_clear_color(
             color.get_red_float(),
             color.get_green_float(),
@@ -35,76 +140,15 @@ impl Renderer {
         &self, min_x: f32, min_y: f32, max_x: f32, max_y: f32,
         shader: &FragmentOnlyShader, parameters: FragmentOnlyDrawParameters
     ) {
-        let shader_name = format!("FragmentOnlyShader {:?}", shader.hash.as_slice());
+        let (min_x, min_y, max_x, max_y) = self.snap_to_pixels(min_x, min_y, max_x, max_y);
+        self.log_repaint(min_x, min_y, max_x, max_y);
+        let viewport_transform = self.get_target_viewport().1;
         self.use_cached_shader(
-            &ShaderId::from_strings("knukki".to_string(), shader_name),
-            |golem| {
-
-                let mut uniforms = Vec::new();
-                uniforms.push(Uniform::new(
-                    "vertexBounds",
-                    UniformType::Vector(NumberType::Float, Dimension::D4)
-                ));
-                for matrix_counter in 1 ..= shader.description.num_float_matrices {
-                    uniforms.push(Uniform::new(
-                        MATRIX_VARIABLE_NAMES[matrix_counter as usize],
-                        UniformType::Matrix(Dimension::D4)
-                    ));
-                }
-                for color_counter in 1 ..= shader.description.num_colors {
-                    uniforms.push(Uniform::new(
-                        COLOR_VARIABLE_NAMES[color_counter as usize],
-                        UniformType::Vector(NumberType::Float, Dimension::D4)
-                    ));
-                }
-                for vector_counter in 1 ..= shader.description.num_float_vectors {
-                    uniforms.push(Uniform::new(
-                        FLOAT_VECTOR_VARIABLE_NAMES[vector_counter as usize],
-                        UniformType::Vector(NumberType::Float, Dimension::D4)
-                    ));
-                }
-                for vector_counter in 1 ..= shader.description.num_int_vectors {
-                    uniforms.push(Uniform::new(
-                        INT_VECTOR_VARIABLE_NAMES[vector_counter as usize],
-                        UniformType::Vector(NumberType::Int, Dimension::D4)
-                    ));
-                }
-                for float_counter in 1 ..= shader.description.num_floats {
-                    uniforms.push(Uniform::new(
-                        FLOAT_VARIABLE_NAMES[float_counter as usize],
-                        UniformType::Scalar(NumberType::Float)
-                    ));
-                }
-                for int_counter in 1 ..= shader.description.num_ints {
-                    uniforms.push(Uniform::new(
-                        INT_VARIABLE_NAMES[int_counter as usize],
-                        UniformType::Scalar(NumberType::Int)
-                    ));
-                }
-
-                let shader_description = ShaderDescription {
-                    vertex_input: &[
-                        Attribute::new("vertexInnerPosition", AttributeType::Vector(Dimension::D2))
-                    ],
-                    fragment_input: &[
-                        Attribute::new("innerPosition", AttributeType::Vector(Dimension::D2)),
-                        Attribute::new("outerPosition", AttributeType::Vector(Dimension::D2))
-                    ],
-                    uniforms: &uniforms,
-                    vertex_shader: "
-                void main() {
-                    innerPosition = 0.5 * vertexInnerPosition + 0.5;
-                    vec2 bottomLeftBounds = vertexBounds.xy;
-                    vec2 topRightBounds = vertexBounds.zw;
-                    outerPosition = bottomLeftBounds + innerPosition * (topRightBounds - bottomLeftBounds);
-                    gl_Position = vec4(2.0 * outerPosition - vec2(1.0, 1.0), 0.0, 1.0);
-                }
-            ",
-                    fragment_shader: &shader.description.source_code
-                };
-                ShaderProgram::new(golem, shader_description)
-            }, |shader_program| {
+            &get_fragment_only_shader_id(shader),
+            |golem| create_fragment_only_shader(golem, shader),
+            |shader_program| {
                 shader_program.set_uniform("vertexBounds", UniformValue::Vector4([min_x, min_y, max_x, max_y]))?;
+                shader_program.set_uniform("viewportTransform", UniformValue::Vector4(viewport_transform))?;
                 for matrix_counter in 1 ..= shader.description.num_float_matrices {
                     let _result = shader_program.set_uniform(
                         &format!("matrix{}", matrix_counter),
@@ -163,8 +207,34 @@ impl Renderer {
 
     // This will be handled internally.
     pub(super) fn apply_viewport_and_scissor(&self) {
-        self.get_viewport().set_viewport(&self.context);
-        self.get_scissor().set_scissor(&self.context);
+        self.get_target_viewport().0.set_viewport(&self.context);
+        self.get_target_scissor().set_scissor(&self.context);
+    }
+
+    // This will be handled internally.
+    pub(super) fn apply_opacity(&self) {
+        use golem::blend::*;
+
+        let opacity = self.get_opacity();
+        if opacity < 1.0 {
+            // Use the opacity as constant alpha, regardless of the alpha of the fragments
+            self.context.set_blend_mode(Some(BlendMode {
+                function: BlendFunction::Same {
+                    source: BlendFactor::Alpha {
+                        source: BlendInput::Constant,
+                        inverted: false,
+                    },
+                    destination: BlendFactor::Alpha {
+                        source: BlendInput::Constant,
+                        inverted: true,
+                    },
+                },
+                global_color: [0.0, 0.0, 0.0, opacity],
+                ..BlendMode::default()
+            }));
+        } else {
+            self.context.set_blend_mode(None);
+        }
     }
 
     /// Gets a reference to a `VertexBuffer` representing the basic `quad` model (simply the
@@ -179,13 +249,13 @@ impl Renderer {
     /// resources to let every component create its own quad `VertexBuffer`. To solve this issue,
     /// all components in need of the quad model can simply share this one.
     pub fn get_quad_vertices(&self) -> &VertexBuffer {
-        &self.storage.quad_vertices
+        &*self.storage.quad_vertices
     }
 
     /// Gets a reference to the corresponding `ElementBuffer` of the `VertexBuffer` given by the
     /// `get_quad_vertices` method. (These indices are just [(0, 1, 2), (2, 3, 0)].)
     pub fn get_quad_indices(&self) -> &ElementBuffer {
-        &self.storage.quad_indices
+        &*self.storage.quad_indices
     }
 
     /// Gets the number of indices in the `ElementBuffer` given by the `get_quad_indices`
@@ -224,6 +294,23 @@ impl Renderer {
         cache.use_shader(id, || create_shader(&self.context), use_shader)
     }
 
+    pub(super) fn prewarm_shader(&self, shader: &BuiltInShader) -> Result<(), GolemError> {
+        let mut cache = self.storage.shader_cache.borrow_mut();
+        let create_shader = || match shader {
+            BuiltInShader::Text => TextRenderer::create_default_shader(&self.context),
+            BuiltInShader::FragmentOnly(shader) => {
+                create_fragment_only_shader(&self.context, shader)
+            }
+        };
+        let id = match shader {
+            BuiltInShader::Text => TextRenderer::get_default_shader_id(),
+            BuiltInShader::FragmentOnly(shader) => get_fragment_only_shader_id(shader),
+        };
+
+        // The shader cache will only create the shader if it doesn't have it already
+        cache.use_shader(&id, create_shader, |_shader| Ok(()))
+    }
+
     pub fn load_texture(&self, cpu_texture: &crate::Texture) -> Result<golem::Texture, GolemError> {
         let mut gpu_texture = golem::Texture::new(&self.context)?;
         let pixel_buffer = cpu_texture.create_pixel_buffer();
@@ -243,16 +330,96 @@ impl Renderer {
     }
 }
 
+fn get_fragment_only_shader_id(shader: &FragmentOnlyShader) -> ShaderId {
+    let shader_name = format!("FragmentOnlyShader {:?}", shader.hash.as_slice());
+    ShaderId::from_strings("knukki".to_string(), shader_name)
+}
+
+fn create_fragment_only_shader(
+    golem: &Context, shader: &FragmentOnlyShader
+) -> Result<ShaderProgram, GolemError> {
+    let mut uniforms = Vec::new();
+    uniforms.push(Uniform::new(
+        "vertexBounds",
+        UniformType::Vector(NumberType::Float, Dimension::D4)
+    ));
+    uniforms.push(Uniform::new(
+        "viewportTransform",
+        UniformType::Vector(NumberType::Float, Dimension::D4)
+    ));
+    for matrix_counter in 1 ..= shader.description.num_float_matrices {
+        uniforms.push(Uniform::new(
+            MATRIX_VARIABLE_NAMES[matrix_counter as usize],
+            UniformType::Matrix(Dimension::D4)
+        ));
+    }
+    for color_counter in 1 ..= shader.description.num_colors {
+        uniforms.push(Uniform::new(
+            COLOR_VARIABLE_NAMES[color_counter as usize],
+            UniformType::Vector(NumberType::Float, Dimension::D4)
+        ));
+    }
+    for vector_counter in 1 ..= shader.description.num_float_vectors {
+        uniforms.push(Uniform::new(
+            FLOAT_VECTOR_VARIABLE_NAMES[vector_counter as usize],
+            UniformType::Vector(NumberType::Float, Dimension::D4)
+        ));
+    }
+    for vector_counter in 1 ..= shader.description.num_int_vectors {
+        uniforms.push(Uniform::new(
+            INT_VECTOR_VARIABLE_NAMES[vector_counter as usize],
+            UniformType::Vector(NumberType::Int, Dimension::D4)
+        ));
+    }
+    for float_counter in 1 ..= shader.description.num_floats {
+        uniforms.push(Uniform::new(
+            FLOAT_VARIABLE_NAMES[float_counter as usize],
+            UniformType::Scalar(NumberType::Float)
+        ));
+    }
+    for int_counter in 1 ..= shader.description.num_ints {
+        uniforms.push(Uniform::new(
+            INT_VARIABLE_NAMES[int_counter as usize],
+            UniformType::Scalar(NumberType::Int)
+        ));
+    }
+
+    let shader_description = ShaderDescription {
+        vertex_input: &[
+            Attribute::new("vertexInnerPosition", AttributeType::Vector(Dimension::D2))
+        ],
+        fragment_input: &[
+            Attribute::new("innerPosition", AttributeType::Vector(Dimension::D2)),
+            Attribute::new("outerPosition", AttributeType::Vector(Dimension::D2))
+        ],
+        uniforms: &uniforms,
+        vertex_shader: "
+    void main() {
+        innerPosition = 0.5 * vertexInnerPosition + 0.5;
+        vec2 bottomLeftBounds = vertexBounds.xy;
+        vec2 topRightBounds = vertexBounds.zw;
+        outerPosition = bottomLeftBounds + innerPosition * (topRightBounds - bottomLeftBounds);
+        vec2 targetPosition = viewportTransform.xy + viewportTransform.zw * outerPosition;
+        gl_Position = vec4(2.0 * targetPosition - vec2(1.0, 1.0), 0.0, 1.0);
+    }
+",
+        fragment_shader: &shader.description.source_code
+    };
+    ShaderProgram::new(golem, shader_description)
+}
+
 pub(super) struct GolemRenderStorage {
     // Frequently used and cheap buffers
-    quad_vertices: VertexBuffer,
-    quad_indices: ElementBuffer,
+    quad_vertices: TrackedGpuResource<VertexBuffer>,
+    quad_indices: TrackedGpuResource<ElementBuffer>,
 
     shader_cache: RefCell<ShaderCache>,
+    frame_surface: RefCell<Option<Rc<Surface>>>,
+    clear_shader: FragmentOnlyShader,
 }
 
 impl GolemRenderStorage {
-    fn new(context: &Context) -> Result<Self, GolemError> {
+    fn new(context: &Context, tracker: &Rc<GpuResourceTracker>) -> Result<Self, GolemError> {
         let mut quad_vertices = VertexBuffer::new(context)?;
         #[rustfmt::skip]
         quad_vertices.set_data(&[-1.0, -1.0,    1.0, -1.0,    1.0, 1.0,    -1.0, 1.0]);
@@ -263,34 +430,72 @@ impl GolemRenderStorage {
         // Practice will have to tell whether 200 is good.
         let max_cached_shaders = 200;
 
+        let buffer_kind = GpuResourceKind::Buffer;
+        let quad_vertices = GpuResourceTracker::track(tracker, buffer_kind, quad_vertices);
+        let quad_indices = GpuResourceTracker::track(tracker, buffer_kind, quad_indices);
+
         Ok(Self {
             quad_vertices,
             quad_indices,
-            shader_cache: RefCell::new(ShaderCache::new(max_cached_shaders)),
+            shader_cache: RefCell::new(ShaderCache::new(max_cached_shaders, Rc::clone(tracker))),
+            frame_surface: RefCell::new(None),
+            clear_shader: Renderer::create_clear_shader(),
         })
     }
+
+    /// Forgets all GPU resources of this storage, without destroying them. See
+    /// `Renderer.recover_from_context_loss`.
+    fn forget_lost(self) {
+        self.quad_vertices.forget_lost();
+        self.quad_indices.forget_lost();
+        self.shader_cache.into_inner().forget_lost();
+
+        // The wrapper owns the frame surface, but this reference should not destroy it
+        std::mem::forget(self.frame_surface);
+    }
 }
 
 struct ShaderCache {
     map: HashMap<ShaderId, CachedShader>,
     max_cached_shaders: usize,
     current_time: u64,
+    last_collection_time: u64,
+    tracker: Rc<GpuResourceTracker>,
 }
 
 impl ShaderCache {
-    fn new(max_cached_shaders: usize) -> Self {
+    fn new(max_cached_shaders: usize, tracker: Rc<GpuResourceTracker>) -> Self {
         assert!(max_cached_shaders > 0);
         Self {
             map: HashMap::new(),
             max_cached_shaders,
             current_time: 0,
+            last_collection_time: 0,
+            tracker,
         }
     }
 
     fn get_existing(&mut self, id: &ShaderId) -> &mut ShaderProgram {
         let cached = self.map.get_mut(id).unwrap();
         cached.last_used = self.current_time;
-        return &mut cached.shader;
+        return &mut *cached.shader;
+    }
+
+    /// Removes all shaders that haven't been used since the previous call to this method, and
+    /// returns the number of removed shaders
+    fn collect_garbage(&mut self) -> usize {
+        let old_size = self.map.len();
+        let last_collection_time = self.last_collection_time;
+        self.map.retain(|_id, cached_shader| cached_shader.last_used > last_collection_time);
+        self.last_collection_time = self.current_time;
+        old_size - self.map.len()
+    }
+
+    /// Forgets all cached shaders, without destroying them. See `GolemRenderStorage.forget_lost`.
+    fn forget_lost(self) {
+        for (_id, cached_shader) in self.map {
+            cached_shader.shader.forget_lost();
+        }
     }
 
     fn use_shader(
@@ -330,18 +535,21 @@ impl ShaderCache {
 
         // Now that we are sure we won't exceed the maximum number of shaders, we can insert the
         // new shader, and return a reference to it.
+        let shader = GpuResourceTracker::track(
+            &self.tracker, GpuResourceKind::Shader, create_shader()?
+        );
         let value = self.map.entry(id.clone()).or_insert(CachedShader {
             last_used: self.current_time,
-            shader: create_shader()?,
+            shader,
         });
         value.shader.bind();
-        use_shader(&mut value.shader)
+        use_shader(&mut *value.shader)
     }
 }
 
 struct CachedShader {
     last_used: u64,
-    shader: ShaderProgram,
+    shader: TrackedGpuResource<ShaderProgram>,
 }
 
 /// Represents a unique identifier for a pair of a vertex shader and fragment shader. This struct
@@ -1,15 +1,22 @@
 use crate::RenderRegion;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
 mod core;
 #[cfg(feature = "golem_rendering")]
 mod golem_renderer;
 
+mod prebaked;
+mod prewarm;
+mod resources;
 mod text;
 
 #[cfg(feature = "golem_rendering")]
 pub use golem_renderer::ShaderId;
 
+pub use prebaked::*;
+pub use prewarm::*;
+pub use resources::*;
 pub use text::*;
 
 /// This struct is used to render `Component`s (and the `Application`). A reference to an instance
@@ -56,14 +63,30 @@ pub struct Renderer {
     text_renderer: TextRenderer,
     viewport_stack: RefCell<Vec<RenderRegion>>,
     scissor_stack: RefCell<Vec<RenderRegion>>,
+    // The position of the bottom-left corner of the render target in the coordinate system of
+    // the viewport stack and scissor stack. See `Renderer.push_viewport`.
+    origin: Cell<(u32, u32)>,
+    opacity_stack: RefCell<Vec<f32>>,
+    pixel_snapping: Cell<bool>,
+    repaint_log: RefCell<Option<Vec<RenderRegion>>>,
+    resource_tracker: Rc<GpuResourceTracker>,
+    context_generation: u64,
+    interpolation_alpha: Cell<f32>,
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "bench"))]
 #[cfg(not(feature = "golem_rendering"))]
 pub(crate) fn test_renderer(initial_viewport: RenderRegion) -> Renderer {
     Renderer {
         text_renderer: TextRenderer::new(),
         viewport_stack: RefCell::new(vec![initial_viewport]),
         scissor_stack: RefCell::new(vec![initial_viewport]),
+        origin: Cell::new((0, 0)),
+        opacity_stack: RefCell::new(vec![1.0]),
+        pixel_snapping: Cell::new(false),
+        repaint_log: RefCell::new(None),
+        resource_tracker: Rc::new(GpuResourceTracker::default()),
+        context_generation: 0,
+        interpolation_alpha: Cell::new(1.0),
     }
 }
@@ -0,0 +1,233 @@
+use crate::*;
+
+use super::text::{
+    add_grapheme_texture,
+    create_font_atlas_group,
+    GroupGraphemeTexture,
+    POINT_SIZE,
+};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::ops::RangeInclusive;
+
+const PREBAKED_MAGIC: &[u8; 8] = b"KNUKKIPG";
+const PREBAKED_VERSION: u8 = 1;
+
+/// Rasterizes a set of characters of a `Font` onto texture atlases ahead of time, and serializes
+/// these atlases together with the metrics table of the glyphs. Applications can run this builder
+/// offline (for instance in a build script), bundle the result, and pass it to
+/// `TextRenderer.register_prebaked_font` at startup. That avoids the cost of rasterizing all
+/// glyphs during the first frames, which is noticeable on slow devices.
+///
+/// Use 1 builder per font. There is no need to specify font sizes: the text renderer rasterizes
+/// every glyph at the same point size, and scales it while drawing.
+pub struct PrebakedGlyphAtlasBuilder<'a> {
+    font: &'a dyn Font,
+    graphemes: Vec<String>,
+    grapheme_set: HashSet<String>,
+}
+
+impl<'a> PrebakedGlyphAtlasBuilder<'a> {
+    pub fn new(font: &'a dyn Font) -> Self {
+        Self {
+            font,
+            graphemes: Vec::new(),
+            grapheme_set: HashSet::new(),
+        }
+    }
+
+    /// Adds all grapheme clusters of *characters* to the set of glyphs that will be prebaked.
+    /// Duplicate graphemes are ignored.
+    pub fn add_characters(&mut self, characters: &str) -> &mut Self {
+        for grapheme in characters.graphemes(true) {
+            if self.grapheme_set.insert(grapheme.to_string()) {
+                self.graphemes.push(grapheme.to_string());
+            }
+        }
+        self
+    }
+
+    /// Adds all characters in the given *range* (for instance `'a' ..= 'z'`) to the set of glyphs
+    /// that will be prebaked.
+    pub fn add_character_range(&mut self, range: RangeInclusive<char>) -> &mut Self {
+        let characters: String = range.collect();
+        self.add_characters(&characters)
+    }
+
+    /// Rasterizes all added glyphs, places them on texture atlases, and returns the serialized
+    /// atlases and metrics table.
+    pub fn build(&self) -> Vec<u8> {
+        let mut atlas_group = create_font_atlas_group::<()>();
+        let grapheme_textures: Vec<_> = self.graphemes.iter().map(
+            |grapheme| add_grapheme_texture(self.font, &mut atlas_group, grapheme)
+        ).collect();
+
+        let texture_ids: Vec<_> = grapheme_textures.iter().filter_map(
+            |maybe_texture| maybe_texture.as_ref().map(|texture| texture.texture_id)
+        ).collect();
+        atlas_group.place_textures(&texture_ids);
+
+        let mut writer = SnapshotWriter::new();
+        writer.write_bytes(PREBAKED_MAGIC);
+        writer.write_u8(PREBAKED_VERSION);
+        writer.write_f32(POINT_SIZE);
+
+        writer.write_u32(self.graphemes.len() as u32);
+        for (grapheme, maybe_texture) in self.graphemes.iter().zip(grapheme_textures.iter()) {
+            writer.write_string(grapheme);
+            match maybe_texture {
+                Some(texture) => {
+                    writer.write_bool(true);
+                    writer.write_texture_id(texture.texture_id);
+                    writer.write_u32(texture.offset_y);
+                    writer.write_u32(texture.width);
+                    writer.write_u32(texture.height);
+                }
+                None => writer.write_bool(false),
+            }
+        }
+
+        writer.write_bytes(&atlas_group.save_snapshot());
+        writer.into_bytes()
+    }
+}
+
+type PrebakedGlyphs<G> = (TextureAtlasGroup<G>, HashMap<String, Option<GroupGraphemeTexture>>);
+
+/// Parses the result of `PrebakedGlyphAtlasBuilder.build`
+pub(super) fn load_prebaked_glyphs<G>(
+    prebaked: &[u8]
+) -> Result<PrebakedGlyphs<G>, InvalidAtlasSnapshot> {
+    let mut reader = SnapshotReader::new(prebaked);
+    let magic = reader.read_bytes(PREBAKED_MAGIC.len());
+    if magic != Ok(&PREBAKED_MAGIC[..]) {
+        return Err(InvalidAtlasSnapshot::NotASnapshot);
+    }
+    let version = reader.read_u8()?;
+    if version != PREBAKED_VERSION {
+        return Err(InvalidAtlasSnapshot::UnsupportedVersion(version));
+    }
+    if reader.read_f32()? != POINT_SIZE {
+        return Err(InvalidAtlasSnapshot::Corrupted("prebaked with a different point size"));
+    }
+
+    let num_graphemes = reader.read_u32()?;
+    let mut grapheme_entries = Vec::new();
+    for _ in 0 .. num_graphemes {
+        let grapheme = reader.read_string()?;
+        let maybe_texture = if reader.read_bool()? {
+            Some(GroupGraphemeTexture {
+                texture_id: reader.read_texture_id()?,
+                offset_y: reader.read_u32()?,
+                width: reader.read_u32()?,
+                height: reader.read_u32()?,
+            })
+        } else {
+            None
+        };
+        grapheme_entries.push((grapheme, maybe_texture));
+    }
+
+    let atlas_group = TextureAtlasGroup::load_snapshot(reader.read_remaining())?;
+
+    let mut char_textures = HashMap::with_capacity(grapheme_entries.len());
+    for (grapheme, maybe_texture) in grapheme_entries {
+        if let Some(texture) = &maybe_texture {
+            if !atlas_group.contains_texture(texture.texture_id) {
+                return Err(InvalidAtlasSnapshot::Corrupted("missing glyph texture"));
+            }
+            let group_texture = atlas_group.get_texture(texture.texture_id);
+            if group_texture.get_width() != texture.width
+                || group_texture.get_height() != texture.height {
+                return Err(InvalidAtlasSnapshot::Corrupted("wrong glyph size"));
+            }
+        }
+        char_textures.insert(grapheme, maybe_texture);
+    }
+
+    Ok((atlas_group, char_textures))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct CountingFont {
+        num_drawn_graphemes: Rc<Cell<u32>>,
+    }
+
+    impl Font for CountingFont {
+        fn draw_grapheme(&self, grapheme: &str, point_size: f32) -> Option<CharTexture> {
+            self.num_drawn_graphemes.set(self.num_drawn_graphemes.get() + 1);
+            if grapheme == " " {
+                None
+            } else {
+                Some(CharTexture {
+                    texture: Texture::new(
+                        (0.5 * point_size) as u32,
+                        (0.7 * point_size) as u32,
+                        Color::rgb(0, 0, 0)
+                    ),
+                    offset_y: (0.3 * point_size) as u32,
+                })
+            }
+        }
+
+        fn get_max_descent(&self, point_size: f32) -> f32 {
+            0.3 * point_size
+        }
+
+        fn get_max_ascent(&self, point_size: f32) -> f32 {
+            0.7 * point_size
+        }
+
+        fn get_whitespace_width(&self, point_size: f32) -> f32 {
+            0.4 * point_size
+        }
+    }
+
+    #[test]
+    fn test_prebaked_glyphs() {
+        let num_drawn_graphemes = Rc::new(Cell::new(0));
+        let font = CountingFont { num_drawn_graphemes: Rc::clone(&num_drawn_graphemes) };
+
+        let mut builder = PrebakedGlyphAtlasBuilder::new(&font);
+        builder.add_character_range('a' ..= 'z').add_characters("hello world");
+        let prebaked = builder.build();
+
+        // 26 letters and 1 space
+        assert_eq!(27, num_drawn_graphemes.get());
+
+        let text_renderer = TextRenderer::new();
+        text_renderer.register_prebaked_font("prebaked", Box::new(font), &prebaked).unwrap();
+        let style = TextStyle {
+            font_id: Some("prebaked".to_string()),
+            ..TextStyle::from_palette(&Palette::light())
+        };
+
+        // The prebaked glyphs shouldn't be drawn again
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 50));
+        text_renderer.get_text_size("hello world", &style, &renderer).unwrap();
+        assert_eq!(27, num_drawn_graphemes.get());
+
+        // But other glyphs should still be drawn
+        text_renderer.get_text_size("Hello", &style, &renderer).unwrap();
+        assert_eq!(28, num_drawn_graphemes.get());
+
+        assert_eq!(
+            Some(InvalidAtlasSnapshot::NotASnapshot),
+            text_renderer.register_prebaked_font(
+                "invalid", Box::new(create_default_font()), &prebaked[1..]
+            ).err()
+        );
+    }
+}
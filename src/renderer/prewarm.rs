@@ -0,0 +1,127 @@
+use crate::*;
+
+use std::fmt::{Display, Formatter};
+
+/// The progress of `Renderer.prewarm`, which will be passed to its progress callback
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct PrewarmProgress {
+    num_finished: usize,
+    num_total: usize,
+}
+
+impl PrewarmProgress {
+    /// Gets the number of shaders that have been compiled so far
+    pub fn get_num_finished(&self) -> usize {
+        self.num_finished
+    }
+
+    /// Gets the total number of shaders that will be compiled
+    pub fn get_num_total(&self) -> usize {
+        self.num_total
+    }
+
+    /// Gets the fraction of the shaders that have been compiled so far, which will be between 0.0
+    /// and 1.0
+    pub fn get_fraction(&self) -> f32 {
+        if self.num_total == 0 {
+            1.0
+        } else {
+            self.num_finished as f32 / self.num_total as f32
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.num_finished == self.num_total
+    }
+}
+
+impl Display for PrewarmProgress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} shaders", self.num_finished, self.num_total)
+    }
+}
+
+/// The shaders that are used by the `Renderer` itself and the built-in components
+pub(crate) enum BuiltInShader {
+    Text,
+    // The shader itself is only used by the golem renderer
+    #[allow(dead_code)]
+    FragmentOnly(FragmentOnlyShader),
+}
+
+pub(crate) fn get_built_in_shaders() -> Vec<BuiltInShader> {
+    vec![
+        BuiltInShader::Text,
+        BuiltInShader::FragmentOnly(Renderer::create_clear_shader()),
+        BuiltInShader::FragmentOnly(HoverColorCircleComponent::create_shader()),
+        BuiltInShader::FragmentOnly(InitialsBadge::create_shader()),
+        BuiltInShader::FragmentOnly(OverlayMenu::create_shader()),
+        BuiltInShader::FragmentOnly(Rating::create_shader()),
+        BuiltInShader::FragmentOnly(TextButton::create_shader()),
+    ]
+}
+
+#[cfg(feature = "golem_rendering")]
+type PrewarmError = golem::GolemError;
+
+#[cfg(not(feature = "golem_rendering"))]
+type PrewarmError = ();
+
+impl Renderer {
+    /// Compiles all shaders that are used by the `Renderer` itself and the built-in components,
+    /// and puts them in the shader cache. Shaders are normally compiled the first time they are
+    /// needed, which can cause a noticeable hitch during the first interaction with a component.
+    /// Calling this right after creating the `Renderer` moves that work to the startup of the
+    /// application instead.
+    ///
+    /// The *progress* callback will be called once before the first shader is compiled, and once
+    /// after each compiled shader, so that the *wrapper* can show a loading indicator.
+    ///
+    /// Note that prewarmed shaders are treated like any other cached shader, so they can still be
+    /// destroyed by `collect_garbage` when they are not used.
+    pub fn prewarm(&self, mut progress: impl FnMut(PrewarmProgress)) -> Result<(), PrewarmError> {
+        let shaders = get_built_in_shaders();
+        let num_total = shaders.len();
+        progress(PrewarmProgress { num_finished: 0, num_total });
+
+        for (index, shader) in shaders.iter().enumerate() {
+            self.prewarm_shader(shader)?;
+            progress(PrewarmProgress { num_finished: index + 1, num_total });
+        }
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    #[cfg(not(feature = "golem_rendering"))]
+    fn prewarm_shader(&self, shader: &BuiltInShader) -> Result<(), PrewarmError> {
+        // There is nothing to be done without a Golem context
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_prewarm_progress() {
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 10, 10));
+        let mut progress_list = Vec::new();
+        renderer.prewarm(|progress| progress_list.push(progress)).unwrap();
+
+        let num_total = get_built_in_shaders().len();
+        assert_eq!(num_total + 1, progress_list.len());
+        for (index, progress) in progress_list.iter().enumerate() {
+            assert_eq!(index, progress.get_num_finished());
+            assert_eq!(num_total, progress.get_num_total());
+        }
+
+        assert_eq!(0.0, progress_list[0].get_fraction());
+        assert!(!progress_list[0].is_finished());
+        assert_eq!(1.0, progress_list[num_total].get_fraction());
+        assert!(progress_list[num_total].is_finished());
+        let expected_string = format!("{}/{} shaders", num_total, num_total);
+        assert_eq!(expected_string, progress_list[num_total].to_string());
+    }
+}
@@ -0,0 +1,202 @@
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+/// The kinds of GPU resources that are tracked by the `Renderer`
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GpuResourceKind {
+    Shader,
+    Buffer,
+}
+
+/// The number of GPU resources of some `GpuResourceKind` that the `Renderer` created and destroyed
+/// so far. See `Renderer.get_gpu_resource_counts`.
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub struct GpuResourceCounts {
+    created: u64,
+    destroyed: u64,
+}
+
+impl GpuResourceCounts {
+    pub fn get_created(&self) -> u64 {
+        self.created
+    }
+
+    pub fn get_destroyed(&self) -> u64 {
+        self.destroyed
+    }
+
+    /// Gets the number of resources that have been created, but not yet destroyed. If this keeps
+    /// growing in a long-running application, something is probably leaking resources.
+    pub fn get_alive(&self) -> u64 {
+        self.created - self.destroyed
+    }
+}
+
+impl Display for GpuResourceCounts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} alive ({} created, {} destroyed)",
+            self.get_alive(),
+            self.created,
+            self.destroyed
+        )
+    }
+}
+
+/// Keeps track of the number of GPU resources of each kind that are created and destroyed. The
+/// resources themselves should be wrapped in a `TrackedGpuResource`, which will report their
+/// creation and destruction.
+#[derive(Default)]
+pub(crate) struct GpuResourceTracker {
+    shaders: Cell<GpuResourceCounts>,
+    buffers: Cell<GpuResourceCounts>,
+}
+
+impl GpuResourceTracker {
+    fn get_cell(&self, kind: GpuResourceKind) -> &Cell<GpuResourceCounts> {
+        match kind {
+            GpuResourceKind::Shader => &self.shaders,
+            GpuResourceKind::Buffer => &self.buffers,
+        }
+    }
+
+    pub fn get_counts(&self, kind: GpuResourceKind) -> GpuResourceCounts {
+        self.get_cell(kind).get()
+    }
+
+    /// Wraps the given *resource* in a `TrackedGpuResource` and records its creation
+    // This is only used by the golem renderer and the unit tests
+    #[allow(dead_code)]
+    pub fn track<T>(
+        tracker: &Rc<GpuResourceTracker>,
+        kind: GpuResourceKind,
+        resource: T,
+    ) -> TrackedGpuResource<T> {
+        let cell = tracker.get_cell(kind);
+        let mut counts = cell.get();
+        counts.created += 1;
+        cell.set(counts);
+
+        TrackedGpuResource {
+            resource: Some(resource),
+            kind,
+            tracker: Rc::clone(tracker),
+        }
+    }
+}
+
+/// A GPU resource whose destruction will be recorded by the `GpuResourceTracker` that created it
+pub(crate) struct TrackedGpuResource<T> {
+    // This is only None after forget_lost, so that the Drop implementation won't destroy it
+    resource: Option<T>,
+    kind: GpuResourceKind,
+    tracker: Rc<GpuResourceTracker>,
+}
+
+impl<T> TrackedGpuResource<T> {
+    fn record_destruction(&self) {
+        let cell = self.tracker.get_cell(self.kind);
+        let mut counts = cell.get();
+        counts.destroyed += 1;
+        cell.set(counts);
+    }
+
+    /// Records the destruction of this resource *without* destroying it. This should be used when
+    /// the GPU context of the resource was lost: the resource is already gone in that case, and
+    /// destroying it could affect the resources of a new context that happen to have the same id.
+    // This is only used by the golem renderer and the unit tests
+    #[allow(dead_code)]
+    pub fn forget_lost(mut self) {
+        // The Drop implementation will still record the destruction
+        if let Some(resource) = self.resource.take() {
+            std::mem::forget(resource);
+        }
+    }
+}
+
+impl<T> Deref for TrackedGpuResource<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.resource.as_ref().expect("Only forget_lost takes the resource")
+    }
+}
+
+impl<T> DerefMut for TrackedGpuResource<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.resource.as_mut().expect("Only forget_lost takes the resource")
+    }
+}
+
+impl<T> Drop for TrackedGpuResource<T> {
+    fn drop(&mut self) {
+        self.record_destruction();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_tracking() {
+        let tracker = Rc::new(GpuResourceTracker::default());
+        let shader1 = GpuResourceTracker::track(&tracker, GpuResourceKind::Shader, 1);
+        let mut shader2 = GpuResourceTracker::track(&tracker, GpuResourceKind::Shader, 2);
+        let buffer = GpuResourceTracker::track(&tracker, GpuResourceKind::Buffer, "buffer");
+
+        assert_eq!(1, *shader1);
+        *shader2 += 1;
+        assert_eq!(3, *shader2);
+        assert_eq!("buffer", *buffer);
+
+        let shader_counts = tracker.get_counts(GpuResourceKind::Shader);
+        assert_eq!(2, shader_counts.get_created());
+        assert_eq!(0, shader_counts.get_destroyed());
+        assert_eq!(2, shader_counts.get_alive());
+        assert_eq!(1, tracker.get_counts(GpuResourceKind::Buffer).get_alive());
+
+        drop(shader1);
+        let shader_counts = tracker.get_counts(GpuResourceKind::Shader);
+        assert_eq!(1, shader_counts.get_destroyed());
+        assert_eq!(1, shader_counts.get_alive());
+        assert_eq!("1 alive (2 created, 1 destroyed)", shader_counts.to_string());
+
+        drop(shader2);
+        drop(buffer);
+        assert_eq!(0, tracker.get_counts(GpuResourceKind::Shader).get_alive());
+        assert_eq!(0, tracker.get_counts(GpuResourceKind::Buffer).get_alive());
+    }
+
+    #[test]
+    fn test_forget_lost() {
+        struct DropCounter(Rc<Cell<u32>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let tracker = Rc::new(GpuResourceTracker::default());
+        let num_drops = Rc::new(Cell::new(0));
+        let kind = GpuResourceKind::Buffer;
+        let lost = GpuResourceTracker::track(&tracker, kind, DropCounter(Rc::clone(&num_drops)));
+        let normal = GpuResourceTracker::track(&tracker, kind, DropCounter(Rc::clone(&num_drops)));
+
+        lost.forget_lost();
+        assert_eq!(0, num_drops.get());
+        assert_eq!(1, tracker.get_counts(kind).get_destroyed());
+
+        drop(normal);
+        assert_eq!(1, num_drops.get());
+        assert_eq!(0, tracker.get_counts(kind).get_alive());
+
+        // The lost resource shouldn't keep the tracker alive
+        assert_eq!(1, Rc::strong_count(&tracker));
+    }
+}
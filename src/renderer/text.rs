@@ -2,6 +2,8 @@ use crate::*;
 
 // TODO Document this file!
 
+use super::prebaked::load_prebaked_glyphs;
+
 use unicode_segmentation::UnicodeSegmentation;
 
 use std::cell::RefCell;
@@ -9,20 +11,32 @@ use std::collections::{
     HashMap,
     HashSet,
 };
+use std::rc::Rc;
 
 pub type BeforeDraw<'a> = Option<&'a mut dyn FnMut(DrawnTextPosition)>;
 
-pub struct TextRenderer {
-    internal: RefCell<InternalTextRenderer>,
+/// The fonts and glyph atlases that are used by `TextRenderer`s. Cloning a `GlyphCache` gives
+/// another handle to the *same* cache.
+///
+/// Every `TextRenderer` has a `GlyphCache`, but multiple `TextRenderer`s can share the same cache
+/// (see `TextRenderer::with_glyph_cache`). This is useful for applications with multiple windows:
+/// when all their renderers share 1 cache, the glyphs only need to be drawn (and stored in CPU
+/// memory) once. The GPU textures can't be shared between GPU contexts, so each `TextRenderer`
+/// keeps its own GPU copies of the glyph atlases. Since all renderers use the same placements,
+/// the GPU atlas slots of all glyphs are the same in every context.
+#[derive(Clone)]
+pub struct GlyphCache {
+    internal: Rc<RefCell<InternalGlyphCache>>,
     default_font_handle: FontHandle,
 }
 
-impl TextRenderer {
+impl GlyphCache {
+    /// Constructs a new `GlyphCache` that only has the default font
     pub fn new() -> Self {
-        let mut internal = InternalTextRenderer::new();
+        let mut internal = InternalGlyphCache::new();
         let default_font_handle = internal.register_font("default", Box::new(create_default_font()));
 
-        Self { internal: RefCell::new(internal), default_font_handle }
+        Self { internal: Rc::new(RefCell::new(internal)), default_font_handle }
     }
 
     pub fn register_font(&self, font_id: &str, font: Box<dyn Font>) -> FontHandle {
@@ -30,6 +44,20 @@ impl TextRenderer {
         internal.register_font(font_id, font)
     }
 
+    /// Registers a font whose glyphs were prebaked by a `PrebakedGlyphAtlasBuilder`. The glyphs
+    /// in the prebaked atlases can be drawn without rasterizing them first. Any other glyphs will
+    /// be rasterized by *font* when they are needed, just like fonts registered by
+    /// `register_font`.
+    ///
+    /// This returns an error if *prebaked* is not a valid result of
+    /// `PrebakedGlyphAtlasBuilder.build`.
+    pub fn register_prebaked_font(
+        &self, font_id: &str, font: Box<dyn Font>, prebaked: &[u8]
+    ) -> Result<FontHandle, InvalidAtlasSnapshot> {
+        let mut internal = self.internal.borrow_mut();
+        internal.register_prebaked_font(font_id, font, prebaked)
+    }
+
     pub fn get_font(&self, font_id: &str) -> Option<FontHandle> {
         let internal = self.internal.borrow();
         internal.get_font(font_id)
@@ -39,6 +67,105 @@ impl TextRenderer {
         self.default_font_handle
     }
 
+    /// Checks whether *other* is a handle to the same cache as this handle
+    pub fn is_same_cache(&self, other: &GlyphCache) -> bool {
+        Rc::ptr_eq(&self.internal, &other.internal)
+    }
+}
+
+impl Default for GlyphCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct InternalGlyphCache {
+    font_id_mapping: HashMap<String, FontHandle>,
+    fonts: HashMap<FontHandle, CachedFont>,
+}
+
+impl InternalGlyphCache {
+    fn new() -> Self {
+        Self {
+            font_id_mapping: HashMap::new(),
+            fonts: HashMap::new(),
+        }
+    }
+
+    fn register_font(&mut self, font_id: &str, font: Box<dyn Font>) -> FontHandle {
+        self.register_font_entry(font_id, font, create_font_atlas_group(), HashMap::new())
+    }
+
+    fn register_prebaked_font(
+        &mut self, font_id: &str, font: Box<dyn Font>, prebaked: &[u8]
+    ) -> Result<FontHandle, InvalidAtlasSnapshot> {
+        let (atlas_group, char_textures) = load_prebaked_glyphs(prebaked)?;
+        Ok(self.register_font_entry(font_id, font, atlas_group, char_textures))
+    }
+
+    fn register_font_entry(
+        &mut self,
+        font_id: &str,
+        font: Box<dyn Font>,
+        atlas_group: TextureAtlasGroup<()>,
+        char_textures: HashMap<String, Option<GroupGraphemeTexture>>,
+    ) -> FontHandle {
+
+        let handle = FontHandle { internal: self.fonts.len() as u16 };
+
+        self.font_id_mapping.insert(font_id.to_string(), handle);
+        self.fonts.insert(handle, CachedFont { font, atlas_group, char_textures });
+        handle
+    }
+
+    fn get_font(&self, font_id: &str) -> Option<FontHandle> {
+        self.font_id_mapping.get(font_id).map(|handle_ref| *handle_ref)
+    }
+}
+
+pub struct TextRenderer {
+    glyph_cache: GlyphCache,
+    internal: RefCell<InternalTextRenderer>,
+}
+
+impl TextRenderer {
+    /// Constructs a new `TextRenderer` with its own `GlyphCache`
+    pub fn new() -> Self {
+        Self::with_glyph_cache(GlyphCache::new())
+    }
+
+    /// Constructs a new `TextRenderer` that uses the given (possibly shared) `GlyphCache`. Fonts
+    /// that are registered to the cache (by any of its users) can be used by this renderer.
+    pub fn with_glyph_cache(glyph_cache: GlyphCache) -> Self {
+        Self { glyph_cache, internal: RefCell::new(InternalTextRenderer::new()) }
+    }
+
+    /// Gets the `GlyphCache` of this `TextRenderer`, which can be passed to
+    /// `Renderer::with_glyph_cache` to let another `Renderer` share it
+    pub fn get_glyph_cache(&self) -> &GlyphCache {
+        &self.glyph_cache
+    }
+
+    pub fn register_font(&self, font_id: &str, font: Box<dyn Font>) -> FontHandle {
+        self.glyph_cache.register_font(font_id, font)
+    }
+
+    /// Registers a prebaked font to the `GlyphCache` of this `TextRenderer`. See the
+    /// `register_prebaked_font` method of `GlyphCache`.
+    pub fn register_prebaked_font(
+        &self, font_id: &str, font: Box<dyn Font>, prebaked: &[u8]
+    ) -> Result<FontHandle, InvalidAtlasSnapshot> {
+        self.glyph_cache.register_prebaked_font(font_id, font, prebaked)
+    }
+
+    pub fn get_font(&self, font_id: &str) -> Option<FontHandle> {
+        self.glyph_cache.get_font(font_id)
+    }
+
+    pub fn get_default_font(&self) -> FontHandle {
+        self.glyph_cache.get_default_font()
+    }
+
     pub fn draw_text(
         &self,
         text: &str,
@@ -52,7 +179,51 @@ impl TextRenderer {
             None => self.get_default_font()
         };
         let mut internal = self.internal.borrow_mut();
-        internal.draw_text(text, style, font_handle, position, renderer, before_draw)
+        let mut glyph_cache = self.glyph_cache.internal.borrow_mut();
+        let drawn_position = internal.draw_text(
+            &mut glyph_cache, text, style, font_handle, position, renderer, before_draw
+        )?;
+        renderer.log_repaint(
+            drawn_position.min_x, drawn_position.min_y, drawn_position.max_x, drawn_position.max_y
+        );
+        Ok(drawn_position)
+    }
+
+    /// Forgets the GPU textures of the glyph atlases and the cached text models, because the GPU
+    /// context was lost. The glyph atlases are kept in CPU memory (by the `GlyphCache`), so they
+    /// will be uploaded again when they are needed.
+    #[cfg(feature = "golem_rendering")]
+    pub(super) fn forget_lost_gpu_resources(&self) {
+        let mut internal = self.internal.borrow_mut();
+        for entry in internal.fonts.values_mut() {
+            // The resources of a lost context shouldn't be destroyed, see TrackedGpuResource
+            for gpu_texture in entry.gpu_atlases.invalidate_gpu_textures() {
+                std::mem::forget(gpu_texture);
+            }
+            for (_text, text_model) in entry.string_models.drain() {
+                std::mem::forget(text_model.fragments);
+            }
+        }
+    }
+
+    /// Drops all cached text models and removes the least recently used glyph atlases from GPU
+    /// memory (only the most recently used atlas of each font remains when the *pressure* is
+    /// `Moderate`, and none remains when it is `Critical`). Returns the number of released text
+    /// models and atlases. The glyph atlases are kept in CPU memory (by the `GlyphCache`), so
+    /// everything will be created again when it is needed. See `Renderer.release_memory`.
+    pub fn release_memory(&self, pressure: MemoryPressure) -> usize {
+        let max_remaining_atlases = match pressure {
+            MemoryPressure::Moderate => 1,
+            MemoryPressure::Critical => 0,
+        };
+        let mut internal = self.internal.borrow_mut();
+        let mut num_released = 0;
+        for entry in internal.fonts.values_mut() {
+            num_released += entry.string_models.len();
+            entry.string_models.clear();
+            num_released += entry.gpu_atlases.evict_gpu_textures(max_remaining_atlases).len();
+        }
+        num_released
     }
 
     pub fn get_text_size(
@@ -66,12 +237,71 @@ impl TextRenderer {
             None => self.get_default_font()
         };
         let mut internal = self.internal.borrow_mut();
-        internal.get_text_size(text, font_handle, renderer)
+        let mut glyph_cache = self.glyph_cache.internal.borrow_mut();
+        internal.get_text_size(&mut glyph_cache, text, font_handle, renderer)
+    }
+
+    #[cfg(feature = "golem_rendering")]
+    pub(crate) fn get_default_shader_id() -> ShaderId {
+        ShaderId::from_strs("knukki", "DefaultTextShader")
+    }
+
+    #[rustfmt::skip]
+    #[cfg(feature = "golem_rendering")]
+    pub(crate) fn create_default_shader(
+        golem: &golem::Context
+    ) -> Result<golem::ShaderProgram, golem::GolemError> {
+        use golem::*;
+
+        let description = ShaderDescription {
+            vertex_input: &[
+                Attribute::new("position", AttributeType::Vector(Dimension::D2)),
+                Attribute::new("textureCoordinates", AttributeType::Vector(Dimension::D2)),
+            ],
+            fragment_input: &[
+                Attribute::new("passTextureCoordinates", AttributeType::Vector(Dimension::D2)),
+            ],
+            uniforms: &[
+                Uniform::new("offset", UniformType::Vector(NumberType::Float, Dimension::D2)),
+                Uniform::new("scale", UniformType::Vector(NumberType::Float, Dimension::D2)),
+                Uniform::new("backgroundColor", UniformType::Vector(NumberType::Float, Dimension::D3)),
+                Uniform::new("textColor", UniformType::Vector(NumberType::Float, Dimension::D3)),
+                Uniform::new("image", UniformType::Sampler2D),
+            ],
+            vertex_shader: "
+            void main() {
+                gl_Position = vec4(offset + scale * position, 0.0, 1.0);
+                passTextureCoordinates = textureCoordinates;
+            }",
+            fragment_shader: "
+            void main() {
+                float intensity = texture(image, passTextureCoordinates).r;
+                vec3 color3d = intensity * textColor + (1.0 - intensity) * backgroundColor;
+                gl_FragColor = vec4(color3d, 1.0);
+            }",
+        };
+
+        ShaderProgram::new(golem, description)
+    }
+}
+
+// The GPU resources that a TextRenderer needs for 1 font
+struct FontEntry {
+    #[allow(dead_code)] // This field is only used when golem rendering is enabled
+    gpu_atlases: GpuAtlasMirror<GpuTexture>,
+    string_models: HashMap<String, TextModel>,
+}
+
+impl FontEntry {
+    fn new(cached_font: &CachedFont) -> Self {
+        Self {
+            gpu_atlases: GpuAtlasMirror::new(cached_font.atlas_group.get_max_num_gpu_atlases()),
+            string_models: HashMap::new(),
+        }
     }
 }
 
 struct InternalTextRenderer {
-    font_id_mapping: HashMap<String, FontHandle>,
     fonts: HashMap<FontHandle, FontEntry>,
     #[cfg(feature = "golem_rendering")]
     texture_unit: std::num::NonZeroU32
@@ -80,35 +310,38 @@ struct InternalTextRenderer {
 impl InternalTextRenderer {
     pub fn new() -> Self {
         Self {
-            font_id_mapping: HashMap::new(),
             fonts: HashMap::new(),
             #[cfg(feature = "golem_rendering")]
             texture_unit: std::num::NonZeroU32::new(1).unwrap()
         }
     }
 
-    pub fn register_font(&mut self, font_id: &str, font: Box<dyn Font>) -> FontHandle {
-
-        let handle = FontHandle { internal: self.fonts.len() as u16 };
-
-        let atlas_group = TextureAtlasGroup::new(
-            1024, 1024, 100, 10, 1, 1
-        );
-
-        let char_textures = HashMap::new();
-        let string_models = HashMap::new();
-
-        self.font_id_mapping.insert(font_id.to_string(), handle);
-        self.fonts.insert(handle, FontEntry { font, atlas_group, char_textures, string_models });
-        handle
-    }
-
-    pub fn get_font(&self, font_id: &str) -> Option<FontHandle> {
-        self.font_id_mapping.get(font_id).map(|handle_ref| *handle_ref)
+    #[allow(unused_variables)] // The renderer is only used when golem_rendering is enabled
+    fn ensure_text_model(
+        &mut self,
+        glyph_cache: &mut InternalGlyphCache,
+        text: &str,
+        font_handle: FontHandle,
+        renderer: &Renderer,
+    ) -> Result<(), TextRenderError> {
+        let cached_font = glyph_cache.fonts.get_mut(&font_handle).expect("Font handle is valid");
+        let entry = self.fonts.entry(font_handle).or_insert_with(|| FontEntry::new(cached_font));
+        if !entry.string_models.contains_key(text) {
+            let text_model = Self::create_text_model(
+                #[cfg(feature = "golem_rendering")]
+                renderer.get_context(),
+                cached_font,
+                text
+            )?;
+            entry.string_models.insert(text.to_string(), text_model);
+        }
+        Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_text(
         &mut self,
+        glyph_cache: &mut InternalGlyphCache,
         text: &str,
         style: &TextStyle,
         font_handle: FontHandle,
@@ -116,53 +349,31 @@ impl InternalTextRenderer {
         renderer: &Renderer,
         before_draw: BeforeDraw,
     ) -> Result<DrawnTextPosition, TextRenderError> {
-        if !self.fonts[&font_handle].string_models.contains_key(text) {
-            let text_model = self.create_text_model(
-                #[cfg(feature = "golem_rendering")]
-                renderer.get_context(),
-                font_handle,
-                text
-            )?;
-            self.fonts.get_mut(&font_handle).expect("Font handle is valid").string_models.insert(text.to_string(), text_model);
-        }
-
-        self.draw_text_model(text, style, font_handle, position, renderer, before_draw)
+        self.ensure_text_model(glyph_cache, text, font_handle, renderer)?;
+        self.draw_text_model(glyph_cache, text, style, font_handle, position, renderer, before_draw)
     }
 
     pub fn get_text_size(
         &mut self,
+        glyph_cache: &mut InternalGlyphCache,
         text: &str,
         font_handle: FontHandle,
         renderer: &Renderer,
     ) -> Result<(u32, u32), TextRenderError> {
-        if !self.fonts[&font_handle].string_models.contains_key(text) {
-            let text_model = self.create_text_model(
-                #[cfg(feature = "golem_rendering")]
-                    renderer.get_context(),
-                font_handle,
-                text
-            )?;
-            self.fonts.get_mut(&font_handle).expect("Font handle is valid").string_models.insert(text.to_string(), text_model);
-        }
+        self.ensure_text_model(glyph_cache, text, font_handle, renderer)?;
 
         let text_model = &self.fonts[&font_handle].string_models[text];
         Ok((text_model.width, text_model.height))
     }
 
-    // This seems to be a reasonable value. Perhaps, I could improve it later
-    const POINT_SIZE: f32 = 100.0;
-
     fn create_text_model(
-        &mut self,
         #[cfg(feature = "golem_rendering")]
         ctx: &golem::Context,
-        font: FontHandle,
+        entry: &mut CachedFont,
         text: &str
     ) -> Result<TextModel, TextRenderError> {
 
-        let entry = self.fonts.get_mut(&font).expect("font handle is invalid");
-
-        let point_size = Self::POINT_SIZE;
+        let point_size = POINT_SIZE;
 
         #[derive(Copy, Clone, Debug)]
         struct GraphemePosition {
@@ -183,31 +394,7 @@ impl InternalTextRenderer {
             let font = &entry.font;
             let atlas_group = &mut entry.atlas_group;
             let maybe_grapheme_texture_id = entry.char_textures.entry(grapheme.to_string()).or_insert_with(
-                || {
-                    let raw_grapheme_texture = font.draw_grapheme(grapheme, point_size);
-                    if let Some(grapheme_texture) = raw_grapheme_texture {
-
-                        let grapheme_texture_width = grapheme_texture.texture.get_width();
-                        let grapheme_texture_height = grapheme_texture.texture.get_height();
-
-                        let maybe_texture_id = atlas_group.add_texture(grapheme_texture.texture);
-                        if let Ok(texture_id) = maybe_texture_id {
-                            Some(GroupGraphemeTexture {
-                                texture_id,
-                                offset_y: grapheme_texture.offset_y,
-                                width: grapheme_texture_width,
-                                height: grapheme_texture_height,
-                            })
-                        } else {
-                            // Edge case: very big character
-                            None
-                        }
-                    } else {
-
-                        // This is in case of a whitespace
-                        None
-                    }
-                }
+                || add_grapheme_texture(font.as_ref(), atlas_group, grapheme)
             );
 
             if let Some(group_grapheme_texture) = maybe_grapheme_texture_id {
@@ -274,59 +461,31 @@ impl InternalTextRenderer {
         })
     }
 
-    #[rustfmt::skip]
-    #[cfg(feature = "golem_rendering")]
-    fn create_default_shader(golem: &golem::Context) -> Result<golem::ShaderProgram, golem::GolemError> {
-        use golem::*;
-
-        let description = ShaderDescription {
-            vertex_input: &[
-                Attribute::new("position", AttributeType::Vector(Dimension::D2)),
-                Attribute::new("textureCoordinates", AttributeType::Vector(Dimension::D2)),
-            ],
-            fragment_input: &[
-                Attribute::new("passTextureCoordinates", AttributeType::Vector(Dimension::D2)),
-            ],
-            uniforms: &[
-                Uniform::new("offset", UniformType::Vector(NumberType::Float, Dimension::D2)),
-                Uniform::new("scale", UniformType::Vector(NumberType::Float, Dimension::D2)),
-                Uniform::new("backgroundColor", UniformType::Vector(NumberType::Float, Dimension::D3)),
-                Uniform::new("textColor", UniformType::Vector(NumberType::Float, Dimension::D3)),
-                Uniform::new("image", UniformType::Sampler2D),
-            ],
-            vertex_shader: "
-            void main() {
-                gl_Position = vec4(offset + scale * position, 0.0, 1.0);
-                passTextureCoordinates = textureCoordinates;
-            }",
-            fragment_shader: "
-            void main() {
-                float intensity = texture(image, passTextureCoordinates).r;
-                vec3 color3d = intensity * textColor + (1.0 - intensity) * backgroundColor;
-                gl_FragColor = vec4(color3d, 1.0);
-            }",
-        };
-
-        ShaderProgram::new(golem, description)
-    }
-
+    // The glyph cache is only used when golem_rendering is enabled
+    #[allow(clippy::too_many_arguments, unused_variables)]
     fn draw_text_model(
-        &mut self, text: &str, style: &TextStyle, font_handle: FontHandle,
-        position: TextDrawPosition, renderer: &Renderer, before_draw: BeforeDraw,
+        &mut self, glyph_cache: &InternalGlyphCache, text: &str, style: &TextStyle,
+        font_handle: FontHandle, position: TextDrawPosition, renderer: &Renderer,
+        before_draw: BeforeDraw,
     ) -> Result<DrawnTextPosition, TextRenderError> {
         let model = &self.fonts[&font_handle].string_models[text];
         debug_assert!(model.is_still_valid());
 
-        let text_position = compute_text_position(
+        let mut text_position = compute_text_position(
             model.width as f32, model.height as f32,
             position, renderer.get_viewport()
         );
+        if renderer.is_pixel_snapping_enabled() {
+            text_position = snap_text_position(text_position, renderer.get_viewport());
+        }
 
         let drawn_position = text_position.1;
         if style.background_fill_mode == TextBackgroundFillMode::DrawnRegion {
-            renderer.push_scissor(drawn_position.min_x, drawn_position.min_y, drawn_position.max_x, drawn_position.max_y, || {
-                renderer.clear(style.background_color);
-            });
+            renderer.push_conservative_scissor(
+                drawn_position.min_x, drawn_position.min_y,
+                drawn_position.max_x, drawn_position.max_y,
+                || renderer.clear(style.background_color)
+            );
         }
         if style.background_fill_mode == TextBackgroundFillMode::EntireDomain {
             renderer.push_scissor(position.min_x, position.min_y, position.max_x, position.max_y, || {
@@ -345,17 +504,28 @@ impl InternalTextRenderer {
                 let texture_unit = self.texture_unit;
                 let my_fonts = &mut self.fonts;
                 let font_entry = my_fonts.get_mut(&font_handle).expect("Valid model font handle");
-                let atlas_group = &mut font_entry.atlas_group;
+                let gpu_atlases = &mut font_entry.gpu_atlases;
+                let atlas_group = &glyph_cache.fonts[&font_handle].atlas_group;
                 let uniform_position = text_position.0;
                 let model = &font_entry.string_models[text];
 
-                let shader_id = ShaderId::from_strs("knukki", "DefaultTextShader");
-                renderer.use_cached_shader(&shader_id, Self::create_default_shader, |shader| {
+                // Convert the position to the viewport of the render target
+                let [target_x, target_y, target_scale_x, target_scale_y] =
+                    renderer.get_target_viewport().1;
+                let target_offset_x = target_scale_x * uniform_position.offset_x
+                    + 2.0 * target_x + target_scale_x - 1.0;
+                let target_offset_y = target_scale_y * uniform_position.offset_y
+                    + 2.0 * target_y + target_scale_y - 1.0;
+
+                let shader_id = TextRenderer::get_default_shader_id();
+                let create_shader = TextRenderer::create_default_shader;
+                renderer.use_cached_shader(&shader_id, create_shader, |shader| {
                     shader.set_uniform("offset", UniformValue::Vector2([
-                        uniform_position.offset_x, uniform_position.offset_y
+                        target_offset_x, target_offset_y
                     ]))?;
                     shader.set_uniform("scale", UniformValue::Vector2([
-                        uniform_position.scale_x, uniform_position.scale_y
+                        target_scale_x * uniform_position.scale_x,
+                        target_scale_y * uniform_position.scale_y
                     ]))?;
                     shader.set_uniform("backgroundColor", UniformValue::Vector3([
                         style.background_color.get_red_float(),
@@ -370,17 +540,19 @@ impl InternalTextRenderer {
                     shader.set_uniform("image", UniformValue::Int(texture_unit.get() as i32))?;
 
                     for fragment in &model.fragments {
-                        let gpu_texture = atlas_group.get_gpu_texture::<GolemError, _>(fragment.atlas_index, |texture| {
-                            let mut golem_texture = Texture::new(renderer.get_context())?;
-
-                            golem_texture.set_image(
-                                Some(&texture.create_pixel_buffer()),
-                                texture.get_width(),
-                                texture.get_height(),
-                                ColorFormat::RGBA
-                            );
-                            Ok(golem_texture)
-                        })?;
+                        let gpu_texture = gpu_atlases.get_gpu_texture::<_, GolemError, _>(
+                            atlas_group, fragment.atlas_index, |texture| {
+                                let mut golem_texture = Texture::new(renderer.get_context())?;
+
+                                golem_texture.set_image(
+                                    Some(&texture.create_pixel_buffer()),
+                                    texture.get_width(),
+                                    texture.get_height(),
+                                    ColorFormat::RGBA
+                                );
+                                Ok(golem_texture)
+                            }
+                        )?;
                         gpu_texture.set_active(texture_unit);
                         unsafe {
                             shader.draw(
@@ -477,6 +649,30 @@ fn compute_text_position(
     (uniform_position, drawn_position)
 }
 
+/// Moves the text position such that the bottom-left corner of the drawn text (and thus the
+/// baseline) is at a whole pixel of the *viewport*. The scale is not changed, since that would
+/// distort the glyphs.
+fn snap_text_position(
+    (uniform_position, drawn_position): (UniformTextDrawPosition, DrawnTextPosition),
+    viewport: RenderRegion
+) -> (UniformTextDrawPosition, DrawnTextPosition) {
+    let delta_x = viewport.snap_x(drawn_position.min_x) - drawn_position.min_x;
+    let delta_y = viewport.snap_y(drawn_position.min_y) - drawn_position.min_y;
+
+    let uniform_position = UniformTextDrawPosition {
+        offset_x: uniform_position.offset_x + 2.0 * delta_x,
+        offset_y: uniform_position.offset_y + 2.0 * delta_y,
+        ..uniform_position
+    };
+    let drawn_position = DrawnTextPosition {
+        min_x: drawn_position.min_x + delta_x,
+        min_y: drawn_position.min_y + delta_y,
+        max_x: drawn_position.max_x + delta_x,
+        max_y: drawn_position.max_y + delta_y,
+    };
+    (uniform_position, drawn_position)
+}
+
 #[derive(Debug)]
 struct TextQuad {
     min_x: f32,
@@ -626,24 +822,61 @@ pub struct FontHandle {
     internal: u16
 }
 
-struct GroupGraphemeTexture {
-    texture_id: GroupTextureID,
-    offset_y: u32,
-    width: u32,
-    height: u32,
+// This seems to be a reasonable value. Perhaps, I could improve it later
+pub(super) const POINT_SIZE: f32 = 100.0;
+
+pub(super) struct GroupGraphemeTexture {
+    pub(super) texture_id: GroupTextureID,
+    pub(super) offset_y: u32,
+    pub(super) width: u32,
+    pub(super) height: u32,
+}
+
+pub(super) fn create_font_atlas_group<G>() -> TextureAtlasGroup<G> {
+    TextureAtlasGroup::new(1024, 1024, 100, 10, 1, 1)
+}
+
+/// Draws the given grapheme with the given font and adds the result to the given atlas group.
+/// Returns `None` if the grapheme is a whitespace (or too big for the atlas group).
+pub(super) fn add_grapheme_texture<G>(
+    font: &dyn Font, atlas_group: &mut TextureAtlasGroup<G>, grapheme: &str
+) -> Option<GroupGraphemeTexture> {
+    let raw_grapheme_texture = font.draw_grapheme(grapheme, POINT_SIZE);
+    if let Some(grapheme_texture) = raw_grapheme_texture {
+
+        let grapheme_texture_width = grapheme_texture.texture.get_width();
+        let grapheme_texture_height = grapheme_texture.texture.get_height();
+
+        let maybe_texture_id = atlas_group.add_texture(grapheme_texture.texture);
+        if let Ok(texture_id) = maybe_texture_id {
+            Some(GroupGraphemeTexture {
+                texture_id,
+                offset_y: grapheme_texture.offset_y,
+                width: grapheme_texture_width,
+                height: grapheme_texture_height,
+            })
+        } else {
+            // Edge case: very big character
+            None
+        }
+    } else {
+
+        // This is in case of a whitespace
+        None
+    }
 }
 
 #[cfg(feature = "golem_rendering")]
-type GpuTexture = golem::Texture;
+pub(super) type GpuTexture = golem::Texture;
 
 #[cfg(not(feature = "golem_rendering"))]
-type GpuTexture = ();
+pub(super) type GpuTexture = ();
 
-struct FontEntry {
+// The CPU resources of a font, which are shared by all users of a GlyphCache
+struct CachedFont {
     font: Box<dyn Font>,
     char_textures: HashMap<String, Option<GroupGraphemeTexture>>,
-    atlas_group: TextureAtlasGroup<GpuTexture>,
-    string_models: HashMap<String, TextModel>,
+    atlas_group: TextureAtlasGroup<()>,
 }
 
 #[cfg(test)]
@@ -685,9 +918,9 @@ mod tests {
         let handle1 = text_renderer.get_font("test1").unwrap();
         let handle2 = text_renderer.get_font("test2").unwrap();
 
-        let internal_text_renderer = text_renderer.internal.borrow();
-        let entry1 = &internal_text_renderer.fonts[&handle1];
-        let entry2 = &internal_text_renderer.fonts[&handle2];
+        let glyph_cache = text_renderer.get_glyph_cache().internal.borrow();
+        let entry1 = &glyph_cache.fonts[&handle1];
+        let entry2 = &glyph_cache.fonts[&handle2];
         assert_eq!(1.23, entry1.font.get_max_ascent(0.0));
         assert_eq!(2.34, entry2.font.get_max_ascent(0.0));
     }
@@ -918,16 +1151,47 @@ mod tests {
         }, drawn_position);
     }
 
+    #[test]
+    fn test_snap_text_position() {
+        let viewport = RenderRegion::with_size(0, 0, 100, 50);
+        let (uniform_position, drawn_position) = snap_text_position((UniformTextDrawPosition {
+            offset_x: -0.494,
+            offset_y: -0.486,
+            scale_x: 0.1,
+            scale_y: 0.1,
+        }, DrawnTextPosition {
+            min_x: 0.253,
+            min_y: 0.257,
+            max_x: 0.5,
+            max_y: 0.5
+        }), viewport);
+
+        // The bottom-left corner should be moved to pixel (25, 13) without changing the size
+        assert_uniform_nearly_equal(UniformTextDrawPosition {
+            offset_x: -0.5,
+            offset_y: -0.48,
+            scale_x: 0.1,
+            scale_y: 0.1,
+        }, uniform_position);
+        assert_drawn_nearly_equal(DrawnTextPosition {
+            min_x: 0.25,
+            min_y: 0.26,
+            max_x: 0.497,
+            max_y: 0.503
+        }, drawn_position);
+    }
+
     #[test]
     #[cfg(not(feature = "golem_rendering"))]
     fn test_create_text_model_single_line() {
         let text_renderer = TextRenderer::new();
         let test_font_handle = text_renderer.register_font("test", Box::new(TestFont {}));
 
-        let mut actual_text_renderer = text_renderer.internal.borrow_mut();
-        let text_model = actual_text_renderer.create_text_model(test_font_handle, "a b ").unwrap();
+        let mut glyph_cache = text_renderer.get_glyph_cache().internal.borrow_mut();
+        let cached_font = glyph_cache.fonts.get_mut(&test_font_handle).unwrap();
+        let text_model = InternalTextRenderer::create_text_model(cached_font, "a b ").unwrap();
 
-        let point_size = InternalTextRenderer::POINT_SIZE;
+        let point_size = POINT_SIZE;
         assert_eq!((3.6 * point_size) as u32, text_model.width);
         assert_eq!((1.0 * point_size) as u32, text_model.height);
 
@@ -950,6 +1214,69 @@ mod tests {
         assert_eq!(0, text_model.fragments[0].atlas_index);
     }
 
+    #[test]
+    #[cfg(not(feature = "golem_rendering"))]
+    fn test_shared_glyph_cache() {
+        let text_renderer1 = TextRenderer::new();
+        let glyph_cache = text_renderer1.get_glyph_cache().clone();
+        let text_renderer2 = TextRenderer::with_glyph_cache(glyph_cache);
+        assert!(text_renderer1.get_glyph_cache().is_same_cache(text_renderer2.get_glyph_cache()));
+        assert!(!text_renderer1.get_glyph_cache().is_same_cache(&GlyphCache::new()));
+
+        // Fonts registered by 1 renderer should be available to the other renderer
+        let font_handle = text_renderer2.register_font("test", Box::new(TestFont {}));
+        assert_eq!(Some(font_handle), text_renderer1.get_font("test"));
+        assert_eq!(text_renderer1.get_default_font(), text_renderer2.get_default_font());
+
+        let style = TextStyle {
+            font_id: Some("test".to_string()),
+            ..TextStyle::from_palette(&Palette::light())
+        };
+        let renderer1 = test_renderer(RenderRegion::with_size(0, 0, 100, 50));
+        let renderer2 = test_renderer(RenderRegion::with_size(0, 0, 80, 60));
+        let size1 = text_renderer1.get_text_size("a b", &style, &renderer1).unwrap();
+        let size2 = text_renderer2.get_text_size("ba", &style, &renderer2).unwrap();
+        assert_eq!((280, 100), size1);
+        assert_eq!((200, 100), size2);
+
+        // The glyphs should only be stored once
+        let glyph_cache = text_renderer1.get_glyph_cache().internal.borrow();
+        let atlas_group = &glyph_cache.fonts[&font_handle].atlas_group;
+        assert_eq!(2, atlas_group.get_stats().num_textures);
+
+        // Both renderers should have their own text models, with the same placements
+        let internal1 = text_renderer1.internal.borrow();
+        let internal2 = text_renderer2.internal.borrow();
+        let quads1 = &internal1.fonts[&font_handle].string_models["a b"].quads;
+        let quads2 = &internal2.fonts[&font_handle].string_models["ba"].quads;
+        assert!(!internal1.fonts[&font_handle].string_models.contains_key("ba"));
+        assert_eq!(quads1[0].placement, quads2[1].placement);
+        assert_eq!(quads1[1].placement, quads2[0].placement);
+    }
+
+    #[test]
+    #[cfg(not(feature = "golem_rendering"))]
+    fn test_release_memory() {
+        let text_renderer = TextRenderer::new();
+        let font_handle = text_renderer.register_font("test", Box::new(TestFont {}));
+        let style = TextStyle {
+            font_id: Some("test".to_string()),
+            ..TextStyle::from_palette(&Palette::light())
+        };
+        let renderer = test_renderer(RenderRegion::with_size(0, 0, 100, 50));
+        text_renderer.get_text_size("a b", &style, &renderer).unwrap();
+        text_renderer.get_text_size("ba", &style, &renderer).unwrap();
+
+        assert_eq!(2, text_renderer.release_memory(MemoryPressure::Moderate));
+        assert_eq!(0, text_renderer.release_memory(MemoryPressure::Critical));
+        assert!(text_renderer.internal.borrow().fonts[&font_handle].string_models.is_empty());
+
+        // The text models should be created again when they are needed, but the glyphs are kept
+        assert_eq!((200, 100), text_renderer.get_text_size("ba", &style, &renderer).unwrap());
+        let glyph_cache = text_renderer.get_glyph_cache().internal.borrow();
+        assert_eq!(2, glyph_cache.fonts[&font_handle].atlas_group.get_stats().num_textures);
+    }
+
     struct TestFont {}
 
     impl Font for TestFont {
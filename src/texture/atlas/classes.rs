@@ -0,0 +1,218 @@
+use super::*;
+
+/// Represents the id/handle of a `Texture` within a `ClassedTextureAtlasGroup`. Instances of this
+/// struct can be obtained by using the `add_texture` method of a `ClassedTextureAtlasGroup`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ClassedTextureID {
+    class_index: u8,
+    id: GroupTextureID,
+}
+
+impl ClassedTextureID {
+    /// Gets the index of the size class to which the corresponding texture was assigned. This is
+    /// needed for the `get_gpu_texture` method of `ClassedTextureAtlasGroup`.
+    pub fn get_class_index(&self) -> u8 {
+        self.class_index
+    }
+
+    /// Gets the `GroupTextureID` of the corresponding texture within the `TextureAtlasGroup` of
+    /// its size class.
+    pub fn get_group_id(&self) -> GroupTextureID {
+        self.id
+    }
+}
+
+struct SizeClass<GpuTexture> {
+    max_texture_width: u32,
+    max_texture_height: u32,
+    group: TextureAtlasGroup<GpuTexture>,
+}
+
+/// Manages a separate `TextureAtlasGroup` for each *size class* of textures. Each texture will be
+/// assigned to the first size class that accepts its size, so the classes should be added from
+/// small to large.
+///
+/// ## Motivation
+/// When a few huge textures (for instance images) share a `TextureAtlasGroup` with thousands of
+/// small textures (for instance glyphs), the atlases of the group must be big enough for the huge
+/// textures, and the rows of the small textures will often end up next to a huge texture, which
+/// wastes a lot of space. Giving each size class its own atlas dimensions avoids this.
+///
+/// ## GPU slots
+/// It is recommended to give the groups of different size classes different GPU atlas slots, so
+/// that textures of different classes can be drawn during the same draw call.
+pub struct ClassedTextureAtlasGroup<GpuTexture> {
+    classes: Vec<SizeClass<GpuTexture>>,
+}
+
+impl<GpuTexture> ClassedTextureAtlasGroup<GpuTexture> {
+    /// Constructs a new `ClassedTextureAtlasGroup` without any size classes. Use `add_class` to add
+    /// them.
+    pub fn new() -> Self {
+        Self { classes: Vec::new() }
+    }
+
+    /// Adds a size class that will accept all textures whose width is at most `max_texture_width`
+    /// and whose height is at most `max_texture_height` (unless an earlier size class accepts
+    /// them). The textures of this class will be stored in the given `group`. Returns the index of
+    /// the new class.
+    ///
+    /// ### Panics
+    /// This will panic if the maximum texture size is larger than the atlas size of the group, or
+    /// if there are already 256 size classes.
+    pub fn add_class(
+        &mut self, max_texture_width: u32, max_texture_height: u32,
+        group: TextureAtlasGroup<GpuTexture>
+    ) -> u8 {
+        assert!(max_texture_width <= group.get_width());
+        assert!(max_texture_height <= group.get_height());
+        assert!(self.classes.len() <= u8::MAX as usize);
+
+        self.classes.push(SizeClass { max_texture_width, max_texture_height, group });
+        (self.classes.len() - 1) as u8
+    }
+
+    /// Gets the number of size classes that have been added
+    pub fn get_num_classes(&self) -> u8 {
+        self.classes.len() as u8
+    }
+
+    /// Gets a reference to the `TextureAtlasGroup` of the size class with the given index. This
+    /// can for instance be used to get its statistics or big textures.
+    pub fn get_class(&self, class_index: u8) -> &TextureAtlasGroup<GpuTexture> {
+        &self.classes[class_index as usize].group
+    }
+
+    /// Adds the given texture to the first size class that accepts its size, and returns its
+    /// `ClassedTextureID`. If none of the size classes accepts it, an error will be returned that
+    /// contains the atlas size of the last size class.
+    pub fn add_texture(
+        &mut self, texture: Texture
+    ) -> Result<ClassedTextureID, TextureTooBigForAtlas> {
+        let class_index = self.classes.iter().position(|class|
+            texture.get_width() <= class.max_texture_width
+                && texture.get_height() <= class.max_texture_height
+        );
+
+        match class_index {
+            Some(class_index) => {
+                let id = self.classes[class_index].group.add_texture(texture)?;
+                Ok(ClassedTextureID { class_index: class_index as u8, id })
+            }
+            None => {
+                let (atlas_width, atlas_height) = match self.classes.last() {
+                    Some(class) => (class.max_texture_width, class.max_texture_height),
+                    None => (0, 0),
+                };
+                Err(TextureTooBigForAtlas {
+                    texture_width: texture.get_width(),
+                    texture_height: texture.get_height(),
+                    atlas_width,
+                    atlas_height,
+                })
+            }
+        }
+    }
+
+    /// Gets a reference to the texture with the given *id*
+    pub fn get_texture(&self, id: ClassedTextureID) -> &Texture {
+        self.classes[id.class_index as usize].group.get_texture(id.id)
+    }
+
+    /// Places the given textures on the texture atlases of their size classes. The textures of
+    /// each size class will be placed in the same way as the `place_textures` method of
+    /// `TextureAtlasGroup`. The `cpu_atlas_index` of each placement is relative to the size class
+    /// of the corresponding texture. The placements will be returned in the same order as the
+    /// given `textures`.
+    pub fn place_textures(&mut self, textures: &[ClassedTextureID]) -> Vec<GroupTexturePlacement> {
+        let mut placements = vec![None; textures.len()];
+        for (class_index, class) in self.classes.iter_mut().enumerate() {
+            let indices: Vec<_> = (0 .. textures.len()).filter(
+                |index| textures[*index].class_index as usize == class_index
+            ).collect();
+            if indices.is_empty() {
+                continue;
+            }
+
+            let group_ids: Vec<_> = indices.iter().map(|index| textures[*index].id).collect();
+            let class_placements = class.group.place_textures(&group_ids);
+            for (index, placement) in indices.into_iter().zip(class_placements) {
+                placements[index] = Some(placement);
+            }
+        }
+
+        placements.into_iter().map(
+            |placement| placement.expect("All textures belong to a size class")
+        ).collect()
+    }
+
+    /// Ensures that the texture atlas with the given *atlas_index* of the given size class is
+    /// present on the GPU, and returns a GPU handle to it. See the `get_gpu_texture` method of
+    /// `TextureAtlasGroup`.
+    pub fn get_gpu_texture<GpuError, F: FnOnce(&Texture) -> Result<GpuTexture, GpuError>>(
+        &mut self, class_index: u8, atlas_index: u16, load_texture: F
+    ) -> Result<&GpuTexture, GpuError> {
+        self.classes[class_index as usize].group.get_gpu_texture(atlas_index, load_texture)
+    }
+
+    /// Removes all GPU textures from all size classes and returns them. See the
+    /// `invalidate_gpu_textures` method of `TextureAtlasGroup`.
+    pub fn invalidate_gpu_textures(&mut self) -> Vec<GpuTexture> {
+        self.classes
+            .iter_mut()
+            .flat_map(|class| class.group.invalidate_gpu_textures())
+            .collect()
+    }
+
+    /// Removes the least recently used GPU textures from each size class until at most
+    /// *max_remaining_per_class* texture atlases of each class are left in GPU memory, and returns
+    /// the removed textures. See the `evict_gpu_textures` method of `TextureAtlasGroup`.
+    pub fn evict_gpu_textures(&mut self, max_remaining_per_class: u16) -> Vec<GpuTexture> {
+        self.classes
+            .iter_mut()
+            .flat_map(|class| class.group.evict_gpu_textures(max_remaining_per_class))
+            .collect()
+    }
+}
+
+impl<GpuTexture> Default for ClassedTextureAtlasGroup<GpuTexture> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_size_classes() {
+        let mut group = ClassedTextureAtlasGroup::<()>::new();
+        assert_eq!(0, group.add_class(8, 8, TextureAtlasGroup::new(32, 32, 2, 1, 0, 0)));
+        assert_eq!(1, group.add_class(100, 80, TextureAtlasGroup::new(100, 80, 4, 2, 1, 2)));
+        assert_eq!(2, group.get_num_classes());
+
+        let glyph1 = group.add_texture(Texture::new(5, 8, Color::rgb(1, 0, 0))).unwrap();
+        let image = group.add_texture(Texture::new(60, 8, Color::rgb(2, 0, 0))).unwrap();
+        let glyph2 = group.add_texture(Texture::new(7, 3, Color::rgb(3, 0, 0))).unwrap();
+        assert_eq!(0, glyph1.get_class_index());
+        assert_eq!(1, image.get_class_index());
+        assert_eq!(0, glyph2.get_class_index());
+        assert_eq!(Color::rgb(2, 0, 0), group.get_texture(image)[0][0]);
+
+        let too_big = group.add_texture(Texture::new(101, 8, Color::rgb(0, 0, 0))).unwrap_err();
+        assert_eq!(100, too_big.atlas_width);
+
+        let placements = group.place_textures(&[glyph1, image, glyph2]);
+        assert_eq!(3, placements.len());
+        assert_eq!(60, placements[1].get_position().width);
+        assert_eq!(1, placements[1].get_gpu_atlas_slot());
+        assert_eq!(0, placements[2].get_gpu_atlas_slot());
+
+        // The small glyphs should not take space on the atlases for images, and vice versa
+        assert_eq!(2, group.get_class(0).get_stats().atlases[0].num_textures);
+        assert_eq!(1, group.get_class(1).get_stats().atlases[0].num_textures);
+        assert!(group.get_gpu_texture::<(), _>(1, 0, |_texture| Ok(())).is_ok());
+    }
+}
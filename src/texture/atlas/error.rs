@@ -29,3 +29,38 @@ impl Display for TextureTooBigForAtlas {
 }
 
 impl Error for TextureTooBigForAtlas {}
+
+/// This error is used to indicate that `TextureAtlasGroup::load_snapshot` was given bytes that are
+/// not a valid snapshot. This typically happens when the snapshot was truncated or corrupted while
+/// it was cached, or when it was created by an incompatible version of this crate.
+///
+/// Applications should handle this error by discarding the cached snapshot and rebuilding the
+/// texture atlases from scratch.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum InvalidAtlasSnapshot {
+    /// The bytes don't start with the magic header of atlas snapshots
+    NotASnapshot,
+    /// The snapshot was created with a newer (or unknown) version of the snapshot format
+    UnsupportedVersion(u8),
+    /// The bytes ended before the snapshot was complete
+    UnexpectedEnd,
+    /// The snapshot is complete, but its contents are inconsistent
+    Corrupted(&'static str),
+}
+
+impl Display for InvalidAtlasSnapshot {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::NotASnapshot => write!(formatter, "The bytes are not a texture atlas snapshot"),
+            Self::UnsupportedVersion(version) => write!(
+                formatter, "Unsupported texture atlas snapshot version {}", version
+            ),
+            Self::UnexpectedEnd => write!(formatter, "The texture atlas snapshot is truncated"),
+            Self::Corrupted(reason) => write!(
+                formatter, "The texture atlas snapshot is corrupted: {}", reason
+            ),
+        }
+    }
+}
+
+impl Error for InvalidAtlasSnapshot {}
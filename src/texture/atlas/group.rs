@@ -12,11 +12,14 @@ use std::collections::{
 };
 use std::rc::Rc;
 
+const SNAPSHOT_MAGIC: &[u8; 8] = b"KNUKKIAG";
+const SNAPSHOT_VERSION: u8 = 1;
+
 /// Represents the id/handle of a `Texture` within a `TextureAtlasGroup`. Instances of this struct
 /// can be obtained by using the `add_texture` method of a `TextureAtlasGroup`.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 pub struct GroupTextureID {
-    internal: u64,
+    pub(super) internal: u64,
 }
 
 /// Represents the placement of a `Texture` onto a `TextureAtlas` of a `TextureAtlasGroup`. See the
@@ -78,6 +81,8 @@ struct TextureEntry {
 struct AtlasEntry<GpuTexture> {
     atlas: TextureAtlas,
     gpu_texture: Option<(GpuTexture, u64)>,
+    // This is incremented whenever the atlas is modified, see get_atlas_version
+    version: u64,
 }
 
 /// Represents a group of texture atlases of limited size that work together to give the illusion of
@@ -102,6 +107,8 @@ pub struct TextureAtlasGroup<GpuTexture> {
 
     // This variable is used to keep track of which gpu atlas texture are recently used
     current_time: u64,
+
+    num_gpu_evictions: u64,
 }
 
 impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
@@ -177,7 +184,9 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
             atlases: Vec::new(),
 
             next_texture_id: 0,
-            current_time: 0
+            current_time: 0,
+
+            num_gpu_evictions: 0
         }
     }
 
@@ -216,6 +225,11 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
         todo!() // Also mark textures as removed, to improve debugging
     }
 
+    /// Checks whether this group has a texture with the given *id*
+    pub fn contains_texture(&self, id: GroupTextureID) -> bool {
+        self.textures.contains_key(&id)
+    }
+
     /// Gets a reference to the texture with the given *id*
     pub fn get_texture(&self, id: GroupTextureID) -> &Texture {
         &self.textures[&id].texture
@@ -227,6 +241,19 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
         self.atlases[index].atlas.get_texture()
     }
 
+    /// Gets the maximum number of texture atlases that this group will simultaneously keep in GPU
+    /// memory (the `max_num_gpu_atlases` parameter of the constructor)
+    pub fn get_max_num_gpu_atlases(&self) -> u16 {
+        self.max_num_gpu_atlases
+    }
+
+    /// Gets the version of the texture atlas with the given *atlas_index*, which is incremented
+    /// whenever textures are placed on that atlas. A GPU copy of the atlas that was made at an
+    /// older version is outdated. This is used by `GpuAtlasMirror`.
+    pub fn get_atlas_version(&self, atlas_index: u16) -> u64 {
+        self.atlases[atlas_index as usize].version
+    }
+
     /// Ensures that the texture atlas with the given *atlas_index* is present on the GPU and
     /// returns a GPU handle to it. If needed, this method will remove an existing texture atlas
     /// from GPU memory. If the texture atlas has been modified since the last render, it will be
@@ -259,6 +286,7 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
                 self.atlases[least_recently_used_index.expect(
                     "There were too many gpu atlas textures, so there must be at least 1"
                 )].gpu_texture = None;
+                self.num_gpu_evictions += 1;
             }
 
             self.atlases[atlas_index as usize].gpu_texture = Some((
@@ -270,6 +298,59 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
         Ok(&self.atlases[atlas_index as usize].gpu_texture.as_ref().unwrap().0)
     }
 
+    /// Removes all GPU textures from this group (without counting them as evictions) and returns
+    /// them. The texture atlases themselves are kept in CPU memory, so `get_gpu_texture` will
+    /// simply load them again when they are needed.
+    ///
+    /// This is needed when the GPU context was lost (for instance a lost WebGL context), since all
+    /// GPU textures are gone in that case. The caller should decide what to do with the returned
+    /// textures: they typically can't be destroyed normally because their context is gone.
+    pub fn invalidate_gpu_textures(&mut self) -> Vec<GpuTexture> {
+        self.atlases
+            .iter_mut()
+            .filter_map(|entry| entry.gpu_texture.take())
+            .map(|(gpu_texture, _last_used)| gpu_texture)
+            .collect()
+    }
+
+    /// Removes the least recently used GPU textures from this group until at most *max_remaining*
+    /// texture atlases are left in GPU memory, and returns the removed textures (these removals
+    /// count as evictions). Unlike `invalidate_gpu_textures`, the GPU context is still alive, so
+    /// the caller should destroy the returned textures normally.
+    ///
+    /// This is useful when the device is running low on memory (see `LowMemoryEvent`). The
+    /// texture atlases themselves are kept in CPU memory, so `get_gpu_texture` will simply load
+    /// them again when they are needed.
+    pub fn evict_gpu_textures(&mut self, max_remaining: u16) -> Vec<GpuTexture> {
+        let mut gpu_indices: Vec<usize> = (0 .. self.atlases.len()).filter(
+            |index| self.atlases[*index].gpu_texture.is_some()
+        ).collect();
+
+        // The most recently used atlases should remain on the GPU
+        gpu_indices.sort_by_key(|index| std::cmp::Reverse(
+            self.atlases[*index].gpu_texture.as_ref().unwrap().1
+        ));
+
+        let evicted: Vec<GpuTexture> = gpu_indices.into_iter().skip(max_remaining as usize).map(
+            |index| self.atlases[index].gpu_texture.take().unwrap().0
+        ).collect();
+        self.num_gpu_evictions += evicted.len() as u64;
+        evicted
+    }
+
+    /// Computes the `TextureAtlasGroupStats` of this group (including the `TextureAtlasStats` of
+    /// each of its texture atlases).
+    pub fn get_stats(&self) -> TextureAtlasGroupStats {
+        TextureAtlasGroupStats {
+            num_textures: self.textures.len() as u32,
+            num_gpu_atlases: self.atlases.iter().filter(
+                |entry| entry.gpu_texture.is_some()
+            ).count() as u16,
+            num_gpu_evictions: self.num_gpu_evictions,
+            atlases: self.atlases.iter().map(|entry| entry.atlas.get_stats()).collect(),
+        }
+    }
+
     fn rate_texture_atlases(&mut self, texture_set: &HashSet<GroupTextureID>) -> Vec<ExistingAtlasRating> {
         let mut existing_ratings = Vec::with_capacity(self.atlases.len());
         for atlas_index in 0 .. self.atlases.len() {
@@ -374,6 +455,7 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
 
             // We need to invalidate the gpu textures of all atlases we modify
             self.atlases[*dest_atlas_index].gpu_texture = None;
+            self.atlases[*dest_atlas_index].version += 1;
 
             let own_textures = &self.textures;
 
@@ -449,7 +531,8 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
             self.atlases.push(AtlasEntry {
                 atlas: next_atlas,
                 // Assigning GPU textures will be postponed until drawing
-                gpu_texture: None
+                gpu_texture: None,
+                version: 0
             });
         }
 
@@ -505,6 +588,159 @@ impl<GpuTexture> TextureAtlasGroup<GpuTexture> {
 
         textures.iter().map(|texture_id| placement_map[texture_id].clone()).collect()
     }
+
+    /// Serializes the CPU state of this group (the textures, the pixels of the texture atlases,
+    /// and the placements of the textures on the atlases) to bytes. This can be used to cache the
+    /// (font or icon) atlases that are built during the first launch of an application (on disk
+    /// or in IndexedDB), and restore them instantly with `load_snapshot` during later launches.
+    ///
+    /// The GPU textures are *not* part of the snapshot: they will simply be recreated when they are
+    /// needed. Placements that are no longer valid are not saved either. The snapshot is
+    /// uncompressed, so applications may want to compress it before storing it.
+    pub fn save_snapshot(&self) -> Vec<u8> {
+        let mut writer = SnapshotWriter::new();
+        writer.write_bytes(SNAPSHOT_MAGIC);
+        writer.write_u8(SNAPSHOT_VERSION);
+
+        writer.write_u32(self.atlas_width);
+        writer.write_u32(self.atlas_height);
+        writer.write_u16(self.max_num_cpu_atlases);
+        writer.write_u16(self.max_num_gpu_atlases);
+        writer.write_u8(self.min_gpu_atlas_slot);
+        writer.write_u8(self.max_gpu_atlas_slot);
+        writer.write_u64(self.next_texture_id);
+
+        writer.write_u16(self.atlases.len() as u16);
+        for entry in &self.atlases {
+            entry.atlas.write_snapshot(&mut writer);
+        }
+
+        // Sort the textures by id to make the snapshot deterministic
+        let mut texture_ids: Vec<_> = self.textures.keys().collect();
+        texture_ids.sort_by_key(|id| id.internal);
+
+        writer.write_u32(texture_ids.len() as u32);
+        for id in texture_ids {
+            let entry = &self.textures[id];
+            writer.write_texture_id(*id);
+            writer.write_texture(&entry.texture);
+
+            let valid_placements: Vec<_> = entry.placements.iter().filter(
+                |placement| placement.is_still_valid()
+            ).collect();
+            writer.write_u32(valid_placements.len() as u32);
+            for placement in valid_placements {
+                writer.write_u16(placement.cpu_atlas_index);
+                writer.write_u8(placement.gpu_atlas_slot);
+                writer.write_position(placement.position);
+            }
+        }
+
+        writer.into_bytes()
+    }
+
+    /// Restores a `TextureAtlasGroup` from a snapshot that was created by `save_snapshot`. The
+    /// restored group will have the same configuration, textures, texture atlases and placements
+    /// as the group that created the snapshot, and the `GroupTextureID`s of that group can still
+    /// be used. None of the texture atlases will be on the GPU yet.
+    ///
+    /// Note that the `GroupTexturePlacement`s returned by the original group are *not* linked to
+    /// the restored group. Use `place_textures` to get the placements from the restored group
+    /// (this is cheap because the textures are already placed).
+    ///
+    /// This returns an error if the bytes are not a valid snapshot, or were created by an
+    /// incompatible version of this crate.
+    pub fn load_snapshot(bytes: &[u8]) -> Result<Self, InvalidAtlasSnapshot> {
+        let mut reader = SnapshotReader::new(bytes);
+        let magic = reader.read_bytes(SNAPSHOT_MAGIC.len());
+        if magic != Ok(&SNAPSHOT_MAGIC[..]) {
+            return Err(InvalidAtlasSnapshot::NotASnapshot);
+        }
+        let version = reader.read_u8()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(InvalidAtlasSnapshot::UnsupportedVersion(version));
+        }
+
+        let atlas_width = reader.read_u32()?;
+        let atlas_height = reader.read_u32()?;
+        let max_num_cpu_atlases = reader.read_u16()?;
+        let max_num_gpu_atlases = reader.read_u16()?;
+        let min_gpu_atlas_slot = reader.read_u8()?;
+        let max_gpu_atlas_slot = reader.read_u8()?;
+        let next_texture_id = reader.read_u64()?;
+
+        // Check the same conditions as the constructor, but without panicking
+        if atlas_width == 0 || atlas_height == 0 || max_num_cpu_atlases == 0
+            || max_num_cpu_atlases < max_num_gpu_atlases
+            || max_gpu_atlas_slot < min_gpu_atlas_slot
+            || max_num_gpu_atlases < 1 + (max_gpu_atlas_slot - min_gpu_atlas_slot) as u16 {
+            return Err(InvalidAtlasSnapshot::Corrupted("invalid group configuration"));
+        }
+        let mut group = Self {
+            atlas_width,
+            atlas_height,
+
+            max_num_cpu_atlases,
+            max_num_gpu_atlases,
+            min_gpu_atlas_slot,
+            max_gpu_atlas_slot,
+
+            textures: HashMap::new(),
+            atlases: Vec::new(),
+
+            next_texture_id,
+            current_time: 0,
+
+            num_gpu_evictions: 0
+        };
+
+        let num_atlases = reader.read_u16()?;
+        if num_atlases > max_num_cpu_atlases {
+            return Err(InvalidAtlasSnapshot::Corrupted("too many atlases"));
+        }
+        for _ in 0 .. num_atlases {
+            group.atlases.push(AtlasEntry {
+                atlas: TextureAtlas::read_snapshot(&mut reader, atlas_width, atlas_height)?,
+                gpu_texture: None,
+                version: 0
+            });
+        }
+
+        let num_textures = reader.read_u32()?;
+        for _ in 0 .. num_textures {
+            let id = reader.read_texture_id()?;
+            if id.internal >= next_texture_id || group.textures.contains_key(&id) {
+                return Err(InvalidAtlasSnapshot::Corrupted("invalid texture id"));
+            }
+            let texture = reader.read_texture(atlas_width, atlas_height)?;
+
+            let num_placements = reader.read_u32()?;
+            let mut placements = Vec::new();
+            for _ in 0 .. num_placements {
+                let cpu_atlas_index = reader.read_u16()?;
+                if cpu_atlas_index >= num_atlases {
+                    return Err(InvalidAtlasSnapshot::Corrupted("invalid atlas index"));
+                }
+                let gpu_atlas_slot = reader.read_u8()?;
+                if gpu_atlas_slot < min_gpu_atlas_slot || gpu_atlas_slot > max_gpu_atlas_slot {
+                    return Err(InvalidAtlasSnapshot::Corrupted("invalid gpu atlas slot"));
+                }
+                placements.push(GroupTexturePlacement {
+                    cpu_atlas_index,
+                    gpu_atlas_slot,
+                    position: reader.read_position(atlas_width, atlas_height)?,
+                    still_valid: Rc::new(Cell::new(true))
+                });
+            }
+
+            group.textures.insert(id, TextureEntry { texture, placements });
+        }
+
+        if !reader.is_at_end() {
+            return Err(InvalidAtlasSnapshot::Corrupted("unexpected bytes after the snapshot"));
+        }
+        Ok(group)
+    }
 }
 
 // This is just a helper struct for determining which texture atlas(es) to use
@@ -578,7 +814,8 @@ mod tests {
         // a clear test case.
         group.atlases.push(AtlasEntry {
             atlas: TextureAtlas::new(atlas_width, atlas_height),
-            gpu_texture: None
+            gpu_texture: None,
+            version: 0
         });
 
         // Preparation: put texture2 on atlas 2
@@ -593,7 +830,8 @@ mod tests {
         assert_eq!(Some(position2), place2.placements[0].get_position());
         group.atlases.push(AtlasEntry {
             atlas: atlas2,
-            gpu_texture: None
+            gpu_texture: None,
+            version: 0
         });
         let gpu_slot_1 = group.gpu_atlas_slot_for(1);
         group.textures.get_mut(&id2).unwrap().placements.push(GroupTexturePlacement {
@@ -615,7 +853,8 @@ mod tests {
         assert_eq!(Some(position3), place3.placements[0].get_position());
         group.atlases.push(AtlasEntry {
             atlas: atlas3,
-            gpu_texture: None
+            gpu_texture: None,
+            version: 0
         });
         let gpu_atlas_slot2 = group.gpu_atlas_slot_for(2);
         group.textures.get_mut(&id4).unwrap().placements.push(GroupTexturePlacement {
@@ -693,11 +932,13 @@ mod tests {
         // Let's prepare some fake data for the test
         group.atlases.push(AtlasEntry {
             atlas: TextureAtlas::new(atlas_width, atlas_height),
-            gpu_texture: None
+            gpu_texture: None,
+            version: 0
         });
         group.atlases.push(AtlasEntry {
             atlas: TextureAtlas::new(atlas_width, atlas_height),
-            gpu_texture: None
+            gpu_texture: None,
+            version: 0
         });
 
         let ratings1 = vec![
@@ -751,7 +992,8 @@ mod tests {
         // Let's prepare some fake data for the test
         group.atlases.push(AtlasEntry {
             atlas: TextureAtlas::new(atlas_width, atlas_height),
-            gpu_texture: None
+            gpu_texture: None,
+            version: 0
         });
 
         let ratings = vec![ExistingAtlasRating {
@@ -885,7 +1127,8 @@ mod tests {
         for _ in 0 .. 3 {
             group.atlases.push(AtlasEntry {
                 atlas: TextureAtlas::new(atlas_width, atlas_height),
-                gpu_texture: None
+                gpu_texture: None,
+                version: 0
             });
         }
 
@@ -1266,4 +1509,128 @@ mod tests {
         assert_eq!(3, group.atlases[1].gpu_texture.unwrap().0);
         assert_eq!(2, group.atlases[2].gpu_texture.unwrap().0);
     }
+
+    #[test]
+    fn test_invalidate_gpu_textures() {
+        let test_color = Color::rgb(0, 0, 0);
+        let mut group = super::TextureAtlasGroup::new(10, 10, 10, 2, 1, 2);
+
+        let id1 = group.add_texture(Texture::new(10, 10, test_color)).unwrap();
+        let id2 = group.add_texture(Texture::new(10, 10, test_color)).unwrap();
+        group.place_textures(&[id1, id2]);
+
+        group.get_gpu_texture::<(), _>(0, |_texture| Ok(0)).unwrap();
+        group.get_gpu_texture::<(), _>(1, |_texture| Ok(1)).unwrap();
+        assert_eq!(2, group.get_stats().num_gpu_atlases);
+
+        let mut invalidated = group.invalidate_gpu_textures();
+        invalidated.sort();
+        assert_eq!(vec![0, 1], invalidated);
+        assert_eq!(0, group.get_stats().num_gpu_atlases);
+        assert_eq!(0, group.get_stats().num_gpu_evictions);
+
+        // The atlases should be loaded again from their CPU copies
+        assert_eq!(2, *group.get_gpu_texture::<(), _>(0, |_texture| Ok(2)).unwrap());
+        assert_eq!(vec![2], group.invalidate_gpu_textures());
+    }
+
+    #[test]
+    fn test_evict_gpu_textures() {
+        let test_color = Color::rgb(0, 0, 0);
+        let mut group = super::TextureAtlasGroup::new(10, 10, 10, 3, 1, 3);
+
+        let ids: Vec<_> = (0 .. 3).map(
+            |_| group.add_texture(Texture::new(10, 10, test_color)).unwrap()
+        ).collect();
+        group.place_textures(&ids);
+
+        group.get_gpu_texture::<(), _>(1, |_texture| Ok(1)).unwrap();
+        group.get_gpu_texture::<(), _>(0, |_texture| Ok(0)).unwrap();
+        group.get_gpu_texture::<(), _>(2, |_texture| Ok(2)).unwrap();
+
+        // The least recently used atlases should be evicted first
+        assert_eq!(vec![0, 1], group.evict_gpu_textures(1));
+        assert_eq!(1, group.get_stats().num_gpu_atlases);
+        assert_eq!(2, group.get_stats().num_gpu_evictions);
+        assert!(group.evict_gpu_textures(1).is_empty());
+
+        assert_eq!(vec![2], group.evict_gpu_textures(0));
+        assert_eq!(0, group.get_stats().num_gpu_atlases);
+        assert_eq!(3, group.get_stats().num_gpu_evictions);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut group = TextureAtlasGroup::new(10, 10, 3, 2, 1, 2);
+        let id1 = group.add_texture(Texture::new(6, 5, Color::rgb(200, 0, 0))).unwrap();
+        let id2 = group.add_texture(Texture::new(7, 4, Color::rgba(0, 100, 0, 50))).unwrap();
+        let id3 = group.add_texture(Texture::new(2, 2, Color::rgb(0, 0, 150))).unwrap();
+        let original_placements = group.place_textures(&[id1, id2]);
+        group.get_gpu_texture::<(), _>(0, |_texture| Ok(())).unwrap();
+
+        let snapshot = group.save_snapshot();
+        let mut restored = TextureAtlasGroup::load_snapshot(&snapshot).unwrap();
+
+        // The snapshot should be deterministic, and the gpu textures should not be restored
+        assert_eq!(snapshot, restored.save_snapshot());
+        assert_eq!(group.atlases.len(), restored.atlases.len());
+        for index in 0 .. group.atlases.len() {
+            assert!(restored.atlases[index].gpu_texture.is_none());
+            assert_eq!(
+                group.get_big_texture(index).create_pixel_buffer(),
+                restored.get_big_texture(index).create_pixel_buffer()
+            );
+        }
+        for id in &[id1, id2, id3] {
+            assert_eq!(
+                group.get_texture(*id).create_pixel_buffer(),
+                restored.get_texture(*id).create_pixel_buffer()
+            );
+        }
+
+        // Placing the same textures again shouldn't need new atlases
+        let restored_placements = restored.place_textures(&[id1, id2]);
+        assert_eq!(group.atlases.len(), restored.atlases.len());
+        for (original, restored) in original_placements.iter().zip(restored_placements.iter()) {
+            assert_eq!(original.get_cpu_atlas_index(), restored.get_cpu_atlas_index());
+            assert_eq!(original.get_gpu_atlas_slot(), restored.get_gpu_atlas_slot());
+            assert_eq!(original.get_position(), restored.get_position());
+        }
+
+        // New textures should get new ids
+        let id4 = restored.add_texture(Texture::new(1, 1, Color::rgb(0, 0, 0))).unwrap();
+        assert!(![id1, id2, id3].contains(&id4));
+    }
+
+    #[test]
+    fn test_invalid_snapshots() {
+        let mut group = TextureAtlasGroup::new(4, 4, 1, 1, 0, 0);
+        let id = group.add_texture(Texture::new(2, 3, Color::rgb(1, 2, 3))).unwrap();
+        group.place_textures(&[id]);
+        let snapshot = group.save_snapshot();
+
+        assert_eq!(
+            Some(InvalidAtlasSnapshot::NotASnapshot),
+            TextureAtlasGroup::load_snapshot(&[1, 2, 3]).err()
+        );
+        let mut wrong_version = snapshot.clone();
+        wrong_version[8] = 100;
+        assert_eq!(
+            Some(InvalidAtlasSnapshot::UnsupportedVersion(100)),
+            TextureAtlasGroup::load_snapshot(&wrong_version).err()
+        );
+        assert_eq!(
+            Some(InvalidAtlasSnapshot::UnexpectedEnd),
+            TextureAtlasGroup::load_snapshot(&snapshot[0 .. snapshot.len() - 1]).err()
+        );
+
+        let mut too_long = snapshot.clone();
+        too_long.push(0);
+        assert!(TextureAtlasGroup::load_snapshot(&too_long).is_err());
+
+        // Any truncation should give an error rather than a panic
+        for length in 0 .. snapshot.len() {
+            assert!(TextureAtlasGroup::load_snapshot(&snapshot[0 .. length]).is_err());
+        }
+    }
 }
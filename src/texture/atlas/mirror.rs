@@ -0,0 +1,178 @@
+use super::*;
+
+use std::collections::HashMap;
+
+struct MirrorEntry<GpuTexture> {
+    gpu_texture: GpuTexture,
+    version: u64,
+    last_used: u64,
+}
+
+/// Keeps GPU copies of the texture atlases of a `TextureAtlasGroup` in 1 GPU context.
+///
+/// A `TextureAtlasGroup` can only keep GPU textures for a single GPU context. When multiple GPU
+/// contexts (for instance the contexts of multiple windows) need to draw textures of the same
+/// group, each context should use its own `GpuAtlasMirror` instead. Since all contexts use the
+/// same group, the textures only need to be placed (and stored in CPU memory) once, and all
+/// placements (including their GPU atlas slots) are valid in every context.
+///
+/// When one context places textures on an atlas that is already on the GPU of another context,
+/// the `get_atlas_version` of that atlas changes. The mirror of the other context will notice
+/// this during its next `get_gpu_texture` call, and upload the atlas again.
+pub struct GpuAtlasMirror<GpuTexture> {
+    max_num_gpu_atlases: u16,
+    entries: HashMap<u16, MirrorEntry<GpuTexture>>,
+
+    // This variable is used to keep track of which gpu atlas texture are recently used
+    current_time: u64,
+
+    num_gpu_evictions: u64,
+}
+
+impl<GpuTexture> GpuAtlasMirror<GpuTexture> {
+    /// Constructs a new `GpuAtlasMirror` that will simultaneously keep at most
+    /// `max_num_gpu_atlases` texture atlases in GPU memory. This is typically the
+    /// `get_max_num_gpu_atlases` of the mirrored group.
+    ///
+    /// ### Panics
+    /// This will panic if `max_num_gpu_atlases` is 0
+    pub fn new(max_num_gpu_atlases: u16) -> Self {
+        assert_ne!(0, max_num_gpu_atlases);
+        Self {
+            max_num_gpu_atlases,
+            entries: HashMap::new(),
+            current_time: 0,
+            num_gpu_evictions: 0,
+        }
+    }
+
+    /// Ensures that an up-to-date copy of the texture atlas with the given *atlas_index* of the
+    /// given *group* is present on the GPU and returns a GPU handle to it. This works like the
+    /// `get_gpu_texture` method of `TextureAtlasGroup`: if needed, this method will remove the
+    /// least recently used texture atlas from GPU memory. If the atlas has been modified since it
+    /// was uploaded, the outdated copy will be replaced.
+    ///
+    /// The *group* must be the same group during every call.
+    pub fn get_gpu_texture<
+        G, GpuError, F: FnOnce(&Texture) -> Result<GpuTexture, GpuError>
+    >(
+        &mut self, group: &TextureAtlasGroup<G>, atlas_index: u16, load_texture: F
+    ) -> Result<&GpuTexture, GpuError> {
+        self.current_time += 1;
+
+        let version = group.get_atlas_version(atlas_index);
+        let is_ready = match self.entries.get(&atlas_index) {
+            Some(entry) => entry.version == version,
+            None => false,
+        };
+
+        if !is_ready {
+            // Outdated copies are simply dropped, they don't count as evictions
+            self.entries.remove(&atlas_index);
+
+            if self.entries.len() >= self.max_num_gpu_atlases as usize {
+                let least_recently_used_index = *self.entries.iter().min_by_key(
+                    |(_, entry)| entry.last_used
+                ).expect("There were too many gpu atlas textures, so there must be at least 1").0;
+                self.entries.remove(&least_recently_used_index);
+                self.num_gpu_evictions += 1;
+            }
+
+            let gpu_texture = load_texture(group.get_big_texture(atlas_index as usize))?;
+            self.entries.insert(atlas_index, MirrorEntry {
+                gpu_texture,
+                version,
+                last_used: self.current_time,
+            });
+        }
+
+        let entry = self.entries.get_mut(&atlas_index).unwrap();
+        entry.last_used = self.current_time;
+        Ok(&entry.gpu_texture)
+    }
+
+    /// Removes all GPU textures from this mirror (without counting them as evictions) and returns
+    /// them. See the `invalidate_gpu_textures` method of `TextureAtlasGroup`.
+    pub fn invalidate_gpu_textures(&mut self) -> Vec<GpuTexture> {
+        self.entries.drain().map(|(_atlas_index, entry)| entry.gpu_texture).collect()
+    }
+
+    /// Removes the least recently used GPU textures from this mirror until at most *max_remaining*
+    /// texture atlases are left, and returns the removed textures. See the `evict_gpu_textures`
+    /// method of `TextureAtlasGroup`.
+    pub fn evict_gpu_textures(&mut self, max_remaining: u16) -> Vec<GpuTexture> {
+        let mut atlas_indices: Vec<u16> = self.entries.keys().copied().collect();
+        atlas_indices.sort_by_key(|index| std::cmp::Reverse(self.entries[index].last_used));
+
+        let evicted: Vec<GpuTexture> = atlas_indices.into_iter().skip(max_remaining as usize).map(
+            |index| self.entries.remove(&index).unwrap().gpu_texture
+        ).collect();
+        self.num_gpu_evictions += evicted.len() as u64;
+        evicted
+    }
+
+    /// Gets the number of texture atlases that this mirror currently keeps in GPU memory
+    pub fn get_num_gpu_atlases(&self) -> u16 {
+        self.entries.len() as u16
+    }
+
+    /// Gets the number of times a texture atlas was removed from GPU memory to make place for
+    /// another texture atlas
+    pub fn get_num_gpu_evictions(&self) -> u64 {
+        self.num_gpu_evictions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_mirrors_share_group() {
+        let mut group = TextureAtlasGroup::<()>::new(10, 10, 3, 2, 0, 1);
+        let small1 = group.add_texture(Texture::new(4, 4, Color::rgb(1, 0, 0))).unwrap();
+        let small2 = group.add_texture(Texture::new(4, 4, Color::rgb(2, 0, 0))).unwrap();
+        let big1 = group.add_texture(Texture::new(8, 8, Color::rgb(3, 0, 0))).unwrap();
+        let big2 = group.add_texture(Texture::new(8, 8, Color::rgb(4, 0, 0))).unwrap();
+        assert_eq!(0, group.place_textures(&[small1])[0].get_cpu_atlas_index());
+
+        let mut mirror1 = GpuAtlasMirror::new(group.get_max_num_gpu_atlases());
+        let mut mirror2 = GpuAtlasMirror::new(group.get_max_num_gpu_atlases());
+
+        // Both mirrors should upload the atlas
+        assert_eq!(1, *mirror1.get_gpu_texture::<_, (), _>(&group, 0, |_| Ok(1)).unwrap());
+        assert_eq!(2, *mirror2.get_gpu_texture::<_, (), _>(&group, 0, |_| Ok(2)).unwrap());
+        assert_eq!(1, *mirror1.get_gpu_texture::<_, (), _>(&group, 0, |_| Ok(3)).unwrap());
+
+        // When 1 context places another texture on the atlas, all copies are outdated
+        let version = group.get_atlas_version(0);
+        assert_eq!(0, group.place_textures(&[small2])[0].get_cpu_atlas_index());
+        assert_eq!(version + 1, group.get_atlas_version(0));
+        assert_eq!(4, *mirror1.get_gpu_texture::<_, (), _>(&group, 0, |_| Ok(4)).unwrap());
+        assert_eq!(5, *mirror2.get_gpu_texture::<_, (), _>(&group, 0, |_| Ok(5)).unwrap());
+        assert_eq!(0, mirror1.get_num_gpu_evictions());
+
+        // The mirrors should evict the least recently used atlas
+        let big_placements = group.place_textures(&[big1, big2]);
+        for placement in &big_placements {
+            let index = placement.get_cpu_atlas_index();
+            mirror1.get_gpu_texture::<_, (), _>(&group, index, |_| Ok(index.into())).unwrap();
+        }
+        assert_eq!(2, mirror1.get_num_gpu_atlases());
+        assert_eq!(1, mirror1.get_num_gpu_evictions());
+        assert_eq!(1, mirror2.get_num_gpu_atlases());
+
+        // When evicting explicitly, the most recently used atlases should remain
+        mirror1.get_gpu_texture::<_, (), _>(&group, 2, |_| Ok(2)).unwrap();
+        assert_eq!(vec![1], mirror1.evict_gpu_textures(1));
+        assert_eq!(2, mirror1.get_num_gpu_evictions());
+        mirror1.get_gpu_texture::<_, (), _>(&group, 1, |_| Ok(1)).unwrap();
+
+        let mut invalidated = mirror1.invalidate_gpu_textures();
+        invalidated.sort();
+        assert_eq!(vec![1, 2], invalidated);
+        assert_eq!(0, mirror1.get_num_gpu_atlases());
+        assert_eq!(2, mirror1.get_num_gpu_evictions());
+    }
+}
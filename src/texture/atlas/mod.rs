@@ -1,10 +1,23 @@
+mod classes;
 mod error;
 mod group;
+mod mirror;
 mod position;
+mod snapshot;
+mod stats;
+mod sync;
 
+pub use classes::*;
 pub use error::*;
 pub use group::*;
+pub use mirror::*;
 pub use position::*;
+pub use stats::*;
+pub use sync::*;
+pub(crate) use snapshot::{
+    SnapshotReader,
+    SnapshotWriter,
+};
 
 use crate::*;
 
@@ -34,6 +47,8 @@ pub struct TextureAtlas {
 
     placements: Vec<Rc<PlacedTexture>>,
     rows_info: RowsInfo,
+
+    num_evicted_textures: u64,
 }
 
 impl TextureAtlas {
@@ -46,6 +61,8 @@ impl TextureAtlas {
 
             placements: Vec::new(),
             rows_info: RowsInfo::new(width, height),
+
+            num_evicted_textures: 0,
         }
     }
 
@@ -149,9 +166,15 @@ impl TextureAtlas {
             }
         }
 
+        // This will stay 0 until the mechanism to remove old textures is created
+        let num_replaced_textures = 0;
+        if !test {
+            self.num_evicted_textures += num_replaced_textures as u64;
+        }
+
         TexturePlaceResult {
             placements: resulting_placements,
-            num_replaced_textures: 0,
+            num_replaced_textures,
         }
     }
 
@@ -318,7 +341,6 @@ pub struct PlacedTexture {
 
     // TODO Manage the priority somehow (for instance, increment each time it is used, and
     // periodically divide all priorities by 2)
-    #[allow(dead_code)]
     priority: Cell<u32>,
 }
 
@@ -0,0 +1,245 @@
+use super::*;
+
+use std::cell::Cell;
+use std::convert::TryInto;
+use std::rc::Rc;
+
+/// Appends the (big-endian) binary representation of snapshot values to a byte vector
+pub(crate) struct SnapshotWriter {
+    bytes: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    pub(crate) fn new() -> Self {
+        Self { bytes: Vec::new() }
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub(crate) fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    pub(crate) fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+
+    pub(crate) fn write_string(&mut self, value: &str) {
+        self.write_u32(value.len() as u32);
+        self.write_bytes(value.as_bytes());
+    }
+
+    pub(crate) fn write_texture_id(&mut self, id: GroupTextureID) {
+        self.write_u64(id.internal);
+    }
+
+    pub(crate) fn write_position(&mut self, position: TextureAtlasPosition) {
+        self.write_u32(position.min_x);
+        self.write_u32(position.min_y);
+        self.write_u32(position.width);
+        self.write_u32(position.height);
+    }
+
+    pub(crate) fn write_texture(&mut self, texture: &Texture) {
+        self.write_u32(texture.get_width());
+        self.write_u32(texture.get_height());
+        self.write_bytes(&texture.create_pixel_buffer());
+    }
+}
+
+/// Reads the values written by a `SnapshotWriter`, in the same order
+pub(crate) struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl<'a> SnapshotReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, index: 0 }
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.index == self.bytes.len()
+    }
+
+    pub(crate) fn read_bytes(&mut self, amount: usize) -> Result<&'a [u8], InvalidAtlasSnapshot> {
+        if amount > self.bytes.len() - self.index {
+            return Err(InvalidAtlasSnapshot::UnexpectedEnd);
+        }
+        let result = &self.bytes[self.index .. self.index + amount];
+        self.index += amount;
+        Ok(result)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8, InvalidAtlasSnapshot> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Result<bool, InvalidAtlasSnapshot> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(InvalidAtlasSnapshot::Corrupted("invalid boolean")),
+        }
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16, InvalidAtlasSnapshot> {
+        Ok(u16::from_be_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, InvalidAtlasSnapshot> {
+        Ok(u32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64, InvalidAtlasSnapshot> {
+        Ok(u64::from_be_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_f32(&mut self) -> Result<f32, InvalidAtlasSnapshot> {
+        Ok(f32::from_bits(self.read_u32()?))
+    }
+
+    pub(crate) fn read_string(&mut self) -> Result<String, InvalidAtlasSnapshot> {
+        let length = self.read_u32()? as usize;
+        match std::str::from_utf8(self.read_bytes(length)?) {
+            Ok(value) => Ok(value.to_string()),
+            Err(_) => Err(InvalidAtlasSnapshot::Corrupted("invalid UTF-8 string")),
+        }
+    }
+
+    pub(crate) fn read_texture_id(&mut self) -> Result<GroupTextureID, InvalidAtlasSnapshot> {
+        Ok(GroupTextureID { internal: self.read_u64()? })
+    }
+
+    /// Reads all bytes that haven't been read yet
+    pub(crate) fn read_remaining(&mut self) -> &'a [u8] {
+        let result = &self.bytes[self.index ..];
+        self.index = self.bytes.len();
+        result
+    }
+
+    /// Reads a position and checks that it fits inside an atlas of the given size
+    pub(crate) fn read_position(
+        &mut self, atlas_width: u32, atlas_height: u32
+    ) -> Result<TextureAtlasPosition, InvalidAtlasSnapshot> {
+        let position = TextureAtlasPosition {
+            min_x: self.read_u32()?,
+            min_y: self.read_u32()?,
+            width: self.read_u32()?,
+            height: self.read_u32()?,
+        };
+        let fits_x = position.min_x as u64 + position.width as u64 <= atlas_width as u64;
+        let fits_y = position.min_y as u64 + position.height as u64 <= atlas_height as u64;
+        if fits_x && fits_y {
+            Ok(position)
+        } else {
+            Err(InvalidAtlasSnapshot::Corrupted("texture position out of atlas bounds"))
+        }
+    }
+
+    /// Reads a texture and checks that it's not bigger than the given maximum size
+    pub(crate) fn read_texture(
+        &mut self, max_width: u32, max_height: u32
+    ) -> Result<Texture, InvalidAtlasSnapshot> {
+        let width = self.read_u32()?;
+        let height = self.read_u32()?;
+        if width > max_width || height > max_height {
+            return Err(InvalidAtlasSnapshot::Corrupted("texture bigger than atlas"));
+        }
+        let pixels = self.read_bytes(4 * width as usize * height as usize)?;
+        Ok(Texture::from_pixel_buffer(width, height, pixels))
+    }
+}
+
+impl TextureAtlas {
+    pub(super) fn write_snapshot(&self, writer: &mut SnapshotWriter) {
+        writer.write_texture(&self.big_texture);
+
+        writer.write_u32(self.placements.len() as u32);
+        for placement in &self.placements {
+            match placement.position.get() {
+                Some(position) => {
+                    writer.write_bool(true);
+                    writer.write_position(position);
+                }
+                None => writer.write_bool(false),
+            }
+            writer.write_u32(placement.priority.get());
+        }
+
+        writer.write_u32(self.rows_info.bound_y);
+        writer.write_u32(self.rows_info.rows.len() as u32);
+        for row in &self.rows_info.rows {
+            writer.write_u32(row.min_y);
+            writer.write_u32(row.height);
+            writer.write_u32(row.bound_x);
+        }
+    }
+
+    pub(super) fn read_snapshot(
+        reader: &mut SnapshotReader, width: u32, height: u32
+    ) -> Result<Self, InvalidAtlasSnapshot> {
+        let big_texture = reader.read_texture(width, height)?;
+        if big_texture.get_width() != width || big_texture.get_height() != height {
+            return Err(InvalidAtlasSnapshot::Corrupted("wrong atlas size"));
+        }
+
+        // Every entry takes at least 5 bytes, which prevents huge allocations for corrupted counts
+        let num_placements = reader.read_u32()? as usize;
+        let mut placements = Vec::with_capacity(num_placements.min(reader.bytes.len() / 5));
+        for _ in 0 .. num_placements {
+            let position = if reader.read_bool()? {
+                Some(reader.read_position(width, height)?)
+            } else {
+                None
+            };
+            placements.push(Rc::new(PlacedTexture {
+                position: Cell::new(position),
+                priority: Cell::new(reader.read_u32()?),
+            }));
+        }
+
+        let mut rows_info = RowsInfo::new(width, height);
+        rows_info.bound_y = reader.read_u32()?;
+        if rows_info.bound_y > height {
+            return Err(InvalidAtlasSnapshot::Corrupted("rows out of atlas bounds"));
+        }
+        let num_rows = reader.read_u32()?;
+        for _ in 0 .. num_rows {
+            let row = RowInfo {
+                min_y: reader.read_u32()?,
+                height: reader.read_u32()?,
+                bound_x: reader.read_u32()?,
+            };
+            if row.min_y as u64 + row.height as u64 > height as u64 || row.bound_x > width {
+                return Err(InvalidAtlasSnapshot::Corrupted("row out of atlas bounds"));
+            }
+            rows_info.rows.push(row);
+        }
+
+        // The statistics are not part of the snapshot
+        Ok(Self { big_texture, placements, rows_info, num_evicted_textures: 0 })
+    }
+}
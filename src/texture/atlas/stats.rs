@@ -0,0 +1,193 @@
+use super::*;
+
+/// Statistics about the space usage of a `TextureAtlas`, which can be obtained with its
+/// `get_stats` method. This is useful for debugging and for choosing good atlas sizes.
+///
+/// ## Areas
+/// All areas are measured in pixels. The *total area* of the atlas consists of the *used area*
+/// (the pixels covered by the textures that are currently placed), the *free area* (the pixels
+/// where new textures can still be placed), and the *wasted area* (everything else: the space
+/// above textures that are smaller than their row, and the space of textures that were
+/// invalidated).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TextureAtlasStats {
+    /// The number of textures that are currently placed on the atlas
+    pub num_textures: u32,
+    pub total_area: u64,
+    pub used_area: u64,
+    pub free_area: u64,
+    /// The area of the largest free rectangle
+    pub largest_free_area: u64,
+    /// The total number of textures that were removed from the atlas to make place for other
+    /// textures
+    pub num_evicted_textures: u64,
+    /// The positions and priorities of the textures that are currently placed on the atlas, in
+    /// the order in which they were placed
+    pub texture_priorities: Vec<(TextureAtlasPosition, u32)>,
+}
+
+impl TextureAtlasStats {
+    /// Gets the fraction of the atlas that is covered by textures, which is between 0.0 and 1.0
+    pub fn get_fill_ratio(&self) -> f32 {
+        self.used_area as f32 / self.total_area as f32
+    }
+
+    /// Gets the number of pixels that are neither used nor free. See the documentation of this
+    /// struct.
+    pub fn get_wasted_area(&self) -> u64 {
+        self.total_area - self.used_area - self.free_area
+    }
+
+    /// Gets the fragmentation of the free space, which is between 0.0 and 1.0. A value of 0.0
+    /// means that all free space forms a single rectangle, and values close to 1.0 mean that the
+    /// free space is scattered over many small rectangles (which makes it hard to place big
+    /// textures, even when there is enough free area).
+    pub fn get_fragmentation(&self) -> f32 {
+        if self.free_area == 0 {
+            0.0
+        } else {
+            1.0 - self.largest_free_area as f32 / self.free_area as f32
+        }
+    }
+}
+
+/// Statistics about a `TextureAtlasGroup`, which can be obtained with its `get_stats` method
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TextureAtlasGroupStats {
+    /// The number of textures that were added to the group
+    pub num_textures: u32,
+    /// The number of texture atlases that are currently in GPU memory
+    pub num_gpu_atlases: u16,
+    /// The total number of times a texture atlas was removed from GPU memory to make place for
+    /// another texture atlas
+    pub num_gpu_evictions: u64,
+    /// The statistics of each texture atlas (in CPU memory) of the group
+    pub atlases: Vec<TextureAtlasStats>,
+}
+
+impl TextureAtlasGroupStats {
+    /// Gets the fraction of the combined area of all texture atlases that is covered by textures,
+    /// or 0.0 if the group doesn't have any texture atlases yet.
+    pub fn get_fill_ratio(&self) -> f32 {
+        let total_area: u64 = self.atlases.iter().map(|atlas| atlas.total_area).sum();
+        let used_area: u64 = self.atlases.iter().map(|atlas| atlas.used_area).sum();
+        if total_area == 0 {
+            0.0
+        } else {
+            used_area as f32 / total_area as f32
+        }
+    }
+
+    /// Gets the total number of textures that were removed from the texture atlases of the group
+    pub fn get_num_evicted_textures(&self) -> u64 {
+        self.atlases.iter().map(|atlas| atlas.num_evicted_textures).sum()
+    }
+}
+
+impl TextureAtlas {
+    /// Computes the `TextureAtlasStats` of this atlas
+    pub fn get_stats(&self) -> TextureAtlasStats {
+        let texture_priorities: Vec<_> = self.placements.iter().filter_map(
+            |placement| placement.position.get().map(
+                |position| (position, placement.priority.get())
+            )
+        ).collect();
+        let used_area = texture_priorities.iter().map(
+            |(position, _)| position.width as u64 * position.height as u64
+        ).sum();
+
+        // New textures can be placed at the end of existing rows, or below the last row
+        let rows = &self.rows_info;
+        let atlas_width = rows.atlas_width as u64;
+        let bottom_area = atlas_width * (rows.atlas_height - rows.bound_y) as u64;
+        let mut free_area = bottom_area;
+        let mut largest_free_area = bottom_area;
+        for row in &rows.rows {
+            let row_area = (atlas_width - row.bound_x as u64) * row.height as u64;
+            free_area += row_area;
+            largest_free_area = largest_free_area.max(row_area);
+        }
+
+        // The free space at the end of the last row is adjacent to the free space below it
+        if let Some(last_row) = rows.rows.last() {
+            let height = (last_row.height + rows.atlas_height - rows.bound_y) as u64;
+            let combined_area = (atlas_width - last_row.bound_x as u64) * height;
+            largest_free_area = largest_free_area.max(combined_area);
+        }
+
+        TextureAtlasStats {
+            num_textures: texture_priorities.len() as u32,
+            total_area: atlas_width * rows.atlas_height as u64,
+            used_area,
+            free_area,
+            largest_free_area,
+            num_evicted_textures: self.num_evicted_textures,
+            texture_priorities,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_atlas_stats() {
+        let mut atlas = TextureAtlas::new(10, 20);
+        let empty_stats = atlas.get_stats();
+        assert_eq!(0, empty_stats.num_textures);
+        assert_eq!(200, empty_stats.total_area);
+        assert_eq!(200, empty_stats.free_area);
+        assert_eq!(0.0, empty_stats.get_fill_ratio());
+        assert_eq!(0.0, empty_stats.get_fragmentation());
+
+        let texture1 = Texture::new(4, 5, Color::rgb(0, 0, 0));
+        let texture2 = Texture::new(3, 3, Color::rgb(0, 0, 0));
+        let texture3 = Texture::new(10, 4, Color::rgb(0, 0, 0));
+        atlas.add_textures(&[&texture1, &texture2], false);
+        atlas.add_textures(&[&texture3], false);
+
+        // Rows: (y 0..5, 7 pixels wide) and (y 5..9, 10 pixels wide)
+        let stats = atlas.get_stats();
+        assert_eq!(3, stats.num_textures);
+        assert_eq!(20 + 9 + 40, stats.used_area);
+        assert_eq!(3 * 5 + 10 * 11, stats.free_area);
+        assert_eq!(10 * 11, stats.largest_free_area);
+        assert_eq!(6, stats.get_wasted_area());
+        assert_eq!(0, stats.num_evicted_textures);
+        assert!((stats.get_fill_ratio() - 69.0 / 200.0).abs() < 0.001);
+        assert!((stats.get_fragmentation() - 15.0 / 125.0).abs() < 0.001);
+
+        assert_eq!(3, stats.texture_priorities.len());
+        assert_eq!(
+            TextureAtlasPosition { min_x: 0, min_y: 5, width: 10, height: 4 },
+            stats.texture_priorities[2].0
+        );
+    }
+
+    #[test]
+    fn test_group_stats() {
+        let mut group = TextureAtlasGroup::<u8>::new(10, 10, 3, 2, 0, 1);
+        assert_eq!(0.0, group.get_stats().get_fill_ratio());
+
+        let id1 = group.add_texture(Texture::new(10, 10, Color::rgb(0, 0, 0))).unwrap();
+        let id2 = group.add_texture(Texture::new(5, 10, Color::rgb(0, 0, 0))).unwrap();
+        let id3 = group.add_texture(Texture::new(10, 3, Color::rgb(0, 0, 0))).unwrap();
+        group.place_textures(&[id1]);
+        group.place_textures(&[id2]);
+        group.place_textures(&[id3]);
+
+        for atlas_index in 0 .. 3 {
+            group.get_gpu_texture::<(), _>(atlas_index, |_texture| Ok(0)).unwrap();
+        }
+
+        let stats = group.get_stats();
+        assert_eq!(3, stats.num_textures);
+        assert_eq!(2, stats.num_gpu_atlases);
+        assert_eq!(1, stats.num_gpu_evictions);
+        assert_eq!(0, stats.get_num_evicted_textures());
+        assert_eq!(3, stats.atlases.len());
+        assert!((stats.get_fill_ratio() - 180.0 / 300.0).abs() < 0.001);
+    }
+}
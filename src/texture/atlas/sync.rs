@@ -0,0 +1,172 @@
+use super::*;
+
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
+/// Represents the id/handle of a `Texture` that was added to a `SyncTextureAtlasGroup` via a
+/// `SyncTextureSender`. Use the `get_group_id` method of the `SyncTextureAtlasGroup` to find the
+/// corresponding `GroupTextureID` once the texture has been applied.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct SyncTextureID {
+    internal: u64,
+}
+
+struct PendingTextures {
+    next_id: u64,
+    textures: Vec<(SyncTextureID, Texture)>,
+}
+
+/// A handle that other threads (for instance background image decoding threads) can use to add
+/// textures to a `SyncTextureAtlasGroup`. This struct is `Send` and `Sync`, and cloning it gives
+/// another handle to the *same* group.
+///
+/// The added textures will be queued until the render thread applies them with the
+/// `apply_pending_textures` method of the group.
+#[derive(Clone)]
+pub struct SyncTextureSender {
+    pending: Arc<Mutex<PendingTextures>>,
+    atlas_width: u32,
+    atlas_height: u32,
+}
+
+impl SyncTextureSender {
+    /// Queues the given texture to be added to the group, and returns its `SyncTextureID`. This
+    /// returns an error if the texture is too big for the texture atlases of the group.
+    pub fn add_texture(&self, texture: Texture) -> Result<SyncTextureID, TextureTooBigForAtlas> {
+        if texture.get_width() > self.atlas_width || texture.get_height() > self.atlas_height {
+            return Err(TextureTooBigForAtlas {
+                texture_width: texture.get_width(),
+                texture_height: texture.get_height(),
+                atlas_width: self.atlas_width,
+                atlas_height: self.atlas_height,
+            });
+        }
+
+        let mut pending = self.pending.lock().expect("Pending textures shouldn't be poisoned");
+        let id = SyncTextureID { internal: pending.next_id };
+        pending.next_id += 1;
+        pending.textures.push((id, texture));
+        Ok(id)
+    }
+}
+
+/// A wrapper around a `TextureAtlasGroup` that allows other threads to add textures to it.
+///
+/// The group itself stays on the render thread (just like a normal `TextureAtlasGroup`, it uses
+/// reference counting that is not thread-safe), but other threads can add textures via
+/// `SyncTextureSender`s, which can be obtained with `create_sender`. These textures will be queued
+/// and applied to the group when the render thread calls `apply_pending_textures`, which should
+/// be done at frame boundaries. This ensures that the group won't change while the render thread
+/// is using its placements.
+pub struct SyncTextureAtlasGroup<GpuTexture> {
+    group: TextureAtlasGroup<GpuTexture>,
+    pending: Arc<Mutex<PendingTextures>>,
+    applied_ids: HashMap<SyncTextureID, GroupTextureID>,
+}
+
+impl<GpuTexture> SyncTextureAtlasGroup<GpuTexture> {
+    /// Constructs a new `SyncTextureAtlasGroup` that wraps the given `group`
+    pub fn new(group: TextureAtlasGroup<GpuTexture>) -> Self {
+        Self {
+            group,
+            pending: Arc::new(Mutex::new(PendingTextures {
+                next_id: 0,
+                textures: Vec::new(),
+            })),
+            applied_ids: HashMap::new(),
+        }
+    }
+
+    /// Creates a `SyncTextureSender` that other threads can use to add textures to this group
+    pub fn create_sender(&self) -> SyncTextureSender {
+        SyncTextureSender {
+            pending: Arc::clone(&self.pending),
+            atlas_width: self.group.get_width(),
+            atlas_height: self.group.get_height(),
+        }
+    }
+
+    /// Adds all textures that were queued by `SyncTextureSender`s to the wrapped group, and
+    /// returns the ids of these textures (in the order in which they were queued). This should be
+    /// called by the render thread at frame boundaries: the returned ids indicate which textures
+    /// became available since the previous call (so models that are waiting for them can be
+    /// created).
+    pub fn apply_pending_textures(&mut self) -> Vec<(SyncTextureID, GroupTextureID)> {
+        let textures = {
+            let mut pending = self.pending.lock().expect("Pending textures shouldn't be poisoned");
+            std::mem::take(&mut pending.textures)
+        };
+
+        let mut applied = Vec::with_capacity(textures.len());
+        for (sync_id, texture) in textures {
+            // The sender already checked that the texture fits
+            let group_id = self.group.add_texture(texture).expect("Sender checked the size");
+            self.applied_ids.insert(sync_id, group_id);
+            applied.push((sync_id, group_id));
+        }
+        applied
+    }
+
+    /// Gets the `GroupTextureID` of the texture with the given `SyncTextureID`, or `None` if the
+    /// texture hasn't been applied yet.
+    pub fn get_group_id(&self, id: SyncTextureID) -> Option<GroupTextureID> {
+        self.applied_ids.get(&id).copied()
+    }
+
+    /// Gets a reference to the wrapped `TextureAtlasGroup`
+    pub fn get_group(&self) -> &TextureAtlasGroup<GpuTexture> {
+        &self.group
+    }
+
+    /// Gets a mutable reference to the wrapped `TextureAtlasGroup`. The render thread can use this
+    /// to place textures, to get GPU textures, and to add textures directly.
+    pub fn get_group_mut(&mut self) -> &mut TextureAtlasGroup<GpuTexture> {
+        &mut self.group
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::thread;
+
+    #[test]
+    fn test_add_textures_from_other_threads() {
+        let mut group = SyncTextureAtlasGroup::<()>::new(
+            TextureAtlasGroup::new(20, 20, 2, 1, 0, 0)
+        );
+        let sender = group.create_sender();
+
+        let threads: Vec<_> = (0 .. 3).map(|index| {
+            let thread_sender = sender.clone();
+            thread::spawn(move || {
+                thread_sender.add_texture(Texture::new(5, 5, Color::rgb(index, 0, 0))).unwrap()
+            })
+        }).collect();
+        let sync_ids: Vec<_> = threads.into_iter().map(|thread| thread.join().unwrap()).collect();
+
+        assert!(sender.add_texture(Texture::new(21, 5, Color::rgb(0, 0, 0))).is_err());
+
+        // The textures shouldn't be added to the group before they are applied
+        assert!(group.get_group_id(sync_ids[0]).is_none());
+        let applied = group.apply_pending_textures();
+        assert_eq!(3, applied.len());
+        assert!(group.apply_pending_textures().is_empty());
+
+        for (index, sync_id) in sync_ids.iter().enumerate() {
+            let group_id = group.get_group_id(*sync_id).unwrap();
+            assert_eq!(
+                Color::rgb(index as u8, 0, 0),
+                group.get_group().get_texture(group_id)[0][0]
+            );
+        }
+
+        let group_ids: Vec<_> = applied.iter().map(|(_, group_id)| *group_id).collect();
+        assert_eq!(3, group.get_group_mut().place_textures(&group_ids).len());
+    }
+}
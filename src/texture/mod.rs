@@ -97,6 +97,25 @@ impl Texture {
         pixel_buffer
     }
 
+    /// Creates a `Texture` from a buffer of RGBA pixels in the same layout as `create_pixel_buffer`
+    /// (which is also the layout used by OpenGL).
+    pub fn from_pixel_buffer(width: u32, height: u32, source: &[u8]) -> Self {
+        assert_eq!((width * height * 4) as usize, source.len());
+        let mut texture = Self::new(width, height, Color::rgba(0, 0, 0, 0));
+        for x in 0 .. width {
+            for y in 0 .. height {
+                let source_index = 4 * (x + y * width) as usize;
+                texture[x][y as usize] = Color::rgba(
+                    source[source_index],
+                    source[source_index + 1],
+                    source[source_index + 2],
+                    source[source_index + 3],
+                );
+            }
+        }
+        texture
+    }
+
     pub fn debug_dump(&self, file_path: &str) {
         let file = std::fs::File::create(std::path::Path::new(file_path)).unwrap();
         let mut w = std::io::BufWriter::new(file);
@@ -223,5 +242,8 @@ mod tests {
         assert_eq!(vec![
             13, 87, 105, 255, 217, 185, 197, 255, 201, 140, 0, 200, 15, 97, 5, 0, 89, 58, 240, 255, 200, 100, 150, 255
         ], pixel_buffer);
+
+        let restored = Texture::from_pixel_buffer(2, 3, &pixel_buffer);
+        assert_eq!(texture.pixels, restored.pixels);
     }
 }
@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+/// Postpones an (expensive) reaction until its trigger has been quiet for a while. Every call to
+/// `trigger` restarts the delay, and `advance` returns the value of the last trigger once the
+/// delay has passed without new triggers. This is useful for reactions that only matter for the
+/// final state, like relayouting after the user stopped resizing something.
+///
+/// Like the `Animator`, a debouncer needs to be advanced explicitly: components should typically
+/// subscribe to tick events and call `advance` from their `on_tick` method.
+///
+/// ## Example
+/// ```
+/// use knukki::*;
+/// use std::time::Duration;
+///
+/// let mut relayout = Debouncer::new(Duration::from_millis(100));
+///
+/// // This would typically be done in on_mouse_move
+/// relayout.trigger(Point::new(0.2, 0.3));
+/// relayout.trigger(Point::new(0.4, 0.5));
+///
+/// // This would typically be done in on_tick
+/// assert_eq!(None, relayout.advance(Duration::from_millis(60)));
+/// assert_eq!(Some(Point::new(0.4, 0.5)), relayout.advance(Duration::from_millis(60)));
+/// assert!(!relayout.is_pending());
+/// ```
+pub struct Debouncer<T = ()> {
+    delay: Duration,
+    pending: Option<(T, Duration)>,
+}
+
+impl<T> Debouncer<T> {
+    /// Constructs a `Debouncer` that waits until its trigger has been quiet for *delay*
+    pub fn new(delay: Duration) -> Self {
+        Self { delay, pending: None }
+    }
+
+    pub fn get_delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Changes the delay. This doesn't affect the remaining time of a pending trigger.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
+    }
+
+    /// Stores *value* (replacing the value of the previous trigger, if it is still pending) and
+    /// restarts the delay
+    pub fn trigger(&mut self, value: T) {
+        self.pending = Some((value, self.delay));
+    }
+
+    /// Checks whether there is a trigger whose value hasn't been returned by `advance` yet
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Advances the time by *delta_time* (typically the delta time of a `TickEvent`), and returns
+    /// the value of the last trigger if the delay has passed since then
+    pub fn advance(&mut self, delta_time: Duration) -> Option<T> {
+        let (_, remaining) = self.pending.as_mut()?;
+        if delta_time >= *remaining {
+            self.pending.take().map(|(value, _)| value)
+        } else {
+            *remaining -= delta_time;
+            None
+        }
+    }
+
+    /// Returns the value of the pending trigger (if any) immediately, without waiting for the
+    /// delay
+    pub fn flush(&mut self) -> Option<T> {
+        self.pending.take().map(|(value, _)| value)
+    }
+
+    /// Forgets the pending trigger (if any)
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_debounce() {
+        let millis = Duration::from_millis;
+        let mut debouncer = Debouncer::new(millis(100));
+        assert_eq!(None, debouncer.advance(millis(500)));
+
+        // Each trigger should restart the delay
+        debouncer.trigger(1);
+        assert_eq!(None, debouncer.advance(millis(80)));
+        debouncer.trigger(2);
+        assert_eq!(None, debouncer.advance(millis(80)));
+        assert!(debouncer.is_pending());
+        assert_eq!(Some(2), debouncer.advance(millis(20)));
+        assert!(!debouncer.is_pending());
+        assert_eq!(None, debouncer.advance(millis(500)));
+
+        debouncer.trigger(3);
+        debouncer.cancel();
+        assert_eq!(None, debouncer.advance(millis(500)));
+
+        debouncer.trigger(4);
+        assert_eq!(Some(4), debouncer.flush());
+        assert_eq!(None, debouncer.advance(millis(500)));
+    }
+}
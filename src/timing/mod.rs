@@ -0,0 +1,5 @@
+mod debounce;
+mod throttle;
+
+pub use debounce::*;
+pub use throttle::*;
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+/// Limits how often an (expensive) reaction can happen: at most once per *interval*. The first
+/// trigger fires immediately, and the triggers during the rest of the interval are collapsed into
+/// a single trailing fire at the end of the interval (with the value of the last trigger). This
+/// is useful for reactions that should keep up with a continuous stream of events without
+/// handling each of them, like updating a preview on every mouse move.
+///
+/// Like the `Animator`, a throttler needs to be advanced explicitly: components should typically
+/// subscribe to tick events and call `advance` from their `on_tick` method.
+///
+/// ## Example
+/// ```
+/// use knukki::*;
+/// use std::time::Duration;
+///
+/// let mut preview = Throttler::new(Duration::from_millis(100));
+///
+/// // This would typically be done in on_mouse_move
+/// assert_eq!(Some(1), preview.trigger(1));
+/// assert_eq!(None, preview.trigger(2));
+/// assert_eq!(None, preview.trigger(3));
+///
+/// // This would typically be done in on_tick
+/// assert_eq!(Some(3), preview.advance(Duration::from_millis(100)));
+/// ```
+pub struct Throttler<T = ()> {
+    interval: Duration,
+    // The time until the next value can be fired, or None if it can be fired immediately
+    cooldown: Option<Duration>,
+    pending: Option<T>,
+}
+
+impl<T> Throttler<T> {
+    /// Constructs a `Throttler` that fires at most once per *interval*
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, cooldown: None, pending: None }
+    }
+
+    pub fn get_interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Changes the interval. This doesn't affect the current cooldown.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Returns *value* if the throttler isn't cooling down (and starts the cooldown). Otherwise,
+    /// *value* is stored (replacing the value of the previous trigger) and will be returned by
+    /// `advance` when the cooldown is over.
+    pub fn trigger(&mut self, value: T) -> Option<T> {
+        if self.cooldown.is_some() {
+            self.pending = Some(value);
+            None
+        } else {
+            self.cooldown = Some(self.interval);
+            Some(value)
+        }
+    }
+
+    /// Checks whether there is a trigger whose value will be returned when the cooldown is over
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Advances the time by *delta_time* (typically the delta time of a `TickEvent`), and returns
+    /// the value of the last trigger during the cooldown if the cooldown is over. In that case, a
+    /// new cooldown will be started.
+    pub fn advance(&mut self, delta_time: Duration) -> Option<T> {
+        let remaining = self.cooldown.as_mut()?;
+        if delta_time < *remaining {
+            *remaining -= delta_time;
+            return None;
+        }
+
+        match self.pending.take() {
+            Some(value) => {
+                self.cooldown = Some(self.interval);
+                Some(value)
+            }
+            None => {
+                self.cooldown = None;
+                None
+            }
+        }
+    }
+
+    /// Ends the cooldown and forgets the pending trigger (if any), so the next trigger will fire
+    /// immediately
+    pub fn reset(&mut self) {
+        self.cooldown = None;
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_throttle() {
+        let millis = Duration::from_millis;
+        let mut throttler = Throttler::new(millis(100));
+        assert_eq!(None, throttler.advance(millis(500)));
+
+        assert_eq!(Some(1), throttler.trigger(1));
+        assert_eq!(None, throttler.trigger(2));
+        assert_eq!(None, throttler.advance(millis(50)));
+        assert_eq!(None, throttler.trigger(3));
+        assert!(throttler.is_pending());
+
+        // The trailing fire should start a new cooldown
+        assert_eq!(Some(3), throttler.advance(millis(50)));
+        assert_eq!(None, throttler.trigger(4));
+        assert_eq!(Some(4), throttler.advance(millis(100)));
+
+        // Without pending triggers, the cooldown should end
+        assert_eq!(None, throttler.advance(millis(100)));
+        assert_eq!(Some(5), throttler.trigger(5));
+
+        throttler.trigger(6);
+        throttler.reset();
+        assert_eq!(None, throttler.advance(millis(500)));
+        assert_eq!(Some(7), throttler.trigger(7));
+    }
+}
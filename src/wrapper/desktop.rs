@@ -1,42 +1,57 @@
-use crate::{Application, MouseEnterEvent, MouseLeaveEvent, MouseMoveEvent, RenderRegion, Renderer, MousePressEvent};
+use crate::{Application, ArrowKey, CharTypeEvent, GamepadAxis, GamepadAxisEvent, GamepadButton, GamepadButtonEvent, InputEvent, MouseEnterEvent, MouseLeaveEvent, MouseMoveEvent, RenderRegion, Renderer, MousePressEvent, MouseReleaseEvent, MouseScrollEvent, PointerDetails, PointerToolType, RelativeMouseMoveEvent, VirtualMouse, WindowFocusEvent, WindowMinimizeEvent};
 
 use golem::*;
 
 use glutin::{
     dpi::PhysicalPosition,
     dpi::PhysicalSize,
-    event::{ElementState, Event, MouseButton, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
+    event::{
+        DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode,
+        WindowEvent,
+    },
+    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
+    monitor::MonitorHandle,
+    window::CursorIcon,
     window::Window,
     window::WindowBuilder,
-    ContextWrapper, PossiblyCurrent,
+    ContextError, ContextWrapper, PossiblyCurrent,
 };
 
 use golem::Dimension::D2;
+use std::rc::Rc;
 use std::thread::sleep;
 use std::time::Duration;
 use std::time::Instant;
 
-pub fn start(mut app: Application, title: &str) {
-    let event_loop = EventLoop::new();
-    let builder = WindowBuilder::new()
-        .with_decorations(true)
-        .with_maximized(false)
-        .with_resizable(true)
-        .with_title(title)
-        .with_visible(true);
-    let windowed_context = unsafe {
-        glutin::ContextBuilder::new()
-            .build_windowed(builder, &event_loop)
-            .expect("Should be able to create a window")
-            .make_current()
-            .expect("Should be able to make context current")
-    };
+/// The minimum width and height (in pixels) that the user can give the window by dragging a
+/// `WindowFrameArea::Resize`
+const MIN_WINDOW_SIZE: i32 = 100;
+
+pub fn start(app: Application, title: &str) {
+    start_window(app, title, true);
+}
 
-    let golem = Context::from_glow(glow::Context::from_loader_function(|function_name| {
-        windowed_context.get_proc_address(function_name)
-    }))
-    .expect("Should be able to create Golem context");
+/// Starts the application in a window without decorations (title bar and borders). Applications
+/// that use this should draw their own title bar, and use `ComponentBuddy.set_window_frame_area`
+/// to let the user move and resize the window.
+pub fn start_borderless(app: Application, title: &str) {
+    start_window(app, title, false);
+}
+
+/// The state of a window move or resize that the `Application` requested
+struct WindowDrag {
+    area: crate::WindowFrameArea,
+    // The position of the cursor (relative to the window) when the drag started
+    grab_position: PhysicalPosition<i32>,
+    // The inner size of the window when the drag started
+    start_size: PhysicalSize<u32>,
+}
+
+fn start_window(mut app: Application, title: &str, decorations: bool) {
+    let event_loop = EventLoop::new();
+    let title = title.to_string();
+    let (mut windowed_context, golem) =
+        create_window::<()>(WindowBuilder::new(), &title, decorations, &event_loop);
 
     let mut renderer = Renderer::new(
         // The initial viewport doesn't matter in this situation because it will be overwritten
@@ -45,19 +60,65 @@ pub fn start(mut app: Application, title: &str) {
         RenderRegion::with_size(0, 0, 1, 1),
     );
 
+    // Compile the built-in shaders now rather than during the first interaction
+    renderer.prewarm(|_progress| {}).expect("Should be able to prewarm shaders");
+
     let mut copy_pack =
         create_copy_pack(renderer.get_context()).expect("Should be able to create copy pack");
 
     let mut start_time = Instant::now();
+    let mut last_tick_time = Instant::now();
+
+    // The frame times that are given to the application are relative to this moment
+    let app_start_time = Instant::now();
+
+    // The refresh rate of the application is updated whenever the window moves to another monitor
+    let mut current_monitor = windowed_context.window().current_monitor();
+    app.set_refresh_rate(get_refresh_rate(&current_monitor));
 
     let mut mouse_position: Option<PhysicalPosition<i32>> = None;
     let mut last_press_point: Option<crate::Point> = None;
     let mut pressed_buttons = Vec::with_capacity(2);
     let mut should_fire_mouse_enter_event = false;
 
-    let mut render_surface: Option<Surface> = None;
+    // Winit doesn't report pen data, so only the tool type of the mouse events is known
+    let mouse_details = PointerDetails::with_tool_type(PointerToolType::Mouse);
+
+    // The arrow keys control this mouse when the virtual mouse is enabled in the input settings
+    let mut virtual_mouse = VirtualMouse::new(crate::Mouse::new(1), crate::Point::new(0.5, 0.5));
+
+    let mut render_surface: Option<Rc<Surface>> = None;
+
+    // The render surface needs to be recreated when the render scale of the application changes
+    let mut current_render_scale = app.get_render_scale();
 
-    event_loop.run(move |event, _target, control_flow| {
+    // The cursor icon that was most recently applied to the window
+    let mut current_cursor = crate::CursorIcon::Arrow;
+
+    // This is Some while the user is moving or resizing the window via a WindowFrameArea
+    let mut window_drag: Option<WindowDrag> = None;
+
+    // Winit doesn't have a minimize event, but minimized windows are resized to 0x0 pixels
+    let mut minimized = false;
+
+    // Whether the cursor is currently grabbed and hidden because a component locked the pointer
+    let mut pointer_locked = false;
+
+    // Gamepads are optional, so the application should still work when gilrs is not supported
+    let mut gilrs = match gilrs::Gilrs::new() {
+        Ok(gilrs) => {
+            for (id, gamepad) in gilrs.gamepads() {
+                app.connect_gamepad(convert_gamepad_id(id), gamepad.name().to_string());
+            }
+            Some(gilrs)
+        }
+        Err(error) => {
+            log::warn!("Gamepads are not available: {}", error);
+            None
+        }
+    };
+
+    event_loop.run(move |event, target, control_flow| {
         // I use `Poll` instead of `Wait` to get more control over the control flow.
         // I use a simple custom system to avoid too large power usage
         *control_flow = ControlFlow::Poll;
@@ -66,15 +127,39 @@ pub fn start(mut app: Application, title: &str) {
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
-            } => *control_flow = ControlFlow::Exit,
+            } => {
+                // The components can veto the close request, for instance to save their work first
+                if app.fire_close_request() {
+                    *control_flow = ControlFlow::Exit;
+                }
+            }
             Event::WindowEvent {
                 window_id: _,
                 event: window_event,
             } => {
                 match window_event {
-                    WindowEvent::Resized(_) => {
+                    WindowEvent::Resized(new_size) => {
                         // TODO app.on_resize
                         render_surface = None;
+                        renderer.set_frame_surface(None);
+
+                        let is_minimized = new_size.width == 0 || new_size.height == 0;
+                        if is_minimized != minimized {
+                            minimized = is_minimized;
+                            app.queue_event(InputEvent::WindowMinimize(
+                                WindowMinimizeEvent::new(minimized)
+                            ));
+                        }
+                    }
+                    WindowEvent::Focused(focused) => {
+                        app.queue_event(InputEvent::WindowFocus(WindowFocusEvent::new(focused)));
+                    }
+                    WindowEvent::Moved(_) => {
+                        let monitor = windowed_context.window().current_monitor();
+                        if monitor != current_monitor {
+                            app.set_refresh_rate(get_refresh_rate(&monitor));
+                            current_monitor = monitor;
+                        }
                     }
                     WindowEvent::MouseInput {
                         device_id: _,
@@ -87,8 +172,8 @@ pub fn start(mut app: Application, title: &str) {
                             // Convert winit button to knukki button
                             let knukki_button = match button {
                                 MouseButton::Left => crate::MouseButton::primary(),
-                                MouseButton::Right => crate::MouseButton::new(1),
-                                MouseButton::Middle => crate::MouseButton::new(2),
+                                MouseButton::Right => crate::MouseButton::secondary(),
+                                MouseButton::Middle => crate::MouseButton::middle(),
                                 MouseButton::Other(id) => crate::MouseButton::new(id),
                             };
 
@@ -96,6 +181,9 @@ pub fn start(mut app: Application, title: &str) {
                                 pressed_buttons.push(knukki_button);
                             } else {
                                 pressed_buttons.retain(|pressed_button| *pressed_button != knukki_button);
+                                if knukki_button == crate::MouseButton::primary() {
+                                    window_drag = None;
+                                }
                             }
 
                             // It would be weird if we don't have a mouse position
@@ -112,31 +200,36 @@ pub fn start(mut app: Application, title: &str) {
 
                                 // Construct and fire the events
                                 if state == ElementState::Pressed {
-                                    let knukki_press_event = crate::MousePressEvent::new(
+                                    let knukki_press_event = MousePressEvent::with_details(
                                         knukki_mouse,
                                         knukki_point,
-                                        knukki_button
+                                        knukki_button,
+                                        mouse_details,
                                     );
 
-                                    app.fire_mouse_press_event(knukki_press_event);
+                                    app.queue_event(InputEvent::MousePress(knukki_press_event));
                                     last_press_point = Some(knukki_point);
                                 } else {
-                                    let knukki_release_event = crate::MouseReleaseEvent::new(
+                                    let knukki_release_event = MouseReleaseEvent::with_details(
                                         knukki_mouse,
                                         knukki_point,
-                                        knukki_button
+                                        knukki_button,
+                                        mouse_details,
                                     );
 
-                                    app.fire_mouse_release_event(knukki_release_event);
+                                    app.queue_event(InputEvent::MouseRelease(knukki_release_event));
 
                                     if let Some(press_point) = last_press_point {
-                                        if knukki_point.distance_to(press_point) < 0.1 {
+                                        let max_distance = app.get_input_settings().drag_start_distance;
+                                        if knukki_point.distance_to(press_point) < max_distance {
                                             let knukki_click_event = crate::MouseClickEvent::new(
                                                 knukki_mouse,
                                                 knukki_point,
                                                 knukki_button,
                                             );
-                                            app.fire_mouse_click_event(knukki_click_event);
+                                            app.queue_event(
+                                                InputEvent::MouseClick(knukki_click_event)
+                                            );
                                         }
                                     }
                                 }
@@ -148,6 +241,27 @@ pub fn start(mut app: Application, title: &str) {
                         position,
                         ..
                     } => {
+                        // While the user is dragging the window, the cursor moves along with the
+                        // window, so there is no need to fire mouse move events
+                        if let Some(drag) = &window_drag {
+                            drag_window(windowed_context.window(), drag, position);
+                            mouse_position = Some(position);
+                            return;
+                        }
+
+                        // While the pointer is locked, the movement is reported by the
+                        // MouseMotion device events, and the cursor should stay in place
+                        if pointer_locked {
+                            if let Some(lock_position) = mouse_position {
+                                if lock_position != position {
+                                    let _ = windowed_context
+                                        .window()
+                                        .set_cursor_position(lock_position);
+                                }
+                            }
+                            return;
+                        }
+
                         // Winit seems to fire mouse move events in occasions like clicking on the
                         // app icon in the taskbar or opening the window, even when the cursor is
                         // not inside the window. Let's just ignore these events.
@@ -172,14 +286,15 @@ pub fn start(mut app: Application, title: &str) {
                                 mouse,
                                 entrance_point
                             );
-                            app.fire_mouse_enter_event(event);
+                            app.queue_event(InputEvent::MouseEnter(event));
                             should_fire_mouse_enter_event = false;
 
                             // Also fire press events for all buttons that are pressed
                             for button in &pressed_buttons {
-                                app.fire_mouse_press_event(MousePressEvent::new(
-                                    mouse, entrance_point, *button
-                                ));
+                                let press_event = MousePressEvent::with_details(
+                                    mouse, entrance_point, *button, mouse_details
+                                );
+                                app.queue_event(InputEvent::MousePress(press_event));
                             }
                         }
 
@@ -196,17 +311,76 @@ pub fn start(mut app: Application, title: &str) {
                                     1.0 - previous_position.y as f32 / window_size.height as f32;
                                 let new_x = position.x as f32 / window_size.width as f32;
                                 let new_y = 1.0 - position.y as f32 / window_size.height as f32;
-                                let event = MouseMoveEvent::new(
+                                let event = MouseMoveEvent::with_details(
                                     crate::Mouse::new(0),
                                     crate::Point::new(old_x, old_y),
                                     crate::Point::new(new_x, new_y),
+                                    mouse_details,
                                 );
-                                app.fire_mouse_move_event(event);
+                                app.queue_event(InputEvent::MouseMove(event));
                             }
                         }
 
                         mouse_position = Some(position);
                     }
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        if let Some(position) = mouse_position {
+                            // Winit uses the same sign convention as knukki, but touchpads tend
+                            // to give pixel deltas, so those need to be converted to lines
+                            let (delta_x, delta_y) = match delta {
+                                MouseScrollDelta::LineDelta(x, y) => (x, y),
+                                MouseScrollDelta::PixelDelta(pixels) => {
+                                    let line_height = app.get_input_settings().scroll_line_height;
+                                    (pixels.x as f32 / line_height, pixels.y as f32 / line_height)
+                                }
+                            };
+
+                            let window_size = windowed_context.window().inner_size();
+                            let x = position.x as f32 / window_size.width as f32;
+                            let y = 1.0 - position.y as f32 / window_size.height as f32;
+                            app.queue_event(InputEvent::MouseScroll(MouseScrollEvent::new(
+                                crate::Mouse::new(0),
+                                crate::Point::new(x, y),
+                                delta_x,
+                                delta_y,
+                            )));
+                        }
+                    }
+                    WindowEvent::ReceivedCharacter(character) => {
+                        // Control characters (like backspace) are not text
+                        if !character.is_control() {
+                            let event = CharTypeEvent::new(character.to_string());
+                            app.queue_event(InputEvent::CharType(event));
+                        }
+                    }
+                    WindowEvent::KeyboardInput {
+                        input: KeyboardInput { state, virtual_keycode: Some(key_code), .. },
+                        ..
+                    } => {
+                        if app.get_input_settings().virtual_mouse {
+                            let pressed = state == ElementState::Pressed;
+                            let arrow = match key_code {
+                                VirtualKeyCode::Left => Some(ArrowKey::Left),
+                                VirtualKeyCode::Right => Some(ArrowKey::Right),
+                                VirtualKeyCode::Down => Some(ArrowKey::Down),
+                                VirtualKeyCode::Up => Some(ArrowKey::Up),
+                                _ => None,
+                            };
+                            if let Some(arrow) = arrow {
+                                virtual_mouse.set_arrow_pressed(arrow, pressed);
+                            }
+
+                            // The space bar acts as the primary button of the virtual mouse
+                            if key_code == VirtualKeyCode::Space {
+                                let button = crate::MouseButton::primary();
+                                if pressed {
+                                    virtual_mouse.press_button(&mut app, button);
+                                } else {
+                                    virtual_mouse.release_button(&mut app, button);
+                                }
+                            }
+                        }
+                    }
                     WindowEvent::CursorEntered { .. } => {
                         should_fire_mouse_enter_event = true;
                     }
@@ -221,7 +395,7 @@ pub fn start(mut app: Application, title: &str) {
                                 crate::Mouse::new(0),
                                 crate::Point::new(old_x, old_y),
                             );
-                            app.fire_mouse_leave_event(event);
+                            app.queue_event(InputEvent::MouseLeave(event));
                         }
 
                         // Once the mouse leaves the window, we have no clue where it is, but it
@@ -231,6 +405,20 @@ pub fn start(mut app: Application, title: &str) {
                     _ => (),
                 }
             }
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta: (delta_x, delta_y) },
+                ..
+            } => {
+                if pointer_locked {
+                    // The y-axis of winit points downwards
+                    let window_size = windowed_context.window().inner_size();
+                    app.queue_event(InputEvent::RelativeMouseMove(RelativeMouseMoveEvent::new(
+                        crate::Mouse::new(0),
+                        delta_x as f32 / window_size.width as f32,
+                        -delta_y as f32 / window_size.height as f32,
+                    )));
+                }
+            }
             Event::MainEventsCleared => {
                 // Let the application decide whether it needs to redraw itself
                 let force = false;
@@ -238,15 +426,75 @@ pub fn start(mut app: Application, title: &str) {
                 // Draw onto the entire inner window buffer
                 let size = windowed_context.window().inner_size();
 
-                // Give the application a render opportunity every ~16 milliseconds
-                let current_time = Instant::now();
-                let elapsed_time = (current_time - start_time).as_millis();
-                if elapsed_time < 16 {
-                    sleep(Duration::from_millis(16 - elapsed_time as u64));
+                // Give the application a render opportunity once per refresh of the monitor, or
+                // every ~16 milliseconds when the refresh rate is unknown
+                let frame_interval = app
+                    .get_frame_timing()
+                    .get_refresh_interval()
+                    .unwrap_or(Duration::from_millis(16));
+                let elapsed_time = Instant::now() - start_time;
+                if elapsed_time < frame_interval {
+                    sleep(frame_interval - elapsed_time);
                 }
                 start_time = Instant::now();
 
-                draw_application(
+                // Fire the queued input events and let the components advance their time-based
+                // state right before rendering
+                let tick_time = Instant::now();
+                app.set_frame_time(tick_time - app_start_time);
+                if app.get_input_settings().virtual_mouse {
+                    virtual_mouse.update(&mut app, tick_time - last_tick_time);
+                }
+                if let Some(gilrs) = &mut gilrs {
+                    poll_gamepads(gilrs, &mut app);
+                }
+                app.update(tick_time - last_tick_time);
+                last_tick_time = tick_time;
+
+                // The components may have changed their cursor while handling the events
+                let cursor = app.get_cursor(crate::Mouse::new(0));
+                if cursor != current_cursor {
+                    windowed_context.window().set_cursor_icon(convert_cursor_icon(cursor));
+                    current_cursor = cursor;
+                }
+
+                // Lock or unlock the pointer when a component requested it
+                let locked = app.is_pointer_locked(crate::Mouse::new(0));
+                if locked != pointer_locked {
+                    let window = windowed_context.window();
+                    // Not all platforms support grabbing the cursor, but the cursor will be
+                    // moved back after each movement anyway
+                    let _ = window.set_cursor_grab(locked);
+                    window.set_cursor_visible(!locked);
+                    pointer_locked = locked;
+                }
+
+                // Start moving or resizing the window if the user pressed in a WindowFrameArea,
+                // unless the button was already released before the application noticed it
+                if let Some(area) = app.take_window_drag_request() {
+                    let primary = crate::MouseButton::primary();
+                    if let (Some(position), true) =
+                        (mouse_position, pressed_buttons.contains(&primary))
+                    {
+                        window_drag = Some(WindowDrag {
+                            area,
+                            grab_position: position,
+                            start_size: windowed_context.window().inner_size(),
+                        });
+
+                        // Releasing the button after dragging the window shouldn't cause a click
+                        last_press_point = None;
+                    }
+                }
+
+                // The render surface has the wrong size after the render scale was changed
+                if app.get_render_scale() != current_render_scale {
+                    render_surface = None;
+                    renderer.set_frame_surface(None);
+                    current_render_scale = app.get_render_scale();
+                }
+
+                let drawn = draw_application(
                     &mut app,
                     &mut renderer,
                     &mut copy_pack,
@@ -256,6 +504,20 @@ pub fn start(mut app: Application, title: &str) {
                     &windowed_context,
                 )
                 .expect("Should be able to draw app");
+                if !drawn {
+                    recover_from_context_loss(
+                        &mut windowed_context,
+                        &title,
+                        decorations,
+                        target,
+                        &mut renderer,
+                        &mut copy_pack,
+                        &mut render_surface,
+                    );
+
+                    // The new window starts with the default cursor
+                    current_cursor = crate::CursorIcon::Arrow;
+                }
             }
             Event::RedrawRequested(_) => {
                 // This *wrapper* will never request a winit redraw, so when this
@@ -265,7 +527,7 @@ pub fn start(mut app: Application, title: &str) {
                 // Draw onto the entire inner window buffer
                 let size = windowed_context.window().inner_size();
 
-                draw_application(
+                let drawn = draw_application(
                     &mut app,
                     &mut renderer,
                     &mut copy_pack,
@@ -275,26 +537,272 @@ pub fn start(mut app: Application, title: &str) {
                     &windowed_context,
                 )
                 .expect("Should be able to force draw app");
+                if !drawn {
+                    recover_from_context_loss(
+                        &mut windowed_context,
+                        &title,
+                        decorations,
+                        target,
+                        &mut renderer,
+                        &mut copy_pack,
+                        &mut render_surface,
+                    );
+
+                    // The new window starts with the default cursor
+                    current_cursor = crate::CursorIcon::Arrow;
+                }
             }
             _ => (),
         }
     });
 
+    fn create_window<T>(
+        builder: WindowBuilder,
+        title: &str,
+        decorations: bool,
+        target: &EventLoopWindowTarget<T>,
+    ) -> (ContextWrapper<PossiblyCurrent, Window>, Context) {
+        let builder = builder
+            .with_decorations(decorations)
+            .with_maximized(false)
+            .with_resizable(true)
+            .with_title(title)
+            .with_visible(true);
+        let windowed_context = unsafe {
+            glutin::ContextBuilder::new()
+                .build_windowed(builder, target)
+                .expect("Should be able to create a window")
+                .make_current()
+                .expect("Should be able to make context current")
+        };
+
+        let golem = Context::from_glow(glow::Context::from_loader_function(|function_name| {
+            windowed_context.get_proc_address(function_name)
+        }))
+        .expect("Should be able to create Golem context");
+
+        (windowed_context, golem)
+    }
+
+    /// Replaces the window and its OpenGL context after the context was lost (for instance after
+    /// a GPU reset). A lost context can't be used anymore, so a new one is needed, and glutin can
+    /// only create new contexts together with a new window.
+    fn recover_from_context_loss<T>(
+        windowed_context: &mut ContextWrapper<PossiblyCurrent, Window>,
+        title: &str,
+        decorations: bool,
+        target: &EventLoopWindowTarget<T>,
+        renderer: &mut Renderer,
+        copy_pack: &mut (ShaderProgram, VertexBuffer, ElementBuffer),
+        render_surface: &mut Option<Rc<Surface>>,
+    ) {
+        let old_window = windowed_context.window();
+        let mut builder = WindowBuilder::new().with_inner_size(old_window.inner_size());
+        if let Ok(position) = old_window.outer_position() {
+            builder = builder.with_position(position);
+        }
+        let (new_windowed_context, golem) = create_window(builder, title, decorations, target);
+
+        // The GPU resources of the lost context are already gone, and destroying them while the
+        // new context is current could destroy resources of the new context instead
+        renderer
+            .recover_from_context_loss(golem)
+            .expect("Should be able to recover from context loss");
+        renderer.prewarm(|_progress| {}).expect("Should be able to prewarm shaders");
+        std::mem::forget(render_surface.take());
+        let new_copy_pack =
+            create_copy_pack(renderer.get_context()).expect("Should be able to create copy pack");
+        std::mem::forget(std::mem::replace(copy_pack, new_copy_pack));
+
+        // Close the old window, but don't touch its lost context
+        let old_windowed_context = std::mem::replace(windowed_context, new_windowed_context);
+        let (old_context, old_window) = unsafe { old_windowed_context.split() };
+        std::mem::forget(old_context);
+        drop(old_window);
+
+        // The Application will render everything again because the context generation changed
+    }
+
+    /// Determines the refresh rate of the given *monitor*. Unfortunately, winit doesn't tell which
+    /// video mode the monitor is currently using, so this uses the highest refresh rate among the
+    /// video modes that have the same resolution as the monitor.
+    fn get_refresh_rate(monitor: &MonitorHandle) -> Option<f32> {
+        let size = monitor.size();
+        monitor
+            .video_modes()
+            .filter(|mode| mode.size() == size)
+            .map(|mode| mode.refresh_rate())
+            .max()
+            .filter(|refresh_rate| *refresh_rate > 0)
+            .map(|refresh_rate| refresh_rate as f32)
+    }
+
+    /// Moves or resizes the *window* such that the cursor (which is now at *position*) stays at
+    /// the same place relative to the dragged edges
+    fn drag_window(window: &Window, drag: &WindowDrag, position: PhysicalPosition<i32>) {
+        use crate::WindowEdge::*;
+
+        let outer_position = match window.outer_position() {
+            Ok(outer_position) => outer_position,
+            // Some platforms (like Wayland) don't allow applications to move their windows
+            Err(_) => return,
+        };
+        let delta_x = position.x - drag.grab_position.x;
+        let delta_y = position.y - drag.grab_position.y;
+
+        let edge = match drag.area {
+            crate::WindowFrameArea::Content => return,
+            crate::WindowFrameArea::TitleBar => {
+                window.set_outer_position(PhysicalPosition::new(
+                    outer_position.x + delta_x,
+                    outer_position.y + delta_y,
+                ));
+                return;
+            }
+            crate::WindowFrameArea::Resize(edge) => edge,
+        };
+
+        let size = window.inner_size();
+        let mut x = outer_position.x;
+        let mut y = outer_position.y;
+        let mut width = size.width as i32;
+        let mut height = size.height as i32;
+
+        // Note that the y-axis of winit points downwards, so the top edge has the smallest y
+        if matches!(edge, Left | BottomLeft | TopLeft) {
+            let new_width = (width - delta_x).max(MIN_WINDOW_SIZE);
+            x += width - new_width;
+            width = new_width;
+        }
+        if matches!(edge, Right | BottomRight | TopRight) {
+            let grab_distance = drag.start_size.width as i32 - drag.grab_position.x;
+            width = (position.x + grab_distance).max(MIN_WINDOW_SIZE);
+        }
+        if matches!(edge, Top | TopLeft | TopRight) {
+            let new_height = (height - delta_y).max(MIN_WINDOW_SIZE);
+            y += height - new_height;
+            height = new_height;
+        }
+        if matches!(edge, Bottom | BottomLeft | BottomRight) {
+            let grab_distance = drag.start_size.height as i32 - drag.grab_position.y;
+            height = (position.y + grab_distance).max(MIN_WINDOW_SIZE);
+        }
+
+        if x != outer_position.x || y != outer_position.y {
+            window.set_outer_position(PhysicalPosition::new(x, y));
+        }
+        window.set_inner_size(PhysicalSize::new(width as u32, height as u32));
+    }
+
+    fn convert_cursor_icon(cursor: crate::CursorIcon) -> CursorIcon {
+        match cursor {
+            crate::CursorIcon::Arrow => CursorIcon::Default,
+            crate::CursorIcon::Hand => CursorIcon::Hand,
+            crate::CursorIcon::Text => CursorIcon::Text,
+            crate::CursorIcon::ResizeHorizontal => CursorIcon::EwResize,
+            crate::CursorIcon::ResizeVertical => CursorIcon::NsResize,
+            crate::CursorIcon::ResizeTopLeftBottomRight => CursorIcon::NwseResize,
+            crate::CursorIcon::ResizeTopRightBottomLeft => CursorIcon::NeswResize,
+        }
+    }
+
+    fn convert_gamepad_id(id: gilrs::GamepadId) -> crate::Gamepad {
+        crate::Gamepad::new(usize::from(id) as u16)
+    }
+
+    /// Queues the events of all gamepad changes since the previous call
+    fn poll_gamepads(gilrs: &mut gilrs::Gilrs, app: &mut Application) {
+        use gilrs::EventType;
+
+        while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
+            let gamepad = convert_gamepad_id(id);
+            match event {
+                EventType::Connected => {
+                    app.connect_gamepad(gamepad, gilrs.gamepad(id).name().to_string());
+                }
+                EventType::Disconnected => app.disconnect_gamepad(gamepad),
+                EventType::ButtonPressed(button, _) | EventType::ButtonReleased(button, _) => {
+                    let pressed = matches!(event, EventType::ButtonPressed(..));
+                    if let Some(button) = convert_gamepad_button(button) {
+                        app.queue_event(InputEvent::GamepadButton(GamepadButtonEvent::new(
+                            gamepad, button, pressed,
+                        )));
+                    }
+                }
+                // Gilrs reports analog triggers as buttons with a value
+                EventType::ButtonChanged(gilrs::Button::LeftTrigger2, value, _) => {
+                    app.queue_event(InputEvent::GamepadAxis(GamepadAxisEvent::new(
+                        gamepad, GamepadAxis::LeftTrigger, value.max(0.0).min(1.0),
+                    )));
+                }
+                EventType::ButtonChanged(gilrs::Button::RightTrigger2, value, _) => {
+                    app.queue_event(InputEvent::GamepadAxis(GamepadAxisEvent::new(
+                        gamepad, GamepadAxis::RightTrigger, value.max(0.0).min(1.0),
+                    )));
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    // The y-axes of gilrs point upwards, just like the y-axis of knukki
+                    let axis = match axis {
+                        gilrs::Axis::LeftStickX => GamepadAxis::LeftStickX,
+                        gilrs::Axis::LeftStickY => GamepadAxis::LeftStickY,
+                        gilrs::Axis::RightStickX => GamepadAxis::RightStickX,
+                        gilrs::Axis::RightStickY => GamepadAxis::RightStickY,
+                        _ => continue,
+                    };
+                    app.queue_event(InputEvent::GamepadAxis(GamepadAxisEvent::new(
+                        gamepad, axis, value.max(-1.0).min(1.0),
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn convert_gamepad_button(button: gilrs::Button) -> Option<GamepadButton> {
+        use gilrs::Button;
+        match button {
+            Button::South => Some(GamepadButton::South),
+            Button::East => Some(GamepadButton::East),
+            Button::North => Some(GamepadButton::North),
+            Button::West => Some(GamepadButton::West),
+            // Gilrs calls the shoulder buttons 'triggers' and the triggers 'triggers 2'
+            Button::LeftTrigger => Some(GamepadButton::LeftShoulder),
+            Button::RightTrigger => Some(GamepadButton::RightShoulder),
+            Button::LeftTrigger2 => Some(GamepadButton::LeftTrigger),
+            Button::RightTrigger2 => Some(GamepadButton::RightTrigger),
+            Button::Select => Some(GamepadButton::Select),
+            Button::Start => Some(GamepadButton::Start),
+            Button::Mode => Some(GamepadButton::Mode),
+            Button::LeftThumb => Some(GamepadButton::LeftStick),
+            Button::RightThumb => Some(GamepadButton::RightStick),
+            Button::DPadUp => Some(GamepadButton::DPadUp),
+            Button::DPadDown => Some(GamepadButton::DPadDown),
+            Button::DPadLeft => Some(GamepadButton::DPadLeft),
+            Button::DPadRight => Some(GamepadButton::DPadRight),
+            _ => None,
+        }
+    }
+
+    /// Draws the application onto the window, and returns false if that failed because the
+    /// OpenGL context was lost
     fn draw_application(
         app: &mut Application,
         renderer: &mut Renderer,
         copy_pack: &mut (ShaderProgram, VertexBuffer, ElementBuffer),
-        render_surface: &mut Option<Surface>,
+        render_surface: &mut Option<Rc<Surface>>,
         size: PhysicalSize<u32>,
         force: bool,
         windowed_context: &ContextWrapper<PossiblyCurrent, Window>,
-    ) -> Result<(), GolemError> {
+    ) -> Result<bool, GolemError> {
         // Don't attempt to draw on an empty window
         if size.width == 0 || size.height == 0 {
-            return Ok(());
+            return Ok(true);
         }
 
-        let region = RenderRegion::with_size(0, 0, size.width, size.height);
+        // When the render scale is smaller than 1, the render texture is smaller than the window,
+        // and it will be upscaled when it is drawn onto the window
+        let (render_width, render_height) = app.get_render_size(size.width, size.height);
+        let region = RenderRegion::with_size(0, 0, render_width, render_height);
 
         let mut created_surface = false;
 
@@ -302,11 +810,12 @@ pub fn start(mut app: Application, title: &str) {
         if render_surface.is_none() {
             let mut render_texture =
                 Texture::new(renderer.get_context()).expect("Should be able to create texture");
-            render_texture.set_image(None, size.width, size.height, ColorFormat::RGBA);
-            *render_surface = Some(
-                Surface::new(renderer.get_context(), render_texture)
-                    .expect("Should be able to create surface"),
-            );
+            render_texture.set_image(None, render_width, render_height, ColorFormat::RGBA);
+            render_texture.set_magnification(TextureFilter::Linear)?;
+            let new_surface = Surface::new(renderer.get_context(), render_texture)
+                .expect("Should be able to create surface");
+            *render_surface = Some(Rc::new(new_surface));
+            renderer.set_frame_surface(render_surface.clone());
             created_surface = true;
             render_surface.as_ref().unwrap().bind();
         }
@@ -339,11 +848,15 @@ pub fn start(mut app: Application, title: &str) {
                 shader.draw_prepared(0..6, GeometryMode::Triangles);
             }
 
-            windowed_context.swap_buffers().expect("Good context");
+            match windowed_context.swap_buffers() {
+                Ok(()) => {}
+                Err(ContextError::ContextLost) => return Ok(false),
+                Err(error) => panic!("Should be able to swap buffers: {:?}", error),
+            }
 
             render_surface.bind();
         }
-        Ok(())
+        Ok(true)
     }
 
     fn create_copy_pack(
@@ -12,6 +12,9 @@ use std::cell::{
 };
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::time::Duration;
+
+use unicode_segmentation::UnicodeSegmentation;
 
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{
@@ -20,12 +23,17 @@ use wasm_bindgen::{
 };
 
 use web_sys::{
+    BeforeUnloadEvent,
     Document,
     Element,
     Event,
     HtmlCanvasElement,
     HtmlElement,
+    KeyboardEvent,
     MouseEvent,
+    Performance,
+    PointerEvent,
+    WheelEvent,
     WebGlRenderingContext,
     window
 };
@@ -47,9 +55,21 @@ pub fn start(app: Application, title: &str) {
     // Similarly, all event handlers must have access to the application
     let wrap_app = Rc::new(RefCell::new(app));
 
+    // The arrow keys control this mouse when the virtual mouse is enabled in the input settings
+    let virtual_mouse = Rc::new(RefCell::new(
+        VirtualMouse::new(Mouse::new(1), Point::new(0.5, 0.5))
+    ));
+
+    // The browser can take the WebGL context away at any time (for instance when too many pages
+    // use WebGL), so the render loop needs to know when that happens
+    let context_status = Rc::new(Cell::new(ContextStatus::Alive));
+
     maintain_canvas_size(&canvas, Rc::clone(&force_next_render));
+    watch_context_loss(&canvas, &context_status);
     propagate_mouse_events(&wrap_app);
-    start_render_loop(&canvas, wrap_app, force_next_render);
+    propagate_keyboard_events(&wrap_app, &virtual_mouse);
+    propagate_window_events(&wrap_app);
+    start_render_loop(&canvas, wrap_app, virtual_mouse, force_next_render, context_status);
 }
 
 fn bind_console() {
@@ -103,14 +123,14 @@ impl Serialize for ContextJSON {
     }
 }
 
-fn start_render_loop(
-    canvas: &HtmlCanvasElement,
-    wrap_app: Rc<RefCell<Application>>,
-    force_next_render: Rc<Cell<bool>>
-) {
-
-    let the_window = window().expect("There should be a window");
+#[derive(Copy, Clone, PartialEq)]
+enum ContextStatus {
+    Alive,
+    Lost,
+    Restored,
+}
 
+fn create_golem_context(canvas: &HtmlCanvasElement) -> golem::Context {
     let context_options = JsValue::from_serde(&ContextJSON{})
         .expect("Should be able to create context options");
 
@@ -124,44 +144,147 @@ fn start_render_loop(
         .expect("Should get a WebGlRenderingContext when creating a 'webgl' canvas context");
 
     let glow_context = glow::Context::from_webgl1_context(gl_context);
-    let golem_context = golem::Context::from_glow(glow_context)
-        .expect("Should be able to turn Glow context into Golem context");
+    golem::Context::from_glow(glow_context)
+        .expect("Should be able to turn Glow context into Golem context")
+}
+
+fn start_render_loop(
+    canvas: &HtmlCanvasElement,
+    wrap_app: Rc<RefCell<Application>>,
+    virtual_mouse: Rc<RefCell<VirtualMouse>>,
+    force_next_render: Rc<Cell<bool>>,
+    context_status: Rc<Cell<ContextStatus>>
+) {
+
+    let the_window = window().expect("There should be a window");
+
+    // Note: This is a clone of a reference to the JS canvas; not a clone of the actual canvas
+    let canvas = canvas.clone();
 
     let mut renderer = Renderer::new(
-        golem_context,
+        create_golem_context(&canvas),
         // The viewport will be set right before rendering, so this value will never be used
         RenderRegion::with_size(0, 0, 100, 100)
     );
 
-    let mut render_function = move || {
+    // Compile the built-in shaders now rather than during the first interaction
+    renderer.prewarm(|progress| log::info!("Prewarming {}", progress))
+        .expect("Should be able to prewarm shaders");
+
+    let performance = the_window.performance().expect("There should be a performance object");
+    let mut last_tick_time = performance.now();
+
+    // The cursor icon that was most recently applied to the canvas
+    let mut current_cursor = CursorIcon::Arrow;
+
+    // Whether the wrapper most recently requested (true) or exited (false) a pointer lock
+    let mut pointer_locked = false;
+
+    // Browsers don't tell when they are low on memory, so the wrapper checks the heap usage
+    // periodically, and fires a low memory event whenever the estimated pressure increases
+    let mut last_memory_check_time = performance.now();
+    let mut last_memory_pressure = None;
+
+    // The *timestamp* is the one that the browser passes to the requestAnimationFrame callback
+    let mut render_function = move |timestamp: f64| {
         let scale_factor = get_scale_factor();
         let unscaled_width = get_window_width();
         let unscaled_height = get_window_height();
 
-        let region = RenderRegion::with_size(
-            0, 0,
+        let mut app = wrap_app.borrow_mut();
+
+        // When the render scale is smaller than 1, the canvas gets less pixels than its CSS size,
+        // so the browser will upscale it
+        let (render_width, render_height) = app.get_render_size(
             get_scaled(unscaled_width, scale_factor),
             get_scaled(unscaled_height, scale_factor)
         );
+        if canvas.width() != render_width || canvas.height() != render_height {
+            // Resizing the canvas clears it, so everything needs to be drawn again
+            canvas.set_width(render_width);
+            canvas.set_height(render_height);
+            force_next_render.set(true);
+        }
+
+        let region = RenderRegion::with_size(0, 0, render_width, render_height);
         renderer.reset_viewport(region);
 
-        let mut app = wrap_app.borrow_mut();
-        app.render(&renderer, force_next_render.get());
+        // Fire the queued input events, let the components advance their time-based state, and
+        // then give the application a render opportunity
+        let tick_time = performance.now();
+        let delta_millis = (tick_time - last_tick_time).max(0.0);
+        last_tick_time = tick_time;
+
+        let delta_time = Duration::from_secs_f64(delta_millis / 1000.0);
 
-        force_next_render.set(false);
+        // Browsers don't expose the refresh rate of the display, but they do tell when the frame
+        // started. Note that this timestamp uses the same clock as performance.now().
+        app.set_frame_time(Duration::from_secs_f64(timestamp.max(0.0) / 1000.0));
+        if app.get_input_settings().virtual_mouse {
+            virtual_mouse.borrow_mut().update(&mut app, delta_time);
+        }
+        if tick_time - last_memory_check_time >= MEMORY_CHECK_INTERVAL_MILLIS {
+            let memory_pressure = estimate_memory_pressure(&performance);
+            if memory_pressure > last_memory_pressure {
+                if let Some(pressure) = memory_pressure {
+                    app.queue_event(InputEvent::LowMemory(LowMemoryEvent::new(pressure)));
+                }
+            }
+            last_memory_pressure = memory_pressure;
+            last_memory_check_time = tick_time;
+        }
+
+        if context_status.get() == ContextStatus::Restored {
+            // The Application will render everything again because the context generation
+            // changed
+            renderer.recover_from_context_loss(create_golem_context(&canvas))
+                .expect("Should be able to recover from context loss");
+            renderer.prewarm(|progress| log::info!("Prewarming {}", progress))
+                .expect("Should be able to prewarm shaders");
+            context_status.set(ContextStatus::Alive);
+        }
+
+        // Rendering is pointless while the context is lost, but the time shouldn't stop
+        if context_status.get() == ContextStatus::Lost {
+            app.update(delta_time);
+        } else {
+            app.frame(&renderer, delta_time, force_next_render.get());
+            force_next_render.set(false);
+        }
+
+        // The components may have changed their cursor while handling the events
+        let cursor = app.get_cursor(Mouse::new(0));
+        if cursor != current_cursor {
+            canvas.style().set_property("cursor", get_css_cursor(cursor))
+                .expect("Should be able to set canvas CSS cursor");
+            current_cursor = cursor;
+        }
+
+        // Lock or unlock the pointer when a component requested it
+        let locked = app.is_pointer_locked(Mouse::new(0));
+        if locked != pointer_locked {
+            if locked {
+                canvas.request_pointer_lock();
+            } else {
+                let document = window().and_then(|window| window.document())
+                    .expect("There should be a document");
+                document.exit_pointer_lock();
+            }
+            pointer_locked = locked;
+        }
     };
 
-    let closure_rr: Rc<RefCell<Option<Closure<dyn FnMut()>>>> = Rc::new(RefCell::new(None));
+    let closure_rr: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
     let closure_rr_inner = Rc::clone(&closure_rr);
 
-    let render_closure = Closure::wrap(Box::new(move || {
-        render_function();
+    let render_closure = Closure::wrap(Box::new(move |timestamp| {
+        render_function(timestamp);
 
         let inner_render_closure = closure_rr_inner.borrow();
         the_window.request_animation_frame(
             inner_render_closure.as_ref().unwrap().as_ref().unchecked_ref()
         ).expect("Should be able to continue requestAnimationFrame");
-    }) as Box<dyn FnMut()>);
+    }) as Box<dyn FnMut(f64)>);
 
     closure_rr.replace(Some(render_closure));
 
@@ -173,6 +296,57 @@ fn start_render_loop(
     ).expect("Should be able to initiate requestAnimationFrame");
 }
 
+/// The time between 2 estimations of the memory pressure, in milliseconds
+const MEMORY_CHECK_INTERVAL_MILLIS: f64 = 5000.0;
+
+#[wasm_bindgen]
+extern "C" {
+    // The non-standard `performance.memory` that is only supported by Chromium-based browsers
+    type PerformanceWithMemory;
+
+    #[wasm_bindgen(method, getter)]
+    fn memory(this: &PerformanceWithMemory) -> Option<MemoryInfo>;
+
+    type MemoryInfo;
+
+    #[wasm_bindgen(method, getter, js_name = usedJSHeapSize)]
+    fn used_js_heap_size(this: &MemoryInfo) -> f64;
+
+    #[wasm_bindgen(method, getter, js_name = jsHeapSizeLimit)]
+    fn js_heap_size_limit(this: &MemoryInfo) -> f64;
+}
+
+/// Estimates the memory pressure from the fraction of the JS heap that is in use. Returns `None`
+/// if the pressure is low, or if the browser doesn't expose the heap usage.
+fn estimate_memory_pressure(performance: &Performance) -> Option<MemoryPressure> {
+    let memory = performance.unchecked_ref::<PerformanceWithMemory>().memory()?;
+    let heap_size_limit = memory.js_heap_size_limit();
+    if heap_size_limit <= 0.0 {
+        return None;
+    }
+
+    let heap_usage = memory.used_js_heap_size() / heap_size_limit;
+    if heap_usage > 0.9 {
+        Some(MemoryPressure::Critical)
+    } else if heap_usage > 0.75 {
+        Some(MemoryPressure::Moderate)
+    } else {
+        None
+    }
+}
+
+fn get_css_cursor(cursor: CursorIcon) -> &'static str {
+    match cursor {
+        CursorIcon::Arrow => "default",
+        CursorIcon::Hand => "pointer",
+        CursorIcon::Text => "text",
+        CursorIcon::ResizeHorizontal => "ew-resize",
+        CursorIcon::ResizeVertical => "ns-resize",
+        CursorIcon::ResizeTopLeftBottomRight => "nwse-resize",
+        CursorIcon::ResizeTopRightBottomLeft => "nesw-resize",
+    }
+}
+
 fn propagate_mouse_events(
     wrap_app: &Rc<RefCell<Application>>
 ) {
@@ -201,8 +375,27 @@ fn propagate_mouse_events(
         MouseButton::new(knukki_button)
     }
 
-    // This mouse will be associated with the standard DOM events. I might add support for
-    // controllers or keyboard-controlled mouses later.
+    fn get_details(event: &PointerEvent) -> PointerDetails {
+        match event.pointer_type().as_str() {
+            "pen" => {
+                // The eraser of a pen is reported as the button with mask 32
+                let tool_type = match event.buttons() & 32 {
+                    0 => PointerToolType::Pen,
+                    _ => PointerToolType::Eraser,
+                };
+                let tilt_x = (event.tilt_x() as f32).to_radians();
+                let tilt_y = (event.tilt_y() as f32).to_radians();
+                PointerDetails::new(Some(tool_type), Some(event.pressure()), Some((tilt_x, tilt_y)))
+            },
+            // Browsers report a fake pressure of 0.5 for devices without pressure sensor, so the
+            // pressure of mouses and fingers is not very useful
+            "touch" => PointerDetails::with_tool_type(PointerToolType::Finger),
+            _ => PointerDetails::with_tool_type(PointerToolType::Mouse),
+        }
+    }
+
+    // This mouse will be associated with the standard DOM events. The keyboard-controlled virtual
+    // mouse uses id 1 instead.
     let primary_mouse = Mouse::new(0);
     let mouse_point_rc = Rc::new(Cell::new(None));
     let last_press_point_rc = Rc::new(Cell::new(None));
@@ -213,6 +406,7 @@ fn propagate_mouse_events(
     let move_wrap_app = Rc::clone(wrap_app);
     let enter_wrap_app = Rc::clone(wrap_app);
     let leave_wrap_app = Rc::clone(wrap_app);
+    let scroll_wrap_app = Rc::clone(wrap_app);
 
     let press_point_rc_press = Rc::clone(&last_press_point_rc);
     let press_point_rc_click = Rc::clone(&last_press_point_rc);
@@ -226,38 +420,67 @@ fn propagate_mouse_events(
 
             // I don't want to count drags as clicks, so I only fire the event if the point of
             // clicking/release is close enough to the point where the mouse was pressed.
-            if click_point.distance_to(press_point) < 0.1 {
-                let mut app = click_wrap_app.borrow_mut();
-                app.fire_mouse_click_event(MouseClickEvent::new(
+            let mut app = click_wrap_app.borrow_mut();
+            if click_point.distance_to(press_point) < app.get_input_settings().drag_start_distance {
+                app.queue_event(InputEvent::MouseClick(MouseClickEvent::new(
                     primary_mouse,
                     click_point,
                     get_button(&event)
-                ));
+                )));
             }
         }
     }) as Box<dyn FnMut(MouseEvent)>);
 
-    let press_closure = Closure::wrap(Box::new(move |event| {
+    // The pointer events are used instead of the mouse events because they can tell the pressure
+    // of pens. Since this wrapper only supports 1 mouse, the other fingers of multi-touch
+    // gestures are ignored.
+    let press_closure = Closure::wrap(Box::new(move |event: PointerEvent| {
+        if !event.is_primary() {
+            return;
+        }
         let mut app = press_wrap_app.borrow_mut();
         let point = Point::new(get_x(&event), get_y(&event));
-        app.fire_mouse_press_event(MousePressEvent::new(
+        app.queue_event(InputEvent::MousePress(MousePressEvent::with_details(
             primary_mouse,
             point,
-            get_button(&event)
-        ));
+            get_button(&event),
+            get_details(&event)
+        )));
         press_point_rc_press.set(Some(point));
-    }) as Box<dyn FnMut(MouseEvent)>);
+    }) as Box<dyn FnMut(PointerEvent)>);
 
-    let release_closure = Closure::wrap(Box::new(move |event| {
+    let release_closure = Closure::wrap(Box::new(move |event: PointerEvent| {
+        if !event.is_primary() {
+            return;
+        }
         let mut app = release_wrap_app.borrow_mut();
-        app.fire_mouse_release_event(MouseReleaseEvent::new(
+        app.queue_event(InputEvent::MouseRelease(MouseReleaseEvent::with_details(
             primary_mouse,
             Point::new(get_x(&event), get_y(&event)),
-            get_button(&event)
-        ));
-    }) as Box<dyn FnMut(MouseEvent)>);
+            get_button(&event),
+            get_details(&event)
+        )));
+    }) as Box<dyn FnMut(PointerEvent)>);
+
+    let move_closure = Closure::wrap(Box::new(move |event: PointerEvent| {
+        if !event.is_primary() {
+            return;
+        }
+
+        // While the pointer is locked, the cursor stays in place, but the browser still reports
+        // how far the mouse moved
+        let document = window().and_then(|window| window.document())
+            .expect("There should be a document");
+        if document.pointer_lock_element().is_some() {
+            let delta_x = event.movement_x() as f32 / get_window_width() as f32;
+            let delta_y = -event.movement_y() as f32 / get_window_height() as f32;
+            let mut app = move_wrap_app.borrow_mut();
+            app.queue_event(InputEvent::RelativeMouseMove(RelativeMouseMoveEvent::new(
+                primary_mouse, delta_x, delta_y
+            )));
+            return;
+        }
 
-    let move_closure = Closure::wrap(Box::new(move |event| {
         let old_mouse_point = mouse_point_rc_move.get();
         let new_mouse_point = Point::new(get_x(&event), get_y(&event));
 
@@ -266,14 +489,14 @@ fn propagate_mouse_events(
             // Protect the Application from 0-length move events
             if prev_mouse_point != new_mouse_point {
                 let mut app = move_wrap_app.borrow_mut();
-                app.fire_mouse_move_event(MouseMoveEvent::new(
-                    primary_mouse, prev_mouse_point, new_mouse_point
-                ));
+                app.queue_event(InputEvent::MouseMove(MouseMoveEvent::with_details(
+                    primary_mouse, prev_mouse_point, new_mouse_point, get_details(&event)
+                )));
             }
         }
 
         mouse_point_rc_move.set(Some(new_mouse_point));
-    }) as Box<dyn FnMut(MouseEvent)>);
+    }) as Box<dyn FnMut(PointerEvent)>);
 
     let enter_closure = Closure::wrap(Box::new(move |event| {
 
@@ -283,9 +506,9 @@ fn propagate_mouse_events(
             let entrance_mouse_point = Point::new(get_x(&event), get_y(&event));
 
             let mut app = enter_wrap_app.borrow_mut();
-            app.fire_mouse_enter_event(MouseEnterEvent::new(
+            app.queue_event(InputEvent::MouseEnter(MouseEnterEvent::new(
                 primary_mouse, entrance_mouse_point
-            ));
+            )));
 
             mouse_point_rc_enter.set(Some(entrance_mouse_point));
         }
@@ -305,26 +528,44 @@ fn propagate_mouse_events(
                 && exit_point.get_y() >= 0.0 && exit_point.get_y() <= 1.0 {
 
                 if exit_point != old_mouse_pos {
-                    app.fire_mouse_move_event(MouseMoveEvent::new(
+                    app.queue_event(InputEvent::MouseMove(MouseMoveEvent::new(
                         primary_mouse, old_mouse_pos, exit_point
-                    ));
+                    )));
                 }
 
-                app.fire_mouse_leave_event(MouseLeaveEvent::new(
+                app.queue_event(InputEvent::MouseLeave(MouseLeaveEvent::new(
                     primary_mouse, exit_point
-                ));
+                )));
             } else {
 
                 // Let's use the last valid mouse position as back-up exit point
-                app.fire_mouse_leave_event(MouseLeaveEvent::new(
+                app.queue_event(InputEvent::MouseLeave(MouseLeaveEvent::new(
                     primary_mouse, old_mouse_pos
-                ));
+                )));
             }
         }
 
         mouse_point_rc_leave.set(None);
     }) as Box<dyn FnMut(MouseEvent)>);
 
+    let scroll_closure = Closure::wrap(Box::new(move |event: WheelEvent| {
+        // The JS deltas can be expressed in pixels, lines, or pages. Also, the JS deltaY is
+        // positive when scrolling down, which is the other way around in knukki.
+        let mut app = scroll_wrap_app.borrow_mut();
+        let scale = match event.delta_mode() {
+            WheelEvent::DOM_DELTA_PIXEL => 1.0 / app.get_input_settings().scroll_line_height,
+            WheelEvent::DOM_DELTA_LINE => 1.0 / 3.0,
+            _ => 1.0
+        };
+
+        app.queue_event(InputEvent::MouseScroll(MouseScrollEvent::new(
+            primary_mouse,
+            Point::new(get_x(&event), get_y(&event)),
+            scale * event.delta_x() as f32,
+            -scale * event.delta_y() as f32
+        )));
+    }) as Box<dyn FnMut(WheelEvent)>);
+
     let context_closure = Closure::wrap(Box::new(|event: Event| {
         event.prevent_default();
     }) as Box<dyn FnMut(Event)>);
@@ -333,16 +574,18 @@ fn propagate_mouse_events(
         .expect("Should be able to add click listener");
     the_window.add_event_listener_with_callback("auxclick", click_closure.as_ref().unchecked_ref())
         .expect("Should be able to add auxclick listener");
-    the_window.add_event_listener_with_callback("mousedown", press_closure.as_ref().unchecked_ref())
-        .expect("Should be able to add mousedown listener");
-    the_window.add_event_listener_with_callback("mouseup", release_closure.as_ref().unchecked_ref())
-        .expect("Should be able to add mouseup listener");
-    the_window.add_event_listener_with_callback("mousemove", move_closure.as_ref().unchecked_ref())
-        .expect("Should be able to add mousemove listener");
+    the_window.add_event_listener_with_callback("pointerdown", press_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add pointerdown listener");
+    the_window.add_event_listener_with_callback("pointerup", release_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add pointerup listener");
+    the_window.add_event_listener_with_callback("pointermove", move_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add pointermove listener");
     the_window.add_event_listener_with_callback("mouseover", enter_closure.as_ref().unchecked_ref())
         .expect("Should be able to add mouseover listener");
     the_window.add_event_listener_with_callback("mouseout", leave_closure.as_ref().unchecked_ref())
         .expect("Should be able to add mouseout listener");
+    the_window.add_event_listener_with_callback("wheel", scroll_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add wheel listener");
     the_window.add_event_listener_with_callback("contextmenu", context_closure.as_ref().unchecked_ref())
         .expect("Should be able to add contextmenu listener");
 
@@ -352,9 +595,138 @@ fn propagate_mouse_events(
     move_closure.forget();
     enter_closure.forget();
     leave_closure.forget();
+    scroll_closure.forget();
     context_closure.forget();
 }
 
+fn propagate_keyboard_events(
+    wrap_app: &Rc<RefCell<Application>>, virtual_mouse: &Rc<RefCell<VirtualMouse>>
+) {
+    let the_window = window().expect("Expected a window");
+
+    fn get_arrow(key: &str) -> Option<ArrowKey> {
+        match key {
+            "ArrowLeft" => Some(ArrowKey::Left),
+            "ArrowRight" => Some(ArrowKey::Right),
+            "ArrowDown" => Some(ArrowKey::Down),
+            "ArrowUp" => Some(ArrowKey::Up),
+            _ => None
+        }
+    }
+
+    // Returns true if the key was consumed by the virtual mouse
+    fn update_virtual_mouse(
+        app: &mut Application, virtual_mouse: &RefCell<VirtualMouse>, key: &str, pressed: bool
+    ) -> bool {
+        if !app.get_input_settings().virtual_mouse {
+            return false;
+        }
+
+        let mut virtual_mouse = virtual_mouse.borrow_mut();
+        if let Some(arrow) = get_arrow(key) {
+            virtual_mouse.set_arrow_pressed(arrow, pressed);
+            true
+        } else if key == " " {
+            // The space bar acts as the primary button of the virtual mouse
+            if pressed {
+                virtual_mouse.press_button(app, MouseButton::primary());
+            } else {
+                virtual_mouse.release_button(app, MouseButton::primary());
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    let key_wrap_app = Rc::clone(wrap_app);
+    let key_virtual_mouse = Rc::clone(virtual_mouse);
+    let key_closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        // Key presses with ctrl or meta are shortcuts rather than text
+        if event.ctrl_key() || event.meta_key() {
+            return;
+        }
+
+        let key = event.key();
+        let mut app = key_wrap_app.borrow_mut();
+        if update_virtual_mouse(&mut app, &key_virtual_mouse, &key, true) {
+            return;
+        }
+
+        // The key of special keys is a name like "Enter" or "ArrowLeft", so only keys with a
+        // single grapheme correspond to typed text
+        if key.graphemes(true).count() == 1 {
+            app.queue_event(InputEvent::CharType(CharTypeEvent::new(key)));
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+
+    let release_wrap_app = Rc::clone(wrap_app);
+    let release_virtual_mouse = Rc::clone(virtual_mouse);
+    let release_closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        let mut app = release_wrap_app.borrow_mut();
+        update_virtual_mouse(&mut app, &release_virtual_mouse, &event.key(), false);
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+
+    the_window.add_event_listener_with_callback("keydown", key_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add keydown listener");
+    the_window.add_event_listener_with_callback("keyup", release_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add keyup listener");
+
+    key_closure.forget();
+    release_closure.forget();
+}
+
+fn propagate_window_events(wrap_app: &Rc<RefCell<Application>>) {
+    let the_window = window().expect("Expected a window");
+    let document = the_window.document().expect("Expected a document");
+
+    let focus_wrap_app = Rc::clone(wrap_app);
+    let focus_closure = Closure::wrap(Box::new(move || {
+        let event = WindowFocusEvent::new(true);
+        focus_wrap_app.borrow_mut().queue_event(InputEvent::WindowFocus(event));
+    }) as Box<dyn FnMut()>);
+
+    let blur_wrap_app = Rc::clone(wrap_app);
+    let blur_closure = Closure::wrap(Box::new(move || {
+        let event = WindowFocusEvent::new(false);
+        blur_wrap_app.borrow_mut().queue_event(InputEvent::WindowFocus(event));
+    }) as Box<dyn FnMut()>);
+
+    // Browsers don't tell when the window is minimized, but hidden tabs are the closest thing
+    let visibility_wrap_app = Rc::clone(wrap_app);
+    let visibility_document = document.clone();
+    let visibility_closure = Closure::wrap(Box::new(move || {
+        let event = WindowMinimizeEvent::new(visibility_document.hidden());
+        visibility_wrap_app.borrow_mut().queue_event(InputEvent::WindowMinimize(event));
+    }) as Box<dyn FnMut()>);
+
+    let unload_wrap_app = Rc::clone(wrap_app);
+    let unload_closure = Closure::wrap(Box::new(move |event: BeforeUnloadEvent| {
+        // The browser will ask the user to confirm leaving the page when the default behavior is
+        // prevented (browsers don't allow pages to show their own message here)
+        if !unload_wrap_app.borrow_mut().fire_close_request() {
+            event.prevent_default();
+            event.set_return_value("");
+        }
+    }) as Box<dyn FnMut(BeforeUnloadEvent)>);
+
+    the_window.add_event_listener_with_callback("focus", focus_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add focus listener");
+    the_window.add_event_listener_with_callback("blur", blur_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add blur listener");
+    document.add_event_listener_with_callback(
+        "visibilitychange", visibility_closure.as_ref().unchecked_ref()
+    ).expect("Should be able to add visibilitychange listener");
+    the_window.add_event_listener_with_callback(
+        "beforeunload", unload_closure.as_ref().unchecked_ref()
+    ).expect("Should be able to add beforeunload listener");
+
+    focus_closure.forget();
+    blur_closure.forget();
+    visibility_closure.forget();
+    unload_closure.forget();
+}
+
 fn maintain_canvas_size(canvas: &HtmlCanvasElement, force_next_render: Rc<Cell<bool>>) {
     let the_window = window().expect("Expected a window");
 
@@ -374,6 +746,33 @@ fn maintain_canvas_size(canvas: &HtmlCanvasElement, force_next_render: Rc<Cell<b
     resize_closure.forget();
 }
 
+fn watch_context_loss(canvas: &HtmlCanvasElement, context_status: &Rc<Cell<ContextStatus>>) {
+    let lost_status = Rc::clone(context_status);
+    let lost_closure = Closure::wrap(Box::new(move |event: Event| {
+        log::warn!("The WebGL context was lost");
+
+        // The browser will only restore the context if the default behavior is prevented
+        event.prevent_default();
+        lost_status.set(ContextStatus::Lost);
+    }) as Box<dyn FnMut(Event)>);
+
+    let restored_status = Rc::clone(context_status);
+    let restored_closure = Closure::wrap(Box::new(move || {
+        log::info!("The WebGL context was restored");
+        restored_status.set(ContextStatus::Restored);
+    }) as Box<dyn FnMut()>);
+
+    canvas.add_event_listener_with_callback(
+        "webglcontextlost", lost_closure.as_ref().unchecked_ref()
+    ).expect("Should be able to add webglcontextlost listener");
+    canvas.add_event_listener_with_callback(
+        "webglcontextrestored", restored_closure.as_ref().unchecked_ref()
+    ).expect("Should be able to add webglcontextrestored listener");
+
+    lost_closure.forget();
+    restored_closure.forget();
+}
+
 fn set_canvas_size(canvas: &HtmlCanvasElement) {
     let unscaled_width = get_window_width();
     let unscaled_height = get_window_height();